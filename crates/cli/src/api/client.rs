@@ -1,16 +1,140 @@
 //! HTTP client for the Vibe Kanban API.
 
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
 use anyhow::{Context, Result, anyhow};
-use reqwest::Client;
+use futures_util::{StreamExt, stream::BoxStream};
+use reqwest::{
+    Client, Method, RequestBuilder, Response,
+    header::{ACCEPT, HeaderMap, HeaderValue},
+};
+use serde::de::DeserializeOwned;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use url::Url;
 use uuid::Uuid;
 
-use crate::types::*;
+use crate::{
+    api::{ApiError, codec::WireFormat, metrics::RequestMetrics},
+    diff::{self, DiffEvent, WorkspaceDiff},
+    journal::{Journal, JournalEntry},
+    links,
+    logs::{self, LogEvent, ProcessLog},
+    types::*,
+};
+
+/// Events from an execution process log, see [`VibeKanbanClient::stream_process_logs`]
+pub type LogStream = BoxStream<'static, Result<LogEvent>>;
+
+/// Header the server uses to recognise retried requests.
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// How long a health check may take before the server counts as unreachable
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the server may take to work out a workspace's diff
+const DIFF_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long a dev server's log may stay quiet before its output so far counts
+/// as read, and how long reading it may take at most
+const DEV_SERVER_LOG_IDLE: Duration = Duration::from_millis(500);
+const DEV_SERVER_LOG_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Decode the API response envelope, keeping the HTTP status for error reporting.
+trait ApiResponseExt {
+    async fn api_json<T: DeserializeOwned>(self) -> Result<ApiResponse<T>>;
+}
+
+impl ApiResponseExt for Response {
+    async fn api_json<T: DeserializeOwned>(self) -> Result<ApiResponse<T>> {
+        let status = self.status();
+        let format = WireFormat::of_response(self.headers());
+        let body = self.bytes().await?;
+        match format.decode::<ApiResponse<T>>(&body) {
+            Ok(mut response) => {
+                response.status = Some(status.as_u16());
+                Ok(response)
+            }
+            // Errors raised before our handlers run (404s, proxies) are not wrapped
+            Err(_) if !status.is_success() => {
+                let text = String::from_utf8_lossy(&body).trim().to_string();
+                Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error_data: None,
+                    message: Some(if text.is_empty() {
+                        status.to_string()
+                    } else {
+                        text
+                    }),
+                    status: Some(status.as_u16()),
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+trait RequestBuilderExt {
+    async fn send_journaled(
+        self,
+        journal: &Journal,
+        metrics: &RequestMetrics,
+    ) -> reqwest::Result<Response>;
+}
+
+impl RequestBuilderExt for RequestBuilder {
+    /// Send the request, recording it in the action journal if it changes server state,
+    /// and how long it took in the request metrics.
+    async fn send_journaled(
+        self,
+        journal: &Journal,
+        metrics: &RequestMetrics,
+    ) -> reqwest::Result<Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+        let entry = (request.method() != Method::GET).then(|| {
+            JournalEntry::new(
+                request.method().as_str(),
+                request.url(),
+                request.body().and_then(|body| body.as_bytes()),
+            )
+        });
+
+        let (method, url) = (request.method().clone(), request.url().clone());
+        let started = Instant::now();
+        let result = client.execute(request).await;
+        let failed = result
+            .as_ref()
+            .map_or(true, |response| response.status().is_server_error());
+        metrics.record(&method, &url, started.elapsed(), failed);
+
+        if let Some(mut entry) = entry {
+            match &result {
+                Ok(response) => entry.status = Some(response.status().as_u16()),
+                Err(e) => entry.error = Some(e.to_string()),
+            }
+            journal.record(&entry);
+        }
+        result
+    }
+}
 
 /// Client for interacting with the Vibe Kanban server API.
 #[derive(Clone)]
 pub struct VibeKanbanClient {
     client: Client,
     base_url: String,
+    /// Prefix for idempotency keys, unique to this client session
+    session_id: Uuid,
+    next_request: Arc<AtomicU64>,
+    journal: Journal,
+    metrics: RequestMetrics,
 }
 
 impl VibeKanbanClient {
@@ -23,9 +147,50 @@ impl VibeKanbanClient {
         Ok(Self {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
+            session_id: Uuid::new_v4(),
+            next_request: Arc::new(AtomicU64::new(1)),
+            journal: Journal::open_default(),
+            metrics: RequestMetrics::default(),
         })
     }
 
+    /// Ask for responses in `format` where the server supports it; the format
+    /// has to be [supported](WireFormat::is_supported) by this build.
+    pub fn with_wire_format(mut self, format: WireFormat) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static(format.accept()));
+        self.client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .context("Failed to create HTTP client")?;
+        Ok(self)
+    }
+
+    /// Record changes in `journal` instead of the default one.
+    pub fn with_journal(mut self, journal: Journal) -> Self {
+        self.journal = journal;
+        self
+    }
+
+    /// Latency and errors of the requests made through this client and its clones.
+    pub fn metrics(&self) -> &RequestMetrics {
+        &self.metrics
+    }
+
+    /// Generate a fresh idempotency key.
+    ///
+    /// Pass the same key when retrying a request so the server returns the original
+    /// result instead of performing the operation twice.
+    pub fn idempotency_key(&self) -> String {
+        let n = self.next_request.fetch_add(1, Ordering::Relaxed);
+        format!("{}-{}", self.session_id, n)
+    }
+
+    /// The server URL the client talks to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Build the full URL for an API endpoint.
     fn url(&self, path: &str) -> String {
         format!("{}/api{}", self.base_url, path)
@@ -36,10 +201,58 @@ impl VibeKanbanClient {
         if response.success {
             response.data.ok_or_else(|| anyhow!("Response success but no data"))
         } else {
-            Err(anyhow!(
-                "API error: {}",
-                response.message.unwrap_or_else(|| "Unknown error".to_string())
-            ))
+            let mut error = ApiError {
+                status: response.status,
+                message: response.message.unwrap_or_else(|| "Unknown error".to_string()),
+                error_data: response.error_data,
+            };
+            // Policy violations come as structured data only
+            if let Some(report) = error.branch_policy_report() {
+                error.message = report.summary();
+            } else if let Some(violation) = error.protected_branch_violation() {
+                error.message = violation.summary();
+            } else if let Some(pr_error) = error.pr_error() {
+                error.message = pr_error.summary();
+            }
+            Err(error.into())
+        }
+    }
+
+    /// Check an API response for endpoints that return no data.
+    fn extract_unit(response: ApiResponse<()>) -> Result<()> {
+        if response.success {
+            Ok(())
+        } else {
+            Self::extract_data(response)
+        }
+    }
+
+    /// Decode a team route response. These return the payload directly rather
+    /// than an ApiResponse, but errors still use the envelope.
+    async fn team_json<T: DeserializeOwned>(response: Response, what: &str) -> Result<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.api_json::<serde_json::Value>().await?;
+            return Err(ApiError {
+                status: Some(status.as_u16()),
+                message: error.message.unwrap_or_else(|| status.to_string()),
+                error_data: error.error_data,
+            }
+            .into());
+        }
+        let format = WireFormat::of_response(response.headers());
+        let body = response.bytes().await?;
+        format
+            .decode(&body)
+            .with_context(|| format!("Failed to parse {} response", what))
+    }
+
+    /// Extract data from a response whose data may legitimately be null.
+    fn extract_optional<T>(response: ApiResponse<T>) -> Result<Option<T>> {
+        if response.success {
+            Ok(response.data)
+        } else {
+            Self::extract_data(response).map(Some)
         }
     }
 
@@ -52,10 +265,10 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url("/projects"))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch projects")?
-            .json::<ApiResponse<Vec<Project>>>()
+            .api_json::<Vec<Project>>()
             .await
             .context("Failed to parse projects response")?;
 
@@ -67,10 +280,10 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url(&format!("/projects/{}", project_id)))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch project")?
-            .json::<ApiResponse<Project>>()
+            .api_json::<Project>()
             .await
             .context("Failed to parse project response")?;
 
@@ -83,10 +296,10 @@ impl VibeKanbanClient {
             .client
             .post(self.url("/projects"))
             .json(payload)
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to create project")?
-            .json::<ApiResponse<Project>>()
+            .api_json::<Project>()
             .await
             .context("Failed to parse create project response")?;
 
@@ -98,16 +311,121 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url(&format!("/projects/{}/repositories", project_id)))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch repositories")?
-            .json::<ApiResponse<Vec<Repo>>>()
+            .api_json::<Vec<Repo>>()
             .await
             .context("Failed to parse repositories response")?;
 
         Self::extract_data(response)
     }
 
+    /// Get the current month's budget status for a project.
+    pub async fn get_project_budget(&self, project_id: Uuid) -> Result<ProjectBudgetStatus> {
+        let response = self
+            .client
+            .get(self.url(&format!("/projects/{}/budget", project_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch project budget")?
+            .api_json::<ProjectBudgetStatus>()
+            .await
+            .context("Failed to parse project budget response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Set or clear a project's monthly budget cap.
+    pub async fn set_project_budget(
+        &self,
+        project_id: Uuid,
+        payload: &SetProjectBudget,
+    ) -> Result<ProjectBudgetStatus> {
+        let response = self
+            .client
+            .put(self.url(&format!("/projects/{}/budget", project_id)))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update project budget")?
+            .api_json::<ProjectBudgetStatus>()
+            .await
+            .context("Failed to parse project budget response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Lift or restore a project's budget cap for the current month.
+    pub async fn set_budget_override(
+        &self,
+        project_id: Uuid,
+        enabled: bool,
+    ) -> Result<ProjectBudgetStatus> {
+        let response = self
+            .client
+            .post(self.url(&format!("/projects/{}/budget/override", project_id)))
+            .json(&BudgetOverrideRequest { enabled })
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update budget override")?
+            .api_json::<ProjectBudgetStatus>()
+            .await
+            .context("Failed to parse project budget response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Get a project's branch naming policy, if it has one.
+    pub async fn get_branch_policy(&self, project_id: Uuid) -> Result<Option<ProjectBranchPolicy>> {
+        let response = self
+            .client
+            .get(self.url(&format!("/projects/{}/branch-policy", project_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch branch policy")?
+            .api_json::<ProjectBranchPolicy>()
+            .await
+            .context("Failed to parse branch policy response")?;
+
+        Self::extract_optional(response)
+    }
+
+    /// Replace a project's branch naming policy.
+    pub async fn set_branch_policy(
+        &self,
+        project_id: Uuid,
+        payload: &SetProjectBranchPolicy,
+    ) -> Result<ProjectBranchPolicy> {
+        let response = self
+            .client
+            .put(self.url(&format!("/projects/{}/branch-policy", project_id)))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update branch policy")?
+            .api_json::<ProjectBranchPolicy>()
+            .await
+            .context("Failed to parse branch policy response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Remove a project's branch naming policy.
+    pub async fn delete_branch_policy(&self, project_id: Uuid) -> Result<()> {
+        let response = self
+            .client
+            .delete(self.url(&format!("/projects/{}/branch-policy", project_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to delete branch policy")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse delete branch policy response")?;
+
+        Self::extract_unit(response)
+    }
+
     // =========================================================================
     // Tasks
     // =========================================================================
@@ -118,10 +436,10 @@ impl VibeKanbanClient {
             .client
             .get(self.url("/tasks"))
             .query(&[("project_id", project_id.to_string())])
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch tasks")?
-            .json::<ApiResponse<Vec<TaskWithAttemptStatus>>>()
+            .api_json::<Vec<TaskWithAttemptStatus>>()
             .await
             .context("Failed to parse tasks response")?;
 
@@ -133,10 +451,10 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url(&format!("/tasks/{}", task_id)))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch task")?
-            .json::<ApiResponse<Task>>()
+            .api_json::<Task>()
             .await
             .context("Failed to parse task response")?;
 
@@ -144,15 +462,16 @@ impl VibeKanbanClient {
     }
 
     /// Create a new task.
-    pub async fn create_task(&self, payload: &CreateTask) -> Result<Task> {
+    pub async fn create_task(&self, payload: &CreateTask, idempotency_key: &str) -> Result<Task> {
         let response = self
             .client
             .post(self.url("/tasks"))
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
             .json(payload)
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to create task")?
-            .json::<ApiResponse<Task>>()
+            .api_json::<Task>()
             .await
             .context("Failed to parse create task response")?;
 
@@ -165,31 +484,109 @@ impl VibeKanbanClient {
             .client
             .put(self.url(&format!("/tasks/{}", task_id)))
             .json(payload)
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to update task")?
-            .json::<ApiResponse<Task>>()
+            .api_json::<Task>()
             .await
             .context("Failed to parse update task response")?;
 
         Self::extract_data(response)
     }
 
-    /// Delete a task.
-    pub async fn delete_task(&self, task_id: Uuid) -> Result<()> {
+    /// Delete a task. Servers with a trash move it there and return it; older
+    /// ones have no trash route, so it is deleted for good and nothing is
+    /// returned.
+    pub async fn delete_task(&self, task_id: Uuid) -> Result<Option<TrashedTask>> {
+        let response = self
+            .client
+            .post(self.url(&format!("/tasks/{}/trash", task_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to delete task")?
+            .api_json::<TrashedTask>()
+            .await
+            .context("Failed to parse delete task response")?;
+        if response.status != Some(404) {
+            return Self::extract_data(response).map(Some);
+        }
+
         let response = self
             .client
             .delete(self.url(&format!("/tasks/{}", task_id)))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to delete task")?
-            .json::<ApiResponse<()>>()
+            .api_json::<()>()
             .await
             .context("Failed to parse delete task response")?;
 
+        Self::extract_unit(response)?;
+        Ok(None)
+    }
+
+    /// List a project's tasks in the trash, most recently deleted first.
+    pub async fn list_trash(&self, project_id: Uuid) -> Result<Vec<TrashedTask>> {
+        let response = self
+            .client
+            .get(self.url("/tasks/trash"))
+            .query(&[("project_id", project_id.to_string())])
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch the trash")?
+            .api_json::<Vec<TrashedTask>>()
+            .await
+            .context("Failed to parse trash response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Take a task out of the trash.
+    pub async fn restore_task(&self, task_id: Uuid) -> Result<Task> {
+        let response = self
+            .client
+            .post(self.url(&format!("/tasks/trash/{}/restore", task_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to restore task")?
+            .api_json::<Task>()
+            .await
+            .context("Failed to parse restore task response")?;
+
         Self::extract_data(response)
     }
 
+    /// Save the order of a board column arranged by hand, all in one request.
+    pub async fn reorder_tasks(&self, payload: &ReorderTasks) -> Result<()> {
+        let response = self
+            .client
+            .post(self.url("/tasks/reorder"))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to reorder tasks")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse reorder tasks response")?;
+
+        Self::extract_unit(response)
+    }
+
+    /// Delete a task in the trash for good, with its workspaces.
+    pub async fn delete_task_permanently(&self, task_id: Uuid) -> Result<()> {
+        let response = self
+            .client
+            .delete(self.url(&format!("/tasks/trash/{}", task_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to delete task")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse delete task response")?;
+
+        Self::extract_unit(response)
+    }
+
     /// Create a task and start it immediately.
     pub async fn create_and_start_task(
         &self,
@@ -199,10 +596,10 @@ impl VibeKanbanClient {
             .client
             .post(self.url("/tasks/create-and-start"))
             .json(payload)
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to create and start task")?
-            .json::<ApiResponse<TaskWithAttemptStatus>>()
+            .api_json::<TaskWithAttemptStatus>()
             .await
             .context("Failed to parse create and start task response")?;
 
@@ -222,41 +619,114 @@ impl VibeKanbanClient {
         }
 
         let response = request
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch workspaces")?
-            .json::<ApiResponse<Vec<Workspace>>>()
+            .api_json::<Vec<Workspace>>()
             .await
             .context("Failed to parse workspaces response")?;
 
         Self::extract_data(response)
     }
 
+    /// Get the parent task and the tasks created from a workspace.
+    pub async fn get_workspace_relationships(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<TaskRelationships> {
+        let response = self
+            .client
+            .get(self.url(&format!("/task-attempts/{}/children", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch task relationships")?
+            .api_json::<TaskRelationships>()
+            .await
+            .context("Failed to parse task relationships response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Get the relationships of each of a task's workspaces, newest workspace
+    /// first. The server reports them per workspace, so a task without
+    /// workspaces has none.
+    pub async fn get_task_relationships(&self, task_id: Uuid) -> Result<Vec<TaskRelationships>> {
+        let mut relationships = Vec::new();
+        for workspace in self.list_workspaces(Some(task_id)).await? {
+            relationships.push(self.get_workspace_relationships(workspace.id).await?);
+        }
+        Ok(relationships)
+    }
+
+    /// Get the latest output and status change for each workspace in a project.
+    pub async fn list_workspace_activity(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<WorkspaceActivity>> {
+        let response = self
+            .client
+            .get(self.url("/task-attempts/activity"))
+            .query(&[("project_id", project_id.to_string())])
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch workspace activity")?
+            .api_json::<Vec<WorkspaceActivity>>()
+            .await
+            .context("Failed to parse workspace activity response")?;
+
+        Self::extract_data(response)
+    }
+
     /// Get a workspace by ID.
     pub async fn get_workspace(&self, workspace_id: Uuid) -> Result<Workspace> {
         let response = self
             .client
             .get(self.url(&format!("/task-attempts/{}", workspace_id)))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch workspace")?
-            .json::<ApiResponse<Workspace>>()
+            .api_json::<Workspace>()
             .await
             .context("Failed to parse workspace response")?;
 
         Self::extract_data(response)
     }
 
+    /// Update the name, pinned or archived state of a workspace.
+    pub async fn update_workspace(
+        &self,
+        workspace_id: Uuid,
+        payload: &UpdateWorkspace,
+    ) -> Result<Workspace> {
+        let response = self
+            .client
+            .put(self.url(&format!("/task-attempts/{}", workspace_id)))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update workspace")?
+            .api_json::<Workspace>()
+            .await
+            .context("Failed to parse update workspace response")?;
+
+        Self::extract_data(response)
+    }
+
     /// Create a task attempt (workspace).
-    pub async fn create_task_attempt(&self, payload: &CreateTaskAttemptBody) -> Result<Workspace> {
+    pub async fn create_task_attempt(
+        &self,
+        payload: &CreateTaskAttemptBody,
+        idempotency_key: &str,
+    ) -> Result<Workspace> {
         let response = self
             .client
             .post(self.url("/task-attempts"))
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
             .json(payload)
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to create task attempt")?
-            .json::<ApiResponse<Workspace>>()
+            .api_json::<Workspace>()
             .await
             .context("Failed to parse create task attempt response")?;
 
@@ -268,151 +738,734 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url(&format!("/task-attempts/{}/branch-status", workspace_id)))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch branch status")?
-            .json::<ApiResponse<Vec<RepoBranchStatus>>>()
+            .api_json::<Vec<RepoBranchStatus>>()
             .await
             .context("Failed to parse branch status response")?;
 
         Self::extract_data(response)
     }
 
-    /// Get repositories for a workspace.
-    pub async fn get_workspace_repos(&self, workspace_id: Uuid) -> Result<Vec<RepoWithTargetBranch>> {
+    /// Get the commits of a workspace branch ahead of the target branch of each repo.
+    pub async fn get_workspace_commits(&self, workspace_id: Uuid) -> Result<Vec<RepoCommits>> {
         let response = self
             .client
-            .get(self.url(&format!("/task-attempts/{}/repos", workspace_id)))
-            .send()
+            .get(self.url(&format!("/task-attempts/{}/commits", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
             .await
-            .context("Failed to fetch workspace repos")?
-            .json::<ApiResponse<Vec<RepoWithTargetBranch>>>()
+            .context("Failed to fetch workspace commits")?
+            .api_json::<Vec<RepoCommits>>()
             .await
-            .context("Failed to parse workspace repos response")?;
+            .context("Failed to parse workspace commits response")?;
 
         Self::extract_data(response)
     }
 
-    /// Stop a workspace execution.
-    pub async fn stop_workspace(&self, workspace_id: Uuid) -> Result<()> {
+    /// Get repositories for a workspace.
+    pub async fn get_workspace_repos(&self, workspace_id: Uuid) -> Result<Vec<RepoWithTargetBranch>> {
         let response = self
             .client
-            .post(self.url(&format!("/task-attempts/{}/stop", workspace_id)))
-            .send()
+            .get(self.url(&format!("/task-attempts/{}/repos", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
             .await
-            .context("Failed to stop workspace")?
-            .json::<ApiResponse<()>>()
+            .context("Failed to fetch workspace repos")?
+            .api_json::<Vec<RepoWithTargetBranch>>()
             .await
-            .context("Failed to parse stop workspace response")?;
+            .context("Failed to parse workspace repos response")?;
 
         Self::extract_data(response)
     }
 
-    // =========================================================================
-    // Git Operations
-    // =========================================================================
-
-    /// Merge changes for a workspace.
-    pub async fn merge_workspace(&self, workspace_id: Uuid, repo_id: Uuid) -> Result<()> {
-        let payload = MergeTaskAttemptRequest { repo_id };
+    /// Get the resource limits of a workspace, if any were set.
+    pub async fn get_workspace_resource_limits(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Option<WorkspaceResourceLimits>> {
         let response = self
             .client
-            .post(self.url(&format!("/task-attempts/{}/merge", workspace_id)))
-            .json(&payload)
-            .send()
+            .get(self.url(&format!("/task-attempts/{}/resource-limits", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
             .await
-            .context("Failed to merge workspace")?
-            .json::<ApiResponse<()>>()
+            .context("Failed to fetch workspace resource limits")?
+            .api_json::<WorkspaceResourceLimits>()
             .await
-            .context("Failed to parse merge response")?;
+            .context("Failed to parse workspace resource limits response")?;
 
-        Self::extract_data(response)
+        Self::extract_optional(response)
     }
 
-    /// Push workspace branch.
-    pub async fn push_workspace(&self, workspace_id: Uuid, repo_id: Uuid) -> Result<()> {
-        let payload = PushTaskAttemptRequest { repo_id };
+    /// Get the auto-rebase policy of a workspace, if it opted in.
+    pub async fn get_workspace_auto_rebase(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Option<WorkspaceAutoRebase>> {
         let response = self
             .client
-            .post(self.url(&format!("/task-attempts/{}/push", workspace_id)))
-            .json(&payload)
-            .send()
+            .get(self.url(&format!("/task-attempts/{}/auto-rebase", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
             .await
-            .context("Failed to push workspace")?
-            .json::<ApiResponse<()>>()
+            .context("Failed to fetch auto-rebase policy")?
+            .api_json::<WorkspaceAutoRebase>()
             .await
-            .context("Failed to parse push response")?;
+            .context("Failed to parse auto-rebase policy response")?;
 
-        Self::extract_data(response)
+        Self::extract_optional(response)
     }
 
-    /// Rebase workspace branch.
-    pub async fn rebase_workspace(
+    /// Enable or change the auto-rebase policy of a workspace, resuming it if paused.
+    pub async fn set_workspace_auto_rebase(
         &self,
         workspace_id: Uuid,
-        repo_id: Uuid,
-        old_base: Option<String>,
-        new_base: Option<String>,
-    ) -> Result<()> {
-        let payload = RebaseTaskAttemptRequest {
-            repo_id,
-            old_base_branch: old_base,
-            new_base_branch: new_base,
-        };
+        payload: &SetWorkspaceAutoRebase,
+    ) -> Result<WorkspaceAutoRebase> {
         let response = self
             .client
-            .post(self.url(&format!("/task-attempts/{}/rebase", workspace_id)))
-            .json(&payload)
-            .send()
+            .put(self.url(&format!("/task-attempts/{}/auto-rebase", workspace_id)))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
             .await
-            .context("Failed to rebase workspace")?
-            .json::<ApiResponse<()>>()
+            .context("Failed to update auto-rebase policy")?
+            .api_json::<WorkspaceAutoRebase>()
             .await
-            .context("Failed to parse rebase response")?;
+            .context("Failed to parse auto-rebase policy response")?;
 
         Self::extract_data(response)
     }
 
-    // =========================================================================
-    // Sessions
-    // =========================================================================
-
-    /// List sessions for a workspace.
-    pub async fn list_sessions(&self, workspace_id: Uuid) -> Result<Vec<Session>> {
+    /// Get the notes kept on a workspace, if it has any.
+    pub async fn get_workspace_note(&self, workspace_id: Uuid) -> Result<Option<WorkspaceNote>> {
         let response = self
             .client
-            .get(self.url("/sessions"))
-            .query(&[("workspace_id", workspace_id.to_string())])
-            .send()
+            .get(self.url(&format!("/task-attempts/{}/notes", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
             .await
-            .context("Failed to fetch sessions")?
-            .json::<ApiResponse<Vec<Session>>>()
+            .context("Failed to fetch workspace notes")?
+            .api_json::<WorkspaceNote>()
             .await
-            .context("Failed to parse sessions response")?;
+            .context("Failed to parse workspace notes response")?;
 
-        Self::extract_data(response)
+        Self::extract_optional(response)
     }
 
-    /// Send a follow-up message to a session.
-    pub async fn send_follow_up(
+    /// Replace the notes kept on a workspace; blank notes remove them.
+    pub async fn set_workspace_note(
         &self,
-        session_id: Uuid,
-        payload: &CreateFollowUpAttempt,
+        workspace_id: Uuid,
+        payload: &SetWorkspaceNote,
+    ) -> Result<Option<WorkspaceNote>> {
+        let response = self
+            .client
+            .put(self.url(&format!("/task-attempts/{}/notes", workspace_id)))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update workspace notes")?
+            .api_json::<WorkspaceNote>()
+            .await
+            .context("Failed to parse workspace notes response")?;
+
+        Self::extract_optional(response)
+    }
+
+    /// Get the server's workspace retention policy.
+    pub async fn get_retention_policy(&self) -> Result<RetentionPolicy> {
+        let response = self
+            .client
+            .get(self.url("/task-attempts/retention-policy"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch retention policy")?
+            .api_json::<RetentionPolicy>()
+            .await
+            .context("Failed to parse retention policy response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Replace the server's workspace retention policy.
+    pub async fn update_retention_policy(
+        &self,
+        policy: &RetentionPolicy,
+    ) -> Result<RetentionPolicy> {
+        let response = self
+            .client
+            .put(self.url("/task-attempts/retention-policy"))
+            .json(policy)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update retention policy")?
+            .api_json::<RetentionPolicy>()
+            .await
+            .context("Failed to parse retention policy response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Workspaces `policy` would archive or remove the worktrees of right now.
+    pub async fn preview_retention_policy(
+        &self,
+        policy: &RetentionPolicy,
+    ) -> Result<Vec<RetentionCandidate>> {
+        let response = self
+            .client
+            .post(self.url("/task-attempts/retention-policy/preview"))
+            .json(policy)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to preview retention policy")?
+            .api_json::<Vec<RetentionCandidate>>()
+            .await
+            .context("Failed to parse retention policy preview response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Turn off auto-rebase for a workspace.
+    pub async fn delete_workspace_auto_rebase(&self, workspace_id: Uuid) -> Result<()> {
+        let response = self
+            .client
+            .delete(self.url(&format!("/task-attempts/{}/auto-rebase", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to delete auto-rebase policy")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse delete auto-rebase policy response")?;
+
+        Self::extract_unit(response)
+    }
+
+    /// Get the container status of every workspace of a task.
+    pub async fn list_container_statuses(
+        &self,
+        task_id: Uuid,
+    ) -> Result<Vec<WorkspaceContainerStatus>> {
+        let response = self
+            .client
+            .get(self.url("/task-attempts/container-status"))
+            .query(&[("task_id", task_id.to_string())])
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch container status")?
+            .api_json::<Vec<WorkspaceContainerStatus>>()
+            .await
+            .context("Failed to parse container status response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// List workspaces whose latest attempt failed, newest first.
+    pub async fn list_workspace_failures(
+        &self,
+        project_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+        category: Option<FailureCategory>,
+    ) -> Result<Vec<WorkspaceFailure>> {
+        let mut query = Vec::new();
+        if let Some(project_id) = project_id {
+            query.push(("project_id", project_id.to_string()));
+        }
+        if let Some(task_id) = task_id {
+            query.push(("task_id", task_id.to_string()));
+        }
+        if let Some(category) = category {
+            query.push(("category", category.as_str().to_string()));
+        }
+        let response = self
+            .client
+            .get(self.url("/task-attempts/failures"))
+            .query(&query)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch workspace failures")?
+            .api_json::<Vec<WorkspaceFailure>>()
+            .await
+            .context("Failed to parse workspace failures response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Get what the coding agent of each workspace of a task said it did, newest
+    /// first.
+    pub async fn list_workspace_results(&self, task_id: Uuid) -> Result<Vec<WorkspaceResult>> {
+        let response = self
+            .client
+            .get(self.url("/task-attempts/results"))
+            .query(&[("task_id", task_id.to_string())])
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch workspace results")?
+            .api_json::<Vec<WorkspaceResult>>()
+            .await
+            .context("Failed to parse workspace results response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Get the latest progress reported by the agents in each workspace of a task.
+    pub async fn list_workspace_progress(&self, task_id: Uuid) -> Result<Vec<SessionProgress>> {
+        let response = self
+            .client
+            .get(self.url("/sessions/progress"))
+            .query(&[("task_id", task_id.to_string())])
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch workspace progress")?
+            .api_json::<Vec<SessionProgress>>()
+            .await
+            .context("Failed to parse workspace progress response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// List execution processes the server failed because they were orphaned.
+    pub async fn list_orphaned_executions(
+        &self,
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<ExecutionProcessOrphan>> {
+        let mut query = vec![("limit", limit.to_string())];
+        if let Some(project_id) = project_id {
+            query.push(("project_id", project_id.to_string()));
+        }
+        let response = self
+            .client
+            .get(self.url("/execution-processes/orphans"))
+            .query(&query)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch orphaned executions")?
+            .api_json::<Vec<ExecutionProcessOrphan>>()
+            .await
+            .context("Failed to parse orphaned executions response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Stop a workspace's processes and recreate anything missing from its directory.
+    pub async fn restart_container(&self, workspace_id: Uuid) -> Result<WorkspaceContainerStatus> {
+        let response = self
+            .client
+            .post(self.url(&format!(
+                "/task-attempts/{}/container/restart",
+                workspace_id
+            )))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to restart container")?
+            .api_json::<WorkspaceContainerStatus>()
+            .await
+            .context("Failed to parse restart container response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Delete a workspace's directory and create it again from its branch.
+    pub async fn rebuild_container(&self, workspace_id: Uuid) -> Result<WorkspaceContainerStatus> {
+        let response = self
+            .client
+            .post(self.url(&format!(
+                "/task-attempts/{}/container/rebuild",
+                workspace_id
+            )))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to rebuild container")?
+            .api_json::<WorkspaceContainerStatus>()
+            .await
+            .context("Failed to parse rebuild container response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Stop a workspace execution.
+    pub async fn stop_workspace(&self, workspace_id: Uuid) -> Result<()> {
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/stop", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to stop workspace")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse stop workspace response")?;
+
+        Self::extract_unit(response)
+    }
+
+    /// Start the dev server script of the workspace's project in its worktree.
+    pub async fn start_dev_server(&self, workspace_id: Uuid) -> Result<()> {
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/start-dev-server", workspace_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to start dev server")?
+            // Newer servers return the started processes, which are reloaded anyway
+            .api_json::<serde::de::IgnoredAny>()
+            .await
+            .context("Failed to parse start dev server response")?;
+
+        if response.success {
+            Ok(())
+        } else {
+            Self::extract_data(response).map(drop)
+        }
+    }
+
+    /// The address a dev server printed it listens on, from its output so far.
+    pub async fn find_dev_server_url(&self, process: &ExecutionProcess) -> Result<Option<String>> {
+        let read = async {
+            let mut stream = self.stream_process_logs(process).await?;
+            let mut log = ProcessLog::default();
+            // The output so far arrives at once, then the stream waits for more
+            while let Ok(Some(event)) =
+                tokio::time::timeout(DEV_SERVER_LOG_IDLE, stream.next()).await
+            {
+                let event = event?;
+                log.apply(&event)?;
+                if matches!(event, LogEvent::Finished) {
+                    break;
+                }
+            }
+            Ok(log
+                .lines()
+                .iter()
+                .find_map(|line| links::find_local_server_url(&line.text)))
+        };
+        tokio::time::timeout(DEV_SERVER_LOG_TIMEOUT, read)
+            .await
+            .context("Timed out reading the dev server's output")?
+    }
+
+    // =========================================================================
+    // Git Operations
+    // =========================================================================
+
+    /// Merge changes for a workspace.
+    pub async fn merge_workspace(
+        &self,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        idempotency_key: &str,
+        confirm_protected: bool,
+    ) -> Result<()> {
+        let payload = MergeTaskAttemptRequest {
+            repo_id,
+            confirm_protected,
+        };
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/merge", workspace_id)))
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+            .json(&payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to merge workspace")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse merge response")?;
+
+        Self::extract_unit(response)
+    }
+
+    /// Push workspace branch.
+    pub async fn push_workspace(
+        &self,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        confirm_protected: bool,
+    ) -> Result<()> {
+        let payload = PushTaskAttemptRequest {
+            repo_id,
+            confirm_protected,
+        };
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/push", workspace_id)))
+            .json(&payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to push workspace")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse push response")?;
+
+        Self::extract_unit(response)
+    }
+
+    /// Push the workspace branch and open a pull request for it. Returns the
+    /// pull request's URL.
+    pub async fn create_pr(&self, workspace_id: Uuid, payload: &CreatePrRequest) -> Result<String> {
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/pr", workspace_id)))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to create pull request")?
+            .api_json::<String>()
+            .await
+            .context("Failed to parse pull request response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Rebase workspace branch.
+    pub async fn rebase_workspace(
+        &self,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        old_base: Option<String>,
+        new_base: Option<String>,
+        confirm_protected: bool,
+    ) -> Result<()> {
+        let payload = RebaseTaskAttemptRequest {
+            repo_id,
+            old_base_branch: old_base,
+            new_base_branch: new_base,
+            confirm_protected,
+        };
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/rebase", workspace_id)))
+            .json(&payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to rebase workspace")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse rebase response")?;
+
+        Self::extract_unit(response)
+    }
+
+    /// Preview a merge without merging.
+    pub async fn preview_merge(
+        &self,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<GitOperationPreview> {
+        let payload = MergeTaskAttemptRequest {
+            repo_id,
+            confirm_protected: false,
+        };
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/merge/preview", workspace_id)))
+            .json(&payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to preview merge")?
+            .api_json::<GitOperationPreview>()
+            .await
+            .context("Failed to parse merge preview response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Preview a push without pushing.
+    pub async fn preview_push(
+        &self,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<GitOperationPreview> {
+        let payload = PushTaskAttemptRequest {
+            repo_id,
+            confirm_protected: false,
+        };
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/push/preview", workspace_id)))
+            .json(&payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to preview push")?
+            .api_json::<GitOperationPreview>()
+            .await
+            .context("Failed to parse push preview response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Preview a rebase without rebasing.
+    pub async fn preview_rebase(
+        &self,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        old_base: Option<String>,
+        new_base: Option<String>,
+    ) -> Result<GitOperationPreview> {
+        let payload = RebaseTaskAttemptRequest {
+            repo_id,
+            old_base_branch: old_base,
+            new_base_branch: new_base,
+            confirm_protected: false,
+        };
+        let response = self
+            .client
+            .post(self.url(&format!("/task-attempts/{}/rebase/preview", workspace_id)))
+            .json(&payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to preview rebase")?
+            .api_json::<GitOperationPreview>()
+            .await
+            .context("Failed to parse rebase preview response")?;
+
+        Self::extract_data(response)
+    }
+
+    // =========================================================================
+    // Sessions
+    // =========================================================================
+
+    /// List sessions for a workspace.
+    pub async fn list_sessions(&self, workspace_id: Uuid) -> Result<Vec<Session>> {
+        let response = self
+            .client
+            .get(self.url("/sessions"))
+            .query(&[("workspace_id", workspace_id.to_string())])
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch sessions")?
+            .api_json::<Vec<Session>>()
+            .await
+            .context("Failed to parse sessions response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Send a follow-up message to a session.
+    pub async fn send_follow_up(
+        &self,
+        session_id: Uuid,
+        payload: &CreateFollowUpAttempt,
     ) -> Result<ExecutionProcess> {
         let response = self
             .client
             .post(self.url(&format!("/sessions/{}/follow-up", session_id)))
             .json(payload)
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to send follow-up")?
-            .json::<ApiResponse<ExecutionProcess>>()
+            .api_json::<ExecutionProcess>()
             .await
             .context("Failed to parse follow-up response")?;
 
         Self::extract_data(response)
     }
 
+    // =========================================================================
+    // Execution Processes
+    // =========================================================================
+
+    /// List the execution processes of a session, oldest first.
+    /// Stop one execution process, leaving the workspace's others running.
+    pub async fn stop_execution_process(&self, process_id: Uuid) -> Result<()> {
+        let response = self
+            .client
+            .post(self.url(&format!("/execution-processes/{}/stop", process_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to stop execution process")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse stop execution process response")?;
+
+        Self::extract_unit(response)
+    }
+
+    pub async fn list_execution_processes(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Vec<ExecutionProcess>> {
+        let response = self
+            .client
+            .get(self.url("/execution-processes"))
+            .query(&[("session_id", session_id.to_string())])
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch execution processes")?
+            .api_json::<Vec<ExecutionProcess>>()
+            .await
+            .context("Failed to parse execution processes response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Stream the log of an execution process: its history, then new output until it
+    /// exits. Coding agents stream their normalized conversation, scripts their raw
+    /// output.
+    pub async fn stream_process_logs(&self, process: &ExecutionProcess) -> Result<LogStream> {
+        let kind = if process.run_reason == "codingagent" {
+            "normalized-logs"
+        } else {
+            "raw-logs"
+        };
+        let mut url =
+            Url::parse(&self.url(&format!("/execution-processes/{}/{}/ws", process.id, kind)))
+                .context("Invalid server URL")?;
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        url.set_scheme(scheme)
+            .map_err(|_| anyhow!("Unsupported server URL: {}", self.base_url))?;
+
+        let (socket, _) = connect_async(url.as_str())
+            .await
+            .context("Failed to connect to log stream")?;
+        let stream = socket.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => logs::parse_log_message(&text).transpose(),
+                Ok(_) => None,
+                Err(e) => Some(Err(anyhow::Error::new(e).context("Log stream failed"))),
+            }
+        });
+        Ok(stream.boxed())
+    }
+
+    /// The changed files of a workspace against its target branches, read from the
+    /// diff stream up to the point where the current changes are all in.
+    pub async fn get_workspace_diff(&self, workspace_id: Uuid) -> Result<Vec<Diff>> {
+        let mut url = Url::parse(&self.url(&format!("/task-attempts/{}/diff/ws", workspace_id)))
+            .context("Invalid server URL")?;
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        url.set_scheme(scheme)
+            .map_err(|_| anyhow!("Unsupported server URL: {}", self.base_url))?;
+
+        let read = async {
+            let (mut socket, _) = connect_async(url.as_str())
+                .await
+                .context("Failed to connect to diff stream")?;
+            let mut workspace_diff = WorkspaceDiff::default();
+            while let Some(message) = socket.next().await {
+                let Message::Text(text) = message.context("Diff stream failed")? else {
+                    continue;
+                };
+                match diff::parse_diff_message(&text)? {
+                    Some(DiffEvent::Patch(patch)) => workspace_diff.apply(&patch)?,
+                    Some(DiffEvent::Ready) => break,
+                    None => {}
+                }
+            }
+            workspace_diff.files()
+        };
+        tokio::time::timeout(DIFF_TIMEOUT, read)
+            .await
+            .context("Timed out waiting for the diff")?
+    }
+
+    /// Open the server's event stream, see [`crate::api::events`].
+    pub(crate) async fn open_event_stream(&self) -> Result<Response> {
+        self.client
+            .get(self.url("/events"))
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .context("Failed to connect to event stream")
+    }
+
     // =========================================================================
     // Repositories
     // =========================================================================
@@ -422,10 +1475,10 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url("/repos"))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch repos")?
-            .json::<ApiResponse<Vec<Repo>>>()
+            .api_json::<Vec<Repo>>()
             .await
             .context("Failed to parse repos response")?;
 
@@ -437,16 +1490,330 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url(&format!("/repos/{}/branches", repo_id)))
-            .send()
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to fetch branches")?
-            .json::<ApiResponse<Vec<GitBranch>>>()
+            .api_json::<Vec<GitBranch>>()
             .await
             .context("Failed to parse branches response")?;
 
         Self::extract_data(response)
     }
 
+    /// List the protected branches of a repository.
+    pub async fn list_protected_branches(&self, repo_id: Uuid) -> Result<Vec<ProtectedBranch>> {
+        let response = self
+            .client
+            .get(self.url(&format!("/repos/{}/protected-branches", repo_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch protected branches")?
+            .api_json::<Vec<ProtectedBranch>>()
+            .await
+            .context("Failed to parse protected branches response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Protect a branch name or glob, or change how an already protected one is.
+    pub async fn protect_branch(
+        &self,
+        repo_id: Uuid,
+        payload: &CreateProtectedBranch,
+    ) -> Result<ProtectedBranch> {
+        let response = self
+            .client
+            .post(self.url(&format!("/repos/{}/protected-branches", repo_id)))
+            .json(payload)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to protect branch")?
+            .api_json::<ProtectedBranch>()
+            .await
+            .context("Failed to parse protected branch response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Lift the protection of a branch.
+    pub async fn unprotect_branch(&self, repo_id: Uuid, protected_branch_id: Uuid) -> Result<()> {
+        let response = self
+            .client
+            .delete(self.url(&format!(
+                "/repos/{}/protected-branches/{}",
+                repo_id, protected_branch_id
+            )))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to unprotect branch")?
+            .api_json::<()>()
+            .await
+            .context("Failed to parse unprotect branch response")?;
+
+        Self::extract_unit(response)
+    }
+
+    // =========================================================================
+    // Executors
+    // =========================================================================
+
+    /// Get installation, login and rate limit health for all executors.
+    pub async fn get_executor_health(&self) -> Result<Vec<ExecutorHealth>> {
+        let response = self
+            .client
+            .get(self.url("/agents/health"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch executor health")?
+            .api_json::<Vec<ExecutorHealth>>()
+            .await
+            .context("Failed to parse executor health response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// List the configured variants of every executor.
+    pub async fn get_executor_variants(&self) -> Result<Vec<ExecutorVariant>> {
+        let response = self
+            .client
+            .get(self.url("/profiles"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch executor profiles")?
+            .api_json::<ProfilesContent>()
+            .await
+            .context("Failed to parse executor profiles response")?;
+
+        let profiles = Self::extract_data(response)?;
+        ExecutorVariant::parse_profiles(&profiles.content)
+            .context("Failed to parse executor profiles")
+    }
+
+    /// Get the settings of this CLI kept on the server for the user.
+    pub async fn get_user_preferences(&self) -> Result<UserPreferences> {
+        let response = self
+            .client
+            .get(self.url("/user-preferences"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch user preferences")?
+            .api_json::<UserPreferences>()
+            .await
+            .context("Failed to parse user preferences response")?;
+
+        Self::extract_data(response)
+    }
+
+    /// Replace the settings of this CLI kept on the server for the user.
+    pub async fn update_user_preferences(
+        &self,
+        preferences: &UserPreferences,
+    ) -> Result<UserPreferences> {
+        let response = self
+            .client
+            .put(self.url("/user-preferences"))
+            .json(preferences)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update user preferences")?
+            .api_json::<UserPreferences>()
+            .await
+            .context("Failed to parse user preferences response")?;
+
+        Self::extract_data(response)
+    }
+
+    // =========================================================================
+    // Teams
+    // =========================================================================
+
+    /// List team tasks waiting for a worker slot, in start order.
+    pub async fn get_attempt_queue(&self) -> Result<Vec<QueuedTeamTask>> {
+        let response = self
+            .client
+            .get(self.url("/teams/queue"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch attempt queue")?;
+
+        Self::team_json(response, "attempt queue").await
+    }
+
+    /// Start a team execution for an epic, marking the task as an epic if it
+    /// isn't one yet.
+    pub async fn create_team_execution(
+        &self,
+        body: &CreateTeamExecutionRequest,
+    ) -> Result<TeamExecution> {
+        let response = self
+            .client
+            .post(self.url("/teams"))
+            .json(body)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to create team execution")?;
+        Self::team_json(response, "team execution").await
+    }
+
+    /// Decompose the epic of a team execution into subtasks.
+    pub async fn plan_team(&self, team_execution_id: Uuid) -> Result<TeamPlanOutput> {
+        let response = self
+            .client
+            .post(self.url(&format!("/teams/{}/plan", team_execution_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to plan team execution")?;
+        let response: TeamPlanResponse = Self::team_json(response, "team plan").await?;
+        Ok(response.plan)
+    }
+
+    /// Create the tasks of a team execution's plan and start executing it.
+    pub async fn execute_team(&self, team_execution_id: Uuid) -> Result<Vec<TeamTask>> {
+        let response = self
+            .client
+            .post(self.url(&format!("/teams/{}/execute", team_execution_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to execute team plan")?;
+        Self::team_json(response, "team tasks").await
+    }
+
+    /// List the subtasks of a team execution, in planned order.
+    pub async fn get_team_tasks(&self, team_execution_id: Uuid) -> Result<Vec<TeamTask>> {
+        let response = self
+            .client
+            .get(self.url(&format!("/teams/{}/tasks", team_execution_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch team tasks")?;
+
+        Self::team_json(response, "team tasks").await
+    }
+
+    /// List saved plan templates.
+    pub async fn list_plan_templates(&self) -> Result<Vec<PlanTemplate>> {
+        let response = self
+            .client
+            .get(self.url("/plan-templates"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch plan templates")?;
+        Self::team_json(response, "plan templates").await
+    }
+
+    /// Save the plan of a completed team execution as a template.
+    pub async fn create_plan_template(&self, body: &CreatePlanTemplate) -> Result<PlanTemplate> {
+        let response = self
+            .client
+            .post(self.url("/plan-templates"))
+            .json(body)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to save plan template")?;
+        Self::team_json(response, "plan template").await
+    }
+
+    /// Delete a plan template. Returns false if it did not exist.
+    pub async fn delete_plan_template(&self, template_id: Uuid) -> Result<bool> {
+        let response = self
+            .client
+            .delete(self.url(&format!("/plan-templates/{}", template_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to delete plan template")?;
+        Self::team_json(response, "delete plan template").await
+    }
+
+    /// List active agent profiles.
+    pub async fn list_agent_profiles(&self) -> Result<Vec<AgentProfile>> {
+        let response = self
+            .client
+            .get(self.url("/agent-profiles"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch agent profiles")?;
+        Self::team_json(response, "agent profiles").await
+    }
+
+    /// Create default profiles for the team roles no active profile fills.
+    /// Returns the profiles created.
+    pub async fn seed_default_agent_profiles(&self) -> Result<Vec<AgentProfile>> {
+        let response = self
+            .client
+            .post(self.url("/agent-profiles/defaults"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to create default agent profiles")?;
+        Self::team_json(response, "default agent profiles").await
+    }
+
+    /// Change an agent profile.
+    pub async fn update_agent_profile(
+        &self,
+        profile_id: Uuid,
+        body: &UpdateAgentProfile,
+    ) -> Result<AgentProfile> {
+        let response = self
+            .client
+            .put(self.url(&format!("/agent-profiles/{}", profile_id)))
+            .json(body)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update agent profile")?;
+        Self::team_json(response, "agent profile").await
+    }
+
+    /// Limits the team planner applies when decomposing epics.
+    pub async fn get_planner_config(&self) -> Result<PlannerConfig> {
+        let response = self
+            .client
+            .get(self.url("/teams/planner-config"))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch planner settings")?;
+        Self::team_json(response, "planner settings").await
+    }
+
+    /// Replace the planner limits; the server saves them to its config file.
+    pub async fn update_planner_config(&self, body: &PlannerConfig) -> Result<PlannerConfig> {
+        let response = self
+            .client
+            .put(self.url("/teams/planner-config"))
+            .json(body)
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to update planner settings")?;
+        Self::team_json(response, "planner settings").await
+    }
+
+    /// Skills a project's repositories call for, and the worker profiles lacking them.
+    pub async fn get_skill_suggestions(&self, project_id: Uuid) -> Result<ProjectSkillSuggestions> {
+        let response = self
+            .client
+            .get(self.url(&format!("/projects/{}/skill-suggestions", project_id)))
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to fetch skill suggestions")?;
+        Self::team_json(response, "skill suggestions").await
+    }
+
+    /// Plan a team execution from a template instead of decomposing its epic.
+    pub async fn plan_team_from_template(
+        &self,
+        team_execution_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<TeamPlanOutput> {
+        let response = self
+            .client
+            .post(self.url(&format!("/teams/{}/plan-from-template", team_execution_id)))
+            .json(&PlanFromTemplateRequest { template_id })
+            .send_journaled(&self.journal, &self.metrics)
+            .await
+            .context("Failed to plan from template")?;
+        let response: TeamPlanResponse = Self::team_json(response, "team plan").await?;
+        Ok(response.plan)
+    }
+
     // =========================================================================
     // Health Check
     // =========================================================================
@@ -456,7 +1823,8 @@ impl VibeKanbanClient {
         let response = self
             .client
             .get(self.url("/health"))
-            .send()
+            .timeout(HEALTH_CHECK_TIMEOUT)
+            .send_journaled(&self.journal, &self.metrics)
             .await
             .context("Failed to reach server")?;
 
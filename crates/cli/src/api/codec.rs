@@ -0,0 +1,112 @@
+//! Wire formats of API responses.
+//!
+//! Requests always go out as JSON, which every server reads. Responses can come
+//! back in a more compact format where the server supports one: the client asks
+//! for its [`WireFormat`] in `Accept`, with JSON as the fallback, and decodes
+//! whatever the response's `Content-Type` says it is. MessagePack and CBOR
+//! support are behind the `msgpack` and `cbor` features. Log and diff streams
+//! are WebSocket messages and stay JSON.
+
+use anyhow::{Result, anyhow};
+use reqwest::header::{CONTENT_TYPE, HeaderMap};
+use serde::{Deserialize, de::DeserializeOwned};
+
+/// Decodes response bodies in one wire format
+pub trait Codec {
+    fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T>;
+}
+
+pub struct Json;
+
+impl Codec for Json {
+    fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MessagePack {
+    fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+        Ok(rmp_serde::from_slice(body)?)
+    }
+}
+
+#[cfg(feature = "cbor")]
+pub struct Cbor;
+
+#[cfg(feature = "cbor")]
+impl Codec for Cbor {
+    fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+        ciborium::from_reader(body).map_err(|e| anyhow!("Invalid CBOR: {:?}", e))
+    }
+}
+
+/// Format the client asks the server to respond in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WireFormat {
+    #[default]
+    Json,
+    #[serde(alias = "messagepack")]
+    Msgpack,
+    Cbor,
+}
+
+impl WireFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            WireFormat::Json => "JSON",
+            WireFormat::Msgpack => "MessagePack",
+            WireFormat::Cbor => "CBOR",
+        }
+    }
+
+    /// Whether this build can decode the format
+    pub fn is_supported(self) -> bool {
+        match self {
+            WireFormat::Json => true,
+            WireFormat::Msgpack => cfg!(feature = "msgpack"),
+            WireFormat::Cbor => cfg!(feature = "cbor"),
+        }
+    }
+
+    /// `Accept` header asking for this format, with JSON for servers without it
+    pub fn accept(self) -> &'static str {
+        match self {
+            WireFormat::Json => "application/json",
+            WireFormat::Msgpack => "application/msgpack, application/json;q=0.9",
+            WireFormat::Cbor => "application/cbor, application/json;q=0.9",
+        }
+    }
+
+    /// The format a response is in, by its `Content-Type`; JSON unless it says otherwise
+    pub fn of_response(headers: &HeaderMap) -> Self {
+        let media_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(str::trim)
+            .unwrap_or_default();
+        match media_type {
+            "application/msgpack" | "application/x-msgpack" => WireFormat::Msgpack,
+            "application/cbor" => WireFormat::Cbor,
+            _ => WireFormat::Json,
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, body: &[u8]) -> Result<T> {
+        match self {
+            WireFormat::Json => Json::decode(body),
+            #[cfg(feature = "msgpack")]
+            WireFormat::Msgpack => MessagePack::decode(body),
+            #[cfg(feature = "cbor")]
+            WireFormat::Cbor => Cbor::decode(body),
+            // Only asked for when supported, so a server sent it unprompted
+            #[allow(unreachable_patterns)]
+            other => Err(anyhow!("This build can't read {} responses", other.label())),
+        }
+    }
+}
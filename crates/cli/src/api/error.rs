@@ -0,0 +1,45 @@
+//! Errors reported by the Vibe Kanban server.
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::types::{BranchPolicyReport, PrError, ProtectedBranchViolation};
+
+/// A failed API call, with everything the server told us about it.
+#[derive(Debug, Clone, Error)]
+#[error("API error: {message}")]
+pub struct ApiError {
+    /// HTTP status code of the response
+    pub status: Option<u16>,
+    pub message: String,
+    /// Structured error payload, e.g. `{"type": "merge_conflicts", ...}`
+    pub error_data: Option<Value>,
+}
+
+impl ApiError {
+    /// The `type` tag of the structured error payload, if any.
+    pub fn error_type(&self) -> Option<&str> {
+        self.error_data.as_ref()?.get("type")?.as_str()
+    }
+
+    /// The rejected name and suggested alternative of a branch policy violation.
+    pub fn branch_policy_report(&self) -> Option<BranchPolicyReport> {
+        if self.error_type() != Some("branch_policy_violation") {
+            return None;
+        }
+        serde_json::from_value(self.error_data.clone()?).ok()
+    }
+
+    /// The protected branch a merge, push or rebase was refused for.
+    pub fn protected_branch_violation(&self) -> Option<ProtectedBranchViolation> {
+        if self.error_type() != Some("protected_branch") {
+            return None;
+        }
+        serde_json::from_value(self.error_data.clone()?).ok()
+    }
+
+    /// Why a pull request couldn't be opened.
+    pub fn pr_error(&self) -> Option<PrError> {
+        serde_json::from_value(self.error_data.clone()?).ok()
+    }
+}
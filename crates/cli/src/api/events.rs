@@ -0,0 +1,147 @@
+//! Live updates from the server's event stream.
+//!
+//! The server publishes changes to tasks, workspaces and execution processes as
+//! JSON patches over server-sent events at `/api/events`. [`subscribe`] keeps a
+//! connection open in the background, reconnecting when it drops, and forwards
+//! the changes over a channel for the event loop to pick up.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use json_patch::{Patch, PatchOperation};
+use reqwest::StatusCode;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::VibeKanbanClient;
+use crate::types::TaskWithAttemptStatus;
+
+/// Server-sent event that carries a JSON patch
+const JSON_PATCH_EVENT: &str = "json_patch";
+
+/// Wait before reconnecting after the stream drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// A change reported by the server
+#[derive(Debug, Clone)]
+pub enum ServerEvent {
+    /// The stream is open; the server replays its recent changes first
+    Connected,
+    /// The stream dropped; another connection is attempted shortly
+    Disconnected,
+    TaskUpdated(Box<TaskWithAttemptStatus>),
+    TaskRemoved(Uuid),
+    WorkspaceChanged(Uuid),
+    ProcessChanged(Uuid),
+}
+
+/// Follow the server's event stream until the receiver is dropped. A server
+/// without the stream sends nothing, leaving the periodic reload to keep views
+/// up to date.
+pub fn subscribe(client: VibeKanbanClient) -> mpsc::UnboundedReceiver<ServerEvent> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            match forward(&client, &tx).await {
+                Ok(Some(StatusCode::NOT_FOUND)) => break,
+                Ok(_) => {}
+                Err(e) => tracing::debug!("Event stream failed: {:#}", e),
+            }
+            if tx.send(ServerEvent::Disconnected).is_err() {
+                break;
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+    rx
+}
+
+/// Forward events until the stream ends. Returns the status of a refused
+/// connection.
+async fn forward(
+    client: &VibeKanbanClient,
+    tx: &mpsc::UnboundedSender<ServerEvent>,
+) -> Result<Option<StatusCode>> {
+    let response = client.open_event_stream().await?;
+    if !response.status().is_success() {
+        return Ok(Some(response.status()));
+    }
+    tx.send(ServerEvent::Connected)?;
+
+    let mut parser = SseParser::default();
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.context("Event stream interrupted")?;
+        for (event, data) in parser.push(&chunk) {
+            if event != JSON_PATCH_EVENT {
+                continue;
+            }
+            let Ok(patch) = serde_json::from_str::<Patch>(&data) else {
+                continue;
+            };
+            for event in patch.0.iter().filter_map(to_server_event) {
+                tx.send(event)?;
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The change a patch operation describes, for the collections the CLI shows.
+fn to_server_event(operation: &PatchOperation) -> Option<ServerEvent> {
+    let (collection, id) = operation
+        .path()
+        .as_str()
+        .trim_start_matches('/')
+        .split_once('/')?;
+    let id = Uuid::parse_str(id).ok()?;
+    match collection {
+        "tasks" => match operation {
+            PatchOperation::Add(op) => serde_json::from_value(op.value.clone()).ok(),
+            PatchOperation::Replace(op) => serde_json::from_value(op.value.clone()).ok(),
+            PatchOperation::Remove(_) => return Some(ServerEvent::TaskRemoved(id)),
+            _ => None,
+        }
+        .map(|task| ServerEvent::TaskUpdated(Box::new(task))),
+        "workspaces" => Some(ServerEvent::WorkspaceChanged(id)),
+        "execution_processes" => Some(ServerEvent::ProcessChanged(id)),
+        _ => None,
+    }
+}
+
+/// Splits a server-sent event stream into `(event, data)` pairs.
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    /// Add a chunk of the stream, returning the events it completes.
+    fn push(&mut self, chunk: &[u8]) -> Vec<(String, String)> {
+        self.buffer.extend(chunk.iter().filter(|b| **b != b'\r'));
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.windows(2).position(|w| w == b"\n\n") {
+            let block: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            let block = String::from_utf8_lossy(&block);
+
+            let mut event = String::from("message");
+            let mut data = Vec::new();
+            for line in block.lines() {
+                let (field, value) = line.split_once(':').unwrap_or((line, ""));
+                let value = value.strip_prefix(' ').unwrap_or(value);
+                match field {
+                    "event" => event = value.to_string(),
+                    "data" => data.push(value),
+                    // Comments (keep-alives) and unknown fields
+                    _ => {}
+                }
+            }
+            if !data.is_empty() {
+                events.push((event, data.join("\n")));
+            }
+        }
+        events
+    }
+}
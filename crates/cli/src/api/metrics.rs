@@ -0,0 +1,124 @@
+//! Latency and outcome of the API requests made this session, per endpoint, for
+//! the debug overlay. Streams (events, logs) are left out: they stay open for as
+//! long as they are watched.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::Method;
+use url::Url;
+use uuid::Uuid;
+
+/// Requests kept per endpoint; percentiles and sparklines cover these
+const MAX_SAMPLES: usize = 500;
+
+/// One finished request
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub latency: Duration,
+    /// No response, or a 5xx one
+    pub failed: bool,
+}
+
+/// Requests to one endpoint, oldest first
+#[derive(Debug, Clone)]
+pub struct EndpointMetrics {
+    /// Method and path, with IDs replaced by `:id`
+    pub endpoint: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub samples: VecDeque<Sample>,
+}
+
+impl EndpointMetrics {
+    /// Latency below which `percent` of the kept requests finished
+    pub fn percentile(&self, percent: usize) -> Option<Duration> {
+        let mut latencies: Vec<_> = self.samples.iter().map(|s| s.latency).collect();
+        latencies.sort();
+        let rank = (latencies.len() * percent).div_ceil(100);
+        latencies.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Share of all requests this session that failed, in percent
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.errors as f64 * 100.0 / self.requests as f64
+    }
+}
+
+/// Shared by every clone of the API client
+#[derive(Clone)]
+pub struct RequestMetrics {
+    started_at: Instant,
+    endpoints: Arc<Mutex<HashMap<String, EndpointMetrics>>>,
+}
+
+impl Default for RequestMetrics {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            endpoints: Arc::default(),
+        }
+    }
+}
+
+impl RequestMetrics {
+    pub fn record(&self, method: &Method, url: &Url, latency: Duration, failed: bool) {
+        let endpoint = endpoint_name(method, url);
+        let mut endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        let metrics = endpoints
+            .entry(endpoint.clone())
+            .or_insert_with(|| EndpointMetrics {
+                endpoint,
+                requests: 0,
+                errors: 0,
+                samples: VecDeque::new(),
+            });
+        metrics.requests += 1;
+        metrics.errors += u64::from(failed);
+        if metrics.samples.len() == MAX_SAMPLES {
+            metrics.samples.pop_front();
+        }
+        metrics.samples.push_back(Sample { latency, failed });
+    }
+
+    /// Every endpoint requested so far, slowest (by p95) first
+    pub fn snapshot(&self) -> Vec<EndpointMetrics> {
+        let endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        let mut snapshot: Vec<_> = endpoints.values().cloned().collect();
+        snapshot.sort_by(|a, b| {
+            b.percentile(95)
+                .cmp(&a.percentile(95))
+                .then_with(|| a.endpoint.cmp(&b.endpoint))
+        });
+        snapshot
+    }
+
+    /// How long the metrics have been collected for
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// `GET /projects/:id/tasks` for `GET http://host/api/projects/<uuid>/tasks?all=1`
+fn endpoint_name(method: &Method, url: &Url) -> String {
+    let path = url.path();
+    let path = path.strip_prefix("/api").unwrap_or(path);
+    let path = path
+        .split('/')
+        .map(|segment| {
+            if Uuid::parse_str(segment).is_ok() {
+                ":id"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{} {}", method, path)
+}
@@ -1,5 +1,10 @@
 //! API client for communicating with the Vibe Kanban server.
 
 pub mod client;
+pub mod codec;
+pub mod error;
+pub mod events;
+pub mod metrics;
 
 pub use client::VibeKanbanClient;
+pub use error::ApiError;
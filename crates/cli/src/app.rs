@@ -1,23 +1,108 @@
 //! Application state and logic.
 
-use anyhow::Result;
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    io,
+    mem::{self, Discriminant},
+    path::PathBuf,
+    pin::Pin,
+    process::ExitStatus,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use futures_util::{FutureExt, StreamExt};
+use ratatui::layout::{Position, Rect};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender, error::TryRecvError},
+    task::JoinHandle,
+};
 use uuid::Uuid;
 
 use crate::{
-    api::VibeKanbanClient,
+    api::{
+        ApiError, VibeKanbanClient,
+        client::LogStream,
+        events::{self, ServerEvent},
+    },
+    batch::Batch,
+    clipboard,
+    config::{Config, TaskSort},
+    datetime::{self, TimestampStyle},
+    diff::{self, DiffComment, Hunk},
+    editor,
+    filter::TaskFilter,
+    fuzzy,
+    keymap::{self, Action},
+    links::{self, LinkTarget},
+    logs::{self, LogBookmark, LogEvent, ProcessLog},
+    metadata,
+    notifications::{NotificationLevel, Notifications, TOAST_DURATION},
+    prompt::TaskPrompt,
+    startup::{Startup, StartupPhase},
+    templates::FollowUpTemplate,
     types::*,
+    ui::theme::Theme,
+    utils::{
+        branch_name_problem, folder_name, parse_executor, resolve_repo_path, suggest_title,
+        task_slug,
+    },
 };
 
+/// How often follow mode checks for workspace activity
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How often the status bar's connection indicator checks the server
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Longest gap between the two clicks of a double click
+const DOUBLE_CLICK: Duration = Duration::from_millis(500);
+
+/// How long a deleted task can be brought back with `u`
+const UNDO_WINDOW: Duration = Duration::from_secs(60);
+
+/// Deleted tasks kept for undo, newest last
+const MAX_UNDO: usize = 10;
+
+/// Branches offered at once while typing a base branch
+const MAX_BRANCH_COMPLETIONS: usize = 8;
+
+/// Executors offered when the server's profiles can't be loaded
+const FALLBACK_EXECUTORS: [BaseCodingAgent; 9] = [
+    BaseCodingAgent::CursorAgent,
+    BaseCodingAgent::ClaudeCode,
+    BaseCodingAgent::Gemini,
+    BaseCodingAgent::Codex,
+    BaseCodingAgent::Opencode,
+    BaseCodingAgent::QwenCode,
+    BaseCodingAgent::Amp,
+    BaseCodingAgent::Copilot,
+    BaseCodingAgent::Droid,
+];
+
 /// View modes for the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum View {
     #[default]
     Projects,
     Tasks,
+    TaskDetail,
     Workspaces,
     WorkspaceDetail,
     CreateTask,
+    CreateProject,
     CreateAttempt,
+    FollowUp,
+    Queue,
+    Failures,
+    Trash,
+    TaskTree,
+    PlannerSettings,
+    RetentionSettings,
+    SessionLogs,
+    Diff,
     Help,
 }
 
@@ -29,532 +114,7319 @@ pub enum InputMode {
     Editing,
 }
 
-/// Task column in the kanban board
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TaskColumn {
-    Todo,
-    InProgress,
-    InReview,
-    Done,
+/// Everything known about the last failed action, shown in the error detail popup
+#[derive(Debug, Clone)]
+pub struct ErrorDetail {
+    /// Full error chain, outermost context first
+    pub message: String,
+    pub status: Option<u16>,
+    pub error_data: Option<serde_json::Value>,
+    pub next_steps: Vec<&'static str>,
 }
 
-impl TaskColumn {
-    pub fn status(&self) -> TaskStatus {
-        match self {
-            TaskColumn::Todo => TaskStatus::Todo,
-            TaskColumn::InProgress => TaskStatus::Inprogress,
-            TaskColumn::InReview => TaskStatus::Inreview,
-            TaskColumn::Done => TaskStatus::Done,
+impl ErrorDetail {
+    pub fn from_message(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            status: None,
+            error_data: None,
+            next_steps: Vec::new(),
         }
     }
 
-    pub fn next(&self) -> Self {
-        match self {
-            TaskColumn::Todo => TaskColumn::InProgress,
-            TaskColumn::InProgress => TaskColumn::InReview,
-            TaskColumn::InReview => TaskColumn::Done,
-            TaskColumn::Done => TaskColumn::Done,
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        let api_error = err.chain().find_map(|e| e.downcast_ref::<ApiError>());
+        let unreachable = err
+            .chain()
+            .filter_map(|e| e.downcast_ref::<reqwest::Error>())
+            .any(|e| e.is_connect() || e.is_timeout());
+
+        Self {
+            message: err
+                .chain()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join("\n  caused by: "),
+            status: api_error.and_then(|e| e.status),
+            error_data: api_error.and_then(|e| e.error_data.clone()),
+            next_steps: next_steps(api_error, unreachable),
         }
     }
 
-    pub fn prev(&self) -> Self {
-        match self {
-            TaskColumn::Todo => TaskColumn::Todo,
-            TaskColumn::InProgress => TaskColumn::Todo,
-            TaskColumn::InReview => TaskColumn::InProgress,
-            TaskColumn::Done => TaskColumn::InReview,
+    /// Whether the error is a merge or rebase stopping on conflicts.
+    pub fn is_conflict(&self) -> bool {
+        self.error_data
+            .as_ref()
+            .and_then(|data| data.get("type")?.as_str())
+            == Some("merge_conflicts")
+    }
+
+    /// Plain text version for the clipboard.
+    pub fn to_plain_text(&self) -> String {
+        let mut text = self.message.clone();
+        if let Some(status) = self.status {
+            text.push_str(&format!("\nHTTP status: {}", status));
+        }
+        if let Some(ref data) = self.error_data {
+            let data = serde_json::to_string_pretty(data).unwrap_or_else(|_| data.to_string());
+            text.push_str(&format!("\nError data: {}", data));
         }
+        text
     }
+}
 
-    pub fn title(&self) -> &'static str {
+/// Instructions for the coding agent to resolve the conflicts of the repos in
+/// `statuses` that a rebase stopped on; `None` when there are none.
+fn conflict_resolution_prompt(statuses: &[RepoBranchStatus]) -> Option<String> {
+    let conflicted: Vec<&RepoBranchStatus> = statuses
+        .iter()
+        .filter(|s| s.status.is_rebase_in_progress || !s.status.conflicted_files.is_empty())
+        .collect();
+    if conflicted.is_empty() {
+        return None;
+    }
+
+    let mut prompt = String::from(
+        "Rebasing this branch onto its target branch stopped on conflicts. Resolve them:\n",
+    );
+    for status in conflicted {
+        let op = status
+            .status
+            .conflict_op
+            .as_deref()
+            .unwrap_or("rebase")
+            .replace('_', "-");
+        prompt.push_str(&format!(
+            "\nIn {} (onto {}):\n",
+            status.repo_name, status.status.target_branch_name
+        ));
+        for file in &status.status.conflicted_files {
+            prompt.push_str(&format!("- {}\n", file));
+        }
+        prompt.push_str(&format!(
+            "Then stage the files and run `GIT_EDITOR=true git {} --continue`, resolving \
+             any further conflicts the same way until it finishes.\n",
+            op
+        ));
+    }
+    prompt.push_str(
+        "\nKeep the intent of both sides in each file. Don't abort the rebase or drop \
+         either side's changes. When you are done, `git status` should show no \
+         operation in progress.",
+    );
+    Some(prompt)
+}
+
+fn next_steps(api_error: Option<&ApiError>, unreachable: bool) -> Vec<&'static str> {
+    if unreachable {
+        return vec!["Check that the server is running and reachable at the --server URL"];
+    }
+    let Some(api_error) = api_error else {
+        return Vec::new();
+    };
+
+    match api_error.error_type() {
+        Some("merge_conflicts") => {
+            return vec![
+                "Resolve the conflicts in the workspace, then retry",
+                "Or have the coding agent resolve them (v)",
+                "Or rebase onto the latest target branch (b)",
+            ];
+        }
+        Some("rebase_in_progress") => {
+            return vec!["Finish or abort the rebase in the workspace before retrying"];
+        }
+        Some("force_push_required") => {
+            return vec!["The remote branch has diverged; force push if that is intended"];
+        }
+        Some("branch_policy_violation") => {
+            return vec![
+                "The suggested name has been filled in; edit it or submit again",
+                "See the rules with `vibe-kanban-cli branch-policy show`",
+            ];
+        }
+        _ => {}
+    }
+
+    match api_error.status {
+        Some(400 | 422) => vec!["Check the values you entered and try again"],
+        Some(403) => vec![
+            "The server refused the action",
+            "For budget limits, see `vibe-kanban-cli budget show`",
+        ],
+        Some(404) => vec!["The item may have been deleted; press r to refresh"],
+        Some(409) => vec!["The item changed on the server; press r to refresh and try again"],
+        Some(500..=599) => vec!["The server failed; check its log for details"],
+        _ => vec!["Press r to refresh and try again"],
+    }
+}
+
+/// A link on screen, labelled so it can be opened from the keyboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkHint {
+    pub label: String,
+    /// Where the link was found, e.g. "Task description"
+    pub source: String,
+    pub url: String,
+}
+
+/// A task deleted from the board, kept so `u` can bring it back
+#[derive(Debug, Clone)]
+struct DeletedTask {
+    task: Task,
+    /// In the server's trash, so undo restores it rather than creating it again
+    trashed: bool,
+    deleted_at: Instant,
+    /// Reused if re-creating the task has to be retried
+    idempotency_key: String,
+}
+
+/// What a row of the task relationship tree stands for
+#[derive(Debug, Clone)]
+pub enum TaskTreeNode {
+    /// The task owning the workspace the task was created from
+    Parent(Task),
+    Task(Task),
+    Workspace(Workspace),
+    /// A task created from the workspace above it
+    Child(Task),
+}
+
+impl TaskTreeNode {
+    /// Where Enter on the row goes
+    fn link(&self) -> LinkTarget {
         match self {
-            TaskColumn::Todo => "To Do",
-            TaskColumn::InProgress => "In Progress",
-            TaskColumn::InReview => "In Review",
-            TaskColumn::Done => "Done",
+            TaskTreeNode::Parent(task) | TaskTreeNode::Task(task) | TaskTreeNode::Child(task) => {
+                LinkTarget::Task {
+                    project_id: task.project_id,
+                    task_id: task.id,
+                }
+            }
+            TaskTreeNode::Workspace(workspace) => LinkTarget::Workspace(workspace.id),
         }
     }
 }
 
-/// Main application state
-pub struct App {
-    /// API client
-    pub client: VibeKanbanClient,
-    /// Current view
-    pub view: View,
-    /// Previous view (for back navigation)
-    pub previous_view: Option<View>,
-    /// Input mode for text editing
-    pub input_mode: InputMode,
-    /// Whether the app should quit
-    pub should_quit: bool,
-    /// Status message to display
-    pub status_message: Option<String>,
-    /// Error message to display
-    pub error_message: Option<String>,
+/// A row of the task relationship tree, with the branch lines drawn before it
+#[derive(Debug, Clone)]
+pub struct TaskTreeRow {
+    pub prefix: String,
+    pub node: TaskTreeNode,
+}
 
-    // Projects
-    pub projects: Vec<Project>,
-    pub selected_project_index: usize,
-    pub selected_project: Option<Project>,
+/// The rows of the tree of `task`: its parent, the task, its workspaces and the
+/// tasks created from each of them.
+fn task_tree_rows(
+    task: Task,
+    parent: Option<Task>,
+    relationships: Vec<TaskRelationships>,
+) -> Vec<TaskTreeRow> {
+    let mut rows = Vec::new();
+    let indent = match parent {
+        Some(parent) => {
+            rows.push(TaskTreeRow {
+                prefix: String::new(),
+                node: TaskTreeNode::Parent(parent),
+            });
+            // The only branch of the parent shown
+            rows.push(TaskTreeRow {
+                prefix: "└─ ".to_string(),
+                node: TaskTreeNode::Task(task),
+            });
+            "   "
+        }
+        None => {
+            rows.push(TaskTreeRow {
+                prefix: String::new(),
+                node: TaskTreeNode::Task(task),
+            });
+            ""
+        }
+    };
+    let workspaces = relationships.len();
+    for (i, relationship) in relationships.into_iter().enumerate() {
+        let last = i + 1 == workspaces;
+        rows.push(TaskTreeRow {
+            prefix: format!("{}{}", indent, if last { "└─ " } else { "├─ " }),
+            node: TaskTreeNode::Workspace(relationship.current_workspace),
+        });
+        let indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+        let children = relationship.children.len();
+        for (j, child) in relationship.children.into_iter().enumerate() {
+            let branch = if j + 1 == children { "└─ " } else { "├─ " };
+            rows.push(TaskTreeRow {
+                prefix: format!("{}{}", indent, branch),
+                node: TaskTreeNode::Child(child),
+            });
+        }
+    }
+    rows
+}
 
-    // Tasks
-    pub tasks: Vec<TaskWithAttemptStatus>,
-    pub selected_column: TaskColumn,
-    pub selected_task_indices: [usize; 4], // Index for each column
-    pub selected_task: Option<TaskWithAttemptStatus>,
+/// A workspace being renamed in the rename popup
+#[derive(Debug, Clone)]
+pub struct WorkspaceRename {
+    pub workspace_id: Uuid,
+    pub branch: String,
+    pub name: String,
+}
 
-    // Workspaces
-    pub workspaces: Vec<Workspace>,
-    pub selected_workspace_index: usize,
-    pub selected_workspace: Option<Workspace>,
-    pub workspace_repos: Vec<RepoWithTargetBranch>,
-    pub branch_statuses: Vec<RepoBranchStatus>,
+/// A workspace's notes being edited in the notes popup
+#[derive(Debug, Clone)]
+pub struct WorkspaceNotesEditor {
+    pub workspace_id: Uuid,
+    pub branch: String,
+    pub body: String,
+}
 
-    // Project repositories
-    pub project_repos: Vec<Repo>,
+/// A task's metadata being inspected and edited in the metadata popup
+#[derive(Debug, Clone)]
+pub struct MetadataEditor {
+    pub task_id: Uuid,
+    pub title: String,
+    /// The keys and values as they will be saved, in order
+    pub entries: Vec<(String, serde_json::Value)>,
+    /// Index of the highlighted key
+    pub selected: usize,
+    /// The key being added or changed, if any
+    pub entry: Option<MetadataEntry>,
+    /// Whether `entries` differ from the saved metadata
+    pub modified: bool,
+    /// Why the entry can't be kept, shown until it is edited again
+    pub problem: Option<String>,
+}
 
-    // Sessions
-    pub sessions: Vec<Session>,
+/// A key of the metadata and its value, typed as JSON
+#[derive(Debug, Clone)]
+pub struct MetadataEntry {
+    /// Index of the key changed, or `None` for a new key
+    pub index: Option<usize>,
+    pub key: String,
+    pub value: String,
+    /// Whether the value is being typed, else the key
+    pub editing_value: bool,
+}
 
-    // Create task form
-    pub new_task_title: String,
-    pub new_task_description: String,
+/// The branch a workspace is being rebased onto, typed in the rebase popup
+#[derive(Debug, Clone)]
+pub struct RebaseForm {
+    pub workspace_id: Uuid,
+    pub branch: String,
+    /// (repo_id, repo name, target branch) of each repo of the workspace
+    pub repos: Vec<(Uuid, String, String)>,
+    pub repo_index: usize,
+    pub new_base: String,
+}
 
-    // Follow-up input
-    pub follow_up_input: String,
+/// The `:` command palette: the query typed so far and the selected command
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub query: String,
+    /// Index into `palette_matches`
+    pub index: usize,
+}
 
-    // Create attempt form
-    pub attempt_executor_index: usize,
-    pub attempt_variant: Option<String>,
-    pub attempt_repo_branches: Vec<(Uuid, String)>, // (repo_id, branch_name)
-    pub attempt_selected_field: usize, // 0=executor, 1=variant, 2+=repo branches
-    pub repo_branches_cache: Vec<(Uuid, Vec<crate::types::GitBranch>)>, // (repo_id, branches)
+/// Field of the pull request popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullRequestField {
+    Title,
+    Body,
+    /// Only for workspaces with several repos
+    Repo,
+    Draft,
 }
 
-impl App {
-    /// Create a new application with the given API client.
-    pub fn new(client: VibeKanbanClient) -> Self {
-        Self {
-            client,
-            view: View::Projects,
-            previous_view: None,
-            input_mode: InputMode::Normal,
-            should_quit: false,
-            status_message: None,
-            error_message: None,
+impl PullRequestField {
+    pub fn next(self, several_repos: bool) -> Self {
+        match self {
+            PullRequestField::Title => PullRequestField::Body,
+            PullRequestField::Body if several_repos => PullRequestField::Repo,
+            PullRequestField::Body | PullRequestField::Repo => PullRequestField::Draft,
+            PullRequestField::Draft => PullRequestField::Title,
+        }
+    }
+}
 
-            projects: Vec::new(),
-            selected_project_index: 0,
-            selected_project: None,
+/// A pull request being written in the pull request popup, prefilled from the
+/// workspace's task
+#[derive(Debug, Clone)]
+pub struct PullRequestForm {
+    pub workspace_id: Uuid,
+    pub branch: String,
+    /// (repo_id, repo name, target branch) of each repo of the workspace
+    pub repos: Vec<(Uuid, String, String)>,
+    pub repo_index: usize,
+    pub title: String,
+    pub body: String,
+    pub draft: bool,
+    pub field: PullRequestField,
+}
 
-            tasks: Vec::new(),
-            selected_column: TaskColumn::Todo,
-            selected_task_indices: [0; 4],
-            selected_task: None,
+/// A git operation that runs in one of a workspace's repos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOperationKind {
+    Merge,
+    Push,
+    Rebase,
+}
 
-            workspaces: Vec::new(),
-            selected_workspace_index: 0,
-            selected_workspace: None,
-            workspace_repos: Vec::new(),
-            branch_statuses: Vec::new(),
+impl GitOperationKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            GitOperationKind::Merge => "Merge",
+            GitOperationKind::Push => "Push",
+            GitOperationKind::Rebase => "Rebase",
+        }
+    }
+}
 
-            project_repos: Vec::new(),
+/// The repo list a merge, push or rebase asks for when the workspace has several
+/// repos; the row after the repos runs it in all of them
+#[derive(Debug, Clone, Copy)]
+pub struct RepoPicker {
+    pub operation: GitOperationKind,
+    pub index: usize,
+}
 
-            sessions: Vec::new(),
+/// A coding agent run resolving the conflicts a rebase of `branch` stopped on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictResolution {
+    pub workspace_id: Uuid,
+    pub branch: String,
+    pub session_id: Uuid,
+    pub process_id: Uuid,
+}
 
-            new_task_title: String::new(),
-            new_task_description: String::new(),
+/// The address a dev server process printed it listens on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevServerUrl {
+    pub process_id: Uuid,
+    pub url: String,
+}
 
-            follow_up_input: String::new(),
+/// How the agent's run ended, and the branch status after it
+#[derive(Debug)]
+struct ConflictOutcome {
+    agent_status: ExecutionProcessStatus,
+    statuses: Vec<RepoBranchStatus>,
+}
 
-            attempt_executor_index: 0,
-            attempt_variant: None,
-            attempt_repo_branches: Vec::new(),
-            attempt_selected_field: 0,
-            repo_branches_cache: Vec::new(),
+/// A destructive action waiting to be confirmed in the confirmation modal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingAction {
+    DeleteTask {
+        task_id: Uuid,
+        title: String,
+    },
+    /// A task in the trash, deleted for good
+    PurgeTask {
+        task_id: Uuid,
+        title: String,
+    },
+    StopWorkspace {
+        workspace_id: Uuid,
+        branch: String,
+    },
+    /// The failed coding agent run of `branch`'s workspace, run again
+    RetryAttempt {
+        workspace_id: Uuid,
+        branch: String,
+    },
+    /// The conflicts a rebase of `branch` stopped on, handed to the coding agent
+    ResolveConflicts {
+        workspace_id: Uuid,
+        branch: String,
+        conflicted_files: usize,
+    },
+    /// One process of `branch`'s workspace, leaving the others running
+    StopProcess {
+        process_id: Uuid,
+        run_reason: String,
+        branch: String,
+    },
+    /// A merge, push or rebase of `branch`, shown with the server's dry run of it
+    /// when the server could produce one
+    GitOperation {
+        action: Box<RetryableAction>,
+        branch: String,
+        target_branch: String,
+        preview: Option<GitOperationPreview>,
+    },
+    /// A merge, push or rebase the server wants confirmed because it writes to a
+    /// protected branch; `action` goes ahead regardless
+    ProtectedBranch {
+        action: Box<RetryableAction>,
+        branch: String,
+        pattern: String,
+    },
+    /// A merge, push or rebase of `branch` in each repo, (repo_id, repo name,
+    /// target branch), one after another
+    AllRepos {
+        operation: GitOperationKind,
+        workspace_id: Uuid,
+        branch: String,
+        repos: Vec<(Uuid, String, String)>,
+    },
+}
+
+impl PendingAction {
+    /// Question shown in the confirmation modal.
+    pub fn prompt(&self) -> String {
+        match self {
+            PendingAction::DeleteTask { title, .. } => {
+                format!("Delete task \"{}\"? u brings it back for a minute.", title)
+            }
+            PendingAction::PurgeTask { title, .. } => format!(
+                "Delete \"{}\" for good? Its workspaces and worktrees go with it.",
+                title
+            ),
+            PendingAction::StopWorkspace { branch, .. } => {
+                format!("Stop the running processes of {}?", branch)
+            }
+            PendingAction::StopProcess {
+                run_reason, branch, ..
+            } => format!("Stop the {} process of {}?", run_reason, branch),
+            PendingAction::RetryAttempt { branch, .. } => format!(
+                "Run the coding agent of {} again? Its worktree is reset to before the failed run.",
+                branch
+            ),
+            PendingAction::ResolveConflicts {
+                branch,
+                conflicted_files: 0,
+                ..
+            } => format!("Have the coding agent finish the rebase of {}?", branch),
+            PendingAction::ResolveConflicts {
+                branch,
+                conflicted_files,
+                ..
+            } => format!(
+                "Have the coding agent resolve the {} conflicted files of {} and finish the rebase?",
+                conflicted_files, branch
+            ),
+            PendingAction::GitOperation {
+                action,
+                branch,
+                target_branch,
+                ..
+            } => match &**action {
+                RetryableAction::Merge { .. } => {
+                    format!("Merge {} into {}?", branch, target_branch)
+                }
+                RetryableAction::Push { .. } => format!("Push {}?", branch),
+                RetryableAction::Rebase {
+                    new_base: Some(new_base),
+                    ..
+                } if new_base != target_branch => format!(
+                    "Rebase {} onto {}, making it the target branch instead of {}?",
+                    branch, new_base, target_branch
+                ),
+                _ => format!("Rebase {} onto {}?", branch, target_branch),
+            },
+            PendingAction::ProtectedBranch {
+                action,
+                branch,
+                pattern,
+            } => {
+                let what = match **action {
+                    RetryableAction::Merge { .. } => "Merge into it",
+                    RetryableAction::Push { .. } => "Push it",
+                    _ => "Rebase it",
+                };
+                format!(
+                    "{} is a protected branch (matches {}). {} anyway?",
+                    branch, pattern, what
+                )
+            }
+            PendingAction::AllRepos {
+                operation,
+                branch,
+                repos,
+                ..
+            } => match operation {
+                GitOperationKind::Merge => format!(
+                    "Merge {} into the target branches of {} repos?",
+                    branch,
+                    repos.len()
+                ),
+                GitOperationKind::Push => format!("Push {} in {} repos?", branch, repos.len()),
+                GitOperationKind::Rebase => format!(
+                    "Rebase {} onto the target branches of {} repos?",
+                    branch,
+                    repos.len()
+                ),
+            },
+        }
+    }
+
+    /// What the server expects the confirmed action to do.
+    pub fn preview(&self) -> Option<&GitOperationPreview> {
+        match self {
+            PendingAction::GitOperation { preview, .. } => preview.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// A project or task the Ctrl+P jumper can jump straight to
+#[derive(Debug, Clone)]
+pub enum JumpTarget {
+    Project(Project),
+    Task {
+        project: Project,
+        task: Box<TaskWithAttemptStatus>,
+    },
+}
+
+impl JumpTarget {
+    pub fn project(&self) -> &Project {
+        match self {
+            JumpTarget::Project(project) | JumpTarget::Task { project, .. } => project,
         }
     }
 
-    /// Set a status message.
-    pub fn set_status(&mut self, message: impl Into<String>) {
-        self.status_message = Some(message.into());
-        self.error_message = None;
+    /// Text the jumper query is matched against; tasks include their project
+    /// name so e.g. `cli login` narrows to login tasks in the CLI project.
+    pub fn search_text(&self) -> String {
+        match self {
+            JumpTarget::Project(project) => project.name.clone(),
+            JumpTarget::Task { project, task } => format!("{} {}", project.name, task.task.title),
+        }
+    }
+}
+
+/// An API operation that can be re-issued, with the same parameters, after it fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryableAction {
+    LoadProjects,
+    LoadTasks {
+        project_id: Uuid,
+    },
+    LoadProjectRepos {
+        project_id: Uuid,
+    },
+    LoadTaskDetail {
+        task_id: Uuid,
+    },
+    LoadWorkspaces {
+        task_id: Uuid,
+    },
+    LoadWorkspaceDetails {
+        workspace_id: Uuid,
+    },
+    LoadAttemptQueue,
+    LoadFailures {
+        project_id: Option<Uuid>,
+        category: Option<FailureCategory>,
+    },
+    LoadTrash {
+        project_id: Uuid,
+    },
+    LoadTaskTree {
+        task_id: Uuid,
+    },
+    LoadExecutionProcesses {
+        session_id: Uuid,
+    },
+    LoadDiff {
+        workspace_id: Uuid,
+    },
+    UpdateTaskStatus {
+        task_id: Uuid,
+        status: TaskStatus,
+    },
+    UpdateTaskText {
+        task_id: Uuid,
+        title: String,
+        description: String,
+    },
+    UpdateTaskMetadata {
+        task_id: Uuid,
+        metadata: String,
+    },
+    DeleteTask {
+        task_id: Uuid,
+    },
+    RestoreTask {
+        task_id: Uuid,
+    },
+    PurgeTask {
+        task_id: Uuid,
+    },
+    RenameWorkspace {
+        workspace_id: Uuid,
+        name: String,
+    },
+    SaveWorkspaceNote {
+        workspace_id: Uuid,
+        body: String,
+    },
+    StopWorkspace {
+        workspace_id: Uuid,
+    },
+    StopExecutionProcess {
+        process_id: Uuid,
+    },
+    StartDevServer {
+        workspace_id: Uuid,
+    },
+    /// Run the workspace's failed coding agent run again with the same prompt
+    RetryAttempt {
+        workspace_id: Uuid,
+    },
+    /// Send the coding agent a follow-up to resolve the conflicts a rebase
+    /// stopped on, and continue it
+    ResolveConflicts {
+        workspace_id: Uuid,
+        branch: String,
+    },
+    RestartContainer {
+        workspace_id: Uuid,
+    },
+    RebuildContainer {
+        workspace_id: Uuid,
+    },
+    Merge {
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        idempotency_key: String,
+        confirm_protected: bool,
+    },
+    Push {
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        confirm_protected: bool,
+    },
+    Rebase {
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        /// Branch to rebase onto, making it the target branch; the current
+        /// target branch if unset
+        new_base: Option<String>,
+        confirm_protected: bool,
+    },
+}
+
+impl RetryableAction {
+    /// Whether the action only reads data.
+    pub fn is_load(&self) -> bool {
+        matches!(
+            self,
+            RetryableAction::LoadProjects
+                | RetryableAction::LoadTasks { .. }
+                | RetryableAction::LoadProjectRepos { .. }
+                | RetryableAction::LoadTaskDetail { .. }
+                | RetryableAction::LoadWorkspaces { .. }
+                | RetryableAction::LoadWorkspaceDetails { .. }
+                | RetryableAction::LoadAttemptQueue
+                | RetryableAction::LoadFailures { .. }
+                | RetryableAction::LoadTrash { .. }
+                | RetryableAction::LoadTaskTree { .. }
+                | RetryableAction::LoadExecutionProcesses { .. }
+                | RetryableAction::LoadDiff { .. }
+        )
+    }
+
+    /// The same merge, push or rebase, going ahead even if its branch is protected.
+    /// The confirmation changes the request body, so a merge is sent under a fresh
+    /// `idempotency_key`; the server rejects a key reused with a different body.
+    fn with_protection_confirmed(mut self, idempotency_key: String) -> Self {
+        if let RetryableAction::Merge {
+            idempotency_key: key,
+            ..
+        } = &mut self
+        {
+            *key = idempotency_key;
+        }
+        if let RetryableAction::Merge {
+            confirm_protected, ..
+        }
+        | RetryableAction::Push {
+            confirm_protected, ..
+        }
+        | RetryableAction::Rebase {
+            confirm_protected, ..
+        } = &mut self
+        {
+            *confirm_protected = true;
+        }
+        self
+    }
+
+    /// Carry out the action. Only needs the client, so it can run on its own task.
+    async fn run(&self, client: &VibeKanbanClient) -> Result<Option<Loaded>> {
+        if let Some(loaded) = Loaded::fetch(client, self).await? {
+            return Ok(Some(loaded));
+        }
+        match *self {
+            RetryableAction::UpdateTaskStatus { task_id, status } => {
+                let payload = UpdateTask {
+                    title: None,
+                    description: None,
+                    status: Some(status),
+                    parent_workspace_id: None,
+                    image_ids: None,
+                    is_epic: None,
+                    complexity: None,
+                    metadata: None,
+                };
+                client.update_task(task_id, &payload).await?;
+            }
+            RetryableAction::UpdateTaskText {
+                task_id,
+                ref title,
+                ref description,
+            } => {
+                // An empty description clears it
+                let payload = UpdateTask {
+                    title: Some(title.clone()),
+                    description: Some(description.clone()),
+                    status: None,
+                    parent_workspace_id: None,
+                    image_ids: None,
+                    is_epic: None,
+                    complexity: None,
+                    metadata: None,
+                };
+                let task = client.update_task(task_id, &payload).await?;
+                return Ok(Some(Loaded::Task(Box::new(task))));
+            }
+            RetryableAction::UpdateTaskMetadata {
+                task_id,
+                ref metadata,
+            } => {
+                let payload = UpdateTask {
+                    title: None,
+                    description: None,
+                    status: None,
+                    parent_workspace_id: None,
+                    image_ids: None,
+                    is_epic: None,
+                    complexity: None,
+                    metadata: Some(metadata.clone()),
+                };
+                let task = client.update_task(task_id, &payload).await?;
+                return Ok(Some(Loaded::Task(Box::new(task))));
+            }
+            RetryableAction::DeleteTask { task_id } => {
+                let trashed = client.delete_task(task_id).await?;
+                return Ok(trashed.map(|task| Loaded::Trashed(Box::new(task))));
+            }
+            RetryableAction::RestoreTask { task_id } => {
+                let task = client.restore_task(task_id).await?;
+                return Ok(Some(Loaded::Task(Box::new(task))));
+            }
+            RetryableAction::PurgeTask { task_id } => {
+                client.delete_task_permanently(task_id).await?;
+            }
+            RetryableAction::RenameWorkspace {
+                workspace_id,
+                ref name,
+            } => {
+                // An empty name clears it
+                let payload = UpdateWorkspace {
+                    name: Some(name.clone()),
+                    ..UpdateWorkspace::default()
+                };
+                let workspace = client.update_workspace(workspace_id, &payload).await?;
+                return Ok(Some(Loaded::Workspace(Box::new(workspace))));
+            }
+            RetryableAction::SaveWorkspaceNote {
+                workspace_id,
+                ref body,
+            } => {
+                let payload = SetWorkspaceNote { body: body.clone() };
+                let note = client.set_workspace_note(workspace_id, &payload).await?;
+                return Ok(Some(Loaded::WorkspaceNote { workspace_id, note }));
+            }
+            RetryableAction::StopWorkspace { workspace_id } => {
+                client.stop_workspace(workspace_id).await?;
+            }
+            RetryableAction::StopExecutionProcess { process_id } => {
+                client.stop_execution_process(process_id).await?;
+            }
+            RetryableAction::StartDevServer { workspace_id } => {
+                client.start_dev_server(workspace_id).await?;
+            }
+            RetryableAction::RetryAttempt { workspace_id } => {
+                let mut processes = Vec::new();
+                for session in client.list_sessions(workspace_id).await? {
+                    processes.extend(client.list_execution_processes(session.id).await?);
+                }
+                let failed = processes
+                    .into_iter()
+                    .filter(|p| !p.dropped && p.run_reason == "codingagent")
+                    .max_by(|a, b| a.started_at.cmp(&b.started_at))
+                    .filter(|p| p.status == ExecutionProcessStatus::Failed)
+                    .ok_or_else(|| anyhow!("The last coding agent run didn't fail"))?;
+                let (prompt, executor_profile_id) = failed
+                    .agent_request()
+                    .ok_or_else(|| anyhow!("The server didn't send the failed run's prompt"))?;
+                // The server resets the worktree to before the failed run and
+                // drops it from the history
+                let payload = CreateFollowUpAttempt {
+                    prompt,
+                    executor_profile_id,
+                    retry_process_id: Some(failed.id),
+                    force_when_dirty: None,
+                    perform_git_reset: None,
+                };
+                client.send_follow_up(failed.session_id, &payload).await?;
+            }
+            RetryableAction::ResolveConflicts {
+                workspace_id,
+                ref branch,
+            } => {
+                let statuses = client.get_branch_status(workspace_id).await?;
+                let prompt = conflict_resolution_prompt(&statuses)
+                    .ok_or_else(|| anyhow!("{} has no conflicts to resolve", branch))?;
+                let mut processes = Vec::new();
+                for session in client.list_sessions(workspace_id).await? {
+                    processes.extend(client.list_execution_processes(session.id).await?);
+                }
+                // The agent that made the changes is the one that knows them
+                let (session_id, executor_profile_id) = processes
+                    .into_iter()
+                    .filter(|p| !p.dropped && p.run_reason == "codingagent")
+                    .max_by(|a, b| a.started_at.cmp(&b.started_at))
+                    .and_then(|p| Some((p.session_id, p.agent_request()?.1)))
+                    .ok_or_else(|| anyhow!("The workspace has no coding agent session"))?;
+                let payload = CreateFollowUpAttempt {
+                    prompt,
+                    executor_profile_id,
+                    retry_process_id: None,
+                    force_when_dirty: None,
+                    perform_git_reset: None,
+                };
+                let process = client.send_follow_up(session_id, &payload).await?;
+                return Ok(Some(Loaded::ConflictResolution(ConflictResolution {
+                    workspace_id,
+                    branch: branch.clone(),
+                    session_id,
+                    process_id: process.id,
+                })));
+            }
+            RetryableAction::RestartContainer { workspace_id } => {
+                client.restart_container(workspace_id).await?;
+            }
+            RetryableAction::RebuildContainer { workspace_id } => {
+                client.rebuild_container(workspace_id).await?;
+            }
+            RetryableAction::Merge {
+                workspace_id,
+                repo_id,
+                ref idempotency_key,
+                confirm_protected,
+            } => {
+                client
+                    .merge_workspace(workspace_id, repo_id, idempotency_key, confirm_protected)
+                    .await?;
+            }
+            RetryableAction::Push {
+                workspace_id,
+                repo_id,
+                confirm_protected,
+            } => {
+                client
+                    .push_workspace(workspace_id, repo_id, confirm_protected)
+                    .await?;
+            }
+            RetryableAction::Rebase {
+                workspace_id,
+                repo_id,
+                ref new_base,
+                confirm_protected,
+            } => {
+                client
+                    .rebase_workspace(
+                        workspace_id,
+                        repo_id,
+                        None,
+                        new_base.clone(),
+                        confirm_protected,
+                    )
+                    .await?;
+            }
+            // Fetched above
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Status shown while the action runs.
+    pub fn progress_message(&self) -> &'static str {
+        match self {
+            RetryableAction::LoadProjects => "Loading projects...",
+            RetryableAction::LoadTasks { .. } => "Loading tasks...",
+            RetryableAction::LoadProjectRepos { .. } => "Loading repositories...",
+            RetryableAction::LoadTaskDetail { .. } => "Loading task...",
+            RetryableAction::LoadWorkspaces { .. } => "Loading workspaces...",
+            RetryableAction::LoadWorkspaceDetails { .. } => "Loading workspace details...",
+            RetryableAction::LoadAttemptQueue => "Loading queue...",
+            RetryableAction::LoadFailures { .. } => "Loading failures...",
+            RetryableAction::LoadTrash { .. } => "Loading trash...",
+            RetryableAction::LoadTaskTree { .. } => "Loading related tasks...",
+            RetryableAction::LoadExecutionProcesses { .. } => "Loading processes...",
+            RetryableAction::LoadDiff { .. } => "Loading diff...",
+            RetryableAction::UpdateTaskStatus { .. }
+            | RetryableAction::UpdateTaskText { .. }
+            | RetryableAction::UpdateTaskMetadata { .. } => "Updating task...",
+            RetryableAction::DeleteTask { .. } | RetryableAction::PurgeTask { .. } => {
+                "Deleting task..."
+            }
+            RetryableAction::RestoreTask { .. } => "Restoring task...",
+            RetryableAction::RenameWorkspace { .. } => "Renaming workspace...",
+            RetryableAction::SaveWorkspaceNote { .. } => "Saving notes...",
+            RetryableAction::StopWorkspace { .. } => "Stopping workspace...",
+            RetryableAction::StopExecutionProcess { .. } => "Stopping process...",
+            RetryableAction::StartDevServer { .. } => "Starting dev server...",
+            RetryableAction::RetryAttempt { .. } => "Retrying the attempt...",
+            RetryableAction::ResolveConflicts { .. } => "Starting the coding agent...",
+            RetryableAction::RestartContainer { .. } => "Restarting container...",
+            RetryableAction::RebuildContainer { .. } => "Rebuilding container...",
+            RetryableAction::Merge { .. } => "Merging...",
+            RetryableAction::Push { .. } => "Pushing...",
+            RetryableAction::Rebase { .. } => "Rebasing...",
+        }
+    }
+}
+
+/// Data fetched by a load action, or returned by a change, ready to be merged
+/// into the app state
+#[derive(Debug)]
+enum Loaded {
+    Projects(Vec<Project>),
+    Tasks {
+        tasks: Vec<TaskWithAttemptStatus>,
+        epic_workspaces: HashMap<Uuid, Uuid>,
+        orphaned_executions: Vec<ExecutionProcessOrphan>,
+    },
+    ProjectRepos(Vec<Repo>),
+    TaskDetail {
+        task: Box<Task>,
+        workspaces: Vec<Workspace>,
+        parent: Option<Task>,
+        children: Vec<Task>,
+        notes: Vec<WorkspaceNote>,
+        results: Vec<WorkspaceResult>,
+    },
+    Workspaces {
+        workspaces: Vec<Workspace>,
+        attempt_queue: Vec<QueuedTeamTask>,
+        container_statuses: Vec<WorkspaceContainerStatus>,
+        workspace_progress: Vec<SessionProgress>,
+        failures: Vec<WorkspaceFailure>,
+        results: Vec<WorkspaceResult>,
+    },
+    WorkspaceDetails {
+        repos: Vec<RepoWithTargetBranch>,
+        branch_statuses: Vec<RepoBranchStatus>,
+        sessions: Vec<Session>,
+        /// Processes of all the sessions, newest first
+        processes: Vec<ExecutionProcess>,
+        limits: Option<WorkspaceResourceLimits>,
+        auto_rebase: Option<WorkspaceAutoRebase>,
+        note: Option<WorkspaceNote>,
+        commits: Vec<RepoCommits>,
+    },
+    AttemptQueue(Vec<QueuedTeamTask>),
+    Failures(Vec<WorkspaceFailure>),
+    Trash(Vec<TrashedTask>),
+    TaskTree {
+        task: Box<Task>,
+        parent: Option<Task>,
+        relationships: Vec<TaskRelationships>,
+    },
+    /// A task the server moved to the trash
+    Trashed(Box<TrashedTask>),
+    ExecutionProcesses(Vec<ExecutionProcess>),
+    Diff(Vec<Diff>),
+    /// A task as saved by the server
+    Task(Box<Task>),
+    /// A workspace as saved by the server
+    Workspace(Box<Workspace>),
+    /// A workspace's notes as saved by the server; `None` once removed
+    WorkspaceNote {
+        workspace_id: Uuid,
+        note: Option<WorkspaceNote>,
+    },
+    /// A coding agent run started to resolve rebase conflicts
+    ConflictResolution(ConflictResolution),
+}
+
+impl Loaded {
+    /// Fetch the data of a load action; `None` for actions that change something.
+    ///
+    /// Only needs the client, so it can run on a background task.
+    async fn fetch(client: &VibeKanbanClient, action: &RetryableAction) -> Result<Option<Self>> {
+        let loaded = match *action {
+            RetryableAction::LoadProjects => Loaded::Projects(client.list_projects().await?),
+            RetryableAction::LoadTasks { project_id } => {
+                let tasks = client.list_tasks(project_id).await?;
+                let mut epic_workspaces = HashMap::new();
+                for epic in tasks.iter().filter(|t| t.task.is_epic) {
+                    for workspace in client.list_workspaces(Some(epic.task.id)).await? {
+                        epic_workspaces.insert(workspace.id, epic.task.id);
+                    }
+                }
+                Loaded::Tasks {
+                    tasks,
+                    epic_workspaces,
+                    // Best effort; older servers don't report orphaned processes
+                    orphaned_executions: client
+                        .list_orphaned_executions(Some(project_id), 100)
+                        .await
+                        .unwrap_or_default(),
+                }
+            }
+            RetryableAction::LoadProjectRepos { project_id } => {
+                Loaded::ProjectRepos(client.get_project_repositories(project_id).await?)
+            }
+            RetryableAction::LoadTaskDetail { task_id } => {
+                let task = client.get_task(task_id).await?;
+                let workspaces = client.list_workspaces(Some(task_id)).await?;
+                let parent = match task.parent_workspace_id {
+                    Some(workspace_id) => {
+                        let parent_workspace = client.get_workspace(workspace_id).await?;
+                        Some(client.get_task(parent_workspace.task_id).await?)
+                    }
+                    None => None,
+                };
+                let mut children = Vec::new();
+                let mut notes = Vec::new();
+                for workspace in &workspaces {
+                    let relationships = client.get_workspace_relationships(workspace.id).await?;
+                    children.extend(relationships.children);
+                    // Older servers keep no notes
+                    if let Ok(Some(note)) = client.get_workspace_note(workspace.id).await {
+                        notes.push(note);
+                    }
+                }
+                Loaded::TaskDetail {
+                    task: Box::new(task),
+                    workspaces,
+                    parent,
+                    children,
+                    notes,
+                    // Older servers keep no results
+                    results: client
+                        .list_workspace_results(task_id)
+                        .await
+                        .unwrap_or_default(),
+                }
+            }
+            RetryableAction::LoadWorkspaces { task_id } => Loaded::Workspaces {
+                workspaces: client.list_workspaces(Some(task_id)).await?,
+                // Queue badges are best effort; older servers have no team routes
+                attempt_queue: client.get_attempt_queue().await.unwrap_or_default(),
+                container_statuses: client
+                    .list_container_statuses(task_id)
+                    .await
+                    .unwrap_or_default(),
+                // Older servers have no progress reports
+                workspace_progress: client
+                    .list_workspace_progress(task_id)
+                    .await
+                    .unwrap_or_default(),
+                // Older servers don't classify failures
+                failures: client
+                    .list_workspace_failures(None, Some(task_id), None)
+                    .await
+                    .unwrap_or_default(),
+                // Older servers keep no results
+                results: client
+                    .list_workspace_results(task_id)
+                    .await
+                    .unwrap_or_default(),
+            },
+            RetryableAction::LoadWorkspaceDetails { workspace_id } => {
+                let sessions = client.list_sessions(workspace_id).await?;
+                let mut processes = Vec::new();
+                for session in &sessions {
+                    processes.extend(client.list_execution_processes(session.id).await?);
+                }
+                processes.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+                Loaded::WorkspaceDetails {
+                    repos: client.get_workspace_repos(workspace_id).await?,
+                    branch_statuses: client.get_branch_status(workspace_id).await?,
+                    sessions,
+                    processes,
+                    // Older servers have no resource limits
+                    limits: client
+                        .get_workspace_resource_limits(workspace_id)
+                        .await
+                        .unwrap_or_default(),
+                    // Older servers don't rebase workspaces on their own
+                    auto_rebase: client
+                        .get_workspace_auto_rebase(workspace_id)
+                        .await
+                        .unwrap_or_default(),
+                    // Older servers keep no notes
+                    note: client
+                        .get_workspace_note(workspace_id)
+                        .await
+                        .unwrap_or_default(),
+                    // Older servers don't list commits
+                    commits: client
+                        .get_workspace_commits(workspace_id)
+                        .await
+                        .unwrap_or_default(),
+                }
+            }
+            RetryableAction::LoadAttemptQueue => {
+                Loaded::AttemptQueue(client.get_attempt_queue().await?)
+            }
+            RetryableAction::LoadFailures {
+                project_id,
+                category,
+            } => Loaded::Failures(
+                client
+                    .list_workspace_failures(project_id, None, category)
+                    .await?,
+            ),
+            RetryableAction::LoadTrash { project_id } => {
+                Loaded::Trash(client.list_trash(project_id).await?)
+            }
+            RetryableAction::LoadTaskTree { task_id } => {
+                let task = client.get_task(task_id).await?;
+                let relationships = client.get_task_relationships(task_id).await?;
+                // Each workspace reports the same parent; without one it is looked up
+                let parent = match relationships.first() {
+                    Some(relationships) => relationships.parent_task.clone(),
+                    None => match task.parent_workspace_id {
+                        Some(workspace_id) => {
+                            let parent_workspace = client.get_workspace(workspace_id).await?;
+                            Some(client.get_task(parent_workspace.task_id).await?)
+                        }
+                        None => None,
+                    },
+                };
+                Loaded::TaskTree {
+                    task: Box::new(task),
+                    parent,
+                    relationships,
+                }
+            }
+            RetryableAction::LoadExecutionProcesses { session_id } => {
+                Loaded::ExecutionProcesses(client.list_execution_processes(session_id).await?)
+            }
+            RetryableAction::LoadDiff { workspace_id } => {
+                Loaded::Diff(client.get_workspace_diff(workspace_id).await?)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(loaded))
+    }
+}
+
+/// The result of a request that ran on its own task, applied to the state by the
+/// event loop, see [`App::spawn_request`]
+type Reply = Box<dyn FnOnce(&mut App) -> Result<()> + Send>;
+
+/// The request a step of a batch makes, see [`App::start_batch`]
+pub type BatchWork = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// A reload of the current view running on a background task
+struct BackgroundRefresh {
+    action: RetryableAction,
+    handle: JoinHandle<Result<Option<Loaded>>>,
+}
+
+/// Focused field in the task detail editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskFormField {
+    #[default]
+    Title,
+    Description,
+}
+
+impl TaskFormField {
+    pub fn next(&self) -> Self {
+        match self {
+            TaskFormField::Title => TaskFormField::Description,
+            TaskFormField::Description => TaskFormField::Title,
+        }
+    }
+}
+
+/// Focused field of a task's title and description form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NewTaskField {
+    #[default]
+    Title,
+    Description,
+    /// Whether to start an attempt on the task as soon as it is created
+    Start,
+    /// The rest only while starting it
+    Executor,
+    /// Base branch of the repository at this index of `attempt_repo_branches`
+    RepoBranch(usize),
+}
+
+impl NewTaskField {
+    pub fn is_text(self) -> bool {
+        matches!(self, NewTaskField::Title | NewTaskField::Description)
+    }
+}
+
+/// Focused field in the create project form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectFormField {
+    #[default]
+    Name,
+    /// Path of the repository entry at this index
+    RepoPath(usize),
+    /// Display name of the repository entry at this index
+    RepoDisplayName(usize),
+}
+
+/// Focused field in the follow-up composer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FollowUpField {
+    #[default]
+    Prompt,
+    Executor,
+    Variant,
+}
+
+impl FollowUpField {
+    pub fn next(&self) -> Self {
+        match self {
+            FollowUpField::Prompt => FollowUpField::Executor,
+            FollowUpField::Executor => FollowUpField::Variant,
+            FollowUpField::Variant => FollowUpField::Prompt,
+        }
+    }
+}
+
+/// Field of the planner settings form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlannerField {
+    #[default]
+    TeamThreshold,
+    MaxSubtasks,
+    MaxParallelWorkers,
+}
+
+impl PlannerField {
+    pub const ALL: [PlannerField; 3] = [
+        PlannerField::TeamThreshold,
+        PlannerField::MaxSubtasks,
+        PlannerField::MaxParallelWorkers,
+    ];
+
+    pub fn next(&self) -> Self {
+        match self {
+            PlannerField::TeamThreshold => PlannerField::MaxSubtasks,
+            PlannerField::MaxSubtasks => PlannerField::MaxParallelWorkers,
+            PlannerField::MaxParallelWorkers => PlannerField::TeamThreshold,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        self.next().next()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlannerField::TeamThreshold => "Team threshold",
+            PlannerField::MaxSubtasks => "Max subtasks",
+            PlannerField::MaxParallelWorkers => "Max parallel workers",
+        }
+    }
+
+    /// What the limit does, shown below its input
+    pub fn description(&self) -> &'static str {
+        match self {
+            PlannerField::TeamThreshold => "Subtasks an epic needs before it runs as a team",
+            PlannerField::MaxSubtasks => "Subtasks the planner splits an epic into at most",
+            PlannerField::MaxParallelWorkers => "Workers per team execution unless it sets its own",
+        }
+    }
+}
+
+/// Field of the workspace retention settings form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetentionField {
+    #[default]
+    ArchiveMergedAfterDays,
+    DeleteCancelledWorktrees,
+    PurgeTrashAfterDays,
+}
+
+impl RetentionField {
+    pub const ALL: [RetentionField; 3] = [
+        RetentionField::ArchiveMergedAfterDays,
+        RetentionField::DeleteCancelledWorktrees,
+        RetentionField::PurgeTrashAfterDays,
+    ];
+
+    pub fn next(&self) -> Self {
+        match self {
+            RetentionField::ArchiveMergedAfterDays => RetentionField::DeleteCancelledWorktrees,
+            RetentionField::DeleteCancelledWorktrees => RetentionField::PurgeTrashAfterDays,
+            RetentionField::PurgeTrashAfterDays => RetentionField::ArchiveMergedAfterDays,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        match self {
+            RetentionField::ArchiveMergedAfterDays => RetentionField::PurgeTrashAfterDays,
+            RetentionField::DeleteCancelledWorktrees => RetentionField::ArchiveMergedAfterDays,
+            RetentionField::PurgeTrashAfterDays => RetentionField::DeleteCancelledWorktrees,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RetentionField::ArchiveMergedAfterDays => "Archive merged workspaces after (days)",
+            RetentionField::DeleteCancelledWorktrees => "Remove worktrees of cancelled tasks",
+            RetentionField::PurgeTrashAfterDays => "Empty the trash after (days)",
+        }
+    }
+
+    /// What the setting does, shown below its input
+    pub fn description(&self) -> &'static str {
+        match self {
+            RetentionField::ArchiveMergedAfterDays => {
+                "Once every repository is merged; leave empty to keep them"
+            }
+            RetentionField::DeleteCancelledWorktrees => {
+                "As soon as nothing runs in them, instead of after an hour idle"
+            }
+            RetentionField::PurgeTrashAfterDays => {
+                "Deletes tasks for good with their workspaces; leave empty to keep them"
+            }
+        }
+    }
+}
+
+/// A number of days typed into `field` of the retention form; empty is never.
+fn retention_days(text: &str, field: RetentionField) -> Result<Option<u32>, String> {
+    let days = text.trim();
+    if days.is_empty() {
+        return Ok(None);
+    }
+    match days.parse::<u32>() {
+        Ok(days) if days >= 1 => Ok(Some(days)),
+        _ => Err(format!(
+            "{} must be a number of at least 1, or empty",
+            field.label()
+        )),
+    }
+}
+
+/// How far the board is zoomed in, with `+` and `-`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoardZoom {
+    /// Every task of the project
+    #[default]
+    Tasks,
+    /// Only the project's epics, with the progress of their subtasks
+    Epics,
+    /// The subtasks of one epic
+    Epic(Uuid),
+}
+
+/// Board quick filter on the attempts of each task, stepped through with `f`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttemptFilter {
+    #[default]
+    All,
+    /// Tasks whose last attempt failed
+    Failed,
+    /// Tasks with an attempt in progress
+    Running,
+}
+
+impl AttemptFilter {
+    pub fn next(self) -> Self {
+        match self {
+            AttemptFilter::All => AttemptFilter::Failed,
+            AttemptFilter::Failed => AttemptFilter::Running,
+            AttemptFilter::Running => AttemptFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AttemptFilter::All => "all tasks",
+            AttemptFilter::Failed => "last attempt failed",
+            AttemptFilter::Running => "attempt running",
+        }
+    }
+
+    pub fn matches(self, task: &TaskWithAttemptStatus) -> bool {
+        match self {
+            AttemptFilter::All => true,
+            AttemptFilter::Failed => task.last_attempt_failed,
+            AttemptFilter::Running => task.has_in_progress_attempt,
+        }
+    }
+}
+
+/// Task column in the kanban board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskColumn {
+    Todo,
+    InProgress,
+    InReview,
+    Done,
+    /// Only on the board while toggled on with `c`
+    Cancelled,
+}
+
+impl TaskColumn {
+    pub const ALL: [TaskColumn; 5] = [
+        TaskColumn::Todo,
+        TaskColumn::InProgress,
+        TaskColumn::InReview,
+        TaskColumn::Done,
+        TaskColumn::Cancelled,
+    ];
+
+    /// Position of the column on the board, from the left
+    pub fn index(&self) -> usize {
+        match self {
+            TaskColumn::Todo => 0,
+            TaskColumn::InProgress => 1,
+            TaskColumn::InReview => 2,
+            TaskColumn::Done => 3,
+            TaskColumn::Cancelled => 4,
+        }
+    }
+
+    pub fn status(&self) -> TaskStatus {
+        match self {
+            TaskColumn::Todo => TaskStatus::Todo,
+            TaskColumn::InProgress => TaskStatus::Inprogress,
+            TaskColumn::InReview => TaskStatus::Inreview,
+            TaskColumn::Done => TaskStatus::Done,
+            TaskColumn::Cancelled => TaskStatus::Cancelled,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            TaskColumn::Todo => TaskColumn::InProgress,
+            TaskColumn::InProgress => TaskColumn::InReview,
+            TaskColumn::InReview => TaskColumn::Done,
+            TaskColumn::Done => TaskColumn::Done,
+            TaskColumn::Cancelled => TaskColumn::Cancelled,
+        }
+    }
+
+    /// The column a task moved back goes to; cancelled tasks are restored to To Do
+    pub fn prev(&self) -> Self {
+        match self {
+            TaskColumn::Todo => TaskColumn::Todo,
+            TaskColumn::InProgress => TaskColumn::Todo,
+            TaskColumn::InReview => TaskColumn::InProgress,
+            TaskColumn::Done => TaskColumn::InReview,
+            TaskColumn::Cancelled => TaskColumn::Todo,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            TaskColumn::Todo => "To Do",
+            TaskColumn::InProgress => "In Progress",
+            TaskColumn::InReview => "In Review",
+            TaskColumn::Done => "Done",
+            TaskColumn::Cancelled => "Cancelled",
+        }
+    }
+}
+
+/// Main application state
+pub struct App {
+    /// API client
+    pub client: VibeKanbanClient,
+    /// Settings from the config file
+    pub config: Config,
+    /// Current view
+    pub view: View,
+    /// Views to return to (for back navigation)
+    pub view_stack: Vec<View>,
+    /// Input mode for text editing
+    pub input_mode: InputMode,
+    /// Whether the app should quit
+    pub should_quit: bool,
+    /// Status message to display
+    pub status_message: Option<String>,
+    /// Error message to display
+    pub error_message: Option<String>,
+    /// Every status and error message this session
+    pub notifications: Notifications,
+    /// Whether the notification center is open
+    pub show_notifications: bool,
+    /// Notifications scrolled past in the notification center
+    pub notifications_scroll: usize,
+    /// Details of the last error, for the drill-down popup
+    pub error_detail: Option<ErrorDetail>,
+    /// Whether the error detail popup is open
+    pub show_error_detail: bool,
+    /// Last failed operation, re-issued with `R`
+    pub failed_action: Option<RetryableAction>,
+    /// Idempotency key for the open create form, reused if a submit is retried
+    pub submit_key: Option<String>,
+    /// Labelled links while link hint mode is open
+    pub link_hints: Vec<LinkHint>,
+    /// Label typed so far in link hint mode
+    pub link_hint_input: String,
+    /// Destructive action awaiting a y/n answer in the confirmation modal
+    pub pending_action: Option<PendingAction>,
+    /// Multi-step operation in progress, or finished with failures until dismissed
+    pub batch: Option<Batch<BatchWork>>,
+    /// Whether the Ctrl+P jumper is open
+    pub show_jumper: bool,
+    /// Whether the request metrics overlay is open
+    pub show_metrics: bool,
+    /// Every project and task, loaded when the jumper opens
+    pub jump_targets: Vec<JumpTarget>,
+    pub jump_query: String,
+    /// Index into `jumper_matches`
+    pub jump_index: usize,
+    /// Theme to restore if the open theme picker is cancelled; `None` when closed
+    pub theme_picker: Option<Theme>,
+    /// Open while a command is being picked from the `:` palette
+    pub palette: Option<CommandPalette>,
+
+    // Projects
+    pub projects: Vec<Project>,
+    pub selected_project_index: usize,
+    /// First visible row of the project list; the view keeps the selection in sight
+    pub project_list_offset: Cell<usize>,
+    pub selected_project: Option<Project>,
+
+    // Tasks
+    pub tasks: Vec<TaskWithAttemptStatus>,
+    pub selected_column: TaskColumn,
+    pub selected_task_indices: [usize; 5], // Index for each column, into the filtered list
+    /// First visible row of each board column
+    pub board_offsets: [Cell<usize>; 5],
+    /// Where the board columns were last drawn, for mouse clicks
+    pub board_columns: Cell<[Rect; 5]>,
+    /// Whether the board shows a column of cancelled tasks after Done
+    pub show_cancelled: bool,
+    pub board_zoom: BoardZoom,
+    /// Epic task of each workspace of the project's epics; subtasks point at one
+    epic_workspaces: HashMap<Uuid, Uuid>,
+    /// Task and time of the last click on the board, to detect double clicks
+    last_board_click: Option<(Uuid, Instant)>,
+    pub selected_task: Option<TaskWithAttemptStatus>,
+    /// Board search query, see [`TaskFilter`]
+    pub task_filter: String,
+    /// Quick filters of the board, on top of the search
+    pub attempt_filter: AttemptFilter,
+    pub executor_filter: Option<String>,
+    /// Highlighted entry of the executor filter popup; 0 is every executor
+    pub executor_picker: Option<usize>,
+    /// Tasks marked on the board for moving or starting attempts together
+    pub marked_tasks: HashSet<Uuid>,
+    /// Recently deleted tasks, newest last
+    deleted_tasks: Vec<DeletedTask>,
+    pub orphaned_executions: Vec<ExecutionProcessOrphan>,
+
+    // Workspaces
+    pub workspaces: Vec<Workspace>,
+    pub selected_workspace_index: usize,
+    /// First visible row of the workspace list
+    pub workspace_list_offset: Cell<usize>,
+    pub selected_workspace: Option<Workspace>,
+    /// Open while a workspace is being renamed
+    pub workspace_rename: Option<WorkspaceRename>,
+    /// Open while a merge, push or rebase waits for a repo to be picked
+    pub repo_picker: Option<RepoPicker>,
+    /// Open while a pull request is being written
+    pub pull_request_form: Option<PullRequestForm>,
+    /// Open while the branch to rebase onto is being typed
+    pub rebase_form: Option<RebaseForm>,
+    /// Directory to open in the editor; the event loop hands the terminal over
+    /// to it once the key press has been handled
+    pub editor_request: Option<PathBuf>,
+    pub workspace_repos: Vec<RepoWithTargetBranch>,
+    pub branch_statuses: Vec<RepoBranchStatus>,
+    pub workspace_limits: Option<WorkspaceResourceLimits>,
+    pub workspace_auto_rebase: Option<WorkspaceAutoRebase>,
+    pub workspace_note: Option<WorkspaceNote>,
+    /// Processes of the workspace's sessions, newest first
+    pub workspace_processes: Vec<ExecutionProcess>,
+    /// The process `s` stops instead of the whole workspace, picked with ↑/↓
+    pub selected_process_index: Option<usize>,
+    pub process_list_offset: Cell<usize>,
+    pub workspace_notes_editor: Option<WorkspaceNotesEditor>,
+    /// Open while the metadata of the task in task detail is inspected or edited
+    pub metadata_editor: Option<MetadataEditor>,
+    /// Commits of the selected workspace branch ahead of each target branch
+    pub workspace_commits: Vec<RepoCommits>,
+    pub container_statuses: Vec<WorkspaceContainerStatus>,
+    pub workspace_progress: Vec<SessionProgress>,
+    pub workspace_failures: Vec<WorkspaceFailure>,
+    /// What the agents of the task's workspaces said they did
+    pub workspace_results: Vec<WorkspaceResult>,
+
+    // Project repositories
+    pub project_repos: Vec<Repo>,
+
+    // Sessions
+    pub sessions: Vec<Session>,
+
+    // Session logs: the processes of one session and the log of the one shown
+    pub log_processes: Vec<ExecutionProcess>,
+    pub log_process_index: usize,
+    pub process_log: ProcessLog,
+    /// Log lines hidden below the bottom of the pane; 0 follows new output
+    pub log_scroll: usize,
+    /// Largest useful `log_scroll`, where the first line is at the top of the pane.
+    /// Depends on the pane size, so it is updated while rendering.
+    pub log_max_scroll: Cell<usize>,
+    log_session_id: Option<Uuid>,
+    log_stream: Option<LogStream>,
+    /// Bookmarked log lines per session, kept for as long as the CLI runs
+    log_bookmarks: HashMap<Uuid, Vec<LogBookmark>>,
+    /// Bookmark last jumped to
+    pub log_bookmark_index: Option<usize>,
+    /// Note of the bookmark being added
+    pub log_bookmark_note: String,
+    /// Line to scroll to once the log being opened has loaded that far
+    log_jump_to: Option<usize>,
+
+    // Task detail, with the title and description being edited in place
+    pub task_parent: Option<Task>,
+    pub task_children: Vec<Task>,
+    /// Notes on the task's workspaces, for the prompt copied from task detail
+    pub task_workspace_notes: Vec<WorkspaceNote>,
+    pub task_edit_title: String,
+    pub task_edit_description: String,
+    pub task_edit_field: TaskFormField,
+
+    // Create task form
+    pub new_task_title: String,
+    /// Whether the title was suggested from the description and not yet edited
+    pub new_task_title_suggested: bool,
+    pub new_task_description: String,
+    pub new_task_field: NewTaskField,
+    /// Start an attempt with the executor and base branches of the attempt form
+    /// fields as the task is created
+    pub new_task_start: bool,
+    /// Workspace the new task is created from, as follow-on work of its attempt
+    pub new_task_parent: Option<Workspace>,
+
+    // Create project form
+    pub new_project_name: String,
+    pub new_project_repos: Vec<CreateProjectRepo>,
+    pub new_project_field: ProjectFormField,
+
+    // Follow-up composer
+    pub follow_up_input: String,
+    pub follow_up_field: FollowUpField,
+    pub follow_up_executor_index: usize,
+    pub follow_up_variant: Option<String>,
+    follow_up_session_id: Option<Uuid>,
+    /// Workspace whose review notes are in the follow-up, dropped once it is sent
+    follow_up_review: Option<Uuid>,
+    /// Selected entry of the template list, while it is open
+    pub template_picker: Option<usize>,
+
+    // Diff of the selected workspace, with review notes to send as a follow-up
+    pub diff_hunks: Vec<Hunk>,
+    pub selected_hunk_index: usize,
+    /// Review notes per workspace, kept until they are sent
+    diff_comments: HashMap<Uuid, Vec<DiffComment>>,
+    pub diff_comment_input: String,
+
+    // Create attempt form
+    pub attempt_executor_index: usize,
+    pub attempt_variant: Option<String>,
+    pub attempt_limits: String, // e.g. "runtime=30m mem=2G"
+    pub attempt_branch: String, // empty lets the server name the branch
+    /// Tasks to start an attempt for each of, (task_id, title); empty for the
+    /// selected task
+    pub attempt_tasks: Vec<(Uuid, String)>,
+    pub attempt_repo_branches: Vec<(Uuid, String)>, // (repo_id, branch_name)
+    pub attempt_selected_field: usize, // 0=executor, 1=variant, 2=limits, 3=branch, 4+=repo branches
+    /// Text typed into the focused base branch field, completed from the
+    /// repository's branches
+    pub attempt_branch_query: String,
+    /// Highlighted entry of [`App::attempt_branch_completions`]
+    pub attempt_branch_completion: usize,
+    pub repo_branches_cache: Vec<(Uuid, Vec<crate::types::GitBranch>)>, // (repo_id, branches)
+    pub executor_health: Vec<ExecutorHealth>,
+    pub executor_variants: Vec<ExecutorVariant>,
+
+    // Team tasks waiting for a worker slot
+    pub attempt_queue: Vec<QueuedTeamTask>,
+    pub selected_queue_index: usize,
+    pub queue_list_offset: Cell<usize>,
+
+    // Workspaces whose latest attempt failed, in the project they were opened
+    // from (or every project), narrowed to one category
+    pub failures: Vec<WorkspaceFailure>,
+    pub failures_project_id: Option<Uuid>,
+    pub failure_filter: Option<FailureCategory>,
+    pub selected_failure_index: usize,
+    pub failure_list_offset: Cell<usize>,
+
+    // Tasks of the selected project in the server's trash
+    pub trash: Vec<TrashedTask>,
+    pub selected_trash_index: usize,
+    pub trash_list_offset: Cell<usize>,
+
+    // Relationship tree of one task, as rows in the order shown
+    pub task_tree_task_id: Option<Uuid>,
+    pub task_tree: Vec<TaskTreeRow>,
+    pub selected_tree_index: usize,
+    pub task_tree_offset: Cell<usize>,
+
+    // Planner settings form, one input per field in `PlannerField` order
+    pub planner_inputs: [String; 3],
+    pub planner_field: PlannerField,
+
+    // Workspace retention form, and what the policy as edited would do right now
+    pub retention_archive_days: String,
+    pub retention_delete_cancelled: bool,
+    pub retention_purge_days: String,
+    pub retention_field: RetentionField,
+    pub retention_preview: Vec<RetentionCandidate>,
+    /// The policy `retention_preview` was computed for
+    retention_previewed: Option<RetentionPolicy>,
+
+    // Follow mode: jump to the most recently active workspace in the project
+    pub follow_mode: bool,
+    pub workspace_activity: Vec<WorkspaceActivity>,
+    last_follow_poll: Option<Instant>,
+    followed_activity_at: Option<DateTime<Utc>>,
+
+    // Periodic reload of the current view, see `Config::refresh_interval`
+    last_auto_refresh: Option<Instant>,
+    background_refresh: Option<BackgroundRefresh>,
+    /// Reload the current view on the next tick, whatever the interval
+    refresh_requested: bool,
+    /// When data from the server was last merged into the view
+    pub last_refreshed_at: Option<DateTime<Utc>>,
+
+    // Connection indicator in the status bar
+    /// Whether the server answered the last health check; `None` before the first
+    pub server_reachable: Option<bool>,
+    last_health_check: Option<Instant>,
+    health_check: Option<JoinHandle<bool>>,
+
+    // Rebase conflicts the coding agent is resolving, reported on once it stops
+    pub conflict_resolution: Option<ConflictResolution>,
+    last_conflict_check: Option<Instant>,
+    conflict_check: Option<JoinHandle<Result<Option<ConflictOutcome>>>>,
+
+    // Address of the shown workspace's dev server, read from its output
+    pub dev_server_url: Option<DevServerUrl>,
+    last_dev_server_lookup: Option<Instant>,
+    dev_server_lookup: Option<JoinHandle<Result<Option<DevServerUrl>>>>,
+
+    // Startup profiling
+    pub startup: Startup,
+    /// Whether `--fast-start` still holds back the event stream, the health
+    /// check and the board's repositories
+    startup_deferred: bool,
+
+    // Live updates from the server's event stream
+    server_events: Option<UnboundedReceiver<ServerEvent>>,
+    pub live_updates: bool,
+
+    // Requests running on their own tasks, so rendering never waits on the server
+    reply_sender: UnboundedSender<(u64, Reply)>,
+    replies: UnboundedReceiver<(u64, Reply)>,
+    /// Requests in flight and what each is doing, oldest first
+    requests: Vec<(u64, &'static str)>,
+    /// When the requests in flight started, for the spinner
+    pub loading_since: Option<Instant>,
+    /// Latest request for each kind of load; replies to earlier ones are dropped
+    latest_loads: HashMap<Discriminant<RetryableAction>, u64>,
+    next_request_id: u64,
+}
+
+impl App {
+    /// Create a new application with the given API client.
+    pub fn new(client: VibeKanbanClient) -> Self {
+        let (reply_sender, replies) = mpsc::unbounded_channel();
+        Self {
+            client,
+            config: Config::default(),
+            view: View::Projects,
+            view_stack: Vec::new(),
+            input_mode: InputMode::Normal,
+            should_quit: false,
+            status_message: None,
+            error_message: None,
+            notifications: Notifications::default(),
+            show_notifications: false,
+            notifications_scroll: 0,
+            error_detail: None,
+            show_error_detail: false,
+            failed_action: None,
+            submit_key: None,
+            link_hints: Vec::new(),
+            link_hint_input: String::new(),
+            pending_action: None,
+            batch: None,
+            show_jumper: false,
+            show_metrics: false,
+            jump_targets: Vec::new(),
+            jump_query: String::new(),
+            jump_index: 0,
+            theme_picker: None,
+            palette: None,
+
+            projects: Vec::new(),
+            selected_project_index: 0,
+            project_list_offset: Cell::new(0),
+            selected_project: None,
+
+            tasks: Vec::new(),
+            selected_column: TaskColumn::Todo,
+            selected_task_indices: [0; 5],
+            board_offsets: Default::default(),
+            board_columns: Cell::new([Rect::default(); 5]),
+            show_cancelled: false,
+            board_zoom: BoardZoom::default(),
+            epic_workspaces: HashMap::new(),
+            last_board_click: None,
+            selected_task: None,
+            task_filter: String::new(),
+            attempt_filter: AttemptFilter::All,
+            executor_filter: None,
+            executor_picker: None,
+            marked_tasks: HashSet::new(),
+            deleted_tasks: Vec::new(),
+            orphaned_executions: Vec::new(),
+
+            workspaces: Vec::new(),
+            selected_workspace_index: 0,
+            workspace_list_offset: Cell::new(0),
+            selected_workspace: None,
+            workspace_rename: None,
+            repo_picker: None,
+            pull_request_form: None,
+            rebase_form: None,
+            editor_request: None,
+            workspace_repos: Vec::new(),
+            workspace_limits: None,
+            workspace_auto_rebase: None,
+            workspace_note: None,
+            workspace_processes: Vec::new(),
+            selected_process_index: None,
+            process_list_offset: Cell::new(0),
+            workspace_notes_editor: None,
+            metadata_editor: None,
+            workspace_commits: Vec::new(),
+            container_statuses: Vec::new(),
+            workspace_progress: Vec::new(),
+            workspace_failures: Vec::new(),
+            workspace_results: Vec::new(),
+            branch_statuses: Vec::new(),
+
+            project_repos: Vec::new(),
+
+            sessions: Vec::new(),
+
+            log_processes: Vec::new(),
+            log_process_index: 0,
+            process_log: ProcessLog::default(),
+            log_scroll: 0,
+            log_max_scroll: Cell::new(0),
+            log_session_id: None,
+            log_stream: None,
+            log_bookmarks: HashMap::new(),
+            log_bookmark_index: None,
+            log_bookmark_note: String::new(),
+            log_jump_to: None,
+
+            task_parent: None,
+            task_children: Vec::new(),
+            task_workspace_notes: Vec::new(),
+            task_edit_title: String::new(),
+            task_edit_description: String::new(),
+            task_edit_field: TaskFormField::Title,
+
+            new_task_title: String::new(),
+            new_task_title_suggested: false,
+            new_task_description: String::new(),
+            new_task_field: NewTaskField::Title,
+            new_task_start: false,
+            new_task_parent: None,
+
+            new_project_name: String::new(),
+            new_project_repos: Vec::new(),
+            new_project_field: ProjectFormField::Name,
+
+            follow_up_input: String::new(),
+            follow_up_field: FollowUpField::Prompt,
+            follow_up_executor_index: 0,
+            follow_up_variant: None,
+            follow_up_session_id: None,
+            follow_up_review: None,
+            template_picker: None,
+
+            diff_hunks: Vec::new(),
+            selected_hunk_index: 0,
+            diff_comments: HashMap::new(),
+            diff_comment_input: String::new(),
+
+            attempt_executor_index: 0,
+            attempt_variant: None,
+            attempt_limits: String::new(),
+            attempt_branch: String::new(),
+            attempt_tasks: Vec::new(),
+            attempt_repo_branches: Vec::new(),
+            attempt_selected_field: 0,
+            attempt_branch_query: String::new(),
+            attempt_branch_completion: 0,
+            repo_branches_cache: Vec::new(),
+            executor_health: Vec::new(),
+            executor_variants: Vec::new(),
+
+            attempt_queue: Vec::new(),
+            selected_queue_index: 0,
+            queue_list_offset: Cell::new(0),
+
+            failures: Vec::new(),
+            failures_project_id: None,
+            failure_filter: None,
+            selected_failure_index: 0,
+            failure_list_offset: Cell::new(0),
+
+            trash: Vec::new(),
+            selected_trash_index: 0,
+            trash_list_offset: Cell::new(0),
+
+            task_tree_task_id: None,
+            task_tree: Vec::new(),
+            selected_tree_index: 0,
+            task_tree_offset: Cell::new(0),
+
+            planner_inputs: Default::default(),
+            planner_field: PlannerField::TeamThreshold,
+
+            retention_archive_days: String::new(),
+            retention_delete_cancelled: false,
+            retention_purge_days: String::new(),
+            retention_field: RetentionField::ArchiveMergedAfterDays,
+            retention_preview: Vec::new(),
+            retention_previewed: None,
+
+            follow_mode: false,
+            workspace_activity: Vec::new(),
+            last_follow_poll: None,
+            followed_activity_at: None,
+
+            last_auto_refresh: None,
+            background_refresh: None,
+            last_refreshed_at: None,
+            server_reachable: None,
+            last_health_check: None,
+            health_check: None,
+            conflict_resolution: None,
+            last_conflict_check: None,
+            conflict_check: None,
+            dev_server_url: None,
+            last_dev_server_lookup: None,
+            dev_server_lookup: None,
+            startup: Startup::default(),
+            startup_deferred: false,
+            refresh_requested: false,
+
+            server_events: None,
+            live_updates: false,
+
+            reply_sender,
+            replies,
+            requests: Vec::new(),
+            loading_since: None,
+            latest_loads: HashMap::new(),
+            next_request_id: 0,
+        }
+    }
+
+    /// Apply settings from the config file.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Time startup from `startup`, deferring loads if it is a fast start.
+    pub fn with_startup(mut self, startup: Startup) -> Self {
+        self.startup_deferred = startup.fast;
+        self.startup = startup;
+        self
+    }
+
+    /// Called after every frame drawn. Once the first requests have finished and
+    /// their result is on screen, a fast start catches up on what it held back.
+    pub fn frame_drawn(&mut self) {
+        self.startup.record(StartupPhase::FirstFrame);
+        if !self.startup_deferred || self.is_loading() {
+            return;
+        }
+        self.startup_deferred = false;
+        self.subscribe_to_server_events();
+        self.load_project_repos();
+    }
+
+    /// Set a status message.
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        self.notifications.push(NotificationLevel::Info, message.clone());
+        self.status_message = Some(message);
+        self.error_message = None;
+    }
+
+    /// Set an error message.
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        self.notifications.push(NotificationLevel::Error, message.clone());
+        self.error_detail = Some(ErrorDetail::from_message(message.clone()));
+        self.error_message = Some(message);
+        self.status_message = None;
+    }
+
+    /// Show a failed action, keeping the full error for the detail popup.
+    pub fn report_error(&mut self, err: &anyhow::Error) {
+        self.set_error(format!("{:#}", err));
+        self.error_detail = Some(ErrorDetail::from_error(err));
+    }
+
+    /// Clear status and error messages.
+    pub fn clear_messages(&mut self) {
+        self.status_message = None;
+        self.error_message = None;
+    }
+
+    /// Called on every tick of the event loop; lets status messages fade from the
+    /// status bar. Errors stay until the next action, as they offer a retry.
+    pub fn expire_status(&mut self) {
+        let expired = self
+            .notifications
+            .latest()
+            .is_none_or(|latest| latest.raised_at.elapsed() >= TOAST_DURATION);
+        if self.status_message.is_some() && expired {
+            self.status_message = None;
+        }
+    }
+
+    /// Open or close the notification center.
+    pub fn toggle_notifications(&mut self) {
+        self.show_notifications = !self.show_notifications;
+        self.notifications_scroll = 0;
+    }
+
+    /// Switch between times relative to now and dates.
+    pub fn toggle_timestamps(&mut self) {
+        self.config.timestamps = self.config.timestamps.toggle();
+        self.set_status(match self.config.timestamps {
+            TimestampStyle::Relative => "Showing relative times".to_string(),
+            TimestampStyle::Absolute => format!("Showing times in {}", self.config.time_zone),
+        });
+    }
+
+    /// `at` as the config asks: relative to now, or as a date in its time zone.
+    pub fn timestamp(&self, at: DateTime<Utc>) -> String {
+        match self.config.timestamps {
+            TimestampStyle::Relative => datetime::relative(at, self.config.time_zone),
+            TimestampStyle::Absolute => self.config.time_zone.format(at, "%Y-%m-%d %H:%M"),
+        }
+    }
+
+    /// Like [`App::timestamp`], for a time the server sent as a string; shown
+    /// as it is if it can't be parsed.
+    pub fn timestamp_str(&self, at: &str) -> String {
+        datetime::parse(at).map_or_else(|| at.to_string(), |at| self.timestamp(at))
+    }
+
+    pub fn scroll_notifications(&mut self, delta: isize) {
+        let max = self.notifications.len().saturating_sub(1);
+        self.notifications_scroll = self
+            .notifications_scroll
+            .saturating_add_signed(delta)
+            .min(max);
+    }
+
+    /// Go back to the previous view.
+    pub fn go_back(&mut self) {
+        if self.view == View::SessionLogs {
+            self.log_stream = None;
+        }
+        if let Some(prev) = self.view_stack.pop() {
+            self.view = prev;
+            self.failed_action = None;
+            self.submit_key = None;
+        }
+    }
+
+    /// Navigate to a new view.
+    pub fn navigate_to(&mut self, view: View) {
+        self.view_stack.push(self.view);
+        self.view = view;
+        self.failed_action = None;
+        self.submit_key = None;
+    }
+
+    // =========================================================================
+    // Retryable Operations
+    // =========================================================================
+
+    /// Run an API operation, remembering it for `retry_failed_action` if it fails.
+    pub fn perform(&mut self, action: RetryableAction) {
+        self.perform_then(action, |_, result| result);
+    }
+
+    /// Like [`App::perform`], handing the outcome to `then` once the result has
+    /// been merged into the state.
+    pub fn perform_then(
+        &mut self,
+        action: RetryableAction,
+        then: impl FnOnce(&mut App, Result<()>) -> Result<()> + Send + 'static,
+    ) {
+        // A reload that started earlier could bring back data older than this
+        self.cancel_background_refresh();
+        self.failed_action = None;
+        self.clear_messages();
+
+        let load_id = self.next_request_id();
+        if action.is_load() {
+            self.latest_loads
+                .insert(mem::discriminant(&action), load_id);
+        }
+
+        let client = self.client.clone();
+        let request = {
+            let action = action.clone();
+            async move { action.run(&client).await }
+        };
+        self.spawn_request(action.progress_message(), request, move |app, result| {
+            if action.is_load()
+                && app.latest_loads.get(&mem::discriminant(&action)) != Some(&load_id)
+            {
+                // Superseded, e.g. by the tasks of another project
+                return Ok(());
+            }
+            let result = match result {
+                Ok(loaded) => {
+                    if let Some(loaded) = loaded {
+                        app.apply_loaded(loaded);
+                    }
+                    Ok(())
+                }
+                Err(e) => {
+                    app.failed_action = Some(action);
+                    Err(e)
+                }
+            };
+            then(app, result)
+        });
+    }
+
+    /// Run a request on its own task so input and rendering carry on meanwhile,
+    /// with `progress` next to the spinner. `then` gets the result on the first
+    /// tick after it arrives.
+    fn spawn_request<T: Send + 'static>(
+        &mut self,
+        progress: &'static str,
+        request: impl Future<Output = T> + Send + 'static,
+        then: impl FnOnce(&mut App, T) -> Result<()> + Send + 'static,
+    ) {
+        let id = self.next_request_id();
+        self.requests.push((id, progress));
+        self.loading_since.get_or_insert_with(Instant::now);
+        let sender = self.reply_sender.clone();
+        tokio::spawn(async move {
+            let value = request.await;
+            let reply: Reply = Box::new(move |app| then(app, value));
+            // Only fails once the app has gone
+            let _ = sender.send((id, reply));
+        });
+    }
+
+    fn next_request_id(&mut self) -> u64 {
+        self.next_request_id += 1;
+        self.next_request_id
+    }
+
+    /// Called on every tick of the event loop; applies the replies to requests
+    /// that have finished since the last tick.
+    pub fn poll_replies(&mut self) {
+        while let Ok((id, reply)) = self.replies.try_recv() {
+            if let Err(e) = self.apply_reply(id, reply) {
+                self.report_error(&e);
+            }
+        }
+    }
+
+    /// Wait for every request in flight, including any their replies start.
+    /// For running without the event loop; stops at the first error.
+    pub async fn wait_for_requests(&mut self) -> Result<()> {
+        while self.is_loading() {
+            let Some((id, reply)) = self.replies.recv().await else {
+                break;
+            };
+            self.apply_reply(id, reply)?;
+        }
+        Ok(())
+    }
+
+    fn apply_reply(&mut self, id: u64, reply: Reply) -> Result<()> {
+        self.requests.retain(|(request_id, _)| *request_id != id);
+        if self.requests.is_empty() {
+            self.loading_since = None;
+        }
+        reply(self)
+    }
+
+    /// Whether any request is still waiting on the server.
+    pub fn is_loading(&self) -> bool {
+        !self.requests.is_empty()
+    }
+
+    /// What the most recent request still in flight is doing.
+    pub fn loading_message(&self) -> Option<&'static str> {
+        self.requests.last().map(|(_, progress)| *progress)
+    }
+
+    /// Idempotency key for submitting the open form, stable until the view changes.
+    fn submit_key(&mut self) -> String {
+        let client = &self.client;
+        self.submit_key
+            .get_or_insert_with(|| client.idempotency_key())
+            .clone()
+    }
+
+    /// Re-issue the last failed operation, then reload the current view.
+    pub fn retry_failed_action(&mut self) {
+        let Some(action) = self.failed_action.take() else {
+            return;
+        };
+        let is_load = action.is_load();
+        self.perform_then(action, move |app, result| {
+            result?;
+            if !is_load {
+                app.refresh();
+                app.set_status("Retry succeeded");
+            }
+            Ok(())
+        });
+    }
+
+    /// Merge freshly loaded data into the state, keeping the selections on the same
+    /// items where they still exist.
+    fn apply_loaded(&mut self, loaded: Loaded) {
+        self.last_refreshed_at = Some(Utc::now());
+        self.startup.record(StartupPhase::FirstData);
+        match loaded {
+            Loaded::Projects(projects) => {
+                let selected = self.projects.get(self.selected_project_index).map(|p| p.id);
+                self.projects = projects;
+                self.selected_project_index = selected
+                    .and_then(|id| self.projects.iter().position(|p| p.id == id))
+                    .unwrap_or(self.selected_project_index)
+                    .min(self.projects.len().saturating_sub(1));
+            }
+            Loaded::Tasks {
+                tasks,
+                epic_workspaces,
+                orphaned_executions,
+            } => {
+                self.epic_workspaces = epic_workspaces;
+                self.update_tasks(|current| *current = tasks);
+                self.orphaned_executions = orphaned_executions;
+            }
+            Loaded::ProjectRepos(repos) => self.project_repos = repos,
+            Loaded::TaskDetail {
+                task,
+                workspaces,
+                parent,
+                children,
+                notes,
+                results,
+            } => {
+                self.workspaces = workspaces;
+                self.task_parent = parent;
+                self.task_children = children;
+                self.task_workspace_notes = notes;
+                self.workspace_results = results;
+                self.replace_task(*task);
+            }
+            Loaded::Workspaces {
+                workspaces,
+                attempt_queue,
+                container_statuses,
+                workspace_progress,
+                failures,
+                results,
+            } => {
+                let selected = self
+                    .workspaces
+                    .get(self.selected_workspace_index)
+                    .map(|w| w.id);
+                self.workspaces = workspaces;
+                self.selected_workspace_index = selected
+                    .and_then(|id| self.workspaces.iter().position(|w| w.id == id))
+                    .unwrap_or(self.selected_workspace_index)
+                    .min(self.workspaces.len().saturating_sub(1));
+                self.attempt_queue = attempt_queue;
+                self.container_statuses = container_statuses;
+                self.workspace_progress = workspace_progress;
+                self.workspace_failures = failures;
+                self.workspace_results = results;
+            }
+            Loaded::WorkspaceDetails {
+                repos,
+                branch_statuses,
+                sessions,
+                processes,
+                limits,
+                auto_rebase,
+                note,
+                commits,
+            } => {
+                let selected = self.selected_process().map(|p| p.id);
+                self.selected_process_index =
+                    selected.and_then(|id| processes.iter().position(|p| p.id == id));
+                self.workspace_processes = processes;
+                self.workspace_repos = repos;
+                self.branch_statuses = branch_statuses;
+                self.sessions = sessions;
+                self.workspace_limits = limits;
+                self.workspace_auto_rebase = auto_rebase;
+                self.workspace_note = note;
+                self.workspace_commits = commits;
+            }
+            Loaded::AttemptQueue(queue) => {
+                self.attempt_queue = queue;
+                self.selected_queue_index = self
+                    .selected_queue_index
+                    .min(self.attempt_queue.len().saturating_sub(1));
+            }
+            Loaded::Failures(failures) => {
+                self.failures = failures;
+                self.selected_failure_index = self
+                    .selected_failure_index
+                    .min(self.failures.len().saturating_sub(1));
+            }
+            Loaded::Trash(trash) => {
+                self.trash = trash;
+                self.selected_trash_index = self
+                    .selected_trash_index
+                    .min(self.trash.len().saturating_sub(1));
+            }
+            Loaded::TaskTree {
+                task,
+                parent,
+                relationships,
+            } => {
+                self.task_tree = task_tree_rows(*task, parent, relationships);
+                self.selected_tree_index = self
+                    .selected_tree_index
+                    .min(self.task_tree.len().saturating_sub(1));
+            }
+            Loaded::Trashed(task) => {
+                self.trash.retain(|t| t.id != task.id);
+                self.trash.insert(0, *task);
+            }
+            Loaded::ExecutionProcesses(processes) => self.log_processes = processes,
+            Loaded::Diff(files) => {
+                self.diff_hunks = files.iter().flat_map(diff::hunks).collect();
+                self.selected_hunk_index = self
+                    .selected_hunk_index
+                    .min(self.diff_hunks.len().saturating_sub(1));
+            }
+            Loaded::Task(task) => self.replace_task(*task),
+            Loaded::Workspace(workspace) => self.replace_workspace(*workspace),
+            Loaded::ConflictResolution(resolution) => {
+                self.conflict_resolution = Some(resolution);
+                self.last_conflict_check = Some(Instant::now());
+            }
+            Loaded::WorkspaceNote { workspace_id, note } => {
+                if self.selected_workspace.as_ref().map(|w| w.id) == Some(workspace_id) {
+                    self.workspace_note = note.clone();
+                }
+                self.task_workspace_notes
+                    .retain(|n| n.workspace_id != workspace_id);
+                self.task_workspace_notes.extend(note);
+            }
+        }
+    }
+
+    // =========================================================================
+    // Data Loading
+    // =========================================================================
+
+    /// Load projects from the server.
+    pub fn load_projects(&mut self) {
+        self.perform(RetryableAction::LoadProjects);
+    }
+
+    /// Load tasks for the selected project.
+    pub fn load_tasks(&mut self) {
+        let project_id = self.selected_project.as_ref().map(|p| p.id);
+        if let Some(project_id) = project_id {
+            self.perform(RetryableAction::LoadTasks { project_id });
+        }
+    }
+
+    /// Load repositories for the selected project.
+    pub fn load_project_repos(&mut self) {
+        // Only the forms need them; a fast start loads them after the board
+        if self.startup_deferred {
+            return;
+        }
+        let project_id = self.selected_project.as_ref().map(|p| p.id);
+        if let Some(project_id) = project_id {
+            self.perform(RetryableAction::LoadProjectRepos { project_id });
+        }
+    }
+
+    /// Load workspaces for the selected task.
+    pub fn load_workspaces(&mut self) {
+        let task_id = self.selected_task.as_ref().map(|t| t.task.id);
+        if let Some(task_id) = task_id {
+            self.perform(RetryableAction::LoadWorkspaces { task_id });
+        }
+    }
+
+    /// Load team tasks waiting for a worker slot.
+    pub fn load_attempt_queue(&mut self) {
+        self.perform(RetryableAction::LoadAttemptQueue);
+    }
+
+    /// Load the failed workspaces for the failures view.
+    pub fn load_failures(&mut self) {
+        self.perform(RetryableAction::LoadFailures {
+            project_id: self.failures_project_id,
+            category: self.failure_filter,
+        });
+    }
+
+    /// Load the selected project's trash.
+    pub fn load_trash(&mut self) {
+        if let Some(project_id) = self.selected_project.as_ref().map(|p| p.id) {
+            self.perform(RetryableAction::LoadTrash { project_id });
+        }
+    }
+
+    /// Load the relationship tree of the task it was opened for.
+    pub fn load_task_tree(&mut self) {
+        if let Some(task_id) = self.task_tree_task_id {
+            self.perform(RetryableAction::LoadTaskTree { task_id });
+        }
+    }
+
+    /// Load details for the selected workspace.
+    pub fn load_workspace_details(&mut self) {
+        let workspace_id = self.selected_workspace.as_ref().map(|w| w.id);
+        if let Some(workspace_id) = workspace_id {
+            self.perform(RetryableAction::LoadWorkspaceDetails { workspace_id });
+        }
+    }
+
+    /// Reload the data shown in the current view.
+    pub fn refresh(&mut self) {
+        match self.view {
+            View::Projects => self.load_projects(),
+            View::Tasks => self.load_tasks(),
+            View::TaskDetail => self.load_task_detail(),
+            View::Workspaces => self.load_workspaces(),
+            View::WorkspaceDetail => self.load_workspace_details(),
+            View::Queue => self.load_attempt_queue(),
+            View::Failures => self.load_failures(),
+            View::Trash => self.load_trash(),
+            View::TaskTree => self.load_task_tree(),
+            View::SessionLogs => self.refresh_session_logs(),
+            View::Diff => self.load_diff(),
+            View::RetentionSettings => {
+                self.retention_previewed = None;
+                self.preview_retention_policy();
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the global queue view.
+    pub fn open_queue(&mut self) {
+        self.navigate_to(View::Queue);
+        self.load_attempt_queue();
+    }
+
+    /// Open the failed workspaces of the selected project, or of every project
+    /// from the project list.
+    pub fn open_failures(&mut self) {
+        self.failures_project_id = match self.view {
+            View::Projects => None,
+            _ => self.selected_project.as_ref().map(|p| p.id),
+        };
+        self.failures.clear();
+        self.selected_failure_index = 0;
+        self.navigate_to(View::Failures);
+        self.load_failures();
+    }
+
+    /// Show only the next failure category, or every category after the last.
+    pub fn cycle_failure_filter(&mut self) {
+        self.failure_filter = match self.failure_filter {
+            None => Some(FailureCategory::ALL[0]),
+            Some(category) => FailureCategory::ALL
+                .iter()
+                .position(|c| *c == category)
+                .and_then(|i| FailureCategory::ALL.get(i + 1))
+                .copied(),
+        };
+        self.selected_failure_index = 0;
+        self.load_failures();
+    }
+
+    /// Open the trash of the selected project.
+    pub fn open_trash(&mut self) {
+        self.trash.clear();
+        self.selected_trash_index = 0;
+        self.navigate_to(View::Trash);
+        self.load_trash();
+    }
+
+    /// Open the workspace of the highlighted failure.
+    pub fn open_selected_failure(&mut self) {
+        if let Some(failure) = self.failures.get(self.selected_failure_index) {
+            self.open_link(LinkTarget::Workspace(failure.workspace_id), false);
+        }
+    }
+
+    /// Current failure of a workspace, if its latest attempt failed.
+    pub fn failure_for(&self, workspace_id: Uuid) -> Option<&WorkspaceFailure> {
+        self.workspace_failures
+            .iter()
+            .find(|f| f.workspace_id == workspace_id)
+    }
+
+    /// What the agent of a workspace said it did in its latest completed run.
+    pub fn result_for(&self, workspace_id: Uuid) -> Option<&WorkspaceResult> {
+        self.workspace_results
+            .iter()
+            .find(|r| r.workspace_id == workspace_id)
+    }
+
+    /// Queue entry for a workspace that is waiting to be rerun.
+    pub fn queue_entry_for_workspace(&self, workspace_id: Uuid) -> Option<&QueuedTeamTask> {
+        self.attempt_queue
+            .iter()
+            .find(|q| q.workspace_id == Some(workspace_id))
+    }
+
+    /// Queue entry for a task that is waiting for its first workspace.
+    pub fn queue_entry_for_task(&self, task_id: Uuid) -> Option<&QueuedTeamTask> {
+        self.attempt_queue
+            .iter()
+            .find(|q| q.task_id == task_id && q.workspace_id.is_none())
+    }
+
+    // =========================================================================
+    // Project Actions
+    // =========================================================================
+
+    /// Select a project and navigate to tasks view.
+    pub fn select_project(&mut self) {
+        if let Some(project) = self.projects.get(self.selected_project_index).cloned() {
+            // Navigate first so a failed load can be retried from the new view
+            self.selected_project = Some(project);
+            self.reset_board_filters();
+            self.navigate_to(View::Tasks);
+            self.load_tasks();
+            self.load_project_repos();
+        }
+    }
+
+    // =========================================================================
+    // Task Actions
+    // =========================================================================
+
+    /// Get tasks filtered by status for a column, and by the board search query,
+    /// in the column's order.
+    pub fn tasks_for_column(&self, column: TaskColumn) -> Vec<&TaskWithAttemptStatus> {
+        let filter = TaskFilter::parse_with(&self.task_filter, &self.config.saved_filters);
+        let mut tasks: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|t| {
+                t.task.status == column.status()
+                    && filter.matches(t)
+                    && self.in_quick_filters(t)
+                    && self.in_board_zoom(t)
+            })
+            .collect();
+        match self.task_sort(column) {
+            TaskSort::Created => tasks.sort_by(|a, b| b.task.created_at.cmp(&a.task.created_at)),
+            TaskSort::Updated => tasks.sort_by(|a, b| b.task.updated_at.cmp(&a.task.updated_at)),
+            TaskSort::Title => tasks.sort_by_cached_key(|t| t.task.title.to_lowercase()),
+            TaskSort::Complexity => {
+                tasks.sort_by_key(|t| (t.task.complexity.is_none(), t.task.complexity))
+            }
+            TaskSort::Manual => {
+                tasks.sort_by(|a, b| b.task.created_at.cmp(&a.task.created_at));
+                tasks.sort_by_key(|t| (t.task.board_position.is_none(), t.task.board_position));
+            }
+        }
+        tasks
+    }
+
+    /// Whether `task` passes the board's quick filters.
+    fn in_quick_filters(&self, task: &TaskWithAttemptStatus) -> bool {
+        self.attempt_filter.matches(task)
+            && self
+                .executor_filter
+                .as_ref()
+                .is_none_or(|executor| task.executor == *executor)
+    }
+
+    pub fn has_quick_filters(&self) -> bool {
+        self.attempt_filter != AttemptFilter::All || self.executor_filter.is_some()
+    }
+
+    /// Show every task of a project freshly opened on the board.
+    fn reset_board_filters(&mut self) {
+        self.task_filter.clear();
+        self.attempt_filter = AttemptFilter::All;
+        self.executor_filter = None;
+        self.board_zoom = BoardZoom::Tasks;
+    }
+
+    /// Step the board from every task to those whose last attempt failed, to
+    /// those with an attempt running, and back.
+    pub fn cycle_attempt_filter(&mut self) {
+        self.attempt_filter = self.attempt_filter.next();
+        self.clamp_task_selection();
+        self.set_status(format!("Showing {}", self.attempt_filter.label()));
+    }
+
+    /// Executors the board's tasks last ran with, to filter the board by.
+    pub fn executor_filter_options(&self) -> Vec<&str> {
+        let mut executors: Vec<&str> = self
+            .tasks
+            .iter()
+            .map(|t| t.executor.as_str())
+            .filter(|executor| !executor.is_empty())
+            .collect();
+        executors.sort_unstable();
+        executors.dedup();
+        executors
+    }
+
+    /// Open the executor filter popup on the current filter.
+    pub fn open_executor_picker(&mut self) {
+        let options = self.executor_filter_options();
+        if options.is_empty() {
+            self.set_error("No task on the board has run with an executor yet");
+            return;
+        }
+        let index = self
+            .executor_filter
+            .as_deref()
+            .and_then(|current| options.iter().position(|e| *e == current))
+            .map_or(0, |i| i + 1);
+        self.executor_picker = Some(index);
+    }
+
+    pub fn executor_picker_move(&mut self, forward: bool) {
+        let count = self.executor_filter_options().len() + 1;
+        if let Some(index) = &mut self.executor_picker {
+            *index = if forward {
+                (*index + 1) % count
+            } else {
+                (*index + count - 1) % count
+            };
+        }
+    }
+
+    /// Close the popup, filtering the board by the highlighted executor if `keep`.
+    pub fn close_executor_picker(&mut self, keep: bool) {
+        let Some(index) = self.executor_picker.take() else {
+            return;
+        };
+        if !keep {
+            return;
+        }
+        self.executor_filter = index
+            .checked_sub(1)
+            .and_then(|i| self.executor_filter_options().get(i).map(|e| e.to_string()));
+        self.clamp_task_selection();
+    }
+
+    /// Drop the quick filters, showing every task the search matches.
+    pub fn clear_quick_filters(&mut self) {
+        self.attempt_filter = AttemptFilter::All;
+        self.executor_filter = None;
+        self.clamp_task_selection();
+    }
+
+    /// Whether the board at its zoom level shows `task`.
+    fn in_board_zoom(&self, task: &TaskWithAttemptStatus) -> bool {
+        match self.board_zoom {
+            BoardZoom::Tasks => true,
+            BoardZoom::Epics => task.task.is_epic,
+            BoardZoom::Epic(epic_id) => self.epic_of(&task.task) == Some(epic_id),
+        }
+    }
+
+    /// The epic `task` is a subtask of, if the epic is in the project.
+    pub fn epic_of(&self, task: &Task) -> Option<Uuid> {
+        self.epic_workspaces
+            .get(&task.parent_workspace_id?)
+            .copied()
+    }
+
+    /// Subtasks of `epic_id` that are done, and all of them but the cancelled ones.
+    pub fn epic_progress(&self, epic_id: Uuid) -> (usize, usize) {
+        let subtasks: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|t| {
+                self.epic_of(&t.task) == Some(epic_id) && t.task.status != TaskStatus::Cancelled
+            })
+            .collect();
+        let done = subtasks
+            .iter()
+            .filter(|t| t.task.status == TaskStatus::Done)
+            .count();
+        (done, subtasks.len())
+    }
+
+    /// Where the board is zoomed to, from the project down: the project, then
+    /// "Epics" and the epic's title when zoomed in that far.
+    pub fn board_breadcrumbs(&self) -> Vec<String> {
+        let mut crumbs: Vec<String> = self
+            .selected_project
+            .iter()
+            .map(|p| p.name.clone())
+            .collect();
+        if self.board_zoom != BoardZoom::Tasks {
+            crumbs.push("Epics".to_string());
+        }
+        if let BoardZoom::Epic(epic_id) = self.board_zoom {
+            let epic = self.tasks.iter().find(|t| t.task.id == epic_id);
+            crumbs.push(epic.map_or_else(|| "Epic".to_string(), |t| t.task.title.clone()));
+        }
+        crumbs
+    }
+
+    /// Zoom in on the selected card: from the epics to the subtasks of the
+    /// selected epic, and from a task to its attempts.
+    pub fn zoom_in(&mut self) {
+        let Some(task) = self.current_column_selected_task().cloned() else {
+            return;
+        };
+        if self.board_zoom == BoardZoom::Epics {
+            self.set_board_zoom(BoardZoom::Epic(task.task.id));
+            return;
+        }
+        self.selected_task = Some(task);
+        self.selected_workspace_index = 0;
+        self.open_task_workspaces();
+    }
+
+    /// Zoom out of a task's attempts back to the board, and out of the board to
+    /// the project's epics.
+    pub fn zoom_out(&mut self) {
+        match self.view {
+            View::Workspaces => self.go_back(),
+            View::Tasks => match self.board_zoom {
+                BoardZoom::Tasks => self.set_board_zoom(BoardZoom::Epics),
+                BoardZoom::Epic(epic_id) => {
+                    self.set_board_zoom(BoardZoom::Epics);
+                    if let Some(status) = self
+                        .tasks
+                        .iter()
+                        .find(|t| t.task.id == epic_id)
+                        .map(|t| t.task.status)
+                    {
+                        self.select_board_task(epic_id, status);
+                    }
+                }
+                BoardZoom::Epics => self.set_status("Zoomed out to the epics; Esc shows all tasks"),
+            },
+            _ => {}
+        }
+    }
+
+    /// Show the board at `zoom`, from the top of each column, moving to a column
+    /// with cards if the selected one has none.
+    pub fn set_board_zoom(&mut self, zoom: BoardZoom) {
+        self.board_zoom = zoom;
+        self.selected_task_indices = [0; 5];
+        for offset in &self.board_offsets {
+            offset.set(0);
+        }
+        if self.tasks_for_column(self.selected_column).is_empty()
+            && let Some(column) = self
+                .visible_columns()
+                .iter()
+                .find(|column| !self.tasks_for_column(**column).is_empty())
+        {
+            self.selected_column = *column;
+        }
+    }
+
+    /// Order of a board column of the selected project.
+    pub fn task_sort(&self, column: TaskColumn) -> TaskSort {
+        self.selected_project
+            .as_ref()
+            .map(|project| self.config.board_sort(project.id, column.status()))
+            .unwrap_or_default()
+    }
+
+    /// Order the selected board column by the next sort mode, and save it to the
+    /// config file for the project.
+    pub fn cycle_task_sort(&mut self) {
+        let Some(project_id) = self.selected_project.as_ref().map(|p| p.id) else {
+            return;
+        };
+        let column = self.selected_column;
+        let sort = self.task_sort(column).next();
+        let selected = self.current_column_selected_task().map(|t| t.task.id);
+        let saved = self
+            .config
+            .set_board_sort(project_id, column.status(), sort);
+        if let Some(index) = selected.and_then(|id| {
+            self.tasks_for_column(column)
+                .iter()
+                .position(|t| t.task.id == id)
+        }) {
+            self.selected_task_indices[column.index()] = index;
+        }
+        match saved {
+            Ok(()) => self.set_status(format!("{} sorted by {}", column.title(), sort.as_str())),
+            Err(e) => self.report_error(&e.context("Sort order not saved")),
+        }
+    }
+
+    /// Columns on the board, from the left.
+    pub fn visible_columns(&self) -> &'static [TaskColumn] {
+        if self.show_cancelled {
+            &TaskColumn::ALL
+        } else {
+            &TaskColumn::ALL[..4]
+        }
+    }
+
+    /// Show or hide the column of cancelled tasks.
+    pub fn toggle_cancelled_column(&mut self) {
+        self.show_cancelled = !self.show_cancelled;
+        if self.show_cancelled {
+            self.selected_column = TaskColumn::Cancelled;
+        } else if self.selected_column == TaskColumn::Cancelled {
+            self.selected_column = TaskColumn::Done;
+        }
+    }
+
+    /// Start typing a board search query.
+    pub fn start_task_search(&mut self) {
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Clear the board search query and stop typing it.
+    pub fn clear_task_filter(&mut self) {
+        self.task_filter.clear();
+        self.input_mode = InputMode::Normal;
+        self.clamp_task_selection();
+    }
+
+    /// Keep each column's selection within its (filtered) task list.
+    pub fn clamp_task_selection(&mut self) {
+        for column in TaskColumn::ALL {
+            let len = self.tasks_for_column(column).len();
+            let selected = &mut self.selected_task_indices[column.index()];
+            *selected = (*selected).min(len.saturating_sub(1));
+        }
+    }
+
+    /// Get the currently selected task in the current column.
+    pub fn current_column_selected_task(&self) -> Option<&TaskWithAttemptStatus> {
+        let tasks = self.tasks_for_column(self.selected_column);
+        let index = self.selected_task_indices[self.selected_column.index()];
+        tasks.get(index).copied()
+    }
+
+    /// Select the board column and task under a mouse click. Returns true when the
+    /// click completes a double click on the same task.
+    pub fn click_board(&mut self, x: u16, y: u16) -> bool {
+        let areas = self.board_columns.get();
+        let Some(index) = areas
+            .iter()
+            .position(|area| area.contains(Position::new(x, y)))
+        else {
+            return false;
+        };
+        let column = TaskColumn::ALL[index];
+        self.selected_column = column;
+
+        // Tasks are listed from the row below the column's top border
+        let area = areas[index];
+        let task = (y > area.y && y + 1 < area.bottom())
+            .then(|| usize::from(y - area.y - 1) + self.board_offsets[index].get())
+            .and_then(|row| {
+                let task = self.tasks_for_column(column).get(row)?.task.id;
+                Some((row, task))
+            });
+        let Some((row, task_id)) = task else {
+            self.last_board_click = None;
+            return false;
+        };
+        self.selected_task_indices[index] = row;
+
+        let now = Instant::now();
+        let double = self
+            .last_board_click
+            .is_some_and(|(id, at)| id == task_id && now.duration_since(at) <= DOUBLE_CLICK);
+        self.last_board_click = (!double).then_some((task_id, now));
+        double
+    }
+
+    /// Most recent orphaned process of a task, if its last attempt failed because of one.
+    pub fn orphan_for_task(&self, task: &TaskWithAttemptStatus) -> Option<&ExecutionProcessOrphan> {
+        if !task.last_attempt_failed {
+            return None;
+        }
+        self.orphaned_executions
+            .iter()
+            .find(|o| o.task_id == task.task.id)
+    }
+
+    /// Select the current task and navigate to workspaces view.
+    pub fn select_task(&mut self) {
+        if let Some(task) = self.current_column_selected_task().cloned() {
+            self.selected_task = Some(task);
+            self.selected_workspace_index = 0;
+            self.navigate_to(View::TaskDetail);
+            self.load_task_detail();
+        }
+    }
+
+    /// Open the relationship tree of the task selected on the board, or shown in
+    /// task detail.
+    pub fn open_task_tree(&mut self) {
+        let task = match self.view {
+            View::Tasks => self.current_column_selected_task(),
+            _ => self.selected_task.as_ref(),
+        };
+        let Some(task_id) = task.map(|t| t.task.id) else {
+            return;
+        };
+        self.task_tree_task_id = Some(task_id);
+        self.task_tree.clear();
+        self.selected_tree_index = 0;
+        self.navigate_to(View::TaskTree);
+        self.load_task_tree();
+    }
+
+    /// Open the task or workspace of the highlighted row of the tree.
+    pub fn open_selected_tree_node(&mut self) {
+        if let Some(row) = self.task_tree.get(self.selected_tree_index) {
+            let target = row.node.link();
+            self.open_link(target, false);
+        }
+    }
+
+    /// Load the selected task with its attempts, parent and child tasks.
+    pub fn load_task_detail(&mut self) {
+        let task_id = self.selected_task.as_ref().map(|t| t.task.id);
+        if let Some(task_id) = task_id {
+            self.perform(RetryableAction::LoadTaskDetail { task_id });
+        }
+    }
+
+    /// The task shown in the task detail view, written up as a prompt.
+    fn task_prompt(&self) -> Option<TaskPrompt> {
+        Some(TaskPrompt {
+            task: self.selected_task.as_ref()?.task.clone(),
+            repos: self.project_repos.clone(),
+            workspaces: self.workspaces.clone(),
+            parent: self.task_parent.clone(),
+            children: self.task_children.clone(),
+            notes: self.task_workspace_notes.clone(),
+        })
+    }
+
+    /// Copy the task shown in the task detail view as a prompt for agents and
+    /// chat tools outside Vibe Kanban.
+    pub fn copy_task_prompt(&mut self) {
+        let Some(prompt) = self.task_prompt() else {
+            return;
+        };
+        match clipboard::copy(&prompt.render()) {
+            Ok(()) => self.set_status("Prompt copied to clipboard"),
+            Err(e) => self.set_error(format!("Failed to copy: {}", e)),
+        }
+    }
+
+    /// Copy the ID of the task selected on the board.
+    pub fn yank_task_id(&mut self) {
+        let Some(task_id) = self.current_column_selected_task().map(|t| t.task.id) else {
+            return;
+        };
+        self.yank("Task ID", &task_id.to_string());
+    }
+
+    /// Copy the branch of the workspace shown, or selected in the list.
+    pub fn yank_branch(&mut self) {
+        let workspace = match self.view {
+            View::Workspaces => self.workspaces.get(self.selected_workspace_index),
+            _ => self.selected_workspace.as_ref(),
+        };
+        let Some(branch) = workspace.map(|w| w.branch.clone()) else {
+            return;
+        };
+        self.yank("Branch", &branch);
+    }
+
+    /// Copy the pull request URLs of the workspace shown, one per repo, open
+    /// ones before merged or closed ones.
+    pub fn yank_pr_url(&mut self) {
+        let Some(workspace) = &self.selected_workspace else {
+            return;
+        };
+        let urls: Vec<String> = self
+            .branch_statuses
+            .iter()
+            .filter_map(|status| {
+                let prs: Vec<_> = status.status.pull_requests().collect();
+                prs.iter()
+                    .find(|pr| pr.status == "open")
+                    .or(prs.last())
+                    .map(|pr| pr.url.clone())
+            })
+            .collect();
+        if urls.is_empty() {
+            self.set_error(format!("{} has no pull request yet", workspace.branch));
+            return;
+        }
+        self.yank("Pull request URL", &urls.join("\n"));
+    }
+
+    fn yank(&mut self, what: &str, text: &str) {
+        match clipboard::copy(text) {
+            Ok(()) => self.set_status(format!(
+                "{} copied to clipboard: {}",
+                what,
+                text.replace('\n', " ")
+            )),
+            Err(e) => self.set_error(format!("Failed to copy: {}", e)),
+        }
+    }
+
+    /// Write the task shown in the task detail view as a prompt to a file in the
+    /// current directory.
+    pub fn save_task_prompt(&mut self) {
+        let Some(prompt) = self.task_prompt() else {
+            return;
+        };
+        let path = prompt.file_name();
+        match std::fs::write(&path, prompt.render()) {
+            Ok(()) => self.set_status(format!("Prompt written to {}", path)),
+            Err(e) => self.set_error(format!("Failed to write {}: {}", path, e)),
+        }
+    }
+
+    /// Open the workspaces of the task shown in the task detail view.
+    pub fn open_task_workspaces(&mut self) {
+        self.navigate_to(View::Workspaces);
+        self.load_workspaces();
+    }
+
+    /// Put a freshly fetched task in place of its stale copies.
+    /// Change the board's tasks, keeping the selected task of each column selected.
+    fn update_tasks(&mut self, update: impl FnOnce(&mut Vec<TaskWithAttemptStatus>)) {
+        let selected = TaskColumn::ALL.map(|column| {
+            let index = self.selected_task_indices[column.index()];
+            self.tasks_for_column(column).get(index).map(|t| t.task.id)
+        });
+        update(&mut self.tasks);
+        for (column, selected) in TaskColumn::ALL.into_iter().zip(selected) {
+            if let Some(index) = selected.and_then(|id| {
+                self.tasks_for_column(column)
+                    .iter()
+                    .position(|t| t.task.id == id)
+            }) {
+                self.selected_task_indices[column.index()] = index;
+            }
+        }
+        self.clamp_task_selection();
+    }
+
+    fn replace_task(&mut self, task: Task) {
+        for entry in self.tasks.iter_mut().chain(self.selected_task.as_mut()) {
+            if entry.task.id == task.id {
+                entry.task = task.clone();
+            }
+        }
+    }
+
+    /// Start editing the title and description of the task in the detail view.
+    pub fn start_task_edit(&mut self) {
+        let Some(task) = self.selected_task.as_ref().map(|t| &t.task) else {
+            return;
+        };
+        self.task_edit_title = task.title.clone();
+        self.task_edit_description = task.description.clone().unwrap_or_default();
+        self.task_edit_field = TaskFormField::Title;
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Save the edited title and description.
+    pub fn save_task_edit(&mut self) {
+        let Some(task_id) = self.selected_task.as_ref().map(|t| t.task.id) else {
+            return;
+        };
+        let title = self.task_edit_title.trim().to_string();
+        if title.is_empty() {
+            self.set_error("Task title cannot be empty");
+            return;
+        }
+        let action = RetryableAction::UpdateTaskText {
+            task_id,
+            title,
+            description: self.task_edit_description.trim_end().to_string(),
+        };
+        self.perform_then(action, |app, result| {
+            result?;
+            app.input_mode = InputMode::Normal;
+            app.set_status("Task updated");
+            Ok(())
+        });
+    }
+
+    /// Stop editing the task, discarding the changes.
+    pub fn cancel_task_edit(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.set_status("Edit discarded");
+    }
+
+    /// Open the metadata popup on the task shown in task detail.
+    pub fn open_metadata_editor(&mut self) {
+        let Some(task) = self.selected_task.as_ref().map(|t| &t.task) else {
+            return;
+        };
+        let entries = match metadata::parse(task.metadata.as_deref()) {
+            Ok(entries) => entries.into_iter().collect(),
+            Err(e) => return self.report_error(&e),
+        };
+        self.metadata_editor = Some(MetadataEditor {
+            task_id: task.id,
+            title: task.title.clone(),
+            entries,
+            selected: 0,
+            entry: None,
+            modified: false,
+            problem: None,
+        });
+    }
+
+    /// Highlight the next or previous key of the metadata.
+    pub fn metadata_move(&mut self, down: bool) {
+        if let Some(editor) = &mut self.metadata_editor {
+            let last = editor.entries.len().saturating_sub(1);
+            editor.selected = if down {
+                (editor.selected + 1).min(last)
+            } else {
+                editor.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Change the highlighted key, or add a key with `new`.
+    pub fn edit_metadata_entry(&mut self, new: bool) {
+        let Some(editor) = &mut self.metadata_editor else {
+            return;
+        };
+        editor.problem = None;
+        editor.entry = match editor.entries.get(editor.selected) {
+            Some((key, value)) if !new => Some(MetadataEntry {
+                index: Some(editor.selected),
+                key: key.clone(),
+                value: metadata::pretty(value),
+                editing_value: true,
+            }),
+            _ => Some(MetadataEntry {
+                index: None,
+                key: String::new(),
+                value: String::new(),
+                editing_value: false,
+            }),
+        };
+    }
+
+    /// Remove the highlighted key; it is gone from the task once saved.
+    pub fn delete_metadata_entry(&mut self) {
+        let Some(editor) = &mut self.metadata_editor else {
+            return;
+        };
+        if editor.selected < editor.entries.len() {
+            editor.entries.remove(editor.selected);
+            editor.selected = editor.selected.min(editor.entries.len().saturating_sub(1));
+            editor.modified = true;
+        }
+    }
+
+    /// Put the entry being typed into the metadata, unless its key or value is
+    /// wrong. Returns whether it did.
+    fn apply_metadata_entry(&mut self) -> bool {
+        let Some(editor) = &mut self.metadata_editor else {
+            return false;
+        };
+        let Some(entry) = editor.entry.take() else {
+            return true;
+        };
+        let key = entry.key.trim().to_string();
+        let taken = editor
+            .entries
+            .iter()
+            .enumerate()
+            .any(|(i, (k, _))| *k == key && Some(i) != entry.index);
+        let value = if key.is_empty() {
+            Err("The key cannot be empty".to_string())
+        } else if taken {
+            Err(format!("There already is a \"{}\" key", key))
+        } else {
+            metadata::parse_value(&key, &entry.value)
+        };
+        match (value, entry.index) {
+            (Ok(value), Some(index)) => {
+                editor.entries[index] = (key, value);
+                editor.selected = index;
+            }
+            (Ok(value), None) => {
+                editor.entries.push((key, value));
+                editor.selected = editor.entries.len() - 1;
+            }
+            (Err(problem), _) => {
+                editor.problem = Some(problem);
+                editor.entry = Some(entry);
+                return false;
+            }
+        }
+        editor.modified = true;
+        true
+    }
+
+    /// Save the metadata, with the entry being typed, once it checks out.
+    pub fn save_metadata(&mut self) {
+        if !self.apply_metadata_entry() {
+            return;
+        }
+        let Some(editor) = self.metadata_editor.take() else {
+            return;
+        };
+        if !editor.modified {
+            return self.set_status("Metadata unchanged");
+        }
+        let metadata: serde_json::Map<String, serde_json::Value> =
+            editor.entries.into_iter().collect();
+        let action = RetryableAction::UpdateTaskMetadata {
+            task_id: editor.task_id,
+            metadata: serde_json::Value::Object(metadata).to_string(),
+        };
+        self.perform_then(action, |app, result| {
+            result?;
+            app.set_status("Metadata saved");
+            Ok(())
+        });
+    }
+
+    /// Leave the entry being typed, or close the popup, discarding the changes.
+    pub fn cancel_metadata_edit(&mut self) {
+        let Some(editor) = &mut self.metadata_editor else {
+            return;
+        };
+        if editor.entry.take().is_some() {
+            editor.problem = None;
+            return;
+        }
+        let modified = editor.modified;
+        self.metadata_editor = None;
+        if modified {
+            self.set_status("Metadata changes discarded");
+        }
+    }
+
+    /// Fill in an empty title of the new task from its description, to confirm
+    /// or edit before the task is created. Returns whether it did.
+    pub fn suggest_task_title(&mut self) -> bool {
+        if !self.new_task_title.trim().is_empty() {
+            return false;
+        }
+        let Some(title) = suggest_title(&self.new_task_description) else {
+            return false;
+        };
+        self.new_task_title = title;
+        self.new_task_title_suggested = true;
+        self.new_task_field = NewTaskField::Title;
+        self.set_status("Title suggested from the description; e edits it, Enter creates the task");
+        true
+    }
+
+    /// Create a new task.
+    pub fn create_task(&mut self) {
+        if self.suggest_task_title() {
+            return;
+        }
+        if self.new_task_title.trim().is_empty() {
+            self.set_error("Task title cannot be empty");
+            return;
+        }
+
+        let project_id = self.selected_project.as_ref().map(|p| p.id);
+        if let Some(id) = project_id {
+            self.clear_messages();
+            let payload = CreateTask {
+                project_id: id,
+                title: self.new_task_title.clone(),
+                description: if self.new_task_description.is_empty() {
+                    None
+                } else {
+                    Some(self.new_task_description.clone())
+                },
+                status: None,
+                parent_workspace_id: self.new_task_parent.as_ref().map(|w| w.id),
+                image_ids: None,
+                is_epic: None,
+                complexity: None,
+                metadata: None,
+            };
+            if self.new_task_start {
+                return self.create_and_start_task(payload);
+            }
+
+            let key = self.submit_key();
+            let client = self.client.clone();
+            self.spawn_request(
+                "Creating task...",
+                async move { client.create_task(&payload, &key).await },
+                |app, result| {
+                    result?;
+                    app.task_created("Task created successfully");
+                    Ok(())
+                },
+            );
+        }
+    }
+
+    /// Create the task and start an attempt on it in one request, with the
+    /// executor and base branches picked in the form.
+    fn create_and_start_task(&mut self, task: CreateTask) {
+        if self.attempt_repo_branches.is_empty() {
+            self.set_error("No repositories configured for this project");
+            return;
+        }
+        let executor = match self.picked_executor() {
+            Ok(executor) => executor,
+            Err(e) => return self.set_error(e),
+        };
+        let request = CreateAndStartTaskRequest {
+            task,
+            executor_profile_id: ExecutorProfileId {
+                executor,
+                variant: None,
+            },
+            repos: self
+                .attempt_repo_branches
+                .iter()
+                .map(|(repo_id, branch)| WorkspaceRepoInput {
+                    repo_id: *repo_id,
+                    target_branch: branch.clone(),
+                })
+                .collect(),
+            branch: None,
+        };
+
+        let client = self.client.clone();
+        self.spawn_request(
+            "Creating and starting task...",
+            async move { client.create_and_start_task(&request).await },
+            move |app, result| {
+                result?;
+                app.task_created(format!(
+                    "Task created and started with {}",
+                    executor.as_str()
+                ));
+                Ok(())
+            },
+        );
+    }
+
+    /// Open an empty create task form; with `parent`, for a task created from
+    /// that workspace.
+    pub fn open_create_task(&mut self, parent: Option<Workspace>) {
+        self.new_task_title.clear();
+        self.new_task_title_suggested = false;
+        self.new_task_description.clear();
+        self.new_task_field = NewTaskField::Title;
+        self.new_task_start = false;
+        self.new_task_parent = parent;
+        self.input_mode = InputMode::Editing;
+        self.navigate_to(View::CreateTask);
+    }
+
+    /// Open the create task form for a subtask of the workspace shown, to capture
+    /// follow-on work its agent came across.
+    pub fn open_create_subtask(&mut self) {
+        let Some(workspace) = self.selected_workspace.clone() else {
+            return;
+        };
+        self.open_create_task(Some(workspace));
+    }
+
+    /// Clear the create task form once its task is on the server.
+    fn task_created(&mut self, message: impl Into<String>) {
+        self.new_task_title.clear();
+        self.new_task_title_suggested = false;
+        self.new_task_description.clear();
+        self.new_task_start = false;
+        self.new_task_parent = None;
+        self.load_tasks();
+        self.set_status(message);
+        if self.view == View::CreateTask {
+            self.go_back();
+        }
+    }
+
+    /// Move to the next field of the create task form; the executor and branches
+    /// only while the task is to be started.
+    pub fn next_new_task_field(&mut self) {
+        let repos = self.attempt_repo_branches.len();
+        self.new_task_field = match self.new_task_field {
+            NewTaskField::Title => NewTaskField::Description,
+            NewTaskField::Description => NewTaskField::Start,
+            NewTaskField::Start if self.new_task_start => NewTaskField::Executor,
+            NewTaskField::Executor if repos > 0 => NewTaskField::RepoBranch(0),
+            NewTaskField::RepoBranch(i) if i + 1 < repos => NewTaskField::RepoBranch(i + 1),
+            _ => NewTaskField::Title,
+        };
+    }
+
+    /// Switch starting the new task on or off, loading the executors and the
+    /// project's branches to pick from when it is switched on.
+    pub fn toggle_new_task_start(&mut self) {
+        self.new_task_start = !self.new_task_start;
+        if self.new_task_start {
+            self.init_create_attempt();
+        }
+    }
+
+    /// Step the focused choice of the create task form.
+    pub fn cycle_new_task_value(&mut self, forward: bool) {
+        // The executor and branches are the attempt form's
+        self.attempt_selected_field = match self.new_task_field {
+            NewTaskField::Start => return self.toggle_new_task_start(),
+            NewTaskField::Executor => 0,
+            NewTaskField::RepoBranch(i) => 4 + i,
+            NewTaskField::Title | NewTaskField::Description => return,
+        };
+        self.cycle_attempt_value(forward);
+    }
+
+    /// Open the create project form with one empty repository entry.
+    pub fn open_create_project(&mut self) {
+        self.new_project_name.clear();
+        self.new_project_repos = vec![CreateProjectRepo::default()];
+        self.new_project_field = ProjectFormField::Name;
+        self.input_mode = InputMode::Editing;
+        self.navigate_to(View::CreateProject);
+    }
+
+    /// Move to the next field of the create project form. Leaving a repository
+    /// path names the entry after its folder unless it already has a name.
+    pub fn next_project_field(&mut self) {
+        let last = self.new_project_repos.len().saturating_sub(1);
+        self.new_project_field = match self.new_project_field {
+            ProjectFormField::Name => ProjectFormField::RepoPath(0),
+            ProjectFormField::RepoPath(i) => {
+                if let Some(repo) = self.new_project_repos.get_mut(i)
+                    && repo.display_name.trim().is_empty()
+                    && let Ok(path) = resolve_repo_path(&repo.git_repo_path)
+                {
+                    repo.display_name = folder_name(&path).unwrap_or_default();
+                }
+                ProjectFormField::RepoDisplayName(i)
+            }
+            ProjectFormField::RepoDisplayName(i) if i < last => ProjectFormField::RepoPath(i + 1),
+            ProjectFormField::RepoDisplayName(_) => ProjectFormField::Name,
+        };
+    }
+
+    /// The input of the focused field of the create project form.
+    pub fn project_input_mut(&mut self) -> Option<&mut String> {
+        match self.new_project_field {
+            ProjectFormField::Name => Some(&mut self.new_project_name),
+            ProjectFormField::RepoPath(i) => self
+                .new_project_repos
+                .get_mut(i)
+                .map(|repo| &mut repo.git_repo_path),
+            ProjectFormField::RepoDisplayName(i) => self
+                .new_project_repos
+                .get_mut(i)
+                .map(|repo| &mut repo.display_name),
+        }
+    }
+
+    /// Add a repository entry to the create project form and start typing its path.
+    pub fn add_project_repo(&mut self) {
+        self.new_project_repos.push(CreateProjectRepo::default());
+        self.new_project_field = ProjectFormField::RepoPath(self.new_project_repos.len() - 1);
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Remove the focused repository entry, keeping at least one.
+    pub fn remove_project_repo(&mut self) {
+        let (ProjectFormField::RepoPath(i) | ProjectFormField::RepoDisplayName(i)) =
+            self.new_project_field
+        else {
+            return;
+        };
+        if self.new_project_repos.len() < 2 {
+            return;
+        }
+        self.new_project_repos.remove(i);
+        self.new_project_field =
+            ProjectFormField::RepoPath(i.min(self.new_project_repos.len() - 1));
+    }
+
+    /// Create a project from the create project form. Every repository path must
+    /// be a git repository on this machine.
+    pub fn create_project(&mut self) {
+        let mut repositories = Vec::with_capacity(self.new_project_repos.len());
+        for (i, repo) in self.new_project_repos.iter().enumerate() {
+            if repo.git_repo_path.trim().is_empty() && self.new_project_repos.len() > 1 {
+                continue;
+            }
+            let path = match resolve_repo_path(&repo.git_repo_path) {
+                Ok(path) => path,
+                Err(e) => {
+                    self.new_project_field = ProjectFormField::RepoPath(i);
+                    self.set_error(format!("Repository {}: {}", i + 1, e));
+                    return;
+                }
+            };
+            let display_name = match repo.display_name.trim() {
+                "" => folder_name(&path).unwrap_or_default(),
+                name => name.to_string(),
+            };
+            repositories.push(CreateProjectRepo {
+                display_name,
+                git_repo_path: path.to_string_lossy().into_owned(),
+            });
+        }
+        let Some(first) = repositories.first() else {
+            self.set_error("Add at least one repository");
+            return;
+        };
+        // Named after its first repository unless given a name
+        let name = match self.new_project_name.trim() {
+            "" => first.display_name.clone(),
+            name => name.to_string(),
+        };
+
+        self.clear_messages();
+        let payload = CreateProject { name, repositories };
+        let client = self.client.clone();
+        self.spawn_request(
+            "Creating project...",
+            async move { client.create_project(&payload).await },
+            |app, result| {
+                let project = result?;
+                if app.view == View::CreateProject {
+                    app.go_back();
+                }
+                app.perform_then(RetryableAction::LoadProjects, move |app, result| {
+                    result?;
+                    if let Some(index) = app.projects.iter().position(|p| p.id == project.id) {
+                        app.selected_project_index = index;
+                    }
+                    app.set_status(format!("Project '{}' created", project.name));
+                    Ok(())
+                });
+                Ok(())
+            },
+        );
+    }
+
+    /// Move the selected card to the column on its left or right, and keep it
+    /// selected there.
+    pub fn move_card(&mut self, right: bool) {
+        let column = self.selected_column;
+        let target = if right { column.next() } else { column.prev() };
+        let Some(task_id) = self.current_column_selected_task().map(|t| t.task.id) else {
+            return;
+        };
+        if target.status() == column.status() {
+            return;
+        }
+        let status = target.status();
+        self.perform_then(
+            RetryableAction::UpdateTaskStatus { task_id, status },
+            move |app, result| {
+                result?;
+                app.update_tasks(|tasks| {
+                    if let Some(entry) = tasks.iter_mut().find(|t| t.task.id == task_id) {
+                        entry.task.status = status;
+                    }
+                });
+                app.selected_column = target;
+                if let Some(index) = app
+                    .tasks_for_column(target)
+                    .iter()
+                    .position(|t| t.task.id == task_id)
+                {
+                    app.selected_task_indices[target.index()] = index;
+                }
+                app.load_tasks();
+                app.set_status(format!("Moved to {}", target.title()));
+                Ok(())
+            },
+        );
+    }
+
+    /// Move the selected card up or down its column. The column switches to
+    /// manual order, starting from the order it was shown in, and the whole
+    /// column's order is saved on the server in one request.
+    pub fn reorder_card(&mut self, down: bool) {
+        let Some(project_id) = self.selected_project.as_ref().map(|p| p.id) else {
+            return;
+        };
+        if !self.task_filter.is_empty()
+            || self.has_quick_filters()
+            || self.board_zoom != BoardZoom::Tasks
+        {
+            self.set_error("Clear the search, filters and zoom to reorder cards");
+            return;
+        }
+        let column = self.selected_column;
+        let index = self.selected_task_indices[column.index()];
+        let mut order: Vec<Uuid> = self
+            .tasks_for_column(column)
+            .into_iter()
+            .map(|t| t.task.id)
+            .collect();
+        let other = if down {
+            index + 1
+        } else {
+            match index.checked_sub(1) {
+                Some(other) => other,
+                None => return,
+            }
+        };
+        if other >= order.len() {
+            return;
+        }
+        order.swap(index, other);
+
+        let task_id = order[other];
+        let payload = ReorderTasks {
+            project_id,
+            task_ids: order,
+        };
+        let client = self.client.clone();
+        self.clear_messages();
+        self.spawn_request(
+            "Reordering...",
+            async move {
+                client.reorder_tasks(&payload).await?;
+                Ok::<_, anyhow::Error>(payload.task_ids)
+            },
+            move |app, result| {
+                let order = result?;
+                let sort = app.task_sort(column);
+                let sort_saved = if sort == TaskSort::Manual {
+                    Ok(())
+                } else {
+                    app.config
+                        .set_board_sort(project_id, column.status(), TaskSort::Manual)
+                };
+                for entry in app.tasks.iter_mut().chain(app.selected_task.as_mut()) {
+                    if let Some(position) = order.iter().position(|id| *id == entry.task.id) {
+                        entry.task.board_position = Some(position as i64);
+                    }
+                }
+                if let Some(index) = app
+                    .tasks_for_column(column)
+                    .iter()
+                    .position(|t| t.task.id == task_id)
+                {
+                    app.selected_task_indices[column.index()] = index;
+                }
+                match sort_saved {
+                    Ok(()) if sort == TaskSort::Manual => {}
+                    Ok(()) => app.set_status(format!("{} sorted by hand", column.title())),
+                    Err(e) => app.report_error(&e.context("Sort order not saved")),
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// Update a task's status.
+    pub fn update_task_status(&mut self, task_id: Uuid, status: TaskStatus) {
+        self.perform_then(
+            RetryableAction::UpdateTaskStatus { task_id, status },
+            |app, result| {
+                result?;
+                app.load_tasks();
+                app.set_status("Task updated");
+                Ok(())
+            },
+        );
+    }
+
+    /// Ask to delete the selected task.
+    pub fn request_delete_task(&mut self) {
+        if let Some(task) = self.current_column_selected_task() {
+            self.pending_action = Some(PendingAction::DeleteTask {
+                task_id: task.task.id,
+                title: task.task.title.clone(),
+            });
+        }
+    }
+
+    fn delete_task(&mut self, task_id: Uuid) {
+        let task = self
+            .tasks
+            .iter()
+            .find(|t| t.task.id == task_id)
+            .map(|t| t.task.clone());
+        let action = RetryableAction::DeleteTask { task_id };
+        self.perform_then(action, move |app, result| {
+            result?;
+            // The server put it in the trash, rather than deleting it for good
+            let trashed = app.trash.iter().any(|t| t.id == task_id);
+            if let Some(task) = task {
+                if app.deleted_tasks.len() == MAX_UNDO {
+                    app.deleted_tasks.remove(0);
+                }
+                app.deleted_tasks.push(DeletedTask {
+                    task,
+                    trashed,
+                    deleted_at: Instant::now(),
+                    idempotency_key: app.client.idempotency_key(),
+                });
+            }
+            app.load_tasks();
+            if trashed {
+                app.set_status("Task moved to the trash; press u to undo");
+            } else {
+                app.set_status("Task deleted; press u to undo");
+            }
+            Ok(())
+        });
+    }
+
+    /// Bring back the most recently deleted task, if it was deleted within the
+    /// undo window: out of the trash with its attempts, or created again without
+    /// them on servers that have no trash.
+    pub fn undo_delete_task(&mut self) {
+        self.deleted_tasks
+            .retain(|deleted| deleted.deleted_at.elapsed() < UNDO_WINDOW);
+        let Some(deleted) = self.deleted_tasks.pop() else {
+            self.set_status("Nothing to undo");
+            return;
+        };
+        if deleted.trashed {
+            let title = deleted.task.title.clone();
+            self.restore_task(deleted.task.id, title);
+            return;
+        }
+        let task = &deleted.task;
+        let payload = CreateTask {
+            project_id: task.project_id,
+            title: task.title.clone(),
+            description: task.description.clone(),
+            status: Some(task.status),
+            parent_workspace_id: task.parent_workspace_id,
+            image_ids: None,
+            is_epic: Some(task.is_epic),
+            complexity: task.complexity,
+            metadata: task.metadata.clone(),
+        };
+        let client = self.client.clone();
+        self.clear_messages();
+        self.spawn_request(
+            "Restoring task...",
+            {
+                let key = deleted.idempotency_key.clone();
+                async move { client.create_task(&payload, &key).await }
+            },
+            move |app, result| {
+                if result.is_err() {
+                    // Keep it for another try
+                    app.deleted_tasks.push(deleted.clone());
+                }
+                result?;
+                app.load_tasks();
+                app.set_status(format!("Restored \"{}\"", deleted.task.title));
+                Ok(())
+            },
+        );
+    }
+
+    /// Take a task out of the trash and put it back on the board.
+    fn restore_task(&mut self, task_id: Uuid, title: String) {
+        let action = RetryableAction::RestoreTask { task_id };
+        self.perform_then(action, move |app, result| {
+            result?;
+            app.trash.retain(|t| t.id != task_id);
+            app.deleted_tasks.retain(|d| d.task.id != task_id);
+            app.selected_trash_index = app
+                .selected_trash_index
+                .min(app.trash.len().saturating_sub(1));
+            app.load_tasks();
+            app.set_status(format!("Restored \"{}\"", title));
+            Ok(())
+        });
+    }
+
+    /// Restore the highlighted task of the trash.
+    pub fn restore_selected_trash(&mut self) {
+        if let Some(task) = self.trash.get(self.selected_trash_index) {
+            let (task_id, title) = (task.id, task.title.clone());
+            self.restore_task(task_id, title);
+        }
+    }
+
+    /// Ask to delete the highlighted task of the trash for good.
+    pub fn request_purge_task(&mut self) {
+        if let Some(task) = self.trash.get(self.selected_trash_index) {
+            self.pending_action = Some(PendingAction::PurgeTask {
+                task_id: task.id,
+                title: task.title.clone(),
+            });
+        }
+    }
+
+    fn purge_task(&mut self, task_id: Uuid) {
+        let action = RetryableAction::PurgeTask { task_id };
+        self.perform_then(action, move |app, result| {
+            result?;
+            app.trash.retain(|t| t.id != task_id);
+            app.deleted_tasks.retain(|d| d.task.id != task_id);
+            app.selected_trash_index = app
+                .selected_trash_index
+                .min(app.trash.len().saturating_sub(1));
+            app.set_status("Task deleted for good");
+            Ok(())
+        });
+    }
+
+    // =========================================================================
+    // Workspace Actions
+    // =========================================================================
+
+    /// Select a workspace and show details.
+    pub fn select_workspace(&mut self) {
+        if let Some(workspace) = self.workspaces.get(self.selected_workspace_index).cloned() {
+            self.selected_workspace = Some(workspace);
+            self.navigate_to(View::WorkspaceDetail);
+            self.load_workspace_details();
+        }
+    }
+
+    /// Put a workspace as saved by the server in place of the loaded copies.
+    fn replace_workspace(&mut self, workspace: Workspace) {
+        for entry in self
+            .workspaces
+            .iter_mut()
+            .chain(self.selected_workspace.as_mut())
+        {
+            if entry.id == workspace.id {
+                *entry = workspace.clone();
+            }
+        }
+    }
+
+    /// Open the rename popup for the highlighted workspace in the list, or the
+    /// one shown in the detail view.
+    pub fn start_workspace_rename(&mut self) {
+        let workspace = if self.view == View::Workspaces {
+            self.workspaces.get(self.selected_workspace_index)
+        } else {
+            self.selected_workspace.as_ref()
+        };
+        if let Some(workspace) = workspace {
+            self.workspace_rename = Some(WorkspaceRename {
+                workspace_id: workspace.id,
+                branch: workspace.branch.clone(),
+                name: workspace.name.clone().unwrap_or_default(),
+            });
+        }
+    }
+
+    /// Save the typed name; an empty one clears the name.
+    pub fn save_workspace_rename(&mut self) {
+        let Some(rename) = self.workspace_rename.take() else {
+            return;
+        };
+        let name = rename.name.trim().to_string();
+        let action = RetryableAction::RenameWorkspace {
+            workspace_id: rename.workspace_id,
+            name: name.clone(),
+        };
+        self.perform_then(action, move |app, result| {
+            result?;
+            if name.is_empty() {
+                app.set_status(format!("Name of {} cleared", rename.branch));
+            } else {
+                app.set_status(format!("Renamed {} to \"{}\"", rename.branch, name));
+            }
+            Ok(())
+        });
+    }
+
+    pub fn cancel_workspace_rename(&mut self) {
+        self.workspace_rename = None;
+    }
+
+    /// Open the notes popup on the workspace shown in the detail view.
+    pub fn start_workspace_notes(&mut self) {
+        if let Some(workspace) = &self.selected_workspace {
+            self.workspace_notes_editor = Some(WorkspaceNotesEditor {
+                workspace_id: workspace.id,
+                branch: workspace.branch.clone(),
+                body: self
+                    .workspace_note
+                    .as_ref()
+                    .map(|note| note.body.clone())
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    /// Save the notes being edited; blank notes are removed.
+    pub fn save_workspace_notes(&mut self) {
+        let Some(editor) = self.workspace_notes_editor.take() else {
+            return;
+        };
+        let body = editor.body.trim_end().to_string();
+        let cleared = body.trim().is_empty();
+        let action = RetryableAction::SaveWorkspaceNote {
+            workspace_id: editor.workspace_id,
+            body,
+        };
+        self.perform_then(action, move |app, result| {
+            result?;
+            if cleared {
+                app.set_status(format!("Notes on {} removed", editor.branch));
+            } else {
+                app.set_status(format!("Notes on {} saved", editor.branch));
+            }
+            Ok(())
+        });
+    }
+
+    pub fn cancel_workspace_notes(&mut self) {
+        self.workspace_notes_editor = None;
+    }
+
+    /// The process picked in the workspace detail view, if any.
+    pub fn selected_process(&self) -> Option<&ExecutionProcess> {
+        self.workspace_processes.get(self.selected_process_index?)
+    }
+
+    /// Pick the next or previous process in the workspace detail view, starting
+    /// from the newest.
+    pub fn move_process_selection(&mut self, down: bool) {
+        let last = self.workspace_processes.len().checked_sub(1);
+        self.selected_process_index = match (self.selected_process_index, last) {
+            (_, None) => None,
+            (None, Some(_)) => Some(0),
+            (Some(index), Some(last)) if down => Some((index + 1).min(last)),
+            (Some(index), Some(_)) => Some(index.saturating_sub(1)),
+        };
+    }
+
+    /// Ask to stop the picked process if it is running, else the whole workspace.
+    pub fn request_stop_selected(&mut self) {
+        let branch = self
+            .selected_workspace
+            .as_ref()
+            .map(|w| w.branch.clone())
+            .unwrap_or_default();
+        match self.selected_process() {
+            Some(process) if process.status == ExecutionProcessStatus::Running => {
+                self.pending_action = Some(PendingAction::StopProcess {
+                    process_id: process.id,
+                    run_reason: process.run_reason.clone(),
+                    branch,
+                });
+            }
+            Some(process) => {
+                self.set_error(format!("The {} process is not running", process.run_reason));
+            }
+            None => self.request_stop_workspace(),
+        }
+    }
+
+    /// The running dev server of the shown workspace.
+    pub fn running_dev_server(&self) -> Option<&ExecutionProcess> {
+        // Newest first
+        self.workspace_processes.iter().find(|p| {
+            let running = p.status == ExecutionProcessStatus::Running;
+            !p.dropped && running && p.run_reason == "devserver"
+        })
+    }
+
+    /// The address the shown workspace's dev server listens on, once it printed one.
+    pub fn dev_server_address(&self) -> Option<&str> {
+        let process = self.running_dev_server()?;
+        self.dev_server_url
+            .as_ref()
+            .filter(|found| found.process_id == process.id)
+            .map(|found| found.url.as_str())
+    }
+
+    /// Start the dev server of the selected workspace, or stop the running one.
+    pub fn toggle_dev_server(&mut self) {
+        let Some(workspace_id) = self.selected_workspace.as_ref().map(|w| w.id) else {
+            return;
+        };
+        if let Some(process) = self.running_dev_server() {
+            let process_id = process.id;
+            self.perform_then(
+                RetryableAction::StopExecutionProcess { process_id },
+                |app, result| {
+                    result?;
+                    app.load_workspace_details();
+                    app.set_status("Dev server stopped");
+                    Ok(())
+                },
+            );
+            return;
+        }
+        self.perform_then(
+            RetryableAction::StartDevServer { workspace_id },
+            |app, result| {
+                result?;
+                app.load_workspace_details();
+                app.set_status("Dev server started; its address shows once it prints one");
+                Ok(())
+            },
+        );
+    }
+
+    /// Called on every tick of the event loop; while the workspace detail shows a
+    /// running dev server, looks for the address it listens on in its output until
+    /// one turns up.
+    pub fn dev_server_tick(&mut self) {
+        if let Some(lookup) = self.dev_server_lookup.take_if(|l| l.is_finished()) {
+            // A failed lookup is tried again on the next one
+            if let Some(Ok(Ok(Some(found)))) = lookup.now_or_never() {
+                self.dev_server_url = Some(found);
+            }
+        }
+        if self.view != View::WorkspaceDetail || self.dev_server_address().is_some() {
+            return;
+        }
+        let Some(process) = self.running_dev_server().cloned() else {
+            return;
+        };
+        let due = self
+            .last_dev_server_lookup
+            .is_none_or(|at| at.elapsed() >= FOLLOW_POLL_INTERVAL);
+        if self.dev_server_lookup.is_some() || !due {
+            return;
+        }
+        self.last_dev_server_lookup = Some(Instant::now());
+        let client = self.client.clone();
+        self.dev_server_lookup = Some(tokio::spawn(async move {
+            let url = client.find_dev_server_url(&process).await?;
+            Ok(url.map(|url| DevServerUrl {
+                process_id: process.id,
+                url,
+            }))
+        }));
+    }
+
+    fn stop_process(&mut self, process_id: Uuid) {
+        self.perform_then(
+            RetryableAction::StopExecutionProcess { process_id },
+            |app, result| {
+                result?;
+                app.load_workspace_details();
+                app.set_status("Process stopped");
+                Ok(())
+            },
+        );
+    }
+
+    /// Ask to run the coding agent of the selected workspace again, if its last
+    /// run failed.
+    pub fn request_retry_attempt(&mut self) {
+        let Some(workspace) = &self.selected_workspace else {
+            return;
+        };
+        let failed = if self.view == View::WorkspaceDetail {
+            // Newest first
+            self.workspace_processes
+                .iter()
+                .find(|p| !p.dropped && p.run_reason == "codingagent")
+                .is_some_and(|p| p.status == ExecutionProcessStatus::Failed)
+        } else {
+            self.failure_for(workspace.id).is_some()
+        };
+        if !failed {
+            let branch = workspace.branch.clone();
+            self.set_error(format!("The last attempt of {} didn't fail", branch));
+            return;
+        }
+        self.pending_action = Some(PendingAction::RetryAttempt {
+            workspace_id: workspace.id,
+            branch: workspace.branch.clone(),
+        });
+    }
+
+    fn retry_attempt(&mut self, workspace_id: Uuid) {
+        self.perform_then(
+            RetryableAction::RetryAttempt { workspace_id },
+            |app, result| {
+                result?;
+                app.refresh();
+                app.set_status("Coding agent started again");
+                Ok(())
+            },
+        );
+    }
+
+    /// Ask to have the coding agent resolve the conflicts a rebase of the
+    /// selected workspace stopped on, after checking there are some.
+    pub fn request_resolve_conflicts(&mut self) {
+        let Some(workspace) = self.selected_workspace.clone() else {
+            return;
+        };
+        if self
+            .conflict_resolution
+            .as_ref()
+            .is_some_and(|r| r.workspace_id == workspace.id)
+        {
+            self.set_error(format!(
+                "The coding agent is already resolving the conflicts of {}",
+                workspace.branch
+            ));
+            return;
+        }
+        self.clear_messages();
+        let client = self.client.clone();
+        let request = async move { client.get_branch_status(workspace.id).await };
+        self.spawn_request(
+            "Checking for conflicts...",
+            request,
+            move |app, statuses| {
+                let statuses = statuses?;
+                let conflicted_files = statuses
+                    .iter()
+                    .map(|s| s.status.conflicted_files.len())
+                    .sum();
+                let in_progress = statuses.iter().any(|s| s.status.is_rebase_in_progress);
+                if app.selected_workspace.as_ref().map(|w| w.id) == Some(workspace.id) {
+                    app.branch_statuses = statuses;
+                }
+                if conflicted_files == 0 && !in_progress {
+                    app.set_error(format!("{} has no conflicts to resolve", workspace.branch));
+                    return Ok(());
+                }
+                if app.has_popup() {
+                    return Ok(());
+                }
+                app.pending_action = Some(PendingAction::ResolveConflicts {
+                    workspace_id: workspace.id,
+                    branch: workspace.branch,
+                    conflicted_files,
+                });
+                Ok(())
+            },
+        );
+    }
+
+    fn resolve_conflicts(&mut self, workspace_id: Uuid, branch: String) {
+        self.perform_then(
+            RetryableAction::ResolveConflicts {
+                workspace_id,
+                branch,
+            },
+            |app, result| {
+                result?;
+                app.refresh();
+                app.set_status("Coding agent started on the conflicts");
+                Ok(())
+            },
+        );
+    }
+
+    /// Called on every tick of the event loop; once the coding agent resolving
+    /// rebase conflicts has stopped, checks the branch again and reports.
+    pub fn conflict_resolution_tick(&mut self) {
+        if let Some(check) = self.conflict_check.take_if(|check| check.is_finished()) {
+            // A failed check is tried again on the next one
+            if let Some(Ok(Ok(Some(outcome)))) = check.now_or_never() {
+                self.report_conflict_resolution(outcome);
+            }
+        }
+        let Some(resolution) = self.conflict_resolution.clone() else {
+            return;
+        };
+        let due = self
+            .last_conflict_check
+            .is_none_or(|at| at.elapsed() >= FOLLOW_POLL_INTERVAL);
+        if self.conflict_check.is_some() || !due {
+            return;
+        }
+        self.last_conflict_check = Some(Instant::now());
+        let client = self.client.clone();
+        self.conflict_check = Some(tokio::spawn(async move {
+            let processes = client
+                .list_execution_processes(resolution.session_id)
+                .await?;
+            let agent_status = match processes.iter().find(|p| p.id == resolution.process_id) {
+                Some(process) if process.status == ExecutionProcessStatus::Running => {
+                    return Ok(None);
+                }
+                Some(process) => process.status,
+                // Dropped from the session's history
+                None => ExecutionProcessStatus::Killed,
+            };
+            let statuses = client.get_branch_status(resolution.workspace_id).await?;
+            Ok(Some(ConflictOutcome {
+                agent_status,
+                statuses,
+            }))
+        }));
+    }
+
+    fn report_conflict_resolution(&mut self, outcome: ConflictOutcome) {
+        let Some(resolution) = self.conflict_resolution.take() else {
+            return;
+        };
+        let remaining: usize = outcome
+            .statuses
+            .iter()
+            .map(|s| s.status.conflicted_files.len())
+            .sum();
+        let in_progress = outcome
+            .statuses
+            .iter()
+            .any(|s| s.status.is_rebase_in_progress);
+        if self.selected_workspace.as_ref().map(|w| w.id) == Some(resolution.workspace_id) {
+            self.branch_statuses = outcome.statuses;
+        }
+
+        let branch = resolution.branch;
+        if remaining == 0 && !in_progress {
+            self.set_status(format!(
+                "The coding agent resolved the conflicts of {}; the rebase is finished",
+                branch
+            ));
+            return;
+        }
+        let agent = match outcome.agent_status {
+            ExecutionProcessStatus::Failed => "The coding agent failed",
+            ExecutionProcessStatus::Killed => "The coding agent was stopped",
+            _ => "The coding agent finished",
+        };
+        if remaining > 0 {
+            self.set_error(format!(
+                "{}, but {} conflicted files remain in {}",
+                agent, remaining, branch
+            ));
+        } else {
+            self.set_error(format!(
+                "{}, but the rebase of {} is still in progress",
+                agent, branch
+            ));
+        }
+    }
+
+    /// Ask to stop the selected workspace execution.
+    pub fn request_stop_workspace(&mut self) {
+        if let Some(workspace) = &self.selected_workspace {
+            self.pending_action = Some(PendingAction::StopWorkspace {
+                workspace_id: workspace.id,
+                branch: workspace.branch.clone(),
+            });
+        }
+    }
+
+    fn stop_workspace(&mut self, workspace_id: Uuid) {
+        self.perform_then(
+            RetryableAction::StopWorkspace { workspace_id },
+            |app, result| {
+                result?;
+                app.load_workspace_details();
+                app.set_status("Workspace stopped");
+                Ok(())
+            },
+        );
+    }
+
+    /// Container status of a workspace, if the server reported one.
+    pub fn container_status_for(&self, workspace_id: Uuid) -> Option<&WorkspaceContainerStatus> {
+        self.container_statuses
+            .iter()
+            .find(|s| s.workspace_id == workspace_id)
+    }
+
+    /// Latest progress reported by the agent of a workspace.
+    pub fn progress_for(&self, workspace_id: Uuid) -> Option<&SessionProgress> {
+        self.workspace_progress
+            .iter()
+            .find(|p| p.workspace_id == workspace_id)
+    }
+
+    /// Restart the container of the highlighted workspace, recreating missing worktrees.
+    pub fn restart_container(&mut self) {
+        let workspace_id = self
+            .workspaces
+            .get(self.selected_workspace_index)
+            .map(|w| w.id);
+        if let Some(workspace_id) = workspace_id {
+            self.perform_then(
+                RetryableAction::RestartContainer { workspace_id },
+                |app, result| {
+                    result?;
+                    app.load_workspaces();
+                    app.set_status("Container restarted");
+                    Ok(())
+                },
+            );
+        }
+    }
+
+    /// Rebuild the container of the highlighted workspace from its branch.
+    pub fn rebuild_container(&mut self) {
+        let workspace_id = self
+            .workspaces
+            .get(self.selected_workspace_index)
+            .map(|w| w.id);
+        if let Some(workspace_id) = workspace_id {
+            self.perform_then(
+                RetryableAction::RebuildContainer { workspace_id },
+                |app, result| {
+                    result?;
+                    app.load_workspaces();
+                    app.set_status("Container rebuilt");
+                    Ok(())
+                },
+            );
+        }
+    }
+
+    // =========================================================================
+    // Git Actions
+    // =========================================================================
+
+    /// Preview a merge of the selected workspace and ask whether to go ahead.
+    pub fn request_merge(&mut self) {
+        self.request_git_operation(GitOperationKind::Merge);
+    }
+
+    /// Preview a push of the selected workspace branch and ask whether to go ahead.
+    pub fn request_push(&mut self) {
+        self.request_git_operation(GitOperationKind::Push);
+    }
+
+    /// Preview a rebase of the selected workspace branch and ask whether to go ahead.
+    pub fn request_rebase(&mut self) {
+        self.request_git_operation(GitOperationKind::Rebase);
+    }
+
+    /// Ask to merge the workspace branch into the target branch of every repo. A
+    /// workspace with a single repo gets the merge preview instead.
+    pub fn request_merge_all(&mut self) {
+        if self.branch_statuses.len() < 2 {
+            return self.request_merge();
+        }
+        self.request_git_operation_all(GitOperationKind::Merge);
+    }
+
+    /// Run `operation` in the workspace's only repo, or ask which repo to run it
+    /// in when there are several.
+    fn request_git_operation(&mut self, operation: GitOperationKind) {
+        if self.selected_workspace.is_none() {
+            return;
+        }
+        match self.branch_statuses.as_slice() {
+            [] => {}
+            [status] => {
+                let repo_id = status.repo_id;
+                self.request_git_operation_in(operation, repo_id);
+            }
+            _ => {
+                self.repo_picker = Some(RepoPicker {
+                    operation,
+                    index: 0,
+                })
+            }
+        }
+    }
+
+    pub fn repo_picker_move(&mut self, forward: bool) {
+        // The repos, then "All repos"
+        let count = self.branch_statuses.len() + 1;
+        if let Some(picker) = &mut self.repo_picker {
+            picker.index = if forward {
+                (picker.index + 1) % count
+            } else {
+                (picker.index + count - 1) % count
+            };
+        }
+    }
+
+    /// Close the repo list, running its operation in the selected repo (or all of
+    /// them) if `confirm` is set.
+    pub fn close_repo_picker(&mut self, confirm: bool) {
+        let Some(picker) = self.repo_picker.take() else {
+            return;
+        };
+        if !confirm {
+            return;
+        }
+        match self.branch_statuses.get(picker.index) {
+            Some(status) => {
+                let repo_id = status.repo_id;
+                self.request_git_operation_in(picker.operation, repo_id);
+            }
+            None => self.request_git_operation_all(picker.operation),
+        }
+    }
+
+    /// Preview `operation` in one repo of the selected workspace and ask whether
+    /// to go ahead.
+    fn request_git_operation_in(&mut self, operation: GitOperationKind, repo_id: Uuid) {
+        let Some(workspace_id) = self.selected_workspace.as_ref().map(|w| w.id) else {
+            return;
+        };
+        let client = self.client.clone();
+        match operation {
+            GitOperationKind::Merge => self.request_git_action(
+                RetryableAction::Merge {
+                    workspace_id,
+                    repo_id,
+                    idempotency_key: self.client.idempotency_key(),
+                    confirm_protected: false,
+                },
+                (workspace_id, repo_id),
+                async move { client.preview_merge(workspace_id, repo_id).await },
+            ),
+            GitOperationKind::Push => self.request_git_action(
+                RetryableAction::Push {
+                    workspace_id,
+                    repo_id,
+                    confirm_protected: false,
+                },
+                (workspace_id, repo_id),
+                async move { client.preview_push(workspace_id, repo_id).await },
+            ),
+            GitOperationKind::Rebase => self.request_git_action(
+                RetryableAction::Rebase {
+                    workspace_id,
+                    repo_id,
+                    new_base: None,
+                    confirm_protected: false,
+                },
+                (workspace_id, repo_id),
+                async move {
+                    client
+                        .preview_rebase(workspace_id, repo_id, None, None)
+                        .await
+                },
+            ),
+        }
+    }
+
+    /// Ask to run `operation` in every repo of the selected workspace.
+    fn request_git_operation_all(&mut self, operation: GitOperationKind) {
+        let Some(workspace) = &self.selected_workspace else {
+            return;
+        };
+        self.pending_action = Some(PendingAction::AllRepos {
+            operation,
+            workspace_id: workspace.id,
+            branch: workspace.branch.clone(),
+            repos: self
+                .branch_statuses
+                .iter()
+                .map(|status| {
+                    (
+                        status.repo_id,
+                        status.repo_name.clone(),
+                        status.status.target_branch_name.clone(),
+                    )
+                })
+                .collect(),
+        });
+    }
+
+    /// Run `operation` in each repo one after another, with each repo's result in
+    /// the batch panel.
+    fn git_operation_all(
+        &mut self,
+        operation: GitOperationKind,
+        workspace_id: Uuid,
+        repos: Vec<(Uuid, String, String)>,
+    ) {
+        let steps: Vec<(String, BatchWork)> = repos
+            .into_iter()
+            .map(|(repo_id, repo_name, target_branch)| {
+                let client = self.client.clone();
+                let (label, work): (String, BatchWork) = match operation {
+                    GitOperationKind::Merge => {
+                        let key = client.idempotency_key();
+                        (
+                            format!("{} → {}", repo_name, target_branch),
+                            Box::pin(async move {
+                                client
+                                    .merge_workspace(workspace_id, repo_id, &key, false)
+                                    .await
+                            }),
+                        )
+                    }
+                    GitOperationKind::Push => (
+                        repo_name,
+                        Box::pin(async move {
+                            client.push_workspace(workspace_id, repo_id, false).await
+                        }),
+                    ),
+                    GitOperationKind::Rebase => (
+                        format!("{} onto {}", repo_name, target_branch),
+                        Box::pin(async move {
+                            client
+                                .rebase_workspace(workspace_id, repo_id, None, None, false)
+                                .await
+                        }),
+                    ),
+                };
+                (label, work)
+            })
+            .collect();
+        let title = match operation {
+            GitOperationKind::Merge => "Merging all repos",
+            GitOperationKind::Push => "Pushing all repos",
+            GitOperationKind::Rebase => "Rebasing all repos",
+        };
+        self.start_batch(Batch::new(title, steps));
+    }
+
+    fn request_git_action(
+        &mut self,
+        action: RetryableAction,
+        (workspace_id, repo_id): (Uuid, Uuid),
+        preview: impl Future<Output = Result<GitOperationPreview>> + Send + 'static,
+    ) {
+        self.clear_messages();
+        self.spawn_request("Previewing changes...", preview, move |app, preview| {
+            // Only ask if the workspace is still on screen with nothing else open
+            if app.view != View::WorkspaceDetail || app.has_popup() {
+                return Ok(());
+            }
+            let Some(workspace) = app
+                .selected_workspace
+                .as_ref()
+                .filter(|w| w.id == workspace_id)
+            else {
+                return Ok(());
+            };
+            let Some(status) = app.branch_statuses.iter().find(|s| s.repo_id == repo_id) else {
+                return Ok(());
+            };
+            app.pending_action = Some(PendingAction::GitOperation {
+                action: Box::new(action),
+                branch: workspace.branch.clone(),
+                target_branch: status.status.target_branch_name.clone(),
+                // Servers without the preview endpoints just get the question
+                preview: preview.ok(),
+            });
+            Ok(())
+        });
+    }
+
+    /// Open the popup for the branch to rebase the workspace shown onto, loading
+    /// the branches of its repos to check the name against.
+    pub fn start_rebase_onto(&mut self) {
+        let Some(workspace) = &self.selected_workspace else {
+            return;
+        };
+        if self.branch_statuses.is_empty() {
+            self.set_error("The workspace's repos haven't loaded yet");
+            return;
+        }
+        let repos: Vec<(Uuid, String, String)> = self
+            .branch_statuses
+            .iter()
+            .map(|status| {
+                (
+                    status.repo_id,
+                    status.repo_name.clone(),
+                    status.status.target_branch_name.clone(),
+                )
+            })
+            .collect();
+        let missing: Vec<Uuid> = repos
+            .iter()
+            .map(|(repo_id, _, _)| *repo_id)
+            .filter(|repo_id| !self.repo_branches_cache.iter().any(|(id, _)| id == repo_id))
+            .collect();
+        self.rebase_form = Some(RebaseForm {
+            workspace_id: workspace.id,
+            branch: workspace.branch.clone(),
+            repos,
+            repo_index: 0,
+            new_base: String::new(),
+        });
+        if missing.is_empty() {
+            return;
+        }
+        let client = self.client.clone();
+        self.spawn_request(
+            "Loading branches...",
+            async move {
+                let mut loaded = Vec::new();
+                for repo_id in missing {
+                    loaded.push((repo_id, client.get_repo_branches(repo_id).await?));
+                }
+                anyhow::Ok(loaded)
+            },
+            |app, loaded| {
+                for (repo_id, branches) in loaded? {
+                    if !app.repo_branches_cache.iter().any(|(id, _)| *id == repo_id) {
+                        app.repo_branches_cache.push((repo_id, branches));
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+
+    pub fn cancel_rebase_onto(&mut self) {
+        self.rebase_form = None;
+    }
+
+    /// Switch the rebase popup to the next or previous repo of the workspace.
+    pub fn rebase_form_step_repo(&mut self, forward: bool) {
+        let Some(form) = &mut self.rebase_form else {
+            return;
+        };
+        let count = form.repos.len();
+        form.repo_index = if forward {
+            (form.repo_index + 1) % count
+        } else {
+            (form.repo_index + count - 1) % count
+        };
+    }
+
+    /// What is wrong with the branch typed in the rebase popup, if anything.
+    pub fn rebase_form_error(&self) -> Option<String> {
+        let form = self.rebase_form.as_ref()?;
+        let new_base = form.new_base.trim();
+        if new_base.is_empty() {
+            return Some("Type the branch to rebase onto".to_string());
+        }
+        if let Some(problem) = branch_name_problem(new_base) {
+            return Some(format!("Not a valid branch name: {}", problem));
+        }
+        if new_base == form.branch {
+            return Some("That is the workspace's own branch".to_string());
+        }
+        let (repo_id, repo_name, _) = form.repos.get(form.repo_index)?;
+        // Until the branches have loaded the server has the last word
+        let (_, branches) = self
+            .repo_branches_cache
+            .iter()
+            .find(|(id, _)| id == repo_id)?;
+        (!branches.iter().any(|b| b.name == new_base))
+            .then(|| format!("{} is not a branch of {}", new_base, repo_name))
+    }
+
+    /// Ask to rebase onto the branch typed in the popup, once it is valid.
+    pub fn submit_rebase_onto(&mut self) {
+        if let Some(error) = self.rebase_form_error() {
+            self.set_error(error);
+            return;
+        }
+        let Some(form) = self.rebase_form.take() else {
+            return;
+        };
+        let Some((repo_id, _, _)) = form.repos.get(form.repo_index).cloned() else {
+            return;
+        };
+        let workspace_id = form.workspace_id;
+        let new_base = form.new_base.trim().to_string();
+        let client = self.client.clone();
+        let preview_base = new_base.clone();
+        self.request_git_action(
+            RetryableAction::Rebase {
+                workspace_id,
+                repo_id,
+                new_base: Some(new_base),
+                confirm_protected: false,
+            },
+            (workspace_id, repo_id),
+            async move {
+                client
+                    .preview_rebase(workspace_id, repo_id, None, Some(preview_base))
+                    .await
+            },
+        );
+    }
+
+    /// Open the worktree of the workspace shown in the editor.
+    pub fn open_in_editor(&mut self) {
+        let Some(workspace) = &self.selected_workspace else {
+            return;
+        };
+        let Some(dir) = editor::workspace_dir(workspace) else {
+            self.set_error("The workspace has no worktree yet");
+            return;
+        };
+        // The server may be on another machine
+        if !dir.is_dir() {
+            self.set_error(format!("{} isn't on this machine", dir.display()));
+            return;
+        }
+        self.editor_request = Some(dir);
+    }
+
+    /// Report how the editor started by [`Self::open_in_editor`] exited, and
+    /// reload the workspace for the changes made in it.
+    pub fn editor_closed(&mut self, command_line: &str, result: io::Result<ExitStatus>) {
+        match result {
+            Ok(status) => {
+                // Reloading clears the status bar, so the error goes up after it
+                self.load_workspace_details();
+                if !status.success() {
+                    self.set_error(format!("{} exited with {}", command_line, status));
+                }
+            }
+            Err(e) => self.set_error(format!("Failed to start {}: {}", command_line, e)),
+        }
+    }
+
+    /// Open the pull request popup for the workspace shown, with the title and
+    /// body taken from its task.
+    pub fn start_pull_request(&mut self) {
+        let Some(workspace) = &self.selected_workspace else {
+            return;
+        };
+        if self.branch_statuses.is_empty() {
+            self.set_error("The workspace's repos haven't loaded yet");
+            return;
+        }
+        let task = self
+            .selected_task
+            .as_ref()
+            .map(|t| &t.task)
+            .filter(|t| t.id == workspace.task_id);
+        self.pull_request_form = Some(PullRequestForm {
+            workspace_id: workspace.id,
+            branch: workspace.branch.clone(),
+            repos: self
+                .branch_statuses
+                .iter()
+                .map(|status| {
+                    (
+                        status.repo_id,
+                        status.repo_name.clone(),
+                        status.status.target_branch_name.clone(),
+                    )
+                })
+                .collect(),
+            repo_index: 0,
+            title: task.map_or_else(|| workspace.branch.clone(), |t| t.title.clone()),
+            body: task.and_then(|t| t.description.clone()).unwrap_or_default(),
+            draft: false,
+            field: PullRequestField::Title,
+        });
+    }
+
+    pub fn cancel_pull_request(&mut self) {
+        self.pull_request_form = None;
+    }
+
+    /// Push the workspace branch and open the pull request written in the popup,
+    /// copying its URL. The popup comes back if the server can't open it.
+    pub fn create_pull_request(&mut self) {
+        let Some(form) = &self.pull_request_form else {
+            return;
+        };
+        if form.title.trim().is_empty() {
+            self.set_error("The pull request needs a title");
+            return;
+        }
+        let Some((repo_id, repo_name, _)) = form.repos.get(form.repo_index).cloned() else {
+            return;
+        };
+        let open = self
+            .branch_statuses
+            .iter()
+            .filter(|status| status.repo_id == repo_id)
+            .flat_map(|status| status.status.pull_requests())
+            .find(|pr| pr.status == "open");
+        if let Some(pr) = open {
+            self.set_error(format!(
+                "{} already has pull request #{}: {}",
+                repo_name, pr.number, pr.url
+            ));
+            return;
+        }
+        let Some(form) = self.pull_request_form.take() else {
+            return;
+        };
+        let body = form.body.trim();
+        let payload = CreatePrRequest {
+            title: form.title.trim().to_string(),
+            body: (!body.is_empty()).then(|| body.to_string()),
+            target_branch: None,
+            draft: Some(form.draft),
+            repo_id,
+        };
+        let client = self.client.clone();
+        let workspace_id = form.workspace_id;
+        self.clear_messages();
+        self.spawn_request(
+            "Creating pull request...",
+            async move { client.create_pr(workspace_id, &payload).await },
+            move |app, result| {
+                let on_screen = app.view == View::WorkspaceDetail
+                    && app.selected_workspace.as_ref().map(|w| w.id) == Some(workspace_id);
+                let url = match result {
+                    Ok(url) => url,
+                    Err(e) => {
+                        if on_screen && !app.has_popup() {
+                            app.pull_request_form = Some(form);
+                        }
+                        return Err(e);
+                    }
+                };
+                match clipboard::copy(&url) {
+                    Ok(()) => app.set_status(format!("Opened {} (copied)", url)),
+                    Err(_) => app.set_status(format!("Opened {}", url)),
+                }
+                if on_screen {
+                    app.load_workspace_details();
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// Run a merge, push or rebase, asking for confirmation and holding off if the
+    /// server reports that it writes to a protected branch.
+    fn perform_git_action(&mut self, action: RetryableAction) {
+        self.perform_then(action.clone(), move |app, result| {
+            if let Err(err) = result {
+                let violation = err
+                    .chain()
+                    .find_map(|e| e.downcast_ref::<ApiError>())
+                    .and_then(ApiError::protected_branch_violation)
+                    .filter(|violation| violation.mode == ProtectionMode::Confirm);
+                let Some(violation) = violation else {
+                    return Err(err);
+                };
+                // Not a failure to retry; the modal decides what happens next
+                app.failed_action = None;
+                app.clear_messages();
+                app.pending_action = Some(PendingAction::ProtectedBranch {
+                    action: Box::new(
+                        action.with_protection_confirmed(app.client.idempotency_key()),
+                    ),
+                    branch: violation.branch,
+                    pattern: violation.pattern,
+                });
+                return Ok(());
+            }
+
+            app.load_workspace_details();
+            app.set_status(match action {
+                RetryableAction::Merge { .. } => "Merged successfully",
+                RetryableAction::Push { .. } => "Pushed successfully",
+                _ => "Rebased successfully",
+            });
+            Ok(())
+        });
+    }
+
+    // =========================================================================
+    // Attempt Creation
+    // =========================================================================
+
+    /// Initialize the create attempt form.
+    pub fn init_create_attempt(&mut self) {
+        // Reset form state
+        self.attempt_executor_index = 0;
+        self.attempt_variant = None;
+        self.attempt_limits.clear();
+        self.attempt_branch.clear();
+        self.attempt_tasks.clear();
+        self.attempt_repo_branches.clear();
+        self.attempt_selected_field = 0;
+        self.attempt_branch_query.clear();
+        self.repo_branches_cache.clear();
+
+        let project_id = self.selected_project.as_ref().map(|p| p.id);
+        let client = self.client.clone();
+        self.clear_messages();
+        self.spawn_request(
+            "Loading branches...",
+            async move {
+                // An older server without the health endpoint simply leaves every
+                // executor enabled
+                let health = client.get_executor_health().await.unwrap_or_default();
+                // Without the profiles the variant falls back to free text
+                let variants = client.get_executor_variants().await.unwrap_or_default();
+                let mut repos = Vec::new();
+                if let Some(project_id) = project_id {
+                    for repo in client.get_project_repositories(project_id).await? {
+                        let branches = client.get_repo_branches(repo.id).await;
+                        repos.push((repo, branches));
+                    }
+                }
+                anyhow::Ok((health, variants, repos))
+            },
+            |app, loaded| {
+                // The create task form picks the same to start the new task with
+                if !matches!(app.view, View::CreateAttempt | View::CreateTask) {
+                    return Ok(());
+                }
+                let (health, variants, repos) = loaded?;
+                app.executor_health = health;
+                app.executor_variants = variants;
+                app.init_attempt_executor();
+                for (repo, branches) in repos {
+                    match branches {
+                        Ok(branches) => {
+                            // Initialize with first branch (or main/master if available)
+                            let default_branch = branches
+                                .iter()
+                                .find(|b| b.name == "main" || b.name == "master")
+                                .map(|b| b.name.clone())
+                                .or_else(|| branches.first().map(|b| b.name.clone()))
+                                .unwrap_or_else(|| "main".to_string());
+                            app.repo_branches_cache.push((repo.id, branches));
+                            app.attempt_repo_branches.push((repo.id, default_branch));
+                        }
+                        Err(e) => {
+                            app.set_error(format!(
+                                "Failed to load branches for {}: {}",
+                                repo.name, e
+                            ));
+                            // Still add repo with empty branch
+                            app.attempt_repo_branches
+                                .push((repo.id, "main".to_string()));
+                        }
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// Pick the configured default executor, if it is available, else the first
+    /// that is.
+    fn init_attempt_executor(&mut self) {
+        let executors = self.available_executors();
+        if let Some(index) = executors
+            .iter()
+            .position(|executor| {
+                Some(*executor) == self.config.default_executor
+                    && self.is_executor_available(*executor)
+            })
+            .or_else(|| {
+                executors
+                    .iter()
+                    .position(|executor| self.is_executor_available(*executor))
+            })
+        {
+            self.attempt_executor_index = index;
+        }
+    }
+
+    /// Executors offered for attempts and follow-ups: those the server's
+    /// profiles configure, in their order, or every executor the CLI knows while
+    /// the profiles can't be loaded.
+    pub fn available_executors(&self) -> Vec<BaseCodingAgent> {
+        let mut executors = Vec::new();
+        for variant in &self.executor_variants {
+            // Executors newer than the CLI can't be started from it
+            if let Ok(executor) = parse_executor(&variant.executor)
+                && !executors.contains(&executor)
+            {
+                executors.push(executor);
+            }
+        }
+        if executors.is_empty() {
+            FALLBACK_EXECUTORS.to_vec()
+        } else {
+            executors
+        }
+    }
+
+    /// Get the reported health for an executor, if known.
+    pub fn executor_health_for(&self, executor: BaseCodingAgent) -> Option<&ExecutorHealth> {
+        self.executor_health.iter().find(|h| h.executor == executor)
+    }
+
+    /// Whether an executor can be picked for a new attempt.
+    pub fn is_executor_available(&self, executor: BaseCodingAgent) -> bool {
+        self.executor_health_for(executor)
+            .map(|h| h.available)
+            .unwrap_or(true)
+    }
+
+    /// Known variants of the executor at `index` in `available_executors`.
+    fn variant_options(&self, index: usize) -> Vec<&ExecutorVariant> {
+        let Some(executor) = self.available_executors().get(index).copied() else {
+            return Vec::new();
+        };
+        self.executor_variants
+            .iter()
+            .filter(|v| v.executor == executor.as_str())
+            .collect()
+    }
+
+    /// Known variants of the executor highlighted in the create attempt form.
+    pub fn attempt_variant_options(&self) -> Vec<&ExecutorVariant> {
+        self.variant_options(self.attempt_executor_index)
+    }
+
+    /// Whether `variant` is the one chosen in the create attempt form.
+    pub fn is_attempt_variant(&self, variant: &ExecutorVariant) -> bool {
+        is_chosen_variant(self.attempt_variant.as_deref(), variant)
+    }
+
+    /// Number of fields in the create attempt form.
+    pub fn attempt_field_count(&self) -> usize {
+        4 + self.attempt_repo_branches.len()
+    }
+
+    /// What is wrong with the value of a create attempt field, checked as it is
+    /// typed so the form can say so before anything is sent.
+    pub fn attempt_field_error(&self, field: usize) -> Option<String> {
+        let repo_name = |repo_id: Uuid| {
+            self.project_repos
+                .iter()
+                .find(|r| r.id == repo_id)
+                .map_or("Unknown", |r| r.name.as_str())
+        };
+        let cached = |repo_id: Uuid| {
+            self.repo_branches_cache
+                .iter()
+                .find(|(id, _)| *id == repo_id)
+                .map(|(_, branches)| branches)
+        };
+        match field {
+            2 => ResourceLimits::parse(&self.attempt_limits)
+                .err()
+                .map(|e| format!("Invalid limits: {}", e)),
+            // Attempts for several tasks each get a generated branch
+            3 if self.attempt_tasks.is_empty() => {
+                let branch = self.attempt_branch.trim();
+                if branch.is_empty() {
+                    return None;
+                }
+                if let Some(problem) = branch_name_problem(branch) {
+                    return Some(format!("Not a valid branch name: {}", problem));
+                }
+                for (repo_id, target) in &self.attempt_repo_branches {
+                    if target == branch {
+                        return Some(format!(
+                            "{} is the base branch of {}",
+                            branch,
+                            repo_name(*repo_id)
+                        ));
+                    }
+                    if cached(*repo_id).is_some_and(|bs| bs.iter().any(|b| b.name == branch)) {
+                        return Some(format!(
+                            "{} already exists in {}",
+                            branch,
+                            repo_name(*repo_id)
+                        ));
+                    }
+                }
+                None
+            }
+            field if field >= 4 => {
+                let (repo_id, branch) = self.attempt_repo_branches.get(field - 4)?;
+                // Without the repo's branches there is nothing to check against
+                let branches = cached(*repo_id)?;
+                (!branches.iter().any(|b| b.name == *branch))
+                    .then(|| format!("{} is not a branch of {}", branch, repo_name(*repo_id)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Focus the next field in the create attempt form.
+    pub fn next_attempt_field(&mut self) {
+        self.attempt_selected_field =
+            (self.attempt_selected_field + 1) % self.attempt_field_count();
+    }
+
+    /// Step the value of the focused attempt field (executor, variant or base branch).
+    pub fn cycle_attempt_value(&mut self, forward: bool) {
+        match self.attempt_selected_field {
+            0 => {
+                let count = self.available_executors().len();
+                self.attempt_executor_index = if forward {
+                    (self.attempt_executor_index + 1) % count
+                } else {
+                    (self.attempt_executor_index + count - 1) % count
+                };
+                self.attempt_variant = None;
+            }
+            1 => {
+                if let Some(variant) = step_variant(
+                    &self.attempt_variant_options(),
+                    self.attempt_variant.as_deref(),
+                    forward,
+                ) {
+                    self.attempt_variant = variant;
+                }
+            }
+            2 | 3 => {}
+            field => {
+                let Some((repo_id, branch)) = self.attempt_repo_branches.get_mut(field - 4) else {
+                    return;
+                };
+                let Some((_, branches)) = self
+                    .repo_branches_cache
+                    .iter()
+                    .find(|(id, _)| id == repo_id)
+                else {
+                    return;
+                };
+                if branches.is_empty() {
+                    return;
+                }
+                let count = branches.len();
+                let next = match branches.iter().position(|b| b.name == *branch) {
+                    Some(i) if forward => (i + 1) % count,
+                    Some(i) => (i + count - 1) % count,
+                    None => 0,
+                };
+                *branch = branches[next].name.clone();
+            }
+        }
+    }
+
+    /// Start typing the base branch of the focused repository, beginning with
+    /// `first` if given.
+    pub fn start_attempt_branch_search(&mut self, first: Option<char>) {
+        if self.attempt_selected_field < 4 {
+            return;
+        }
+        self.attempt_branch_query = first.map(String::from).unwrap_or_default();
+        self.attempt_branch_completion = 0;
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Branches of the focused repository matching the typed base branch, best
+    /// match first, with local branches ahead of remote ones that match as well.
+    pub fn attempt_branch_completions(&self) -> Vec<&GitBranch> {
+        let Some((repo_id, _)) = self
+            .attempt_selected_field
+            .checked_sub(4)
+            .and_then(|index| self.attempt_repo_branches.get(index))
+        else {
+            return Vec::new();
+        };
+        let Some((_, branches)) = self
+            .repo_branches_cache
+            .iter()
+            .find(|(id, _)| id == repo_id)
+        else {
+            return Vec::new();
+        };
+        let mut matches: Vec<(i64, &GitBranch)> = branches
+            .iter()
+            .filter_map(|b| Some((fuzzy::score(&self.attempt_branch_query, &b.name)?, b)))
+            .collect();
+        matches.sort_by_key(|(score, b)| (std::cmp::Reverse(*score), b.is_remote));
+        matches
+            .into_iter()
+            .take(MAX_BRANCH_COMPLETIONS)
+            .map(|(_, b)| b)
+            .collect()
+    }
+
+    /// Type into the focused base branch field.
+    pub fn edit_attempt_branch_query(&mut self, edit: impl FnOnce(&mut String)) {
+        edit(&mut self.attempt_branch_query);
+        self.attempt_branch_completion = 0;
+    }
+
+    /// Highlight the next (or previous) completion of the typed base branch.
+    pub fn move_attempt_branch_completion(&mut self, forward: bool) {
+        let count = self.attempt_branch_completions().len();
+        if count == 0 {
+            return;
+        }
+        self.attempt_branch_completion = if forward {
+            (self.attempt_branch_completion + 1) % count
+        } else {
+            (self.attempt_branch_completion + count - 1) % count
+        };
+    }
+
+    /// Stop typing the base branch, taking the highlighted completion, or the
+    /// text as typed when no branch matches it. `keep` false leaves the branch
+    /// as it was.
+    pub fn finish_attempt_branch_search(&mut self, keep: bool) {
+        self.input_mode = InputMode::Normal;
+        let typed = self.attempt_branch_query.trim();
+        let branch = match self
+            .attempt_branch_completions()
+            .get(self.attempt_branch_completion)
+        {
+            Some(completion) => completion.name.clone(),
+            None => typed.to_string(),
+        };
+        self.attempt_branch_query.clear();
+        if !keep || branch.is_empty() {
+            return;
+        }
+        if let Some((_, target)) = self
+            .attempt_selected_field
+            .checked_sub(4)
+            .and_then(|index| self.attempt_repo_branches.get_mut(index))
+        {
+            *target = branch;
+        }
+    }
+
+    /// The executor picked in the attempt form, if it can start an attempt.
+    fn picked_executor(&self) -> std::result::Result<BaseCodingAgent, String> {
+        let executor = self
+            .available_executors()
+            .get(self.attempt_executor_index)
+            .copied()
+            .ok_or("Invalid executor selection")?;
+        if !self.is_executor_available(executor) {
+            let reason = self
+                .executor_health_for(executor)
+                .and_then(|h| h.message.clone())
+                .unwrap_or_else(|| "unavailable".to_string());
+            return Err(format!("{} is unavailable: {}", executor.as_str(), reason));
+        }
+        Ok(executor)
+    }
+
+    /// Create a new attempt for the selected task.
+    pub fn create_attempt(&mut self) {
+        let task_id = self.selected_task.as_ref().map(|t| t.task.id);
+        if task_id.is_none() && self.attempt_tasks.is_empty() {
+            self.set_error("No task selected");
+            return;
+        }
+
+        if self.attempt_repo_branches.is_empty() {
+            self.set_error("No repositories configured for this project");
+            return;
+        }
+
+        let executor = match self.picked_executor() {
+            Ok(executor) => executor,
+            Err(e) => return self.set_error(e),
+        };
+        let executor_profile_id = crate::types::ExecutorProfileId {
+            executor,
+            variant: self.attempt_variant.clone(),
+        };
+        if let Some((field, error)) = (2..self.attempt_field_count())
+            .find_map(|field| Some((field, self.attempt_field_error(field)?)))
+        {
+            self.attempt_selected_field = field;
+            self.set_error(error);
+            return;
+        }
+        let resource_limits = match ResourceLimits::parse(&self.attempt_limits) {
+            Ok(limits) => Some(limits).filter(|l| !l.is_empty()),
+            Err(e) => {
+                self.set_error(format!("Invalid limits: {}", e));
+                return;
+            }
+        };
+
+        let repos: Vec<crate::types::WorkspaceRepoInput> = self
+            .attempt_repo_branches
+            .iter()
+            .map(|(repo_id, branch)| crate::types::WorkspaceRepoInput {
+                repo_id: *repo_id,
+                target_branch: branch.clone(),
+            })
+            .collect();
+        if !self.attempt_tasks.is_empty() {
+            return self.start_attempts(executor_profile_id, repos, resource_limits);
+        }
+
+        self.clear_messages();
+        let payload = crate::types::CreateTaskAttemptBody {
+            task_id: task_id.unwrap(),
+            executor_profile_id,
+            repos,
+            resource_limits,
+            branch: Some(self.attempt_branch.trim().to_string()).filter(|b| !b.is_empty()),
+        };
+
+        let key = self.submit_key();
+        let client = self.client.clone();
+        self.spawn_request(
+            "Creating attempt...",
+            async move { client.create_task_attempt(&payload, &key).await },
+            |app, result| {
+                if let Err(err) = result {
+                    // Offer the compliant name so Enter on the form tries again with it
+                    if let Some(report) = err
+                        .chain()
+                        .find_map(|e| e.downcast_ref::<ApiError>())
+                        .and_then(ApiError::branch_policy_report)
+                    {
+                        app.attempt_branch = report.suggested_branch;
+                        app.attempt_selected_field = 3;
+                    }
+                    return Err(err);
+                }
+                app.load_workspaces();
+                app.set_status("Attempt created successfully");
+                if app.view == View::CreateAttempt {
+                    app.go_back();
+                }
+                Ok(())
+            },
+        );
+    }
+
+    // =========================================================================
+    // Follow-up
+    // =========================================================================
+
+    /// Open the follow-up composer for the selected workspace's most recent session.
+    /// A draft for the same session is kept from the last time the composer was open.
+    pub fn open_follow_up(&mut self) {
+        let Some(session) = self.sessions.first().cloned() else {
+            self.set_error("This workspace has no sessions yet");
+            return;
+        };
+        if self.follow_up_session_id != Some(session.id) {
+            self.follow_up_session_id = Some(session.id);
+            self.follow_up_review = None;
+            self.follow_up_input.clear();
+            self.follow_up_variant = None;
+        }
+        self.follow_up_field = FollowUpField::Prompt;
+
+        let client = self.client.clone();
+        self.clear_messages();
+        self.spawn_request(
+            "Loading executors...",
+            async move {
+                // Same fallbacks as the create attempt form
+                let health = client.get_executor_health().await.unwrap_or_default();
+                let variants = client.get_executor_variants().await.unwrap_or_default();
+                (health, variants)
+            },
+            move |app, (health, variants)| {
+                app.executor_health = health;
+                app.executor_variants = variants;
+
+                let executors = app.available_executors();
+                let index = match session.executor.as_deref() {
+                    Some(name) => executors.iter().position(|e| e.as_str() == name),
+                    None => executors.iter().position(|e| app.is_executor_available(*e)),
+                };
+                app.follow_up_executor_index = index.unwrap_or(0);
+
+                if matches!(app.view, View::WorkspaceDetail | View::Diff) && !app.has_popup() {
+                    app.input_mode = InputMode::Editing;
+                    app.navigate_to(View::FollowUp);
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// The session the follow-up composer sends to.
+    pub fn follow_up_session(&self) -> Option<&Session> {
+        let session_id = self.follow_up_session_id?;
+        self.sessions.iter().find(|s| s.id == session_id)
+    }
+
+    /// Whether the session already has an executor. The server rejects follow-ups
+    /// that switch executors, so only the variant can be changed then.
+    pub fn is_follow_up_executor_fixed(&self) -> bool {
+        self.follow_up_session()
+            .is_some_and(|s| s.executor.is_some())
+    }
+
+    /// Known variants of the executor chosen in the follow-up composer.
+    pub fn follow_up_variant_options(&self) -> Vec<&ExecutorVariant> {
+        self.variant_options(self.follow_up_executor_index)
+    }
+
+    /// Whether `variant` is the one chosen in the follow-up composer.
+    pub fn is_follow_up_variant(&self, variant: &ExecutorVariant) -> bool {
+        is_chosen_variant(self.follow_up_variant.as_deref(), variant)
+    }
+
+    /// Step the value of the focused follow-up field (executor or variant).
+    pub fn cycle_follow_up_value(&mut self, forward: bool) {
+        match self.follow_up_field {
+            FollowUpField::Prompt => {}
+            FollowUpField::Executor => {
+                if self.is_follow_up_executor_fixed() {
+                    return;
+                }
+                let count = self.available_executors().len();
+                self.follow_up_executor_index = if forward {
+                    (self.follow_up_executor_index + 1) % count
+                } else {
+                    (self.follow_up_executor_index + count - 1) % count
+                };
+                self.follow_up_variant = None;
+            }
+            FollowUpField::Variant => {
+                if let Some(variant) = step_variant(
+                    &self.follow_up_variant_options(),
+                    self.follow_up_variant.as_deref(),
+                    forward,
+                ) {
+                    self.follow_up_variant = variant;
+                }
+            }
+        }
+    }
+
+    pub fn follow_up_templates(&self) -> Vec<FollowUpTemplate> {
+        self.config.follow_up_templates()
+    }
+
+    pub fn open_template_picker(&mut self) {
+        self.template_picker = Some(0);
+    }
+
+    pub fn template_picker_move(&mut self, forward: bool) {
+        let count = self.follow_up_templates().len();
+        if let Some(index) = &mut self.template_picker
+            && count > 0
+        {
+            *index = if forward {
+                (*index + 1) % count
+            } else {
+                (*index + count - 1) % count
+            };
+        }
+    }
+
+    /// Close the template list, adding the selected template to the message if
+    /// `insert` is set.
+    pub fn close_template_picker(&mut self, insert: bool) {
+        let Some(index) = self.template_picker.take() else {
+            return;
+        };
+        if !insert {
+            return;
+        }
+        let Some(template) = self.follow_up_templates().into_iter().nth(index) else {
+            return;
+        };
+        let (text, missing) = self.expand_template(&template);
+        let draft = self.follow_up_input.trim_end();
+        self.follow_up_input = if draft.is_empty() {
+            text
+        } else {
+            format!("{}\n\n{}", draft, text)
+        };
+        if template.uses("comments") && !self.diff_comments().is_empty() {
+            self.follow_up_review = self.selected_workspace.as_ref().map(|w| w.id);
+        }
+        self.follow_up_field = FollowUpField::Prompt;
+        self.input_mode = InputMode::Editing;
+        if missing.is_empty() {
+            self.set_status(format!("Added \"{}\"", template.name));
+        } else {
+            let missing: Vec<String> = missing.iter().map(|p| format!("{{{}}}", p)).collect();
+            self.set_status(format!(
+                "Added \"{}\"; nothing to fill in for {}",
+                template.name,
+                missing.join(", ")
+            ));
+        }
+    }
+
+    /// The template's prompt filled in from the selected task and workspace, and
+    /// the placeholders that had nothing to fill in.
+    pub fn expand_template(&self, template: &FollowUpTemplate) -> (String, Vec<&'static str>) {
+        let task = self.selected_task.as_ref().map(|t| &t.task);
+        template.expand(|placeholder| match placeholder {
+            "task" => task.map(|t| t.title.clone()),
+            "description" => task.and_then(|t| t.description.clone()),
+            "branch" => self.selected_workspace.as_ref().map(|w| w.branch.clone()),
+            "comments" => Some(diff::review_notes(self.diff_comments())),
+            _ => None,
+        })
+    }
+
+    /// Send the composed follow-up to the session and return to the workspace.
+    pub fn send_follow_up(&mut self) {
+        let Some(session_id) = self.follow_up_session_id else {
+            self.set_error("No session selected");
+            return;
+        };
+        let prompt = self.follow_up_input.trim();
+        if prompt.is_empty() {
+            self.set_error("Follow-up message cannot be empty");
+            return;
+        }
+        let Some(executor) = self
+            .available_executors()
+            .get(self.follow_up_executor_index)
+            .copied()
+        else {
+            self.set_error("Invalid executor selection");
+            return;
+        };
+        if !self.is_executor_available(executor) {
+            let reason = self
+                .executor_health_for(executor)
+                .and_then(|h| h.message.clone())
+                .unwrap_or_else(|| "unavailable".to_string());
+            self.set_error(format!("{} is unavailable: {}", executor.as_str(), reason));
+            return;
+        }
+
+        let payload = CreateFollowUpAttempt {
+            prompt: prompt.to_string(),
+            executor_profile_id: ExecutorProfileId {
+                executor,
+                variant: self.follow_up_variant.clone(),
+            },
+            retry_process_id: None,
+            force_when_dirty: None,
+            perform_git_reset: None,
+        };
+        self.input_mode = InputMode::Normal;
+        self.clear_messages();
+        let client = self.client.clone();
+        self.spawn_request(
+            "Sending follow-up...",
+            async move { client.send_follow_up(session_id, &payload).await },
+            |app, result| {
+                result?;
+                app.follow_up_input.clear();
+                if let Some(workspace_id) = app.follow_up_review.take() {
+                    app.diff_comments.remove(&workspace_id);
+                }
+                if app.view == View::FollowUp {
+                    app.go_back();
+                }
+                app.load_workspace_details();
+                app.set_status("Follow-up sent; press l to watch the log");
+                Ok(())
+            },
+        );
+    }
+
+    // =========================================================================
+    // Diff Review
+    // =========================================================================
+
+    /// Open the diff of the selected workspace against its target branches.
+    pub fn open_diff(&mut self) {
+        self.navigate_to(View::Diff);
+        self.diff_hunks.clear();
+        self.selected_hunk_index = 0;
+        self.load_diff();
+    }
+
+    fn load_diff(&mut self) {
+        let workspace_id = self.selected_workspace.as_ref().map(|w| w.id);
+        if let Some(workspace_id) = workspace_id {
+            self.perform(RetryableAction::LoadDiff { workspace_id });
+        }
+    }
+
+    pub fn selected_hunk(&self) -> Option<&Hunk> {
+        self.diff_hunks.get(self.selected_hunk_index)
+    }
+
+    /// Review notes on the selected workspace, in the order they were written.
+    pub fn diff_comments(&self) -> &[DiffComment] {
+        self.selected_workspace
+            .as_ref()
+            .and_then(|w| self.diff_comments.get(&w.id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The review note on `hunk`, if it has one.
+    pub fn hunk_comment(&self, hunk: &Hunk) -> Option<&DiffComment> {
+        self.diff_comments().iter().find(|c| c.is_on(hunk))
+    }
+
+    /// Start writing a note on the selected hunk, or editing the one it has.
+    pub fn start_diff_comment(&mut self) {
+        let Some(hunk) = self.selected_hunk() else {
+            return;
+        };
+        self.diff_comment_input = self
+            .hunk_comment(hunk)
+            .map(|c| c.comment.clone())
+            .unwrap_or_default();
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Attach the typed note to the selected hunk; an empty note removes it.
+    pub fn save_diff_comment(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let comment = mem::take(&mut self.diff_comment_input).trim().to_string();
+        if comment.is_empty() {
+            self.delete_diff_comment();
+            return;
+        }
+        let (Some(workspace_id), Some(hunk)) = (
+            self.selected_workspace.as_ref().map(|w| w.id),
+            self.diff_hunks.get(self.selected_hunk_index),
+        ) else {
+            return;
+        };
+        let comments = self.diff_comments.entry(workspace_id).or_default();
+        match comments.iter_mut().find(|c| c.is_on(hunk)) {
+            Some(existing) => existing.comment = comment,
+            None => comments.push(DiffComment::new(hunk, comment)),
+        }
+        let count = comments.len();
+        self.set_status(format!(
+            "{} review note{}; press S to send them to the agent",
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+
+    pub fn cancel_diff_comment(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.diff_comment_input.clear();
+    }
+
+    /// Remove the note on the selected hunk.
+    pub fn delete_diff_comment(&mut self) {
+        let (Some(workspace_id), Some(hunk)) = (
+            self.selected_workspace.as_ref().map(|w| w.id),
+            self.diff_hunks.get(self.selected_hunk_index),
+        ) else {
+            return;
+        };
+        if let Some(comments) = self.diff_comments.get_mut(&workspace_id) {
+            comments.retain(|c| !c.is_on(hunk));
+        }
+    }
+
+    /// Put the review notes into a follow-up for the agent, to look over before
+    /// sending. They are kept until the follow-up is sent.
+    pub fn send_review(&mut self) {
+        let Some(workspace_id) = self.selected_workspace.as_ref().map(|w| w.id) else {
+            return;
+        };
+        if self.diff_comments().is_empty() {
+            self.set_error("No review notes yet; press c on a hunk to add one");
+            return;
+        }
+        let prompt = diff::review_prompt(self.diff_comments());
+        self.open_follow_up();
+        if self.follow_up_session_id != self.sessions.first().map(|s| s.id) {
+            return;
+        }
+        // Keep a draft the user started, unless it is an earlier copy of the notes
+        let draft = self.follow_up_input.trim();
+        self.follow_up_input = if draft.is_empty() || self.follow_up_review.is_some() {
+            prompt
+        } else {
+            format!("{}\n\n{}", draft, prompt)
+        };
+        self.follow_up_review = Some(workspace_id);
+    }
+
+    // =========================================================================
+    // Planner Settings
+    // =========================================================================
+
+    /// Open the planner settings form with the server's current limits.
+    pub fn open_planner_settings(&mut self) {
+        let view = self.view;
+        let client = self.client.clone();
+        self.clear_messages();
+        self.spawn_request(
+            "Loading planner settings...",
+            async move { client.get_planner_config().await },
+            move |app, config| {
+                let config = config?;
+                if app.view != view || app.has_popup() {
+                    return Ok(());
+                }
+                app.planner_inputs = [
+                    config.team_threshold.to_string(),
+                    config.max_subtasks.to_string(),
+                    config.max_parallel_workers.to_string(),
+                ];
+                app.planner_field = PlannerField::TeamThreshold;
+                app.navigate_to(View::PlannerSettings);
+                Ok(())
+            },
+        );
+    }
+
+    /// The input of a planner settings field.
+    pub fn planner_input(&self, field: PlannerField) -> &str {
+        &self.planner_inputs[field as usize]
+    }
+
+    /// The input of the focused planner settings field.
+    pub fn planner_input_mut(&mut self) -> &mut String {
+        &mut self.planner_inputs[self.planner_field as usize]
+    }
+
+    /// Save the planner settings form to the server.
+    pub fn save_planner_settings(&mut self) {
+        let mut values = [0; 3];
+        for field in PlannerField::ALL {
+            match self.planner_input(field).trim().parse::<i32>() {
+                Ok(value) if value >= 1 => values[field as usize] = value,
+                _ => {
+                    self.planner_field = field;
+                    self.set_error(format!("{} must be a number of at least 1", field.label()));
+                    return;
+                }
+            }
+        }
+        let [team_threshold, max_subtasks, max_parallel_workers] = values;
+
+        self.input_mode = InputMode::Normal;
+        self.clear_messages();
+        let config = PlannerConfig {
+            team_threshold,
+            max_subtasks,
+            max_parallel_workers,
+        };
+        let client = self.client.clone();
+        self.spawn_request(
+            "Saving planner settings...",
+            async move { client.update_planner_config(&config).await },
+            |app, result| {
+                result?;
+                if app.view == View::PlannerSettings {
+                    app.go_back();
+                }
+                app.set_status("Planner settings saved");
+                Ok(())
+            },
+        );
+    }
+
+    // =========================================================================
+    // Retention Settings
+    // =========================================================================
+
+    /// Open the workspace retention form with the server's current policy and
+    /// what it would do right now.
+    pub fn open_retention_settings(&mut self) {
+        let view = self.view;
+        let client = self.client.clone();
+        self.clear_messages();
+        let request = async move {
+            let policy = client.get_retention_policy().await?;
+            let preview = client.preview_retention_policy(&policy).await?;
+            anyhow::Ok((policy, preview))
+        };
+        self.spawn_request(
+            "Loading retention policy...",
+            request,
+            move |app, loaded| {
+                let (policy, preview) = loaded?;
+                if app.view != view || app.has_popup() {
+                    return Ok(());
+                }
+                app.retention_archive_days = policy
+                    .archive_merged_after_days
+                    .map(|days| days.to_string())
+                    .unwrap_or_default();
+                app.retention_delete_cancelled = policy.delete_cancelled_worktrees;
+                app.retention_purge_days = policy
+                    .purge_trash_after_days
+                    .map(|days| days.to_string())
+                    .unwrap_or_default();
+                app.retention_field = RetentionField::ArchiveMergedAfterDays;
+                app.retention_preview = preview;
+                app.retention_previewed = Some(policy);
+                app.navigate_to(View::RetentionSettings);
+                Ok(())
+            },
+        );
+    }
+
+    /// The policy as filled in on the form.
+    pub fn retention_policy_input(&self) -> Result<RetentionPolicy, String> {
+        Ok(RetentionPolicy {
+            archive_merged_after_days: retention_days(
+                &self.retention_archive_days,
+                RetentionField::ArchiveMergedAfterDays,
+            )?,
+            delete_cancelled_worktrees: self.retention_delete_cancelled,
+            purge_trash_after_days: retention_days(
+                &self.retention_purge_days,
+                RetentionField::PurgeTrashAfterDays,
+            )?,
+        })
+    }
+
+    /// The text of the focused days field of the retention form.
+    pub fn retention_days_input(&mut self) -> &mut String {
+        match self.retention_field {
+            RetentionField::PurgeTrashAfterDays => &mut self.retention_purge_days,
+            _ => &mut self.retention_archive_days,
+        }
+    }
+
+    /// Whether the preview shows what the policy on the form would do.
+    pub fn is_retention_preview_current(&self) -> bool {
+        self.retention_policy_input().ok() == self.retention_previewed
+    }
+
+    pub fn toggle_retention_delete_cancelled(&mut self) {
+        self.retention_delete_cancelled = !self.retention_delete_cancelled;
+        self.preview_retention_policy();
+    }
+
+    /// Fetch what the policy on the form would do, unless the preview already shows it.
+    pub fn preview_retention_policy(&mut self) {
+        let policy = match self.retention_policy_input() {
+            Ok(policy) => policy,
+            Err(e) => return self.set_error(e),
+        };
+        if self.retention_previewed.as_ref() == Some(&policy) {
+            return;
+        }
+        self.clear_messages();
+        let client = self.client.clone();
+        self.spawn_request(
+            "Previewing retention policy...",
+            {
+                let policy = policy.clone();
+                async move { client.preview_retention_policy(&policy).await }
+            },
+            move |app, preview| {
+                let preview = preview?;
+                // Edited again while this was on its way
+                if app.view != View::RetentionSettings
+                    || app.retention_policy_input().ok().as_ref() != Some(&policy)
+                {
+                    return Ok(());
+                }
+                app.retention_preview = preview;
+                app.retention_previewed = Some(policy);
+                Ok(())
+            },
+        );
+    }
+
+    /// Save the retention form to the server.
+    pub fn save_retention_settings(&mut self) {
+        let policy = match self.retention_policy_input() {
+            Ok(policy) => policy,
+            Err(e) => {
+                // Focus the field in error, checked in form order
+                self.retention_field = match retention_days(
+                    &self.retention_archive_days,
+                    RetentionField::ArchiveMergedAfterDays,
+                ) {
+                    Ok(_) => RetentionField::PurgeTrashAfterDays,
+                    Err(_) => RetentionField::ArchiveMergedAfterDays,
+                };
+                return self.set_error(e);
+            }
+        };
+
+        self.input_mode = InputMode::Normal;
+        self.clear_messages();
+        let client = self.client.clone();
+        self.spawn_request(
+            "Saving retention policy...",
+            async move { client.update_retention_policy(&policy).await },
+            |app, result| {
+                result?;
+                if app.view == View::RetentionSettings {
+                    app.go_back();
+                }
+                app.set_status("Retention policy saved");
+                Ok(())
+            },
+        );
+    }
+
+    // =========================================================================
+    // Follow Mode
+    // =========================================================================
+
+    /// Turn follow mode on or off for the selected project.
+    pub fn toggle_follow(&mut self) {
+        if self.follow_mode {
+            self.stop_following();
+            return;
+        }
+        if self.selected_project.is_none() {
+            self.set_error("Open a project to follow its workspaces");
+            return;
+        }
+        self.follow_mode = true;
+        self.followed_activity_at = None;
+        self.set_status("Following the most recently active workspace");
+        self.poll_follow();
+    }
+
+    pub fn stop_following(&mut self) {
+        self.follow_mode = false;
+        self.set_status("Follow mode off");
+    }
+
+    /// Called on every tick of the event loop; polls for activity when due.
+    pub fn follow_tick(&mut self) {
+        let due = self
+            .last_follow_poll
+            .is_none_or(|at| at.elapsed() >= FOLLOW_POLL_INTERVAL);
+        // The previous poll, or whatever the user asked for, comes first
+        if !self.follow_mode || self.is_busy() || self.is_loading() || !due {
+            return;
+        }
+        self.poll_follow();
+    }
+
+    /// Whether a popup has the keyboard.
+    pub fn has_popup(&self) -> bool {
+        self.show_error_detail
+            || !self.link_hints.is_empty()
+            || self.pending_action.is_some()
+            || self.show_jumper
+            || self.palette.is_some()
+            || self.show_metrics
+            || self.show_notifications
+            || self.theme_picker.is_some()
+            || self.executor_picker.is_some()
+            || self.template_picker.is_some()
+            || self.workspace_rename.is_some()
+            || self.workspace_notes_editor.is_some()
+            || self.metadata_editor.is_some()
+            || self.repo_picker.is_some()
+            || self.pull_request_form.is_some()
+            || self.rebase_form.is_some()
+    }
+
+    /// Whether the user is typing, reading a popup or filling in a form, so the
+    /// view shouldn't change under them.
+    fn is_busy(&self) -> bool {
+        self.has_popup()
+            || self.input_mode == InputMode::Editing
+            || matches!(
+                self.view,
+                View::CreateTask
+                    | View::CreateProject
+                    | View::CreateAttempt
+                    | View::FollowUp
+                    | View::PlannerSettings
+                    | View::RetentionSettings
+                    | View::SessionLogs
+                    | View::Diff
+                    | View::Help
+            )
+    }
+
+    /// Called on every tick of the event loop; merges a finished background reload
+    /// and starts the next one once the configured refresh interval has passed.
+    ///
+    /// The reload runs on its own task so input is never held up by the server.
+    pub fn auto_refresh_tick(&mut self) -> Result<()> {
+        let result = self.finish_background_refresh();
+
+        let due = self.refresh_requested
+            || self.config.refresh_interval().is_some_and(|interval| {
+                self.last_auto_refresh
+                    .is_none_or(|at| at.elapsed() >= interval)
+            });
+        // Follow mode keeps its own view up to date, and a request in flight may be
+        // about to change what a reload would show
+        if self.background_refresh.is_some()
+            || self.follow_mode
+            || self.is_busy()
+            || self.is_loading()
+            || !due
+        {
+            return result;
+        }
+        let Some(action) = self.auto_refresh_action() else {
+            return result;
+        };
+        self.last_auto_refresh = Some(Instant::now());
+        self.refresh_requested = false;
+
+        let client = self.client.clone();
+        let handle = tokio::spawn({
+            let action = action.clone();
+            async move { Loaded::fetch(&client, &action).await }
+        });
+        self.background_refresh = Some(BackgroundRefresh { action, handle });
+        result
+    }
+
+    /// Called on every tick of the event loop; checks the server's health when due
+    /// and keeps the answer of the last check for the status bar.
+    pub fn health_tick(&mut self) {
+        if let Some(check) = self.health_check.take_if(|check| check.is_finished()) {
+            self.server_reachable = Some(matches!(check.now_or_never(), Some(Ok(true))));
+            self.startup.record(StartupPhase::HealthCheck);
+        }
+        if self.startup_deferred {
+            return;
+        }
+        let due = self
+            .last_health_check
+            .is_none_or(|at| at.elapsed() >= HEALTH_CHECK_INTERVAL);
+        if self.health_check.is_some() || !due {
+            return;
+        }
+        self.last_health_check = Some(Instant::now());
+        let client = self.client.clone();
+        self.health_check = Some(tokio::spawn(async move {
+            matches!(client.health_check().await, Ok(true))
+        }));
+    }
+
+    /// Merge the result of the background reload if it has finished and still
+    /// belongs to what is on screen.
+    fn finish_background_refresh(&mut self) -> Result<()> {
+        let Some(refresh) = self
+            .background_refresh
+            .take_if(|refresh| refresh.handle.is_finished())
+        else {
+            return Ok(());
+        };
+        // The user may have moved elsewhere or opened a form in the meantime; the
+        // next reload will pick up where they are
+        if self.auto_refresh_action().as_ref() != Some(&refresh.action) || self.is_busy() {
+            return Ok(());
+        }
+        // Not through `perform`, which would flash a status message on every reload
+        if let Some(result) = refresh.handle.now_or_never()
+            && let Some(loaded) = result??
+        {
+            self.apply_loaded(loaded);
+        }
+        Ok(())
+    }
+
+    fn cancel_background_refresh(&mut self) {
+        if let Some(refresh) = self.background_refresh.take() {
+            refresh.handle.abort();
+        }
+    }
+
+    /// Start following the server's event stream.
+    pub fn subscribe_to_server_events(&mut self) {
+        self.server_events = Some(events::subscribe(self.client.clone()));
+    }
+
+    /// Apply the changes the server has reported since the last tick. Tasks are
+    /// updated on the board directly; other changes reload the current view.
+    pub fn poll_server_events(&mut self) {
+        let Some(receiver) = self.server_events.as_mut() else {
+            return;
+        };
+        let mut received = Vec::new();
+        let closed = loop {
+            match receiver.try_recv() {
+                Ok(event) => received.push(event),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if closed {
+            self.server_events = None;
+            self.live_updates = false;
+        }
+
+        let project_id = self.selected_project.as_ref().map(|p| p.id);
+        for event in received {
+            match event {
+                ServerEvent::Connected => {
+                    self.live_updates = true;
+                    // Catch up on whatever changed while disconnected
+                    self.refresh_requested = true;
+                }
+                ServerEvent::Disconnected => self.live_updates = false,
+                ServerEvent::TaskUpdated(task) => {
+                    if let Some(selected) = self
+                        .selected_task
+                        .as_mut()
+                        .filter(|t| t.task.id == task.task.id)
+                    {
+                        *selected = (*task).clone();
+                    }
+                    if Some(task.task.project_id) == project_id {
+                        self.update_tasks(|tasks| {
+                            match tasks.iter_mut().find(|t| t.task.id == task.task.id) {
+                                Some(existing) => *existing = *task,
+                                None => tasks.push(*task),
+                            }
+                        });
+                    }
+                }
+                ServerEvent::TaskRemoved(task_id) => {
+                    self.update_tasks(|tasks| tasks.retain(|t| t.task.id != task_id));
+                }
+                ServerEvent::WorkspaceChanged(_) | ServerEvent::ProcessChanged(_) => {
+                    self.refresh_requested = true;
+                }
+            }
+        }
+    }
+
+    /// The load action that reloads the current view in the background.
+    fn auto_refresh_action(&self) -> Option<RetryableAction> {
+        let action = match self.view {
+            View::Projects => RetryableAction::LoadProjects,
+            View::Tasks => RetryableAction::LoadTasks {
+                project_id: self.selected_project.as_ref()?.id,
+            },
+            View::TaskDetail => RetryableAction::LoadTaskDetail {
+                task_id: self.selected_task.as_ref()?.task.id,
+            },
+            View::Workspaces => RetryableAction::LoadWorkspaces {
+                task_id: self.selected_task.as_ref()?.task.id,
+            },
+            View::WorkspaceDetail => RetryableAction::LoadWorkspaceDetails {
+                workspace_id: self.selected_workspace.as_ref()?.id,
+            },
+            View::Queue => RetryableAction::LoadAttemptQueue,
+            View::Failures => RetryableAction::LoadFailures {
+                project_id: self.failures_project_id,
+                category: self.failure_filter,
+            },
+            View::Trash => RetryableAction::LoadTrash {
+                project_id: self.selected_project.as_ref()?.id,
+            },
+            View::TaskTree => RetryableAction::LoadTaskTree {
+                task_id: self.task_tree_task_id?,
+            },
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    /// Fetch the project's workspace activity and follow the most recent.
+    fn poll_follow(&mut self) {
+        self.last_follow_poll = Some(Instant::now());
+        let Some(project_id) = self.selected_project.as_ref().map(|p| p.id) else {
+            self.follow_mode = false;
+            return;
+        };
+        let client = self.client.clone();
+        self.spawn_request(
+            "Checking workspace activity...",
+            async move { client.list_workspace_activity(project_id).await },
+            move |app, activity| {
+                app.workspace_activity = activity?;
+                app.follow_latest_activity(project_id);
+                Ok(())
+            },
+        );
+    }
+
+    /// Switch to the project's most recently active workspace, or refresh it if it
+    /// is already open and has new activity.
+    fn follow_latest_activity(&mut self, project_id: Uuid) {
+        let Some(latest) = self
+            .workspace_activity
+            .iter()
+            .filter(|a| a.last_activity_at().is_some())
+            .max_by_key(|a| a.last_activity_at())
+            .cloned()
+        else {
+            return;
+        };
+        let showing = self.view == View::WorkspaceDetail
+            && self
+                .selected_workspace
+                .as_ref()
+                .is_some_and(|w| w.id == latest.workspace_id);
+        if !self.follow_mode || (showing && self.followed_activity_at == latest.last_activity_at())
+        {
+            return;
+        }
+        self.followed_activity_at = latest.last_activity_at();
+
+        let workspace_id = latest.workspace_id;
+        let task_id = latest.task_id;
+        let load_tasks = !showing && !self.tasks.iter().any(|t| t.task.id == task_id);
+        let client = self.client.clone();
+        let request = async move {
+            let tasks = if load_tasks {
+                Loaded::fetch(&client, &RetryableAction::LoadTasks { project_id }).await?
+            } else {
+                None
+            };
+            let workspaces = if showing {
+                None
+            } else {
+                Loaded::fetch(&client, &RetryableAction::LoadWorkspaces { task_id }).await?
+            };
+            let details = Loaded::fetch(
+                &client,
+                &RetryableAction::LoadWorkspaceDetails { workspace_id },
+            )
+            .await?;
+            anyhow::Ok((tasks, workspaces, details))
+        };
+        self.spawn_request("Loading workspace...", request, move |app, loaded| {
+            let (tasks, workspaces, details) = loaded?;
+            // Turned off, or the user went elsewhere, while the data was on its way
+            if !app.follow_mode || app.is_busy() {
+                return Ok(());
+            }
+            if !showing {
+                if let Some(tasks) = tasks {
+                    app.apply_loaded(tasks);
+                }
+                let Some(task) = app.tasks.iter().find(|t| t.task.id == task_id).cloned() else {
+                    return Ok(());
+                };
+                app.selected_task = Some(task);
+                if let Some(workspaces) = workspaces {
+                    app.apply_loaded(workspaces);
+                }
+                let Some(index) = app.workspaces.iter().position(|w| w.id == workspace_id) else {
+                    return Ok(());
+                };
+                app.selected_workspace_index = index;
+                app.selected_workspace = app.workspaces.get(index).cloned();
+                app.view_stack = vec![View::Projects, View::Tasks, View::Workspaces];
+                app.view = View::WorkspaceDetail;
+                app.failed_action = None;
+            }
+            if let Some(details) = details {
+                app.apply_loaded(details);
+            }
+            if !showing && let Some(workspace) = &app.selected_workspace {
+                app.set_status(format!("Following {}", workspace.branch));
+            }
+            Ok(())
+        });
+    }
+
+    /// Activity for the selected workspace, if follow mode has fetched it.
+    pub fn selected_workspace_activity(&self) -> Option<&WorkspaceActivity> {
+        let workspace_id = self.selected_workspace.as_ref()?.id;
+        self.workspace_activity
+            .iter()
+            .find(|a| a.workspace_id == workspace_id)
+    }
+
+    // =========================================================================
+    // Session Logs
+    // =========================================================================
+
+    /// Open the log of the selected workspace's most recent session, at its latest
+    /// process.
+    pub fn open_session_logs(&mut self) {
+        let Some(session_id) = self.sessions.first().map(|s| s.id) else {
+            self.set_error("This workspace has no sessions yet");
+            return;
+        };
+        self.navigate_to(View::SessionLogs);
+        self.log_session_id = Some(session_id);
+        self.log_bookmark_index = None;
+        self.log_processes.clear();
+        self.perform_then(
+            RetryableAction::LoadExecutionProcesses { session_id },
+            |app, result| {
+                result?;
+                app.select_log_process(app.log_processes.len().saturating_sub(1));
+                Ok(())
+            },
+        );
+    }
+
+    /// Reload the session's processes, moving to the newest one if the latest was
+    /// being shown.
+    fn refresh_session_logs(&mut self) {
+        let Some(session_id) = self.log_session_id else {
+            return;
+        };
+        let was_latest = self.log_process_index + 1 >= self.log_processes.len();
+        self.perform_then(
+            RetryableAction::LoadExecutionProcesses { session_id },
+            move |app, result| {
+                result?;
+                let index = if was_latest {
+                    app.log_processes.len().saturating_sub(1)
+                } else {
+                    app.log_process_index
+                };
+                app.select_log_process(index);
+                Ok(())
+            },
+        );
+    }
+
+    /// The process whose log is shown.
+    pub fn log_process(&self) -> Option<&ExecutionProcess> {
+        self.log_processes.get(self.log_process_index)
+    }
+
+    /// Show the log of the previous or next process in the session.
+    pub fn cycle_log_process(&mut self, forward: bool) {
+        let index = if forward {
+            self.log_process_index + 1
+        } else {
+            self.log_process_index.wrapping_sub(1)
+        };
+        if index < self.log_processes.len() {
+            self.select_log_process(index);
+        }
+    }
+
+    /// Start tailing the log of the process at `index`.
+    fn select_log_process(&mut self, index: usize) {
+        self.log_stream = None;
+        self.process_log = ProcessLog::default();
+        self.log_scroll = 0;
+        self.log_jump_to = None;
+        self.log_process_index = index;
+        let Some(process) = self.log_processes.get(index).cloned() else {
+            return;
+        };
+        let client = self.client.clone();
+        self.spawn_request(
+            "Opening log...",
+            {
+                let process = process.clone();
+                async move { client.stream_process_logs(&process).await }
+            },
+            move |app, stream| {
+                let stream = stream?;
+                // Another process may have been picked, or the log closed, meanwhile
+                if app.view == View::SessionLogs
+                    && app.log_process().is_some_and(|p| p.id == process.id)
+                {
+                    app.log_stream = Some(stream);
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// Whether the shown log is still receiving output.
+    pub fn is_log_streaming(&self) -> bool {
+        self.log_stream.is_some()
+    }
+
+    /// Apply the log output that has arrived since the last tick, without waiting
+    /// for more.
+    pub fn poll_session_logs(&mut self) -> Result<()> {
+        let Some(stream) = self.log_stream.as_mut() else {
+            return Ok(());
+        };
+        let mut events = Vec::new();
+        let mut ended = false;
+        let mut failed = None;
+        while let Some(next) = stream.next().now_or_never() {
+            match next {
+                Some(Ok(event)) => events.push(event),
+                Some(Err(e)) => {
+                    failed = Some(e);
+                    break;
+                }
+                None => {
+                    ended = true;
+                    break;
+                }
+            }
+        }
+        if events.is_empty() && !ended && failed.is_none() {
+            return Ok(());
+        }
+
+        // Keep a scrolled-back view in place as lines arrive below it
+        let before = self.process_log.lines().len();
+        for event in &events {
+            self.process_log.apply(event)?;
+            ended |= matches!(event, LogEvent::Finished);
+        }
+        let after = self.process_log.lines().len();
+        if self.log_scroll > 0 {
+            self.log_scroll += after.saturating_sub(before);
+        }
+        if let Some(line) = self.log_jump_to.take_if(|line| *line < after) {
+            self.log_scroll = after - line - 1;
+        }
+
+        if ended || failed.is_some() {
+            self.log_stream = None;
+        }
+        match failed {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Scroll the log by `lines`, positive towards older output.
+    pub fn scroll_log(&mut self, lines: isize) {
+        let max = self.log_max_scroll.get();
+        self.log_scroll = self
+            .log_scroll
+            .min(max)
+            .saturating_add_signed(lines)
+            .min(max);
+    }
+
+    /// Jump to the end of the log and follow new output.
+    pub fn follow_log(&mut self) {
+        self.log_scroll = 0;
+    }
+
+    /// Bookmarks of the session whose log is open, in log order.
+    pub fn log_bookmarks(&self) -> &[LogBookmark] {
+        self.log_session_id
+            .and_then(|id| self.log_bookmarks.get(&id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Start typing the note of a bookmark for the line at the bottom of the pane.
+    pub fn start_log_bookmark(&mut self) {
+        if self.process_log.lines().is_empty() {
+            self.set_error("Nothing to bookmark yet");
+            return;
+        }
+        self.log_bookmark_note.clear();
+        self.input_mode = InputMode::Editing;
+    }
+
+    /// Bookmark the line at the bottom of the pane with the typed note.
+    pub fn save_log_bookmark(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let (Some(session_id), Some(process)) = (self.log_session_id, self.log_process()) else {
+            return;
+        };
+        let process_id = process.id;
+        let shown = self.process_log.lines().len() - self.log_scroll.min(self.log_max_scroll.get());
+        let line = shown.saturating_sub(1);
+        let note = match self.log_bookmark_note.trim() {
+            "" => format!("Line {}", line + 1),
+            note => note.to_string(),
+        };
+
+        let order: HashMap<Uuid, usize> = self
+            .log_processes
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.id, i))
+            .collect();
+        let bookmarks = self.log_bookmarks.entry(session_id).or_default();
+        bookmarks.push(LogBookmark {
+            process_id,
+            line,
+            note,
+        });
+        bookmarks.sort_by_key(|b| (order.get(&b.process_id).copied(), b.line));
+        self.log_bookmark_index = bookmarks
+            .iter()
+            .position(|b| b.process_id == process_id && b.line == line);
+        self.set_status(format!("Bookmarked line {}", line + 1));
+    }
+
+    pub fn cancel_log_bookmark(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.log_bookmark_note.clear();
+    }
+
+    /// Scroll to the next or previous bookmark of the session, opening the log of
+    /// its process if another one is shown.
+    pub fn jump_to_bookmark(&mut self, forward: bool) {
+        let count = self.log_bookmarks().len();
+        if count == 0 {
+            self.set_status("No bookmarks in this session; press m to add one");
+            return;
+        }
+        let index = match self.log_bookmark_index {
+            Some(index) if forward => (index + 1) % count,
+            Some(index) => (index + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        self.log_bookmark_index = Some(index);
+        let bookmark = self.log_bookmarks()[index].clone();
+
+        if self
+            .log_process()
+            .is_some_and(|p| p.id == bookmark.process_id)
+        {
+            let lines = self.process_log.lines().len();
+            self.log_scroll = lines.saturating_sub(bookmark.line + 1);
+            return;
+        }
+        let Some(process_index) = self
+            .log_processes
+            .iter()
+            .position(|p| p.id == bookmark.process_id)
+        else {
+            return;
+        };
+        self.select_log_process(process_index);
+        self.log_jump_to = Some(bookmark.line);
+    }
+
+    /// Write the shown process log, with the session's bookmarks in it, to a
+    /// Markdown file in the current directory.
+    pub fn export_transcript(&mut self) {
+        let Some(process) = self.log_process() else {
+            return;
+        };
+        let branch = self
+            .selected_workspace
+            .as_ref()
+            .map(|w| w.branch.as_str())
+            .unwrap_or("session");
+        let title = format!(
+            "{} · {} ({}/{})",
+            branch,
+            process.run_reason,
+            self.log_process_index + 1,
+            self.log_processes.len()
+        );
+        let path = format!(
+            "{}-{}.transcript.md",
+            task_slug(branch),
+            self.log_process_index + 1
+        );
+        let bookmarks: Vec<_> = self
+            .log_bookmarks()
+            .iter()
+            .filter(|b| b.process_id == process.id)
+            .collect();
+        let notes = self.workspace_note.as_ref().map(|note| note.body.as_str());
+        let transcript = logs::transcript(&title, notes, &self.process_log.lines(), &bookmarks);
+        match std::fs::write(&path, transcript) {
+            Ok(()) => self.set_status(format!("Transcript written to {}", path)),
+            Err(e) => self.set_error(format!("Failed to write {}: {}", path, e)),
+        }
+    }
+
+    // =========================================================================
+    // Links
+    // =========================================================================
+
+    /// Links shown on the current screen, as (source, url) pairs.
+    pub fn visible_links(&self) -> Vec<(String, String)> {
+        let mut found = Vec::new();
+        let task = match self.view {
+            View::Tasks => self.current_column_selected_task(),
+            View::TaskDetail | View::Workspaces | View::WorkspaceDetail => {
+                self.selected_task.as_ref()
+            }
+            _ => None,
+        };
+        if let Some(description) = task.and_then(|t| t.task.description.as_deref()) {
+            for url in links::find_urls(description) {
+                found.push(("Task description".to_string(), url));
+            }
+        }
+        if let Some(Ok(metadata)) = task.map(|t| metadata::parse(t.task.metadata.as_deref())) {
+            for link in metadata::links(&metadata) {
+                let source = link.title.unwrap_or_else(|| "Task link".to_string());
+                found.push((source, link.url));
+            }
+        }
+        if self.view == View::SessionLogs {
+            for line in self.process_log.lines() {
+                for url in links::find_urls(&line.text) {
+                    found.push(("Log".to_string(), url));
+                }
+            }
+        }
+        if self.view == View::WorkspaceDetail {
+            for status in &self.branch_statuses {
+                for pr in status.status.pull_requests() {
+                    found.push((
+                        format!("PR #{} ({})", pr.number, status.repo_name),
+                        pr.url.clone(),
+                    ));
+                }
+            }
+        }
+
+        let mut seen = Vec::new();
+        found.retain(|(_, url)| {
+            let new = !seen.contains(url);
+            seen.push(url.clone());
+            new
+        });
+        found
     }
 
-    /// Set an error message.
-    pub fn set_error(&mut self, message: impl Into<String>) {
-        self.error_message = Some(message.into());
-        self.status_message = None;
+    /// Label the links on screen so one can be opened by typing its label.
+    pub fn show_link_hints(&mut self) {
+        let found = self.visible_links();
+        if found.is_empty() {
+            self.set_status("No links on this screen");
+            return;
+        }
+        let labels = links::hint_labels(found.len());
+        self.link_hints = labels
+            .into_iter()
+            .zip(found)
+            .map(|(label, (source, url))| LinkHint { label, source, url })
+            .collect();
+        self.link_hint_input.clear();
     }
 
-    /// Clear status and error messages.
-    pub fn clear_messages(&mut self) {
-        self.status_message = None;
-        self.error_message = None;
+    pub fn close_link_hints(&mut self) {
+        self.link_hints.clear();
+        self.link_hint_input.clear();
     }
 
-    /// Go back to the previous view.
-    pub fn go_back(&mut self) {
-        if let Some(prev) = self.previous_view.take() {
-            self.view = prev;
+    /// Extend the typed label, opening the link once it matches one exactly.
+    pub fn type_link_hint(&mut self, c: char) {
+        self.link_hint_input.push(c);
+        let input = self.link_hint_input.as_str();
+        if let Some(hint) = self.link_hints.iter().find(|h| h.label == input) {
+            let url = hint.url.clone();
+            self.close_link_hints();
+            match links::open(&url) {
+                Ok(()) => self.set_status(format!("Opened {}", url)),
+                Err(e) => self.set_error(format!("Failed to open {}: {}", url, e)),
+            }
+        } else if !self.link_hints.iter().any(|h| h.label.starts_with(input)) {
+            self.link_hint_input.clear();
         }
     }
 
-    /// Navigate to a new view.
-    pub fn navigate_to(&mut self, view: View) {
-        self.previous_view = Some(self.view);
-        self.view = view;
-    }
-
     // =========================================================================
-    // Data Loading
+    // Batches
     // =========================================================================
 
-    /// Load projects from the server.
-    pub async fn load_projects(&mut self) -> Result<()> {
-        self.set_status("Loading projects...");
-        self.projects = self.client.list_projects().await?;
-        self.selected_project_index = 0.min(self.projects.len().saturating_sub(1));
+    /// Run the steps of a multi-step operation one after another, with their
+    /// progress shown above the status bar. The current view is reloaded once the
+    /// last step is done.
+    pub fn start_batch(&mut self, batch: Batch<BatchWork>) {
+        if self
+            .batch
+            .as_ref()
+            .is_some_and(|batch| !batch.is_finished())
+        {
+            self.set_error("Wait for the running steps to finish, or cancel them");
+            return;
+        }
         self.clear_messages();
-        Ok(())
+        self.batch = Some(batch);
+        self.run_next_batch_step();
     }
 
-    /// Load tasks for the selected project.
-    pub async fn load_tasks(&mut self) -> Result<()> {
-        let project_id = self.selected_project.as_ref().map(|p| p.id);
-        if let Some(id) = project_id {
-            self.set_status("Loading tasks...");
-            self.tasks = self.client.list_tasks(id).await?;
-            self.clear_messages();
+    fn run_next_batch_step(&mut self) {
+        let Some(batch) = &mut self.batch else {
+            return;
+        };
+        if let Some(work) = batch.start_next() {
+            self.spawn_request("Running steps...", work, |app, result| {
+                if let Some(batch) = &mut app.batch {
+                    batch.finish_current(result.map_err(|e| format!("{:#}", e)));
+                }
+                app.run_next_batch_step();
+                Ok(())
+            });
+            return;
         }
-        Ok(())
-    }
-
-    /// Load repositories for the selected project.
-    pub async fn load_project_repos(&mut self) -> Result<()> {
-        let project_id = self.selected_project.as_ref().map(|p| p.id);
-        if let Some(id) = project_id {
-            self.project_repos = self.client.get_project_repositories(id).await?;
+        if !batch.is_finished() {
+            return;
         }
-        Ok(())
-    }
 
-    /// Load workspaces for the selected task.
-    pub async fn load_workspaces(&mut self) -> Result<()> {
-        let task_id = self.selected_task.as_ref().map(|t| t.task.id);
-        if let Some(id) = task_id {
-            self.set_status("Loading workspaces...");
-            self.workspaces = self.client.list_workspaces(Some(id)).await?;
-            self.selected_workspace_index = 0.min(self.workspaces.len().saturating_sub(1));
-            self.clear_messages();
+        let summary = batch.summary();
+        let failed = batch.failed() > 0;
+        self.refresh();
+        if failed {
+            // The widget keeps the failures on screen until it is dismissed
+            self.set_error(summary);
+        } else {
+            self.batch = None;
+            self.set_status(summary);
         }
-        Ok(())
     }
 
-    /// Load details for the selected workspace.
-    pub async fn load_workspace_details(&mut self) -> Result<()> {
-        let workspace_id = self.selected_workspace.as_ref().map(|w| w.id);
-        if let Some(id) = workspace_id {
-            self.set_status("Loading workspace details...");
-            self.workspace_repos = self.client.get_workspace_repos(id).await?;
-            self.branch_statuses = self.client.get_branch_status(id).await?;
-            self.sessions = self.client.list_sessions(id).await?;
+    /// Skip the steps of the running batch that haven't started, or dismiss a
+    /// finished one.
+    pub fn cancel_batch(&mut self) {
+        let Some(batch) = &mut self.batch else {
+            return;
+        };
+        if batch.is_finished() {
+            self.batch = None;
             self.clear_messages();
+            return;
         }
-        Ok(())
+        let skipped = batch.cancel();
+        self.set_status(format!("Skipping {} remaining steps", skipped));
     }
 
-    // =========================================================================
-    // Project Actions
-    // =========================================================================
-
-    /// Select a project and navigate to tasks view.
-    pub async fn select_project(&mut self) -> Result<()> {
-        if let Some(project) = self.projects.get(self.selected_project_index).cloned() {
-            self.selected_project = Some(project);
-            self.load_tasks().await?;
-            self.load_project_repos().await?;
-            self.navigate_to(View::Tasks);
+    /// Mark or unmark the selected task on the board.
+    pub fn toggle_task_mark(&mut self) {
+        if let Some(task_id) = self.current_column_selected_task().map(|t| t.task.id)
+            && !self.marked_tasks.remove(&task_id)
+        {
+            self.marked_tasks.insert(task_id);
         }
-        Ok(())
     }
 
-    // =========================================================================
-    // Task Actions
-    // =========================================================================
-
-    /// Get tasks filtered by status for a column.
-    pub fn tasks_for_column(&self, column: TaskColumn) -> Vec<&TaskWithAttemptStatus> {
+    /// Marked tasks that are still on the board
+    fn marked_board_tasks(&self) -> Vec<&TaskWithAttemptStatus> {
         self.tasks
             .iter()
-            .filter(|t| t.task.status == column.status())
+            .filter(|t| self.marked_tasks.contains(&t.task.id))
             .collect()
     }
 
-    /// Get the currently selected task in the current column.
-    pub fn current_column_selected_task(&self) -> Option<&TaskWithAttemptStatus> {
-        let column_index = match self.selected_column {
-            TaskColumn::Todo => 0,
-            TaskColumn::InProgress => 1,
-            TaskColumn::InReview => 2,
-            TaskColumn::Done => 3,
-        };
-        let tasks = self.tasks_for_column(self.selected_column);
-        let index = self.selected_task_indices[column_index];
-        tasks.get(index).copied()
-    }
-
-    /// Select the current task and navigate to workspaces view.
-    pub async fn select_task(&mut self) -> Result<()> {
-        if let Some(task) = self.current_column_selected_task().cloned() {
-            self.selected_task = Some(task);
-            self.load_workspaces().await?;
-            self.navigate_to(View::Workspaces);
-        }
-        Ok(())
-    }
-
-    /// Create a new task.
-    pub async fn create_task(&mut self) -> Result<()> {
-        if self.new_task_title.trim().is_empty() {
-            self.set_error("Task title cannot be empty");
-            return Ok(());
-        }
-
-        let project_id = self.selected_project.as_ref().map(|p| p.id);
-        if let Some(id) = project_id {
-            self.set_status("Creating task...");
-            let payload = CreateTask {
-                project_id: id,
-                title: self.new_task_title.clone(),
-                description: if self.new_task_description.is_empty() {
-                    None
+    /// Advance every marked task to the column after its own.
+    pub fn move_marked_tasks(&mut self, backwards: bool) {
+        let steps: Vec<(String, BatchWork)> = self
+            .marked_board_tasks()
+            .into_iter()
+            .filter_map(|task| {
+                let column = TaskColumn::ALL
+                    .into_iter()
+                    .find(|column| column.status() == task.task.status)?;
+                let next = if backwards {
+                    column.prev()
                 } else {
-                    Some(self.new_task_description.clone())
-                },
-                status: None,
-                parent_workspace_id: None,
-                image_ids: None,
-                is_epic: None,
-                complexity: None,
-                metadata: None,
-            };
-
-            self.client.create_task(&payload).await?;
-            self.new_task_title.clear();
-            self.new_task_description.clear();
-            self.load_tasks().await?;
-            self.set_status("Task created successfully");
-            self.go_back();
+                    column.next()
+                };
+                if next == column {
+                    return None;
+                }
+                let action = RetryableAction::UpdateTaskStatus {
+                    task_id: task.task.id,
+                    status: next.status(),
+                };
+                let client = self.client.clone();
+                let work: BatchWork =
+                    Box::pin(async move { action.run(&client).await.map(|_| ()) });
+                Some((format!("{} → {}", task.task.title, next.title()), work))
+            })
+            .collect();
+        self.marked_tasks.clear();
+        if steps.is_empty() {
+            self.set_status(if backwards {
+                "The marked tasks are already in To Do"
+            } else {
+                "The marked tasks are already done"
+            });
+            return;
         }
-        Ok(())
+        self.start_batch(Batch::new(format!("Moving {} tasks", steps.len()), steps));
     }
 
-    /// Update a task's status.
-    pub async fn update_task_status(&mut self, task_id: Uuid, status: TaskStatus) -> Result<()> {
-        self.set_status("Updating task...");
-        let payload = UpdateTask {
-            title: None,
-            description: None,
-            status: Some(status),
-            parent_workspace_id: None,
-            image_ids: None,
-            is_epic: None,
-            complexity: None,
-            metadata: None,
+    /// Open the create attempt form for every marked task, or the selected one.
+    pub fn open_start_attempts(&mut self) {
+        let mut tasks: Vec<TaskWithAttemptStatus> = if self.marked_tasks.is_empty() {
+            self.current_column_selected_task()
+                .into_iter()
+                .cloned()
+                .collect()
+        } else {
+            self.marked_board_tasks().into_iter().cloned().collect()
         };
-        self.client.update_task(task_id, &payload).await?;
-        self.load_tasks().await?;
-        self.set_status("Task updated");
-        Ok(())
+        if tasks.is_empty() {
+            return;
+        }
+        self.init_create_attempt();
+        if tasks.len() == 1 {
+            // A single attempt is created the usual way and opens its workspace
+            self.selected_task = tasks.pop();
+        } else {
+            self.attempt_tasks = tasks
+                .into_iter()
+                .map(|t| (t.task.id, t.task.title))
+                .collect();
+        }
+        self.navigate_to(View::CreateAttempt);
     }
 
-    /// Delete the selected task.
-    pub async fn delete_selected_task(&mut self) -> Result<()> {
-        let task_id = self.current_column_selected_task().map(|t| t.task.id);
-        if let Some(id) = task_id {
-            self.set_status("Deleting task...");
-            self.client.delete_task(id).await?;
-            self.load_tasks().await?;
-            self.set_status("Task deleted");
+    /// Start an attempt for each task of the form, one after another.
+    fn start_attempts(
+        &mut self,
+        executor_profile_id: ExecutorProfileId,
+        repos: Vec<WorkspaceRepoInput>,
+        resource_limits: Option<ResourceLimits>,
+    ) {
+        let tasks = mem::take(&mut self.attempt_tasks);
+        let steps: Vec<(String, BatchWork)> = tasks
+            .into_iter()
+            .map(|(task_id, title)| {
+                let payload = CreateTaskAttemptBody {
+                    task_id,
+                    executor_profile_id: executor_profile_id.clone(),
+                    repos: repos.clone(),
+                    resource_limits: resource_limits.clone(),
+                    // Attempts can't share a branch, so the server names each one
+                    branch: None,
+                };
+                let client = self.client.clone();
+                let key = client.idempotency_key();
+                let work: BatchWork = Box::pin(async move {
+                    client.create_task_attempt(&payload, &key).await.map(|_| ())
+                });
+                (title, work)
+            })
+            .collect();
+        self.marked_tasks.clear();
+        if self.view == View::CreateAttempt {
+            self.go_back();
         }
-        Ok(())
+        self.start_batch(Batch::new(
+            format!("Starting {} attempts", steps.len()),
+            steps,
+        ));
     }
 
     // =========================================================================
-    // Workspace Actions
+    // Confirmation
     // =========================================================================
 
-    /// Select a workspace and show details.
-    pub async fn select_workspace(&mut self) -> Result<()> {
-        if let Some(workspace) = self.workspaces.get(self.selected_workspace_index).cloned() {
-            self.selected_workspace = Some(workspace);
-            self.load_workspace_details().await?;
-            self.navigate_to(View::WorkspaceDetail);
+    /// Run the action the user just confirmed.
+    pub fn confirm_pending_action(&mut self) {
+        match self.pending_action.take() {
+            Some(PendingAction::DeleteTask { task_id, .. }) => self.delete_task(task_id),
+            Some(PendingAction::PurgeTask { task_id, .. }) => self.purge_task(task_id),
+            Some(PendingAction::StopWorkspace { workspace_id, .. }) => {
+                self.stop_workspace(workspace_id)
+            }
+            Some(PendingAction::StopProcess { process_id, .. }) => self.stop_process(process_id),
+            Some(PendingAction::RetryAttempt { workspace_id, .. }) => {
+                self.retry_attempt(workspace_id)
+            }
+            Some(PendingAction::ResolveConflicts {
+                workspace_id,
+                branch,
+                ..
+            }) => self.resolve_conflicts(workspace_id, branch),
+            Some(
+                PendingAction::GitOperation { action, .. }
+                | PendingAction::ProtectedBranch { action, .. },
+            ) => self.perform_git_action(*action),
+            Some(PendingAction::AllRepos {
+                operation,
+                workspace_id,
+                repos,
+                ..
+            }) => self.git_operation_all(operation, workspace_id, repos),
+            None => {}
         }
-        Ok(())
     }
 
-    /// Stop the selected workspace execution.
-    pub async fn stop_workspace(&mut self) -> Result<()> {
-        let workspace_id = self.selected_workspace.as_ref().map(|w| w.id);
-        if let Some(id) = workspace_id {
-            self.set_status("Stopping workspace...");
-            self.client.stop_workspace(id).await?;
-            self.load_workspace_details().await?;
-            self.set_status("Workspace stopped");
+    pub fn cancel_pending_action(&mut self) {
+        if self.pending_action.take().is_some() {
+            self.set_status("Cancelled");
         }
-        Ok(())
     }
 
     // =========================================================================
-    // Git Actions
+    // Command palette
     // =========================================================================
 
-    /// Merge the selected workspace.
-    pub async fn merge_workspace(&mut self) -> Result<()> {
-        let workspace_id = self.selected_workspace.as_ref().map(|w| w.id);
-        let repo_id = self.branch_statuses.first().map(|s| s.repo_id);
-        if let (Some(ws_id), Some(r_id)) = (workspace_id, repo_id) {
-            self.set_status("Merging...");
-            self.client.merge_workspace(ws_id, r_id).await?;
-            self.load_workspace_details().await?;
-            self.set_status("Merged successfully");
+    pub fn open_palette(&mut self) {
+        self.palette = Some(CommandPalette::default());
+    }
+
+    pub fn close_palette(&mut self) {
+        self.palette = None;
+    }
+
+    /// Commands of the current view matching the palette query, best match
+    /// first; the view's own come before the global ones on an empty query.
+    pub fn palette_matches(&self) -> Vec<(Action, &'static str)> {
+        let query = self.palette.as_ref().map_or("", |p| p.query.as_str());
+        let error = self.error_message.is_some();
+        // Commands that only mean something at the moment
+        let conditional = [
+            (error && self.error_detail.is_some())
+                .then_some((Action::ErrorDetails, "Show details of the last error")),
+            (error && self.failed_action.is_some())
+                .then_some((Action::Retry, "Retry the failed action")),
+            self.batch
+                .is_some()
+                .then_some((Action::CancelSteps, "Cancel the remaining steps")),
+        ];
+        let mut matches: Vec<(i64, (Action, &'static str))> = keymap::commands(self.view)
+            .iter()
+            .chain(keymap::GLOBAL_COMMANDS)
+            .copied()
+            .chain(conditional.into_iter().flatten())
+            // Actions left without a key can't be run through the view
+            .filter(|(action, _)| self.config.keybindings.key_event(*action).is_some())
+            .filter_map(|command| fuzzy::score(query, command.1).map(|s| (s, command)))
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(_, command)| command).collect()
+    }
+
+    pub fn type_palette(&mut self, c: char) {
+        if let Some(palette) = &mut self.palette {
+            palette.query.push(c);
+            palette.index = 0;
         }
-        Ok(())
     }
 
-    /// Push the selected workspace branch.
-    pub async fn push_workspace(&mut self) -> Result<()> {
-        let workspace_id = self.selected_workspace.as_ref().map(|w| w.id);
-        let repo_id = self.branch_statuses.first().map(|s| s.repo_id);
-        if let (Some(ws_id), Some(r_id)) = (workspace_id, repo_id) {
-            self.set_status("Pushing...");
-            self.client.push_workspace(ws_id, r_id).await?;
-            self.load_workspace_details().await?;
-            self.set_status("Pushed successfully");
+    pub fn palette_backspace(&mut self) {
+        if let Some(palette) = &mut self.palette {
+            palette.query.pop();
+            palette.index = 0;
         }
-        Ok(())
     }
 
-    /// Rebase the selected workspace branch.
-    pub async fn rebase_workspace(&mut self) -> Result<()> {
-        let workspace_id = self.selected_workspace.as_ref().map(|w| w.id);
-        let repo_id = self.branch_statuses.first().map(|s| s.repo_id);
-        if let (Some(ws_id), Some(r_id)) = (workspace_id, repo_id) {
-            self.set_status("Rebasing...");
-            self.client.rebase_workspace(ws_id, r_id, None, None).await?;
-            self.load_workspace_details().await?;
-            self.set_status("Rebased successfully");
+    pub fn palette_move(&mut self, forward: bool) {
+        let count = self.palette_matches().len();
+        if let Some(palette) = &mut self.palette {
+            palette.index = if forward {
+                (palette.index + 1).min(count.saturating_sub(1))
+            } else {
+                palette.index.saturating_sub(1)
+            };
         }
-        Ok(())
+    }
+
+    /// Close the palette, returning the selected command's action for the
+    /// view to run.
+    pub fn take_palette_command(&mut self) -> Option<Action> {
+        let index = self.palette.as_ref()?.index;
+        let action = self.palette_matches().get(index).map(|(action, _)| *action);
+        self.close_palette();
+        action
     }
 
     // =========================================================================
-    // Attempt Creation
+    // Theme picker
     // =========================================================================
 
-    /// Initialize the create attempt form.
-    pub async fn init_create_attempt(&mut self) -> Result<()> {
-        // Reset form state
-        self.attempt_executor_index = 0;
-        self.attempt_variant = None;
-        self.attempt_repo_branches.clear();
-        self.attempt_selected_field = 0;
-        self.repo_branches_cache.clear();
+    /// Open the theme picker. Themes are previewed as they are selected.
+    pub fn open_theme_picker(&mut self) {
+        self.theme_picker = Some(self.config.theme());
+    }
 
-        // Load branches for all repos
-        if let Some(project_id) = self.selected_project.as_ref().map(|p| p.id) {
-            self.set_status("Loading branches...");
-            let repos = self.client.get_project_repositories(project_id).await?;
-            
-            for repo in repos {
-                match self.client.get_repo_branches(repo.id).await {
-                    Ok(branches) => {
-                        self.repo_branches_cache.push((repo.id, branches.clone()));
-                        // Initialize with first branch (or main/master if available)
-                        let default_branch = branches
-                            .iter()
-                            .find(|b| b.name == "main" || b.name == "master")
-                            .map(|b| b.name.clone())
-                            .or_else(|| branches.first().map(|b| b.name.clone()))
-                            .unwrap_or_else(|| "main".to_string());
-                        self.attempt_repo_branches.push((repo.id, default_branch));
-                    }
+    pub fn theme_picker_move(&mut self, forward: bool) {
+        self.config.theme = Some(self.config.theme().cycle(forward));
+    }
+
+    /// Close the picker, keeping the selected theme or restoring the previous one.
+    /// A kept theme is saved to the user's preferences on the server, so other
+    /// machines pick it up too.
+    pub fn close_theme_picker(&mut self, keep: bool) {
+        let Some(previous) = self.theme_picker.take() else {
+            return;
+        };
+        if !keep {
+            self.config.theme = Some(previous);
+            return;
+        }
+        let theme = self.config.theme();
+        self.set_status(format!("Theme: {}", theme.name()));
+        if theme == previous {
+            return;
+        }
+        let client = self.client.clone();
+        self.spawn_request(
+            "Saving theme",
+            async move {
+                let mut preferences = client.get_user_preferences().await?;
+                preferences.theme = Some(theme.as_str().to_string());
+                client.update_user_preferences(&preferences).await
+            },
+            |app, result| {
+                if let Err(e) = result {
+                    app.report_error(&e.context("Theme not saved to the server"));
+                }
+                Ok(())
+            },
+        );
+    }
+
+    /// Fill in the settings the config file leaves unset from the user's
+    /// preferences on the server. Servers without them are left alone.
+    pub fn load_user_preferences(&mut self) {
+        let client = self.client.clone();
+        self.spawn_request(
+            "Loading preferences",
+            async move { client.get_user_preferences().await },
+            |app, result| {
+                let preferences = match result {
+                    Ok(preferences) => preferences,
                     Err(e) => {
-                        self.set_error(format!("Failed to load branches for {}: {}", repo.name, e));
-                        // Still add repo with empty branch
-                        self.attempt_repo_branches.push((repo.id, "main".to_string()));
+                        tracing::debug!("no user preferences from the server: {:#}", e);
+                        return Ok(());
                     }
+                };
+                let problems = app.config.apply_preferences(&preferences);
+                if !problems.is_empty() {
+                    app.notifications.push(
+                        NotificationLevel::Error,
+                        format!("Ignored user preferences: {}", problems.join("; ")),
+                    );
                 }
+                Ok(())
+            },
+        );
+    }
+
+    // =========================================================================
+    // Jumper
+    // =========================================================================
+
+    /// Open the Ctrl+P jumper over every project and its tasks.
+    pub fn open_jumper(&mut self) {
+        self.clear_messages();
+        let client = self.client.clone();
+        let request = async move {
+            let projects = client.list_projects().await?;
+            let tasks = futures_util::future::try_join_all(
+                projects.iter().map(|p| client.list_tasks(p.id)),
+            )
+            .await?;
+            anyhow::Ok((projects, tasks))
+        };
+        self.spawn_request("Loading projects and tasks...", request, |app, loaded| {
+            let (projects, tasks) = loaded?;
+            if app.is_busy() {
+                return Ok(());
             }
-            self.clear_messages();
-        }
-        Ok(())
+            app.jump_targets = projects
+                .iter()
+                .zip(tasks)
+                .flat_map(|(project, tasks)| {
+                    std::iter::once(JumpTarget::Project(project.clone())).chain(
+                        tasks.into_iter().map(|task| JumpTarget::Task {
+                            project: project.clone(),
+                            task: Box::new(task),
+                        }),
+                    )
+                })
+                .collect();
+            app.projects = projects;
+            app.jump_query.clear();
+            app.jump_index = 0;
+            app.show_jumper = true;
+            Ok(())
+        });
     }
 
-    /// Get available executors list.
-    pub fn available_executors() -> Vec<crate::types::BaseCodingAgent> {
-        vec![
-            crate::types::BaseCodingAgent::CursorAgent,
-            crate::types::BaseCodingAgent::ClaudeCode,
-            crate::types::BaseCodingAgent::Gemini,
-            crate::types::BaseCodingAgent::Codex,
-            crate::types::BaseCodingAgent::Opencode,
-            crate::types::BaseCodingAgent::QwenCode,
-            crate::types::BaseCodingAgent::Amp,
-            crate::types::BaseCodingAgent::Copilot,
-            crate::types::BaseCodingAgent::Droid,
-        ]
+    pub fn close_jumper(&mut self) {
+        self.show_jumper = false;
+        self.jump_targets.clear();
+        self.jump_query.clear();
     }
 
-    /// Create a new attempt for the selected task.
-    pub async fn create_attempt(&mut self) -> Result<()> {
-        let task_id = self.selected_task.as_ref().map(|t| t.task.id);
-        if task_id.is_none() {
-            self.set_error("No task selected");
-            return Ok(());
-        }
+    /// Jump targets matching the query, best match first.
+    pub fn jumper_matches(&self) -> Vec<&JumpTarget> {
+        let mut matches: Vec<(i64, &JumpTarget)> = self
+            .jump_targets
+            .iter()
+            .filter_map(|t| fuzzy::score(&self.jump_query, &t.search_text()).map(|s| (s, t)))
+            .collect();
+        // Stable, so an empty query keeps each project followed by its tasks
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(_, t)| t).collect()
+    }
 
-        if self.attempt_repo_branches.is_empty() {
-            self.set_error("No repositories configured for this project");
-            return Ok(());
-        }
+    pub fn type_jumper(&mut self, c: char) {
+        self.jump_query.push(c);
+        self.jump_index = 0;
+    }
 
-        let executors = Self::available_executors();
-        if self.attempt_executor_index >= executors.len() {
-            self.set_error("Invalid executor selection");
-            return Ok(());
+    pub fn jumper_backspace(&mut self) {
+        self.jump_query.pop();
+        self.jump_index = 0;
+    }
+
+    pub fn jumper_move_up(&mut self) {
+        self.jump_index = self.jump_index.saturating_sub(1);
+    }
+
+    pub fn jumper_move_down(&mut self) {
+        let count = self.jumper_matches().len();
+        if self.jump_index + 1 < count {
+            self.jump_index += 1;
         }
+    }
 
-        let executor = executors[self.attempt_executor_index];
-        let executor_profile_id = crate::types::ExecutorProfileId {
-            executor,
-            variant: self.attempt_variant.clone(),
+    /// Jump to the selected project's board, or straight to the selected task,
+    /// leaving Esc to step back through the usual views.
+    pub fn jump_to_selected(&mut self) {
+        let Some(target) = self
+            .jumper_matches()
+            .get(self.jump_index)
+            .map(|t| (*t).clone())
+        else {
+            return;
         };
+        self.close_jumper();
+        if self.follow_mode {
+            self.stop_following();
+        }
 
-        let repos: Vec<crate::types::WorkspaceRepoInput> = self
-            .attempt_repo_branches
+        let project = target.project().clone();
+        self.selected_project_index = self
+            .projects
             .iter()
-            .map(|(repo_id, branch)| crate::types::WorkspaceRepoInput {
-                repo_id: *repo_id,
-                target_branch: branch.clone(),
-            })
-            .collect();
+            .position(|p| p.id == project.id)
+            .unwrap_or_default();
+        self.selected_project = Some(project);
+        self.reset_board_filters();
+        self.input_mode = InputMode::Normal;
+        self.log_stream = None;
+        self.view_stack = vec![View::Projects];
+        self.view = View::Tasks;
+        self.failed_action = None;
+        self.submit_key = None;
+        self.load_project_repos();
 
-        self.set_status("Creating attempt...");
-        let payload = crate::types::CreateTaskAttemptBody {
-            task_id: task_id.unwrap(),
-            executor_profile_id,
-            repos,
+        let JumpTarget::Task { task, .. } = target else {
+            self.load_tasks();
+            return;
         };
+        let project_id = task.task.project_id;
+        let task_id = task.task.id;
+        let status = task.task.status;
+        self.perform_then(
+            RetryableAction::LoadTasks { project_id },
+            move |app, result| {
+                result?;
+                app.select_board_task(task_id, status);
+                Ok(())
+            },
+        );
+        self.selected_task = Some(*task);
+        self.selected_workspace_index = 0;
+        self.navigate_to(View::TaskDetail);
+        self.load_task_detail();
+    }
 
-        self.client.create_task_attempt(&payload).await?;
-        self.load_workspaces().await?;
-        self.set_status("Attempt created successfully");
-        self.go_back();
-        Ok(())
+    /// Leave the board on a task, for when Esc goes back to it.
+    fn select_board_task(&mut self, task_id: Uuid, status: TaskStatus) {
+        let column = TaskColumn::ALL
+            .into_iter()
+            .find(|column| column.status() == status);
+        if let Some(column) = column {
+            self.show_cancelled |= column == TaskColumn::Cancelled;
+            self.selected_column = column;
+            self.selected_task_indices[column.index()] = self
+                .tasks_for_column(column)
+                .iter()
+                .position(|t| t.task.id == task_id)
+                .unwrap_or_default();
+        }
+    }
+
+    /// Open the project board, task or workspace a web UI link points at, with
+    /// the views that lead to it behind it for Esc. With `logs`, a workspace
+    /// opens on to its latest session's logs.
+    pub fn open_link(&mut self, target: LinkTarget, logs: bool) {
+        let origin = self.view;
+        let client = self.client.clone();
+        let request = async move {
+            // Workspace links don't say which task and project they belong to
+            let (project_id, task_id, workspace_id) = match target {
+                LinkTarget::Project(project_id) => (project_id, None, None),
+                LinkTarget::Task {
+                    project_id,
+                    task_id,
+                } => (project_id, Some(task_id), None),
+                LinkTarget::Workspace(workspace_id) => {
+                    let workspace = client.get_workspace(workspace_id).await?;
+                    let task = client.get_task(workspace.task_id).await?;
+                    (task.project_id, Some(task.id), Some(workspace_id))
+                }
+            };
+            let projects = client.list_projects().await?;
+            let tasks = Loaded::fetch(&client, &RetryableAction::LoadTasks { project_id }).await?;
+            let workspaces = match workspace_id.and(task_id) {
+                Some(task_id) => {
+                    Loaded::fetch(&client, &RetryableAction::LoadWorkspaces { task_id }).await?
+                }
+                None => None,
+            };
+            anyhow::Ok((
+                projects,
+                project_id,
+                tasks,
+                task_id,
+                workspaces,
+                workspace_id,
+            ))
+        };
+        self.spawn_request("Opening link...", request, move |app, loaded| {
+            let (projects, project_id, tasks, task_id, workspaces, workspace_id) = loaded?;
+            // The user went elsewhere while the link was loading
+            if app.view != origin || app.is_busy() {
+                return Ok(());
+            }
+            app.apply_loaded(Loaded::Projects(projects));
+            let Some(index) = app.projects.iter().position(|p| p.id == project_id) else {
+                return Err(anyhow!("Project {project_id} not found"));
+            };
+            app.selected_project_index = index;
+            app.selected_project = app.projects.get(index).cloned();
+            app.reset_board_filters();
+            app.navigate_to(View::Tasks);
+            if let Some(tasks) = tasks {
+                app.apply_loaded(tasks);
+            }
+            app.load_project_repos();
+
+            let Some(task_id) = task_id else {
+                return Ok(());
+            };
+            let Some(task) = app.tasks.iter().find(|t| t.task.id == task_id).cloned() else {
+                return Err(anyhow!("Task {task_id} not found"));
+            };
+            app.select_board_task(task_id, task.task.status);
+            app.selected_task = Some(task);
+            app.selected_workspace_index = 0;
+
+            let Some(workspace_id) = workspace_id else {
+                app.navigate_to(View::TaskDetail);
+                app.load_task_detail();
+                return Ok(());
+            };
+            if let Some(workspaces) = workspaces {
+                app.apply_loaded(workspaces);
+            }
+            let Some(index) = app.workspaces.iter().position(|w| w.id == workspace_id) else {
+                return Err(anyhow!("Workspace {workspace_id} not found"));
+            };
+            app.selected_workspace_index = index;
+            app.selected_workspace = app.workspaces.get(index).cloned();
+            app.navigate_to(View::Workspaces);
+            app.navigate_to(View::WorkspaceDetail);
+            if !logs {
+                app.load_workspace_details();
+                return Ok(());
+            }
+            app.perform_then(
+                RetryableAction::LoadWorkspaceDetails { workspace_id },
+                |app, result| {
+                    result?;
+                    if app.view == View::WorkspaceDetail {
+                        app.open_session_logs();
+                    }
+                    Ok(())
+                },
+            );
+            Ok(())
+        });
     }
 
     // =========================================================================
@@ -564,26 +7436,32 @@ impl App {
     /// Move selection up in the current list.
     pub fn move_up(&mut self) {
         match self.view {
-            View::Projects => {
-                if self.selected_project_index > 0 {
-                    self.selected_project_index -= 1;
-                }
+            View::Projects if self.selected_project_index > 0 => {
+                self.selected_project_index -= 1;
             }
             View::Tasks => {
-                let column_index = match self.selected_column {
-                    TaskColumn::Todo => 0,
-                    TaskColumn::InProgress => 1,
-                    TaskColumn::InReview => 2,
-                    TaskColumn::Done => 3,
-                };
+                let column_index = self.selected_column.index();
                 if self.selected_task_indices[column_index] > 0 {
                     self.selected_task_indices[column_index] -= 1;
                 }
             }
-            View::Workspaces => {
-                if self.selected_workspace_index > 0 {
-                    self.selected_workspace_index -= 1;
-                }
+            View::Workspaces if self.selected_workspace_index > 0 => {
+                self.selected_workspace_index -= 1;
+            }
+            View::Queue if self.selected_queue_index > 0 => {
+                self.selected_queue_index -= 1;
+            }
+            View::Failures if self.selected_failure_index > 0 => {
+                self.selected_failure_index -= 1;
+            }
+            View::Trash if self.selected_trash_index > 0 => {
+                self.selected_trash_index -= 1;
+            }
+            View::TaskTree if self.selected_tree_index > 0 => {
+                self.selected_tree_index -= 1;
+            }
+            View::Diff if self.selected_hunk_index > 0 => {
+                self.selected_hunk_index -= 1;
             }
             _ => {}
         }
@@ -592,27 +7470,43 @@ impl App {
     /// Move selection down in the current list.
     pub fn move_down(&mut self) {
         match self.view {
-            View::Projects => {
-                if self.selected_project_index < self.projects.len().saturating_sub(1) {
-                    self.selected_project_index += 1;
-                }
+            View::Projects
+                if self.selected_project_index < self.projects.len().saturating_sub(1) =>
+            {
+                self.selected_project_index += 1;
             }
             View::Tasks => {
-                let column_index = match self.selected_column {
-                    TaskColumn::Todo => 0,
-                    TaskColumn::InProgress => 1,
-                    TaskColumn::InReview => 2,
-                    TaskColumn::Done => 3,
-                };
+                let column_index = self.selected_column.index();
                 let tasks = self.tasks_for_column(self.selected_column);
                 if self.selected_task_indices[column_index] < tasks.len().saturating_sub(1) {
                     self.selected_task_indices[column_index] += 1;
                 }
             }
-            View::Workspaces => {
-                if self.selected_workspace_index < self.workspaces.len().saturating_sub(1) {
-                    self.selected_workspace_index += 1;
-                }
+            View::Workspaces
+                if self.selected_workspace_index < self.workspaces.len().saturating_sub(1) =>
+            {
+                self.selected_workspace_index += 1;
+            }
+            View::Queue
+                if self.selected_queue_index < self.attempt_queue.len().saturating_sub(1) =>
+            {
+                self.selected_queue_index += 1;
+            }
+            View::Failures
+                if self.selected_failure_index < self.failures.len().saturating_sub(1) =>
+            {
+                self.selected_failure_index += 1;
+            }
+            View::Trash if self.selected_trash_index < self.trash.len().saturating_sub(1) => {
+                self.selected_trash_index += 1;
+            }
+            View::TaskTree
+                if self.selected_tree_index < self.task_tree.len().saturating_sub(1) =>
+            {
+                self.selected_tree_index += 1;
+            }
+            View::Diff if self.selected_hunk_index < self.diff_hunks.len().saturating_sub(1) => {
+                self.selected_hunk_index += 1;
             }
             _ => {}
         }
@@ -621,14 +7515,45 @@ impl App {
     /// Move selection left (columns in tasks view).
     pub fn move_left(&mut self) {
         if self.view == View::Tasks {
-            self.selected_column = self.selected_column.prev();
+            let index = self.selected_column.index();
+            self.selected_column = self.visible_columns()[index.saturating_sub(1)];
         }
     }
 
     /// Move selection right (columns in tasks view).
     pub fn move_right(&mut self) {
         if self.view == View::Tasks {
-            self.selected_column = self.selected_column.next();
+            let columns = self.visible_columns();
+            let index = (self.selected_column.index() + 1).min(columns.len() - 1);
+            self.selected_column = columns[index];
         }
     }
 }
+
+/// Whether `variant` is the chosen one, where `None` means the executor's default.
+fn is_chosen_variant(chosen: Option<&str>, variant: &ExecutorVariant) -> bool {
+    match chosen {
+        Some(name) => name == variant.name,
+        None => variant.is_default(),
+    }
+}
+
+/// The variant after (or before) the chosen one in `options`, or `None` when there
+/// is nothing to choose from. The default variant is returned as `Some(None)`.
+fn step_variant(
+    options: &[&ExecutorVariant],
+    chosen: Option<&str>,
+    forward: bool,
+) -> Option<Option<String>> {
+    if options.is_empty() {
+        return None;
+    }
+    let count = options.len();
+    let next = match options.iter().position(|v| is_chosen_variant(chosen, v)) {
+        Some(i) if forward => (i + 1) % count,
+        Some(i) => (i + count - 1) % count,
+        None => 0,
+    };
+    let variant = options[next];
+    Some((!variant.is_default()).then(|| variant.name.clone()))
+}
@@ -0,0 +1,138 @@
+//! Operations made of several steps, such as merging every repo of a workspace.
+//!
+//! The steps of a [`Batch`] run one after another. A failed step doesn't stop the
+//! ones after it, and cancelling skips the steps that haven't started yet while
+//! the running one finishes.
+
+use std::time::Instant;
+
+/// Where a step of a batch is at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepState {
+    Pending,
+    Running,
+    Done,
+    Failed(String),
+    /// Cancelled before it started
+    Skipped,
+}
+
+/// One step of a batch, carrying the work to do for it
+#[derive(Debug)]
+pub struct BatchStep<W> {
+    /// Shown in the progress widget, e.g. the repo or task the step is about
+    pub label: String,
+    pub state: StepState,
+    work: Option<W>,
+}
+
+#[derive(Debug)]
+pub struct Batch<W> {
+    /// What the batch does, e.g. "Merging all repos"
+    pub title: String,
+    pub steps: Vec<BatchStep<W>>,
+    pub started_at: Instant,
+}
+
+impl<W> Batch<W> {
+    pub fn new(title: impl Into<String>, steps: impl IntoIterator<Item = (String, W)>) -> Self {
+        Self {
+            title: title.into(),
+            steps: steps
+                .into_iter()
+                .map(|(label, work)| BatchStep {
+                    label,
+                    state: StepState::Pending,
+                    work: Some(work),
+                })
+                .collect(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Mark the next pending step running and hand out its work. `None` when
+    /// a step is still running or none are left.
+    pub fn start_next(&mut self) -> Option<W> {
+        if self.current().is_some() {
+            return None;
+        }
+        let step = self
+            .steps
+            .iter_mut()
+            .find(|step| step.state == StepState::Pending)?;
+        step.state = StepState::Running;
+        step.work.take()
+    }
+
+    /// Record how the running step went.
+    pub fn finish_current(&mut self, result: Result<(), String>) {
+        if let Some(step) = self
+            .steps
+            .iter_mut()
+            .find(|step| step.state == StepState::Running)
+        {
+            step.state = match result {
+                Ok(()) => StepState::Done,
+                Err(error) => StepState::Failed(error),
+            };
+        }
+    }
+
+    /// Skip the steps that haven't started. Returns how many were skipped.
+    pub fn cancel(&mut self) -> usize {
+        let mut skipped = 0;
+        for step in &mut self.steps {
+            if step.state == StepState::Pending {
+                step.state = StepState::Skipped;
+                step.work = None;
+                skipped += 1;
+            }
+        }
+        skipped
+    }
+
+    /// The step that is running, if any
+    pub fn current(&self) -> Option<&BatchStep<W>> {
+        self.steps
+            .iter()
+            .find(|step| step.state == StepState::Running)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|step| !matches!(step.state, StepState::Pending | StepState::Running))
+    }
+
+    /// Steps that succeeded
+    pub fn done(&self) -> usize {
+        self.count(|state| *state == StepState::Done)
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|state| matches!(state, StepState::Failed(_)))
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.count(|state| *state == StepState::Skipped)
+    }
+
+    /// One line about the outcome, e.g. "Merging all repos: 5/7 done, 1 failed, 1 skipped"
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{}: {}/{} done", self.title, self.done(), self.steps.len());
+        if self.failed() > 0 {
+            summary.push_str(&format!(", {} failed", self.failed()));
+        }
+        if self.skipped() > 0 {
+            summary.push_str(&format!(", {} skipped", self.skipped()));
+        }
+        summary
+    }
+
+    fn count(&self, predicate: impl Fn(&StepState) -> bool) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| predicate(&step.state))
+            .count()
+    }
+}
@@ -1,20 +1,101 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// Vibe Kanban CLI - Terminal-based real-time task list
 #[derive(Parser, Debug)]
 #[command(name = "vibe-kanban-cli")]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
-    /// Vibe Kanban server URL
-    #[arg(short, long, default_value = "http://localhost:5173")]
-    pub server: String,
+    /// Vibe Kanban server URL [default: `server` from the config file, or
+    /// http://localhost:5173]
+    #[arg(short, long)]
+    pub server: Option<String>,
+
+    /// Config file to use instead of ~/.config/vibe-kanban-cli/config.toml
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
 
     /// Enable debug logging
     #[arg(short, long)]
     pub debug: bool,
 
+    /// Hold back the live event stream, the health check and repository loads
+    /// until the first screen of data is drawn
+    #[arg(long)]
+    pub fast_start: bool,
+
+    /// Render a view once to stdout and exit, instead of opening the interactive UI
+    #[arg(long, value_enum, value_name = "VIEW")]
+    pub print: Option<PrintView>,
+
+    /// Project ID or name to open the board of, or for --print
+    #[arg(long)]
+    pub project: Option<String>,
+
+    /// Task ID, or task slug with --project, to open the latest workspace of,
+    /// or for --print workspaces/workspace
+    #[arg(long)]
+    pub task: Option<String>,
+
+    /// Workspace ID to open, or for --print workspace (defaults to the task's
+    /// first workspace)
+    #[arg(long)]
+    pub workspace: Option<String>,
+
+    /// Open the session logs of the workspace given with --task or --workspace
+    #[arg(long, conflicts_with = "print")]
+    pub logs: bool,
+
+    /// Width of the --print output (defaults to the terminal width, or 120)
+    #[arg(long, requires = "print")]
+    pub width: Option<u16>,
+
+    /// Height of the --print output (defaults to the terminal height, or 40)
+    #[arg(long, requires = "print")]
+    pub height: Option<u16>,
+
+    /// How subcommands print what they fetch or change; --json is short for
+    /// --format json
+    #[arg(long, value_enum, global = true, default_value = "table")]
+    pub format: OutputFormat,
+
+    /// Subcommand to run; launches the interactive UI when omitted
     #[command(subcommand)]
-    pub command: Command,
+    pub command: Option<Command>,
+}
+
+/// Output formats of subcommands
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text
+    Table,
+    /// Pretty-printed JSON, as the server returns it
+    Json,
+    /// One row per item with a header; nested values are flattened into
+    /// dotted columns
+    Csv,
+}
+
+impl OutputFormat {
+    /// The format to use given a command's `--json` flag
+    pub fn or_json(self, json: bool) -> Self {
+        if json { OutputFormat::Json } else { self }
+    }
+}
+
+/// Views that can be rendered with --print
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrintView {
+    Projects,
+    Tasks,
+    Workspaces,
+    /// Detail of one workspace
+    Workspace,
+    Queue,
+    /// Workspaces whose latest attempt failed, of --project or every project
+    Failures,
+    Help,
 }
 
 #[derive(Subcommand, Debug)]
@@ -38,8 +119,10 @@ pub enum Command {
         status: String,
 
         /// Tool/executor to use (e.g. codex, claude-code, cursor, gemini)
-        #[arg(long, alias = "executor", default_value = "codex")]
-        tool: String,
+        /// [default: `default_executor` from the config file or the server's user
+        /// preferences, or codex]
+        #[arg(long, alias = "executor")]
+        tool: Option<String>,
 
         /// Model/variant for the executor
         #[arg(long)]
@@ -54,6 +137,11 @@ pub enum Command {
         #[arg(long)]
         branch: Option<String>,
 
+        /// Name of the branch to create for the attempt (generated from the title
+        /// by default; must follow the project's branch policy)
+        #[arg(long)]
+        attempt_branch: Option<String>,
+
         /// Watch the created task in real time
         #[arg(long)]
         watch: bool,
@@ -72,6 +160,11 @@ pub enum Command {
         /// Task slug (derived from title) to watch
         #[arg(long)]
         slug: Option<String>,
+
+        /// Print a line per status change or finished attempt instead of
+        /// redrawing, for piping into scripts (`--format json` for JSON Lines)
+        #[arg(long)]
+        lines: bool,
     },
     /// List projects available on the server
     Projects {
@@ -84,6 +177,125 @@ pub enum Command {
         #[command(subcommand)]
         command: ProjectCommand,
     },
+    /// List, show, create, update and delete tasks
+    Task {
+        #[command(subcommand)]
+        command: TaskCommand,
+    },
+    /// Start attempts on existing tasks
+    Attempt {
+        #[command(subcommand)]
+        command: AttemptCommand,
+    },
+    /// List, stop, merge and push a task's workspaces
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommand,
+    },
+    /// Show or manage a project's monthly cost budget
+    Budget {
+        #[command(subcommand)]
+        command: BudgetCommand,
+    },
+    /// Show or manage the naming rules for a project's attempt branches
+    BranchPolicy {
+        #[command(subcommand)]
+        command: BranchPolicyCommand,
+    },
+    /// Show or manage the branches merges, pushes and rebases must not write to
+    /// without confirmation
+    ProtectedBranches {
+        #[command(subcommand)]
+        command: ProtectedBranchCommand,
+    },
+    /// Keep a long-lived workspace rebased onto its target branches
+    AutoRebase {
+        #[command(subcommand)]
+        command: AutoRebaseCommand,
+    },
+    /// Reuse plans of successful team executions for similar epics
+    Plans {
+        #[command(subcommand)]
+        command: PlanCommand,
+    },
+    /// Review and edit the agent profiles team executions run on
+    Profiles {
+        #[command(subcommand)]
+        command: ProfileCommand,
+    },
+    /// Show or change the limits the team planner decomposes epics with
+    Planner {
+        #[command(subcommand)]
+        command: PlannerCommand,
+    },
+    /// Show the subtasks of a team execution and how their branches stack
+    Teams {
+        #[command(subcommand)]
+        command: TeamCommand,
+    },
+    /// Show the skills a project's repositories call for, and worker profiles lacking them
+    Skills {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the local journal of changes made through this CLI
+    History {
+        /// Number of most recent entries to show
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Only show entries whose endpoint contains this text (e.g. a task ID)
+        #[arg(long)]
+        filter: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List processes the server marked failed because they had stopped running
+    Orphans {
+        /// Project ID or name
+        #[arg(long)]
+        project: Option<String>,
+        /// Number of most recent entries to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Save a project's board under a name and later list what changed since,
+    /// e.g. for a sprint review
+    Snapshot {
+        #[command(subcommand)]
+        command: SnapshotCommand,
+    },
+    /// Write a task up as a prompt for agents or chat tools outside Vibe Kanban
+    Prompt {
+        /// Task ID, or task slug with --project
+        task: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: Option<String>,
+        /// Write the prompt to this file instead of printing it
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Open the interactive UI at the project, task or workspace a web UI link
+    /// points at
+    Open {
+        /// Web UI URL, e.g. http://localhost:5173/workspaces/<id>, or the same
+        /// path as a vibe-kanban:// link
+        url: String,
+    },
+    /// Keep the theme, default executor, saved filters and key bindings on the
+    /// server, so they follow you to other machines
+    Preferences {
+        #[command(subcommand)]
+        command: PreferencesCommand,
+    },
     /// Manage a local Vibe Kanban server process
     Server {
         #[command(subcommand)]
@@ -107,6 +319,476 @@ pub enum ProjectCommand {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum TaskCommand {
+    /// List a project's tasks
+    List {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Only tasks with this status: todo, inprogress, inreview, done, cancelled
+        #[arg(long)]
+        status: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a task
+    Show {
+        /// Task ID, or task slug with --project
+        task: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Create a task without starting an attempt
+    Create {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Task title
+        #[arg(long)]
+        title: String,
+        /// Task description
+        #[arg(long)]
+        description: Option<String>,
+        /// Task status: todo, inprogress, inreview, done, cancelled
+        #[arg(long, default_value = "todo")]
+        status: String,
+        /// Output the created task as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Change a task; unset fields are left as they are
+    Update {
+        /// Task ID, or task slug with --project
+        task: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: Option<String>,
+        /// New title
+        #[arg(long)]
+        title: Option<String>,
+        /// New description
+        #[arg(long)]
+        description: Option<String>,
+        /// New status: todo, inprogress, inreview, done, cancelled
+        #[arg(long)]
+        status: Option<String>,
+        /// Output the updated task as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Move a task to the trash, or delete it and its workspaces on servers
+    /// without one
+    Delete {
+        /// Task ID, or task slug with --project
+        task: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AttemptCommand {
+    /// Start an attempt on a task
+    Start {
+        /// Task ID, or task slug with --project
+        task: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: Option<String>,
+        /// Tool/executor to use (e.g. codex, claude-code, cursor, gemini)
+        /// [default: `default_executor` from the config file or the server's user
+        /// preferences, or codex]
+        #[arg(long, alias = "executor")]
+        tool: Option<String>,
+        /// Model/variant for the executor
+        #[arg(long)]
+        model: Option<String>,
+        /// Repo/worktree to use (name, display name, or UUID). Can be repeated.
+        /// Use "repo@branch" to override per-repo branch.
+        #[arg(long = "repo", alias = "worktree")]
+        repos: Vec<String>,
+        /// Branch name (default branch by default)
+        #[arg(long)]
+        branch: Option<String>,
+        /// Name of the branch to create for the attempt (generated from the title
+        /// by default; must follow the project's branch policy)
+        #[arg(long)]
+        attempt_branch: Option<String>,
+        /// Output the created workspace as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WorkspaceCommand {
+    /// List a task's workspaces
+    List {
+        /// Task ID, or task slug with --project
+        task: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Stop the workspace's running processes
+    Stop {
+        /// Workspace ID
+        workspace: String,
+    },
+    /// Merge the workspace's branch into its target branch
+    Merge {
+        /// Workspace ID
+        workspace: String,
+        /// Repo name, display name, or ID (optional if the workspace has one repo)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Go ahead when the target branch is protected with confirmation
+        #[arg(long)]
+        confirm_protected: bool,
+    },
+    /// Push the workspace's branch to the remote
+    Push {
+        /// Workspace ID
+        workspace: String,
+        /// Repo name, display name, or ID (optional if the workspace has one repo)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Go ahead when the branch is protected with confirmation
+        #[arg(long)]
+        confirm_protected: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PlanCommand {
+    /// List saved plan templates
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the subtasks of a plan template
+    Show {
+        /// Template ID or name
+        template: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Save the plan of a completed team execution as a template
+    Save {
+        /// Team execution ID
+        #[arg(long)]
+        team: String,
+        /// Template name (defaults to the epic title)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Plan a team execution from a template instead of decomposing its epic
+    Use {
+        /// Team execution ID
+        #[arg(long)]
+        team: String,
+        /// Template ID or name
+        #[arg(long)]
+        template: String,
+    },
+    /// Delete a plan template
+    Delete {
+        /// Template ID or name
+        template: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommand {
+    /// Record the status of every task, replacing a snapshot of the same name
+    Save {
+        /// Snapshot name, e.g. sprint-12 or monday
+        name: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+    },
+    /// List a project's snapshots, oldest first
+    List {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the tasks added, moved, completed and removed since a snapshot
+    Diff {
+        /// Snapshot name
+        name: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Write the changes to this file as a Markdown report instead of
+        /// printing them
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Delete a snapshot
+    Delete {
+        /// Snapshot name
+        name: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PreferencesCommand {
+    /// Show the preferences kept on the server and those the config file overrides
+    Show {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Save the theme, default executor, saved filters and key bindings of the
+    /// config file to the server, keeping the others already there
+    Push,
+    /// Remove the preferences kept on the server
+    Clear,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BudgetCommand {
+    /// Show this month's spend by executor against the cap
+    Show {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Set the monthly cap (in USD)
+    Set {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Monthly cap in USD, e.g. 50 or 12.50
+        #[arg(long)]
+        cap: f64,
+    },
+    /// Remove the monthly cap
+    Clear {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+    },
+    /// Lift the cap for the rest of this month
+    Override {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Restore the cap instead of lifting it
+        #[arg(long)]
+        off: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BranchPolicyCommand {
+    /// Show the project's branch naming policy
+    Show {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Replace the project's branch naming policy
+    Set {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Prefix branches must start with, e.g. "feature/". Can be repeated;
+        /// any prefix is allowed when omitted
+        #[arg(long = "prefix", value_name = "PREFIX")]
+        prefixes: Vec<String>,
+        /// Maximum length of a branch name in characters
+        #[arg(long)]
+        max_length: Option<i64>,
+        /// Characters branch names may not contain, e.g. "_#"
+        #[arg(long)]
+        forbidden: Option<String>,
+    },
+    /// Remove the project's branch naming policy
+    Clear {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProtectedBranchCommand {
+    /// List the protected branches of a repository
+    List {
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Repo name, display name, or ID (optional if the project has one repo)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Protect a branch name or glob such as "release/*", or change how it is protected
+    Add {
+        /// Branch name or glob; `*` matches within one path segment, `**` across
+        pattern: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Repo name, display name, or ID (optional if the project has one repo)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Refuse operations on matching branches instead of asking for confirmation
+        #[arg(long)]
+        block: bool,
+    },
+    /// Lift the protection of a branch name or glob
+    Remove {
+        /// Pattern as it was added
+        pattern: String,
+        /// Project ID or name
+        #[arg(long)]
+        project: String,
+        /// Repo name, display name, or ID (optional if the project has one repo)
+        #[arg(long)]
+        repo: Option<String>,
+    },
+}
+
+/// When `auto-rebase set` rebases a workspace
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoRebaseOn {
+    /// Once a night, if a target branch has new commits
+    Nightly,
+    /// Whenever a target branch has new commits
+    TargetMoved,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AutoRebaseCommand {
+    /// Show a workspace's auto-rebase policy and why it is paused, if it is
+    Show {
+        /// Workspace ID
+        workspace: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rebase a workspace onto its target branches automatically. Rebases that
+    /// run into conflicts are aborted and pause the policy.
+    Set {
+        /// Workspace ID
+        workspace: String,
+        /// When to rebase
+        #[arg(long, value_enum, default_value = "nightly")]
+        on: AutoRebaseOn,
+    },
+    /// Resume a policy that paused, once the conflicts are resolved
+    Resume {
+        /// Workspace ID
+        workspace: String,
+    },
+    /// Stop rebasing a workspace automatically
+    Disable {
+        /// Workspace ID
+        workspace: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProfileCommand {
+    /// List active agent profiles
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Create default planner, worker and reviewer profiles for roles no profile fills
+    Seed,
+    /// Change an agent profile
+    Update {
+        /// Profile ID or name
+        profile: String,
+        /// Executor to run on (codex, claude-code, cursor, gemini, ...)
+        #[arg(long)]
+        executor: Option<String>,
+        /// Executor variant, e.g. PLAN
+        #[arg(long)]
+        variant: Option<String>,
+        /// Tasks the profile may work on at once
+        #[arg(long)]
+        max_concurrent: Option<i32>,
+        /// Preference when several profiles fit (higher first)
+        #[arg(long)]
+        priority: Option<i32>,
+        /// Enable or disable the profile
+        #[arg(long)]
+        active: Option<bool>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PlannerCommand {
+    /// Show the planner settings
+    Show {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Change planner settings; unset ones are left as they are
+    Set {
+        /// Subtasks an epic needs before it runs as a team
+        #[arg(long)]
+        team_threshold: Option<i32>,
+        /// Subtasks the planner splits an epic into at most
+        #[arg(long)]
+        max_subtasks: Option<i32>,
+        /// Workers per team execution unless it sets its own
+        #[arg(long)]
+        max_parallel_workers: Option<i32>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TeamCommand {
+    /// Show the subtasks as a dependency graph, with the branch each one is
+    /// stacked on
+    Dag {
+        /// Team execution ID
+        team: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ServerCommand {
     /// Start the server (optionally in the background)
@@ -0,0 +1,75 @@
+//! Clipboard access.
+//!
+//! On the machine itself the platform's clipboard tool (`pbcopy`, `wl-copy`,
+//! `xclip`, ...) does the copying. Over SSH, or without such a tool, the OSC 52
+//! escape sequence asks the terminal to copy instead, which also works inside
+//! tmux (with `set-clipboard on`) without a display server.
+
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+/// Put `text` on the system clipboard.
+pub fn copy(text: &str) -> io::Result<()> {
+    let over_ssh =
+        std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+    if !over_ssh && copy_with_tool(text).is_ok() {
+        return Ok(());
+    }
+    copy_with_terminal(text)
+}
+
+/// Copy with the first of the platform's clipboard tools that works.
+fn copy_with_tool(text: &str) -> io::Result<()> {
+    for (program, args) in clipboard_tools() {
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        // Dropping stdin closes it, so the tool sees the end of the text
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "No clipboard tool found",
+    ))
+}
+
+/// Commands that copy their input, with their arguments
+fn clipboard_tools() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        return vec![("pbcopy", &[])];
+    }
+    if cfg!(windows) {
+        return vec![("clip", &[])];
+    }
+    let mut tools = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        tools.push(("wl-copy", &[][..]));
+    }
+    if std::env::var_os("DISPLAY").is_some() {
+        tools.push(("xclip", &["-selection", "clipboard"][..]));
+        tools.push(("xsel", &["--clipboard", "--input"][..]));
+    }
+    tools
+}
+
+/// Ask the terminal to put `text` on the clipboard.
+fn copy_with_terminal(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
@@ -0,0 +1,401 @@
+//! Persistent settings, read from `~/.config/vibe-kanban-cli/config.toml` (or the
+//! file given with `--config`) at startup. Command-line flags take precedence.
+//!
+//! ```toml
+//! server = "http://localhost:3000"
+//! # Reload the current view every 30 seconds; 0 turns reloading off
+//! refresh_interval = 30
+//! default_executor = "claude-code"
+//! # "dark", "light", "solarized", "high-contrast" or "monochrome"
+//! theme = "solarized"
+//! # Leave the mouse to the terminal, e.g. for selecting text under tmux
+//! mouse = false
+//! # Show the F1-F10 shortcut bar and bind its keys
+//! function_keys = true
+//! # 16 colours and ASCII only, for serial consoles and old terminals; detected
+//! # from TERM and the locale when unset
+//! compat = true
+//! # Opens workspaces with `e`, the directory going last; $VISUAL or $EDITOR when unset
+//! editor = "code"
+//! # Ask for MessagePack ("msgpack") or CBOR ("cbor") responses, which servers
+//! # that support them send smaller; needs a build with that feature
+//! wire_format = "msgpack"
+//! # Show times as dates rather than "3m ago", in a time zone the team agrees on;
+//! # see [`crate::datetime`]
+//! timestamps = "absolute"
+//! time_zone = "+05:30"
+//!
+//! # Still spinners and at most 10 frames a second, e.g. over a slow SSH link
+//! [animation]
+//! enabled = false
+//! max_fps = 10
+//!
+//! [keybindings]
+//! quit = "ctrl+q"
+//! refresh = ["r", "f5"]
+//!
+//! # Task order of the board columns of a project, set with `s` on the board:
+//! # "created", "updated", "title", "complexity" or "manual"
+//! [board_sort.6f1c0e52-5a0b-4b8e-9d3c-2a4f1e7b9c10]
+//! todo = "title"
+//! inreview = "updated"
+//!
+//! # Prompts picked with `t` in the follow-up composer, in place of the built-in
+//! # ones; see [`crate::templates`] for the placeholders
+//! [[follow_up_templates]]
+//! name = "Fix clippy"
+//! prompt = "Run cargo clippy on {branch} and fix every warning."
+//!
+//! # Board searches used as `@name`, e.g. `@mine login`
+//! [saved_filters]
+//! mine = "executor:claude status:progress"
+//! ```
+//!
+//! The theme, default executor, saved filters and key bindings can also be kept
+//! on the server with `vibe-kanban-cli preferences push`, so they follow the user
+//! to other machines. Those from the server fill in what this file leaves unset.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use toml_edit::{DocumentMut, Table};
+use uuid::Uuid;
+
+use crate::{
+    api::codec::WireFormat,
+    datetime::{TimeZone, TimestampStyle},
+    keymap::{KeyList, KeyMap},
+    templates::FollowUpTemplate,
+    types::{BaseCodingAgent, TaskStatus, UserPreferences},
+    ui::{compat, theme::Theme},
+    utils::parse_executor,
+};
+
+/// Server URL used when neither `--server` nor the config file sets one
+pub const DEFAULT_SERVER: &str = "http://localhost:5173";
+
+/// Seconds between reloads of the current view when the config file doesn't say
+const DEFAULT_REFRESH_INTERVAL: u64 = 10;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: Option<String>,
+    /// Seconds between background reloads of the current view; 0 turns them off
+    pub refresh_interval: Option<u64>,
+    /// Executor preselected for new attempts and used by `create` without `--tool`
+    #[serde(deserialize_with = "deserialize_executor")]
+    pub default_executor: Option<BaseCodingAgent>,
+    /// Initial theme; it can be changed at runtime with the theme picker.
+    /// Dark when neither this file nor the server's user preferences set one
+    pub theme: Option<Theme>,
+    /// Whether the interactive UI captures the mouse; on when unset
+    pub mouse: Option<bool>,
+    /// Whether F1 to F10 run the actions of the function key bar shown at the bottom
+    pub function_keys: bool,
+    /// Whether to render for a limited terminal, see [`crate::ui::compat`];
+    /// detected when unset
+    pub compat: Option<bool>,
+    /// Command that opens a workspace's directory, given as its last argument;
+    /// `$VISUAL` or `$EDITOR` when unset
+    pub editor: Option<String>,
+    /// Format to ask the server to respond in, see [`crate::api::codec`]
+    pub wire_format: WireFormat,
+    /// Whether times are shown relative to now or as dates; `A` switches
+    pub timestamps: TimestampStyle,
+    /// Time zone dates and clock times are shown in
+    pub time_zone: TimeZone,
+    /// Spinners and how often the interactive UI redraws
+    pub animation: Animation,
+    /// Keys of the interactive UI, see [`crate::keymap`]
+    pub keybindings: KeyMap,
+    /// Task order of the board columns, by project and column status
+    pub board_sort: HashMap<Uuid, HashMap<TaskStatus, TaskSort>>,
+    /// Prompts offered in the follow-up composer; the built-in ones when empty
+    pub follow_up_templates: Vec<FollowUpTemplate>,
+    /// Board search queries by name, used as `@name` in a search
+    pub saved_filters: BTreeMap<String, String>,
+    /// Where the config was read from, or would be; settings changed in the UI are saved there
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Spinners and redrawing of the interactive UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Animation {
+    /// Whether spinners turn; off, they stand still and the UI doesn't redraw
+    /// for them
+    pub enabled: bool,
+    /// Spinner frames a second
+    pub spinner_fps: u32,
+    /// Most frames drawn a second; whatever happens in between shows in the
+    /// next one
+    pub max_fps: u32,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            spinner_fps: 12,
+            max_fps: 30,
+        }
+    }
+}
+
+impl Animation {
+    /// How long each spinner frame is shown
+    pub fn spinner_frame_time(&self) -> Duration {
+        Duration::from_secs(1) / self.spinner_fps.max(1)
+    }
+
+    /// Shortest time between two frames
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_secs(1) / self.max_fps.max(1)
+    }
+}
+
+/// Order of the tasks in a board column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskSort {
+    /// Newest first, the order the server lists tasks in
+    #[default]
+    Created,
+    /// Most recently updated first
+    Updated,
+    /// Alphabetical
+    Title,
+    /// Simplest first, tasks without a complexity last
+    Complexity,
+    /// As arranged with Shift+J/K, kept with the tasks on the server;
+    /// tasks never arranged come last, newest first
+    Manual,
+}
+
+impl TaskSort {
+    pub fn next(self) -> Self {
+        match self {
+            TaskSort::Created => TaskSort::Updated,
+            TaskSort::Updated => TaskSort::Title,
+            TaskSort::Title => TaskSort::Complexity,
+            TaskSort::Complexity => TaskSort::Manual,
+            TaskSort::Manual => TaskSort::Created,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskSort::Created => "created",
+            TaskSort::Updated => "updated",
+            TaskSort::Title => "title",
+            TaskSort::Complexity => "complexity",
+            TaskSort::Manual => "manual",
+        }
+    }
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/vibe-kanban-cli/config.toml`, or under `~/.config`.
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+            .map(|dir| dir.join("vibe-kanban-cli").join("config.toml"))
+    }
+
+    /// Load the config from `path`, which must exist, or else from the default
+    /// location if there is a file there.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Self::default_path() {
+                Some(path) if path.exists() => path,
+                path => {
+                    return Ok(Self {
+                        path,
+                        ..Self::default()
+                    });
+                }
+            },
+        };
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let config: Self = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+        Ok(Self {
+            path: Some(path),
+            ..config
+        })
+    }
+
+    /// Task order of a board column of a project.
+    pub fn board_sort(&self, project_id: Uuid, status: TaskStatus) -> TaskSort {
+        self.board_sort
+            .get(&project_id)
+            .and_then(|sorts| sorts.get(&status))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Change the task order of a board column of a project and save it to the
+    /// config file, leaving the rest of the file as it is.
+    pub fn set_board_sort(
+        &mut self,
+        project_id: Uuid,
+        status: TaskStatus,
+        sort: TaskSort,
+    ) -> Result<()> {
+        self.board_sort
+            .entry(project_id)
+            .or_default()
+            .insert(status, sort);
+
+        let path = self
+            .path
+            .as_deref()
+            .context("No location for the config file")?;
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read config file {}", path.display()));
+            }
+        };
+        let mut doc: DocumentMut = contents
+            .parse()
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+        // One `[board_sort.<project>]` table per project, without an empty `[board_sort]`
+        let board_sort = doc.entry("board_sort").or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            table.into()
+        });
+        let project = board_sort
+            .as_table_like_mut()
+            .context("`board_sort` in the config file is not a table")?
+            .entry(&project_id.to_string())
+            .or_insert(toml_edit::table());
+        project[status.as_str()] = toml_edit::value(sort.as_str());
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(path, doc.to_string())
+            .with_context(|| format!("Failed to write config file {}", path.display()))
+    }
+
+    /// Fill in the settings this file leaves unset from the user's preferences
+    /// on the server. Returns what couldn't be applied, e.g. a theme this
+    /// version doesn't know.
+    pub fn apply_preferences(&mut self, preferences: &UserPreferences) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.theme.is_none()
+            && let Some(name) = &preferences.theme
+        {
+            match toml::Value::from(name.as_str()).try_into() {
+                Ok(theme) => self.theme = Some(theme),
+                Err(_) => problems.push(format!("unknown theme '{}'", name)),
+            }
+        }
+        if self.default_executor.is_none()
+            && let Some(name) = &preferences.default_executor
+        {
+            match parse_executor(name) {
+                Ok(executor) => self.default_executor = Some(executor),
+                Err(e) => problems.push(e.to_string()),
+            }
+        }
+        for (name, query) in &preferences.saved_filters {
+            self.saved_filters
+                .entry(name.clone())
+                .or_insert_with(|| query.clone());
+        }
+        // One at a time, so a binding this version can't read doesn't lose the rest
+        for (action, keys) in &preferences.keybindings {
+            let mut table = toml::Table::new();
+            table.insert(action.clone(), keys.clone().into());
+            match toml::Value::Table(table).try_into::<KeyMap>() {
+                Ok(keymap) => self.keybindings.fill_unbound(keymap),
+                Err(_) => problems.push(format!("can't bind '{}' to {:?}", action, keys)),
+            }
+        }
+        problems
+    }
+
+    /// The settings of this file that can be kept on the server, as written.
+    /// Settings filled in from the server are left out.
+    pub fn local_preferences(&self) -> Result<UserPreferences> {
+        /// The settings shared through the server, leaving the rest of the file alone
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct Shared {
+            theme: Option<String>,
+            default_executor: Option<String>,
+            saved_filters: BTreeMap<String, String>,
+            keybindings: BTreeMap<String, KeyList>,
+        }
+
+        let Some(path) = self.path.as_deref().filter(|path| path.exists()) else {
+            return Ok(UserPreferences::default());
+        };
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let shared: Shared = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+        Ok(UserPreferences {
+            theme: shared.theme,
+            default_executor: shared.default_executor,
+            saved_filters: shared.saved_filters,
+            keybindings: shared
+                .keybindings
+                .into_iter()
+                .map(|(action, keys)| (action, keys.into_vec()))
+                .collect(),
+        })
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme.unwrap_or_default()
+    }
+
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        Some(self.refresh_interval.unwrap_or(DEFAULT_REFRESH_INTERVAL))
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+    }
+
+    pub fn mouse(&self) -> bool {
+        self.mouse.unwrap_or(true)
+    }
+
+    pub fn compat(&self) -> bool {
+        self.compat.unwrap_or_else(compat::detect)
+    }
+
+    pub fn follow_up_templates(&self) -> Vec<FollowUpTemplate> {
+        if self.follow_up_templates.is_empty() {
+            FollowUpTemplate::defaults()
+        } else {
+            self.follow_up_templates.clone()
+        }
+    }
+}
+
+fn deserialize_executor<'de, D>(deserializer: D) -> Result<Option<BaseCodingAgent>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|name| parse_executor(&name).map_err(serde::de::Error::custom))
+        .transpose()
+}
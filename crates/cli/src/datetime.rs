@@ -0,0 +1,160 @@
+//! Parsing and showing the timestamps the server sends.
+//!
+//! The interactive UI shows times relative to now ("3m ago") unless the config
+//! file sets `timestamps = "absolute"`; `A` switches between the two. Absolute
+//! times are shown in the `time_zone` of the config file, so a team spread over
+//! regions can agree on one:
+//!
+//! ```toml
+//! timestamps = "absolute"
+//! # "local" (the default), "utc", or an offset from UTC such as "+05:30"
+//! time_zone = "utc"
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// Time zone absolute times are shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeZone {
+    /// The time zone of this machine
+    #[default]
+    Local,
+    Utc,
+    /// A fixed offset from UTC; daylight saving time is not followed
+    Fixed(FixedOffset),
+}
+
+impl TimeZone {
+    /// `at` in this time zone, formatted with a `strftime` format.
+    pub fn format(self, at: DateTime<Utc>, format: &str) -> String {
+        match self {
+            TimeZone::Local => at.with_timezone(&Local).format(format).to_string(),
+            TimeZone::Utc => at.format(format).to_string(),
+            TimeZone::Fixed(offset) => at.with_timezone(&offset).format(format).to_string(),
+        }
+    }
+}
+
+impl FromStr for TimeZone {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("local") {
+            return Ok(TimeZone::Local);
+        }
+        if input.eq_ignore_ascii_case("utc") || input == "Z" {
+            return Ok(TimeZone::Utc);
+        }
+        let Some(sign) = input.chars().next().filter(|c| matches!(c, '+' | '-')) else {
+            bail!(
+                "Unknown time zone `{}`: expected \"local\", \"utc\" or an offset such as \"+05:30\"",
+                input
+            );
+        };
+        let digits = input[1..].replace(':', "");
+        let (hours, minutes) = match digits.len() {
+            1 | 2 => (digits.as_str(), "0"),
+            4 => digits.split_at(2),
+            _ => bail!("Invalid UTC offset `{}`", input),
+        };
+        let hours: i32 = hours
+            .parse()
+            .with_context(|| format!("Invalid UTC offset `{}`", input))?;
+        let minutes: i32 = minutes
+            .parse()
+            .with_context(|| format!("Invalid UTC offset `{}`", input))?;
+        let seconds = (hours * 60 + minutes) * 60;
+        let offset = if sign == '-' {
+            FixedOffset::west_opt(seconds)
+        } else {
+            FixedOffset::east_opt(seconds)
+        };
+        match offset {
+            Some(offset) if minutes < 60 => Ok(TimeZone::Fixed(offset)),
+            _ => bail!("UTC offset `{}` is out of range", input),
+        }
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeZone::Local => write!(f, "local"),
+            TimeZone::Utc => write!(f, "UTC"),
+            TimeZone::Fixed(offset) => write!(f, "UTC{}", offset),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeZone {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// How the interactive UI shows timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampStyle {
+    /// "3m ago"
+    #[default]
+    Relative,
+    /// "2026-10-17 14:20" in the configured time zone
+    Absolute,
+}
+
+impl TimestampStyle {
+    pub fn toggle(self) -> Self {
+        match self {
+            TimestampStyle::Relative => TimestampStyle::Absolute,
+            TimestampStyle::Absolute => TimestampStyle::Relative,
+        }
+    }
+}
+
+/// Parse a timestamp as the server sends it: RFC 3339, or without an offset
+/// (taken as UTC) as SQLite stores them.
+pub fn parse(input: &str) -> Option<DateTime<Utc>> {
+    let input = input.trim();
+    DateTime::parse_from_rfc3339(input)
+        .map(|at| at.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S%.f")
+                .ok()
+                .map(|at| at.and_utc())
+        })
+}
+
+/// How long ago `at` was, e.g. "3m ago", or "in 5m" for times still to come.
+/// Times more than a month away are shown as a date.
+pub fn relative(at: DateTime<Utc>, zone: TimeZone) -> String {
+    let seconds = (Utc::now() - at).num_seconds();
+    let span = seconds.abs();
+    let amount = if span < 60 {
+        return "just now".to_string();
+    } else if span < 60 * 60 {
+        format!("{}m", span / 60)
+    } else if span < 24 * 60 * 60 {
+        format!("{}h", span / (60 * 60))
+    } else if span < 30 * 24 * 60 * 60 {
+        format!("{}d", span / (24 * 60 * 60))
+    } else {
+        return zone.format(at, "%Y-%m-%d");
+    };
+    if seconds < 0 {
+        format!("in {}", amount)
+    } else {
+        format!("{} ago", amount)
+    }
+}
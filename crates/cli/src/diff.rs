@@ -0,0 +1,338 @@
+//! Workspace diffs, split into hunks for review.
+//!
+//! The server streams a workspace's changes as JSON patches against a map of
+//! entries keyed by path, and sends `Ready` once the current changes are in.
+//! The hunks are worked out here from the old and new content of each file.
+
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use json_patch::Patch;
+use serde_json::Value;
+
+use crate::{
+    types::{Diff, DiffChangeKind},
+    utils::code_fence,
+};
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Largest line comparison table worked out; bigger rewrites show as one
+/// removal and one addition
+const MAX_COMPARISONS: usize = 4_000_000;
+
+/// Changed lines of a hunk quoted in a review note
+const MAX_EXCERPT_LINES: usize = 12;
+
+/// A message from a diff stream
+#[derive(Debug, Clone)]
+pub enum DiffEvent {
+    Patch(Patch),
+    /// The workspace's current changes have all been sent
+    Ready,
+}
+
+/// Parse a websocket message from a diff stream. Returns `None` for messages that
+/// carry nothing to apply.
+pub fn parse_diff_message(text: &str) -> Result<Option<DiffEvent>> {
+    let value: Value = serde_json::from_str(text).context("Failed to parse diff message")?;
+    if value.get("Ready").and_then(|v| v.as_bool()) == Some(true) {
+        return Ok(Some(DiffEvent::Ready));
+    }
+    match value.get("JsonPatch") {
+        Some(patch) => {
+            let patch =
+                serde_json::from_value(patch.clone()).context("Failed to parse diff patch")?;
+            Ok(Some(DiffEvent::Patch(patch)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// The changed files of a workspace, built up from its diff stream
+#[derive(Debug, Clone)]
+pub struct WorkspaceDiff {
+    state: Value,
+}
+
+impl Default for WorkspaceDiff {
+    fn default() -> Self {
+        Self {
+            state: serde_json::json!({ "entries": {} }),
+        }
+    }
+}
+
+impl WorkspaceDiff {
+    pub fn apply(&mut self, patch: &Patch) -> Result<()> {
+        json_patch::patch(&mut self.state, patch).context("Failed to apply diff patch")
+    }
+
+    /// The changed files, by path.
+    pub fn files(&self) -> Result<Vec<Diff>> {
+        let Some(entries) = self.state["entries"].as_object() else {
+            return Ok(Vec::new());
+        };
+        let mut files = entries
+            .values()
+            .map(|entry| serde_json::from_value::<Diff>(entry["content"].clone()))
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse diff")?;
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        Ok(files)
+    }
+}
+
+impl Diff {
+    /// Path of the file after the change, or before it for deletions
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkLine {
+    pub kind: HunkLineKind,
+    pub text: String,
+}
+
+/// A run of changes in one file, with the unchanged lines around it
+#[derive(Debug, Clone)]
+pub struct Hunk {
+    pub path: String,
+    /// `@@ -old +new @@` range, or what changed for files without shown content
+    pub header: String,
+    pub lines: Vec<HunkLine>,
+}
+
+impl Hunk {
+    /// The added and removed lines, as in a unified diff
+    fn excerpt(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line.kind {
+                HunkLineKind::Context => None,
+                HunkLineKind::Added => Some(format!("+{}", line.text)),
+                HunkLineKind::Removed => Some(format!("-{}", line.text)),
+            })
+            .take(MAX_EXCERPT_LINES)
+            .collect()
+    }
+}
+
+/// The hunks of a changed file. Files whose content isn't shown (binary, too
+/// large, renamed without changes) get one hunk without lines, so they can still
+/// be commented on.
+pub fn hunks(diff: &Diff) -> Vec<Hunk> {
+    let path = diff.path().to_string();
+    let old: Vec<&str> = diff
+        .old_content
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .collect();
+    let new: Vec<&str> = diff
+        .new_content
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .collect();
+    let ops = if diff.content_omitted {
+        Vec::new()
+    } else {
+        line_ops(&old, &new)
+    };
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (kind, _))| *kind != HunkLineKind::Context)
+        .map(|(i, _)| i)
+        .collect();
+    let mut hunks = Vec::new();
+    let mut i = 0;
+    while i < changes.len() {
+        let start = changes[i].saturating_sub(CONTEXT_LINES);
+        let mut last = changes[i];
+        i += 1;
+        // Changes close enough to share their context go in one hunk
+        while i < changes.len() && changes[i] - last <= 2 * CONTEXT_LINES {
+            last = changes[i];
+            i += 1;
+        }
+        let end = (last + CONTEXT_LINES + 1).min(ops.len());
+
+        let count = |ops: &[(HunkLineKind, &str)], skip: HunkLineKind| {
+            ops.iter().filter(|(kind, _)| *kind != skip).count()
+        };
+        let range = |before: usize, len: usize| {
+            // An empty range starts at the line before it, as in git
+            let start = if len == 0 { before } else { before + 1 };
+            format!("{},{}", start, len)
+        };
+        let header = format!(
+            "@@ -{} +{} @@",
+            range(
+                count(&ops[..start], HunkLineKind::Added),
+                count(&ops[start..end], HunkLineKind::Added)
+            ),
+            range(
+                count(&ops[..start], HunkLineKind::Removed),
+                count(&ops[start..end], HunkLineKind::Removed)
+            ),
+        );
+        hunks.push(Hunk {
+            path: path.clone(),
+            header,
+            lines: ops[start..end]
+                .iter()
+                .map(|(kind, text)| HunkLine {
+                    kind: *kind,
+                    text: text.to_string(),
+                })
+                .collect(),
+        });
+    }
+
+    if hunks.is_empty() {
+        let header = match (diff.change, &diff.old_path) {
+            (DiffChangeKind::Renamed, Some(old_path)) if !diff.content_omitted => {
+                format!("renamed from {}", old_path)
+            }
+            (DiffChangeKind::PermissionChange, _) => "permissions changed".to_string(),
+            _ if diff.content_omitted => "content not shown (binary or too large)".to_string(),
+            _ => "no line changes".to_string(),
+        };
+        hunks.push(Hunk {
+            path,
+            header,
+            lines: Vec::new(),
+        });
+    }
+    hunks
+}
+
+/// Every line of `old` and `new` in order, marked as kept, removed or added.
+fn line_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(HunkLineKind, &'a str)> {
+    use HunkLineKind::*;
+
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<_> = old[..prefix].iter().map(|line| (Context, *line)).collect();
+    let (mut i, mut j) = (0, 0);
+    if a.len().saturating_mul(b.len()) <= MAX_COMPARISONS {
+        // lcs[i * width + j]: length of the longest common subsequence of a[i..] and b[j..]
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        while i < a.len() && j < b.len() {
+            if a[i] == b[j] {
+                ops.push((Context, a[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                ops.push((Removed, a[i]));
+                i += 1;
+            } else {
+                ops.push((Added, b[j]));
+                j += 1;
+            }
+        }
+    }
+    ops.extend(a[i..].iter().map(|line| (Removed, *line)));
+    ops.extend(b[j..].iter().map(|line| (Added, *line)));
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| (Context, *line)),
+    );
+    ops
+}
+
+/// A review note on one hunk
+#[derive(Debug, Clone)]
+pub struct DiffComment {
+    pub path: String,
+    pub header: String,
+    /// The hunk's changed lines, quoted in the prompt
+    pub excerpt: Vec<String>,
+    pub comment: String,
+}
+
+impl DiffComment {
+    pub fn new(hunk: &Hunk, comment: String) -> Self {
+        Self {
+            path: hunk.path.clone(),
+            header: hunk.header.clone(),
+            excerpt: hunk.excerpt(),
+            comment,
+        }
+    }
+
+    /// Whether the note is on `hunk`.
+    pub fn is_on(&self, hunk: &Hunk) -> bool {
+        self.path == hunk.path && self.header == hunk.header
+    }
+}
+
+/// The review notes as one follow-up prompt for the agent.
+pub fn review_prompt(comments: &[DiffComment]) -> String {
+    format!(
+        "Address these review notes on your changes:\n\n{}",
+        review_notes(comments)
+    )
+}
+
+/// The review notes, each with the lines it is on.
+pub fn review_notes(comments: &[DiffComment]) -> String {
+    let mut out = String::new();
+    for (i, comment) in comments.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let _ = writeln!(
+            out,
+            "## {}. `{}` ({})\n",
+            i + 1,
+            comment.path,
+            comment.header
+        );
+        if !comment.excerpt.is_empty() {
+            let fence = code_fence(comment.excerpt.iter().map(String::as_str));
+            let _ = writeln!(out, "{}diff", fence);
+            for line in &comment.excerpt {
+                let _ = writeln!(out, "{}", line);
+            }
+            let _ = writeln!(out, "{}\n", fence);
+        }
+        let _ = writeln!(out, "{}", comment.comment.trim());
+    }
+    out
+}
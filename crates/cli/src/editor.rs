@@ -0,0 +1,46 @@
+//! Opening a workspace's worktree in a local editor.
+//!
+//! Terminal editors need the terminal to themselves, so the interactive UI
+//! hands it over while the editor runs and takes it back when it exits.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::types::Workspace;
+
+/// Editor used when neither the config file nor `$VISUAL` or `$EDITOR` names one
+const DEFAULT_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+/// The editor command line: `configured` (e.g. `code` or `nvim -O`), else
+/// `$VISUAL`, else `$EDITOR`.
+pub fn command_line(configured: Option<&str>) -> String {
+    configured
+        .map(str::to_string)
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// The command that opens `path` with `command_line`, the path going last.
+pub fn command(command_line: &str, path: &Path) -> Command {
+    let mut words = command_line.split_whitespace();
+    let mut command = Command::new(words.next().unwrap_or(DEFAULT_EDITOR));
+    command.args(words).arg(path);
+    command
+}
+
+/// The directory the workspace's agent works in: its worktree, or the
+/// repository inside it the agent was started in.
+pub fn workspace_dir(workspace: &Workspace) -> Option<PathBuf> {
+    let worktree = PathBuf::from(workspace.container_ref.as_deref()?);
+    let working_dir = workspace
+        .agent_working_dir
+        .as_deref()
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| worktree.join(dir))
+        .filter(|dir| dir.is_dir());
+    Some(working_dir.unwrap_or(worktree))
+}
@@ -0,0 +1,74 @@
+//! Board search queries.
+//!
+//! A query is a list of whitespace separated terms. Plain terms must all appear
+//! in a task's title or description; `executor:` and `status:` terms match those
+//! fields instead. Matching is case-insensitive and by substring, so
+//! `status:review` finds tasks in review and `executor:claude` finds Claude Code.
+//! `@name` stands for the query saved as `name` in the `[saved_filters]` of the
+//! config file or the user's preferences on the server.
+
+use std::collections::BTreeMap;
+
+use crate::types::TaskWithAttemptStatus;
+
+/// A parsed board search query
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFilter {
+    terms: Vec<String>,
+    executors: Vec<String>,
+    statuses: Vec<String>,
+}
+
+impl TaskFilter {
+    pub fn parse(query: &str) -> Self {
+        let mut filter = Self::default();
+        for term in query.split_whitespace() {
+            let term = term.to_lowercase();
+            if let Some(executor) = term.strip_prefix("executor:") {
+                // Executors are reported as e.g. CLAUDE_CODE but typed as claude-code
+                filter.executors.push(executor.replace('-', "_"));
+            } else if let Some(status) = term.strip_prefix("status:") {
+                filter.statuses.push(status.replace(['-', '_'], ""));
+            } else {
+                filter.terms.push(term);
+            }
+        }
+        filter
+    }
+
+    /// Parse `query` with its `@name` terms replaced by the saved queries of
+    /// those names. Unknown names are searched for as they are.
+    pub fn parse_with(query: &str, saved: &BTreeMap<String, String>) -> Self {
+        let expanded: Vec<&str> = query
+            .split_whitespace()
+            .map(|term| {
+                term.strip_prefix('@')
+                    .and_then(|name| saved.get(name))
+                    .map_or(term, String::as_str)
+            })
+            .collect();
+        Self::parse(&expanded.join(" "))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.executors.is_empty() && self.statuses.is_empty()
+    }
+
+    pub fn matches(&self, task: &TaskWithAttemptStatus) -> bool {
+        let title = task.task.title.to_lowercase();
+        let description = task
+            .task
+            .description
+            .as_deref()
+            .unwrap_or_default()
+            .to_lowercase();
+        let executor = task.executor.to_lowercase();
+        let status = task.task.status.as_str();
+
+        self.terms
+            .iter()
+            .all(|term| title.contains(term.as_str()) || description.contains(term.as_str()))
+            && self.executors.iter().all(|e| executor.contains(e.as_str()))
+            && self.statuses.iter().all(|s| status.contains(s.as_str()))
+    }
+}
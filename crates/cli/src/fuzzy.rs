@@ -0,0 +1,49 @@
+//! Fuzzy matching for the Ctrl+P jumper and the command palette.
+//!
+//! A query matches when its characters appear in order in the candidate,
+//! ignoring case. Matches are scored so that consecutive characters and
+//! characters at the start of a word rank higher, which puts `vkcli` close to
+//! "vibe-kanban-cli" and an exact substring above a scattered match.
+
+/// Score `candidate` against `query`, or `None` if it does not match.
+/// Higher is better; an empty query matches everything with a score of 0.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+    for (index, c) in candidate.chars().flat_map(char::to_lowercase).enumerate() {
+        if next < query.len() && c == query[next] {
+            score += 1;
+            if last_match.is_some_and(|last| last + 1 == index) {
+                score += 5;
+            }
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+            last_match = Some(index);
+            next += 1;
+        }
+        previous = Some(c);
+    }
+    if next < query.len() {
+        return None;
+    }
+    // Greedy matching can scatter a query that also appears whole further on
+    let query: String = query.into_iter().collect();
+    if candidate.to_lowercase().contains(&query) {
+        score += 6 * query.chars().count() as i64;
+    }
+
+    // Prefer shorter candidates when the match is otherwise as good
+    Some(score * 100 - candidate.chars().count() as i64)
+}
@@ -0,0 +1,131 @@
+//! Local, append-only journal of mutating API requests.
+//!
+//! Every POST, PUT, PATCH or DELETE sent by the client is appended as one JSON line,
+//! so `vibe-kanban-cli history` can answer "who deleted that task" on single-user
+//! setups. Payloads are stored as a SHA-256 digest rather than verbatim, since task
+//! descriptions and prompts may contain sensitive text. Journal failures are logged
+//! and never fail the request itself.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Overrides the journal location
+pub const JOURNAL_PATH_ENV: &str = "VIBE_KANBAN_CLI_JOURNAL";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Utc>,
+    pub method: String,
+    /// Server base URL the request was sent to
+    pub server: String,
+    /// Request path, including the query string
+    pub endpoint: String,
+    /// SHA-256 of the request body, if there was one
+    pub payload_digest: Option<String>,
+    /// HTTP status, absent if the request never got a response
+    pub status: Option<u16>,
+    /// Transport error, if the request failed before a response
+    pub error: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn new(method: &str, url: &url::Url, body: Option<&[u8]>) -> Self {
+        let endpoint = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        Self {
+            timestamp: Utc::now(),
+            method: method.to_string(),
+            server: url.origin().ascii_serialization(),
+            endpoint,
+            payload_digest: body.map(|body| format!("{:x}", Sha256::digest(body))),
+            status: None,
+            error: None,
+        }
+    }
+
+    /// Short description of the outcome, e.g. "200" or "error: connection refused".
+    pub fn result(&self) -> String {
+        match (self.status, &self.error) {
+            (Some(status), _) => status.to_string(),
+            (None, Some(error)) => format!("error: {}", error),
+            (None, None) => "unknown".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Journal {
+    path: Option<PathBuf>,
+}
+
+impl Journal {
+    /// Journal in the user's local data directory, or `$VIBE_KANBAN_CLI_JOURNAL` if set.
+    pub fn open_default() -> Self {
+        let path = std::env::var_os(JOURNAL_PATH_ENV)
+            .map(PathBuf::from)
+            .or_else(|| {
+                dirs::data_local_dir().map(|dir| dir.join("vibe-kanban-cli").join("journal.jsonl"))
+            });
+        Self { path }
+    }
+
+    /// Journal kept in `path`.
+    pub fn at(path: PathBuf) -> Self {
+        Self { path: Some(path) }
+    }
+
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Append an entry. Errors are logged, not returned.
+    pub fn record(&self, entry: &JournalEntry) {
+        if let Err(e) = self.append(entry) {
+            tracing::warn!("Failed to write action journal: {}", e);
+        }
+    }
+
+    fn append(&self, entry: &JournalEntry) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// Read all entries, oldest first. Unparseable lines are skipped.
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let Some(path) = &self.path else {
+            return Ok(Vec::new());
+        };
+        let file = match fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to open {}", path.display()));
+            }
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if let Ok(entry) = serde_json::from_str(&line) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
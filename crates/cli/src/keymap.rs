@@ -0,0 +1,858 @@
+//! Key bindings of the interactive UI.
+//!
+//! Every view looks up the [`Action`] a key press is bound to among the actions
+//! it supports, so the same key can mean different things in different views
+//! (`m` moves a task on the board and merges in the workspace detail view). The
+//! `[keybindings]` table of the config file replaces the keys of an action:
+//!
+//! ```toml
+//! [keybindings]
+//! move_up = ["up", "ctrl+p"]
+//! move_down = ["down", "ctrl+n"]
+//! # Ctrl+P would otherwise still open the jumper
+//! jump = "ctrl+g"
+//! ```
+//!
+//! With `function_keys = true`, F1 to F10 also run the most common actions of each
+//! view, as listed by [`function_keys`] and shown in a bar at the bottom.
+//!
+//! The `:` command palette lists the actions of each view by name, as given by
+//! [`commands`], so the less common ones can be found without their key.
+
+use std::{collections::HashMap, fmt};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::app::View;
+
+/// Something a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Select,
+    Back,
+    Quit,
+    Help,
+    Refresh,
+    Search,
+    Jump,
+    /// Open the theme picker
+    Theme,
+    /// Open the request metrics overlay
+    Metrics,
+    /// Open the history of status and error messages
+    Notifications,
+    /// Switch between relative times and dates
+    Timestamps,
+    /// Open the command palette
+    Palette,
+    /// New task on the board, new attempt in the workspace list
+    #[serde(alias = "new_task")]
+    New,
+    Edit,
+    Delete,
+    /// Bring back the last deleted task
+    Undo,
+    /// Advance the selected task to the next column
+    MoveTask,
+    /// Return the selected task to the previous column
+    MoveTaskBack,
+    /// Move the selected card to the column on its left, keeping it selected
+    MoveCardLeft,
+    /// Move the selected card to the column on its right, keeping it selected
+    MoveCardRight,
+    /// Move the selected card up its column, ordering the column by hand
+    MoveCardUp,
+    /// Move the selected card down its column, ordering the column by hand
+    MoveCardDown,
+    /// Show or hide the column of cancelled tasks
+    ToggleCancelled,
+    /// Order the selected board column by the next sort mode
+    SortTasks,
+    /// Zoom in from the epics to an epic's subtasks, and from a task to its attempts
+    ZoomIn,
+    /// Zoom out from a task's attempts to the board, and from the board to the epics
+    ZoomOut,
+    /// Copy the task as a prompt for agents outside Vibe Kanban
+    CopyPrompt,
+    /// Write the task as a prompt to a file
+    SavePrompt,
+    /// Mark the selected task for a bulk status change or starting attempts
+    Mark,
+    /// Start attempts for the marked tasks, or the selected one
+    StartAttempts,
+    Workspaces,
+    /// Name the workspace, or clear its name
+    Rename,
+    /// Edit the free-form notes kept on the workspace
+    Notes,
+    /// Create a task from the workspace, for follow-on work of its attempt
+    NewSubtask,
+    Merge,
+    /// Merge the workspace branch in every repo, one after another
+    MergeAll,
+    Push,
+    Rebase,
+    /// Rebase the workspace branch onto another branch, typed in a popup
+    RebaseOnto,
+    /// Open the workspace's worktree in the editor
+    OpenEditor,
+    /// Copy the selected task's ID, or the workspace's branch
+    Yank,
+    /// Copy the workspace's pull request URL
+    YankUrl,
+    /// Push the workspace branch and open a pull request for it
+    CreatePr,
+    Stop,
+    /// Run the coding agent again after its last run failed
+    RetryAttempt,
+    /// Have the coding agent resolve the conflicts a rebase stopped on
+    ResolveConflicts,
+    /// Start the workspace's dev server, or stop the running one
+    DevServer,
+    FollowUp,
+    /// Pick a saved prompt in the follow-up composer
+    Templates,
+    Logs,
+    /// Open the workspace's diff for review
+    Diff,
+    /// Write a review note on the selected hunk
+    Comment,
+    /// Send the review notes to the agent as a follow-up
+    SendReview,
+    PreviousProcess,
+    NextProcess,
+    /// Bookmark the log line at the bottom of the pane
+    Bookmark,
+    NextBookmark,
+    PreviousBookmark,
+    /// Write the shown log with its bookmarks to a file
+    ExportTranscript,
+    RestartContainer,
+    RebuildContainer,
+    OpenLink,
+    Queue,
+    /// Open the workspaces whose latest attempt failed
+    Failures,
+    /// Open the project's deleted tasks
+    Trash,
+    /// Open the tree of a task's parent, workspaces and child tasks
+    TaskTree,
+    /// Inspect and edit the task's metadata
+    Metadata,
+    /// Show the next failure category in the failures view, or the next attempt
+    /// filter on the board
+    CycleFilter,
+    /// Pick the executor to narrow the board to
+    ExecutorFilter,
+    /// Open the planner settings form
+    PlannerSettings,
+    /// Open the workspace retention policy form
+    RetentionSettings,
+    Follow,
+    ErrorDetails,
+    Retry,
+    /// Skip the remaining steps of a multi-step operation
+    CancelSteps,
+}
+
+impl Action {
+    /// Keys bound to the action unless the config file says otherwise
+    fn default_keys(self) -> Vec<KeyChord> {
+        let char = |c| KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let key = |code| KeyChord::new(code, KeyModifiers::NONE);
+        match self {
+            Action::MoveUp => vec![key(KeyCode::Up), char('k')],
+            Action::MoveDown => vec![key(KeyCode::Down), char('j')],
+            Action::MoveLeft => vec![key(KeyCode::Left), char('h')],
+            Action::MoveRight => vec![key(KeyCode::Right), char('l')],
+            Action::PageUp => vec![key(KeyCode::PageUp)],
+            Action::PageDown => vec![key(KeyCode::PageDown)],
+            Action::Top => vec![key(KeyCode::Home), char('g')],
+            Action::Bottom => vec![key(KeyCode::End), char('G')],
+            Action::Select => vec![key(KeyCode::Enter)],
+            Action::Back => vec![key(KeyCode::Esc)],
+            Action::Quit => vec![char('q')],
+            Action::Help => vec![char('?')],
+            Action::Refresh => vec![char('r')],
+            Action::Search => vec![char('/')],
+            Action::Jump => vec![KeyChord::new(KeyCode::Char('p'), KeyModifiers::CONTROL)],
+            Action::Theme => vec![char('T')],
+            Action::Metrics => vec![key(KeyCode::F(12))],
+            Action::Notifications => vec![char('N')],
+            Action::Timestamps => vec![char('A')],
+            Action::Palette => vec![char(':')],
+            Action::New => vec![char('n')],
+            Action::Edit => vec![char('e')],
+            Action::Delete => vec![char('d')],
+            Action::Undo => vec![char('u')],
+            Action::MoveTask => vec![char('m')],
+            Action::MoveTaskBack => vec![char('M')],
+            Action::MoveCardLeft => vec![char('H')],
+            Action::MoveCardRight => vec![char('L')],
+            Action::MoveCardUp => vec![char('K')],
+            Action::MoveCardDown => vec![char('J')],
+            Action::ToggleCancelled => vec![char('c')],
+            Action::SortTasks => vec![char('s')],
+            Action::ZoomIn => vec![char('+'), char('=')],
+            Action::ZoomOut => vec![char('-')],
+            Action::CopyPrompt => vec![char('y')],
+            Action::SavePrompt => vec![char('Y')],
+            Action::Mark => vec![char(' ')],
+            Action::StartAttempts => vec![char('a')],
+            Action::Workspaces => vec![char('w')],
+            Action::Rename => vec![char('R')],
+            Action::Notes => vec![char('n')],
+            Action::NewSubtask => vec![char('S')],
+            Action::Merge => vec![char('m')],
+            Action::MergeAll => vec![char('M')],
+            Action::Push => vec![char('p')],
+            Action::Rebase => vec![char('b')],
+            Action::RebaseOnto => vec![char('B')],
+            Action::OpenEditor => vec![char('e')],
+            Action::Yank => vec![char('y')],
+            Action::YankUrl => vec![char('Y')],
+            Action::CreatePr => vec![char('O')],
+            Action::Stop => vec![char('s')],
+            Action::RetryAttempt => vec![char('t')],
+            Action::ResolveConflicts => vec![char('v')],
+            Action::DevServer => vec![char('D')],
+            Action::FollowUp => vec![char('f')],
+            Action::Templates => vec![char('t')],
+            Action::Logs => vec![char('l')],
+            Action::Diff => vec![char('d')],
+            Action::Comment => vec![char('c')],
+            Action::SendReview => vec![char('S')],
+            Action::PreviousProcess => vec![char('[')],
+            Action::NextProcess => vec![char(']')],
+            Action::Bookmark => vec![char('m')],
+            Action::NextBookmark => vec![char('b')],
+            Action::PreviousBookmark => vec![char('B')],
+            Action::ExportTranscript => vec![char('w')],
+            Action::RestartContainer => vec![char('c')],
+            Action::RebuildContainer => vec![char('C')],
+            Action::OpenLink => vec![char('o')],
+            Action::Queue => vec![char('Q')],
+            Action::Failures => vec![char('X')],
+            Action::Trash => vec![char('D')],
+            Action::TaskTree => vec![char('t')],
+            Action::Metadata => vec![char('i')],
+            Action::CycleFilter => vec![char('f')],
+            Action::ExecutorFilter => vec![char('e')],
+            Action::PlannerSettings => vec![char('P')],
+            Action::RetentionSettings => vec![char('W')],
+            Action::Follow => vec![char('F')],
+            Action::ErrorDetails => vec![char('E')],
+            Action::Retry => vec![char('R')],
+            Action::CancelSteps => vec![char('x')],
+        }
+    }
+}
+
+/// The actions F1 to F10 run in `view`, with their labels for the function key bar
+pub fn function_keys(view: View) -> [Option<(Action, &'static str)>; 10] {
+    let keys: &[(usize, Action, &'static str)] = match view {
+        View::Projects => &[
+            (1, Action::Help, "Help"),
+            (3, Action::Select, "Open"),
+            (5, Action::Refresh, "Refresh"),
+            (6, Action::PlannerSettings, "Planner"),
+            (7, Action::New, "New"),
+            (8, Action::RetentionSettings, "Cleanup"),
+            (9, Action::Queue, "Queue"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::Tasks => &[
+            (1, Action::Help, "Help"),
+            (2, Action::Search, "Search"),
+            (3, Action::Select, "View"),
+            (4, Action::StartAttempts, "Attempt"),
+            (5, Action::Refresh, "Refresh"),
+            (6, Action::MoveTask, "Move"),
+            (7, Action::New, "New"),
+            (8, Action::Delete, "Delete"),
+            (9, Action::Queue, "Queue"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::TaskDetail => &[
+            (1, Action::Help, "Help"),
+            (3, Action::Workspaces, "Attempts"),
+            (4, Action::Edit, "Edit"),
+            (5, Action::Refresh, "Refresh"),
+            (6, Action::TaskTree, "Tree"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::Workspaces => &[
+            (1, Action::Help, "Help"),
+            (2, Action::Rename, "Rename"),
+            (3, Action::Select, "Open"),
+            (4, Action::RestartContainer, "Restart"),
+            (5, Action::Refresh, "Refresh"),
+            (6, Action::RebuildContainer, "Rebuild"),
+            (7, Action::New, "New"),
+            (8, Action::Stop, "Stop"),
+            (9, Action::Queue, "Queue"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::WorkspaceDetail => &[
+            (1, Action::Help, "Help"),
+            (2, Action::FollowUp, "Reply"),
+            (3, Action::Logs, "Logs"),
+            (4, Action::Rebase, "Rebase"),
+            (5, Action::Refresh, "Refresh"),
+            (6, Action::Merge, "Merge"),
+            (7, Action::Push, "Push"),
+            (8, Action::Stop, "Stop"),
+            (9, Action::Diff, "Diff"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::Diff => &[
+            (1, Action::Help, "Help"),
+            (2, Action::Comment, "Comment"),
+            (5, Action::Refresh, "Refresh"),
+            (8, Action::Delete, "Uncomment"),
+            (9, Action::SendReview, "Send"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::SessionLogs => &[
+            (1, Action::Help, "Help"),
+            (3, Action::PreviousProcess, "Prev"),
+            (4, Action::NextProcess, "Next"),
+            (5, Action::Refresh, "Refresh"),
+            (6, Action::Bookmark, "Mark"),
+            (7, Action::NextBookmark, "Jump"),
+            (8, Action::ExportTranscript, "Export"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::Queue => &[
+            (1, Action::Help, "Help"),
+            (5, Action::Refresh, "Refresh"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::Failures => &[
+            (1, Action::Help, "Help"),
+            (2, Action::CycleFilter, "Filter"),
+            (3, Action::Select, "Open"),
+            (5, Action::Refresh, "Refresh"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::TaskTree => &[
+            (1, Action::Help, "Help"),
+            (3, Action::Select, "Open"),
+            (5, Action::Refresh, "Refresh"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::Trash => &[
+            (1, Action::Help, "Help"),
+            (2, Action::Undo, "Restore"),
+            (5, Action::Refresh, "Refresh"),
+            (8, Action::Delete, "Purge"),
+            (10, Action::Quit, "Quit"),
+        ],
+        View::CreateTask | View::PlannerSettings | View::RetentionSettings => {
+            &[(4, Action::Edit, "Edit"), (10, Action::Back, "Cancel")]
+        }
+        View::FollowUp => &[
+            (3, Action::Templates, "Templates"),
+            (4, Action::Edit, "Edit"),
+            (10, Action::Back, "Cancel"),
+        ],
+        View::CreateProject => &[
+            (4, Action::Edit, "Edit"),
+            (7, Action::New, "Add repo"),
+            (8, Action::Delete, "Remove"),
+            (10, Action::Back, "Cancel"),
+        ],
+        View::CreateAttempt => &[(10, Action::Back, "Cancel")],
+        View::Help => &[(1, Action::Back, "Close"), (10, Action::Back, "Close")],
+    };
+    let mut bar = [None; 10];
+    for &(number, action, label) in keys {
+        bar[number - 1] = Some((action, label));
+    }
+    bar
+}
+
+/// The actions the command palette offers in `view` besides the global ones,
+/// with what they do
+pub fn commands(view: View) -> &'static [(Action, &'static str)] {
+    match view {
+        View::Projects => &[
+            (Action::Select, "Open the project's board"),
+            (Action::New, "Create a project"),
+            (Action::RetentionSettings, "Edit the cleanup policy"),
+            (Action::PlannerSettings, "Edit the team planner settings"),
+            (Action::Queue, "Show the attempt queue"),
+            (Action::Failures, "Show failed attempts"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::Tasks => &[
+            (Action::Select, "View the task"),
+            (Action::New, "Create a task"),
+            (Action::StartAttempts, "Start attempts (marked tasks)"),
+            (Action::MoveTask, "Move the task to the next status"),
+            (Action::MoveTaskBack, "Move the task back a status"),
+            (Action::MoveCardLeft, "Move the card to the left column"),
+            (Action::MoveCardRight, "Move the card to the right column"),
+            (Action::MoveCardUp, "Move the card up its column"),
+            (Action::MoveCardDown, "Move the card down its column"),
+            (Action::Mark, "Mark the task"),
+            (Action::Search, "Search tasks"),
+            (Action::CycleFilter, "Show failed or running tasks only"),
+            (Action::ExecutorFilter, "Show one executor's tasks"),
+            (Action::SortTasks, "Sort the column"),
+            (Action::ToggleCancelled, "Show or hide cancelled tasks"),
+            (Action::ZoomIn, "Zoom in to subtasks or attempts"),
+            (Action::ZoomOut, "Zoom out to epics"),
+            (Action::Delete, "Move the task to the trash"),
+            (Action::Undo, "Undo the task deletion"),
+            (Action::Trash, "Show the deleted tasks"),
+            (Action::TaskTree, "Show the task's related tasks"),
+            (Action::Yank, "Copy the task ID"),
+            (Action::OpenLink, "Open a link on screen"),
+            (Action::Follow, "Follow the most active workspace"),
+            (Action::PlannerSettings, "Edit the team planner settings"),
+            (Action::Queue, "Show the attempt queue"),
+            (Action::Failures, "Show failed attempts"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::TaskDetail => &[
+            (Action::Workspaces, "Show the task's attempts"),
+            (Action::Edit, "Edit the title and description"),
+            (Action::CopyPrompt, "Copy the task as an agent prompt"),
+            (Action::SavePrompt, "Save the task as an agent prompt"),
+            (Action::TaskTree, "Show the task's related tasks"),
+            (Action::Metadata, "Inspect and edit the task metadata"),
+            (Action::OpenLink, "Open a link on screen"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::Workspaces => &[
+            (Action::Select, "Open the workspace"),
+            (Action::New, "Create an attempt"),
+            (Action::Stop, "Stop the workspace's process"),
+            (Action::RetryAttempt, "Retry the failed attempt"),
+            (Action::ResolveConflicts, "Resolve conflicts with the agent"),
+            (Action::RestartContainer, "Restart the container"),
+            (Action::RebuildContainer, "Rebuild the container"),
+            (Action::Rename, "Name the workspace"),
+            (Action::Yank, "Copy the branch name"),
+            (Action::OpenLink, "Open a link on screen"),
+            (Action::Follow, "Follow the most active workspace"),
+            (Action::Queue, "Show the attempt queue"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::WorkspaceDetail => &[
+            (Action::Merge, "Merge to the target branch"),
+            (Action::MergeAll, "Merge in every repo"),
+            (Action::Push, "Push to the remote"),
+            (Action::Rebase, "Rebase on the target branch"),
+            (Action::RebaseOnto, "Rebase onto another branch"),
+            (Action::CreatePr, "Open a pull request"),
+            (Action::FollowUp, "Send a follow-up message"),
+            (Action::MoveDown, "Pick a process"),
+            (Action::Stop, "Stop the picked process, or the workspace"),
+            (Action::RetryAttempt, "Retry the failed attempt"),
+            (Action::ResolveConflicts, "Resolve conflicts with the agent"),
+            (Action::DevServer, "Start or stop the dev server"),
+            (Action::Logs, "Show the session logs"),
+            (Action::Diff, "Review the diff"),
+            (Action::OpenEditor, "Open the worktree in the editor"),
+            (Action::Rename, "Name the workspace"),
+            (Action::Notes, "Edit the workspace notes"),
+            (Action::NewSubtask, "Create a subtask from the workspace"),
+            (Action::Yank, "Copy the branch name"),
+            (Action::YankUrl, "Copy the pull request URL"),
+            (Action::OpenLink, "Open a link on screen"),
+            (Action::Follow, "Follow the most active workspace"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::SessionLogs => &[
+            (Action::Top, "Jump to the top"),
+            (Action::Bottom, "Follow the output"),
+            (Action::PreviousProcess, "Show the previous process"),
+            (Action::NextProcess, "Show the next process"),
+            (Action::Bookmark, "Bookmark the bottom line"),
+            (Action::NextBookmark, "Jump to the next bookmark"),
+            (Action::PreviousBookmark, "Jump to the previous bookmark"),
+            (Action::ExportTranscript, "Export the transcript"),
+            (Action::OpenLink, "Open a link on screen"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::Diff => &[
+            (Action::Comment, "Comment on the hunk"),
+            (Action::Delete, "Remove the hunk's comment"),
+            (Action::SendReview, "Send the comments as a follow-up"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::Queue => &[
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::Failures => &[
+            (Action::Select, "Open the workspace"),
+            (Action::CycleFilter, "Show the next failure category"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::TaskTree => &[
+            (Action::Select, "Open the task or workspace"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::Trash => &[
+            (Action::Undo, "Restore the task"),
+            (Action::Delete, "Delete the task for good"),
+            (Action::Refresh, "Refresh"),
+            (Action::Help, "Show keyboard shortcuts"),
+            (Action::Quit, "Quit"),
+        ],
+        View::FollowUp => &[(Action::Templates, "Pick a follow-up template")],
+        View::RetentionSettings => &[(Action::Refresh, "Refresh")],
+        View::CreateTask
+        | View::CreateProject
+        | View::CreateAttempt
+        | View::PlannerSettings
+        | View::Help => &[],
+    }
+}
+
+/// Actions the command palette offers in every view
+pub const GLOBAL_COMMANDS: &[(Action, &str)] = &[
+    (Action::Jump, "Jump to a project or task"),
+    (Action::Theme, "Switch the colour theme"),
+    (Action::Notifications, "Show past messages and errors"),
+    (Action::Timestamps, "Show times as dates or relative"),
+    (Action::Metrics, "Show API request metrics"),
+];
+
+/// A key together with the Ctrl and Alt modifiers held with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Parse a key such as `x`, `Q`, `ctrl+r`, `alt+enter` or `f5`.
+    fn parse(input: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = input;
+        while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return None,
+            };
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                name => KeyCode::F(name.strip_prefix('f')?.parse().ok()?),
+            },
+        };
+        Some(Self::new(code, modifiers))
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        // Shift is implied by the character itself, e.g. `Q`
+        let modifiers = match key.code {
+            KeyCode::Char(_) => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+        Self::new(key.code, modifiers)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.is_empty() => write!(f, "{}", c),
+            KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// The keys bound to every action
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "HashMap<Action, KeyList>")]
+pub struct KeyMap {
+    keys: HashMap<Action, Vec<KeyChord>>,
+}
+
+impl KeyMap {
+    /// The keys bound to `action`.
+    pub fn keys(&self, action: Action) -> Vec<KeyChord> {
+        self.keys
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_keys())
+    }
+
+    /// The first of `actions` that `key` is bound to.
+    pub fn action(&self, key: KeyEvent, actions: &[Action]) -> Option<Action> {
+        let chord = KeyChord::from(key);
+        actions
+            .iter()
+            .copied()
+            .find(|action| self.keys(*action).contains(&chord))
+    }
+
+    /// A key press that runs `action`, for keys that stand in for it.
+    pub fn key_event(&self, action: Action) -> Option<KeyEvent> {
+        self.keys(action)
+            .first()
+            .map(|chord| KeyEvent::new(chord.code, chord.modifiers))
+    }
+
+    /// Take the bindings of `other` for the actions this map leaves at their
+    /// default keys.
+    pub fn fill_unbound(&mut self, other: KeyMap) {
+        for (action, keys) in other.keys {
+            self.keys.entry(action).or_insert(keys);
+        }
+    }
+
+    /// The keys of `action` for display, e.g. `↑/k`.
+    pub fn describe(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// One key or several keys for an action in the config file
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn into_vec(self) -> Vec<String> {
+        match self {
+            KeyList::One(key) => vec![key],
+            KeyList::Many(keys) => keys,
+        }
+    }
+}
+
+impl TryFrom<HashMap<Action, KeyList>> for KeyMap {
+    type Error = InvalidKey;
+
+    fn try_from(map: HashMap<Action, KeyList>) -> Result<Self, Self::Error> {
+        let keys = map
+            .into_iter()
+            .map(|(action, keys)| {
+                let chords = keys
+                    .into_vec()
+                    .into_iter()
+                    .map(|key| KeyChord::parse(&key).ok_or(InvalidKey(key)))
+                    .collect::<Result<_, _>>()?;
+                Ok((action, chords))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { keys })
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidKey(String);
+
+impl fmt::Display for InvalidKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown key '{}', expected e.g. 'x', 'ctrl+x', 'esc' or 'f5'",
+            self.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(toml: &str) -> Result<KeyMap, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn chords_parse_with_their_modifiers() {
+        let parse = |input| KeyChord::parse(input).unwrap();
+        assert_eq!(
+            parse("x"),
+            KeyChord::new(KeyCode::Char('x'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse("Q"),
+            KeyChord::new(KeyCode::Char('Q'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse("ctrl+r"),
+            KeyChord::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse("Control+Alt+Enter"),
+            KeyChord::new(KeyCode::Enter, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(
+            parse("ctrl++"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse("+"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse("space"),
+            KeyChord::new(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse("F5"),
+            KeyChord::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert_eq!(parse("PageDown").to_string(), "PgDn");
+        assert_eq!(parse("alt+x").to_string(), "Alt+X");
+    }
+
+    #[test]
+    fn invalid_bindings_are_rejected() {
+        for input in [
+            "",
+            "shift+x",
+            "ctrl+",
+            "fx",
+            "f99x",
+            "escape!",
+            "ctrl+enterr",
+        ] {
+            assert_eq!(KeyChord::parse(input), None, "{:?}", input);
+        }
+
+        let error = keymap("move_up = [\"up\", \"hyper+k\"]").unwrap_err();
+        assert!(
+            error.to_string().contains("unknown key 'hyper+k'"),
+            "{}",
+            error
+        );
+        assert!(keymap("fly = \"x\"").is_err());
+    }
+
+    #[test]
+    fn configured_keys_replace_the_defaults() {
+        let keys = keymap("move_up = [\"up\", \"ctrl+p\"]\nnew_task = \"ctrl+n\"").unwrap();
+        assert_eq!(keys.describe(Action::MoveUp), "↑/Ctrl+P");
+        assert_eq!(keys.describe(Action::New), "Ctrl+N");
+        assert_eq!(keys.describe(Action::MoveDown), "↓/j");
+
+        let k = press(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(keys.action(k, &[Action::MoveUp]), None);
+        assert_eq!(
+            KeyMap::default().action(k, &[Action::MoveUp]),
+            Some(Action::MoveUp)
+        );
+
+        // The first action of the view wins when a key is bound twice
+        let ctrl_p = press(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert_eq!(
+            keys.action(ctrl_p, &[Action::MoveUp, Action::Jump]),
+            Some(Action::MoveUp)
+        );
+        assert_eq!(
+            keys.action(ctrl_p, &[Action::Jump, Action::MoveUp]),
+            Some(Action::Jump)
+        );
+    }
+
+    #[test]
+    fn shift_is_implied_by_the_character() {
+        let keys = KeyMap::default();
+        let shifted = press(KeyCode::Char('Q'), KeyModifiers::SHIFT);
+        assert_eq!(keys.action(shifted, &[Action::Queue]), Some(Action::Queue));
+        let shift_up = press(KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(keys.action(shift_up, &[Action::MoveUp]), None);
+    }
+
+    #[test]
+    fn file_bindings_take_precedence_over_filled_in_ones() {
+        let mut keys = keymap("jump = \"ctrl+g\"").unwrap();
+        keys.fill_unbound(keymap("jump = \"ctrl+j\"\nquit = \"ctrl+q\"").unwrap());
+
+        assert_eq!(keys.describe(Action::Jump), "Ctrl+G");
+        assert_eq!(keys.describe(Action::Quit), "Ctrl+Q");
+        assert_eq!(keys.describe(Action::Help), "?");
+        assert_eq!(
+            keys.key_event(Action::Quit),
+            Some(press(KeyCode::Char('q'), KeyModifiers::CONTROL))
+        );
+    }
+}
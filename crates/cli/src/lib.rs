@@ -8,7 +8,27 @@
 
 pub mod api;
 pub mod app;
+pub mod batch;
+pub mod clipboard;
+pub mod config;
+pub mod datetime;
+pub mod diff;
+pub mod editor;
+pub mod filter;
+pub mod fuzzy;
+pub mod journal;
+pub mod keymap;
+pub mod links;
+pub mod logs;
+pub mod metadata;
+pub mod notifications;
+pub mod prompt;
+pub mod snapshot;
+pub mod startup;
+pub mod templates;
 pub mod types;
+pub mod ui;
+pub mod utils;
 
 pub use api::VibeKanbanClient;
 pub use app::App;
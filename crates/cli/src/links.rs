@@ -0,0 +1,227 @@
+//! Finding and opening links shown in the UI, and reading links to the web UI.
+//!
+//! Links are opened with the platform's default handler, and rendered as OSC 8
+//! hyperlinks on terminals that are known to support them.
+
+use std::{io, sync::LazyLock};
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use url::Url;
+use uuid::Uuid;
+
+/// Set to `1` or `0` to force OSC 8 hyperlinks on or off
+pub const HYPERLINKS_ENV: &str = "VIBE_KANBAN_CLI_HYPERLINKS";
+
+/// Scheme of links that open in the CLI, e.g. `vibe-kanban://workspaces/<id>`
+pub const LINK_SCHEME: &str = "vibe-kanban";
+
+/// Keys used for link hints, home row first
+const HINT_KEYS: &[char] = &['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l'];
+
+static URL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s<>"'`()\[\]{}]+"#).unwrap());
+
+/// URLs in `text`, in order of appearance and without duplicates.
+pub fn find_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for m in URL_RE.find_iter(text) {
+        // Sentence punctuation right after a URL is almost never part of it
+        let url = m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+/// Colour and style escapes, which dev servers print in the middle of URLs
+static SGR_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+
+/// The first address on this machine with a port in `text`, such as the
+/// `http://localhost:5173/` a dev server prints once it listens.
+pub fn find_local_server_url(text: &str) -> Option<String> {
+    let text = SGR_RE.replace_all(text, "");
+    find_urls(&text).into_iter().find(|url| {
+        Url::parse(url).is_ok_and(|url| {
+            url.port().is_some()
+                && matches!(
+                    url.host_str(),
+                    Some("localhost" | "127.0.0.1" | "0.0.0.0" | "[::1]" | "[::]")
+                )
+        })
+    })
+}
+
+/// Labels for `count` links. Single keys while they suffice, then pairs, so no
+/// label is a prefix of another.
+pub fn hint_labels(count: usize) -> Vec<String> {
+    if count <= HINT_KEYS.len() {
+        return HINT_KEYS[..count].iter().map(|c| c.to_string()).collect();
+    }
+    HINT_KEYS
+        .iter()
+        .flat_map(|a| HINT_KEYS.iter().map(move |b| format!("{a}{b}")))
+        .take(count)
+        .collect()
+}
+
+/// Open `url` in the system browser.
+///
+/// The URL is handed to the platform's opener as a single argument, and to
+/// ShellExecute on Windows, so it never reaches a shell that would read `&` or
+/// `|` in a query string as the start of another command.
+pub fn open(url: &str) -> io::Result<()> {
+    open::that_detached(url)
+}
+
+/// What a link to the web UI points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkTarget {
+    Project(Uuid),
+    Task { project_id: Uuid, task_id: Uuid },
+    Workspace(Uuid),
+}
+
+/// A web UI link, read with [`parse_web_link`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebLink {
+    /// Origin of an `http(s)` link, e.g. `http://localhost:5173`; `None` for
+    /// [`LINK_SCHEME`] links, which don't name a server
+    pub server: Option<String>,
+    pub target: LinkTarget,
+}
+
+/// Read a link to a project, task or workspace in the web UI.
+///
+/// Takes the web UI's own URLs, such as
+/// `http://localhost:5173/projects/<id>/tasks/<id>` or `.../workspaces/<id>`,
+/// and the same paths under [`LINK_SCHEME`], e.g. `vibe-kanban://workspaces/<id>`.
+pub fn parse_web_link(link: &str) -> Result<WebLink> {
+    let url = Url::parse(link.trim()).with_context(|| format!("Invalid link: {link}"))?;
+    let (server, mut segments) = match url.scheme() {
+        "http" | "https" => (Some(url.origin().ascii_serialization()), Vec::new()),
+        // The first path segment ends up as the host of `vibe-kanban://projects/...`
+        LINK_SCHEME => (None, url.host_str().into_iter().collect()),
+        scheme => bail!("Unsupported link scheme '{scheme}': {link}"),
+    };
+    segments.extend(url.path_segments().into_iter().flatten());
+    segments.retain(|segment| !segment.is_empty());
+
+    let id = |segment: &str| {
+        Uuid::parse_str(segment).with_context(|| format!("Invalid ID '{segment}' in {link}"))
+    };
+    let target = match segments.as_slice() {
+        ["projects", project] | ["projects", project, "tasks"] => LinkTarget::Project(id(project)?),
+        // `latest` is the web UI's way to show the task's newest attempt
+        ["projects", project, "tasks", task]
+        | ["projects", project, "tasks", task, "attempts", "latest"] => LinkTarget::Task {
+            project_id: id(project)?,
+            task_id: id(task)?,
+        },
+        ["projects", _, "tasks", _, "attempts", workspace]
+        | ["projects", _, "tasks", _, "attempts", workspace, "full"]
+        | ["workspaces", workspace] => LinkTarget::Workspace(id(workspace)?),
+        _ => bail!("Not a link to a project, task or workspace: {link}"),
+    };
+    Ok(WebLink { server, target })
+}
+
+/// Whether the terminal renders OSC 8 hyperlinks.
+///
+/// Terminals that don't understand OSC 8 may print the escape sequence, so this
+/// only says yes for terminals known to support it.
+pub fn hyperlinks_supported() -> bool {
+    let var = |name: &str| std::env::var(name).ok();
+
+    match var(HYPERLINKS_ENV).as_deref() {
+        Some("1") => return true,
+        Some("0") => return false,
+        _ => {}
+    }
+    if matches!(
+        var("TERM_PROGRAM").as_deref(),
+        Some("iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper")
+    ) {
+        return true;
+    }
+    if var("TERM").is_some_and(|t| t.contains("kitty") || t.contains("alacritty")) {
+        return true;
+    }
+    // GNOME Terminal, Tilix and other VTE terminals from 0.50
+    var("VTE_VERSION")
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v >= 5000)
+        || var("WT_SESSION").is_some()
+        || var("KITTY_WINDOW_ID").is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_urls_stops_at_delimiters_and_trailing_punctuation() {
+        let text = "See https://example.com/a?b=1&c=2. Also (http://localhost:3000/x), \
+                    <https://docs.rs/regex> and \"https://q.io/\"!";
+        assert_eq!(
+            find_urls(text),
+            [
+                "https://example.com/a?b=1&c=2",
+                "http://localhost:3000/x",
+                "https://docs.rs/regex",
+                "https://q.io/",
+            ]
+        );
+    }
+
+    #[test]
+    fn find_urls_skips_duplicates_and_other_schemes() {
+        let text = "https://a.dev https://a.dev, ftp://files.dev mailto:me@a.dev http:/broken";
+        assert_eq!(find_urls(text), ["https://a.dev"]);
+    }
+
+    #[test]
+    fn find_local_server_url_needs_a_local_host_and_port() {
+        let log = "docs at https://vitejs.dev\n  \x1b[32m➜\x1b[0m  Local:   \
+                   http://localhost:\x1b[1m5173\x1b[22m/\n";
+        assert_eq!(
+            find_local_server_url(log).as_deref(),
+            Some("http://localhost:5173/")
+        );
+        assert_eq!(
+            find_local_server_url("listening on http://localhost/"),
+            None
+        );
+        assert_eq!(find_local_server_url("https://example.com:8443/"), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn open_never_hands_url_to_a_shell() {
+        let url = "https://example.com/?a=1&calc|more^x;$(id)`id`";
+        let launchers = open::commands(url);
+        assert!(!launchers.is_empty());
+        for launcher in launchers {
+            let program = launcher.get_program().to_string_lossy().into_owned();
+            assert!(
+                !matches!(program.as_str(), "sh" | "bash" | "zsh" | "cmd" | "cmd.exe"),
+                "{program} is a shell"
+            );
+            // The URL goes over as a whole value, never spliced into a command line
+            let mut values = launcher
+                .get_args()
+                .chain(launcher.get_envs().filter_map(|(_, value)| value));
+            assert!(
+                values.any(|value| value == url),
+                "{program} didn't get the URL"
+            );
+            assert!(
+                !launcher
+                    .get_args()
+                    .any(|arg| arg != url && arg.to_string_lossy().contains(url)),
+                "{program} got the URL inside another argument"
+            );
+        }
+    }
+}
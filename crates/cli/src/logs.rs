@@ -0,0 +1,189 @@
+//! Execution process logs, built up from the server's log stream.
+//!
+//! The server streams a process's log as JSON patches against a list of entries:
+//! normalized conversation entries for coding agents, and raw stdout/stderr for
+//! scripts. [`ProcessLog`] applies the patches and turns the entries into lines.
+
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use json_patch::Patch;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::utils::code_fence;
+
+/// A message from a process log stream
+#[derive(Debug, Clone)]
+pub enum LogEvent {
+    Patch(Patch),
+    /// The process exited and its log is complete
+    Finished,
+}
+
+/// Parse a websocket message from a log stream. Returns `None` for messages that
+/// carry nothing to show, such as the initial `Ready`.
+pub fn parse_log_message(text: &str) -> Result<Option<LogEvent>> {
+    let value: Value = serde_json::from_str(text).context("Failed to parse log message")?;
+    if value.get("finished").and_then(|v| v.as_bool()) == Some(true) {
+        return Ok(Some(LogEvent::Finished));
+    }
+    match value.get("JsonPatch") {
+        Some(patch) => {
+            let patch =
+                serde_json::from_value(patch.clone()).context("Failed to parse log patch")?;
+            Ok(Some(LogEvent::Patch(patch)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// How a log line is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogKind {
+    Stdout,
+    Stderr,
+    User,
+    Assistant,
+    Tool,
+    Thinking,
+    System,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub kind: LogKind,
+    pub text: String,
+}
+
+/// The log of one execution process
+#[derive(Debug, Clone)]
+pub struct ProcessLog {
+    state: Value,
+    pub finished: bool,
+}
+
+impl Default for ProcessLog {
+    fn default() -> Self {
+        Self {
+            state: serde_json::json!({ "entries": [] }),
+            finished: false,
+        }
+    }
+}
+
+impl ProcessLog {
+    pub fn apply(&mut self, event: &LogEvent) -> Result<()> {
+        match event {
+            LogEvent::Patch(patch) => {
+                json_patch::patch(&mut self.state, patch).context("Failed to apply log patch")
+            }
+            LogEvent::Finished => {
+                self.finished = true;
+                Ok(())
+            }
+        }
+    }
+
+    fn entries(&self) -> &[Value] {
+        self.state["entries"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// All lines of the log, one per line of entry text.
+    pub fn lines(&self) -> Vec<LogLine> {
+        self.entries().iter().flat_map(entry_lines).collect()
+    }
+}
+
+/// A line of a process log marked with `m` while reading it
+#[derive(Debug, Clone)]
+pub struct LogBookmark {
+    pub process_id: Uuid,
+    /// Index into the process's [`ProcessLog::lines`]
+    pub line: usize,
+    pub note: String,
+}
+
+/// A process log as Markdown, with the workspace's notes and the log's bookmarks
+/// up front, and the bookmarks also marked above the lines they point at.
+pub fn transcript(
+    title: &str,
+    notes: Option<&str>,
+    lines: &[LogLine],
+    bookmarks: &[&LogBookmark],
+) -> String {
+    let mut out = format!("# {}\n", title);
+    if let Some(notes) = notes {
+        let _ = writeln!(out, "\n## Workspace notes\n\n{}", notes.trim_end());
+    }
+    if !bookmarks.is_empty() {
+        out.push_str("\n## Bookmarks\n\n");
+        for bookmark in bookmarks {
+            let _ = writeln!(out, "- Line {}: {}", bookmark.line + 1, bookmark.note);
+        }
+    }
+    let fence = code_fence(lines.iter().map(|line| line.text.as_str()));
+    let _ = writeln!(out, "\n## Log\n\n{}text", fence);
+    for (i, line) in lines.iter().enumerate() {
+        for bookmark in bookmarks.iter().filter(|b| b.line == i) {
+            let _ = writeln!(out, ">>> {}", bookmark.note);
+        }
+        let _ = writeln!(out, "{}", line.text);
+    }
+    let _ = writeln!(out, "{}", fence);
+    out
+}
+
+fn entry_lines(entry: &Value) -> Vec<LogLine> {
+    let content = &entry["content"];
+    let (kind, prefix, text) = match entry["type"].as_str() {
+        Some("STDOUT") => (LogKind::Stdout, "", content.as_str().unwrap_or_default()),
+        Some("STDERR") => (LogKind::Stderr, "", content.as_str().unwrap_or_default()),
+        Some("NORMALIZED_ENTRY") => {
+            let text = content["content"].as_str().unwrap_or_default();
+            let entry_type = &content["entry_type"];
+            match entry_type["type"].as_str() {
+                Some("user_message") => (LogKind::User, "› ", text),
+                Some("assistant_message") => (LogKind::Assistant, "", text),
+                Some("tool_use") => {
+                    let tool = entry_type["tool_name"].as_str().unwrap_or("tool");
+                    return prefixed(LogKind::Tool, &format!("⚙ {}: ", tool), text);
+                }
+                Some("thinking") => (LogKind::Thinking, "", text),
+                Some("system_message") => (LogKind::System, "", text),
+                Some("error_message") => (LogKind::Error, "✗ ", text),
+                Some("user_feedback") => {
+                    let tool = entry_type["denied_tool"].as_str().unwrap_or("tool");
+                    return prefixed(LogKind::Error, &format!("✗ denied {}: ", tool), text);
+                }
+                // Loading indicators, token usage and next-action hints are UI chrome
+                _ => return Vec::new(),
+            }
+        }
+        _ => return Vec::new(),
+    };
+    prefixed(kind, prefix, text)
+}
+
+/// Lines of `text`, with `prefix` on the first one.
+fn prefixed(kind: LogKind, prefix: &str, text: &str) -> Vec<LogLine> {
+    let text = text.trim_end_matches('\n');
+    if text.is_empty() && prefix.is_empty() {
+        return Vec::new();
+    }
+    text.split('\n')
+        .enumerate()
+        .map(|(i, line)| LogLine {
+            kind,
+            text: if i == 0 {
+                format!("{}{}", prefix, line.trim_end_matches('\r'))
+            } else {
+                line.trim_end_matches('\r').to_string()
+            },
+        })
+        .collect()
+}
@@ -1,29 +1,61 @@
 //! Vibe Kanban CLI - Terminal-first, real-time task viewer and creator.
 
 mod cli_args;
+mod output;
+mod print;
 mod render;
 mod resolve;
-mod utils;
+mod tui;
 mod watch;
 
+use std::collections::{HashMap, hash_map::Entry};
+
 use anyhow::{Context, Result, anyhow};
 use clap::Parser;
-use serde_json;
+use uuid::Uuid;
 
 use vibe_kanban_cli::{
     VibeKanbanClient,
-    types::{CreateAndStartTaskRequest, CreateProject, CreateProjectRepo, CreateTask, ExecutorProfileId},
+    api::ApiError,
+    config::{Config, DEFAULT_SERVER},
+    datetime::TimeZone,
+    journal::{Journal, JournalEntry},
+    links::{self, LinkTarget},
+    prompt::TaskPrompt,
+    snapshot::{BoardDiff, BoardSnapshot, ChangeKind, SnapshotStore},
+    startup::{Startup, StartupPhase},
+    types::{
+        AgentProfile, AutoRebaseTrigger, BaseCodingAgent, BudgetState, CreateAndStartTaskRequest,
+        CreatePlanTemplate, CreateProject, CreateProjectRepo, CreateProtectedBranch, CreateTask,
+        CreateTaskAttemptBody, ExecutionProcessOrphan, ExecutorProfileId, PlanTemplate,
+        PlannerConfig, ProjectBranchPolicy, ProjectBudgetStatus, ProjectSkillSuggestions,
+        ProtectedBranch, ProtectionMode, SetProjectBranchPolicy, SetProjectBudget,
+        SetWorkspaceAutoRebase, Task, TaskWithAttemptStatus, TeamPlanOutput, TeamTask,
+        UpdateAgentProfile, UpdateTask, UserPreferences, Workspace, WorkspaceAutoRebase,
+    },
+    utils,
 };
 
 use crate::{
-    cli_args::{Args, Command, ProjectCommand, ServerCommand},
-    resolve::{parse_uuid, resolve_project, resolve_repo_inputs},
-    utils::{truncate_title},
-    watch::{WatchFilter, watch_tasks},
+    cli_args::{
+        Args, AttemptCommand, AutoRebaseCommand, AutoRebaseOn, BranchPolicyCommand, BudgetCommand,
+        Command, OutputFormat, PlanCommand, PlannerCommand, PreferencesCommand, ProfileCommand,
+        ProjectCommand, ProtectedBranchCommand, ServerCommand, SnapshotCommand, TaskCommand,
+        TeamCommand, WorkspaceCommand,
+    },
+    print::PrintOptions,
+    resolve::{
+        parse_uuid, resolve_agent_profile_id, resolve_plan_template, resolve_project,
+        resolve_project_repo, resolve_repo_inputs, resolve_task, resolve_workspace_repo,
+    },
+    utils::{format_cents, format_runtime, parse_executor, truncate_title},
+    watch::{WatchFilter, WatchOutput, watch_tasks},
 };
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let mut startup = Startup::begin();
+
     // Install rustls crypto provider before any TLS operations
     rustls::crypto::aws_lc_rs::default_provider()
         .install_default()
@@ -36,9 +68,48 @@ async fn main() -> Result<()> {
         tracing_subscriber::fmt().with_env_filter("debug").init();
     }
 
-    let client = VibeKanbanClient::new(&args.server).context("Failed to create API client")?;
+    let config = Config::load(args.config.as_deref())?;
+    startup.fast = args.fast_start;
+    startup.record(StartupPhase::Config);
+    let server_flag = args.server.is_some();
+    let server = args
+        .server
+        .or_else(|| config.server.clone())
+        .unwrap_or_else(|| DEFAULT_SERVER.to_string());
+    let client = api_client(&server, &config)?;
+    let format = args.format;
+
+    if let Some(view) = args.print {
+        if args.command.is_some() {
+            return Err(anyhow!("--print cannot be combined with a subcommand"));
+        }
+        let options = PrintOptions {
+            view,
+            project: args.project,
+            task: args.task,
+            workspace: args.workspace,
+            width: args.width,
+            height: args.height,
+        };
+        return print::run(client, options).await;
+    }
+
+    let Some(command) = args.command else {
+        let target = attach_target(&client, args.project, args.task, args.workspace).await?;
+        if args.logs && !matches!(target, Some(LinkTarget::Workspace(_))) {
+            return Err(anyhow!(
+                "--logs needs a task with a workspace, or --workspace"
+            ));
+        }
+        return tui::run(client, config, target, args.logs, startup).await;
+    };
+    if args.project.is_some() || args.task.is_some() || args.workspace.is_some() || args.logs {
+        return Err(anyhow!(
+            "--project, --task, --workspace and --logs open the interactive UI; put subcommand options after the subcommand"
+        ));
+    }
 
-    match args.command {
+    match command {
         Command::Create {
             project,
             prompt,
@@ -48,10 +119,15 @@ async fn main() -> Result<()> {
             model,
             repos,
             branch,
+            attempt_branch,
             watch,
         } => {
             let project = resolve_project(&client, &project).await?;
-            let executor = parse_executor(&tool)?;
+            let executor = match tool {
+                Some(tool) => parse_executor(&tool)?,
+                None => default_executor(&client, &config).await,
+            };
+            warn_if_executor_unavailable(&client, executor).await;
             let status = parse_status(&status)?;
             let repo_inputs =
                 resolve_repo_inputs(&client, project.id, repos, branch.as_deref()).await?;
@@ -78,6 +154,7 @@ async fn main() -> Result<()> {
                 task,
                 executor_profile_id,
                 repos: repo_inputs,
+                branch: attempt_branch,
             };
 
             let created = client.create_and_start_task(&request).await?;
@@ -87,17 +164,34 @@ async fn main() -> Result<()> {
                 created.task.id, project_name
             );
 
+            if let Ok(budget) = client.get_project_budget(project.id).await
+                && budget.state == BudgetState::Warning
+            {
+                println!(
+                    "Warning: project has used {:.0}% of its monthly budget ({} of {})",
+                    budget.percent_used.unwrap_or_default(),
+                    format_cents(budget.spent_cents),
+                    format_cents(budget.monthly_cap_cents.unwrap_or_default())
+                );
+            }
+
             if watch {
                 watch_tasks(
                     &client,
-                    &args.server,
+                    &server,
                     WatchFilter::TaskId(created.task.id),
                     Some(project),
+                    WatchOutput::Screen,
                 )
                 .await?;
             }
         }
-        Command::Watch { project, task, slug } => {
+        Command::Watch {
+            project,
+            task,
+            slug,
+            lines,
+        } => {
             let filter = match (task, slug) {
                 (Some(task_id), None) => WatchFilter::TaskId(parse_uuid(&task_id)?),
                 (None, Some(slug)) => WatchFilter::Slug(slug),
@@ -128,20 +222,25 @@ async fn main() -> Result<()> {
                 ));
             }
 
-            watch_tasks(&client, &args.server, filter, project).await?;
+            let output = if lines {
+                WatchOutput::Lines(format)
+            } else {
+                WatchOutput::Screen
+            };
+            watch_tasks(&client, &server, filter, project, output).await?;
         }
         Command::Projects { json } => {
             let projects = client.list_projects().await?;
-            if json {
-                println!("{}", serde_json::to_string_pretty(&projects)?);
-            } else if projects.is_empty() {
-                println!("No projects found.");
-            } else {
-                println!("Projects:");
-                for project in projects {
-                    println!("  {}  {}", project.id, project.name);
+            output::print(format.or_json(json), &projects, || {
+                if projects.is_empty() {
+                    println!("No projects found.");
+                } else {
+                    println!("Projects:");
+                    for project in &projects {
+                        println!("  {}  {}", project.id, project.name);
+                    }
                 }
-            }
+            })?;
         }
         Command::Project { command } => match command {
             ProjectCommand::Add {
@@ -177,6 +276,631 @@ async fn main() -> Result<()> {
                 println!("Created project {} ({})", created.name, created.id);
             }
         },
+        Command::Task { command } => match command {
+            TaskCommand::List {
+                project,
+                status,
+                json,
+            } => {
+                let project = resolve_project(&client, &project).await?;
+                let status = status.as_deref().map(parse_status).transpose()?;
+                let mut tasks = client.list_tasks(project.id).await?;
+                if let Some(status) = status {
+                    tasks.retain(|t| t.task.status == status);
+                }
+                output::print(format.or_json(json), &tasks, || print_tasks(&tasks))?;
+            }
+            TaskCommand::Show {
+                task,
+                project,
+                json,
+            } => {
+                let project = match project {
+                    Some(project_ref) => Some(resolve_project(&client, &project_ref).await?),
+                    None => None,
+                };
+                let (project, task) = resolve_task(&client, project, &task).await?;
+                output::print(format.or_json(json), &task, || {
+                    print_task(&project.name, &task);
+                })?;
+            }
+            TaskCommand::Create {
+                project,
+                title,
+                description,
+                status,
+                json,
+            } => {
+                let project = resolve_project(&client, &project).await?;
+                let payload = CreateTask {
+                    project_id: project.id,
+                    title,
+                    description,
+                    status: Some(parse_status(&status)?),
+                    parent_workspace_id: None,
+                    image_ids: None,
+                    is_epic: None,
+                    complexity: None,
+                    metadata: None,
+                };
+                let task = client
+                    .create_task(&payload, &client.idempotency_key())
+                    .await?;
+                output::print(format.or_json(json), &task, || {
+                    println!("Created task {} in project {}", task.id, project.name);
+                })?;
+            }
+            TaskCommand::Update {
+                task,
+                project,
+                title,
+                description,
+                status,
+                json,
+            } => {
+                if title.is_none() && description.is_none() && status.is_none() {
+                    return Err(anyhow!(
+                        "Nothing to update; pass --title, --description or --status"
+                    ));
+                }
+                let project = match project {
+                    Some(project_ref) => Some(resolve_project(&client, &project_ref).await?),
+                    None => None,
+                };
+                let (_, task) = resolve_task(&client, project, &task).await?;
+                let payload = UpdateTask {
+                    title,
+                    description,
+                    status: status.as_deref().map(parse_status).transpose()?,
+                    parent_workspace_id: None,
+                    image_ids: None,
+                    is_epic: None,
+                    complexity: None,
+                    metadata: None,
+                };
+                let task = client.update_task(task.task.id, &payload).await?;
+                output::print(format.or_json(json), &task, || {
+                    println!("Updated task {} ({})", task.title, task.id);
+                })?;
+            }
+            TaskCommand::Delete { task, project } => {
+                let project = match project {
+                    Some(project_ref) => Some(resolve_project(&client, &project_ref).await?),
+                    None => None,
+                };
+                let (_, task) = resolve_task(&client, project, &task).await?;
+                if client.delete_task(task.task.id).await?.is_some() {
+                    println!(
+                        "Moved task {} ({}) to the trash",
+                        task.task.title, task.task.id
+                    );
+                } else {
+                    println!("Deleted task {} ({})", task.task.title, task.task.id);
+                }
+            }
+        },
+        Command::Attempt { command } => match command {
+            AttemptCommand::Start {
+                task,
+                project,
+                tool,
+                model,
+                repos,
+                branch,
+                attempt_branch,
+                json,
+            } => {
+                let project = match project {
+                    Some(project_ref) => Some(resolve_project(&client, &project_ref).await?),
+                    None => None,
+                };
+                let (project, task) = resolve_task(&client, project, &task).await?;
+                let executor = match tool {
+                    Some(tool) => parse_executor(&tool)?,
+                    None => default_executor(&client, &config).await,
+                };
+                warn_if_executor_unavailable(&client, executor).await;
+                let payload = CreateTaskAttemptBody {
+                    task_id: task.task.id,
+                    executor_profile_id: ExecutorProfileId {
+                        executor,
+                        variant: model,
+                    },
+                    repos: resolve_repo_inputs(&client, project.id, repos, branch.as_deref())
+                        .await?,
+                    resource_limits: None,
+                    branch: attempt_branch,
+                };
+                let workspace = client
+                    .create_task_attempt(&payload, &client.idempotency_key())
+                    .await?;
+                output::print(format.or_json(json), &workspace, || {
+                    println!(
+                        "Started attempt {} on {} for task {}",
+                        workspace.id, workspace.branch, task.task.title
+                    );
+                })?;
+            }
+        },
+        Command::Workspace { command } => match command {
+            WorkspaceCommand::List {
+                task,
+                project,
+                json,
+            } => {
+                let project = match project {
+                    Some(project_ref) => Some(resolve_project(&client, &project_ref).await?),
+                    None => None,
+                };
+                let (_, task) = resolve_task(&client, project, &task).await?;
+                let workspaces = client.list_workspaces(Some(task.task.id)).await?;
+                output::print(format.or_json(json), &workspaces, || {
+                    print_workspaces(&task.task, &workspaces);
+                })?;
+            }
+            WorkspaceCommand::Stop { workspace } => {
+                let workspace = client.get_workspace(parse_uuid(&workspace)?).await?;
+                client.stop_workspace(workspace.id).await?;
+                println!("Stopped {}", workspace.branch);
+            }
+            WorkspaceCommand::Merge {
+                workspace,
+                repo,
+                confirm_protected,
+            } => {
+                let workspace = client.get_workspace(parse_uuid(&workspace)?).await?;
+                let repo = resolve_workspace_repo(&client, workspace.id, repo.as_deref()).await?;
+                client
+                    .merge_workspace(
+                        workspace.id,
+                        repo.repo.id,
+                        &client.idempotency_key(),
+                        confirm_protected,
+                    )
+                    .await
+                    .map_err(protected_branch_hint)?;
+                println!(
+                    "Merged {} into {} in {}",
+                    workspace.branch, repo.target_branch, repo.repo.display_name
+                );
+            }
+            WorkspaceCommand::Push {
+                workspace,
+                repo,
+                confirm_protected,
+            } => {
+                let workspace = client.get_workspace(parse_uuid(&workspace)?).await?;
+                let repo = resolve_workspace_repo(&client, workspace.id, repo.as_deref()).await?;
+                client
+                    .push_workspace(workspace.id, repo.repo.id, confirm_protected)
+                    .await
+                    .map_err(protected_branch_hint)?;
+                println!("Pushed {} in {}", workspace.branch, repo.repo.display_name);
+            }
+        },
+        Command::Budget { command } => match command {
+            BudgetCommand::Show { project, json } => {
+                let project = resolve_project(&client, &project).await?;
+                let budget = client.get_project_budget(project.id).await?;
+                output::print(format.or_json(json), &budget, || {
+                    print_budget(&project.name, &budget);
+                })?;
+            }
+            BudgetCommand::Set { project, cap } => {
+                if !cap.is_finite() || cap < 0.0 {
+                    return Err(anyhow!("--cap must be a non-negative amount in USD"));
+                }
+                let project = resolve_project(&client, &project).await?;
+                let payload = SetProjectBudget {
+                    monthly_cap_cents: Some((cap * 100.0).round() as i64),
+                };
+                let budget = client.set_project_budget(project.id, &payload).await?;
+                print_budget(&project.name, &budget);
+            }
+            BudgetCommand::Clear { project } => {
+                let project = resolve_project(&client, &project).await?;
+                let payload = SetProjectBudget {
+                    monthly_cap_cents: None,
+                };
+                client.set_project_budget(project.id, &payload).await?;
+                println!("Removed monthly budget for project {}", project.name);
+            }
+            BudgetCommand::Override { project, off } => {
+                let project = resolve_project(&client, &project).await?;
+                let budget = client.set_budget_override(project.id, !off).await?;
+                print_budget(&project.name, &budget);
+            }
+        },
+        Command::BranchPolicy { command } => match command {
+            BranchPolicyCommand::Show { project, json } => {
+                let project = resolve_project(&client, &project).await?;
+                let policy = client.get_branch_policy(project.id).await?;
+                output::print(format.or_json(json), &policy, || {
+                    print_branch_policy(&project.name, policy.as_ref());
+                })?;
+            }
+            BranchPolicyCommand::Set {
+                project,
+                prefixes,
+                max_length,
+                forbidden,
+            } => {
+                let project = resolve_project(&client, &project).await?;
+                let payload = SetProjectBranchPolicy {
+                    allowed_prefixes: prefixes,
+                    max_length,
+                    forbidden_chars: forbidden.unwrap_or_default(),
+                };
+                let policy = client.set_branch_policy(project.id, &payload).await?;
+                print_branch_policy(&project.name, Some(&policy));
+            }
+            BranchPolicyCommand::Clear { project } => {
+                let project = resolve_project(&client, &project).await?;
+                client.delete_branch_policy(project.id).await?;
+                println!("Removed branch policy for project {}", project.name);
+            }
+        },
+        Command::AutoRebase { command } => match command {
+            AutoRebaseCommand::Show { workspace, json } => {
+                let workspace = client.get_workspace(parse_uuid(&workspace)?).await?;
+                let policy = client.get_workspace_auto_rebase(workspace.id).await?;
+                output::print(format.or_json(json), &policy, || {
+                    print_auto_rebase(&workspace.branch, policy.as_ref());
+                })?;
+            }
+            AutoRebaseCommand::Set { workspace, on } => {
+                let workspace = client.get_workspace(parse_uuid(&workspace)?).await?;
+                let trigger = match on {
+                    AutoRebaseOn::Nightly => AutoRebaseTrigger::Nightly,
+                    AutoRebaseOn::TargetMoved => AutoRebaseTrigger::TargetMoved,
+                };
+                let policy = client
+                    .set_workspace_auto_rebase(workspace.id, &SetWorkspaceAutoRebase { trigger })
+                    .await?;
+                print_auto_rebase(&workspace.branch, Some(&policy));
+            }
+            AutoRebaseCommand::Resume { workspace } => {
+                let workspace = client.get_workspace(parse_uuid(&workspace)?).await?;
+                let Some(policy) = client.get_workspace_auto_rebase(workspace.id).await? else {
+                    return Err(anyhow!(
+                        "Auto-rebase is not enabled for {}; enable it with `auto-rebase set`",
+                        workspace.branch
+                    ));
+                };
+                let policy = client
+                    .set_workspace_auto_rebase(
+                        workspace.id,
+                        &SetWorkspaceAutoRebase {
+                            trigger: policy.trigger,
+                        },
+                    )
+                    .await?;
+                print_auto_rebase(&workspace.branch, Some(&policy));
+            }
+            AutoRebaseCommand::Disable { workspace } => {
+                let workspace = client.get_workspace(parse_uuid(&workspace)?).await?;
+                client.delete_workspace_auto_rebase(workspace.id).await?;
+                println!("Auto-rebase disabled for {}", workspace.branch);
+            }
+        },
+        Command::ProtectedBranches { command } => match command {
+            ProtectedBranchCommand::List {
+                project,
+                repo,
+                json,
+            } => {
+                let project = resolve_project(&client, &project).await?;
+                let repo = resolve_project_repo(&client, project.id, repo.as_deref()).await?;
+                let protected = client.list_protected_branches(repo.id).await?;
+                output::print(format.or_json(json), &protected, || {
+                    print_protected_branches(&repo.display_name, &protected);
+                })?;
+            }
+            ProtectedBranchCommand::Add {
+                pattern,
+                project,
+                repo,
+                block,
+            } => {
+                let project = resolve_project(&client, &project).await?;
+                let repo = resolve_project_repo(&client, project.id, repo.as_deref()).await?;
+                let payload = CreateProtectedBranch {
+                    pattern,
+                    mode: if block {
+                        ProtectionMode::Block
+                    } else {
+                        ProtectionMode::Confirm
+                    },
+                };
+                let protected = client.protect_branch(repo.id, &payload).await?;
+                println!(
+                    "Protected {} in {} ({})",
+                    protected.pattern,
+                    repo.display_name,
+                    protection_description(protected.mode)
+                );
+            }
+            ProtectedBranchCommand::Remove {
+                pattern,
+                project,
+                repo,
+            } => {
+                let project = resolve_project(&client, &project).await?;
+                let repo = resolve_project_repo(&client, project.id, repo.as_deref()).await?;
+                let protected = client.list_protected_branches(repo.id).await?;
+                let Some(entry) = protected.iter().find(|p| p.pattern == pattern) else {
+                    return Err(anyhow!(
+                        "'{}' is not protected in {}",
+                        pattern,
+                        repo.display_name
+                    ));
+                };
+                client.unprotect_branch(repo.id, entry.id).await?;
+                println!(
+                    "Lifted the protection of {} in {}",
+                    pattern, repo.display_name
+                );
+            }
+        },
+        Command::Plans { command } => match command {
+            PlanCommand::List { json } => {
+                let templates = client.list_plan_templates().await?;
+                output::print(format.or_json(json), &templates, || {
+                    print_plan_templates(&templates);
+                })?;
+            }
+            PlanCommand::Show { template, json } => {
+                let template = resolve_plan_template(&client, &template).await?;
+                output::print(format.or_json(json), &template, || {
+                    println!("{} ({})", template.name, template.id);
+                    print_plan(&template.plan);
+                })?;
+            }
+            PlanCommand::Save { team, name } => {
+                let payload = CreatePlanTemplate {
+                    team_execution_id: parse_uuid(&team)?,
+                    name,
+                };
+                let template = client.create_plan_template(&payload).await?;
+                println!(
+                    "Saved plan template {} ({}) with {} subtasks",
+                    template.name,
+                    template.id,
+                    template.plan.subtasks.len()
+                );
+            }
+            PlanCommand::Use { team, template } => {
+                let team_id = parse_uuid(&team)?;
+                let template = resolve_plan_template(&client, &template).await?;
+                let plan = client.plan_team_from_template(team_id, template.id).await?;
+                println!("Planned team {} from template {}", team_id, template.name);
+                print_plan(&plan);
+            }
+            PlanCommand::Delete { template } => {
+                let template = resolve_plan_template(&client, &template).await?;
+                client.delete_plan_template(template.id).await?;
+                println!("Deleted plan template {}", template.name);
+            }
+        },
+        Command::Profiles { command } => match command {
+            ProfileCommand::List { json } => {
+                let profiles = client.list_agent_profiles().await?;
+                output::print(format.or_json(json), &profiles, || {
+                    print_agent_profiles(&profiles);
+                })?;
+            }
+            ProfileCommand::Seed => {
+                let profiles = client.seed_default_agent_profiles().await?;
+                if profiles.is_empty() {
+                    println!("Every team role already has an active profile.");
+                } else {
+                    println!("Created default profiles:");
+                    print_agent_profiles(&profiles);
+                }
+            }
+            ProfileCommand::Update {
+                profile,
+                executor,
+                variant,
+                max_concurrent,
+                priority,
+                active,
+            } => {
+                let profile_id = resolve_agent_profile_id(&client, &profile).await?;
+                let payload = UpdateAgentProfile {
+                    executor: executor
+                        .map(|e| parse_executor(&e).map(|e| e.as_str().to_string()))
+                        .transpose()?,
+                    variant,
+                    max_concurrent_tasks: max_concurrent,
+                    priority,
+                    active,
+                };
+                let profile = client.update_agent_profile(profile_id, &payload).await?;
+                println!("Updated agent profile {}", profile.name);
+                print_agent_profiles(&[profile]);
+            }
+        },
+        Command::Planner { command } => match command {
+            PlannerCommand::Show { json } => {
+                let config = client.get_planner_config().await?;
+                output::print(format.or_json(json), &config, || {
+                    print_planner_config(&config);
+                })?;
+            }
+            PlannerCommand::Set {
+                team_threshold,
+                max_subtasks,
+                max_parallel_workers,
+            } => {
+                let mut config = client.get_planner_config().await?;
+                config.team_threshold = team_threshold.unwrap_or(config.team_threshold);
+                config.max_subtasks = max_subtasks.unwrap_or(config.max_subtasks);
+                config.max_parallel_workers =
+                    max_parallel_workers.unwrap_or(config.max_parallel_workers);
+                let config = client.update_planner_config(&config).await?;
+                println!("Updated planner settings");
+                print_planner_config(&config);
+            }
+        },
+        Command::Teams { command } => match command {
+            TeamCommand::Dag { team, json } => {
+                let tasks = client.get_team_tasks(parse_uuid(&team)?).await?;
+                match format.or_json(json) {
+                    // Looks up titles and branches, so it can't go in the callback
+                    OutputFormat::Table => print_team_dag(&client, &tasks).await?,
+                    format => output::print(format, &tasks, || {})?,
+                }
+            }
+        },
+        Command::Skills { project, json } => {
+            let project = resolve_project(&client, &project).await?;
+            let suggestions = client.get_skill_suggestions(project.id).await?;
+            output::print(format.or_json(json), &suggestions, || {
+                print_skill_suggestions(&project.name, &suggestions);
+            })?;
+        }
+        Command::History {
+            limit,
+            filter,
+            json,
+        } => {
+            let journal = Journal::open_default();
+            let mut entries = journal.entries()?;
+            if let Some(filter) = &filter {
+                entries.retain(|entry| entry.endpoint.contains(filter.as_str()));
+            }
+            let entries = &entries[entries.len().saturating_sub(limit)..];
+            output::print(format.or_json(json), entries, || {
+                print_history(&journal, entries);
+            })?;
+        }
+        Command::Orphans {
+            project,
+            limit,
+            json,
+        } => {
+            let project_id = match project {
+                Some(project_ref) => Some(resolve_project(&client, &project_ref).await?.id),
+                None => None,
+            };
+            let orphans = client.list_orphaned_executions(project_id, limit).await?;
+            match format.or_json(json) {
+                // Looks up task titles, so it can't go in the callback
+                OutputFormat::Table => print_orphans(&client, &orphans).await,
+                format => output::print(format, &orphans, || {})?,
+            }
+        }
+        Command::Snapshot { command } => match command {
+            SnapshotCommand::Save { name, project } => {
+                let project = resolve_project(&client, &project).await?;
+                let tasks = client.list_tasks(project.id).await?;
+                let snapshot = BoardSnapshot::capture(&name, &project, &tasks);
+                let replaced = SnapshotStore::open_default().save(&snapshot)?;
+                println!(
+                    "{} snapshot {} of {} with {} tasks",
+                    if replaced { "Replaced" } else { "Saved" },
+                    snapshot.name,
+                    project.name,
+                    snapshot.tasks.len()
+                );
+            }
+            SnapshotCommand::List { project, json } => {
+                let project = resolve_project(&client, &project).await?;
+                let snapshots = SnapshotStore::open_default().list(project.id)?;
+                output::print(format.or_json(json), &snapshots, || {
+                    print_snapshots(&project.name, &snapshots, config.time_zone);
+                })?;
+            }
+            SnapshotCommand::Diff {
+                name,
+                project,
+                json,
+                output,
+            } => {
+                let project = resolve_project(&client, &project).await?;
+                let snapshot = SnapshotStore::open_default().load(project.id, &name)?;
+                let tasks = client.list_tasks(project.id).await?;
+                let diff = BoardDiff::between(snapshot, &tasks);
+                match output {
+                    Some(path) => {
+                        std::fs::write(&path, diff.to_markdown(config.time_zone))
+                            .with_context(|| format!("Failed to write {}", path.display()))?;
+                        println!("Changes written to {}", path.display());
+                    }
+                    None => output::print(format.or_json(json), &diff.changes, || {
+                        print_board_diff(&diff, config.time_zone);
+                    })?,
+                }
+            }
+            SnapshotCommand::Delete { name, project } => {
+                let project = resolve_project(&client, &project).await?;
+                SnapshotStore::open_default().delete(project.id, &name)?;
+                println!("Deleted snapshot {} of {}", name, project.name);
+            }
+        },
+        Command::Preferences { command } => match command {
+            PreferencesCommand::Show { json } => {
+                let preferences = client.get_user_preferences().await?;
+                let local = config.local_preferences()?;
+                output::print(format.or_json(json), &preferences, || {
+                    print_preferences(&preferences, &local);
+                })?;
+            }
+            PreferencesCommand::Push => {
+                let local = config.local_preferences()?;
+                if local == UserPreferences::default() {
+                    println!(
+                        "The config file sets no theme, default_executor, saved_filters or keybindings"
+                    );
+                    return Ok(());
+                }
+                let mut preferences = client.get_user_preferences().await?;
+                preferences.theme = local.theme.or(preferences.theme);
+                preferences.default_executor =
+                    local.default_executor.or(preferences.default_executor);
+                preferences.saved_filters.extend(local.saved_filters);
+                preferences.keybindings.extend(local.keybindings);
+                let preferences = client.update_user_preferences(&preferences).await?;
+                println!("Saved preferences to the server");
+                print_preferences(&preferences, &UserPreferences::default());
+            }
+            PreferencesCommand::Clear => {
+                client
+                    .update_user_preferences(&UserPreferences::default())
+                    .await?;
+                println!("Cleared the preferences kept on the server");
+            }
+        },
+        Command::Prompt {
+            task,
+            project,
+            output,
+        } => {
+            let project = match project {
+                Some(project_ref) => Some(resolve_project(&client, &project_ref).await?),
+                None => None,
+            };
+            let (_, task) = resolve_task(&client, project, &task).await?;
+            let prompt = TaskPrompt::load(&client, task.task).await?.render();
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, prompt)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    println!("Prompt written to {}", path.display());
+                }
+                None => print!("{}", prompt),
+            }
+        }
+        Command::Open { url } => {
+            let link = links::parse_web_link(&url)?;
+            // Open it on the server the link came from, unless --server says otherwise
+            let client = match link.server {
+                Some(link_server) if !server_flag => api_client(&link_server, &config)?,
+                _ => client,
+            };
+            return tui::run(client, config, Some(link.target), false, startup).await;
+        }
         Command::Server { command } => match command {
             ServerCommand::Start {
                 command,
@@ -192,6 +916,602 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Where `--project`, `--task` or `--workspace` open the interactive UI: the
+/// workspace, else the task's latest workspace, or the task itself while it has
+/// none, else the project's board.
+async fn attach_target(
+    client: &VibeKanbanClient,
+    project: Option<String>,
+    task: Option<String>,
+    workspace: Option<String>,
+) -> Result<Option<LinkTarget>> {
+    if let Some(workspace_ref) = workspace {
+        return Ok(Some(LinkTarget::Workspace(parse_uuid(&workspace_ref)?)));
+    }
+    let project = match project {
+        Some(project_ref) => Some(resolve_project(client, &project_ref).await?),
+        None => None,
+    };
+    let Some(task_ref) = task else {
+        return Ok(project.map(|project| LinkTarget::Project(project.id)));
+    };
+    let (project, task) = resolve_task(client, project, &task_ref).await?;
+    let workspaces = client.list_workspaces(Some(task.task.id)).await?;
+    let latest = workspaces
+        .iter()
+        .max_by(|a, b| a.created_at.cmp(&b.created_at));
+    let target = match latest {
+        Some(workspace) => LinkTarget::Workspace(workspace.id),
+        None => LinkTarget::Task {
+            project_id: project.id,
+            task_id: task.task.id,
+        },
+    };
+    Ok(Some(target))
+}
+
+/// A client for `server`, asking for responses in the configured wire format.
+fn api_client(server: &str, config: &Config) -> Result<VibeKanbanClient> {
+    let client = VibeKanbanClient::new(server).context("Failed to create API client")?;
+    let format = config.wire_format;
+    if !format.is_supported() {
+        eprintln!(
+            "Warning: this build can't read {} responses, using JSON. Build with --features msgpack or cbor.",
+            format.label()
+        );
+        return Ok(client);
+    }
+    client.with_wire_format(format)
+}
+
+/// Print a warning when the server reports the executor as unavailable.
+/// The check is advisory: the attempt is still created.
+/// Executor for attempts started without `--tool`: the config file's, else the
+/// one in the user's preferences on the server, else Codex.
+async fn default_executor(client: &VibeKanbanClient, config: &Config) -> BaseCodingAgent {
+    if let Some(executor) = config.default_executor {
+        return executor;
+    }
+    client
+        .get_user_preferences()
+        .await
+        .ok()
+        .and_then(|preferences| preferences.default_executor)
+        .and_then(|name| parse_executor(&name).ok())
+        .unwrap_or(BaseCodingAgent::Codex)
+}
+
+async fn warn_if_executor_unavailable(
+    client: &VibeKanbanClient,
+    executor: vibe_kanban_cli::types::BaseCodingAgent,
+) {
+    let Ok(health) = client.get_executor_health().await else {
+        return;
+    };
+    if let Some(health) = health.iter().find(|h| h.executor == executor && !h.available) {
+        eprintln!(
+            "Warning: {} is unavailable: {}",
+            executor.as_str(),
+            health.message.as_deref().unwrap_or("unknown reason")
+        );
+    }
+}
+
+/// Point out `--confirm-protected` when the server wants a protected branch confirmed.
+fn protected_branch_hint(err: anyhow::Error) -> anyhow::Error {
+    let violation = err
+        .chain()
+        .find_map(|e| e.downcast_ref::<ApiError>())
+        .and_then(ApiError::protected_branch_violation)
+        .filter(|violation| violation.mode == ProtectionMode::Confirm);
+    match violation {
+        Some(violation) => anyhow!(
+            "Branch '{}' is protected (matches '{}'); pass --confirm-protected to go ahead",
+            violation.branch,
+            violation.pattern
+        ),
+        None => err,
+    }
+}
+
+fn print_tasks(tasks: &[TaskWithAttemptStatus]) {
+    if tasks.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+    for task in tasks {
+        let attempt = if task.has_in_progress_attempt {
+            "  (running)"
+        } else if task.last_attempt_failed {
+            "  (failed)"
+        } else {
+            ""
+        };
+        println!(
+            "{}  {:<10}  {}{}",
+            task.task.id,
+            task.task.status.as_str(),
+            task.task.title,
+            attempt
+        );
+    }
+}
+
+fn print_task(project_name: &str, task: &TaskWithAttemptStatus) {
+    println!("{} ({})", task.task.title, task.task.id);
+    println!("  Project: {}", project_name);
+    println!("  Status: {}", task.task.status.display_name());
+    if task.has_in_progress_attempt {
+        println!("  Attempt: running on {}", task.executor);
+    } else if task.last_attempt_failed {
+        println!("  Attempt: last one failed on {}", task.executor);
+    }
+    if let Some(description) = task.task.description.as_deref().filter(|d| !d.is_empty()) {
+        println!();
+        for line in description.lines() {
+            println!("  {}", line);
+        }
+    }
+}
+
+fn print_workspaces(task: &Task, workspaces: &[Workspace]) {
+    if workspaces.is_empty() {
+        println!("No workspaces for {}", task.title);
+        return;
+    }
+    for workspace in workspaces {
+        let archived = if workspace.archived {
+            "  (archived)"
+        } else {
+            ""
+        };
+        println!("{}  {}{}", workspace.id, workspace.branch, archived);
+    }
+}
+
+fn print_plan_templates(templates: &[PlanTemplate]) {
+    if templates.is_empty() {
+        println!("No plan templates. Save one with: plans save --team <id>");
+        return;
+    }
+    for template in templates {
+        println!(
+            "{}  {} ({} subtasks, saved {})",
+            template.id,
+            template.name,
+            template.plan.subtasks.len(),
+            template.created_at.format("%Y-%m-%d")
+        );
+    }
+}
+
+fn print_plan(plan: &TeamPlanOutput) {
+    for (index, subtask) in plan.subtasks.iter().enumerate() {
+        let depends_on = if subtask.depends_on.is_empty() {
+            String::new()
+        } else {
+            let indices: Vec<String> = subtask.depends_on.iter().map(|i| i.to_string()).collect();
+            format!(" (after {})", indices.join(", "))
+        };
+        println!("  {}. {}{}", index, subtask.title, depends_on);
+        for scope in &subtask.repo_scopes {
+            println!("       {}: {}", scope.repo_name, scope.hint);
+        }
+    }
+}
+
+fn print_agent_profiles(profiles: &[AgentProfile]) {
+    if profiles.is_empty() {
+        println!("No agent profiles. Create the defaults with: profiles seed");
+        return;
+    }
+    for profile in profiles {
+        let executor = match &profile.variant {
+            Some(variant) => format!("{}/{}", profile.executor, variant),
+            None => profile.executor.clone(),
+        };
+        let disabled = if profile.active { "" } else { ", disabled" };
+        println!(
+            "{}  {} ({} on {}, up to {} at once, priority {}{})",
+            profile.id,
+            profile.name,
+            profile.roles(),
+            executor,
+            profile.max_concurrent_tasks,
+            profile.priority,
+            disabled
+        );
+    }
+}
+
+fn print_planner_config(config: &PlannerConfig) {
+    println!("Team threshold:        {}", config.team_threshold);
+    println!("Max subtasks:          {}", config.max_subtasks);
+    println!("Max parallel workers:  {}", config.max_parallel_workers);
+}
+
+/// The preferences kept on the server, noting those `local`, the config file's,
+/// takes precedence over on this machine.
+fn print_preferences(preferences: &UserPreferences, local: &UserPreferences) {
+    let overridden = |server: &str, local: Option<&str>| match local {
+        Some(local) if local != server => format!("{} (here: {})", server, local),
+        _ => server.to_string(),
+    };
+    let setting = |value: &Option<String>, local: &Option<String>| {
+        value.as_deref().map_or_else(
+            || "not set".to_string(),
+            |value| overridden(value, local.as_deref()),
+        )
+    };
+    println!(
+        "Theme:             {}",
+        setting(&preferences.theme, &local.theme)
+    );
+    println!(
+        "Default executor:  {}",
+        setting(&preferences.default_executor, &local.default_executor)
+    );
+    if !preferences.saved_filters.is_empty() {
+        println!("Saved filters:");
+        for (name, query) in &preferences.saved_filters {
+            let query = overridden(query, local.saved_filters.get(name).map(String::as_str));
+            println!("  @{:<16} {}", name, query);
+        }
+    }
+    if !preferences.keybindings.is_empty() {
+        println!("Key bindings:");
+        for (action, keys) in &preferences.keybindings {
+            let local_keys = local.keybindings.get(action).map(|k| k.join(", "));
+            let keys = overridden(&keys.join(", "), local_keys.as_deref());
+            println!("  {:<18} {}", action, keys);
+        }
+    }
+}
+
+fn print_skill_suggestions(project_name: &str, suggestions: &ProjectSkillSuggestions) {
+    let detected = &suggestions.detected;
+    let list = |values: &[String]| {
+        if values.is_empty() {
+            "none detected".to_string()
+        } else {
+            values.join(", ")
+        }
+    };
+    println!("Skills for {}", project_name);
+    println!("  Languages: {}", list(&detected.languages));
+    println!("  Frameworks: {}", list(&detected.frameworks));
+    println!("  Skills: {}", list(&detected.skills));
+    if suggestions.skills.is_empty() {
+        return;
+    }
+    if suggestions.profiles.is_empty() {
+        println!("All worker profiles have these skills.");
+        return;
+    }
+    println!("Worker profiles missing skills:");
+    for suggestion in &suggestions.profiles {
+        let missing: Vec<&str> = suggestion
+            .missing_skills
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        println!("  {}: {}", suggestion.profile.name, missing.join(", "));
+    }
+}
+
+fn print_budget(project_name: &str, budget: &ProjectBudgetStatus) {
+    println!("Budget for {} ({})", project_name, budget.month);
+    match budget.monthly_cap_cents {
+        Some(cap) => println!(
+            "  Spent: {} of {} ({:.0}%)",
+            format_cents(budget.spent_cents),
+            format_cents(cap),
+            budget.percent_used.unwrap_or_default()
+        ),
+        None => println!("  Spent: {} (no cap)", format_cents(budget.spent_cents)),
+    }
+    println!("  Status: {}", budget.state.display_name());
+    if budget.state == BudgetState::Exceeded {
+        println!("  New attempts are blocked until the cap is raised or overridden.");
+    }
+
+    if !budget.by_executor.is_empty() {
+        println!();
+        println!("  {:<16} {:>9} {:>10}", "EXECUTOR", "RUNTIME", "EST. COST");
+        for spend in &budget.by_executor {
+            println!(
+                "  {:<16} {:>9} {:>10}",
+                spend.executor,
+                format_runtime(spend.runtime_seconds),
+                format_cents(spend.estimated_cost_cents)
+            );
+        }
+    }
+}
+
+fn print_branch_policy(project_name: &str, policy: Option<&ProjectBranchPolicy>) {
+    let Some(policy) = policy else {
+        println!(
+            "Project {} has no branch policy; any branch name is allowed",
+            project_name
+        );
+        return;
+    };
+    println!("Branch policy for {}", project_name);
+    if policy.allowed_prefixes.is_empty() {
+        println!("  Prefixes: any");
+    } else {
+        println!("  Prefixes: {}", policy.allowed_prefixes.join(", "));
+    }
+    match policy.max_length {
+        Some(max_length) => println!("  Max length: {}", max_length),
+        None => println!("  Max length: unlimited"),
+    }
+    if policy.forbidden_chars.is_empty() {
+        println!("  Forbidden characters: none");
+    } else {
+        println!("  Forbidden characters: {}", policy.forbidden_chars);
+    }
+}
+
+fn print_auto_rebase(branch: &str, policy: Option<&WorkspaceAutoRebase>) {
+    let Some(policy) = policy else {
+        println!("Auto-rebase is not enabled for {}", branch);
+        return;
+    };
+    println!("Auto-rebase for {}: {}", branch, policy.trigger.label());
+    if let Some(reason) = &policy.paused_reason {
+        println!("  Paused: {}", reason);
+        println!("  Resolve it, then run `auto-rebase resume` to continue");
+    } else if let Some(error) = &policy.last_error {
+        println!("  Last check failed: {}", error);
+    }
+    match policy.last_rebased_at {
+        Some(at) => println!(
+            "  Last rebased: {}",
+            at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        ),
+        None => println!("  Last rebased: never"),
+    }
+}
+
+fn print_protected_branches(repo_name: &str, protected: &[ProtectedBranch]) {
+    if protected.is_empty() {
+        println!("No protected branches in {}", repo_name);
+        return;
+    }
+    println!("Protected branches in {}", repo_name);
+    for entry in protected {
+        println!(
+            "  {:<24} {}",
+            entry.pattern,
+            protection_description(entry.mode)
+        );
+    }
+}
+
+fn protection_description(mode: ProtectionMode) -> &'static str {
+    match mode {
+        ProtectionMode::Confirm => "merges, pushes and rebases need confirming",
+        ProtectionMode::Block => "merges, pushes and rebases are refused",
+    }
+}
+
+fn print_history(journal: &Journal, entries: &[JournalEntry]) {
+    if entries.is_empty() {
+        match journal.path() {
+            Some(path) => println!("No recorded changes in {}", path.display()),
+            None => println!("No recorded changes."),
+        }
+        return;
+    }
+
+    println!(
+        "{:<19}  {:<6}  {:<8}  {:<12}  ENDPOINT",
+        "TIME", "METHOD", "RESULT", "PAYLOAD"
+    );
+    for entry in entries {
+        let digest = entry
+            .payload_digest
+            .as_deref()
+            .map(|d| d.chars().take(12).collect::<String>())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<19}  {:<6}  {:<8}  {:<12}  {}",
+            entry
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            entry.method,
+            entry.result(),
+            digest,
+            entry.endpoint
+        );
+    }
+}
+
+fn print_snapshots(project_name: &str, snapshots: &[BoardSnapshot], zone: TimeZone) {
+    if snapshots.is_empty() {
+        println!("No snapshots of {}", project_name);
+        return;
+    }
+    println!("{:<24}  {:<16}  TASKS", "NAME", "TAKEN");
+    for snapshot in snapshots {
+        println!(
+            "{:<24}  {:<16}  {}",
+            snapshot.name,
+            zone.format(snapshot.taken_at, "%Y-%m-%d %H:%M"),
+            snapshot.tasks.len()
+        );
+    }
+}
+
+fn print_board_diff(diff: &BoardDiff, zone: TimeZone) {
+    let snapshot = &diff.snapshot;
+    println!(
+        "{}: changes since {} ({})",
+        snapshot.project_name,
+        snapshot.name,
+        zone.format(snapshot.taken_at, "%Y-%m-%d %H:%M")
+    );
+    println!();
+    println!("{:<12}  {:>5}  {:>5}", "COLUMN", "THEN", "NOW");
+    for ((status, then), (_, now)) in snapshot.column_counts().iter().zip(&diff.columns_now) {
+        println!("{:<12}  {:>5}  {:>5}", status.display_name(), then, now);
+    }
+
+    if diff.changes.is_empty() {
+        println!();
+        println!("No changes.");
+    }
+    for kind in ChangeKind::ALL {
+        let changes: Vec<_> = diff.of_kind(kind).collect();
+        if changes.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{} ({})", kind.heading(), changes.len());
+        for change in changes {
+            println!("  {}{}", change.title, change.transition());
+        }
+    }
+}
+
+async fn print_orphans(client: &VibeKanbanClient, orphans: &[ExecutionProcessOrphan]) {
+    if orphans.is_empty() {
+        println!("No orphaned processes found.");
+        return;
+    }
+
+    println!(
+        "{:<19}  {:<17}  {:<36}  TASK",
+        "DETECTED", "REASON", "WORKSPACE"
+    );
+    let mut titles: HashMap<Uuid, String> = HashMap::new();
+    for orphan in orphans {
+        if let Entry::Vacant(entry) = titles.entry(orphan.task_id) {
+            // Deleted tasks take their orphan records with them, so this rarely fails
+            let title = match client.get_task(orphan.task_id).await {
+                Ok(task) => task.title,
+                Err(_) => orphan.task_id.to_string(),
+            };
+            entry.insert(title);
+        }
+        println!(
+            "{:<19}  {:<17}  {:<36}  {}",
+            orphan
+                .detected_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            orphan.reason.label(),
+            orphan.workspace_id,
+            titles[&orphan.task_id]
+        );
+    }
+}
+
+/// A team subtask with what `teams dag` shows of it
+struct DagNode<'a> {
+    task: &'a TeamTask,
+    title: String,
+    branch: Option<String>,
+    target_branches: Vec<String>,
+}
+
+/// Print team subtasks as a tree, each under the first subtask it waits for.
+/// A subtask whose branch builds on another subtask's branch is marked as
+/// stacked on it; the rest build on the epic's base branch.
+async fn print_team_dag(client: &VibeKanbanClient, tasks: &[TeamTask]) -> Result<()> {
+    if tasks.is_empty() {
+        println!("No subtasks yet. Plan and execute the team first.");
+        return Ok(());
+    }
+
+    let mut nodes = Vec::new();
+    for task in tasks {
+        let title = client.get_task(task.task_id).await?.title;
+        let (branch, target_branches) = match task.workspace_id {
+            Some(workspace_id) => {
+                let workspace = client.get_workspace(workspace_id).await?;
+                let repos = client.get_workspace_repos(workspace_id).await?;
+                (
+                    Some(workspace.branch),
+                    repos.into_iter().map(|r| r.target_branch).collect(),
+                )
+            }
+            None => (None, Vec::new()),
+        };
+        nodes.push(DagNode {
+            task,
+            title,
+            branch,
+            target_branches,
+        });
+    }
+
+    let index: HashMap<Uuid, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.task.id, i))
+        .collect();
+    let mut children: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+    for (i, node) in nodes.iter().enumerate() {
+        let parent = node
+            .task
+            .dependencies()
+            .first()
+            .and_then(|id| index.get(id).copied());
+        children.entry(parent).or_default().push(i);
+    }
+
+    fn print_node(
+        nodes: &[DagNode],
+        index: &HashMap<Uuid, usize>,
+        children: &HashMap<Option<usize>, Vec<usize>>,
+        i: usize,
+        depth: usize,
+    ) {
+        let node = &nodes[i];
+        let dependencies: Vec<&DagNode> = node
+            .task
+            .dependencies()
+            .iter()
+            .filter_map(|id| index.get(id).map(|&j| &nodes[j]))
+            .collect();
+        let mut line = format!(
+            "{}{:<9}  {}",
+            "  ".repeat(depth),
+            node.task.status.label(),
+            node.title
+        );
+        if let Some(branch) = &node.branch {
+            line.push_str(&format!("  [{}]", branch));
+        }
+        let stacked_on = dependencies.iter().find_map(|dependency| {
+            dependency
+                .branch
+                .as_ref()
+                .filter(|branch| node.target_branches.contains(branch))
+        });
+        if let Some(upstream) = stacked_on {
+            line.push_str(&format!(" stacked on {}", upstream));
+        }
+        if dependencies.len() > 1 {
+            let titles: Vec<&str> = dependencies[1..].iter().map(|d| d.title.as_str()).collect();
+            line.push_str(&format!(" (also after {})", titles.join(", ")));
+        }
+        println!("{}", line);
+        for &child in children.get(&Some(i)).into_iter().flatten() {
+            print_node(nodes, index, children, child, depth + 1);
+        }
+    }
+
+    for &root in children.get(&None).into_iter().flatten() {
+        print_node(&nodes, &index, &children, root, 0);
+    }
+    Ok(())
+}
+
 fn start_server(
     command: &str,
     background: bool,
@@ -221,7 +1541,7 @@ fn start_server(
             cmd.env("BACKEND_PORT", port.to_string());
         }
 
-        let mut child = cmd
+        let child = cmd
             .stdin(Stdio::null())
             .stdout(log_file.try_clone()?)
             .stderr(log_file)
@@ -256,28 +1576,6 @@ fn start_server(
     }
 }
 
-fn parse_executor(input: &str) -> Result<vibe_kanban_cli::types::BaseCodingAgent> {
-    let normalized = input.trim().to_lowercase();
-    let executor = match normalized.as_str() {
-        "claude" | "claude-code" | "claude_code" => vibe_kanban_cli::types::BaseCodingAgent::ClaudeCode,
-        "amp" => vibe_kanban_cli::types::BaseCodingAgent::Amp,
-        "gemini" => vibe_kanban_cli::types::BaseCodingAgent::Gemini,
-        "codex" => vibe_kanban_cli::types::BaseCodingAgent::Codex,
-        "opencode" | "open-code" | "open_code" => vibe_kanban_cli::types::BaseCodingAgent::Opencode,
-        "cursor" | "cursor-agent" | "cursor_agent" => vibe_kanban_cli::types::BaseCodingAgent::CursorAgent,
-        "qwen" | "qwen-code" | "qwen_code" => vibe_kanban_cli::types::BaseCodingAgent::QwenCode,
-        "copilot" => vibe_kanban_cli::types::BaseCodingAgent::Copilot,
-        "droid" => vibe_kanban_cli::types::BaseCodingAgent::Droid,
-        _ => {
-            return Err(anyhow!(
-                "Unknown tool '{}'. Try codex, claude-code, cursor, gemini, opencode, qwen-code, amp, copilot, droid.",
-                input
-            ))
-        }
-    };
-    Ok(executor)
-}
-
 fn parse_status(input: &str) -> Result<vibe_kanban_cli::types::TaskStatus> {
     let normalized = input.trim().to_lowercase();
     let status = match normalized.as_str() {
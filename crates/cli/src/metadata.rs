@@ -0,0 +1,136 @@
+//! Task metadata: the free-form JSON object kept on each task. Read here for
+//! the keys the CLI knows about, the task's `links` and its `external_ids` in
+//! other trackers, and checked before the metadata popup saves it.
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Map, Value};
+
+/// Key of the task's links: a list of URLs or of `{"title", "url"}` objects, or
+/// an object of titles to URLs
+pub const LINKS_KEY: &str = "links";
+
+/// Key of the task's IDs in other trackers, an object of tracker names to IDs,
+/// e.g. `{"jira": "ENG-42"}`
+pub const EXTERNAL_IDS_KEY: &str = "external_ids";
+
+/// A link kept in a task's metadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataLink {
+    pub title: Option<String>,
+    pub url: String,
+}
+
+/// The metadata as an object; a task without metadata has an empty one.
+pub fn parse(text: Option<&str>) -> Result<Map<String, Value>> {
+    let text = match text {
+        Some(text) if !text.trim().is_empty() => text,
+        _ => return Ok(Map::new()),
+    };
+    match serde_json::from_str(text).context("Metadata is not JSON")? {
+        Value::Object(object) => Ok(object),
+        _ => bail!("Metadata is not a JSON object"),
+    }
+}
+
+/// The links of the metadata, in order; entries not shaped like links are left out.
+pub fn links(metadata: &Map<String, Value>) -> Vec<MetadataLink> {
+    let link = |title: Option<&str>, url: &Value| {
+        url.as_str().map(|url| MetadataLink {
+            title: title.map(str::to_string),
+            url: url.to_string(),
+        })
+    };
+    match metadata.get(LINKS_KEY) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                Value::Object(object) => link(
+                    object.get("title").and_then(Value::as_str),
+                    object.get("url")?,
+                ),
+                url => link(None, url),
+            })
+            .collect(),
+        Some(Value::Object(object)) => object
+            .iter()
+            .filter_map(|(title, url)| link(Some(title), url))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The tracker names and IDs of the metadata, in order.
+pub fn external_ids(metadata: &Map<String, Value>) -> Vec<(String, String)> {
+    let Some(Value::Object(ids)) = metadata.get(EXTERNAL_IDS_KEY) else {
+        return Vec::new();
+    };
+    ids.iter()
+        .filter_map(|(tracker, id)| match id {
+            Value::String(id) => Some((tracker.clone(), id.clone())),
+            Value::Number(id) => Some((tracker.clone(), id.to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A value typed in the metadata popup, or why it can't be saved.
+pub fn parse_value(key: &str, text: &str) -> Result<Value, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| {
+        format!(
+            "\"{}\" is not valid JSON: line {}, column {}: {}",
+            key,
+            e.line(),
+            e.column(),
+            e
+        )
+    })?;
+    check_well_known(key, &value)?;
+    Ok(value)
+}
+
+/// Check that the keys the CLI reads hold what it reads from them.
+fn check_well_known(key: &str, value: &Value) -> Result<(), String> {
+    let web_url = |url: &str| url.starts_with("http://") || url.starts_with("https://");
+    match key {
+        LINKS_KEY => {
+            let mut metadata = Map::new();
+            metadata.insert(key.to_string(), value.clone());
+            let found = links(&metadata);
+            let expected = match value {
+                Value::Array(items) => items.len(),
+                Value::Object(object) => object.len(),
+                _ => return Err("\"links\" must be a list or an object of URLs".to_string()),
+            };
+            if found.len() != expected {
+                return Err(
+                    "Each of the \"links\" must be a URL, or an object with a \"url\"".to_string(),
+                );
+            }
+            match found.iter().find(|link| !web_url(&link.url)) {
+                Some(link) => Err(format!("\"{}\" is not an http(s) URL", link.url)),
+                None => Ok(()),
+            }
+        }
+        EXTERNAL_IDS_KEY => match value {
+            Value::Object(ids) if ids.values().all(|id| id.is_string() || id.is_number()) => Ok(()),
+            _ => Err("\"external_ids\" must be an object of tracker names to IDs".to_string()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// A value on one line for the list of keys: strings, numbers and the like as
+/// they are, lists and objects by their size.
+pub fn summary(value: &Value) -> String {
+    match value {
+        Value::Array(items) => format!("[{} items]", items.len()),
+        Value::Object(object) => format!("{{{} keys}}", object.len()),
+        Value::String(text) => format!("{:?}", text),
+        value => value.to_string(),
+    }
+}
+
+/// A value as it is edited, pretty-printed over several lines.
+pub fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
@@ -0,0 +1,105 @@
+//! Status and error messages of this session. The status bar only has room for
+//! the latest; earlier ones stay on screen as toasts for a few seconds and can
+//! be reviewed in the notification center (`N`).
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+
+/// Messages kept for the notification center; older ones are dropped
+const MAX_NOTIFICATIONS: usize = 200;
+
+/// How long a status message stays in the status bar, and a replaced message
+/// stays on screen as a toast
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Toasts shown at once, newest first
+const MAX_TOASTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub message: String,
+    pub at: DateTime<Utc>,
+    /// When it was last raised, for expiry
+    pub raised_at: Instant,
+    /// How many times in a row the same message was raised
+    pub count: usize,
+}
+
+/// Ring buffer of notifications, oldest first
+#[derive(Debug, Default)]
+pub struct Notifications {
+    entries: VecDeque<Notification>,
+}
+
+impl Notifications {
+    /// Record a message; one repeating the latest only bumps its count.
+    pub fn push(&mut self, level: NotificationLevel, message: String) {
+        if let Some(latest) = self.entries.back_mut()
+            && latest.level == level
+            && latest.message == message
+        {
+            latest.count += 1;
+            latest.at = Utc::now();
+            latest.raised_at = Instant::now();
+            return;
+        }
+        if self.entries.len() == MAX_NOTIFICATIONS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Notification {
+            level,
+            message,
+            at: Utc::now(),
+            raised_at: Instant::now(),
+            count: 1,
+        });
+    }
+
+    pub fn latest(&self) -> Option<&Notification> {
+        self.entries.back()
+    }
+
+    /// Newest first
+    pub fn newest_first(&self) -> impl Iterator<Item = &Notification> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|n| n.level == NotificationLevel::Error)
+            .count()
+    }
+
+    /// Messages replaced in the status bar within the last few seconds, newest
+    /// first. The latest is left out: the status bar shows it.
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.newest_first()
+            .skip(1)
+            .take_while(|n| n.raised_at.elapsed() < TOAST_DURATION)
+            .take(MAX_TOASTS)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
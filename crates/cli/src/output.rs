@@ -0,0 +1,141 @@
+//! `--format`: print what a subcommand fetched or changed as text, JSON or CSV.
+
+use std::io::{self, Write};
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cli_args::OutputFormat;
+
+/// Print `value` in `format`, with `table` printing the human-readable form.
+pub fn print<T: Serialize + ?Sized>(
+    format: OutputFormat,
+    value: &T,
+    table: impl FnOnce(),
+) -> Result<()> {
+    match format {
+        OutputFormat::Table => table(),
+        OutputFormat::Json => write_stdout(&(serde_json::to_string_pretty(value)? + "\n"))?,
+        OutputFormat::Csv => write_stdout(&to_csv(&serde_json::to_value(value)?))?,
+    }
+    Ok(())
+}
+
+/// Print `value` as one line of a stream in `format`: a line of JSON, or a CSV
+/// row, after the header when `header` is set.
+pub fn print_line<T: Serialize>(
+    format: OutputFormat,
+    value: &T,
+    header: bool,
+    table: impl FnOnce(),
+) -> Result<()> {
+    match format {
+        OutputFormat::Table => table(),
+        OutputFormat::Json => write_stdout(&(serde_json::to_string(value)? + "\n"))?,
+        OutputFormat::Csv => {
+            let csv = to_csv(&serde_json::to_value(value)?);
+            let row = if header {
+                csv.as_str()
+            } else {
+                csv.split_once('\n').map_or("", |(_, row)| row)
+            };
+            write_stdout(row)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_stdout(text: &str) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    match stdout
+        .write_all(text.as_bytes())
+        .and_then(|()| stdout.flush())
+    {
+        // The reader, e.g. `head`, has all it wanted
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// `value` as CSV: a row per item of a list, or a single row for anything else.
+/// Nothing at all for an empty list or `null`, as there are no columns to name.
+fn to_csv(value: &Value) -> String {
+    let items = match value {
+        Value::Array(items) => items.as_slice(),
+        Value::Null => &[],
+        other => std::slice::from_ref(other),
+    };
+    let rows: Vec<Vec<(String, String)>> = items
+        .iter()
+        .map(|item| {
+            let mut row = Vec::new();
+            flatten("", item, &mut row);
+            row
+        })
+        .collect();
+
+    // Optional fields can be missing from some rows, so collect columns from all
+    let mut columns: Vec<&str> = Vec::new();
+    for (column, _) in rows.iter().flatten() {
+        if !columns.contains(&column.as_str()) {
+            columns.push(column);
+        }
+    }
+
+    let mut csv = String::new();
+    if columns.is_empty() {
+        return csv;
+    }
+    write_record(&mut csv, columns.iter().copied());
+    for row in &rows {
+        write_record(
+            &mut csv,
+            columns.iter().map(|column| {
+                row.iter()
+                    .find(|(name, _)| name == column)
+                    .map_or("", |(_, cell)| cell.as_str())
+            }),
+        );
+    }
+    csv
+}
+
+/// Add the cells of `value` to `row`. Objects become `parent.child` columns;
+/// lists stay whole, as JSON.
+fn flatten(prefix: &str, value: &Value, row: &mut Vec<(String, String)>) {
+    if let Value::Object(fields) = value {
+        for (key, value) in fields {
+            let column = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            flatten(&column, value, row);
+        }
+        return;
+    }
+    let column = if prefix.is_empty() { "value" } else { prefix };
+    let cell = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    row.push((column.to_string(), cell));
+}
+
+fn write_record<'a>(csv: &mut String, cells: impl Iterator<Item = &'a str>) {
+    for (index, cell) in cells.enumerate() {
+        if index > 0 {
+            csv.push(',');
+        }
+        if cell.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&cell.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(cell);
+        }
+    }
+    csv.push('\n');
+}
@@ -0,0 +1,205 @@
+//! `--print`: render one view of the interactive UI to stdout and exit.
+
+use std::io::{self, IsTerminal, Write};
+
+use anyhow::{Result, anyhow};
+use crossterm::{
+    queue,
+    style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    buffer::Buffer,
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+use vibe_kanban_cli::{App, VibeKanbanClient, app::View, ui};
+
+use crate::{
+    cli_args::PrintView,
+    resolve::{parse_uuid, resolve_project, resolve_task},
+};
+
+/// Size used when stdout is not a terminal and no size is given
+const DEFAULT_SIZE: (u16, u16) = (120, 40);
+
+pub struct PrintOptions {
+    pub view: PrintView,
+    pub project: Option<String>,
+    pub task: Option<String>,
+    pub workspace: Option<String>,
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+}
+
+pub async fn run(client: VibeKanbanClient, options: PrintOptions) -> Result<()> {
+    let mut app = App::new(client);
+    load_view(&mut app, &options).await?;
+    // Loading leaves progress messages behind; the snapshot shouldn't show them
+    app.clear_messages();
+
+    let stdout = io::stdout();
+    let tty_size = if stdout.is_terminal() {
+        crossterm::terminal::size().ok()
+    } else {
+        None
+    };
+    let (width, height) = tty_size.unwrap_or(DEFAULT_SIZE);
+    let width = options.width.unwrap_or(width);
+    let height = options.height.unwrap_or(height);
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| ui::render(frame, &app))?;
+
+    let color = stdout.is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let output = buffer_to_text(terminal.backend().buffer(), color)?;
+    let mut stdout = stdout.lock();
+    stdout.write_all(&output)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Put `app` in the requested view with its data loaded, as if navigated to by hand.
+async fn load_view(app: &mut App, options: &PrintOptions) -> Result<()> {
+    let client = app.client.clone();
+    let project = match &options.project {
+        Some(project_ref) => Some(resolve_project(&client, project_ref).await?),
+        None => None,
+    };
+
+    match options.view {
+        PrintView::Projects => {
+            app.view = View::Projects;
+            app.load_projects();
+            app.wait_for_requests().await?;
+        }
+        PrintView::Tasks => {
+            let project = project.ok_or_else(|| anyhow!("--print tasks requires --project"))?;
+            app.view = View::Tasks;
+            app.selected_project = Some(project);
+            app.load_tasks();
+            app.wait_for_requests().await?;
+        }
+        PrintView::Workspaces | PrintView::Workspace => {
+            let workspace = match &options.workspace {
+                Some(workspace_ref) => {
+                    Some(client.get_workspace(parse_uuid(workspace_ref)?).await?)
+                }
+                None => None,
+            };
+            let task_ref = match (&options.task, &workspace) {
+                (Some(task_ref), _) => task_ref.clone(),
+                (None, Some(workspace)) => workspace.task_id.to_string(),
+                (None, None) => {
+                    return Err(anyhow!(
+                        "Choose the task to print with --task or --workspace"
+                    ));
+                }
+            };
+            let (project, task) = resolve_task(&client, project, &task_ref).await?;
+
+            app.selected_project = Some(project);
+            app.selected_task = Some(task);
+            app.view = View::Workspaces;
+            app.load_workspaces();
+            app.wait_for_requests().await?;
+
+            if options.view == PrintView::Workspace {
+                let workspace = match workspace {
+                    Some(workspace) => workspace,
+                    None => app
+                        .workspaces
+                        .first()
+                        .cloned()
+                        .ok_or_else(|| anyhow!("Task has no workspaces"))?,
+                };
+                app.selected_workspace = Some(workspace);
+                app.view = View::WorkspaceDetail;
+                app.load_workspace_details();
+                app.wait_for_requests().await?;
+            }
+        }
+        PrintView::Queue => {
+            app.view = View::Queue;
+            app.load_attempt_queue();
+            app.wait_for_requests().await?;
+        }
+        PrintView::Failures => {
+            app.view = View::Failures;
+            app.failures_project_id = project.as_ref().map(|p| p.id);
+            app.selected_project = project;
+            app.load_failures();
+            app.wait_for_requests().await?;
+        }
+        PrintView::Help => app.view = View::Help,
+    }
+    Ok(())
+}
+
+/// Lines of the rendered buffer, with trailing blanks removed and styles written as
+/// ANSI escape codes when `color` is set.
+fn buffer_to_text(buffer: &Buffer, color: bool) -> Result<Vec<u8>> {
+    let area = buffer.area;
+    let mut out = Vec::new();
+    for y in area.top()..area.bottom() {
+        let mut line = Vec::new();
+        // Byte length of the line up to its last non-blank cell
+        let mut end = 0;
+        let mut style = Style::default();
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            // Cells after a wide character only pad it out
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let cell = &buffer[(x, y)];
+            let symbol = cell.symbol();
+            skip = Span::raw(symbol).width().saturating_sub(1);
+
+            if color && cell.style() != style {
+                write_style(&mut line, cell.style())?;
+                style = cell.style();
+            }
+            line.extend_from_slice(symbol.as_bytes());
+            if !symbol.trim().is_empty() || (color && cell.bg != Color::Reset) {
+                end = line.len();
+            }
+        }
+        line.truncate(end);
+        if color {
+            queue!(line, SetAttribute(Attribute::Reset), ResetColor)?;
+        }
+        out.extend_from_slice(&line);
+        out.push(b'\n');
+    }
+    // Drop blank rows at the bottom
+    while out.ends_with(b"\n\n") {
+        out.pop();
+    }
+    Ok(out)
+}
+
+fn write_style(out: &mut Vec<u8>, style: Style) -> io::Result<()> {
+    queue!(
+        out,
+        SetAttribute(Attribute::Reset),
+        SetForegroundColor(style.fg.unwrap_or(Color::Reset).into()),
+        SetBackgroundColor(style.bg.unwrap_or(Color::Reset).into()),
+    )?;
+    let modifiers = [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ];
+    for (modifier, attribute) in modifiers {
+        if style.add_modifier.contains(modifier) {
+            queue!(out, SetAttribute(attribute))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,191 @@
+//! A task written up as a prompt for coding agents and chat tools outside Vibe
+//! Kanban: its description, the repositories to work in, a checklist to finish
+//! against, and the tasks and attempts around it, with the notes kept on those
+//! attempts. Copied with `y` in the task
+//! detail view, or printed by `vibe-kanban-cli prompt`.
+
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::{
+    api::VibeKanbanClient,
+    types::{Repo, Task, Workspace, WorkspaceNote},
+    utils::task_slug,
+};
+
+/// Attempts listed in the prompt, newest first
+const RECENT_ATTEMPTS: usize = 5;
+
+/// Used when the description has no checklist of its own
+const DEFAULT_CHECKLIST: [&str; 3] = [
+    "The change does what the description asks, and nothing unrelated",
+    "The project builds and its existing tests pass",
+    "New behaviour is covered by tests",
+];
+
+/// Everything the prompt is written from
+pub struct TaskPrompt {
+    pub task: Task,
+    pub repos: Vec<Repo>,
+    pub workspaces: Vec<Workspace>,
+    pub parent: Option<Task>,
+    pub children: Vec<Task>,
+    /// Notes kept on the attempts, e.g. manual test results
+    pub notes: Vec<WorkspaceNote>,
+}
+
+impl TaskPrompt {
+    /// Fetch the repositories, attempts and related tasks of `task`.
+    pub async fn load(client: &VibeKanbanClient, task: Task) -> Result<Self> {
+        let repos = client.get_project_repositories(task.project_id).await?;
+        let workspaces = client.list_workspaces(Some(task.id)).await?;
+        let parent = match task.parent_workspace_id {
+            Some(workspace_id) => {
+                let parent_workspace = client.get_workspace(workspace_id).await?;
+                Some(client.get_task(parent_workspace.task_id).await?)
+            }
+            None => None,
+        };
+        let mut children = Vec::new();
+        let mut notes = Vec::new();
+        for workspace in &workspaces {
+            let relationships = client.get_workspace_relationships(workspace.id).await?;
+            children.extend(relationships.children);
+            // Older servers keep no notes
+            if let Ok(Some(note)) = client.get_workspace_note(workspace.id).await {
+                notes.push(note);
+            }
+        }
+        Ok(Self {
+            task,
+            repos,
+            workspaces,
+            parent,
+            children,
+            notes,
+        })
+    }
+
+    /// Name to save the prompt under, e.g. `add-login-page.prompt.md`.
+    pub fn file_name(&self) -> String {
+        format!("{}.prompt.md", task_slug(&self.task.title))
+    }
+
+    /// The prompt, as Markdown.
+    pub fn render(&self) -> String {
+        let task = &self.task;
+        let mut out = format!("# {}\n\n## Description\n\n", task.title);
+        match task.description.as_deref().map(str::trim) {
+            Some(description) if !description.is_empty() => {
+                out.push_str(description);
+                out.push('\n');
+            }
+            _ => out.push_str("No description.\n"),
+        }
+
+        if !self.repos.is_empty() {
+            out.push_str("\n## Repositories\n\n");
+            for repo in &self.repos {
+                let _ = writeln!(out, "- {}: `{}`", repo.display_name, repo.path);
+            }
+        }
+        // The worktree of the newest attempt has the work done so far
+        if let Some(workspace) = self.recent_attempts().next()
+            && let Some(worktree) = &workspace.container_ref
+        {
+            let _ = writeln!(
+                out,
+                "\nThe latest attempt is in `{}`, on branch `{}`.",
+                worktree, workspace.branch
+            );
+        }
+
+        out.push_str("\n## Acceptance checklist\n\n");
+        let checklist = checklist(task.description.as_deref().unwrap_or_default());
+        if checklist.is_empty() {
+            for item in DEFAULT_CHECKLIST {
+                let _ = writeln!(out, "- [ ] {}", item);
+            }
+        } else {
+            for item in checklist {
+                let _ = writeln!(out, "{}", item);
+            }
+        }
+
+        if self.parent.is_some() || !self.children.is_empty() {
+            out.push_str("\n## Related tasks\n\n");
+            if let Some(parent) = &self.parent {
+                let _ = writeln!(
+                    out,
+                    "- Created from: {} ({})",
+                    parent.title,
+                    parent.status.display_name()
+                );
+            }
+            for child in &self.children {
+                let _ = writeln!(
+                    out,
+                    "- Follow-up: {} ({})",
+                    child.title,
+                    child.status.display_name()
+                );
+            }
+        }
+
+        let attempts: Vec<_> = self.recent_attempts().take(RECENT_ATTEMPTS).collect();
+        if !attempts.is_empty() {
+            out.push_str("\n## Recent attempts\n\n");
+            for workspace in attempts {
+                let name = workspace
+                    .name
+                    .as_deref()
+                    .map(|name| format!(" ({})", name))
+                    .unwrap_or_default();
+                let archived = if workspace.archived { ", archived" } else { "" };
+                let _ = writeln!(
+                    out,
+                    "- `{}`{}, started {}{}",
+                    workspace.branch, name, workspace.created_at, archived
+                );
+                let note = self.notes.iter().find(|n| n.workspace_id == workspace.id);
+                if let Some(note) = note {
+                    out.push_str("\n  Notes:\n\n");
+                    for line in note.body.lines() {
+                        let _ = writeln!(out, "  > {}", line);
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+
+    fn recent_attempts(&self) -> impl Iterator<Item = &Workspace> {
+        let mut workspaces: Vec<_> = self.workspaces.iter().collect();
+        workspaces.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        workspaces.into_iter()
+    }
+}
+
+/// The Markdown task list items (`- [ ] ...`, `* [x] ...`) of a description.
+fn checklist(description: &str) -> Vec<String> {
+    description
+        .lines()
+        .filter_map(|line| {
+            let item = line.trim().strip_prefix(['-', '*'])?.trim_start();
+            let (done, text) = if let Some(text) = item.strip_prefix("[ ]") {
+                (false, text)
+            } else if let Some(text) = item
+                .strip_prefix("[x]")
+                .or_else(|| item.strip_prefix("[X]"))
+            {
+                (true, text)
+            } else {
+                return None;
+            };
+            let mark = if done { "x" } else { " " };
+            Some(format!("- [{}] {}", mark, text.trim()))
+        })
+        .collect()
+}
@@ -3,7 +3,13 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::VibeKanbanClient;
-use vibe_kanban_cli::types::{GitBranch, Project, Repo, WorkspaceRepoInput};
+use vibe_kanban_cli::{
+    types::{
+        GitBranch, PlanTemplate, Project, Repo, RepoWithTargetBranch, TaskWithAttemptStatus,
+        WorkspaceRepoInput,
+    },
+    utils::task_slug,
+};
 
 pub fn parse_uuid(input: &str) -> Result<Uuid> {
     Uuid::parse_str(input).context("Invalid UUID")
@@ -29,6 +35,84 @@ pub async fn resolve_project(client: &VibeKanbanClient, project_ref: &str) -> Re
     ))
 }
 
+/// Find a task by ID, or by slug within `project`.
+pub async fn resolve_task(
+    client: &VibeKanbanClient,
+    project: Option<Project>,
+    task_ref: &str,
+) -> Result<(Project, TaskWithAttemptStatus)> {
+    let task_id = parse_uuid(task_ref).ok();
+    let project = match (project, task_id) {
+        (Some(project), _) => project,
+        (None, Some(task_id)) => {
+            let task = client.get_task(task_id).await?;
+            client.get_project(task.project_id).await?
+        }
+        (None, None) => return Err(anyhow!("Use a task ID, or --project with a task slug")),
+    };
+
+    let tasks = client.list_tasks(project.id).await?;
+    let task = tasks
+        .into_iter()
+        .find(|t| match task_id {
+            Some(id) => t.task.id == id,
+            None => task_slug(&t.task.title) == task_ref,
+        })
+        .with_context(|| format!("Task '{}' not found in {}", task_ref, project.name))?;
+    Ok((project, task))
+}
+
+/// Resolve an agent profile by ID or name. IDs are taken as given, so disabled
+/// profiles, which the server does not list, can still be referred to.
+pub async fn resolve_agent_profile_id(
+    client: &VibeKanbanClient,
+    profile_ref: &str,
+) -> Result<Uuid> {
+    if let Ok(id) = Uuid::parse_str(profile_ref) {
+        return Ok(id);
+    }
+
+    let lower = profile_ref.to_lowercase();
+    client
+        .list_agent_profiles()
+        .await?
+        .into_iter()
+        .find(|p| p.name.to_lowercase() == lower)
+        .map(|p| p.id)
+        .ok_or_else(|| {
+            anyhow!(
+                "Agent profile '{}' not found. Use a profile ID or exact profile name.",
+                profile_ref
+            )
+        })
+}
+
+pub async fn resolve_plan_template(
+    client: &VibeKanbanClient,
+    template_ref: &str,
+) -> Result<PlanTemplate> {
+    let templates = client.list_plan_templates().await?;
+    if let Ok(id) = Uuid::parse_str(template_ref) {
+        if let Some(template) = templates.into_iter().find(|t| t.id == id) {
+            return Ok(template);
+        }
+        return Err(anyhow!("Plan template ID not found: {}", template_ref));
+    }
+
+    let lower = template_ref.to_lowercase();
+    if let Some(template) = templates
+        .into_iter()
+        .find(|t| t.name.to_lowercase() == lower)
+    {
+        return Ok(template);
+    }
+
+    Err(anyhow!(
+        "Plan template '{}' not found. Use a template ID or exact template name.",
+        template_ref
+    ))
+}
+
 pub async fn resolve_repo_inputs(
     client: &VibeKanbanClient,
     project_id: Uuid,
@@ -80,6 +164,74 @@ pub async fn resolve_repo_inputs(
     Ok(inputs)
 }
 
+/// Resolve one of a project's repos; the reference may be left out if there is only one.
+pub async fn resolve_project_repo(
+    client: &VibeKanbanClient,
+    project_id: Uuid,
+    repo_ref: Option<&str>,
+) -> Result<Repo> {
+    let repos = client.get_project_repositories(project_id).await?;
+    let repo = match repo_ref {
+        Some(repo_ref) => find_repo(&repos, repo_ref).ok_or_else(|| {
+            anyhow!(
+                "Repo '{}' not found for project. Use repo name, display name, or ID.",
+                repo_ref
+            )
+        })?,
+        None if repos.len() == 1 => &repos[0],
+        None => {
+            let repo_list = repos
+                .iter()
+                .map(|r| format!("{} ({})", r.display_name, r.id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(anyhow!(
+                "Project has multiple repos. Specify --repo. Available: {}",
+                repo_list
+            ));
+        }
+    };
+    Ok(repo.clone())
+}
+
+/// Resolve one of a workspace's repos; the reference may be left out if there is only one.
+pub async fn resolve_workspace_repo(
+    client: &VibeKanbanClient,
+    workspace_id: Uuid,
+    repo_ref: Option<&str>,
+) -> Result<RepoWithTargetBranch> {
+    let mut repos = client.get_workspace_repos(workspace_id).await?;
+    let index = match repo_ref {
+        Some(repo_ref) => {
+            let plain: Vec<Repo> = repos.iter().map(|r| r.repo.clone()).collect();
+            let repo = find_repo(&plain, repo_ref).ok_or_else(|| {
+                anyhow!(
+                    "Repo '{}' not found for workspace. Use repo name, display name, or ID.",
+                    repo_ref
+                )
+            })?;
+            // Same order as `repos`
+            plain
+                .iter()
+                .position(|r| r.id == repo.id)
+                .unwrap_or_default()
+        }
+        None if repos.len() == 1 => 0,
+        None => {
+            let repo_list = repos
+                .iter()
+                .map(|r| format!("{} ({})", r.repo.display_name, r.repo.id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(anyhow!(
+                "Workspace has multiple repos. Specify --repo. Available: {}",
+                repo_list
+            ));
+        }
+    };
+    Ok(repos.swap_remove(index))
+}
+
 pub fn split_repo_branch(input: &str) -> (String, Option<String>) {
     if let Some(idx) = input.rfind('@') {
         let (left, right) = input.split_at(idx);
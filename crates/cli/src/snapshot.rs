@@ -0,0 +1,491 @@
+//! Named snapshots of a project's board, kept locally, and what changed on the
+//! board since one was taken.
+//!
+//! `vibe-kanban-cli snapshot save monday --project web` records the status of
+//! every task; `snapshot diff monday --project web` later lists the tasks added,
+//! moved, completed and removed since, e.g. for a sprint review. Snapshots are
+//! JSON files under the user's local data directory, one directory per project.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    datetime::TimeZone,
+    types::{Project, TaskStatus, TaskWithAttemptStatus},
+};
+
+/// Overrides the directory snapshots are kept in
+pub const SNAPSHOT_DIR_ENV: &str = "VIBE_KANBAN_CLI_SNAPSHOTS";
+
+/// The columns of the board, in order
+const COLUMNS: [TaskStatus; 5] = [
+    TaskStatus::Todo,
+    TaskStatus::Inprogress,
+    TaskStatus::Inreview,
+    TaskStatus::Done,
+    TaskStatus::Cancelled,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardSnapshot {
+    pub name: String,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub taken_at: DateTime<Utc>,
+    pub tasks: Vec<SnapshotTask>,
+}
+
+/// A task as it stood when the snapshot was taken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotTask {
+    pub id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+}
+
+impl SnapshotTask {
+    pub fn from_tasks(tasks: &[TaskWithAttemptStatus]) -> Vec<Self> {
+        tasks
+            .iter()
+            .map(|t| SnapshotTask {
+                id: t.task.id,
+                title: t.task.title.clone(),
+                status: t.task.status,
+            })
+            .collect()
+    }
+}
+
+impl BoardSnapshot {
+    /// The board of `project` as it is now.
+    pub fn capture(name: &str, project: &Project, tasks: &[TaskWithAttemptStatus]) -> Self {
+        Self {
+            name: name.to_string(),
+            project_id: project.id,
+            project_name: project.name.clone(),
+            taken_at: Utc::now(),
+            tasks: SnapshotTask::from_tasks(tasks),
+        }
+    }
+
+    /// Number of tasks in each column, in board order.
+    pub fn column_counts(&self) -> Vec<(TaskStatus, usize)> {
+        column_counts(&self.tasks)
+    }
+}
+
+fn column_counts(tasks: &[SnapshotTask]) -> Vec<(TaskStatus, usize)> {
+    COLUMNS
+        .iter()
+        .map(|&status| (status, tasks.iter().filter(|t| t.status == status).count()))
+        .collect()
+}
+
+/// How a task changed since the snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Moved to done, or created done
+    Completed,
+    Added,
+    Moved,
+    /// Deleted from the board
+    Removed,
+}
+
+impl ChangeKind {
+    pub const ALL: [ChangeKind; 4] = [
+        ChangeKind::Completed,
+        ChangeKind::Added,
+        ChangeKind::Moved,
+        ChangeKind::Removed,
+    ];
+
+    pub fn heading(self) -> &'static str {
+        match self {
+            ChangeKind::Completed => "Completed",
+            ChangeKind::Added => "Added",
+            ChangeKind::Moved => "Moved",
+            ChangeKind::Removed => "Removed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardChange {
+    pub change: ChangeKind,
+    pub task_id: Uuid,
+    pub title: String,
+    /// Status in the snapshot; none for added tasks
+    pub from: Option<TaskStatus>,
+    /// Status now; none for removed tasks
+    pub to: Option<TaskStatus>,
+}
+
+/// What changed on a board since a snapshot
+#[derive(Debug, Clone)]
+pub struct BoardDiff {
+    pub snapshot: BoardSnapshot,
+    /// Completed first, then added, moved and removed, each in board order
+    pub changes: Vec<BoardChange>,
+    /// Number of tasks in each column now
+    pub columns_now: Vec<(TaskStatus, usize)>,
+}
+
+impl BoardDiff {
+    pub fn between(snapshot: BoardSnapshot, tasks: &[TaskWithAttemptStatus]) -> Self {
+        let now = SnapshotTask::from_tasks(tasks);
+        let then: HashMap<Uuid, &SnapshotTask> = snapshot.tasks.iter().map(|t| (t.id, t)).collect();
+
+        let mut changes: Vec<BoardChange> = now
+            .iter()
+            .filter_map(|task| {
+                let from = then.get(&task.id).map(|t| t.status);
+                let change = match from {
+                    Some(from) if from == task.status => return None,
+                    _ if task.status == TaskStatus::Done => ChangeKind::Completed,
+                    Some(_) => ChangeKind::Moved,
+                    None => ChangeKind::Added,
+                };
+                Some(BoardChange {
+                    change,
+                    task_id: task.id,
+                    title: task.title.clone(),
+                    from,
+                    to: Some(task.status),
+                })
+            })
+            .collect();
+        changes.extend(
+            snapshot
+                .tasks
+                .iter()
+                .filter(|t| !now.iter().any(|n| n.id == t.id))
+                .map(|t| BoardChange {
+                    change: ChangeKind::Removed,
+                    task_id: t.id,
+                    title: t.title.clone(),
+                    from: Some(t.status),
+                    to: None,
+                }),
+        );
+        let column = |status: Option<TaskStatus>| {
+            status.map_or(COLUMNS.len(), |s| {
+                COLUMNS.iter().position(|c| *c == s).unwrap_or(0)
+            })
+        };
+        changes.sort_by_key(|c| (c.change as u8, column(c.to.or(c.from)), c.title.clone()));
+
+        Self {
+            snapshot,
+            changes,
+            columns_now: column_counts(&now),
+        }
+    }
+
+    /// The changes of one kind.
+    pub fn of_kind(&self, kind: ChangeKind) -> impl Iterator<Item = &BoardChange> {
+        self.changes.iter().filter(move |c| c.change == kind)
+    }
+
+    /// The diff as a Markdown report, e.g. for sprint review notes, with times
+    /// in `zone`.
+    pub fn to_markdown(&self, zone: TimeZone) -> String {
+        let snapshot = &self.snapshot;
+        let mut out = format!(
+            "# {}: changes since {}\n\nSnapshot `{}` taken {}, compared {}.\n\n",
+            snapshot.project_name,
+            snapshot.name,
+            snapshot.name,
+            zone.format(snapshot.taken_at, "%Y-%m-%d %H:%M"),
+            zone.format(Utc::now(), "%Y-%m-%d %H:%M"),
+        );
+
+        out.push_str("| Column | Then | Now |\n|---|---:|---:|\n");
+        for ((status, then), (_, now)) in snapshot.column_counts().iter().zip(&self.columns_now) {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                status.display_name(),
+                then,
+                now
+            ));
+        }
+
+        if self.changes.is_empty() {
+            out.push_str("\nNo changes.\n");
+        }
+        for kind in ChangeKind::ALL {
+            let changes: Vec<_> = self.of_kind(kind).collect();
+            if changes.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {} ({})\n\n", kind.heading(), changes.len()));
+            for change in changes {
+                out.push_str(&format!("- {}{}\n", change.title, change.transition()));
+            }
+        }
+        out
+    }
+}
+
+impl BoardChange {
+    /// " (In Progress → Done)", or the one status there is for added and
+    /// removed tasks.
+    pub fn transition(&self) -> String {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => {
+                format!(" ({} → {})", from.display_name(), to.display_name())
+            }
+            (None, Some(status)) | (Some(status), None) => {
+                format!(" ({})", status.display_name())
+            }
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// Snapshots saved on this machine
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    dir: Option<PathBuf>,
+}
+
+impl SnapshotStore {
+    /// Snapshots in the user's local data directory, or `$VIBE_KANBAN_CLI_SNAPSHOTS` if set.
+    pub fn open_default() -> Self {
+        let dir = std::env::var_os(SNAPSHOT_DIR_ENV)
+            .map(PathBuf::from)
+            .or_else(|| {
+                dirs::data_local_dir().map(|dir| dir.join("vibe-kanban-cli").join("snapshots"))
+            });
+        Self { dir }
+    }
+
+    fn project_dir(&self, project_id: Uuid) -> Result<PathBuf> {
+        let dir = self
+            .dir
+            .as_ref()
+            .context("No local data directory to keep snapshots in")?;
+        Ok(dir.join(project_id.to_string()))
+    }
+
+    fn path(&self, project_id: Uuid, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            bail!(
+                "Invalid snapshot name '{}': use letters, digits, '-', '_' and '.'",
+                name
+            );
+        }
+        Ok(self.project_dir(project_id)?.join(format!("{}.json", name)))
+    }
+
+    /// Save `snapshot`, replacing one of the same name. Returns whether it did.
+    pub fn save(&self, snapshot: &BoardSnapshot) -> Result<bool> {
+        let path = self.path(snapshot.project_id, &snapshot.name)?;
+        let replaced = path.exists();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(snapshot)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(replaced)
+    }
+
+    pub fn load(&self, project_id: Uuid, name: &str) -> Result<BoardSnapshot> {
+        let path = self.path(project_id, name)?;
+        match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                bail!("No snapshot named '{}' for this project", name)
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// The project's snapshots, oldest first. Unreadable files are skipped.
+    pub fn list(&self, project_id: Uuid) -> Result<Vec<BoardSnapshot>> {
+        let dir = self.project_dir(project_id)?;
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+        };
+        let mut snapshots: Vec<BoardSnapshot> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| read_snapshot(&path))
+            .collect();
+        snapshots.sort_by_key(|s| s.taken_at);
+        Ok(snapshots)
+    }
+
+    pub fn delete(&self, project_id: Uuid, name: &str) -> Result<()> {
+        let path = self.path(project_id, name)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                bail!("No snapshot named '{}' for this project", name)
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to delete {}", path.display())),
+        }
+    }
+}
+
+fn read_snapshot(path: &Path) -> Option<BoardSnapshot> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Task;
+
+    fn task(title: &str, status: TaskStatus) -> TaskWithAttemptStatus {
+        TaskWithAttemptStatus {
+            has_in_progress_attempt: false,
+            last_attempt_failed: false,
+            executor: String::new(),
+            task: Task {
+                id: Uuid::new_v4(),
+                project_id: Uuid::nil(),
+                title: title.to_string(),
+                description: None,
+                status,
+                parent_workspace_id: None,
+                is_epic: false,
+                complexity: None,
+                metadata: None,
+                board_position: None,
+                created_at: String::new(),
+                updated_at: String::new(),
+            },
+        }
+    }
+
+    fn moved(task: &TaskWithAttemptStatus, status: TaskStatus) -> TaskWithAttemptStatus {
+        let mut task = task.clone();
+        task.task.status = status;
+        task
+    }
+
+    fn snapshot(tasks: &[TaskWithAttemptStatus]) -> BoardSnapshot {
+        BoardSnapshot {
+            name: "monday".to_string(),
+            project_id: Uuid::nil(),
+            project_name: "Web".to_string(),
+            taken_at: Utc::now(),
+            tasks: SnapshotTask::from_tasks(tasks),
+        }
+    }
+
+    fn summary(diff: &BoardDiff) -> Vec<(ChangeKind, &str)> {
+        diff.changes
+            .iter()
+            .map(|c| (c.change, c.title.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_board_has_no_changes() {
+        let tasks = [
+            task("Login", TaskStatus::Todo),
+            task("Signup", TaskStatus::Done),
+        ];
+        let diff = BoardDiff::between(snapshot(&tasks), &tasks);
+
+        assert!(diff.changes.is_empty());
+        assert_eq!(diff.columns_now, diff.snapshot.column_counts());
+        assert!(diff.to_markdown(TimeZone::Utc).contains("\nNo changes.\n"));
+    }
+
+    #[test]
+    fn changes_are_classified_and_ordered() {
+        let login = task("Login", TaskStatus::Inprogress);
+        let signup = task("Signup", TaskStatus::Todo);
+        let search = task("Search", TaskStatus::Todo);
+        let logout = task("Logout", TaskStatus::Inreview);
+        let then = snapshot(&[login.clone(), signup.clone(), search.clone(), logout]);
+
+        let now = [
+            moved(&login, TaskStatus::Done),
+            moved(&signup, TaskStatus::Inreview),
+            moved(&search, TaskStatus::Cancelled),
+            task("Billing", TaskStatus::Todo),
+            task("Hotfix", TaskStatus::Done),
+        ];
+        let diff = BoardDiff::between(then, &now);
+
+        assert_eq!(
+            summary(&diff),
+            [
+                (ChangeKind::Completed, "Hotfix"),
+                (ChangeKind::Completed, "Login"),
+                (ChangeKind::Added, "Billing"),
+                (ChangeKind::Moved, "Signup"),
+                (ChangeKind::Moved, "Search"),
+                (ChangeKind::Removed, "Logout"),
+            ]
+        );
+        let completed: Vec<_> = diff.of_kind(ChangeKind::Completed).collect();
+        assert_eq!(completed[0].from, None);
+        assert_eq!(completed[1].from, Some(TaskStatus::Inprogress));
+        assert_eq!(diff.changes[5].to, None);
+    }
+
+    #[test]
+    fn column_counts_follow_the_board() {
+        let login = task("Login", TaskStatus::Todo);
+        let then = snapshot(&[login.clone(), task("Signup", TaskStatus::Todo)]);
+        let diff = BoardDiff::between(then, &[moved(&login, TaskStatus::Done)]);
+
+        assert_eq!(
+            diff.snapshot.column_counts(),
+            [
+                (TaskStatus::Todo, 2),
+                (TaskStatus::Inprogress, 0),
+                (TaskStatus::Inreview, 0),
+                (TaskStatus::Done, 0),
+                (TaskStatus::Cancelled, 0),
+            ]
+        );
+        assert_eq!(diff.columns_now[0], (TaskStatus::Todo, 0));
+        assert_eq!(diff.columns_now[3], (TaskStatus::Done, 1));
+    }
+
+    #[test]
+    fn markdown_lists_each_kind_with_its_transition() {
+        let login = task("Login", TaskStatus::Inprogress);
+        let signup = task("Signup", TaskStatus::Todo);
+        let then = snapshot(&[login.clone(), signup]);
+        let now = [
+            moved(&login, TaskStatus::Done),
+            task("Billing", TaskStatus::Todo),
+        ];
+        let markdown = BoardDiff::between(then, &now).to_markdown(TimeZone::Utc);
+
+        assert!(markdown.starts_with("# Web: changes since monday\n"));
+        assert!(markdown.contains("| To Do | 1 | 1 |\n| In Progress | 1 | 0 |\n"));
+        assert!(markdown.contains("## Completed (1)\n\n- Login (In Progress → Done)\n"));
+        assert!(markdown.contains("## Added (1)\n\n- Billing (To Do)\n"));
+        assert!(markdown.contains("## Removed (1)\n\n- Signup (To Do)\n"));
+        assert!(!markdown.contains("## Moved"));
+        assert!(!markdown.contains("No changes."));
+    }
+}
@@ -0,0 +1,87 @@
+//! Startup profiling of the interactive UI.
+//!
+//! The time from launch to each startup phase is recorded once and shown in the
+//! F12 debug overlay. With `--fast-start`, loads the first screen doesn't need
+//! (the event stream, the first health check and the board's repositories) wait
+//! until the first data is on screen.
+
+use std::time::{Duration, Instant};
+
+/// Launch to first data that still feels instant against a local server
+pub const STARTUP_BUDGET: Duration = Duration::from_millis(200);
+
+/// A point in the startup of the interactive UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupPhase {
+    /// The config file has been read
+    Config,
+    /// The terminal has been queried and set up for the UI
+    Terminal,
+    /// The first frame has been drawn, with or without data
+    FirstFrame,
+    /// The first data from the server has been applied
+    FirstData,
+    /// The server has answered, or failed, its first health check
+    HealthCheck,
+}
+
+impl StartupPhase {
+    pub fn label(self) -> &'static str {
+        match self {
+            StartupPhase::Config => "config",
+            StartupPhase::Terminal => "terminal",
+            StartupPhase::FirstFrame => "first frame",
+            StartupPhase::FirstData => "first data",
+            StartupPhase::HealthCheck => "health check",
+        }
+    }
+}
+
+/// When the process started, and how long it took to reach each phase so far
+#[derive(Debug, Clone)]
+pub struct Startup {
+    started: Instant,
+    /// Whether `--fast-start` defers the loads the first screen doesn't need
+    pub fast: bool,
+    phases: Vec<(StartupPhase, Duration)>,
+}
+
+impl Startup {
+    /// Start the clock; call as early in `main` as possible.
+    pub fn begin() -> Self {
+        Self {
+            started: Instant::now(),
+            fast: false,
+            phases: Vec::new(),
+        }
+    }
+
+    /// Note that `phase` has been reached, unless it already was.
+    pub fn record(&mut self, phase: StartupPhase) {
+        if self.get(phase).is_some() {
+            return;
+        }
+        let elapsed = self.started.elapsed();
+        tracing::debug!("startup: {} after {:?}", phase.label(), elapsed);
+        self.phases.push((phase, elapsed));
+    }
+
+    /// Time from launch to `phase`, if it has been reached
+    pub fn get(&self, phase: StartupPhase) -> Option<Duration> {
+        self.phases
+            .iter()
+            .find(|(reached, _)| *reached == phase)
+            .map(|(_, elapsed)| *elapsed)
+    }
+
+    /// The phases reached so far, in the order they were reached
+    pub fn phases(&self) -> &[(StartupPhase, Duration)] {
+        &self.phases
+    }
+}
+
+impl Default for Startup {
+    fn default() -> Self {
+        Self::begin()
+    }
+}
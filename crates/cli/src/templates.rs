@@ -0,0 +1,80 @@
+//! Reusable follow-up prompts, picked with `t` in the follow-up composer.
+//!
+//! A template's prompt can name parts of the current context in braces, filled
+//! in when the template is used: `{task}`, `{description}`, `{branch}` and
+//! `{comments}` (the review notes on the workspace's diff). Other text in braces
+//! is left alone.
+
+use serde::Deserialize;
+
+/// Placeholders a template can use
+pub const PLACEHOLDERS: [&str; 4] = ["task", "description", "branch", "comments"];
+
+/// Used when the config file has no `[[follow_up_templates]]`
+const DEFAULT_TEMPLATES: [(&str, &str); 4] = [
+    ("Run the tests", "Run the tests and fix any failures."),
+    (
+        "Address review comments",
+        "Address these review comments:\n\n{comments}",
+    ),
+    (
+        "Check against the task",
+        "Re-read the task and make sure everything it asks for is done:\n\n{task}\n\n{description}",
+    ),
+    (
+        "Explain the changes",
+        "Summarise what you changed on {branch} and why, file by file.",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FollowUpTemplate {
+    pub name: String,
+    pub prompt: String,
+}
+
+impl FollowUpTemplate {
+    pub fn defaults() -> Vec<Self> {
+        DEFAULT_TEMPLATES
+            .iter()
+            .map(|(name, prompt)| Self {
+                name: name.to_string(),
+                prompt: prompt.to_string(),
+            })
+            .collect()
+    }
+
+    /// Whether the prompt uses `placeholder`.
+    pub fn uses(&self, placeholder: &str) -> bool {
+        self.prompt.contains(&format!("{{{}}}", placeholder))
+    }
+
+    /// The prompt with its placeholders filled in by `value`, and the
+    /// placeholders it used that had nothing to fill in.
+    pub fn expand(&self, value: impl Fn(&str) -> Option<String>) -> (String, Vec<&'static str>) {
+        let mut out = String::new();
+        let mut missing = Vec::new();
+        let mut rest = self.prompt.as_str();
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let placeholder = PLACEHOLDERS
+                .into_iter()
+                .find(|p| rest[1..].starts_with(p) && rest[1 + p.len()..].starts_with('}'));
+            let Some(placeholder) = placeholder else {
+                out.push('{');
+                rest = &rest[1..];
+                continue;
+            };
+            let text = value(placeholder).unwrap_or_default();
+            if text.trim().is_empty() && !missing.contains(&placeholder) {
+                missing.push(placeholder);
+            }
+            out.push_str(text.trim());
+            rest = &rest[placeholder.len() + 2..];
+        }
+        out.push_str(rest);
+        (out.trim().to_string(), missing)
+    }
+}
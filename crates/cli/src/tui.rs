@@ -0,0 +1,1467 @@
+//! Interactive terminal UI: terminal setup, event loop and key handling.
+
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use crossterm::{
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers, KeyboardEnhancementFlags, MouseButton, MouseEvent, MouseEventKind,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute,
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
+};
+use ratatui::{Terminal, backend::CrosstermBackend};
+use vibe_kanban_cli::{
+    App, VibeKanbanClient,
+    app::{
+        BoardZoom, ErrorDetail, FollowUpField, InputMode, NewTaskField, PullRequestField,
+        RetentionField, RetryableAction, TaskFormField, View,
+    },
+    clipboard,
+    config::Config,
+    editor,
+    keymap::{self, Action},
+    links::LinkTarget,
+    startup::{Startup, StartupPhase},
+    ui,
+};
+
+type Tui = Terminal<CrosstermBackend<io::Stdout>>;
+
+/// How long to wait for input before redrawing
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Log lines scrolled by Page Up and Page Down
+const LOG_PAGE: isize = 20;
+
+/// Log lines scrolled by a turn of the mouse wheel
+const LOG_WHEEL: isize = 3;
+
+/// Run the interactive UI until the user quits, starting at `link` if given,
+/// and in its session logs with `logs` when it is a workspace.
+pub async fn run(
+    client: VibeKanbanClient,
+    config: Config,
+    link: Option<LinkTarget>,
+    logs: bool,
+    mut startup: Startup,
+) -> Result<()> {
+    // Detected once rather than on every frame
+    let config = Config {
+        compat: Some(config.compat()),
+        ..config
+    };
+    // Lets terminals that support it report Shift+Enter apart from Enter
+    let enhanced_keys = supports_keyboard_enhancement().unwrap_or(false);
+    let mouse = config.mouse();
+    let mut terminal = setup_terminal(enhanced_keys, mouse)?;
+    startup.record(StartupPhase::Terminal);
+    let mut app = App::new(client).with_config(config).with_startup(startup);
+    app.load_user_preferences();
+    if !app.startup.fast {
+        app.subscribe_to_server_events();
+    }
+    if let Some(target) = link {
+        app.open_link(target, logs);
+    }
+
+    let result = run_loop(&mut terminal, &mut app, enhanced_keys, mouse).await;
+
+    restore_terminal(&mut terminal, enhanced_keys, mouse)?;
+    result
+}
+
+fn setup_terminal(enhanced_keys: bool, mouse: bool) -> Result<Tui> {
+    let mut stdout = io::stdout();
+    enter_ui(&mut stdout, enhanced_keys, mouse)?;
+    Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+}
+
+/// Put the terminal in raw mode on the alternate screen, as the UI needs it.
+fn enter_ui(out: &mut impl io::Write, enhanced_keys: bool, mouse: bool) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(out, EnterAlternateScreen)?;
+    if mouse {
+        execute!(out, EnableMouseCapture)?;
+    }
+    if enhanced_keys {
+        execute!(
+            out,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
+    Ok(())
+}
+
+fn restore_terminal(terminal: &mut Tui, enhanced_keys: bool, mouse: bool) -> Result<()> {
+    disable_raw_mode()?;
+    if enhanced_keys {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+/// Hand the terminal over to the editor the app asked for, and take it back once
+/// the editor exits.
+fn run_editor(terminal: &mut Tui, app: &mut App, enhanced_keys: bool, mouse: bool) -> Result<()> {
+    let Some(dir) = app.editor_request.take() else {
+        return Ok(());
+    };
+    let command_line = editor::command_line(app.config.editor.as_deref());
+    restore_terminal(terminal, enhanced_keys, mouse)?;
+    let result = editor::command(&command_line, &dir).status();
+    enter_ui(terminal.backend_mut(), enhanced_keys, mouse)?;
+    // Draw the whole screen again over whatever the editor left on it
+    terminal.clear()?;
+    app.editor_closed(&command_line, result);
+    Ok(())
+}
+
+async fn run_loop(
+    terminal: &mut Tui,
+    app: &mut App,
+    enhanced_keys: bool,
+    mouse: bool,
+) -> Result<()> {
+    app.perform_then(RetryableAction::LoadProjects, |_, result| {
+        result.context("Failed to load projects")
+    });
+
+    let animation = app.config.animation;
+    let mut last_frame: Option<Instant> = None;
+    while !app.should_quit {
+        // At most one frame per frame time; a key press in between shows in the
+        // next frame, drawn once the time is up
+        let wait = last_frame.map_or(Duration::ZERO, |drawn| {
+            animation.frame_time().saturating_sub(drawn.elapsed())
+        });
+        if wait.is_zero() {
+            terminal.draw(|frame| ui::render(frame, app))?;
+            last_frame = Some(Instant::now());
+            app.frame_drawn();
+        }
+
+        app.poll_replies();
+        app.follow_tick();
+        app.health_tick();
+        app.conflict_resolution_tick();
+        app.dev_server_tick();
+        app.expire_status();
+        if let Err(e) = app.auto_refresh_tick() {
+            app.report_error(&e);
+        }
+        if let Err(e) = app.poll_session_logs() {
+            app.report_error(&e);
+        }
+        app.poll_server_events();
+        // Keep the spinner turning while requests are in flight
+        let tick_rate = if app.is_loading() && animation.enabled {
+            animation.spinner_frame_time().min(TICK_RATE)
+        } else {
+            TICK_RATE
+        };
+        let timeout = if wait.is_zero() {
+            tick_rate
+        } else {
+            wait.min(tick_rate)
+        };
+        if !event::poll(timeout)? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => handle_key(app, key),
+            Event::Mouse(mouse) => handle_mouse(app, mouse),
+            _ => {}
+        }
+        run_editor(terminal, app, enhanced_keys, mouse)?;
+    }
+
+    Ok(())
+}
+
+fn handle_key(app: &mut App, key: KeyEvent) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.should_quit = true;
+        return;
+    }
+
+    if app.show_error_detail {
+        handle_error_detail_key(app, key);
+        return;
+    }
+
+    if !app.link_hints.is_empty() {
+        handle_link_hint_key(app, key);
+        return;
+    }
+
+    if app.pending_action.is_some() {
+        return handle_confirmation_key(app, key);
+    }
+
+    if app.show_jumper {
+        return handle_jumper_key(app, key);
+    }
+
+    if app.palette.is_some() {
+        return handle_palette_key(app, key);
+    }
+
+    if app.theme_picker.is_some() {
+        handle_theme_picker_key(app, key);
+        return;
+    }
+
+    if app.executor_picker.is_some() {
+        handle_executor_picker_key(app, key);
+        return;
+    }
+
+    if app.template_picker.is_some() {
+        handle_template_picker_key(app, key);
+        return;
+    }
+
+    if app.repo_picker.is_some() {
+        handle_repo_picker_key(app, key);
+        return;
+    }
+
+    if app.pull_request_form.is_some() {
+        handle_pull_request_key(app, key);
+        return;
+    }
+
+    if app.rebase_form.is_some() {
+        handle_rebase_form_key(app, key);
+        return;
+    }
+
+    if let Some(rename) = &mut app.workspace_rename {
+        match key.code {
+            KeyCode::Enter => app.save_workspace_rename(),
+            KeyCode::Esc => app.cancel_workspace_rename(),
+            code => edit_text(&mut rename.name, code),
+        }
+        return;
+    }
+
+    if app.workspace_notes_editor.is_some() {
+        handle_workspace_notes_key(app, key);
+        return;
+    }
+
+    if app.metadata_editor.is_some() {
+        handle_metadata_editor_key(app, key);
+        return;
+    }
+
+    if app.show_metrics {
+        let keymap = &app.config.keybindings;
+        if key.code == KeyCode::Esc || keymap.action(key, &[Action::Metrics]).is_some() {
+            app.show_metrics = false;
+        }
+        return;
+    }
+
+    if app.show_notifications {
+        handle_notifications_key(app, key);
+        return;
+    }
+
+    // Forms without a plain submit key save from anywhere, including while typing
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+        match app.view {
+            View::FollowUp => return app.send_follow_up(),
+            View::PlannerSettings => return app.save_planner_settings(),
+            View::RetentionSettings => return app.save_retention_settings(),
+            View::TaskDetail if app.input_mode == InputMode::Editing => {
+                return app.save_task_edit();
+            }
+            _ => {}
+        }
+    }
+
+    if app.input_mode == InputMode::Editing {
+        handle_editing_key(app, key);
+        return;
+    }
+
+    let key = function_key_event(app, key).unwrap_or(key);
+    let keymap = &app.config.keybindings;
+
+    let actions = [
+        Action::Jump,
+        Action::Palette,
+        Action::Theme,
+        Action::Metrics,
+        Action::Notifications,
+        Action::Timestamps,
+    ];
+    match keymap.action(key, &actions) {
+        Some(Action::Jump) => return app.open_jumper(),
+        Some(Action::Palette) => return app.open_palette(),
+        Some(Action::Theme) => {
+            app.open_theme_picker();
+            return;
+        }
+        Some(Action::Metrics) => {
+            app.show_metrics = true;
+            return;
+        }
+        Some(Action::Notifications) => return app.toggle_notifications(),
+        Some(Action::Timestamps) => return app.toggle_timestamps(),
+        _ => {}
+    }
+
+    if app.error_message.is_some() {
+        let actions = [
+            Action::ErrorDetails,
+            Action::Retry,
+            Action::ResolveConflicts,
+        ];
+        match keymap.action(key, &actions) {
+            Some(Action::ErrorDetails) if app.error_detail.is_some() => {
+                app.show_error_detail = true;
+                return;
+            }
+            Some(Action::Retry) if app.failed_action.is_some() => {
+                return app.retry_failed_action();
+            }
+            Some(Action::ResolveConflicts)
+                if app
+                    .error_detail
+                    .as_ref()
+                    .is_some_and(ErrorDetail::is_conflict) =>
+            {
+                return app.request_resolve_conflicts();
+            }
+            _ => {}
+        }
+    }
+
+    if app.batch.is_some() && keymap.action(key, &[Action::CancelSteps]).is_some() {
+        return app.cancel_batch();
+    }
+
+    // Navigating away by hand hands control back to the user
+    if app.follow_mode && keymap.action(key, &[Action::Back]).is_some() {
+        app.stop_following();
+    }
+
+    match app.view {
+        View::Projects => handle_projects_key(app, key),
+        View::Tasks => handle_tasks_key(app, key),
+        View::TaskDetail => handle_task_detail_key(app, key),
+        View::Workspaces => handle_workspaces_key(app, key),
+        View::WorkspaceDetail => handle_workspace_detail_key(app, key),
+        View::CreateTask => handle_create_task_key(app, key),
+        View::CreateProject => handle_create_project_key(app, key),
+        View::CreateAttempt => handle_create_attempt_key(app, key),
+        View::FollowUp => handle_follow_up_key(app, key),
+        View::Queue => handle_queue_key(app, key),
+        View::Failures => handle_failures_key(app, key),
+        View::Trash => handle_trash_key(app, key),
+        View::TaskTree => handle_task_tree_key(app, key),
+        View::PlannerSettings => handle_planner_settings_key(app, key),
+        View::RetentionSettings => handle_retention_settings_key(app, key),
+        View::SessionLogs => handle_session_logs_key(app, key),
+        View::Diff => handle_diff_key(app, key),
+        View::Help => {
+            let actions = [Action::Back, Action::Help, Action::Quit];
+            if app.config.keybindings.action(key, &actions).is_some() {
+                app.go_back();
+            }
+        }
+    }
+}
+
+/// The key press that an F-key of the function key bar stands in for
+fn function_key_event(app: &App, key: KeyEvent) -> Option<KeyEvent> {
+    let KeyCode::F(number) = key.code else {
+        return None;
+    };
+    if !app.config.function_keys || !key.modifiers.is_empty() {
+        return None;
+    }
+    let (action, _) = keymap::function_keys(app.view)
+        .get(usize::from(number).checked_sub(1)?)
+        .copied()
+        .flatten()?;
+    app.config.keybindings.key_event(action)
+}
+
+/// Clicks pick a column and task on the board and a double click opens the task;
+/// the wheel moves the selection in lists and scrolls the session log.
+fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    if app.has_popup() || app.input_mode == InputMode::Editing {
+        return;
+    }
+    match (mouse.kind, app.view) {
+        (MouseEventKind::ScrollUp, View::SessionLogs) => app.scroll_log(LOG_WHEEL),
+        (MouseEventKind::ScrollDown, View::SessionLogs) => app.scroll_log(-LOG_WHEEL),
+        (MouseEventKind::ScrollUp, _) => app.move_up(),
+        (MouseEventKind::ScrollDown, _) => app.move_down(),
+        (MouseEventKind::Down(MouseButton::Left), View::Tasks) => {
+            let double_click = app.click_board(mouse.column, mouse.row);
+            if double_click {
+                app.select_task();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_error_detail_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('c') | KeyCode::Char('y') => {
+            let text = app.error_detail.as_ref().map(|d| d.to_plain_text());
+            if let Some(text) = text {
+                match clipboard::copy(&text) {
+                    Ok(()) => app.set_status("Error details copied to clipboard"),
+                    Err(e) => app.set_error(format!("Failed to copy: {}", e)),
+                }
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('E') | KeyCode::Char('q') => app.show_error_detail = false,
+        _ => {}
+    }
+}
+
+fn handle_notifications_key(app: &mut App, key: KeyEvent) {
+    let actions = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Notifications,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::MoveUp) => app.scroll_notifications(-1),
+        Some(Action::MoveDown) => app.scroll_notifications(1),
+        Some(Action::PageUp) => app.scroll_notifications(-10),
+        Some(Action::PageDown) => app.scroll_notifications(10),
+        Some(Action::Top) => app.notifications_scroll = 0,
+        Some(Action::Notifications | Action::Back) => app.toggle_notifications(),
+        _ => {}
+    }
+}
+
+fn handle_link_hint_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('o') => app.close_link_hints(),
+        KeyCode::Char(c) if c.is_ascii_lowercase() => app.type_link_hint(c),
+        KeyCode::Backspace => {
+            app.link_hint_input.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_confirmation_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_pending_action(),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_pending_action(),
+        _ => {}
+    }
+}
+
+fn handle_jumper_key(app: &mut App, key: KeyEvent) {
+    let keymap = &app.config.keybindings;
+    if keymap.action(key, &[Action::Jump]).is_some() {
+        app.close_jumper();
+        return;
+    }
+    match key.code {
+        KeyCode::Esc => app.close_jumper(),
+        KeyCode::Enter => app.jump_to_selected(),
+        KeyCode::Up => app.jumper_move_up(),
+        KeyCode::Down => app.jumper_move_down(),
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => app.type_jumper(c),
+        KeyCode::Backspace => app.jumper_backspace(),
+        _ => {}
+    }
+}
+
+fn handle_palette_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.close_palette(),
+        KeyCode::Enter => {
+            let Some(action) = app.take_palette_command() else {
+                return;
+            };
+            // An error on screen would turn keys such as `R` into a retry
+            if !matches!(action, Action::ErrorDetails | Action::Retry) {
+                app.clear_messages();
+            }
+            // Run it as its key, like the function keys, so each view decides
+            // what it does
+            if let Some(key) = app.config.keybindings.key_event(action) {
+                handle_key(app, key);
+            }
+        }
+        KeyCode::Up => app.palette_move(false),
+        KeyCode::Down => app.palette_move(true),
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => app.type_palette(c),
+        KeyCode::Backspace => app.palette_backspace(),
+        _ => {}
+    }
+}
+
+fn handle_theme_picker_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.close_theme_picker(true),
+        KeyCode::Esc => app.close_theme_picker(false),
+        _ => {
+            let actions = [Action::MoveUp, Action::MoveDown, Action::Theme];
+            match app.config.keybindings.action(key, &actions) {
+                Some(Action::MoveUp) => app.theme_picker_move(false),
+                Some(Action::MoveDown) => app.theme_picker_move(true),
+                Some(Action::Theme) => app.close_theme_picker(true),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_executor_picker_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.close_executor_picker(true),
+        KeyCode::Esc => app.close_executor_picker(false),
+        _ => {
+            let actions = [Action::MoveUp, Action::MoveDown];
+            match app.config.keybindings.action(key, &actions) {
+                Some(Action::MoveUp) => app.executor_picker_move(false),
+                Some(Action::MoveDown) => app.executor_picker_move(true),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_repo_picker_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.close_repo_picker(true),
+        KeyCode::Esc => app.close_repo_picker(false),
+        _ => match app
+            .config
+            .keybindings
+            .action(key, &[Action::MoveUp, Action::MoveDown])
+        {
+            Some(Action::MoveUp) => app.repo_picker_move(false),
+            Some(Action::MoveDown) => app.repo_picker_move(true),
+            _ => {}
+        },
+    }
+}
+
+fn handle_pull_request_key(app: &mut App, key: KeyEvent) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+        return app.create_pull_request();
+    }
+    let Some(form) = &mut app.pull_request_form else {
+        return;
+    };
+    let several_repos = form.repos.len() > 1;
+    match (key.code, form.field) {
+        (KeyCode::Esc, _) => app.cancel_pull_request(),
+        (KeyCode::Tab, field) | (KeyCode::Enter, field @ PullRequestField::Title) => {
+            form.field = field.next(several_repos);
+        }
+        // The body is multi-line; Ctrl+S creates the pull request
+        (KeyCode::Enter, PullRequestField::Body) => form.body.push('\n'),
+        (KeyCode::Enter, _) => app.create_pull_request(),
+        (KeyCode::Left, PullRequestField::Repo) => {
+            form.repo_index = (form.repo_index + form.repos.len() - 1) % form.repos.len();
+        }
+        (KeyCode::Right | KeyCode::Char(' '), PullRequestField::Repo) => {
+            form.repo_index = (form.repo_index + 1) % form.repos.len();
+        }
+        (KeyCode::Left | KeyCode::Right | KeyCode::Char(' '), PullRequestField::Draft) => {
+            form.draft = !form.draft;
+        }
+        (code, PullRequestField::Title) => edit_text(&mut form.title, code),
+        (code, PullRequestField::Body) => edit_text(&mut form.body, code),
+        _ => {}
+    }
+}
+
+fn handle_workspace_notes_key(app: &mut App, key: KeyEvent) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+        return app.save_workspace_notes();
+    }
+    let Some(editor) = &mut app.workspace_notes_editor else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.cancel_workspace_notes(),
+        // Notes are multi-line; Ctrl+S saves them
+        KeyCode::Enter => editor.body.push('\n'),
+        code => edit_text(&mut editor.body, code),
+    }
+}
+
+// The key list takes the usual keys; typing a key or value takes text, with
+// Tab moving between them and Enter starting a new line of the value
+fn handle_metadata_editor_key(app: &mut App, key: KeyEvent) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+        return app.save_metadata();
+    }
+    let Some(editor) = &mut app.metadata_editor else {
+        return;
+    };
+    if let Some(entry) = &mut editor.entry {
+        editor.problem = None;
+        match key.code {
+            KeyCode::Esc => app.cancel_metadata_edit(),
+            KeyCode::Tab => entry.editing_value = !entry.editing_value,
+            KeyCode::Enter if entry.editing_value => entry.value.push('\n'),
+            KeyCode::Enter => entry.editing_value = true,
+            code if entry.editing_value => edit_text(&mut entry.value, code),
+            code => edit_text(&mut entry.key, code),
+        }
+        return;
+    }
+    if key.code == KeyCode::Enter {
+        return app.edit_metadata_entry(false);
+    }
+    let actions = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::Edit,
+        Action::New,
+        Action::Delete,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::MoveUp) => app.metadata_move(false),
+        Some(Action::MoveDown) => app.metadata_move(true),
+        Some(Action::Edit) => app.edit_metadata_entry(false),
+        Some(Action::New) => app.edit_metadata_entry(true),
+        Some(Action::Delete) => app.delete_metadata_entry(),
+        Some(Action::Back) => app.cancel_metadata_edit(),
+        _ => {}
+    }
+}
+
+fn handle_rebase_form_key(app: &mut App, key: KeyEvent) {
+    let Some(form) = &mut app.rebase_form else {
+        return;
+    };
+    match key.code {
+        KeyCode::Enter => app.submit_rebase_onto(),
+        KeyCode::Esc => app.cancel_rebase_onto(),
+        KeyCode::Left => app.rebase_form_step_repo(false),
+        KeyCode::Right => app.rebase_form_step_repo(true),
+        code => edit_text(&mut form.new_base, code),
+    }
+}
+
+fn handle_template_picker_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.close_template_picker(true),
+        KeyCode::Esc => app.close_template_picker(false),
+        _ => {
+            let actions = [Action::MoveUp, Action::MoveDown, Action::Templates];
+            match app.config.keybindings.action(key, &actions) {
+                Some(Action::MoveUp) => app.template_picker_move(false),
+                Some(Action::MoveDown) => app.template_picker_move(true),
+                Some(Action::Templates) => app.close_template_picker(false),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Actions shared by the list views
+const LIST_ACTIONS: [Action; 6] = [
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::Help,
+    Action::Quit,
+];
+
+/// Keys shared by the list views. Returns true if the key was handled.
+fn handle_list_navigation(app: &mut App, key: KeyEvent) -> bool {
+    match app.config.keybindings.action(key, &LIST_ACTIONS) {
+        Some(Action::MoveUp) => app.move_up(),
+        Some(Action::MoveDown) => app.move_down(),
+        Some(Action::MoveLeft) => app.move_left(),
+        Some(Action::MoveRight) => app.move_right(),
+        Some(Action::Help) => app.navigate_to(View::Help),
+        Some(Action::Quit) => app.should_quit = true,
+        _ => return false,
+    }
+    true
+}
+
+fn handle_projects_key(app: &mut App, key: KeyEvent) {
+    if handle_list_navigation(app, key) {
+        return;
+    }
+    let actions = [
+        Action::Select,
+        Action::New,
+        Action::Queue,
+        Action::Failures,
+        Action::PlannerSettings,
+        Action::RetentionSettings,
+        Action::Refresh,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Select) => app.select_project(),
+        Some(Action::New) => app.open_create_project(),
+        Some(Action::Queue) => app.open_queue(),
+        Some(Action::Failures) => app.open_failures(),
+        Some(Action::PlannerSettings) => app.open_planner_settings(),
+        Some(Action::RetentionSettings) => app.open_retention_settings(),
+        Some(Action::Refresh) => app.refresh(),
+        _ => {}
+    }
+}
+
+fn handle_tasks_key(app: &mut App, key: KeyEvent) {
+    if handle_list_navigation(app, key) {
+        return;
+    }
+    let actions = [
+        Action::Select,
+        Action::New,
+        Action::MoveTask,
+        Action::MoveTaskBack,
+        Action::MoveCardLeft,
+        Action::MoveCardRight,
+        Action::MoveCardUp,
+        Action::MoveCardDown,
+        Action::ToggleCancelled,
+        Action::SortTasks,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::Mark,
+        Action::StartAttempts,
+        Action::Delete,
+        Action::Undo,
+        Action::Trash,
+        Action::TaskTree,
+        Action::Yank,
+        Action::OpenLink,
+        Action::Follow,
+        Action::Queue,
+        Action::Failures,
+        Action::PlannerSettings,
+        Action::Refresh,
+        Action::Search,
+        Action::CycleFilter,
+        Action::ExecutorFilter,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Select) => app.select_task(),
+        Some(Action::New) => app.open_create_task(None),
+        Some(Action::MoveTask) if !app.marked_tasks.is_empty() => app.move_marked_tasks(false),
+        Some(Action::MoveTaskBack) if !app.marked_tasks.is_empty() => app.move_marked_tasks(true),
+        Some(action @ (Action::MoveTask | Action::MoveTaskBack)) => {
+            let current = app
+                .current_column_selected_task()
+                .map(|t| (t.task.id, t.task.status));
+            if let Some((task_id, status)) = current {
+                let target = if action == Action::MoveTaskBack {
+                    app.selected_column.prev()
+                } else {
+                    app.selected_column.next()
+                };
+                if target.status() != status {
+                    app.update_task_status(task_id, target.status());
+                }
+            }
+        }
+        Some(Action::MoveCardLeft) => app.move_card(false),
+        Some(Action::MoveCardRight) => app.move_card(true),
+        Some(Action::MoveCardUp) => app.reorder_card(false),
+        Some(Action::MoveCardDown) => app.reorder_card(true),
+        Some(Action::ToggleCancelled) => app.toggle_cancelled_column(),
+        Some(Action::SortTasks) => app.cycle_task_sort(),
+        Some(Action::ZoomIn) => app.zoom_in(),
+        Some(Action::ZoomOut) => app.zoom_out(),
+        Some(Action::Mark) => app.toggle_task_mark(),
+        Some(Action::StartAttempts) => app.open_start_attempts(),
+        Some(Action::Delete) => app.request_delete_task(),
+        Some(Action::Undo) => app.undo_delete_task(),
+        Some(Action::Trash) => app.open_trash(),
+        Some(Action::TaskTree) => app.open_task_tree(),
+        Some(Action::Yank) => app.yank_task_id(),
+        Some(Action::OpenLink) => app.show_link_hints(),
+        Some(Action::Follow) => app.toggle_follow(),
+        Some(Action::Queue) => app.open_queue(),
+        Some(Action::Failures) => app.open_failures(),
+        Some(Action::PlannerSettings) => app.open_planner_settings(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Search) => app.start_task_search(),
+        Some(Action::CycleFilter) => app.cycle_attempt_filter(),
+        Some(Action::ExecutorFilter) => app.open_executor_picker(),
+        // Back clears the marks, then an active search, then the quick filters,
+        // then the zoom, before leaving the board
+        Some(Action::Back) if !app.marked_tasks.is_empty() => app.marked_tasks.clear(),
+        Some(Action::Back) if !app.task_filter.is_empty() => app.clear_task_filter(),
+        Some(Action::Back) if app.has_quick_filters() => app.clear_quick_filters(),
+        Some(Action::Back) if app.board_zoom != BoardZoom::Tasks => {
+            app.set_board_zoom(BoardZoom::Tasks)
+        }
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_task_detail_key(app: &mut App, key: KeyEvent) {
+    let actions = [
+        Action::Select,
+        Action::Workspaces,
+        Action::Edit,
+        Action::CopyPrompt,
+        Action::SavePrompt,
+        Action::TaskTree,
+        Action::Metadata,
+        Action::OpenLink,
+        Action::Refresh,
+        Action::Help,
+        Action::Quit,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Select | Action::Workspaces) => app.open_task_workspaces(),
+        Some(Action::Edit) => app.start_task_edit(),
+        Some(Action::CopyPrompt) => app.copy_task_prompt(),
+        Some(Action::SavePrompt) => app.save_task_prompt(),
+        Some(Action::TaskTree) => app.open_task_tree(),
+        Some(Action::Metadata) => app.open_metadata_editor(),
+        Some(Action::OpenLink) => app.show_link_hints(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Help) => app.navigate_to(View::Help),
+        Some(Action::Quit) => app.should_quit = true,
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_workspaces_key(app: &mut App, key: KeyEvent) {
+    if handle_list_navigation(app, key) {
+        return;
+    }
+    let actions = [
+        Action::Select,
+        Action::New,
+        Action::Stop,
+        Action::RetryAttempt,
+        Action::ResolveConflicts,
+        Action::RestartContainer,
+        Action::RebuildContainer,
+        Action::Rename,
+        Action::Yank,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::OpenLink,
+        Action::Follow,
+        Action::Queue,
+        Action::Refresh,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Select | Action::ZoomIn) => app.select_workspace(),
+        Some(Action::ZoomOut) => app.zoom_out(),
+        Some(Action::New) => {
+            app.init_create_attempt();
+            app.navigate_to(View::CreateAttempt);
+        }
+        Some(Action::Stop) => {
+            app.selected_workspace = app.workspaces.get(app.selected_workspace_index).cloned();
+            app.request_stop_workspace();
+        }
+        Some(Action::RetryAttempt) => {
+            app.selected_workspace = app.workspaces.get(app.selected_workspace_index).cloned();
+            app.request_retry_attempt();
+        }
+        Some(Action::ResolveConflicts) => {
+            app.selected_workspace = app.workspaces.get(app.selected_workspace_index).cloned();
+            app.request_resolve_conflicts();
+        }
+        Some(Action::RestartContainer) => app.restart_container(),
+        Some(Action::RebuildContainer) => app.rebuild_container(),
+        Some(Action::Rename) => app.start_workspace_rename(),
+        Some(Action::Yank) => app.yank_branch(),
+        Some(Action::OpenLink) => app.show_link_hints(),
+        Some(Action::Follow) => app.toggle_follow(),
+        Some(Action::Queue) => app.open_queue(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_workspace_detail_key(app: &mut App, key: KeyEvent) {
+    let actions = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::Merge,
+        Action::MergeAll,
+        Action::Push,
+        Action::Rebase,
+        Action::RebaseOnto,
+        Action::CreatePr,
+        Action::OpenEditor,
+        Action::Yank,
+        Action::YankUrl,
+        Action::Stop,
+        Action::RetryAttempt,
+        Action::ResolveConflicts,
+        Action::DevServer,
+        Action::FollowUp,
+        Action::Logs,
+        Action::Diff,
+        Action::Rename,
+        Action::Notes,
+        Action::NewSubtask,
+        Action::OpenLink,
+        Action::Follow,
+        Action::Refresh,
+        Action::Help,
+        Action::Quit,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Merge) => app.request_merge(),
+        Some(Action::MergeAll) => app.request_merge_all(),
+        Some(Action::Push) => app.request_push(),
+        Some(Action::Rebase) => app.request_rebase(),
+        Some(Action::RebaseOnto) => app.start_rebase_onto(),
+        Some(Action::CreatePr) => app.start_pull_request(),
+        Some(Action::OpenEditor) => app.open_in_editor(),
+        Some(Action::Yank) => app.yank_branch(),
+        Some(Action::YankUrl) => app.yank_pr_url(),
+        Some(Action::MoveUp) => app.move_process_selection(false),
+        Some(Action::MoveDown) => app.move_process_selection(true),
+        Some(Action::Stop) => app.request_stop_selected(),
+        Some(Action::RetryAttempt) => app.request_retry_attempt(),
+        Some(Action::ResolveConflicts) => app.request_resolve_conflicts(),
+        Some(Action::DevServer) => app.toggle_dev_server(),
+        Some(Action::FollowUp) => app.open_follow_up(),
+        Some(Action::Logs) => app.open_session_logs(),
+        Some(Action::Diff) => app.open_diff(),
+        Some(Action::Rename) => app.start_workspace_rename(),
+        Some(Action::Notes) => app.start_workspace_notes(),
+        Some(Action::NewSubtask) => app.open_create_subtask(),
+        Some(Action::OpenLink) => app.show_link_hints(),
+        Some(Action::Follow) => app.toggle_follow(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Help) => app.navigate_to(View::Help),
+        Some(Action::Quit) => app.should_quit = true,
+        // Esc lets go of a picked process before leaving the view
+        Some(Action::Back) if app.selected_process_index.is_some() => {
+            app.selected_process_index = None;
+        }
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+// Tab and Enter move through the forms and are not remappable
+fn handle_create_task_key(app: &mut App, key: KeyEvent) {
+    let actions = [Action::Edit, Action::MoveUp, Action::MoveDown, Action::Back];
+    match key.code {
+        KeyCode::Tab => app.next_new_task_field(),
+        KeyCode::Enter => app.create_task(),
+        KeyCode::Char(' ') if app.new_task_field == NewTaskField::Start => {
+            app.toggle_new_task_start()
+        }
+        _ => match app.config.keybindings.action(key, &actions) {
+            Some(Action::Edit) => {
+                if !app.new_task_field.is_text() {
+                    app.new_task_field = NewTaskField::Title;
+                }
+                app.input_mode = InputMode::Editing;
+            }
+            Some(Action::MoveUp) => app.cycle_new_task_value(false),
+            Some(Action::MoveDown) => app.cycle_new_task_value(true),
+            Some(Action::Back) => app.go_back(),
+            _ => {}
+        },
+    }
+}
+
+fn handle_create_project_key(app: &mut App, key: KeyEvent) {
+    let actions = [Action::Edit, Action::New, Action::Delete, Action::Back];
+    match key.code {
+        KeyCode::Tab => app.next_project_field(),
+        KeyCode::Enter => app.create_project(),
+        _ => match app.config.keybindings.action(key, &actions) {
+            Some(Action::Edit) => app.input_mode = InputMode::Editing,
+            Some(Action::New) => app.add_project_repo(),
+            Some(Action::Delete) => app.remove_project_repo(),
+            Some(Action::Back) => app.go_back(),
+            _ => {}
+        },
+    }
+}
+
+fn handle_create_attempt_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Tab => app.next_attempt_field(),
+        // Known variants are picked with ↑/↓; free text is only for older servers
+        KeyCode::Enter if app.attempt_selected_field == 1 => {
+            if app.attempt_variant_options().is_empty() {
+                app.input_mode = InputMode::Editing;
+            } else {
+                app.next_attempt_field();
+            }
+        }
+        KeyCode::Enter if matches!(app.attempt_selected_field, 2 | 3) => {
+            app.input_mode = InputMode::Editing
+        }
+        KeyCode::Enter => app.create_attempt(),
+        _ => {
+            let actions = [
+                Action::MoveUp,
+                Action::MoveDown,
+                Action::Search,
+                Action::Back,
+            ];
+            match app.config.keybindings.action(key, &actions) {
+                Some(Action::MoveUp) => app.cycle_attempt_value(false),
+                Some(Action::MoveDown) => app.cycle_attempt_value(true),
+                Some(Action::Search) => app.start_attempt_branch_search(None),
+                Some(Action::Back) => app.go_back(),
+                // Typing on a base branch starts completing it
+                None => match key.code {
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.start_attempt_branch_search(Some(c))
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_follow_up_key(app: &mut App, key: KeyEvent) {
+    let editing_prompt = app.follow_up_field == FollowUpField::Prompt;
+    match key.code {
+        KeyCode::Tab => app.follow_up_field = app.follow_up_field.next(),
+        KeyCode::Enter if editing_prompt => app.input_mode = InputMode::Editing,
+        KeyCode::Enter => app.send_follow_up(),
+        _ => {
+            let actions = [
+                Action::MoveUp,
+                Action::MoveDown,
+                Action::Edit,
+                Action::Templates,
+                Action::Back,
+            ];
+            match app.config.keybindings.action(key, &actions) {
+                Some(Action::MoveUp) => app.cycle_follow_up_value(false),
+                Some(Action::MoveDown) => app.cycle_follow_up_value(true),
+                Some(Action::Edit) if editing_prompt => app.input_mode = InputMode::Editing,
+                Some(Action::Templates) => app.open_template_picker(),
+                Some(Action::Back) => app.go_back(),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_planner_settings_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Tab => app.planner_field = app.planner_field.next(),
+        KeyCode::Enter => app.input_mode = InputMode::Editing,
+        _ => {
+            let actions = [Action::MoveUp, Action::MoveDown, Action::Edit, Action::Back];
+            match app.config.keybindings.action(key, &actions) {
+                Some(Action::MoveUp) => app.planner_field = app.planner_field.previous(),
+                Some(Action::MoveDown) => app.planner_field = app.planner_field.next(),
+                Some(Action::Edit) => app.input_mode = InputMode::Editing,
+                Some(Action::Back) => app.go_back(),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_retention_settings_key(app: &mut App, key: KeyEvent) {
+    let toggle = app.retention_field == RetentionField::DeleteCancelledWorktrees;
+    match key.code {
+        KeyCode::Tab => app.retention_field = app.retention_field.next(),
+        KeyCode::Enter if toggle => app.toggle_retention_delete_cancelled(),
+        KeyCode::Enter => app.input_mode = InputMode::Editing,
+        _ => {
+            let actions = [
+                Action::MoveUp,
+                Action::MoveDown,
+                Action::Edit,
+                Action::Refresh,
+                Action::Back,
+            ];
+            match app.config.keybindings.action(key, &actions) {
+                Some(Action::MoveUp) => app.retention_field = app.retention_field.prev(),
+                Some(Action::MoveDown) => app.retention_field = app.retention_field.next(),
+                Some(Action::Edit) if toggle => app.toggle_retention_delete_cancelled(),
+                Some(Action::Edit) => app.input_mode = InputMode::Editing,
+                Some(Action::Refresh) => app.refresh(),
+                Some(Action::Back) => app.go_back(),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_queue_key(app: &mut App, key: KeyEvent) {
+    if handle_list_navigation(app, key) {
+        return;
+    }
+    let actions = [Action::Refresh, Action::Back];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_failures_key(app: &mut App, key: KeyEvent) {
+    if handle_list_navigation(app, key) {
+        return;
+    }
+    let actions = [
+        Action::Select,
+        Action::CycleFilter,
+        Action::Refresh,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Select) => app.open_selected_failure(),
+        Some(Action::CycleFilter) => app.cycle_failure_filter(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_task_tree_key(app: &mut App, key: KeyEvent) {
+    if handle_list_navigation(app, key) {
+        return;
+    }
+    let actions = [Action::Select, Action::Refresh, Action::Back];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Select) => app.open_selected_tree_node(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_trash_key(app: &mut App, key: KeyEvent) {
+    if handle_list_navigation(app, key) {
+        return;
+    }
+    let actions = [Action::Undo, Action::Delete, Action::Refresh, Action::Back];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::Undo) => app.restore_selected_trash(),
+        Some(Action::Delete) => app.request_purge_task(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_session_logs_key(app: &mut App, key: KeyEvent) {
+    let actions = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::PreviousProcess,
+        Action::NextProcess,
+        Action::Bookmark,
+        Action::NextBookmark,
+        Action::PreviousBookmark,
+        Action::ExportTranscript,
+        Action::OpenLink,
+        Action::Refresh,
+        Action::Help,
+        Action::Quit,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::MoveUp) => app.scroll_log(1),
+        Some(Action::MoveDown) => app.scroll_log(-1),
+        Some(Action::PageUp) => app.scroll_log(LOG_PAGE),
+        Some(Action::PageDown) => app.scroll_log(-LOG_PAGE),
+        Some(Action::Top) => app.scroll_log(isize::MAX),
+        Some(Action::Bottom) => app.follow_log(),
+        Some(Action::PreviousProcess) => app.cycle_log_process(false),
+        Some(Action::NextProcess) => app.cycle_log_process(true),
+        Some(Action::Bookmark) => app.start_log_bookmark(),
+        Some(Action::NextBookmark) => app.jump_to_bookmark(true),
+        Some(Action::PreviousBookmark) => app.jump_to_bookmark(false),
+        Some(Action::ExportTranscript) => app.export_transcript(),
+        Some(Action::OpenLink) => app.show_link_hints(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Help) => app.navigate_to(View::Help),
+        Some(Action::Quit) => app.should_quit = true,
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+fn handle_diff_key(app: &mut App, key: KeyEvent) {
+    let actions = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::Top,
+        Action::Bottom,
+        Action::Comment,
+        Action::Delete,
+        Action::SendReview,
+        Action::Refresh,
+        Action::Help,
+        Action::Quit,
+        Action::Back,
+    ];
+    match app.config.keybindings.action(key, &actions) {
+        Some(Action::MoveUp) => app.move_up(),
+        Some(Action::MoveDown) => app.move_down(),
+        Some(Action::Top) => app.selected_hunk_index = 0,
+        Some(Action::Bottom) => app.selected_hunk_index = app.diff_hunks.len().saturating_sub(1),
+        Some(Action::Comment) => app.start_diff_comment(),
+        Some(Action::Delete) => app.delete_diff_comment(),
+        Some(Action::SendReview) => app.send_review(),
+        Some(Action::Refresh) => app.refresh(),
+        Some(Action::Help) => app.navigate_to(View::Help),
+        Some(Action::Quit) => app.should_quit = true,
+        Some(Action::Back) => app.go_back(),
+        _ => {}
+    }
+}
+
+/// Text entry for the forms and the task detail editor.
+fn handle_editing_key(app: &mut App, key: KeyEvent) {
+    if app.view == View::CreateTask {
+        let leaving_description = app.new_task_field == NewTaskField::Description;
+        match key.code {
+            KeyCode::Tab => {
+                app.next_new_task_field();
+                if leaving_description {
+                    app.suggest_task_title();
+                }
+                // The start toggle and what it picks aren't typed
+                if !app.new_task_field.is_text() {
+                    app.input_mode = InputMode::Normal;
+                }
+                return;
+            }
+            // Terminals without Shift+Enter reporting send Alt+Enter instead
+            KeyCode::Enter
+                if app.new_task_field == NewTaskField::Description
+                    && key
+                        .modifiers
+                        .intersects(KeyModifiers::SHIFT | KeyModifiers::ALT) =>
+            {
+                app.new_task_description.push('\n');
+                return;
+            }
+            KeyCode::Enter | KeyCode::Esc => {
+                app.input_mode = InputMode::Normal;
+                if leaving_description {
+                    app.suggest_task_title();
+                }
+                return;
+            }
+            _ => {}
+        }
+        let field = match app.new_task_field {
+            NewTaskField::Title => {
+                app.new_task_title_suggested = false;
+                &mut app.new_task_title
+            }
+            NewTaskField::Description => &mut app.new_task_description,
+            _ => return,
+        };
+        edit_text(field, key.code);
+        return;
+    }
+
+    if app.view == View::CreateProject {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => app.input_mode = InputMode::Normal,
+            KeyCode::Tab => app.next_project_field(),
+            code => {
+                if let Some(input) = app.project_input_mut() {
+                    edit_text(input, code);
+                }
+            }
+        }
+        return;
+    }
+
+    if app.view == View::CreateAttempt && app.attempt_selected_field >= 4 {
+        match key.code {
+            KeyCode::Enter => app.finish_attempt_branch_search(true),
+            KeyCode::Tab => {
+                app.finish_attempt_branch_search(true);
+                app.next_attempt_field();
+            }
+            KeyCode::Esc => app.finish_attempt_branch_search(false),
+            KeyCode::Up => app.move_attempt_branch_completion(false),
+            KeyCode::Down => app.move_attempt_branch_completion(true),
+            code => app.edit_attempt_branch_query(|query| edit_text(query, code)),
+        }
+        return;
+    }
+
+    if app.view == View::CreateAttempt {
+        if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Tab) {
+            app.input_mode = InputMode::Normal;
+            if key.code == KeyCode::Tab {
+                app.next_attempt_field();
+            }
+            return;
+        }
+        if app.attempt_selected_field == 2 {
+            edit_text(&mut app.attempt_limits, key.code);
+            return;
+        }
+        if app.attempt_selected_field == 3 {
+            edit_text(&mut app.attempt_branch, key.code);
+            return;
+        }
+        let variant = app.attempt_variant.get_or_insert_with(String::new);
+        edit_text(variant, key.code);
+        if variant.is_empty() {
+            app.attempt_variant = None;
+        }
+        return;
+    }
+
+    if app.view == View::Tasks {
+        match key.code {
+            KeyCode::Enter => app.input_mode = InputMode::Normal,
+            KeyCode::Esc => app.clear_task_filter(),
+            // The selection can be moved without leaving the search
+            KeyCode::Up => app.move_up(),
+            KeyCode::Down => app.move_down(),
+            KeyCode::Left => app.move_left(),
+            KeyCode::Right => app.move_right(),
+            code => {
+                edit_text(&mut app.task_filter, code);
+                app.clamp_task_selection();
+            }
+        }
+        return;
+    }
+
+    if app.view == View::TaskDetail {
+        match (key.code, app.task_edit_field) {
+            (KeyCode::Esc, _) => app.cancel_task_edit(),
+            (KeyCode::Tab, field) | (KeyCode::Enter, field @ TaskFormField::Title) => {
+                app.task_edit_field = field.next();
+            }
+            // The description is multi-line; Ctrl+S saves
+            (KeyCode::Enter, TaskFormField::Description) => app.task_edit_description.push('\n'),
+            (code, TaskFormField::Title) => edit_text(&mut app.task_edit_title, code),
+            (code, TaskFormField::Description) => edit_text(&mut app.task_edit_description, code),
+        }
+        return;
+    }
+
+    if app.view == View::Diff {
+        match key.code {
+            KeyCode::Enter => app.save_diff_comment(),
+            KeyCode::Esc => app.cancel_diff_comment(),
+            code => edit_text(&mut app.diff_comment_input, code),
+        }
+        return;
+    }
+
+    if app.view == View::SessionLogs {
+        match key.code {
+            KeyCode::Enter => app.save_log_bookmark(),
+            KeyCode::Esc => app.cancel_log_bookmark(),
+            code => edit_text(&mut app.log_bookmark_note, code),
+        }
+        return;
+    }
+
+    if app.view == View::FollowUp {
+        match key.code {
+            KeyCode::Esc => app.input_mode = InputMode::Normal,
+            KeyCode::Tab => {
+                app.input_mode = InputMode::Normal;
+                app.follow_up_field = app.follow_up_field.next();
+            }
+            // The prompt is multi-line; Ctrl+S sends it
+            KeyCode::Enter => app.follow_up_input.push('\n'),
+            code => edit_text(&mut app.follow_up_input, code),
+        }
+        return;
+    }
+
+    if app.view == View::RetentionSettings {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                app.input_mode = InputMode::Normal;
+                app.preview_retention_policy();
+            }
+            KeyCode::Tab => {
+                app.input_mode = InputMode::Normal;
+                app.retention_field = app.retention_field.next();
+                app.preview_retention_policy();
+            }
+            // Whole days
+            KeyCode::Char(c) if !c.is_ascii_digit() => {}
+            code => edit_text(app.retention_days_input(), code),
+        }
+        return;
+    }
+
+    if app.view == View::PlannerSettings {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => app.input_mode = InputMode::Normal,
+            KeyCode::Tab => {
+                app.input_mode = InputMode::Normal;
+                app.planner_field = app.planner_field.next();
+            }
+            // The limits are whole numbers
+            KeyCode::Char(c) if !c.is_ascii_digit() => {}
+            code => edit_text(app.planner_input_mut(), code),
+        }
+        return;
+    }
+
+    app.input_mode = InputMode::Normal;
+}
+
+fn edit_text(text: &mut String, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => text.push(c),
+        KeyCode::Backspace => {
+            text.pop();
+        }
+        _ => {}
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! These types are used for API communication with the Vibe Kanban server.
 
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -13,6 +16,9 @@ pub struct ApiResponse<T> {
     pub data: Option<T>,
     pub error_data: Option<serde_json::Value>,
     pub message: Option<String>,
+    /// HTTP status of the response, filled in by the client
+    #[serde(skip)]
+    pub status: Option<u16>,
 }
 
 /// Project model
@@ -34,14 +40,14 @@ pub struct CreateProject {
 }
 
 /// Create project repository
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CreateProjectRepo {
     pub display_name: String,
     pub git_repo_path: String,
 }
 
 /// Task status enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskStatus {
     Todo,
@@ -74,7 +80,7 @@ impl TaskStatus {
 }
 
 /// Task complexity enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskComplexity {
     Trivial,
@@ -96,6 +102,9 @@ pub struct Task {
     pub is_epic: bool,
     pub complexity: Option<TaskComplexity>,
     pub metadata: Option<String>,
+    /// Place in its board column when the column is ordered by hand
+    #[serde(default)]
+    pub board_position: Option<i64>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -110,6 +119,20 @@ pub struct TaskWithAttemptStatus {
     pub task: Task,
 }
 
+/// A task in the trash: off the board, but kept with its workspaces until it is
+/// restored or deleted for good
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrashedTask {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub deleted_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Create task request
 #[derive(Debug, Serialize)]
 pub struct CreateTask {
@@ -137,6 +160,13 @@ pub struct UpdateTask {
     pub metadata: Option<String>,
 }
 
+/// The tasks of a board column in the order they were arranged in by hand
+#[derive(Debug, Serialize)]
+pub struct ReorderTasks {
+    pub project_id: Uuid,
+    pub task_ids: Vec<Uuid>,
+}
+
 /// Repository model
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Repo {
@@ -169,6 +199,77 @@ pub struct Workspace {
     pub name: Option<String>,
 }
 
+/// Update workspace request; fields left out are unchanged
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateWorkspace {
+    pub archived: Option<bool>,
+    pub pinned: Option<bool>,
+    /// An empty name clears it
+    pub name: Option<String>,
+}
+
+/// Tasks related to a workspace's task
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TaskRelationships {
+    /// Task owning the workspace the current task was created from
+    pub parent_task: Option<Task>,
+    pub current_workspace: Workspace,
+    /// Tasks created from the workspace
+    pub children: Vec<Task>,
+}
+
+/// Latest activity in a workspace
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkspaceActivity {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub last_output_at: Option<DateTime<Utc>>,
+    pub last_status_change_at: Option<DateTime<Utc>>,
+    pub is_running: bool,
+}
+
+impl WorkspaceActivity {
+    pub fn last_activity_at(&self) -> Option<DateTime<Utc>> {
+        self.last_output_at.max(self.last_status_change_at)
+    }
+}
+
+/// State of a workspace's directory on the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerState {
+    Running,
+    Stopped,
+    Missing,
+}
+
+impl ContainerState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Stopped => "stopped",
+            Self::Missing => "missing",
+        }
+    }
+}
+
+/// Container status of a workspace
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkspaceContainerStatus {
+    pub workspace_id: Uuid,
+    pub state: ContainerState,
+    pub container_ref: Option<String>,
+    /// Repositories whose worktree is missing from the workspace directory
+    pub missing_repos: Vec<String>,
+}
+
+impl WorkspaceContainerStatus {
+    /// The directory exists but no longer matches the workspace's repositories.
+    pub fn is_drifted(&self) -> bool {
+        !self.missing_repos.is_empty()
+    }
+}
+
 /// Session model
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Session {
@@ -179,6 +280,35 @@ pub struct Session {
     pub updated_at: String,
 }
 
+/// Progress last reported by the coding agent of a session
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SessionProgress {
+    pub session_id: Uuid,
+    pub workspace_id: Uuid,
+    pub files_touched: Vec<String>,
+    pub steps_completed: Option<i32>,
+    pub steps_total: Option<i32>,
+    pub message: Option<String>,
+    /// Time of the latest report; agents report as they work, so it is also a heartbeat
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SessionProgress {
+    /// Agents that haven't reported for this long are probably stuck
+    pub const STALE_AFTER_SECS: i64 = 5 * 60;
+
+    /// Share of plan steps completed, when the agent reported a plan.
+    pub fn percent(&self) -> Option<u8> {
+        let total = self.steps_total.filter(|t| *t > 0)?;
+        let completed = self.steps_completed.unwrap_or(0).clamp(0, total);
+        Some((completed * 100 / total) as u8)
+    }
+
+    pub fn is_stale(&self) -> bool {
+        (Utc::now() - self.updated_at).num_seconds() > Self::STALE_AFTER_SECS
+    }
+}
+
 /// Execution process status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -198,12 +328,59 @@ pub struct ExecutionProcess {
     pub status: ExecutionProcessStatus,
     pub exit_code: Option<i64>,
     pub dropped: bool,
+    /// What the process was asked to run; kept loose as the CLI only reads the
+    /// prompt of coding agent runs, see [`ExecutionProcess::agent_request`]
+    #[serde(default)]
+    pub executor_action: serde_json::Value,
     pub started_at: String,
     pub completed_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+impl ExecutionProcess {
+    /// The prompt and executor of a coding agent run, to run it again.
+    pub fn agent_request(&self) -> Option<(String, ExecutorProfileId)> {
+        let typ = self.executor_action.get("typ")?;
+        match typ.get("type")?.as_str()? {
+            "CodingAgentInitialRequest" | "CodingAgentFollowUpRequest" => {}
+            _ => return None,
+        }
+        let prompt = typ.get("prompt")?.as_str()?.to_string();
+        let profile = serde_json::from_value(typ.get("executor_profile_id")?.clone()).ok()?;
+        Some((prompt, profile))
+    }
+}
+
+/// Why the server failed a process that was still marked running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanReason {
+    ServerRestart,
+    ProcessGone,
+    ContainerMissing,
+}
+
+impl OrphanReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ServerRestart => "server restarted",
+            Self::ProcessGone => "process gone",
+            Self::ContainerMissing => "workspace missing",
+        }
+    }
+}
+
+/// Execution process the server marked failed because it was orphaned
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecutionProcessOrphan {
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub reason: OrphanReason,
+    pub detected_at: DateTime<Utc>,
+}
+
 /// Base coding agent types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -242,16 +419,688 @@ pub struct ExecutorProfileId {
     pub variant: Option<String>,
 }
 
+/// Executor installation/login state
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AvailabilityInfo {
+    LoginDetected { last_auth_timestamp: i64 },
+    InstallationFound,
+    NotFound,
+}
+
+/// Executor health as reported by the server
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecutorHealth {
+    pub executor: BaseCodingAgent,
+    pub availability: AvailabilityInfo,
+    pub rate_limited_at: Option<DateTime<Utc>>,
+    pub available: bool,
+    pub message: Option<String>,
+}
+
+/// Executor profiles file as returned by the server
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProfilesContent {
+    /// JSON of the form `{"executors": {EXECUTOR: {VARIANT: {EXECUTOR: settings}}}}`
+    pub content: String,
+    pub path: String,
+}
+
+/// A configured variant of an executor, e.g. CLAUDE_CODE/PLAN
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExecutorVariant {
+    pub executor: String,
+    pub name: String,
+    pub description: String,
+}
+
+impl ExecutorVariant {
+    /// Variant the server uses when none is given
+    pub const DEFAULT: &'static str = "DEFAULT";
+
+    /// Extract every executor variant from the profiles JSON, in file order.
+    pub fn parse_profiles(content: &str) -> serde_json::Result<Vec<Self>> {
+        let profiles: serde_json::Value = serde_json::from_str(content)?;
+        let mut variants = Vec::new();
+        let executors = profiles.get("executors").and_then(|e| e.as_object());
+        for (executor, configs) in executors.into_iter().flatten() {
+            for (name, config) in configs.as_object().into_iter().flatten() {
+                let settings = config.get(executor).and_then(|s| s.as_object());
+                variants.push(Self {
+                    executor: executor.clone(),
+                    name: name.clone(),
+                    description: settings.map(describe_settings).unwrap_or_default(),
+                });
+            }
+        }
+        Ok(variants)
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.name == Self::DEFAULT
+    }
+}
+
+/// Summarise the settings that matter when picking a variant: model, reasoning,
+/// temperature and tool policy.
+fn describe_settings(settings: &serde_json::Map<String, serde_json::Value>) -> String {
+    let text = |key: &str| settings.get(key).and_then(|v| v.as_str());
+    let flag = |key: &str| settings.get(key).and_then(|v| v.as_bool());
+
+    let mut parts = Vec::new();
+    if let Some(model) = text("model") {
+        parts.push(format!("model {}", model));
+    }
+    if let Some(effort) = text("model_reasoning_effort").or(text("reasoning_effort")) {
+        parts.push(format!("{} reasoning", effort));
+    }
+    if let Some(temperature) = settings.get("temperature").and_then(|v| v.as_f64()) {
+        parts.push(format!("temperature {}", temperature));
+    }
+
+    if flag("plan") == Some(true) || text("mode") == Some("plan") {
+        parts.push("plan only".to_string());
+    } else if flag("approvals") == Some(true)
+        || flag("yolo") == Some(false)
+        || flag("auto_approve") == Some(false)
+    {
+        parts.push("asks before using tools".to_string());
+    } else if [
+        "dangerously_skip_permissions",
+        "dangerously_allow_all",
+        "yolo",
+        "force",
+        "allow_all_tools",
+        "auto_approve",
+    ]
+    .iter()
+    .any(|key| flag(key) == Some(true))
+    {
+        parts.push("tools auto-approved".to_string());
+    }
+    if let Some(autonomy) = text("autonomy") {
+        parts.push(format!("autonomy {}", autonomy));
+    }
+    if let Some(sandbox) = text("sandbox") {
+        parts.push(format!("sandbox {}", sandbox));
+    }
+    if let Some(approval) = text("ask_for_approval") {
+        parts.push(format!("approval {}", approval));
+    }
+
+    parts.join(", ")
+}
+
+/// A team task waiting for a free worker slot
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QueuedTeamTask {
+    pub team_task_id: Uuid,
+    pub team_execution_id: Uuid,
+    pub task_id: Uuid,
+    pub workspace_id: Option<Uuid>,
+    pub task_title: String,
+    pub position: i32,
+    pub running: i32,
+    pub max_parallel_workers: i32,
+    pub estimated_start_at: DateTime<Utc>,
+}
+
+/// Where a team execution is, from planning its epic to finishing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamExecutionStatus {
+    Planning,
+    Planned,
+    Executing,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// A team of agents working through the subtasks of an epic, as far as the
+/// CLI uses it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeamExecution {
+    pub id: Uuid,
+    pub epic_task_id: Uuid,
+    pub epic_workspace_id: Option<Uuid>,
+    pub status: TeamExecutionStatus,
+    pub max_parallel_workers: i32,
+    pub error_message: Option<String>,
+}
+
+/// Create team execution request
+#[derive(Debug, Serialize)]
+pub struct CreateTeamExecutionRequest {
+    pub epic_task_id: Uuid,
+    /// Workspace whose branch the subtasks branch off
+    pub workspace_id: Option<Uuid>,
+    /// Workers at once; the planner setting if unset
+    pub max_parallel_workers: Option<i32>,
+}
+
+/// Where a team task is in its execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamTaskStatus {
+    Pending,
+    Blocked,
+    Assigned,
+    Running,
+    Completed,
+    Failed,
+    Skipped,
+}
+
+impl TeamTaskStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Blocked => "blocked",
+            Self::Assigned => "assigned",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+/// One subtask of a team execution, as far as the CLI uses it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeamTask {
+    pub id: Uuid,
+    pub team_execution_id: Uuid,
+    pub task_id: Uuid,
+    pub workspace_id: Option<Uuid>,
+    pub sequence_order: i32,
+    /// JSON array of the IDs of the team tasks this one waits for
+    pub depends_on: Option<String>,
+    pub status: TeamTaskStatus,
+    pub error_message: Option<String>,
+}
+
+impl TeamTask {
+    /// The team tasks this one waits for
+    pub fn dependencies(&self) -> Vec<Uuid> {
+        self.depends_on
+            .as_deref()
+            .and_then(|d| serde_json::from_str(d).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Plan produced by the team planner for an epic
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeamPlanOutput {
+    pub complexity: String,
+    pub requires_team: bool,
+    pub subtasks: Vec<PlannedSubtask>,
+    pub estimated_total_duration: Option<i32>,
+    pub reasoning: String,
+}
+
+/// One subtask of a team plan
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlannedSubtask {
+    pub title: String,
+    pub description: String,
+    pub required_skills: Vec<String>,
+    /// Indices of the subtasks this one waits for
+    pub depends_on: Vec<i32>,
+    pub complexity: i32,
+    pub estimated_duration: Option<i32>,
+    /// Repositories the subtask works in; empty means all of them
+    #[serde(default)]
+    pub repo_scopes: Vec<RepoScope>,
+}
+
+/// The part of a subtask's work that falls in one repository
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RepoScope {
+    pub repo_name: String,
+    pub hint: String,
+}
+
+/// Plan of a successful team execution saved for reuse.
+/// Subtasks refer to the epic as `{epic_title}`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlanTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub source_team_execution_id: Option<Uuid>,
+    pub plan: TeamPlanOutput,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Save plan template request
+#[derive(Debug, Serialize)]
+pub struct CreatePlanTemplate {
+    pub team_execution_id: Uuid,
+    pub name: Option<String>,
+}
+
+/// Plan from template request
+#[derive(Debug, Serialize)]
+pub struct PlanFromTemplateRequest {
+    pub template_id: Uuid,
+}
+
+/// Plan stored on a team execution
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeamPlanResponse {
+    pub plan: TeamPlanOutput,
+}
+
+/// Agent skill, as far as the CLI uses it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AgentSkill {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+}
+
+/// Agent profile, as far as the CLI uses it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AgentProfile {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub executor: String,
+    pub variant: Option<String>,
+    pub is_planner: bool,
+    pub is_reviewer: bool,
+    pub is_worker: bool,
+    pub max_concurrent_tasks: i32,
+    pub priority: i32,
+    pub active: bool,
+}
+
+impl AgentProfile {
+    /// The team roles the profile fills, e.g. "planner, worker"
+    pub fn roles(&self) -> String {
+        let roles: Vec<&str> = [
+            (self.is_planner, "planner"),
+            (self.is_worker, "worker"),
+            (self.is_reviewer, "reviewer"),
+        ]
+        .into_iter()
+        .filter_map(|(fills, role)| fills.then_some(role))
+        .collect();
+        if roles.is_empty() {
+            "no role".to_string()
+        } else {
+            roles.join(", ")
+        }
+    }
+}
+
+/// Agent profile changes; unset fields are left as they are
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateAgentProfile {
+    pub executor: Option<String>,
+    pub variant: Option<String>,
+    pub max_concurrent_tasks: Option<i32>,
+    pub priority: Option<i32>,
+    pub active: Option<bool>,
+}
+
+/// Limits the team planner applies when decomposing epics
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlannerConfig {
+    /// Minimum subtasks for an epic to run as a team
+    pub team_threshold: i32,
+    pub max_subtasks: i32,
+    /// Workers per team execution unless it sets its own
+    pub max_parallel_workers: i32,
+}
+
+/// Languages, frameworks and skills detected in a project's repositories
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RepoSkillProfile {
+    pub languages: Vec<String>,
+    pub frameworks: Vec<String>,
+    pub skills: Vec<String>,
+}
+
+/// Skills a worker profile is missing for a project
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProfileSkillSuggestion {
+    pub profile: AgentProfile,
+    pub missing_skills: Vec<AgentSkill>,
+}
+
+/// Skills a project's repositories call for
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectSkillSuggestions {
+    pub detected: RepoSkillProfile,
+    pub skills: Vec<AgentSkill>,
+    pub profiles: Vec<ProfileSkillSuggestion>,
+}
+
 /// Create task attempt body
 #[derive(Debug, Serialize)]
 pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_limits: Option<ResourceLimits>,
+    /// Branch to create; the server generates one when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
 }
 
-/// Workspace repository input
+/// Limits enforced on an attempt's coding agent
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ResourceLimits {
+    pub max_runtime_secs: Option<i64>,
+    pub max_cpu_secs: Option<i64>,
+    pub max_memory_mb: Option<i64>,
+}
+
+impl ResourceLimits {
+    /// Parse a spec such as `runtime=30m cpu=10m mem=2G`. Durations take an
+    /// `s`, `m` or `h` suffix (default seconds), memory `M` or `G` (default MiB).
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut limits = Self::default();
+        for part in spec.split([' ', ',']).filter(|p| !p.is_empty()) {
+            let Some((key, value)) = part.split_once('=') else {
+                bail!("Expected key=value, got '{}'", part);
+            };
+            match key {
+                "runtime" | "time" => {
+                    limits.max_runtime_secs = Some(parse_limit(value, &DURATION_UNITS)?)
+                }
+                "cpu" => limits.max_cpu_secs = Some(parse_limit(value, &DURATION_UNITS)?),
+                "mem" | "memory" => limits.max_memory_mb = Some(parse_limit(value, &MEMORY_UNITS)?),
+                _ => bail!("Unknown limit '{}' (expected runtime, cpu or mem)", key),
+            }
+        }
+        Ok(limits)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// e.g. "runtime 30m, CPU 10m, memory 2048 MiB"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(secs) = self.max_runtime_secs {
+            parts.push(format!("runtime {}", format_limit_secs(secs)));
+        }
+        if let Some(secs) = self.max_cpu_secs {
+            parts.push(format!("CPU {}", format_limit_secs(secs)));
+        }
+        if let Some(mb) = self.max_memory_mb {
+            parts.push(format!("memory {} MiB", mb));
+        }
+        parts.join(", ")
+    }
+}
+
+const DURATION_UNITS: [(&str, i64); 3] = [("s", 1), ("m", 60), ("h", 3600)];
+const MEMORY_UNITS: [(&str, i64); 2] = [("M", 1), ("G", 1024)];
+
+/// Parse a positive number with an optional unit suffix, scaled to the base unit.
+fn parse_limit(value: &str, units: &[(&str, i64)]) -> Result<i64> {
+    let (number, scale) = units
+        .iter()
+        .find_map(|(suffix, scale)| {
+            value
+                .strip_suffix(suffix)
+                .or_else(|| value.strip_suffix(&*suffix.to_lowercase()))
+                .map(|number| (number, *scale))
+        })
+        .unwrap_or((value, 1));
+    match number.parse::<i64>() {
+        Ok(n) if n > 0 => n
+            .checked_mul(scale)
+            .ok_or_else(|| anyhow::anyhow!("Limit value '{}' is too large", value)),
+        _ => bail!("Invalid limit value '{}'", value),
+    }
+}
+
+fn format_limit_secs(secs: i64) -> String {
+    if secs % 3600 == 0 {
+        format!("{}h", secs / 3600)
+    } else if secs % 60 == 0 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Limit that stopped a coding agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceLimitKind {
+    Runtime,
+    Cpu,
+    Memory,
+}
+
+impl ResourceLimitKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Runtime => "runtime",
+            Self::Cpu => "CPU",
+            Self::Memory => "memory",
+        }
+    }
+}
+
+/// Resource limits of a workspace and whether the last run hit one
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkspaceResourceLimits {
+    pub workspace_id: Uuid,
+    #[serde(flatten)]
+    pub limits: ResourceLimits,
+    pub exceeded_limit: Option<ResourceLimitKind>,
+    pub exceeded_at: Option<DateTime<Utc>>,
+}
+
+/// When a workspace is rebased onto its target branches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoRebaseTrigger {
+    /// Once a night, if a target branch has new commits
+    Nightly,
+    /// Whenever a target branch has new commits
+    TargetMoved,
+}
+
+impl AutoRebaseTrigger {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Nightly => "nightly",
+            Self::TargetMoved => "when the target moves",
+        }
+    }
+}
+
+/// Scheduled rebasing of a workspace, paused when a rebase runs into conflicts
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkspaceAutoRebase {
+    pub workspace_id: Uuid,
+    pub trigger: AutoRebaseTrigger,
+    pub paused_reason: Option<String>,
+    pub paused_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub last_rebased_at: Option<DateTime<Utc>>,
+}
+
+/// Set workspace auto-rebase request; also resumes a paused policy
+#[derive(Debug, Serialize)]
+pub struct SetWorkspaceAutoRebase {
+    pub trigger: AutoRebaseTrigger,
+}
+
+/// Free-form notes kept alongside a workspace
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkspaceNote {
+    pub workspace_id: Uuid,
+    pub body: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Set workspace notes request; blank notes are removed
 #[derive(Debug, Serialize)]
+pub struct SetWorkspaceNote {
+    pub body: String,
+}
+
+/// The final message of the latest coding agent run that completed in a
+/// workspace, kept as what the attempt did
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkspaceResult {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub execution_process_id: Option<Uuid>,
+    pub summary: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkspaceResult {
+    /// The first line of the summary that says something
+    pub fn headline(&self) -> &str {
+        self.summary
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+/// Server-wide policy for archiving finished workspaces and removing their worktrees
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RetentionPolicy {
+    /// Archive workspaces this many days after their last repository was merged
+    pub archive_merged_after_days: Option<u32>,
+    /// Remove the worktrees of cancelled tasks once nothing runs in them
+    pub delete_cancelled_worktrees: bool,
+    /// Delete tasks for good this many days after they went into the trash
+    #[serde(default)]
+    pub purge_trash_after_days: Option<u32>,
+}
+
+/// What a retention policy does to a workspace
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    Archive,
+    DeleteWorktree,
+}
+
+impl RetentionAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Archive => "Archive",
+            Self::DeleteWorktree => "Remove worktree",
+        }
+    }
+}
+
+/// A workspace a retention policy applies to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetentionCandidate {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub project_name: String,
+    pub task_title: String,
+    pub branch: String,
+    pub action: RetentionAction,
+    /// When the last repository was merged, or when the cancelled task was last updated
+    pub since: DateTime<Utc>,
+}
+
+/// Settings of this CLI kept on the server so they follow the user across
+/// machines; the local config file takes precedence, see
+/// [`crate::config::Config::apply_preferences`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UserPreferences {
+    /// Theme name as in the config file, e.g. "solarized"
+    pub theme: Option<String>,
+    /// Executor name as in the config file, e.g. "claude-code"
+    pub default_executor: Option<String>,
+    /// Board search queries by name, used as `@name` in a search
+    pub saved_filters: BTreeMap<String, String>,
+    /// Keys by action name, as in the `[keybindings]` table
+    pub keybindings: BTreeMap<String, Vec<String>>,
+}
+
+/// Why an attempt failed, as classified by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCategory {
+    SetupScript,
+    AgentCrash,
+    RateLimit,
+    Timeout,
+    MergeConflict,
+}
+
+impl FailureCategory {
+    pub const ALL: [FailureCategory; 5] = [
+        FailureCategory::SetupScript,
+        FailureCategory::AgentCrash,
+        FailureCategory::RateLimit,
+        FailureCategory::Timeout,
+        FailureCategory::MergeConflict,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SetupScript => "setup_script",
+            Self::AgentCrash => "agent_crash",
+            Self::RateLimit => "rate_limit",
+            Self::Timeout => "timeout",
+            Self::MergeConflict => "merge_conflict",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SetupScript => "setup script",
+            Self::AgentCrash => "agent crash",
+            Self::RateLimit => "rate limit",
+            Self::Timeout => "timeout",
+            Self::MergeConflict => "merge conflict",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::SetupScript => "⚙",
+            Self::AgentCrash => "✗",
+            Self::RateLimit => "⧗",
+            Self::Timeout => "◷",
+            Self::MergeConflict => "⇅",
+        }
+    }
+}
+
+/// The current failure of a workspace, with the task it belongs to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkspaceFailure {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    pub branch: String,
+    pub execution_process_id: Option<Uuid>,
+    pub category: FailureCategory,
+    /// The last line of output, or what went wrong, if known
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Workspace repository input
+#[derive(Debug, Clone, Serialize)]
 pub struct WorkspaceRepoInput {
     pub repo_id: Uuid,
     pub target_branch: String,
@@ -263,6 +1112,9 @@ pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Branch to create; the server generates one when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
 }
 
 /// Follow-up request
@@ -279,12 +1131,16 @@ pub struct CreateFollowUpAttempt {
 #[derive(Debug, Serialize)]
 pub struct MergeTaskAttemptRequest {
     pub repo_id: Uuid,
+    /// Go ahead even though the target branch is protected
+    pub confirm_protected: bool,
 }
 
 /// Push task attempt request
 #[derive(Debug, Serialize)]
 pub struct PushTaskAttemptRequest {
     pub repo_id: Uuid,
+    /// Go ahead even though the workspace branch is protected
+    pub confirm_protected: bool,
 }
 
 /// Rebase task attempt request
@@ -293,6 +1149,59 @@ pub struct RebaseTaskAttemptRequest {
     pub repo_id: Uuid,
     pub old_base_branch: Option<String>,
     pub new_base_branch: Option<String>,
+    /// Go ahead even though the workspace branch is protected
+    pub confirm_protected: bool,
+}
+
+/// Create pull request request
+#[derive(Debug, Serialize)]
+pub struct CreatePrRequest {
+    pub title: String,
+    pub body: Option<String>,
+    /// The repo's target branch unless set
+    pub target_branch: Option<String>,
+    pub draft: Option<bool>,
+    pub repo_id: Uuid,
+}
+
+/// Why the server couldn't open a pull request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PrError {
+    CliNotInstalled { provider: String },
+    CliNotLoggedIn { provider: String },
+    GitCliNotLoggedIn,
+    GitCliNotInstalled,
+    TargetBranchNotFound { branch: String },
+    UnsupportedProvider,
+}
+
+impl PrError {
+    pub fn summary(&self) -> String {
+        let cli = |provider: &str| match provider {
+            "git_hub" => "The GitHub CLI (gh)",
+            "azure_dev_ops" => "The Azure CLI (az)",
+            _ => "The git host's CLI",
+        };
+        match self {
+            PrError::CliNotInstalled { provider } => {
+                format!("{} is not installed on the server", cli(provider))
+            }
+            PrError::CliNotLoggedIn { provider } => {
+                format!("{} on the server is not logged in", cli(provider))
+            }
+            PrError::GitCliNotLoggedIn => {
+                "git on the server can't authenticate to the remote".to_string()
+            }
+            PrError::GitCliNotInstalled => "git is not installed on the server".to_string(),
+            PrError::TargetBranchNotFound { branch } => {
+                format!("Target branch '{}' is not on the remote", branch)
+            }
+            PrError::UnsupportedProvider => {
+                "Pull requests are only supported for GitHub and Azure DevOps remotes".to_string()
+            }
+        }
+    }
 }
 
 /// Git branch info
@@ -304,6 +1213,25 @@ pub struct GitBranch {
     pub last_commit_date: DateTime<Utc>,
 }
 
+/// A commit a merge, rebase or push would carry over
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PreviewCommit {
+    pub sha: String,
+    pub subject: String,
+}
+
+/// What a merge, rebase or push would do, as worked out by the server without doing it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GitOperationPreview {
+    /// Newest first
+    pub commits: Vec<PreviewCommit>,
+    pub fast_forward: bool,
+    pub behind: usize,
+    pub conflicted_files: Vec<String>,
+    /// Why the operation itself would be refused
+    pub blocker: Option<String>,
+}
+
 /// Branch status
 #[derive(Debug, Clone, Deserialize)]
 pub struct BranchStatus {
@@ -319,6 +1247,39 @@ pub struct BranchStatus {
     pub is_rebase_in_progress: bool,
     pub conflict_op: Option<String>,
     pub conflicted_files: Vec<String>,
+    #[serde(default)]
+    pub merges: Vec<Merge>,
+    /// Older servers don't protect branches
+    #[serde(default)]
+    pub target_branch_protection: Option<ProtectionMode>,
+    #[serde(default)]
+    pub branch_protection: Option<ProtectionMode>,
+}
+
+impl BranchStatus {
+    /// Pull requests opened for this branch, newest first.
+    pub fn pull_requests(&self) -> impl Iterator<Item = &PullRequestInfo> {
+        self.merges.iter().filter_map(|merge| match merge {
+            Merge::Pr { pr_info } => Some(pr_info),
+            Merge::Direct {} => None,
+        })
+    }
+}
+
+/// A merge of the workspace branch, either direct or through a pull request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Merge {
+    Direct {},
+    Pr { pr_info: PullRequestInfo },
+}
+
+/// Pull request info
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestInfo {
+    pub number: i64,
+    pub url: String,
+    pub status: String,
 }
 
 /// Repository branch status
@@ -330,9 +1291,29 @@ pub struct RepoBranchStatus {
     pub status: BranchStatus,
 }
 
+/// A commit of a workspace branch
+#[derive(Debug, Clone, Deserialize)]
+pub struct BranchCommit {
+    pub sha: String,
+    pub subject: String,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub authored_at: DateTime<Utc>,
+}
+
+/// Commits of a workspace branch that the target branch of a repo doesn't have
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoCommits {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub target_branch_name: String,
+    /// Newest first
+    pub commits: Vec<BranchCommit>,
+}
+
 /// Diff change kind
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
 pub enum DiffChangeKind {
     Added,
     Deleted,
@@ -393,3 +1374,177 @@ pub struct WorkspaceSummary {
     pub has_unseen_turns: bool,
     pub pr_status: Option<String>,
 }
+
+/// Project budget state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetState {
+    Unlimited,
+    Ok,
+    Warning,
+    Exceeded,
+    Overridden,
+}
+
+impl BudgetState {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BudgetState::Unlimited => "no budget",
+            BudgetState::Ok => "ok",
+            BudgetState::Warning => "warning",
+            BudgetState::Exceeded => "exceeded",
+            BudgetState::Overridden => "overridden",
+        }
+    }
+}
+
+/// Estimated spend for one executor
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecutorSpend {
+    pub executor: String,
+    pub runtime_seconds: i64,
+    pub estimated_cost_cents: i64,
+}
+
+/// Project budget status for the current month
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectBudgetStatus {
+    pub project_id: Uuid,
+    pub month: String,
+    pub monthly_cap_cents: Option<i64>,
+    pub spent_cents: i64,
+    pub percent_used: Option<f64>,
+    pub state: BudgetState,
+    pub by_executor: Vec<ExecutorSpend>,
+}
+
+/// Set project budget request
+#[derive(Debug, Serialize)]
+pub struct SetProjectBudget {
+    pub monthly_cap_cents: Option<i64>,
+}
+
+/// Budget override request
+#[derive(Debug, Serialize)]
+pub struct BudgetOverrideRequest {
+    pub enabled: bool,
+}
+
+/// Branch naming rules of a project
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectBranchPolicy {
+    pub project_id: Uuid,
+    pub allowed_prefixes: Vec<String>,
+    pub max_length: Option<i64>,
+    pub forbidden_chars: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Set project branch policy request
+#[derive(Debug, Serialize)]
+pub struct SetProjectBranchPolicy {
+    pub allowed_prefixes: Vec<String>,
+    pub max_length: Option<i64>,
+    pub forbidden_chars: String,
+}
+
+/// One way in which a branch name breaks a project's policy
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BranchPolicyViolation {
+    MissingPrefix { allowed_prefixes: Vec<String> },
+    TooLong { length: usize, max_length: usize },
+    ForbiddenCharacters { characters: String },
+}
+
+impl BranchPolicyViolation {
+    pub fn summary(&self) -> String {
+        match self {
+            BranchPolicyViolation::MissingPrefix { allowed_prefixes } => {
+                format!("must start with one of: {}", allowed_prefixes.join(", "))
+            }
+            BranchPolicyViolation::TooLong { length, max_length } => {
+                format!("is {} characters long, the limit is {}", length, max_length)
+            }
+            BranchPolicyViolation::ForbiddenCharacters { characters } => {
+                format!("contains forbidden characters: {}", characters)
+            }
+        }
+    }
+}
+
+/// What happens to a merge, push or rebase that writes to a protected branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProtectionMode {
+    /// Allowed once confirmed
+    #[default]
+    Confirm,
+    /// Refused until the protection is lifted
+    Block,
+}
+
+/// A protected branch name or glob of a repo
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtectedBranch {
+    pub id: Uuid,
+    pub repo_id: Uuid,
+    pub pattern: String,
+    pub mode: ProtectionMode,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Protect branch request
+#[derive(Debug, Serialize)]
+pub struct CreateProtectedBranch {
+    pub pattern: String,
+    pub mode: ProtectionMode,
+}
+
+/// A merge, push or rebase the server refused because its branch is protected
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProtectedBranchViolation {
+    pub branch: String,
+    pub pattern: String,
+    pub mode: ProtectionMode,
+}
+
+impl ProtectedBranchViolation {
+    pub fn summary(&self) -> String {
+        match self.mode {
+            ProtectionMode::Confirm => format!(
+                "Branch '{}' is protected (matches '{}'); confirm to continue",
+                self.branch, self.pattern
+            ),
+            ProtectionMode::Block => format!(
+                "Branch '{}' is protected (matches '{}') and cannot be changed from here. \
+                 Remove the protection to change it.",
+                self.branch, self.pattern
+            ),
+        }
+    }
+}
+
+/// A branch name the server rejected, with a name that would pass
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BranchPolicyReport {
+    pub branch: String,
+    pub violations: Vec<BranchPolicyViolation>,
+    pub suggested_branch: String,
+}
+
+impl BranchPolicyReport {
+    pub fn summary(&self) -> String {
+        let violations = self
+            .violations
+            .iter()
+            .map(BranchPolicyViolation::summary)
+            .collect::<Vec<_>>()
+            .join("; ");
+        format!(
+            "Branch '{}' violates the project's naming policy ({}). Try '{}' instead.",
+            self.branch, violations, self.suggested_branch
+        )
+    }
+}
@@ -0,0 +1,82 @@
+//! `:` command palette: a fuzzy finder over the actions of the current view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::App,
+    ui::components::{centered_rect, selected_style},
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(palette) = &app.palette else {
+        return;
+    };
+    let area = centered_rect(60, 50, frame.area());
+    let matches = app.palette_matches();
+    let block = Block::default()
+        .title(" Commands ")
+        .title_bottom(" ↑/↓ Select │ Enter Run │ Esc Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner);
+
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled(": ", Style::default().fg(Color::Cyan)),
+        Span::raw(palette.query.as_str()),
+    ]));
+    frame.render_widget(prompt, chunks[0]);
+    frame.set_cursor_position((
+        chunks[0].x + 2 + palette.query.chars().count() as u16,
+        chunks[0].y,
+    ));
+
+    if matches.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "No matching commands",
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    // Scroll just far enough to keep the selection in view
+    let height = chunks[1].height as usize;
+    let offset = (palette.index + 1).saturating_sub(height);
+    let width = chunks[1].width as usize;
+    let lines: Vec<Line> = matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(i, (action, label))| {
+            // The key goes on the right, so the palette also teaches it
+            let key = app.config.keybindings.describe(*action);
+            let gap = width.saturating_sub(label.chars().count() + key.chars().count());
+            let line = Line::from(vec![
+                Span::styled(*label, Style::default().fg(Color::White)),
+                Span::raw(" ".repeat(gap)),
+                Span::styled(key, Style::default().fg(Color::Yellow)),
+            ]);
+            if i == palette.index {
+                line.style(selected_style())
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+}
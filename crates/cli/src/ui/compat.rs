@@ -0,0 +1,183 @@
+//! Compatibility mode for limited terminals: serial consoles, the Linux console
+//! and old terminal emulators.
+//!
+//! Like the theme, it works on the rendered frame: after the theme has coloured
+//! it, every colour is brought down to the 16 ANSI colours, box drawing and other
+//! symbols are swapped for ASCII, and italics are dropped.
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+use super::theme::Theme;
+
+/// Set to `1` or `0` to force the compatibility mode on or off
+pub const COMPAT_ENV: &str = "VIBE_KANBAN_CLI_COMPAT";
+
+/// The 16 ANSI colours, as xterm shows them by default
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (128, 0, 0)),
+    (Color::Green, (0, 128, 0)),
+    (Color::Yellow, (128, 128, 0)),
+    (Color::Blue, (0, 0, 128)),
+    (Color::Magenta, (128, 0, 128)),
+    (Color::Cyan, (0, 128, 128)),
+    (Color::Gray, (192, 192, 192)),
+    (Color::DarkGray, (128, 128, 128)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (0, 0, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// ASCII stand-ins for the symbols the views draw
+const SYMBOLS: &[(char, char)] = &[
+    ('↑', '^'),
+    ('↓', 'v'),
+    ('←', '<'),
+    ('→', '>'),
+    ('⇅', '^'),
+    ('▸', '>'),
+    ('◂', '<'),
+    ('›', '>'),
+    ('·', '.'),
+    ('•', '*'),
+    ('●', '*'),
+    ('★', '*'),
+    ('◆', '*'),
+    ('✓', '+'),
+    ('✗', 'x'),
+    ('×', 'x'),
+    ('⚠', '!'),
+    ('ⓘ', 'i'),
+    ('⊘', '-'),
+    ('⧗', '~'),
+    ('◷', '~'),
+    ('⚙', '*'),
+    ('✎', '*'),
+    ('…', '.'),
+    ('–', '-'),
+    ('⏹', '#'),
+    ('⏸', '='),
+    ('█', '#'),
+    ('▣', '#'),
+    ('░', '.'),
+    // Sparkline bars, lowest to highest
+    ('▁', '_'),
+    ('▂', '.'),
+    ('▃', '-'),
+    ('▄', '='),
+    ('▅', '+'),
+    ('▆', '*'),
+    ('▇', '#'),
+    // Spinner frames, so it still turns
+    ('⠋', '|'),
+    ('⠙', '/'),
+    ('⠹', '-'),
+    ('⠸', '\\'),
+    ('⠼', '|'),
+    ('⠴', '/'),
+    ('⠦', '-'),
+    ('⠧', '\\'),
+    ('⠇', '|'),
+    ('⠏', '/'),
+];
+
+/// Whether to render for a limited terminal, judging by its name and the locale.
+pub fn detect() -> bool {
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+    match var(COMPAT_ENV).as_deref() {
+        Some("1") => return true,
+        Some("0") => return false,
+        _ => {}
+    }
+    // Serial consoles and the terminals they emulate
+    if var("TERM").is_some_and(|term| {
+        matches!(
+            term.as_str(),
+            "dumb" | "linux" | "ansi" | "vt100" | "vt102" | "vt220" | "vt320" | "cons25" | "sun"
+        )
+    }) {
+        return true;
+    }
+    // Without a UTF-8 locale the box drawing comes out garbled
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(var)
+        .is_some_and(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            !locale.contains("utf-8") && !locale.contains("utf8")
+        })
+}
+
+/// Bring a frame coloured with `theme` down to what a limited terminal can show.
+pub fn apply(theme: Theme, buffer: &mut Buffer) {
+    let background = nearest(theme.background());
+    for cell in &mut buffer.content {
+        let highlighted = cell.bg != theme.background();
+        let fg = nearest(cell.fg);
+        let bg = nearest(cell.bg);
+        // Highlights that come out black, or as the background, would be lost;
+        // reverse video keeps them visible as in the monochrome theme
+        if highlighted && (bg == Color::Black || bg == background) {
+            cell.modifier.insert(Modifier::REVERSED);
+            cell.set_bg(background);
+        } else {
+            cell.set_bg(bg);
+        }
+        cell.set_fg(fg);
+        cell.modifier.remove(Modifier::ITALIC);
+
+        if !cell.symbol().is_ascii() {
+            let symbol = cell.symbol().chars().next().map_or(' ', ascii);
+            cell.set_char(symbol);
+        }
+    }
+}
+
+/// The ANSI colour closest to `color`; named colours and the terminal's
+/// default are kept.
+fn nearest(color: Color) -> Color {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) if i < 16 => return ANSI[i as usize].0,
+        Color::Indexed(i) if i < 232 => {
+            // 6x6x6 colour cube
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        Color::Indexed(i) => {
+            let gray = 8 + (i - 232) * 10;
+            (gray, gray, gray)
+        }
+        color => return color,
+    };
+    let distance = |(r2, g2, b2): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+    ANSI.iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map_or(color, |(ansi, _)| *ansi)
+}
+
+/// An ASCII character standing in for `c`.
+fn ascii(c: char) -> char {
+    if let Some((_, replacement)) = SYMBOLS.iter().find(|(symbol, _)| *symbol == c) {
+        return *replacement;
+    }
+    match c {
+        '─' | '━' | '═' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' => '-',
+        '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' => '|',
+        // Corners and junctions of boxes, square or rounded
+        '\u{2500}'..='\u{257f}' => '+',
+        _ => '?',
+    }
+}
@@ -1,14 +1,29 @@
 //! Reusable UI components.
 
+use std::{cell::Cell, time::Instant};
+
 use ratatui::{
-    layout::{Alignment, Rect},
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListState, Paragraph, Widget, Wrap},
     Frame,
 };
 
-use crate::app::App;
+use crate::{
+    app::App,
+    batch::StepState,
+    config::Animation,
+    keymap::{self, Action},
+    types::{FailureCategory, GitOperationPreview},
+};
+
+/// Frames of the spinner shown while requests are in flight
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Shown in place of the spinner with animations off
+const SPINNER_STILL: &str = "…";
 
 /// Render the header bar.
 pub fn render_header(frame: &mut Frame, area: Rect, title: &str) {
@@ -33,23 +48,139 @@ pub fn render_header(frame: &mut Frame, area: Rect, title: &str) {
 
 /// Render the status bar at the bottom.
 pub fn render_status_bar(frame: &mut Frame, area: Rect, app: &App) {
-    let (message, style) = if let Some(ref err) = app.error_message {
-        (err.as_str(), Style::default().fg(Color::Red))
+    let keys = &app.config.keybindings;
+    let mut spans = Vec::new();
+    if app.live_updates {
+        spans.push(Span::styled("● ", Style::default().fg(Color::Green)));
+    }
+    if app.follow_mode {
+        spans.push(Span::styled(
+            " FOLLOW ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    if let (Some(since), Some(message)) = (app.loading_since, app.loading_message()) {
+        spans.push(spinner(since, &app.config.animation));
+        spans.push(Span::styled(
+            format!(" {}  ", message),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    spans.extend(if let Some(ref err) = app.error_message {
+        vec![Span::styled(err.as_str(), Style::default().fg(Color::Red))]
     } else if let Some(ref status) = app.status_message {
-        (status.as_str(), Style::default().fg(Color::Yellow))
+        vec![Span::styled(status.as_str(), Style::default().fg(Color::Yellow))]
+    } else if app.is_loading() {
+        Vec::new()
     } else {
-        ("Press ? for help", Style::default().fg(Color::DarkGray))
-    };
+        vec![Span::styled(
+            view_key_hints(app),
+            Style::default().fg(Color::DarkGray),
+        )]
+    });
+    if app.error_message.is_some() {
+        let mut actions = Vec::new();
+        if app.failed_action.is_some() {
+            actions.push(format!("{} retry", keys.describe(Action::Retry)));
+        }
+        if app.error_detail.as_ref().is_some_and(|d| d.is_conflict()) {
+            actions.push(format!(
+                "{} resolve",
+                keys.describe(Action::ResolveConflicts)
+            ));
+        }
+        if app.error_detail.is_some() {
+            actions.push(format!("{} details", keys.describe(Action::ErrorDetails)));
+        }
+        if !actions.is_empty() {
+            spans.push(Span::styled(
+                format!("  ({})", actions.join(" · ")),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
 
-    let status = Paragraph::new(Line::from(vec![Span::styled(message, style)]))
-        .block(
-            Block::default()
-                .borders(Borders::TOP)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .alignment(Alignment::Left);
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
 
-    frame.render_widget(status, area);
+    let connection = connection_line(app);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(connection.width() as u16),
+        ])
+        .split(inner);
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).alignment(Alignment::Left),
+        chunks[0],
+    );
+    frame.render_widget(connection, chunks[1]);
+}
+
+/// The keys of the current view's main actions, as bound in the active keymap
+fn view_key_hints(app: &App) -> String {
+    let keys = &app.config.keybindings;
+    let mut actions: Vec<Action> = Vec::new();
+    let hints: Vec<String> = keymap::function_keys(app.view)
+        .into_iter()
+        .flatten()
+        .filter(|(action, _)| {
+            let new = !actions.contains(action);
+            actions.push(*action);
+            new
+        })
+        .map(|(action, label)| format!("{} {}", keys.describe(action), label))
+        .collect();
+    if hints.is_empty() {
+        format!("Press {} for help", keys.describe(Action::Help))
+    } else {
+        hints.join(" · ")
+    }
+}
+
+/// Last refresh, server health and server URL, for the right of the status bar
+fn connection_line(app: &App) -> Line<'static> {
+    let mut spans = vec![Span::raw("  ")];
+    if let Some(at) = app.last_refreshed_at {
+        spans.push(Span::styled(
+            format!(
+                "refreshed {}  ",
+                app.config.time_zone.format(at, "%H:%M:%S")
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let dot = match app.server_reachable {
+        Some(true) => Color::Green,
+        Some(false) => Color::Red,
+        None => Color::DarkGray,
+    };
+    spans.push(Span::styled("● ", Style::default().fg(dot)));
+    spans.push(Span::styled(
+        format!("{} ", app.client.base_url()),
+        Style::default().fg(Color::Gray),
+    ));
+    Line::from(spans)
+}
+
+/// Spinner for work that started at `since`.
+pub fn spinner(since: Instant, animation: &Animation) -> Span<'static> {
+    let symbol = if animation.enabled {
+        let frame_time = animation.spinner_frame_time().as_millis().max(1);
+        let frame = since.elapsed().as_millis() / frame_time;
+        SPINNER_FRAMES[frame as usize % SPINNER_FRAMES.len()]
+    } else {
+        SPINNER_STILL
+    };
+    Span::styled(symbol, Style::default().fg(Color::Cyan))
 }
 
 /// Render keyboard hints at the bottom.
@@ -88,6 +219,23 @@ pub fn selected_style() -> Style {
         .add_modifier(Modifier::BOLD)
 }
 
+/// Render `list` scrolled so that row `selected` is visible. The list only
+/// scrolls once the selection reaches an edge, so `offset` carries the first
+/// visible row from one frame to the next.
+pub fn render_scrolled_list(
+    frame: &mut Frame,
+    area: Rect,
+    list: List,
+    selected: usize,
+    offset: &Cell<usize>,
+) {
+    let mut state = ListState::default()
+        .with_offset(offset.get())
+        .with_selected(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+    offset.set(state.offset());
+}
+
 /// Style for normal items.
 pub fn normal_style() -> Style {
     Style::default()
@@ -102,3 +250,308 @@ pub fn focused_border_style() -> Style {
 pub fn unfocused_border_style() -> Style {
     Style::default().fg(Color::DarkGray)
 }
+
+/// Style for an invalid value and what is wrong with it.
+pub fn error_style() -> Style {
+    Style::default().fg(Color::Red)
+}
+
+/// Style for a failure category; failures that go away on a retry are yellow.
+pub fn failure_style(category: FailureCategory) -> Style {
+    match category {
+        FailureCategory::RateLimit | FailureCategory::Timeout => Style::default().fg(Color::Yellow),
+        _ => Style::default().fg(Color::Red),
+    }
+}
+
+/// Create a rect centered in `r`, sized as a percentage of it.
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// Render a y/n confirmation modal asking `prompt`, centered over the screen.
+pub fn render_confirmation(frame: &mut Frame, title: &str, prompt: &str, details: Vec<Line>) {
+    // Room for the details below the question, if there are any
+    let height = if details.is_empty() { 20 } else { 50 };
+    let area = centered_rect(60, height, frame.area());
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(prompt, Style::default().fg(Color::White))),
+    ];
+    if !details.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(details.into_iter().map(Line::left_aligned));
+    }
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .title(format!(" {} ", title))
+                .title_bottom(" y Confirm │ n/Esc Cancel ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// Render a one-line text input modal over the screen, with the cursor at the
+/// end of `value` and `placeholder` shown while it is empty.
+pub fn render_input_popup(frame: &mut Frame, title: &str, value: &str, placeholder: &str) {
+    let outer = centered_rect(60, 20, frame.area());
+    let area = Rect {
+        y: outer.y + outer.height.saturating_sub(3) / 2,
+        height: 3.min(outer.height),
+        ..outer
+    };
+    let text = if value.is_empty() {
+        Span::styled(placeholder, Style::default().fg(Color::DarkGray))
+    } else {
+        Span::styled(value, Style::default().fg(Color::White))
+    };
+    let paragraph = Paragraph::new(Line::from(text)).block(
+        Block::default()
+            .title(format!(" {} ", title))
+            .title_bottom(" Enter Save │ Esc Cancel ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+    let cursor_x = (area.x + 1 + value.chars().count() as u16).min(area.right().saturating_sub(2));
+    frame.set_cursor_position((cursor_x, area.y + 1));
+}
+
+/// Render the F1-F10 shortcut bar of the current view on the line between the
+/// hints and the status bar.
+pub fn render_function_keys(frame: &mut Frame, app: &App) {
+    let screen = frame.area();
+    if screen.height < 4 {
+        return;
+    }
+    let spans: Vec<Span> = keymap::function_keys(app.view)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, key)| {
+            let label = key.map_or("", |(_, label)| label);
+            [
+                Span::styled(format!("{:>2}", i + 1), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("{:<8}", label),
+                    Style::default().fg(Color::Black).bg(Color::Cyan),
+                ),
+                Span::raw(" "),
+            ]
+        })
+        .collect();
+    let area = Rect {
+        x: screen.x,
+        y: screen.bottom() - 3,
+        width: screen.width,
+        height: 1,
+    };
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Render the progress of a multi-step operation stacked above the hints and
+/// status bar: how many steps are done, the running one and the failures so far.
+pub fn render_batch_progress(frame: &mut Frame, app: &App) {
+    /// Step lines shown around the running step
+    const MAX_STEPS: usize = 6;
+
+    let Some(batch) = &app.batch else {
+        return;
+    };
+    let keys = &app.config.keybindings;
+    let total = batch.steps.len();
+    // Keep the running step, or the last one to finish, in view
+    let focus = batch
+        .steps
+        .iter()
+        .position(|step| step.state == StepState::Running)
+        .or_else(|| {
+            batch
+                .steps
+                .iter()
+                .rposition(|step| step.state != StepState::Pending)
+        })
+        .unwrap_or(0);
+    let first = focus
+        .saturating_sub(MAX_STEPS / 2)
+        .min(total.saturating_sub(MAX_STEPS));
+
+    let mut lines: Vec<Line> = batch
+        .steps
+        .iter()
+        .skip(first)
+        .take(MAX_STEPS)
+        .map(|step| {
+            let (icon, style) = match &step.state {
+                StepState::Pending => (Span::raw("·"), Style::default().fg(Color::DarkGray)),
+                StepState::Running => (
+                    spinner(batch.started_at, &app.config.animation),
+                    Style::default().fg(Color::White),
+                ),
+                StepState::Done => (
+                    Span::styled("✓", Style::default().fg(Color::Green)),
+                    Style::default().fg(Color::Gray),
+                ),
+                StepState::Failed(_) => (
+                    Span::styled("✗", Style::default().fg(Color::Red)),
+                    Style::default().fg(Color::Red),
+                ),
+                StepState::Skipped => (Span::raw("–"), Style::default().fg(Color::DarkGray)),
+            };
+            let mut spans = vec![icon, Span::styled(format!(" {}", step.label), style)];
+            if let StepState::Failed(error) = &step.state {
+                spans.push(Span::styled(
+                    format!(": {}", error),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    if total > first + MAX_STEPS {
+        lines.push(Line::styled(
+            format!("… {} more", total - first - MAX_STEPS),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let mut title = format!(" {} · {}/{} done", batch.title, batch.done(), total);
+    if batch.failed() > 0 {
+        title.push_str(&format!(" · {} failed", batch.failed()));
+    }
+    title.push(' ');
+    let hint = if batch.is_finished() {
+        format!(" {} dismiss ", keys.describe(Action::CancelSteps))
+    } else {
+        format!(" {} cancel remaining ", keys.describe(Action::CancelSteps))
+    };
+    let border = if batch.failed() > 0 {
+        Color::Red
+    } else {
+        Color::Cyan
+    };
+
+    // Stacked on top of the hints and status bar at the bottom of every view
+    let screen = frame.area();
+    let height = (lines.len() as u16 + 2).min(screen.height.saturating_sub(4));
+    let area = Rect {
+        x: screen.x,
+        y: screen.bottom().saturating_sub(4 + height),
+        width: screen.width,
+        height,
+    };
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .title_bottom(hint)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(widget, area);
+}
+
+/// What a previewed merge, push or rebase would do, for the confirmation modal.
+pub fn git_preview_lines(preview: &GitOperationPreview) -> Vec<Line<'static>> {
+    /// Commits listed before the rest are summarised
+    const MAX_COMMITS: usize = 5;
+
+    let mut lines = Vec::new();
+    if preview.commits.is_empty() {
+        lines.push(Line::styled(
+            "No commits to carry over",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        lines.push(Line::from(format!(
+            "{} commit{}:",
+            preview.commits.len(),
+            if preview.commits.len() == 1 { "" } else { "s" }
+        )));
+        for commit in preview.commits.iter().take(MAX_COMMITS) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{} ", commit.sha.get(..7).unwrap_or(&commit.sha)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(commit.subject.clone()),
+            ]));
+        }
+        if preview.commits.len() > MAX_COMMITS {
+            lines.push(Line::styled(
+                format!("… and {} more", preview.commits.len() - MAX_COMMITS),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
+
+    lines.push(if !preview.conflicted_files.is_empty() {
+        Line::styled(
+            format!("Conflicts in {}", preview.conflicted_files.join(", ")),
+            Style::default().fg(Color::Red),
+        )
+    } else if preview.fast_forward {
+        Line::styled(
+            "Fast-forward, no conflicts",
+            Style::default().fg(Color::Green),
+        )
+    } else {
+        Line::styled("No conflicts", Style::default().fg(Color::Green))
+    });
+    if let Some(blocker) = &preview.blocker {
+        lines.push(Line::styled(
+            format!("⚠ {}", blocker),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    lines
+}
+
+/// A single line of text that links to `url`, written as an OSC 8 hyperlink when
+/// `enabled` and drawn as plain text otherwise.
+pub struct Hyperlink<'a> {
+    pub text: &'a str,
+    pub url: &'a str,
+    pub style: Style,
+    pub enabled: bool,
+}
+
+impl Widget for Hyperlink<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Line::styled(self.text, self.style).render(area, buf);
+        if !self.enabled || !self.text.is_ascii() || !self.url.is_ascii() {
+            return;
+        }
+        // Ratatui measures a cell holding an escape sequence as two columns wide, so
+        // the link is written two characters per cell and the next cell is skipped
+        let chars: Vec<char> = self.text.chars().take(area.width as usize).collect();
+        for (i, pair) in chars.chunks(2).enumerate() {
+            let pair: String = pair.iter().collect();
+            buf[(area.x + i as u16 * 2, area.y)]
+                .set_symbol(&format!("\x1b]8;;{}\x07{}\x1b]8;;\x07", self.url, pair));
+        }
+    }
+}
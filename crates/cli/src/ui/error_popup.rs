@@ -0,0 +1,69 @@
+//! Error detail popup, opened with `E` while an error is shown.
+
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::{app::App, ui::components::centered_rect};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(ref detail) = app.error_detail else {
+        return;
+    };
+
+    let area = centered_rect(80, 70, frame.area());
+    let label = Style::default().fg(Color::Gray);
+    let heading = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from(Span::styled("Message", heading))];
+    lines.extend(
+        detail
+            .message
+            .lines()
+            .map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(Color::Red)))),
+    );
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("HTTP status: ", label),
+        Span::raw(
+            detail
+                .status
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "none (no response)".to_string()),
+        ),
+    ]));
+
+    if let Some(ref data) = detail.error_data {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Error data", heading)));
+        let pretty = serde_json::to_string_pretty(data).unwrap_or_else(|_| data.to_string());
+        lines.extend(pretty.lines().map(|l| Line::from(l.to_string())));
+    }
+
+    if !detail.next_steps.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Suggested next steps", heading)));
+        lines.extend(
+            detail
+                .next_steps
+                .iter()
+                .map(|step| Line::from(format!("  • {}", step))),
+        );
+    }
+
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Error Details ")
+            .title_bottom(" c Copy │ Esc Close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
@@ -0,0 +1,52 @@
+//! Executor filter popup: narrows the board to the tasks of one executor.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::App,
+    ui::components::{centered_rect, selected_style},
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(selected) = app.executor_picker else {
+        return;
+    };
+    let options = app.executor_filter_options();
+    let outer = centered_rect(40, 40, frame.area());
+    // Just tall enough for the list, centred in the usual popup area
+    let height = (options.len() as u16 + 3).min(outer.height);
+    let area = Rect {
+        y: outer.y + (outer.height - height) / 2,
+        height,
+        ..outer
+    };
+    let block = Block::default()
+        .title(" Executor ")
+        .title_bottom(" ↑/↓ Select │ Enter Filter │ Esc Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let lines: Vec<Line> = std::iter::once("All executors")
+        .chain(options)
+        .enumerate()
+        .map(|(i, label)| {
+            if i == selected {
+                Line::from(Span::raw(format!("▸ {}", label))).style(selected_style())
+            } else {
+                Line::from(Span::styled(
+                    format!("  {}", label),
+                    Style::default().fg(Color::White),
+                ))
+            }
+        })
+        .collect();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
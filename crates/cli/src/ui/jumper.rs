@@ -0,0 +1,92 @@
+//! Ctrl+P jumper popup: a fuzzy finder over every project and task.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::{App, JumpTarget},
+    ui::{
+        components::{centered_rect, selected_style},
+        views::tasks::status_color,
+    },
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 60, frame.area());
+    let matches = app.jumper_matches();
+    let block = Block::default()
+        .title(" Jump To ")
+        .title_bottom(format!(
+            " {} of {} │ ↑/↓ Select │ Enter Jump │ Esc Close ",
+            matches.len(),
+            app.jump_targets.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner);
+
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(app.jump_query.as_str()),
+    ]));
+    frame.render_widget(prompt, chunks[0]);
+    frame.set_cursor_position((
+        chunks[0].x + 2 + app.jump_query.chars().count() as u16,
+        chunks[0].y,
+    ));
+
+    if matches.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "No matching projects or tasks",
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    // Scroll just far enough to keep the selection in view
+    let height = chunks[1].height as usize;
+    let offset = (app.jump_index + 1).saturating_sub(height);
+    let lines: Vec<Line> = matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(i, target)| {
+            let line = match target {
+                JumpTarget::Project(project) => Line::from(vec![
+                    Span::styled("▣ ", Style::default().fg(Color::Cyan)),
+                    Span::styled(project.name.as_str(), Style::default().fg(Color::White)),
+                    Span::styled("  project", Style::default().fg(Color::DarkGray)),
+                ]),
+                JumpTarget::Task { project, task } => Line::from(vec![
+                    Span::styled("• ", Style::default().fg(status_color(task.task.status))),
+                    Span::styled(task.task.title.as_str(), Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("  {} · {}", project.name, task.task.status.display_name()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]),
+            };
+            if i == app.jump_index {
+                line.style(selected_style())
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+}
@@ -0,0 +1,87 @@
+//! Link hint popup, opened with `o`: each link on screen gets a short label that
+//! opens it in the browser when typed.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear},
+};
+
+use crate::{
+    app::App,
+    links,
+    ui::components::{Hyperlink, centered_rect},
+};
+
+/// Width of the label and source columns
+const SOURCE_WIDTH: usize = 28;
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let area = centered_rect(80, 50, frame.area());
+    let typed = if app.link_hint_input.is_empty() {
+        String::new()
+    } else {
+        format!(" typed: {} │", app.link_hint_input)
+    };
+    let block = Block::default()
+        .title(" Open Link ")
+        .title_bottom(format!("{} type a label │ Esc Close ", typed))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let hyperlinks = links::hyperlinks_supported();
+    for (i, hint) in app.link_hints.iter().enumerate() {
+        if i as u16 >= inner.height {
+            break;
+        }
+        let row = Rect {
+            y: inner.y + i as u16,
+            height: 1,
+            ..inner
+        };
+        // Dim labels that no longer match what has been typed
+        let matches = hint.label.starts_with(&app.link_hint_input);
+        let label_style = if matches {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let source: String = hint.source.chars().take(SOURCE_WIDTH - 1).collect();
+        let prefix = Line::from(vec![
+            Span::styled(format!(" {} ", hint.label), label_style),
+            Span::raw(" "),
+            Span::styled(
+                format!("{:width$}", source, width = SOURCE_WIDTH),
+                Style::default().fg(Color::Gray),
+            ),
+        ]);
+        let prefix_width = prefix.width() as u16;
+        frame.render_widget(prefix, row);
+
+        let url_area = Rect {
+            x: row.x + prefix_width,
+            width: row.width.saturating_sub(prefix_width),
+            ..row
+        };
+        frame.render_widget(
+            Hyperlink {
+                text: &hint.url,
+                url: &hint.url,
+                style: Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::UNDERLINED),
+                enabled: hyperlinks,
+            },
+            url_area,
+        );
+    }
+}
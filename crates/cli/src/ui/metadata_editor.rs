@@ -0,0 +1,161 @@
+//! Task metadata popup: the keys of the metadata on the left, the highlighted
+//! value or the entry being typed on the right.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::{App, MetadataEntry},
+    metadata,
+    ui::components::centered_rect,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(editor) = &app.metadata_editor else {
+        return;
+    };
+    let area = centered_rect(80, 70, frame.area());
+    let hints = if editor.entry.is_some() {
+        " Tab Key/Value │ Enter New line │ Ctrl+S Save │ Esc Cancel "
+    } else {
+        " ↑/↓ Key │ Enter Edit │ n New │ d Delete │ Ctrl+S Save │ Esc Close "
+    };
+    let title = if editor.modified {
+        format!(" Metadata of {} (modified) ", editor.title)
+    } else {
+        format!(" Metadata of {} ", editor.title)
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(hints)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(editor.problem.is_some() as u16),
+        ])
+        .split(inner);
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[0]);
+
+    let keys: Vec<Line> = if editor.entries.is_empty() {
+        vec![Line::from(Span::styled(
+            "No metadata yet, n to add a key",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        editor
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| {
+                let well_known = key == metadata::LINKS_KEY || key == metadata::EXTERNAL_IDS_KEY;
+                let mut style = Style::default().fg(if well_known {
+                    Color::Cyan
+                } else {
+                    Color::White
+                });
+                if i == editor.selected {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Line::from(vec![
+                    Span::styled(key.clone(), style),
+                    Span::styled(
+                        format!(" {}", metadata::summary(value)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    let scroll = (editor.selected + 1).saturating_sub(panes[0].height as usize) as u16;
+    frame.render_widget(
+        Paragraph::new(keys)
+            .scroll((scroll, 0))
+            .block(Block::default().borders(Borders::RIGHT)),
+        panes[0],
+    );
+
+    let value_area = panes[1].inner(Margin::new(1, 0));
+    match &editor.entry {
+        Some(entry) => render_entry(frame, entry, value_area),
+        None => {
+            let value = editor
+                .entries
+                .get(editor.selected)
+                .map(|(_, value)| metadata::pretty(value))
+                .unwrap_or_default();
+            frame.render_widget(
+                Paragraph::new(value).style(Style::default().fg(Color::White)),
+                value_area,
+            );
+        }
+    }
+
+    if let Some(problem) = &editor.problem {
+        frame.render_widget(
+            Paragraph::new(problem.as_str()).style(Style::default().fg(Color::Red)),
+            rows[1],
+        );
+    }
+}
+
+/// The key and the JSON value being typed, with the cursor in the active one.
+fn render_entry(frame: &mut Frame, entry: &MetadataEntry, area: Rect) {
+    let label = |text: &'static str, active: bool| {
+        Span::styled(
+            text,
+            Style::default().fg(if active { Color::Yellow } else { Color::Gray }),
+        )
+    };
+    // Lines are not wrapped so the cursor can follow the last one
+    let mut lines = vec![
+        Line::from(vec![
+            label("Key: ", !entry.editing_value),
+            Span::raw(entry.key.clone()),
+        ]),
+        Line::from(label("Value (JSON):", entry.editing_value)),
+    ];
+    lines.extend(
+        entry
+            .value
+            .split('\n')
+            .map(|line| Line::from(line.to_string())),
+    );
+    let line_count = lines.len();
+    let scroll = if entry.editing_value {
+        line_count.saturating_sub(area.height as usize) as u16
+    } else {
+        0
+    };
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().fg(Color::White))
+            .scroll((scroll, 0)),
+        area,
+    );
+
+    let (column, row) = if entry.editing_value {
+        let last = entry.value.rsplit('\n').next().unwrap_or_default();
+        (
+            last.chars().count(),
+            line_count.saturating_sub(1) as u16 - scroll,
+        )
+    } else {
+        ("Key: ".len() + entry.key.chars().count(), 0)
+    };
+    frame.set_cursor_position((area.x + column as u16, area.y + row));
+}
@@ -0,0 +1,186 @@
+//! Debug overlay with the latency and error rate of each API endpoint this
+//! session, opened with F12.
+
+use std::time::Duration;
+
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    api::metrics::EndpointMetrics,
+    app::App,
+    startup::{STARTUP_BUDGET, Startup},
+    ui::components::centered_rect,
+    utils::{format_runtime, pad_truncate},
+};
+
+/// Bars of the latency sparklines, shortest first
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+const ENDPOINT_WIDTH: usize = 38;
+
+/// Request count, three percentiles and the error rate, with their gaps
+const NUMBERS_WIDTH: usize = 7 + 3 * 8 + 8;
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let area = centered_rect(90, 80, frame.area());
+    let metrics = app.client.metrics();
+    let endpoints = metrics.snapshot();
+
+    let heading = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let requests: u64 = endpoints.iter().map(|e| e.requests).sum();
+    let errors: u64 = endpoints.iter().map(|e| e.errors).sum();
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("{} requests", requests),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!(
+                    ", {} failed, in {}",
+                    errors,
+                    format_runtime(metrics.elapsed().as_secs() as i64)
+                ),
+                Style::default().fg(Color::Gray),
+            ),
+        ]),
+        startup_line(&app.startup),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "{}{:>7}{:>8}{:>8}{:>8}{:>8}  Latest requests",
+                pad_truncate("Endpoint", ENDPOINT_WIDTH),
+                "Reqs",
+                "p50",
+                "p95",
+                "p99",
+                "Errors"
+            ),
+            heading,
+        )),
+    ];
+
+    // Borders and the gap before the sparkline
+    let spark_width = (area.width as usize).saturating_sub(2 + ENDPOINT_WIDTH + NUMBERS_WIDTH + 2);
+    if endpoints.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No requests yet",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for endpoint in &endpoints {
+        lines.push(endpoint_line(endpoint, spark_width));
+    }
+
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Debug: API Requests ")
+            .title_bottom(" Slowest first · red: no response or 5xx │ Esc Close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Time to each startup phase so far; phases past the budget are highlighted
+fn startup_line(startup: &Startup) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        if startup.fast {
+            "Fast start: "
+        } else {
+            "Startup: "
+        },
+        Style::default().fg(Color::Gray),
+    )];
+    for (i, (phase, elapsed)) in startup.phases().iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" · ", Style::default().fg(Color::DarkGray)));
+        }
+        let color = if *elapsed > STARTUP_BUDGET {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        spans.push(Span::styled(
+            format!("{} {}", phase.label(), format_latency(*elapsed)),
+            Style::default().fg(color),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn endpoint_line(endpoint: &EndpointMetrics, spark_width: usize) -> Line<'static> {
+    let percentile = |p| {
+        endpoint
+            .percentile(p)
+            .map(format_latency)
+            .unwrap_or_default()
+    };
+    let error_rate = endpoint.error_rate();
+    let error_style = if endpoint.errors == 0 {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default().fg(Color::Red)
+    };
+
+    let mut spans = vec![
+        Span::styled(
+            pad_truncate(&endpoint.endpoint, ENDPOINT_WIDTH),
+            Style::default().fg(Color::White),
+        ),
+        Span::raw(format!(
+            "{:>7}{:>8}{:>8}{:>8}",
+            endpoint.requests,
+            percentile(50),
+            percentile(95),
+            percentile(99)
+        )),
+        Span::styled(format!("{:>7.1}%", error_rate), error_style),
+        Span::raw("  "),
+    ];
+    spans.extend(sparkline(endpoint, spark_width));
+    Line::from(spans)
+}
+
+/// The latency of the latest requests, scaled to the slowest of them
+fn sparkline(endpoint: &EndpointMetrics, width: usize) -> Vec<Span<'static>> {
+    let start = endpoint.samples.len().saturating_sub(width);
+    let samples = endpoint.samples.range(start..);
+    let slowest = samples
+        .clone()
+        .map(|s| s.latency)
+        .max()
+        .unwrap_or_default()
+        .max(Duration::from_millis(1));
+    samples
+        .map(|sample| {
+            let level = (sample.latency.as_secs_f64() / slowest.as_secs_f64()
+                * (BARS.len() - 1) as f64)
+                .round() as usize;
+            let color = if sample.failed {
+                Color::Red
+            } else {
+                Color::Green
+            };
+            Span::styled(BARS[level].to_string(), Style::default().fg(color))
+        })
+        .collect()
+}
+
+fn format_latency(latency: Duration) -> String {
+    let millis = latency.as_millis();
+    if millis < 1000 {
+        format!("{}ms", millis)
+    } else {
+        format!("{:.1}s", latency.as_secs_f64())
+    }
+}
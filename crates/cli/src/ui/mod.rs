@@ -1,7 +1,23 @@
 //! UI components and rendering.
 
+pub mod command_palette;
+pub mod compat;
 pub mod components;
+pub mod error_popup;
+pub mod executor_picker;
+pub mod jumper;
+pub mod link_hints;
+pub mod metadata_editor;
+pub mod metrics_popup;
+pub mod notifications_popup;
+pub mod pull_request_form;
+pub mod rebase_form;
+pub mod repo_picker;
+pub mod template_picker;
+pub mod theme;
+pub mod theme_picker;
 pub mod views;
+pub mod workspace_notes;
 
 use ratatui::Frame;
 
@@ -14,10 +30,94 @@ pub fn render(frame: &mut Frame, app: &App) {
     match app.view {
         View::Projects => views::projects::render(frame, app),
         View::Tasks => views::tasks::render(frame, app),
+        View::TaskDetail => views::task_detail::render(frame, app),
         View::Workspaces => views::workspaces::render(frame, app),
         View::WorkspaceDetail => views::workspace_detail::render(frame, app),
         View::CreateTask => views::create_task::render(frame, app),
+        View::CreateProject => views::create_project::render(frame, app),
         View::CreateAttempt => views::create_attempt::render(frame, app),
+        View::FollowUp => views::follow_up::render(frame, app),
+        View::Queue => views::queue::render(frame, app),
+        View::Failures => views::failures::render(frame, app),
+        View::Trash => views::trash::render(frame, app),
+        View::TaskTree => views::task_tree::render(frame, app),
+        View::PlannerSettings => views::planner_settings::render(frame, app),
+        View::RetentionSettings => views::retention_settings::render(frame, app),
+        View::SessionLogs => views::session_logs::render(frame, app),
+        View::Diff => views::diff::render(frame, app),
         View::Help => views::help::render(frame, app),
     }
+
+    // The help screen has no hints to put the bar under
+    if app.config.function_keys && app.view != View::Help {
+        components::render_function_keys(frame, app);
+    }
+    if app.batch.is_some() {
+        components::render_batch_progress(frame, app);
+    }
+    if !app.has_popup() {
+        notifications_popup::render_toasts(frame, app);
+    }
+    if !app.link_hints.is_empty() {
+        link_hints::render(frame, app);
+    }
+    if app.show_jumper {
+        jumper::render(frame, app);
+    }
+    if app.palette.is_some() {
+        command_palette::render(frame, app);
+    }
+    if app.show_metrics {
+        metrics_popup::render(frame, app);
+    }
+    if app.show_notifications {
+        notifications_popup::render(frame, app);
+    }
+    if app.show_error_detail {
+        error_popup::render(frame, app);
+    }
+    if let Some(action) = &app.pending_action {
+        let details = action
+            .preview()
+            .map(components::git_preview_lines)
+            .unwrap_or_default();
+        components::render_confirmation(frame, "Confirm", &action.prompt(), details);
+    }
+    if app.theme_picker.is_some() {
+        theme_picker::render(frame, app);
+    }
+    if app.executor_picker.is_some() {
+        executor_picker::render(frame, app);
+    }
+    if app.template_picker.is_some() {
+        template_picker::render(frame, app);
+    }
+    if app.repo_picker.is_some() {
+        repo_picker::render(frame, app);
+    }
+    if app.pull_request_form.is_some() {
+        pull_request_form::render(frame, app);
+    }
+    if app.rebase_form.is_some() {
+        rebase_form::render(frame, app);
+    }
+    if app.workspace_notes_editor.is_some() {
+        workspace_notes::render(frame, app);
+    }
+    if app.metadata_editor.is_some() {
+        metadata_editor::render(frame, app);
+    }
+    if let Some(rename) = &app.workspace_rename {
+        components::render_input_popup(
+            frame,
+            &format!("Name {}", rename.branch),
+            &rename.name,
+            "No name; the branch is shown",
+        );
+    }
+
+    theme::apply(app.config.theme(), frame.buffer_mut());
+    if app.config.compat() {
+        compat::apply(app.config.theme(), frame.buffer_mut());
+    }
 }
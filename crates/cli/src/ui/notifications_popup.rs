@@ -0,0 +1,113 @@
+//! The notification center, opened with `N`, and the toasts of messages the
+//! status bar has moved past.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    app::App,
+    datetime::TimeZone,
+    notifications::{Notification, NotificationLevel},
+    ui::components::centered_rect,
+};
+
+/// Widest a toast gets, borders included
+const TOAST_WIDTH: u16 = 50;
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let area = centered_rect(80, 70, frame.area());
+    let notifications = &app.notifications;
+
+    let mut lines = Vec::new();
+    if notifications.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nothing yet",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for notification in notifications.newest_first().skip(app.notifications_scroll) {
+        lines.push(notification_line(notification, app.config.time_zone));
+    }
+
+    let title = format!(
+        " Notifications ({}, {} errors) ",
+        notifications.len(),
+        notifications.errors()
+    );
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(title)
+            .title_bottom(" Newest first │ ↑/↓ Scroll │ Esc Close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn notification_line(notification: &Notification, zone: TimeZone) -> Line<'static> {
+    let (icon, color) = level_style(notification.level);
+    let mut spans = vec![
+        Span::styled(
+            format!("{} ", zone.format(notification.at, "%H:%M:%S")),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(format!("{} ", icon), Style::default().fg(color)),
+        Span::styled(
+            notification.message.clone(),
+            Style::default().fg(Color::White),
+        ),
+    ];
+    if notification.count > 1 {
+        spans.push(Span::styled(
+            format!(" ×{}", notification.count),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Recently replaced messages, stacked in the top right corner.
+pub fn render_toasts(frame: &mut Frame, app: &App) {
+    let screen = frame.area();
+    let width = TOAST_WIDTH.min(screen.width);
+    let mut y = screen.y + 1;
+    for notification in app.notifications.toasts() {
+        if y + 3 > screen.bottom() {
+            break;
+        }
+        let area = Rect::new(screen.right() - width, y, width, 3);
+        let (icon, color) = level_style(notification.level);
+        let toast = Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} ", icon),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                notification.message.clone(),
+                Style::default().fg(Color::White),
+            ),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        );
+        frame.render_widget(Clear, area);
+        frame.render_widget(toast, area);
+        y += 3;
+    }
+}
+
+fn level_style(level: NotificationLevel) -> (&'static str, Color) {
+    match level {
+        NotificationLevel::Info => ("●", Color::Yellow),
+        NotificationLevel::Error => ("✗", Color::Red),
+    }
+}
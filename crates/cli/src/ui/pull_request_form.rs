@@ -0,0 +1,130 @@
+//! Pull request popup: title and body prefilled from the task, the repo to open
+//! it in and whether it is a draft.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::{App, PullRequestField, PullRequestForm},
+    ui::components::centered_rect,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(form) = &app.pull_request_form else {
+        return;
+    };
+    let area = centered_rect(70, 60, frame.area());
+    let block = Block::default()
+        .title(format!(" Pull request for {} ", form.branch))
+        .title_bottom(" Tab Next field │ Ctrl+S Create │ Esc Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(3),    // Body
+            Constraint::Length(1), // Repo
+            Constraint::Length(1), // Draft
+        ])
+        .split(inner);
+
+    let border_style = |field: PullRequestField| {
+        if form.field == field {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            form.title.as_str(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .block(
+            Block::default()
+                .title(" Title ")
+                .borders(Borders::ALL)
+                .border_style(border_style(PullRequestField::Title)),
+        ),
+        chunks[0],
+    );
+
+    // Lines are not wrapped so the cursor can follow the last one
+    let body_lines: Vec<Line> = form
+        .body
+        .split('\n')
+        .map(|line| Line::from(line.to_string()))
+        .collect();
+    let line_count = body_lines.len();
+    let scroll = line_count.saturating_sub(chunks[1].height.saturating_sub(2) as usize) as u16;
+    frame.render_widget(
+        Paragraph::new(body_lines)
+            .style(Style::default().fg(Color::White))
+            .scroll((scroll, 0))
+            .block(
+                Block::default()
+                    .title(" Body ")
+                    .borders(Borders::ALL)
+                    .border_style(border_style(PullRequestField::Body)),
+            ),
+        chunks[1],
+    );
+
+    render_choice(frame, chunks[2], form, PullRequestField::Repo);
+    render_choice(frame, chunks[3], form, PullRequestField::Draft);
+
+    let (area, row, column) = match form.field {
+        PullRequestField::Title => (chunks[0], 0, form.title.chars().count()),
+        PullRequestField::Body => {
+            let last = form.body.rsplit('\n').next().unwrap_or_default();
+            let row = line_count.saturating_sub(1) as u16 - scroll;
+            (chunks[1], row, last.chars().count())
+        }
+        _ => return,
+    };
+    frame.set_cursor_position((area.x + 1 + column as u16, area.y + 1 + row));
+}
+
+/// The repo or draft line, changed with ←/→ or Space while selected.
+fn render_choice(frame: &mut Frame, area: Rect, form: &PullRequestForm, field: PullRequestField) {
+    let (label, value) = match field {
+        PullRequestField::Repo => {
+            let (_, name, target) = &form.repos[form.repo_index];
+            let value = format!("{} → {}", name, target);
+            if form.repos.len() > 1 {
+                ("Repo", format!("◂ {} ▸", value))
+            } else {
+                ("Repo", value)
+            }
+        }
+        _ => ("Draft", if form.draft { "yes" } else { "no" }.to_string()),
+    };
+    let (marker, style) = if form.field == field {
+        ("▸ ", Style::default().fg(Color::Yellow))
+    } else {
+        ("  ", Style::default().fg(Color::White))
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{}{}: ", marker, label),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled(value, style),
+        ])),
+        area,
+    );
+}
@@ -0,0 +1,78 @@
+//! Rebase popup: the branch to rebase the workspace onto, checked as it is typed.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::App,
+    ui::components::{centered_rect, error_style},
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(form) = &app.rebase_form else {
+        return;
+    };
+    // Nothing typed yet isn't worth a red line
+    let typed = !form.new_base.trim().is_empty();
+    let error = app.rebase_form_error().filter(|_| typed);
+    let outer = centered_rect(60, 30, frame.area());
+    // Repo, branch and the problem with it, centred in the usual popup area
+    let height = 5.min(outer.height);
+    let area = Rect {
+        y: outer.y + (outer.height - height) / 2,
+        height,
+        ..outer
+    };
+    let block = Block::default()
+        .title(format!(" Rebase {} onto ", form.branch))
+        .title_bottom(if form.repos.len() > 1 {
+            " ←/→ Repo │ Enter Rebase │ Esc Cancel "
+        } else {
+            " Enter Rebase │ Esc Cancel "
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if error.is_some() {
+            Color::Red
+        } else {
+            Color::Cyan
+        }));
+
+    let (_, name, target) = &form.repos[form.repo_index];
+    let repo = if form.repos.len() > 1 {
+        format!("◂ {} ▸", name)
+    } else {
+        name.clone()
+    };
+    let branch = if form.new_base.is_empty() {
+        Span::styled(
+            format!("branch to replace {}", target),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else {
+        Span::styled(form.new_base.as_str(), Style::default().fg(Color::White))
+    };
+    let status = match &error {
+        Some(error) => Line::from(Span::styled(format!("✗ {}", error), error_style())),
+        None if typed => Line::from(Span::styled("✓", Style::default().fg(Color::Green))),
+        None => Line::from(""),
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Repo: ", Style::default().fg(Color::Gray)),
+            Span::styled(repo, Style::default().fg(Color::White)),
+        ]),
+        Line::from(branch),
+        status,
+    ];
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+    let cursor_x =
+        (area.x + 1 + form.new_base.chars().count() as u16).min(area.right().saturating_sub(2));
+    frame.set_cursor_position((cursor_x, area.y + 2));
+}
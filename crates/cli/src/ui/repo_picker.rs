@@ -0,0 +1,62 @@
+//! Repo picker popup: which of the workspace's repos a merge, push or rebase runs
+//! in, or all of them.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::App,
+    ui::components::{centered_rect, selected_style},
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(picker) = app.repo_picker else {
+        return;
+    };
+    let outer = centered_rect(50, 50, frame.area());
+    // The repos and "All repos", centred in the usual popup area
+    let height = (app.branch_statuses.len() as u16 + 3).min(outer.height);
+    let area = Rect {
+        y: outer.y + (outer.height - height) / 2,
+        height,
+        ..outer
+    };
+    let block = Block::default()
+        .title(format!(" {} in which repo? ", picker.operation.label()))
+        .title_bottom(" ↑/↓ Select │ Enter Continue │ Esc Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let rows = app
+        .branch_statuses
+        .iter()
+        .map(|status| {
+            (
+                status.repo_name.clone(),
+                format!("  → {}", status.status.target_branch_name),
+            )
+        })
+        .chain([("All repos".to_string(), String::new())]);
+    let lines: Vec<Line> = rows
+        .enumerate()
+        .map(|(i, (name, target))| {
+            let target = Span::styled(target, Style::default().fg(Color::DarkGray));
+            if i == picker.index {
+                Line::from(vec![Span::raw(format!("▸ {}", name)), target]).style(selected_style())
+            } else {
+                Line::from(vec![
+                    Span::styled(format!("  {}", name), Style::default().fg(Color::White)),
+                    target,
+                ])
+            }
+        })
+        .collect();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
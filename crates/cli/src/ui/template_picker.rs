@@ -0,0 +1,86 @@
+//! Follow-up template picker: lists the templates and previews the selected one
+//! filled in from the current task and workspace.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    app::App,
+    ui::components::{centered_rect, selected_style},
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(selected) = app.template_picker else {
+        return;
+    };
+    let templates = app.follow_up_templates();
+    let area = centered_rect(70, 70, frame.area());
+    let block = Block::default()
+        .title(" Templates ")
+        .title_bottom(" ↑/↓ Select │ Enter Add │ Esc Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(templates.len() as u16 + 1),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    let names: Vec<Line> = templates
+        .iter()
+        .enumerate()
+        .map(|(i, template)| {
+            if i == selected {
+                Line::from(Span::raw(format!("▸ {}", template.name))).style(selected_style())
+            } else {
+                Line::from(Span::styled(
+                    format!("  {}", template.name),
+                    Style::default().fg(Color::White),
+                ))
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(names), chunks[0]);
+
+    let Some(template) = templates.get(selected) else {
+        return;
+    };
+    let (text, missing) = app.expand_template(template);
+    let mut lines: Vec<Line> = text
+        .lines()
+        .map(|line| {
+            Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(Color::Gray),
+            ))
+        })
+        .collect();
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(|p| format!("{{{}}}", p)).collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("Nothing to fill in for {}", missing.join(", ")),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .title(" Preview ")
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        ),
+        chunks[1],
+    );
+}
@@ -0,0 +1,184 @@
+//! Colour themes.
+//!
+//! Views draw with the terminal's named colours (plus the selection highlight).
+//! After a frame is rendered, the theme's palette maps each of those colours to
+//! its own, so every view, popup and overlay follows the theme without knowing
+//! about it.
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use serde::Deserialize;
+
+/// Background of selected rows, see [`super::components::selected_style`]
+const SELECTION: Color = Color::Rgb(40, 40, 60);
+
+/// Colour scheme of the interactive UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// The terminal's own colours
+    #[default]
+    #[serde(alias = "default")]
+    Dark,
+    Light,
+    Solarized,
+    /// Bright colours on black
+    HighContrast,
+    /// No colours; highlighted text is shown in reverse video instead
+    Monochrome,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 5] = [
+        Theme::Dark,
+        Theme::Light,
+        Theme::Solarized,
+        Theme::HighContrast,
+        Theme::Monochrome,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+            Theme::Solarized => "Solarized",
+            Theme::HighContrast => "High contrast",
+            Theme::Monochrome => "Monochrome",
+        }
+    }
+
+    /// Name in the config file and the server's user preferences
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::Solarized => "solarized",
+            Theme::HighContrast => "high-contrast",
+            Theme::Monochrome => "monochrome",
+        }
+    }
+
+    /// The theme after (or before) this one in [`Theme::ALL`], wrapping around.
+    pub fn cycle(self, forward: bool) -> Theme {
+        let index = Theme::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        let len = Theme::ALL.len();
+        let next = if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        };
+        Theme::ALL[next]
+    }
+
+    /// Background of the recoloured frame; the terminal's own unless the theme sets one
+    pub fn background(self) -> Color {
+        self.palette()
+            .and_then(|palette| palette.background)
+            .unwrap_or(Color::Reset)
+    }
+
+    fn palette(self) -> Option<Palette> {
+        match self {
+            Theme::Dark | Theme::Monochrome => None,
+            Theme::Light => Some(Palette {
+                foreground: Some(Color::Black),
+                background: Some(Color::White),
+                colors: &[
+                    (Color::White, Color::Black),
+                    (Color::Black, Color::White),
+                    (Color::Gray, Color::Rgb(60, 60, 60)),
+                    (Color::DarkGray, Color::Rgb(120, 120, 120)),
+                    (Color::Yellow, Color::Rgb(150, 105, 0)),
+                    (Color::Cyan, Color::Rgb(0, 95, 175)),
+                    (Color::Green, Color::Rgb(0, 130, 0)),
+                    (Color::Red, Color::Rgb(190, 0, 0)),
+                    (Color::Magenta, Color::Rgb(140, 0, 140)),
+                    (SELECTION, Color::Rgb(205, 215, 235)),
+                ],
+            }),
+            Theme::Solarized => Some(Palette {
+                foreground: Some(Color::Rgb(131, 148, 150)),
+                background: Some(Color::Rgb(0, 43, 54)),
+                colors: &[
+                    (Color::White, Color::Rgb(147, 161, 161)),
+                    (Color::Black, Color::Rgb(0, 43, 54)),
+                    (Color::Gray, Color::Rgb(131, 148, 150)),
+                    (Color::DarkGray, Color::Rgb(88, 110, 117)),
+                    (Color::Yellow, Color::Rgb(181, 137, 0)),
+                    (Color::Cyan, Color::Rgb(42, 161, 152)),
+                    (Color::Green, Color::Rgb(133, 153, 0)),
+                    (Color::Red, Color::Rgb(220, 50, 47)),
+                    (Color::Magenta, Color::Rgb(211, 54, 130)),
+                    (Color::Blue, Color::Rgb(38, 139, 210)),
+                    (SELECTION, Color::Rgb(7, 54, 66)),
+                ],
+            }),
+            Theme::HighContrast => Some(Palette {
+                foreground: Some(Color::White),
+                background: Some(Color::Black),
+                colors: &[
+                    (Color::Gray, Color::White),
+                    (Color::DarkGray, Color::Gray),
+                    (Color::Yellow, Color::LightYellow),
+                    (Color::Cyan, Color::LightCyan),
+                    (Color::Green, Color::LightGreen),
+                    (Color::Red, Color::LightRed),
+                    (Color::Magenta, Color::LightMagenta),
+                    (Color::Blue, Color::LightBlue),
+                    (SELECTION, Color::Blue),
+                ],
+            }),
+        }
+    }
+}
+
+/// Replacement colours of a theme
+struct Palette {
+    /// Used where the views leave the colour to the terminal
+    foreground: Option<Color>,
+    background: Option<Color>,
+    colors: &'static [(Color, Color)],
+}
+
+impl Palette {
+    fn map(&self, color: Color, default: Option<Color>) -> Color {
+        if color == Color::Reset {
+            return default.unwrap_or(color);
+        }
+        self.colors
+            .iter()
+            .find(|(from, _)| *from == color)
+            .map_or(color, |(_, to)| *to)
+    }
+}
+
+/// Recolour a rendered frame with `theme`.
+pub fn apply(theme: Theme, buffer: &mut Buffer) {
+    if theme == Theme::Monochrome {
+        strip_colors(buffer);
+        return;
+    }
+    let Some(palette) = theme.palette() else {
+        return;
+    };
+    for cell in &mut buffer.content {
+        let fg = palette.map(cell.fg, palette.foreground);
+        let bg = palette.map(cell.bg, palette.background);
+        cell.set_fg(fg);
+        cell.set_bg(bg);
+    }
+}
+
+/// Drop all colours from the rendered frame, showing highlighted (background
+/// coloured) cells in reverse video so selections stay visible.
+fn strip_colors(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.set_fg(Color::Reset);
+        cell.set_bg(Color::Reset);
+    }
+}
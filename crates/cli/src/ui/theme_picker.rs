@@ -0,0 +1,50 @@
+//! Theme picker popup: lists the themes and previews the selected one.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    app::App,
+    ui::{
+        components::{centered_rect, selected_style},
+        theme::Theme,
+    },
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let outer = centered_rect(40, 40, frame.area());
+    // Just tall enough for the list, centred in the usual popup area
+    let height = (Theme::ALL.len() as u16 + 2).min(outer.height);
+    let area = Rect {
+        y: outer.y + (outer.height - height) / 2,
+        height,
+        ..outer
+    };
+    let block = Block::default()
+        .title(" Theme ")
+        .title_bottom(" ↑/↓ Preview │ Enter Keep │ Esc Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let lines: Vec<Line> = Theme::ALL
+        .iter()
+        .map(|theme| {
+            if *theme == app.config.theme() {
+                Line::from(Span::raw(format!("▸ {}", theme.name()))).style(selected_style())
+            } else {
+                Line::from(Span::styled(
+                    format!("  {}", theme.name()),
+                    Style::default().fg(Color::White),
+                ))
+            }
+        })
+        .collect();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
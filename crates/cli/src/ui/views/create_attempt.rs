@@ -9,8 +9,9 @@ use ratatui::{
 };
 
 use crate::{
-    app::App,
-    ui::components::{focused_border_style, render_header, render_hints, render_status_bar, selected_style},
+    app::{App, InputMode},
+    types::ExecutorVariant,
+    ui::components::{error_style, focused_border_style, render_header, render_hints, render_status_bar, selected_style},
 };
 
 pub fn render(frame: &mut Frame, app: &App) {
@@ -25,7 +26,9 @@ pub fn render(frame: &mut Frame, app: &App) {
         .split(frame.area());
 
     // Header
-    let title = if let Some(ref task) = app.selected_task {
+    let title = if !app.attempt_tasks.is_empty() {
+        format!("Create Attempt - {} tasks", app.attempt_tasks.len())
+    } else if let Some(ref task) = app.selected_task {
         format!("Create Attempt - {}", task.task.title)
     } else {
         "Create Attempt".to_string()
@@ -36,50 +39,65 @@ pub fn render(frame: &mut Frame, app: &App) {
     render_form(frame, chunks[1], app);
 
     // Hints
-    render_hints(
-        frame,
-        chunks[2],
-        &[
-            ("↑/↓", "Navigate"),
-            ("Enter", "Select/Edit"),
-            ("Tab", "Next Field"),
-            ("Esc", "Cancel"),
-        ],
-    );
+    let hints: &[(&str, &str)] =
+        if app.input_mode == InputMode::Editing && app.attempt_selected_field >= 4 {
+            &[("↑/↓", "Pick"), ("Enter", "Use Branch"), ("Esc", "Cancel")]
+        } else {
+            &[
+                ("↑/↓", "Navigate"),
+                ("Enter", "Select/Edit"),
+                ("Tab", "Next Field"),
+                ("Esc", "Cancel"),
+            ]
+        };
+    render_hints(frame, chunks[2], hints);
 
     // Status bar
     render_status_bar(frame, chunks[3], app);
 }
 
 fn render_form(frame: &mut Frame, area: Rect, app: &App) {
+    let executors = app.available_executors();
+    let variants = app.attempt_variant_options();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Executor
-            Constraint::Length(3),  // Variant
+            Constraint::Length(executors.len() as u16 + 2),  // Executor
+            Constraint::Length(1),  // Executor health tooltip
+            Constraint::Length(variants.len().max(1) as u16 + 2),  // Variant
+            Constraint::Length(3),  // Resource limits
+            Constraint::Length(3),  // Branch name
             Constraint::Min(5),     // Repo branches
         ])
         .split(area);
 
     // Executor selection
-    let executors = App::available_executors();
     let executor_items: Vec<ListItem> = executors
         .iter()
         .enumerate()
         .map(|(i, exec)| {
-            let style = if i == app.attempt_executor_index && app.attempt_selected_field == 0 {
+            let available = app.is_executor_available(*exec);
+            let mut style = if i == app.attempt_executor_index && app.attempt_selected_field == 0 {
                 selected_style()
             } else {
                 Style::default()
             };
+            if !available {
+                style = style.fg(Color::DarkGray);
+            }
             let marker = if i == app.attempt_executor_index && app.attempt_selected_field == 0 {
                 "▸ "
             } else {
                 "  "
             };
+            let label = if available {
+                format!("{:?}", exec)
+            } else {
+                format!("{:?} (unavailable)", exec)
+            };
             ListItem::new(Line::from(vec![
                 Span::styled(marker, style),
-                Span::styled(format!("{:?}", exec), style),
+                Span::styled(label, style),
             ]))
         })
         .collect();
@@ -87,11 +105,7 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
     let executor_list = List::new(executor_items)
         .block(
             Block::default()
-                .title(if app.attempt_selected_field == 0 {
-                    " Executor * "
-                } else {
-                    " Executor * "
-                })
+                .title(" Executor * ")
                 .borders(Borders::ALL)
                 .border_style(if app.attempt_selected_field == 0 {
                     focused_border_style()
@@ -102,34 +116,77 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
 
     frame.render_widget(executor_list, chunks[0]);
 
-    // Variant input
-    let variant_text = app.attempt_variant.as_deref().unwrap_or("(optional)");
-    let variant_style = if app.attempt_selected_field == 1 {
-        focused_border_style()
+    // Health tooltip for the highlighted executor
+    let tooltip = executors
+        .get(app.attempt_executor_index)
+        .and_then(|exec| app.executor_health_for(*exec))
+        .map(|health| {
+            let style = if health.available {
+                Style::default().fg(Color::Gray)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            let message = health.message.as_deref().unwrap_or("Ready");
+            Line::from(Span::styled(format!(" ⓘ {}", message), style))
+        })
+        .unwrap_or_default();
+    frame.render_widget(Paragraph::new(tooltip), chunks[1]);
+
+    // Variant
+    if variants.is_empty() {
+        render_variant_input(frame, chunks[2], app);
     } else {
-        Style::default().fg(Color::DarkGray)
+        render_variant_list(frame, chunks[2], app, &variants);
+    }
+
+    // Resource limits
+    let limits_focused = app.attempt_selected_field == 2;
+    let limits_text = if app.attempt_limits.is_empty() {
+        "(optional) e.g. runtime=30m cpu=10m mem=2G"
+    } else {
+        app.attempt_limits.as_str()
     };
-    let variant_paragraph = Paragraph::new(variant_text)
-        .block(
-            Block::default()
-                .title(" Variant ")
-                .borders(Borders::ALL)
-                .border_style(variant_style),
-        )
-        .style(if app.attempt_selected_field == 1 {
+    let limits_paragraph = Paragraph::new(limits_text)
+        .block(field_block(
+            " Limits ",
+            limits_focused,
+            app.attempt_field_error(2),
+        ))
+        .style(if limits_focused && !app.attempt_limits.is_empty() {
             Style::default().fg(Color::White)
         } else {
             Style::default().fg(Color::DarkGray)
         });
+    frame.render_widget(limits_paragraph, chunks[3]);
 
-    frame.render_widget(variant_paragraph, chunks[1]);
+    // Branch name
+    let branch_focused = app.attempt_selected_field == 3;
+    let branch_text = if app.attempt_branch.is_empty() {
+        "(optional) generated from the task title"
+    } else {
+        app.attempt_branch.as_str()
+    };
+    let branch_paragraph = Paragraph::new(branch_text)
+        .block(field_block(
+            " Branch ",
+            branch_focused,
+            app.attempt_field_error(3),
+        ))
+        .style(if !app.attempt_branch.is_empty() {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
+    frame.render_widget(branch_paragraph, chunks[4]);
 
     // Repo branches
+    let searching = app.input_mode == InputMode::Editing && app.attempt_selected_field >= 4;
+    let mut cursor = None;
     let repo_items: Vec<ListItem> = app
         .attempt_repo_branches
         .iter()
         .enumerate()
-        .map(|(i, (repo_id, branch))| {
+        .flat_map(|(i, (repo_id, branch))| {
             let repo_name = app
                 .project_repos
                 .iter()
@@ -137,7 +194,7 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
                 .map(|r| r.name.as_str())
                 .unwrap_or("Unknown");
             
-            let field_index = 2 + i;
+            let field_index = 4 + i;
             let style = if field_index == app.attempt_selected_field {
                 selected_style()
             } else {
@@ -164,30 +221,172 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
                 format!("{} (custom)", branch)
             };
 
-            ListItem::new(Line::from(vec![
+            if searching && field_index == app.attempt_selected_field {
+                let typed = format!("{}{}: {}", marker, repo_name, app.attempt_branch_query);
+                cursor = Some((i, typed.chars().count()));
+                let mut items = vec![ListItem::new(Line::from(vec![
+                    Span::styled(marker, style),
+                    Span::styled(format!("{}: ", repo_name), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        app.attempt_branch_query.clone(),
+                        Style::default().fg(Color::White),
+                    ),
+                ]))];
+                items.extend(branch_completions(app, repo_name.chars().count() + 4));
+                return items;
+            }
+
+            let mut spans = vec![
                 Span::styled(marker, style),
                 Span::styled(format!("{}: ", repo_name), Style::default().fg(Color::Gray)),
                 Span::styled(branch_display, style),
-            ]))
+            ];
+            if let Some(error) = app.attempt_field_error(field_index) {
+                spans.push(Span::styled(format!("  ✗ {}", error), error_style()));
+            } else if field_index == app.attempt_selected_field {
+                spans.push(Span::styled(
+                    "  type to search branches",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            vec![ListItem::new(Line::from(spans))]
         })
         .collect();
 
     let repo_list = List::new(repo_items)
         .block(
             Block::default()
-                .title(if app.attempt_selected_field >= 2 {
-                    " Base Branches * "
-                } else {
-                    " Base Branches * "
-                })
+                .title(" Base Branches * ")
                 .borders(Borders::ALL)
-                .border_style(if app.attempt_selected_field >= 2 {
+                .border_style(if app.attempt_selected_field >= 4 {
                     focused_border_style()
                 } else {
                     Style::default().fg(Color::DarkGray)
                 }),
         );
 
-    frame.render_widget(repo_list, chunks[2]);
+    frame.render_widget(repo_list, chunks[5]);
+    if let Some((row, column)) = cursor {
+        let area = chunks[5];
+        frame.set_cursor_position((area.x + 1 + column as u16, area.y + 1 + row as u16));
+    }
 }
 
+/// The branches matching the typed base branch, indented by `indent` to line up
+/// with it; remote branches are marked as such.
+fn branch_completions(app: &App, indent: usize) -> Vec<ListItem<'static>> {
+    let completions = app.attempt_branch_completions();
+    if completions.is_empty() {
+        return vec![ListItem::new(Line::from(Span::styled(
+            format!(
+                "{}no matching branch, Enter uses it as typed",
+                " ".repeat(indent)
+            ),
+            Style::default().fg(Color::DarkGray),
+        )))];
+    }
+    completions
+        .iter()
+        .enumerate()
+        .map(|(i, branch)| {
+            let highlighted = i == app.attempt_branch_completion;
+            let style = if highlighted {
+                selected_style()
+            } else {
+                Style::default()
+            };
+            let mut spans = vec![
+                Span::raw(" ".repeat(indent.saturating_sub(2))),
+                Span::styled(if highlighted { "▸ " } else { "  " }, style),
+                Span::styled(branch.name.clone(), style),
+            ];
+            if branch.is_remote {
+                spans.push(Span::styled(
+                    "  remote",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect()
+}
+
+/// A text field's box, red with the problem underneath while its value is invalid.
+fn field_block(title: &str, focused: bool, error: Option<String>) -> Block<'_> {
+    let block = Block::default().title(title).borders(Borders::ALL);
+    match error {
+        Some(error) => block
+            .border_style(error_style())
+            .title_bottom(Line::from(format!(" {} ", error)).style(error_style())),
+        None if focused => block.border_style(focused_border_style()),
+        None => block.border_style(Style::default().fg(Color::DarkGray)),
+    }
+}
+
+/// Selectable list of the variants the server knows for the executor.
+fn render_variant_list(frame: &mut Frame, area: Rect, app: &App, variants: &[&ExecutorVariant]) {
+    let focused = app.attempt_selected_field == 1;
+    let name_width = variants.iter().map(|v| v.name.len()).max().unwrap_or(0);
+    let items: Vec<ListItem> = variants
+        .iter()
+        .map(|variant| {
+            let chosen = app.is_attempt_variant(variant);
+            let style = if chosen && focused {
+                selected_style()
+            } else if chosen {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let marker = if chosen { "▸ " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(
+                    format!("{:width$}  ", variant.name, width = name_width),
+                    style,
+                ),
+                Span::styled(
+                    variant.description.as_str(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .title(" Variant ")
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                focused_border_style()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            }),
+    );
+
+    frame.render_widget(list, area);
+}
+
+/// Free-text variant, for servers that do not report their profiles.
+fn render_variant_input(frame: &mut Frame, area: Rect, app: &App) {
+    let variant_text = app.attempt_variant.as_deref().unwrap_or("(optional)");
+    let variant_style = if app.attempt_selected_field == 1 {
+        focused_border_style()
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let variant_paragraph = Paragraph::new(variant_text)
+        .block(
+            Block::default()
+                .title(" Variant ")
+                .borders(Borders::ALL)
+                .border_style(variant_style),
+        )
+        .style(if app.attempt_selected_field == 1 {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
+
+    frame.render_widget(variant_paragraph, area);
+}
@@ -0,0 +1,176 @@
+//! Create project form view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    app::{App, InputMode, ProjectFormField},
+    ui::components::{
+        centered_rect, focused_border_style, render_header, render_hints, render_status_bar,
+    },
+    utils::resolve_repo_path,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Min(10),   // Form
+            Constraint::Length(2), // Hints
+            Constraint::Length(2), // Status
+        ])
+        .split(frame.area());
+
+    render_header(frame, chunks[0], "Create New Project");
+
+    // The name, then an input row and a validation line per repository
+    let outer = centered_rect(70, 90, chunks[1]);
+    let height = (3 + app.new_project_repos.len() as u16 * 4 + 3).min(outer.height);
+    let form_area = Rect {
+        y: outer.y + (outer.height - height) / 2,
+        height,
+        ..outer
+    };
+    render_form(frame, form_area, app);
+
+    let hints = if app.input_mode == InputMode::Editing {
+        vec![
+            ("Enter", "Done"),
+            ("Tab", "Next Field"),
+            ("Esc", "Stop Editing"),
+        ]
+    } else {
+        vec![
+            ("Tab", "Next Field"),
+            ("e", "Edit"),
+            ("n", "Add Repository"),
+            ("d", "Remove Repository"),
+            ("Enter", "Create"),
+            ("Esc", "Cancel"),
+        ]
+    };
+    render_hints(frame, chunks[2], &hints);
+
+    render_status_bar(frame, chunks[3], app);
+}
+
+fn render_form(frame: &mut Frame, area: Rect, app: &App) {
+    let outer_block = Block::default()
+        .title(" New Project ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(outer_block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [Constraint::Length(3)]
+                .into_iter()
+                .chain(
+                    app.new_project_repos
+                        .iter()
+                        .flat_map(|_| [Constraint::Length(3), Constraint::Length(1)]),
+                )
+                .chain([Constraint::Min(0)]),
+        )
+        .split(area);
+
+    render_input(
+        frame,
+        rows[0],
+        app,
+        ProjectFormField::Name,
+        " Name ",
+        &app.new_project_name,
+        "Named after the first repository if left empty",
+    );
+
+    for (i, repo) in app.new_project_repos.iter().enumerate() {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(rows[1 + i * 2]);
+        render_input(
+            frame,
+            columns[0],
+            app,
+            ProjectFormField::RepoPath(i),
+            &format!(" Repository {} ", i + 1),
+            &repo.git_repo_path,
+            "Path to a git repository, e.g. ~/code/app",
+        );
+        render_input(
+            frame,
+            columns[1],
+            app,
+            ProjectFormField::RepoDisplayName(i),
+            " Display Name ",
+            &repo.display_name,
+            "Folder name",
+        );
+
+        // Checked as it is typed, on this machine
+        let validation = if repo.git_repo_path.trim().is_empty() {
+            Line::from("")
+        } else {
+            match resolve_repo_path(&repo.git_repo_path) {
+                Ok(path) => Line::from(Span::styled(
+                    format!(" ✓ {}", path.display()),
+                    Style::default().fg(Color::Green),
+                )),
+                Err(e) => Line::from(Span::styled(
+                    format!(" ✗ {}", e),
+                    Style::default().fg(Color::Red),
+                )),
+            }
+        };
+        frame.render_widget(Paragraph::new(validation), rows[2 + i * 2]);
+    }
+}
+
+fn render_input(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App,
+    field: ProjectFormField,
+    title: &str,
+    value: &str,
+    placeholder: &str,
+) {
+    let focused = app.new_project_field == field;
+    let editing = focused && app.input_mode == InputMode::Editing;
+    let border_style = if editing {
+        Style::default().fg(Color::Yellow)
+    } else if focused {
+        focused_border_style()
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+
+    let content = if value.is_empty() {
+        Span::styled(placeholder, Style::default().fg(Color::DarkGray))
+    } else if editing {
+        Span::styled(value, Style::default().fg(Color::Yellow))
+    } else {
+        Span::styled(value, Style::default().fg(Color::White))
+    };
+    let input = Paragraph::new(content).block(
+        Block::default()
+            .title(title.to_string())
+            .borders(Borders::ALL)
+            .border_style(border_style),
+    );
+    frame.render_widget(input, area);
+
+    if editing {
+        let cursor_x = area.x + 1 + value.chars().count() as u16;
+        frame.set_cursor_position((cursor_x, area.y + 1));
+    }
+}
@@ -9,8 +9,8 @@ use ratatui::{
 };
 
 use crate::{
-    app::{App, InputMode},
-    ui::components::{render_header, render_hints, render_status_bar},
+    app::{App, InputMode, NewTaskField},
+    ui::components::{render_header, render_hints, render_status_bar, selected_style},
 };
 
 pub fn render(frame: &mut Frame, app: &App) {
@@ -25,19 +25,55 @@ pub fn render(frame: &mut Frame, app: &App) {
         .split(frame.area());
 
     // Header
-    render_header(frame, chunks[0], "Create New Task");
+    let title = match &app.new_task_parent {
+        Some(workspace) => format!(
+            "Create Subtask - from {}",
+            workspace.name.as_deref().unwrap_or(&workspace.branch)
+        ),
+        None => "Create New Task".to_string(),
+    };
+    render_header(frame, chunks[0], &title);
 
     // Form area
     let form_area = centered_rect(60, 50, chunks[1]);
     render_form(frame, form_area, app);
 
     // Hints
-    let hints = if app.input_mode == InputMode::Editing {
+    let hints = if app.input_mode == InputMode::Editing
+        && app.new_task_field == NewTaskField::Description
+    {
+        vec![
+            ("Enter", "Save"),
+            ("Shift+Enter", "New Line"),
+            ("Esc", "Cancel Edit"),
+            ("Tab", "Next Field"),
+        ]
+    } else if app.input_mode == InputMode::Editing {
         vec![
             ("Enter", "Save"),
             ("Esc", "Cancel Edit"),
             ("Tab", "Next Field"),
         ]
+    } else if app.new_task_field == NewTaskField::Start {
+        vec![
+            ("Space", "Toggle"),
+            ("Tab", "Next Field"),
+            ("Enter", "Create"),
+            ("Esc", "Cancel"),
+        ]
+    } else if !app.new_task_field.is_text() {
+        vec![
+            ("↑/↓", "Pick"),
+            ("Tab", "Next Field"),
+            ("Enter", "Create & Start"),
+            ("Esc", "Cancel"),
+        ]
+    } else if app.new_task_start {
+        vec![
+            ("e", "Edit"),
+            ("Enter", "Create & Start"),
+            ("Esc", "Cancel"),
+        ]
     } else {
         vec![
             ("e", "Edit"),
@@ -59,6 +95,7 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
             Constraint::Length(3),  // Title field
             Constraint::Length(1),  // Spacer
             Constraint::Min(5),     // Description field
+            Constraint::Length(start_height(app)),  // Start an attempt
         ])
         .split(area);
 
@@ -68,8 +105,13 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
         .border_style(Style::default().fg(Color::Cyan));
     frame.render_widget(outer_block, area);
 
+    let editing_title =
+        app.input_mode == InputMode::Editing && app.new_task_field == NewTaskField::Title;
+    let editing_description =
+        app.input_mode == InputMode::Editing && app.new_task_field == NewTaskField::Description;
+
     // Title field
-    let title_style = if app.input_mode == InputMode::Editing {
+    let title_style = if editing_title {
         Style::default().fg(Color::Yellow)
     } else {
         Style::default().fg(Color::White)
@@ -84,10 +126,15 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
         Line::from(Span::styled(&app.new_task_title, title_style))
     };
 
+    let title_label = if app.new_task_title_suggested {
+        " Title (suggested from the description) "
+    } else {
+        " Title "
+    };
     let title_block = Block::default()
-        .title(Span::styled(" Title ", Style::default().fg(Color::Cyan)))
+        .title(Span::styled(title_label, Style::default().fg(Color::Cyan)))
         .borders(Borders::ALL)
-        .border_style(if app.input_mode == InputMode::Editing {
+        .border_style(if editing_title {
             Style::default().fg(Color::Yellow)
         } else {
             Style::default().fg(Color::DarkGray)
@@ -97,32 +144,134 @@ fn render_form(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(title_paragraph, chunks[0]);
 
     // Description field
-    let desc_content = if app.new_task_description.is_empty() {
-        Line::from(Span::styled(
+    let desc_content: Vec<Line> = if app.new_task_description.is_empty() {
+        vec![Line::from(Span::styled(
             "Enter task description (optional)...",
             Style::default().fg(Color::DarkGray),
-        ))
+        ))]
     } else {
-        Line::from(Span::styled(
-            &app.new_task_description,
-            Style::default().fg(Color::White),
-        ))
+        app.new_task_description
+            .split('\n')
+            .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::White))))
+            .collect()
     };
+    // Keep the last line, where the cursor is, in view
+    let desc_line_count = desc_content.len();
+    let desc_scroll = desc_line_count.saturating_sub(chunks[2].height.saturating_sub(2) as usize);
 
     let desc_block = Block::default()
         .title(Span::styled(" Description ", Style::default().fg(Color::Gray)))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(if editing_description {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
 
-    let desc_paragraph = Paragraph::new(desc_content).block(desc_block);
+    let desc_paragraph = Paragraph::new(desc_content)
+        .block(desc_block)
+        .scroll((desc_scroll as u16, 0));
     frame.render_widget(desc_paragraph, chunks[2]);
 
+    render_start(frame, chunks[3], app);
+
     // Show cursor when editing
-    if app.input_mode == InputMode::Editing {
+    if editing_title {
         let cursor_x = chunks[0].x + 1 + app.new_task_title.len() as u16;
         let cursor_y = chunks[0].y + 1;
         frame.set_cursor_position((cursor_x, cursor_y));
+    } else if editing_description {
+        let last_line = app
+            .new_task_description
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default();
+        let cursor_x = chunks[2].x + 1 + last_line.chars().count() as u16;
+        let cursor_y = chunks[2].y + 1 + (desc_line_count - 1 - desc_scroll) as u16;
+        frame.set_cursor_position((cursor_x, cursor_y));
+    }
+}
+
+/// Rows of the start section: the toggle, and the executor and a base branch
+/// per repository while it is on.
+fn start_height(app: &App) -> u16 {
+    let rows = if app.new_task_start {
+        2 + app.attempt_repo_branches.len()
+    } else {
+        1
+    };
+    rows as u16 + 2
+}
+
+fn render_start(frame: &mut Frame, area: Rect, app: &App) {
+    let field = app.new_task_field;
+    let style_for = |focused: bool| {
+        if focused {
+            selected_style()
+        } else {
+            Style::default().fg(Color::White)
+        }
+    };
+    let focused = !field.is_text();
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            if app.new_task_start { "[x] " } else { "[ ] " },
+            style_for(field == NewTaskField::Start),
+        ),
+        Span::styled(
+            "Start an attempt right away",
+            style_for(field == NewTaskField::Start),
+        ),
+    ])];
+    if app.new_task_start {
+        let executor = app
+            .available_executors()
+            .get(app.attempt_executor_index)
+            .map(|executor| {
+                if app.is_executor_available(*executor) {
+                    format!("{:?}", executor)
+                } else {
+                    format!("{:?} (unavailable)", executor)
+                }
+            })
+            .unwrap_or_else(|| "Loading...".to_string());
+        lines.push(Line::from(vec![
+            Span::styled("    Executor: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("◂ {} ▸", executor),
+                style_for(field == NewTaskField::Executor),
+            ),
+        ]));
+        for (i, (repo_id, branch)) in app.attempt_repo_branches.iter().enumerate() {
+            let repo_name = app
+                .project_repos
+                .iter()
+                .find(|r| r.id == *repo_id)
+                .map(|r| r.name.as_str())
+                .unwrap_or("Unknown");
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {} from: ", repo_name),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    format!("◂ {} ▸", branch),
+                    style_for(field == NewTaskField::RepoBranch(i)),
+                ),
+            ]));
+        }
     }
+
+    let block = Block::default()
+        .title(Span::styled(" Start ", Style::default().fg(Color::Gray)))
+        .borders(Borders::ALL)
+        .border_style(if focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
+    frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
 /// Helper function to create a centered rect.
@@ -0,0 +1,173 @@
+//! Diff review view: the hunks of a workspace's changes, with review notes on
+//! them to send to the agent as one follow-up.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{App, InputMode},
+    diff::{Hunk, HunkLineKind},
+    ui::components::{render_header, render_hints, render_status_bar},
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let commenting = app.input_mode == InputMode::Editing;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),                     // Header
+            Constraint::Min(5),                        // Hunks
+            Constraint::Length(u16::from(commenting)), // Comment
+            Constraint::Length(2),                     // Hints
+            Constraint::Length(2),                     // Status
+        ])
+        .split(frame.area());
+
+    let title = match &app.selected_workspace {
+        Some(workspace) => format!("Diff - {}", workspace.branch),
+        None => "Diff".to_string(),
+    };
+    render_header(frame, chunks[0], &title);
+
+    render_hunks(frame, chunks[1], app);
+
+    let hints = if commenting {
+        render_comment_input(frame, chunks[2], app);
+        vec![("Enter", "Save"), ("Esc", "Cancel")]
+    } else {
+        vec![
+            ("↑/↓", "Hunk"),
+            ("c", "Comment"),
+            ("d", "Remove Comment"),
+            ("S", "Send to Agent"),
+            ("r", "Refresh"),
+            ("Esc", "Back"),
+        ]
+    };
+    render_hints(frame, chunks[3], &hints);
+
+    render_status_bar(frame, chunks[4], app);
+}
+
+/// The hunks from the selected one down, each under its file's path.
+fn render_hunks(frame: &mut Frame, area: Rect, app: &App) {
+    let files = {
+        let mut paths: Vec<_> = app.diff_hunks.iter().map(|h| h.path.as_str()).collect();
+        paths.dedup();
+        paths.len()
+    };
+    let title = if app.diff_hunks.is_empty() {
+        " No changes ".to_string()
+    } else {
+        format!(
+            " {} files · hunk {}/{} · {} comments ",
+            files,
+            app.selected_hunk_index + 1,
+            app.diff_hunks.len(),
+            app.diff_comments().len()
+        )
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let mut lines = Vec::new();
+    let mut previous_path = app
+        .selected_hunk_index
+        .checked_sub(1)
+        .and_then(|i| app.diff_hunks.get(i))
+        .map(|h| h.path.as_str());
+    for (i, hunk) in app
+        .diff_hunks
+        .iter()
+        .enumerate()
+        .skip(app.selected_hunk_index)
+    {
+        // Sized by the pane; the rest is cut off anyway
+        if lines.len() > area.height as usize {
+            break;
+        }
+        if previous_path != Some(hunk.path.as_str()) {
+            lines.push(Line::from(Span::styled(
+                hunk.path.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        previous_path = Some(hunk.path.as_str());
+        lines.extend(hunk_lines(app, hunk, i == app.selected_hunk_index));
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block),
+        area,
+    );
+}
+
+fn hunk_lines(app: &App, hunk: &Hunk, selected: bool) -> Vec<Line<'static>> {
+    let (marker, header_style) = if selected {
+        (
+            "▸ ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        ("  ", Style::default().fg(Color::Magenta))
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(marker, Style::default().fg(Color::Cyan)),
+        Span::styled(hunk.header.clone(), header_style),
+    ])];
+    for line in &hunk.lines {
+        let (sign, color) = match line.kind {
+            HunkLineKind::Context => (' ', Color::DarkGray),
+            HunkLineKind::Added => ('+', Color::Green),
+            HunkLineKind::Removed => ('-', Color::Red),
+        };
+        lines.push(Line::from(Span::styled(
+            format!("  {}{}", sign, line.text),
+            Style::default().fg(color),
+        )));
+    }
+    if let Some(comment) = app.hunk_comment(hunk) {
+        for (i, text) in comment.comment.lines().enumerate() {
+            let prefix = if i == 0 { "  ✎ " } else { "    " };
+            lines.push(Line::from(Span::styled(
+                format!("{}{}", prefix, text),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+fn render_comment_input(frame: &mut Frame, area: Rect, app: &App) {
+    let mut spans = vec![
+        Span::styled(" Comment: ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            app.diff_comment_input.as_str(),
+            Style::default().fg(Color::White),
+        ),
+    ];
+    if app.diff_comment_input.is_empty() {
+        spans.push(Span::styled(
+            "what the agent should change here (empty removes the comment)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    let cursor_x = area.x + 10 + app.diff_comment_input.chars().count() as u16;
+    frame.set_cursor_position((cursor_x, area.y));
+}
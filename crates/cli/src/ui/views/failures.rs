@@ -0,0 +1,199 @@
+//! Workspaces whose latest attempt failed, by why they failed.
+
+use chrono::Utc;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+
+use crate::{
+    app::App,
+    types::{FailureCategory, WorkspaceFailure},
+    ui::components::{
+        failure_style, focused_border_style, render_header, render_hints, render_scrolled_list,
+        render_status_bar, selected_style,
+    },
+    utils::{format_runtime, pad_truncate},
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Length(1), // Category filter
+            Constraint::Min(10),   // Content
+            Constraint::Length(2), // Hints
+            Constraint::Length(2), // Status
+        ])
+        .split(frame.area());
+
+    let title = match &app.selected_project {
+        Some(project) if app.failures_project_id == Some(project.id) => {
+            format!("Failures - {}", project.name)
+        }
+        _ => "Failures".to_string(),
+    };
+    render_header(frame, chunks[0], &title);
+    render_filter(frame, chunks[1], app);
+
+    let content_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(7)])
+        .split(chunks[2]);
+    render_failure_list(frame, content_chunks[0], app);
+    render_failure_details(frame, content_chunks[1], app);
+
+    render_hints(
+        frame,
+        chunks[3],
+        &[
+            ("↑/↓", "Navigate"),
+            ("Enter", "Open Workspace"),
+            ("f", "Filter"),
+            ("r", "Refresh"),
+            ("Esc", "Back"),
+        ],
+    );
+
+    render_status_bar(frame, chunks[4], app);
+}
+
+/// Every category, with the one shown highlighted
+fn render_filter(frame: &mut Frame, area: Rect, app: &App) {
+    let highlight = |active: bool| {
+        if active {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+    let mut spans = vec![
+        Span::raw(" "),
+        Span::styled(" all ", highlight(app.failure_filter.is_none())),
+    ];
+    for category in FailureCategory::ALL {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!(" {} {} ", category.icon(), category.label()),
+            highlight(app.failure_filter == Some(category)),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn render_failure_list(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(format!(" Failed Attempts ({}) ", app.failures.len()))
+        .borders(Borders::ALL)
+        .border_style(focused_border_style());
+
+    if app.failures.is_empty() {
+        let message = match app.failure_filter {
+            Some(category) => format!("No {} failures", category.label()),
+            None => "No failed attempts".to_string(),
+        };
+        let empty = Paragraph::new(Line::from(Span::styled(
+            message,
+            Style::default().fg(Color::DarkGray),
+        )))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    // Marker, icon, category, branch and age around the task title
+    let title_width = (area.width as usize).saturating_sub(2 + 2 + 2 + 16 + 26 + 14);
+    let items: Vec<ListItem> = app
+        .failures
+        .iter()
+        .enumerate()
+        .map(|(i, failure)| {
+            let selected = i == app.selected_failure_index;
+            let style = if selected {
+                selected_style()
+            } else {
+                Style::default()
+            };
+            let marker = if selected { "▸ " } else { "  " };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(
+                    format!("{} ", failure.category.icon()),
+                    failure_style(failure.category),
+                ),
+                Span::styled(
+                    pad_truncate(failure.category.label(), 16),
+                    failure_style(failure.category),
+                ),
+                Span::styled(pad_truncate(&failure.task_title, title_width), style),
+                Span::styled(
+                    format!(" {}", pad_truncate(&failure.branch, 25)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!(" {:>13}", failed_ago(failure)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    render_scrolled_list(
+        frame,
+        area,
+        List::new(items).block(block),
+        app.selected_failure_index,
+        &app.failure_list_offset,
+    );
+}
+
+fn render_failure_details(frame: &mut Frame, area: Rect, app: &App) {
+    let content = match app.failures.get(app.selected_failure_index) {
+        Some(failure) => vec![
+            Line::from(vec![
+                Span::styled("Task: ", Style::default().fg(Color::Gray)),
+                Span::styled(&failure.task_title, Style::default().fg(Color::White)),
+            ]),
+            Line::from(vec![
+                Span::styled("Failed: ", Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!("{} {}", failure.category.icon(), failure.category.label()),
+                    failure_style(failure.category),
+                ),
+                Span::styled(
+                    format!(" · {}", failed_ago(failure)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Detail: ", Style::default().fg(Color::Gray)),
+                match &failure.detail {
+                    Some(detail) => Span::styled(detail, Style::default().fg(Color::White)),
+                    None => Span::styled("none", Style::default().fg(Color::DarkGray)),
+                },
+            ]),
+        ],
+        None => vec![Line::from(Span::styled(
+            "No failure selected",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Details ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn failed_ago(failure: &WorkspaceFailure) -> String {
+    let seconds = (Utc::now() - failure.created_at).num_seconds();
+    format!("{} ago", format_runtime(seconds))
+}
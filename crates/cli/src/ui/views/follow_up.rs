@@ -0,0 +1,200 @@
+//! Follow-up composer view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{App, FollowUpField, InputMode},
+    ui::components::{
+        focused_border_style, render_header, render_hints, render_status_bar, selected_style,
+    },
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let variant_rows = app.follow_up_variant_options().len().max(1) as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),                // Header
+            Constraint::Min(5),                   // Prompt
+            Constraint::Length(3),                // Executor
+            Constraint::Length(variant_rows + 2), // Variant
+            Constraint::Length(2),                // Hints
+            Constraint::Length(2),                // Status
+        ])
+        .split(frame.area());
+
+    let title = match &app.selected_task {
+        Some(task) => format!("Follow-up - {}", task.task.title),
+        None => "Follow-up".to_string(),
+    };
+    render_header(frame, chunks[0], &title);
+
+    render_prompt(frame, chunks[1], app);
+    render_executor(frame, chunks[2], app);
+    render_variants(frame, chunks[3], app);
+
+    let hints = if app.input_mode == InputMode::Editing {
+        vec![
+            ("Enter", "New Line"),
+            ("Ctrl+S", "Send"),
+            ("Tab", "Next Field"),
+            ("Esc", "Stop Editing"),
+        ]
+    } else {
+        vec![
+            ("e", "Edit"),
+            ("t", "Templates"),
+            ("↑/↓", "Change"),
+            ("Tab", "Next Field"),
+            ("Ctrl+S", "Send"),
+            ("Esc", "Cancel"),
+        ]
+    };
+    render_hints(frame, chunks[4], &hints);
+
+    render_status_bar(frame, chunks[5], app);
+}
+
+fn field_border_style(app: &App, field: FollowUpField) -> Style {
+    if app.follow_up_field == field {
+        focused_border_style()
+    } else {
+        Style::default().fg(Color::DarkGray)
+    }
+}
+
+fn render_prompt(frame: &mut Frame, area: Rect, app: &App) {
+    let editing = app.input_mode == InputMode::Editing;
+    let border_style = if editing {
+        Style::default().fg(Color::Yellow)
+    } else {
+        field_border_style(app, FollowUpField::Prompt)
+    };
+    let block = Block::default()
+        .title(" Message * ")
+        .borders(Borders::ALL)
+        .border_style(border_style);
+
+    let text: Vec<Line> = if app.follow_up_input.is_empty() {
+        vec![Line::from(Span::styled(
+            "What should the agent do next?",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.follow_up_input
+            .split('\n')
+            .map(|line| Line::from(line.to_string()))
+            .collect()
+    };
+
+    // Keep the end of a long message, where the cursor is, in view
+    let line_count = text.len();
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let scroll = line_count.saturating_sub(inner_height) as u16;
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .style(Style::default().fg(Color::White))
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, area);
+
+    if editing {
+        let last = app.follow_up_input.rsplit('\n').next().unwrap_or_default();
+        let row = line_count.saturating_sub(1) as u16 - scroll;
+        let cursor_x = area.x + 1 + last.chars().count() as u16;
+        let cursor_y = area.y + 1 + row;
+        frame.set_cursor_position((cursor_x, cursor_y));
+    }
+}
+
+fn render_executor(frame: &mut Frame, area: Rect, app: &App) {
+    let executor = app
+        .available_executors()
+        .get(app.follow_up_executor_index)
+        .copied();
+    let focused = app.follow_up_field == FollowUpField::Executor;
+    let mut spans = vec![Span::styled(
+        executor.map(|e| format!("{:?}", e)).unwrap_or_default(),
+        if focused {
+            selected_style()
+        } else {
+            Style::default().fg(Color::White)
+        },
+    )];
+    if app.is_follow_up_executor_fixed() {
+        spans.push(Span::styled(
+            "  (used by this session)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else if let Some(health) = executor.and_then(|e| app.executor_health_for(e))
+        && !health.available
+    {
+        let message = health.message.as_deref().unwrap_or("unavailable");
+        spans.push(Span::styled(
+            format!("  ⓘ {}", message),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans))
+        .block(
+            Block::default()
+                .title(" Executor ")
+                .borders(Borders::ALL)
+                .border_style(field_border_style(app, FollowUpField::Executor)),
+        )
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
+
+fn render_variants(frame: &mut Frame, area: Rect, app: &App) {
+    let variants = app.follow_up_variant_options();
+    let focused = app.follow_up_field == FollowUpField::Variant;
+    let block = Block::default()
+        .title(" Variant ")
+        .borders(Borders::ALL)
+        .border_style(field_border_style(app, FollowUpField::Variant));
+
+    if variants.is_empty() {
+        let paragraph = Paragraph::new(Span::styled(
+            "Default",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let name_width = variants.iter().map(|v| v.name.len()).max().unwrap_or(0);
+    let items: Vec<ListItem> = variants
+        .iter()
+        .map(|variant| {
+            let chosen = app.is_follow_up_variant(variant);
+            let style = if chosen && focused {
+                selected_style()
+            } else if chosen {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let marker = if chosen { "▸ " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(
+                    format!("{:width$}  ", variant.name, width = name_width),
+                    style,
+                ),
+                Span::styled(
+                    variant.description.as_str(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(block), area);
+}
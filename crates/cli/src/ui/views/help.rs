@@ -10,7 +10,8 @@ use ratatui::{
 
 use crate::{
     app::App,
-    ui::components::{render_header, render_status_bar},
+    keymap::{Action, KeyMap},
+    ui::components::{centered_rect, render_header, render_status_bar},
 };
 
 pub fn render(frame: &mut Frame, app: &App) {
@@ -28,13 +29,13 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     // Help content
     let help_area = centered_rect(80, 80, chunks[1]);
-    render_help_content(frame, help_area);
+    render_help_content(frame, help_area, &app.config.keybindings);
 
     // Status bar
     render_status_bar(frame, chunks[2], app);
 }
 
-fn render_help_content(frame: &mut Frame, area: Rect) {
+fn render_help_content(frame: &mut Frame, area: Rect, keys: &KeyMap) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -50,18 +51,51 @@ fn render_help_content(frame: &mut Frame, area: Rect) {
     // Navigation shortcuts
     let nav_content = vec![
         section_header("Navigation"),
-        shortcut("↑/k", "Move up"),
-        shortcut("↓/j", "Move down"),
-        shortcut("←/h", "Move left / Previous column"),
-        shortcut("→/l", "Move right / Next column"),
-        shortcut("Enter", "Select / Confirm"),
-        shortcut("Esc", "Go back / Cancel"),
+        bound(keys, Action::MoveUp, "Move up"),
+        bound(keys, Action::MoveDown, "Move down"),
+        bound(keys, Action::MoveLeft, "Move left / Previous column"),
+        bound(keys, Action::MoveRight, "Move right / Next column"),
+        bound(keys, Action::Select, "Select / Confirm"),
+        bound(keys, Action::Back, "Go back / Cancel"),
         shortcut("Tab", "Next field (in forms)"),
+        shortcut("Mouse", "Click a task to select, double-click to open"),
+        shortcut("Wheel", "Move selection / Scroll log"),
         Line::from(""),
         section_header("Global"),
-        shortcut("?", "Show this help"),
-        shortcut("q", "Quit application"),
-        shortcut("r", "Refresh current view"),
+        bound(keys, Action::Help, "Show this help"),
+        bound(keys, Action::Quit, "Quit application"),
+        bound(keys, Action::Jump, "Jump to a project or task"),
+        bound(keys, Action::Palette, "Run a command by name"),
+        bound(keys, Action::Theme, "Pick a colour theme"),
+        bound(keys, Action::Metrics, "Show API request metrics"),
+        bound(keys, Action::Notifications, "Show past messages and errors"),
+        bound(keys, Action::Timestamps, "Show times as dates or relative"),
+        bound(keys, Action::Refresh, "Refresh current view"),
+        bound(keys, Action::Queue, "Show attempt queue"),
+        bound(keys, Action::Failures, "Show failed attempts"),
+        bound(keys, Action::PlannerSettings, "Edit team planner settings"),
+        bound(keys, Action::Follow, "Follow the most active workspace"),
+        bound(keys, Action::OpenLink, "Open a link on screen"),
+        bound(keys, Action::ErrorDetails, "Show details of the last error"),
+        bound(keys, Action::Retry, "Retry the failed action"),
+        bound(keys, Action::CancelSteps, "Cancel remaining steps"),
+        Line::from(""),
+        section_header("Session Logs"),
+        bound(keys, Action::Logs, "Open from workspace detail"),
+        bound(keys, Action::Top, "Jump to top"),
+        bound(keys, Action::Bottom, "Follow output"),
+        bound(keys, Action::PreviousProcess, "Previous process"),
+        bound(keys, Action::NextProcess, "Next process"),
+        bound(keys, Action::Bookmark, "Bookmark the bottom line"),
+        bound(keys, Action::NextBookmark, "Next bookmark"),
+        bound(keys, Action::PreviousBookmark, "Previous bookmark"),
+        bound(keys, Action::ExportTranscript, "Export transcript"),
+        Line::from(""),
+        section_header("Diff Review"),
+        bound(keys, Action::Diff, "Open from workspace detail"),
+        bound(keys, Action::Comment, "Comment on the hunk"),
+        bound(keys, Action::Delete, "Remove the hunk's comment"),
+        bound(keys, Action::SendReview, "Send comments as a follow-up"),
     ];
 
     let nav_paragraph = Paragraph::new(nav_content);
@@ -70,22 +104,79 @@ fn render_help_content(frame: &mut Frame, area: Rect) {
     // Action shortcuts
     let action_content = vec![
         section_header("Projects"),
-        shortcut("n", "Create new project"),
+        bound(keys, Action::New, "Create new project"),
+        bound(keys, Action::RetentionSettings, "Workspace cleanup policy"),
         shortcut("Enter", "Select project"),
         Line::from(""),
         section_header("Tasks"),
-        shortcut("n", "Create new task"),
-        shortcut("m", "Move task to next status"),
-        shortcut("d", "Delete task"),
+        bound(keys, Action::New, "Create new task"),
+        bound(keys, Action::MoveTask, "Move task(s) to next status"),
+        bound(keys, Action::MoveTaskBack, "Move task(s) back a status"),
+        bound(keys, Action::MoveCardLeft, "Move card to the left column"),
+        bound(keys, Action::MoveCardRight, "Move card to the right column"),
+        bound(keys, Action::MoveCardUp, "Move card up (order by hand)"),
+        bound(keys, Action::MoveCardDown, "Move card down (order by hand)"),
+        bound(keys, Action::ToggleCancelled, "Show cancelled tasks"),
+        bound(keys, Action::SortTasks, "Sort column"),
+        bound(keys, Action::ZoomOut, "Zoom out to epics"),
+        bound(keys, Action::ZoomIn, "Zoom in to subtasks or attempts"),
+        bound(keys, Action::Mark, "Mark task"),
+        bound(keys, Action::StartAttempts, "Start attempts (marked tasks)"),
+        bound(keys, Action::Search, "Search tasks (executor:, status:)"),
+        bound(keys, Action::CycleFilter, "Failed or running tasks only"),
+        bound(keys, Action::ExecutorFilter, "One executor's tasks only"),
+        bound(keys, Action::Delete, "Move task to the trash"),
+        bound(keys, Action::Undo, "Undo task deletion"),
+        bound(keys, Action::Trash, "Show deleted tasks"),
+        bound(keys, Action::TaskTree, "Show parent, attempts and subtasks"),
+        bound(keys, Action::Yank, "Copy task ID"),
+        shortcut("Enter", "View task details"),
+        Line::from(""),
+        section_header("Task Details"),
+        bound(keys, Action::Edit, "Edit title and description"),
+        bound(keys, Action::CopyPrompt, "Copy as an agent prompt"),
+        bound(keys, Action::SavePrompt, "Save as an agent prompt"),
+        bound(keys, Action::Metadata, "Inspect and edit metadata"),
+        shortcut("Ctrl+S", "Save edits"),
         shortcut("Enter", "View task workspaces"),
         Line::from(""),
+        section_header("Workspaces"),
+        bound(keys, Action::RestartContainer, "Restart container"),
+        bound(
+            keys,
+            Action::RebuildContainer,
+            "Rebuild container from branch",
+        ),
+        bound(keys, Action::Rename, "Name the workspace"),
+        bound(keys, Action::Notes, "Edit the workspace notes"),
+        bound(keys, Action::NewSubtask, "Create a subtask from the workspace"),
+        bound(keys, Action::Yank, "Copy branch name"),
+        Line::from(""),
+        section_header("Failures"),
+        bound(keys, Action::CycleFilter, "Show next category"),
+        shortcut("Enter", "Open the workspace"),
+        Line::from(""),
+        section_header("Trash"),
+        bound(keys, Action::Undo, "Restore the task"),
+        bound(keys, Action::Delete, "Delete the task for good"),
+        Line::from(""),
         section_header("Git Operations"),
-        shortcut("m", "Merge to target branch"),
-        shortcut("p", "Push to remote"),
+        bound(keys, Action::Merge, "Merge to target branch"),
+        bound(keys, Action::MergeAll, "Merge in every repo"),
+        bound(keys, Action::Push, "Push to remote"),
         shortcut("P", "Force push to remote"),
-        shortcut("b", "Rebase on target branch"),
-        shortcut("s", "Stop running process"),
-        shortcut("f", "Send follow-up message"),
+        bound(keys, Action::Rebase, "Rebase on target branch"),
+        bound(keys, Action::RebaseOnto, "Rebase onto another branch"),
+        bound(keys, Action::CreatePr, "Open a pull request"),
+        bound(keys, Action::OpenEditor, "Open the worktree in the editor"),
+        bound(keys, Action::YankUrl, "Copy pull request URL"),
+        bound(keys, Action::Stop, "Stop the picked or running process"),
+        bound(keys, Action::RetryAttempt, "Retry a failed attempt"),
+        bound(keys, Action::ResolveConflicts, "Agent resolves conflicts"),
+        bound(keys, Action::DevServer, "Start or stop the dev server"),
+        bound(keys, Action::FollowUp, "Send follow-up message"),
+        shortcut("Ctrl+S", "Send (in follow-up composer)"),
+        bound(keys, Action::Templates, "Follow-up templates (in composer)"),
     ];
 
     let action_paragraph = Paragraph::new(action_content);
@@ -104,7 +195,7 @@ fn section_header(title: &str) -> Line<'static> {
 fn shortcut(key: &str, desc: &str) -> Line<'static> {
     Line::from(vec![
         Span::styled(
-            format!("  {:12}", key),
+            format!("  {:12} ", key),
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -113,23 +204,7 @@ fn shortcut(key: &str, desc: &str) -> Line<'static> {
     ])
 }
 
-/// Helper function to create a centered rect.
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+/// A shortcut for the keys currently bound to `action`
+fn bound(keys: &KeyMap, action: Action, desc: &str) -> Line<'static> {
+    shortcut(&keys.describe(action), desc)
 }
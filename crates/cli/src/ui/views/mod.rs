@@ -1,9 +1,20 @@
 //! View modules for different screens.
 
 pub mod create_attempt;
+pub mod create_project;
 pub mod create_task;
+pub mod diff;
+pub mod failures;
+pub mod follow_up;
 pub mod help;
+pub mod planner_settings;
 pub mod projects;
+pub mod queue;
+pub mod retention_settings;
+pub mod session_logs;
+pub mod task_detail;
+pub mod task_tree;
 pub mod tasks;
+pub mod trash;
 pub mod workspace_detail;
 pub mod workspaces;
@@ -0,0 +1,114 @@
+//! Planner settings form view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    app::{App, InputMode, PlannerField},
+    ui::components::{
+        centered_rect, focused_border_style, render_header, render_hints, render_status_bar,
+    },
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Min(14),   // Form
+            Constraint::Length(2), // Hints
+            Constraint::Length(2), // Status
+        ])
+        .split(frame.area());
+
+    render_header(frame, chunks[0], "Planner Settings");
+
+    // Just tall enough for the fields, centred in the usual form area
+    let outer = centered_rect(60, 80, chunks[1]);
+    let height = (PlannerField::ALL.len() as u16 * 4 + 2).min(outer.height);
+    let form_area = Rect {
+        y: outer.y + (outer.height - height) / 2,
+        height,
+        ..outer
+    };
+    render_form(frame, form_area, app);
+
+    let hints = if app.input_mode == InputMode::Editing {
+        vec![("Enter", "Done"), ("Tab", "Next Field"), ("Ctrl+S", "Save")]
+    } else {
+        vec![
+            ("↑/↓", "Navigate"),
+            ("Enter", "Edit"),
+            ("Ctrl+S", "Save"),
+            ("Esc", "Cancel"),
+        ]
+    };
+    render_hints(frame, chunks[2], &hints);
+
+    render_status_bar(frame, chunks[3], app);
+}
+
+fn render_form(frame: &mut Frame, area: Rect, app: &App) {
+    let outer_block = Block::default()
+        .title(" Team Planner ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(outer_block, area);
+
+    // An input and its description per field
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            PlannerField::ALL
+                .iter()
+                .flat_map(|_| [Constraint::Length(3), Constraint::Length(1)])
+                .chain([Constraint::Min(0)]),
+        )
+        .split(area);
+
+    for (i, field) in PlannerField::ALL.into_iter().enumerate() {
+        let focused = field == app.planner_field;
+        let editing = focused && app.input_mode == InputMode::Editing;
+        let border_style = if editing {
+            Style::default().fg(Color::Yellow)
+        } else if focused {
+            focused_border_style()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        let input = Paragraph::new(Span::styled(
+            app.planner_input(field),
+            if editing {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::White)
+            },
+        ))
+        .block(
+            Block::default()
+                .title(format!(" {} ", field.label()))
+                .borders(Borders::ALL)
+                .border_style(border_style),
+        );
+        let input_area = rows[i * 2];
+        frame.render_widget(input, input_area);
+
+        let description = Line::from(Span::styled(
+            format!(" {}", field.description()),
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(Paragraph::new(description), rows[i * 2 + 1]);
+
+        if editing {
+            let cursor_x = input_area.x + 1 + app.planner_input(field).chars().count() as u16;
+            frame.set_cursor_position((cursor_x, input_area.y + 1));
+        }
+    }
+}
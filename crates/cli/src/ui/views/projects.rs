@@ -2,7 +2,7 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
@@ -11,7 +11,8 @@ use ratatui::{
 use crate::{
     app::App,
     ui::components::{
-        focused_border_style, render_header, render_hints, render_status_bar, selected_style,
+        focused_border_style, render_header, render_hints, render_scrolled_list, render_status_bar,
+        selected_style,
     },
 };
 
@@ -80,20 +81,20 @@ fn render_project_list(frame: &mut Frame, area: Rect, app: &App) {
         })
         .collect();
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .title(" Projects ")
-                .borders(Borders::ALL)
-                .border_style(focused_border_style()),
-        )
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .bg(Color::Rgb(40, 40, 60)),
-        );
-
-    frame.render_widget(list, area);
+    let list = List::new(items).block(
+        Block::default()
+            .title(" Projects ")
+            .borders(Borders::ALL)
+            .border_style(focused_border_style()),
+    );
+
+    render_scrolled_list(
+        frame,
+        area,
+        list,
+        app.selected_project_index,
+        &app.project_list_offset,
+    );
 }
 
 fn render_project_details(frame: &mut Frame, area: Rect, app: &App) {
@@ -115,7 +116,7 @@ fn render_project_details(frame: &mut Frame, area: Rect, app: &App) {
             Line::from(vec![
                 Span::styled("Created: ", Style::default().fg(Color::Gray)),
                 Span::styled(
-                    project.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                    app.timestamp(project.created_at),
                     Style::default().fg(Color::White),
                 ),
             ]),
@@ -123,7 +124,7 @@ fn render_project_details(frame: &mut Frame, area: Rect, app: &App) {
             Line::from(vec![
                 Span::styled("Updated: ", Style::default().fg(Color::Gray)),
                 Span::styled(
-                    project.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                    app.timestamp(project.updated_at),
                     Style::default().fg(Color::White),
                 ),
             ]),
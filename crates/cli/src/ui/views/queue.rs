@@ -0,0 +1,106 @@
+//! Global queue of team tasks waiting for a worker slot.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+use crate::{
+    app::App,
+    ui::components::{
+        focused_border_style, render_header, render_hints, render_scrolled_list, render_status_bar,
+        selected_style,
+    },
+    utils::format_eta,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Min(10),   // Content
+            Constraint::Length(2), // Hints
+            Constraint::Length(2), // Status
+        ])
+        .split(frame.area());
+
+    render_header(frame, chunks[0], "Attempt Queue");
+    render_queue_list(frame, chunks[1], app);
+
+    render_hints(
+        frame,
+        chunks[2],
+        &[("↑/↓", "Navigate"), ("r", "Refresh"), ("Esc", "Back")],
+    );
+
+    render_status_bar(frame, chunks[3], app);
+}
+
+fn render_queue_list(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(format!(" Queued ({}) ", app.attempt_queue.len()))
+        .borders(Borders::ALL)
+        .border_style(focused_border_style());
+
+    if app.attempt_queue.is_empty() {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "Nothing is waiting for a worker",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .attempt_queue
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let selected = i == app.selected_queue_index;
+            let style = if selected {
+                selected_style()
+            } else {
+                Style::default()
+            };
+            let marker = if selected { "▸ " } else { "  " };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(
+                    format!("#{:<3} ", entry.position),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(entry.task_title.clone(), style),
+                Span::styled(
+                    format!(
+                        "  starts {}",
+                        format_eta(entry.estimated_start_at, app.config.time_zone)
+                    ),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    format!(
+                        "  [{}/{} workers busy · team {}]",
+                        entry.running,
+                        entry.max_parallel_workers,
+                        &entry.team_execution_id.to_string()[..8]
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    render_scrolled_list(
+        frame,
+        area,
+        List::new(items).block(block),
+        app.selected_queue_index,
+        &app.queue_list_offset,
+    );
+}
@@ -0,0 +1,218 @@
+//! Workspace retention policy form view, with what the policy would do right now.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    app::{App, InputMode, RetentionField},
+    types::RetentionAction,
+    ui::components::{focused_border_style, render_header, render_hints, render_status_bar},
+    utils::pad_truncate,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    // An input and its description per field
+    let form_height = RetentionField::ALL.len() as u16 * 4 + 2;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),           // Header
+            Constraint::Length(form_height), // Form
+            Constraint::Min(5),              // Preview
+            Constraint::Length(2),           // Hints
+            Constraint::Length(2),           // Status
+        ])
+        .split(frame.area());
+
+    render_header(frame, chunks[0], "Workspace Cleanup");
+    render_form(frame, chunks[1], app);
+    render_preview(frame, chunks[2], app);
+
+    let hints = if app.input_mode == InputMode::Editing {
+        vec![("Enter", "Done"), ("Tab", "Next Field"), ("Ctrl+S", "Save")]
+    } else {
+        vec![
+            ("↑/↓", "Navigate"),
+            ("Enter", "Edit/Toggle"),
+            ("r", "Refresh Preview"),
+            ("Ctrl+S", "Save"),
+            ("Esc", "Cancel"),
+        ]
+    };
+    render_hints(frame, chunks[3], &hints);
+
+    render_status_bar(frame, chunks[4], app);
+}
+
+fn render_form(frame: &mut Frame, area: Rect, app: &App) {
+    let outer_block = Block::default()
+        .title(" Retention Policy ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(outer_block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            RetentionField::ALL
+                .iter()
+                .flat_map(|_| [Constraint::Length(3), Constraint::Length(1)])
+                .chain([Constraint::Min(0)]),
+        )
+        .split(area);
+
+    for (i, field) in RetentionField::ALL.into_iter().enumerate() {
+        let focused = field == app.retention_field;
+        let editing = focused && app.input_mode == InputMode::Editing;
+        let border_style = if editing {
+            Style::default().fg(Color::Yellow)
+        } else if focused {
+            focused_border_style()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        let value = match field {
+            RetentionField::ArchiveMergedAfterDays if app.retention_archive_days.is_empty() => {
+                Span::styled("Never", Style::default().fg(Color::DarkGray))
+            }
+            RetentionField::ArchiveMergedAfterDays => Span::styled(
+                app.retention_archive_days.as_str(),
+                if editing {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::White)
+                },
+            ),
+            RetentionField::PurgeTrashAfterDays if app.retention_purge_days.is_empty() => {
+                Span::styled("Never", Style::default().fg(Color::DarkGray))
+            }
+            RetentionField::PurgeTrashAfterDays => Span::styled(
+                app.retention_purge_days.as_str(),
+                if editing {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::White)
+                },
+            ),
+            RetentionField::DeleteCancelledWorktrees if app.retention_delete_cancelled => {
+                Span::styled("[x] Yes", Style::default().fg(Color::White))
+            }
+            RetentionField::DeleteCancelledWorktrees => {
+                Span::styled("[ ] No", Style::default().fg(Color::White))
+            }
+        };
+        let input = Paragraph::new(value).block(
+            Block::default()
+                .title(format!(" {} ", field.label()))
+                .borders(Borders::ALL)
+                .border_style(border_style),
+        );
+        let input_area = rows[i * 2];
+        frame.render_widget(input, input_area);
+
+        let description = Line::from(Span::styled(
+            format!(" {}", field.description()),
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(Paragraph::new(description), rows[i * 2 + 1]);
+
+        if editing {
+            let text = match field {
+                RetentionField::PurgeTrashAfterDays => &app.retention_purge_days,
+                _ => &app.retention_archive_days,
+            };
+            let cursor_x = input_area.x + 1 + text.chars().count() as u16;
+            frame.set_cursor_position((cursor_x, input_area.y + 1));
+        }
+    }
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, app: &App) {
+    // Typed but not yet previewed; the list is for the policy before the edit
+    let current = app.is_retention_preview_current();
+    let title = if current {
+        format!(" Affected Now ({}) ", app.retention_preview.len())
+    } else {
+        " Affected Now (out of date, press Enter to update) ".to_string()
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines: Vec<Line> = if app.retention_preview.is_empty() {
+        vec![Line::from(Span::styled(
+            " Nothing would be archived or removed",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        let visible = inner.height as usize;
+        let hidden = app.retention_preview.len().saturating_sub(visible);
+        // Keep the last row to say how many more there are
+        let shown = if hidden > 0 {
+            visible.saturating_sub(1)
+        } else {
+            visible
+        };
+        let task_width = (inner.width as usize).saturating_sub(16 + 24 + 20 + 4);
+        let mut lines: Vec<Line> = app
+            .retention_preview
+            .iter()
+            .take(shown)
+            .map(|candidate| {
+                let (color, since) = match candidate.action {
+                    RetentionAction::Archive => (Color::Cyan, "merged"),
+                    RetentionAction::DeleteWorktree => (Color::Red, "cancelled"),
+                };
+                let style = if current {
+                    Style::default()
+                } else {
+                    Style::default().add_modifier(Modifier::DIM)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {}", pad_truncate(candidate.action.label(), 16)),
+                        style.fg(color),
+                    ),
+                    Span::styled(
+                        pad_truncate(
+                            &format!("{} / {}", candidate.project_name, candidate.task_title),
+                            task_width,
+                        ),
+                        style,
+                    ),
+                    Span::styled(
+                        format!(" {}", pad_truncate(&candidate.branch, 24)),
+                        style.fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!(
+                            " {} {}",
+                            since,
+                            app.config.time_zone.format(candidate.since, "%Y-%m-%d")
+                        ),
+                        style.fg(Color::DarkGray),
+                    ),
+                ])
+            })
+            .collect();
+        let remaining = app.retention_preview.len() - lines.len();
+        if remaining > 0 {
+            lines.push(Line::from(Span::styled(
+                format!(" … and {} more", remaining),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines
+    };
+    frame.render_widget(Paragraph::new(lines), inner);
+}
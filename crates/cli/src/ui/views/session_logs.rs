@@ -0,0 +1,241 @@
+//! Session log view: tails the output of one execution process of a session.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    app::{App, InputMode},
+    logs::{LogKind, LogLine},
+    types::ExecutionProcessStatus,
+    ui::components::{render_header, render_hints, render_status_bar},
+};
+
+/// Width of the bookmark sidebar, borders included
+const SIDEBAR_WIDTH: u16 = 36;
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let noting = app.input_mode == InputMode::Editing;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),                 // Header
+            Constraint::Min(5),                    // Log
+            Constraint::Length(u16::from(noting)), // Bookmark note
+            Constraint::Length(2),                 // Hints
+            Constraint::Length(2),                 // Status
+        ])
+        .split(frame.area());
+
+    let title = match &app.selected_workspace {
+        Some(workspace) => format!("Logs - {}", workspace.branch),
+        None => "Logs".to_string(),
+    };
+    render_header(frame, chunks[0], &title);
+
+    if app.log_bookmarks().is_empty() {
+        render_log_pane(frame, chunks[1], app);
+    } else {
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(20), Constraint::Length(SIDEBAR_WIDTH)])
+            .split(chunks[1]);
+        render_log_pane(frame, panes[0], app);
+        render_bookmarks(frame, panes[1], app);
+    }
+
+    let hints = if noting {
+        render_note(frame, chunks[2], app);
+        vec![("Enter", "Bookmark"), ("Esc", "Cancel")]
+    } else {
+        vec![
+            ("↑/↓", "Scroll"),
+            ("g/G", "Top/Follow"),
+            ("[/]", "Prev/Next Process"),
+            ("m", "Bookmark"),
+            ("b/B", "Next/Prev Bookmark"),
+            ("w", "Export"),
+            ("Esc", "Back"),
+        ]
+    };
+    render_hints(frame, chunks[3], &hints);
+
+    render_status_bar(frame, chunks[4], app);
+}
+
+fn render_note(frame: &mut Frame, area: Rect, app: &App) {
+    let mut spans = vec![
+        Span::styled(" Note: ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            app.log_bookmark_note.as_str(),
+            Style::default().fg(Color::White),
+        ),
+    ];
+    if app.log_bookmark_note.is_empty() {
+        spans.push(Span::styled(
+            "what happened here (optional)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    let cursor_x = area.x + 7 + app.log_bookmark_note.chars().count() as u16;
+    frame.set_cursor_position((cursor_x, area.y));
+}
+
+/// The session's bookmarks, with the process they are in when it is not the one
+/// shown, and the last one jumped to highlighted.
+fn render_bookmarks(frame: &mut Frame, area: Rect, app: &App) {
+    let shown = app.log_process().map(|p| p.id);
+    let lines: Vec<Line> = app
+        .log_bookmarks()
+        .iter()
+        .enumerate()
+        .map(|(i, bookmark)| {
+            let selected = app.log_bookmark_index == Some(i);
+            let location = match app
+                .log_processes
+                .iter()
+                .position(|p| p.id == bookmark.process_id)
+            {
+                _ if shown == Some(bookmark.process_id) => format!("{:>5} ", bookmark.line + 1),
+                Some(index) => format!("{:>2}:{:<3}", index + 1, bookmark.line + 1),
+                None => "    ? ".to_string(),
+            };
+            let style = if selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(vec![
+                Span::styled(location, Style::default().fg(Color::DarkGray)),
+                Span::styled(format!(" {}", bookmark.note), style),
+            ])
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(" Bookmarks ({}) ", lines.len()))
+        .title_bottom(" b/B Jump ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Scrollable log pane. Lines are wrapped to the pane width, and the pane shows the
+/// end of the log unless scrolled back.
+fn render_log_pane(frame: &mut Frame, area: Rect, app: &App) {
+    let title = match app.log_process() {
+        Some(process) => format!(
+            " {} ({}/{}) · {} ",
+            process.run_reason,
+            app.log_process_index + 1,
+            app.log_processes.len(),
+            process_state(process.status, app.is_log_streaming())
+        ),
+        None => " No processes ".to_string(),
+    };
+    let position = if app.log_scroll == 0 {
+        " following ".to_string()
+    } else {
+        format!(" {} lines below · G to follow ", app.log_scroll)
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(position)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = app.process_log.lines();
+    if lines.is_empty() {
+        let text = if app.is_log_streaming() {
+            "Waiting for output..."
+        } else {
+            "No output"
+        };
+        frame.render_widget(
+            Paragraph::new(Span::styled(text, Style::default().fg(Color::DarkGray))),
+            inner,
+        );
+        return;
+    }
+
+    let width = inner.width.max(1) as usize;
+    let height = inner.height as usize;
+
+    // Scrolling stops once the first line reaches the top of the pane
+    let mut first_page = 0;
+    let mut first_page_rows = 0;
+    for line in &lines {
+        first_page_rows += wrap(&line.text, width).len();
+        if first_page_rows > height {
+            break;
+        }
+        first_page += 1;
+    }
+    let max_scroll = lines.len() - first_page;
+    app.log_max_scroll.set(max_scroll);
+
+    // Fill the pane upwards from the last visible line
+    let end = lines.len() - app.log_scroll.min(max_scroll);
+    let mut rows: Vec<Line> = Vec::new();
+    for line in lines[..end].iter().rev() {
+        let style = line_style(line);
+        let mut wrapped: Vec<Line> = wrap(&line.text, width)
+            .into_iter()
+            .map(|row| Line::from(Span::styled(row, style)))
+            .collect();
+        wrapped.append(&mut rows);
+        rows = wrapped;
+        if rows.len() >= height {
+            break;
+        }
+    }
+    let skip = rows.len().saturating_sub(height);
+    frame.render_widget(Paragraph::new(rows.split_off(skip)), inner);
+}
+
+fn process_state(status: ExecutionProcessStatus, streaming: bool) -> &'static str {
+    match status {
+        ExecutionProcessStatus::Running if streaming => "running",
+        ExecutionProcessStatus::Running => "running, r to reconnect",
+        ExecutionProcessStatus::Completed => "completed",
+        ExecutionProcessStatus::Failed => "failed",
+        ExecutionProcessStatus::Killed => "killed",
+    }
+}
+
+fn line_style(line: &LogLine) -> Style {
+    match line.kind {
+        LogKind::Stdout | LogKind::Assistant => Style::default().fg(Color::White),
+        LogKind::Stderr | LogKind::Error => Style::default().fg(Color::Red),
+        LogKind::User => Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+        LogKind::Tool => Style::default().fg(Color::Yellow),
+        LogKind::Thinking => Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+        LogKind::System => Style::default().fg(Color::Gray),
+    }
+}
+
+/// Split `text` into rows of at most `width` characters.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars
+        .chunks(width)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
@@ -0,0 +1,364 @@
+//! Task detail view, with the title and description editable in place.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use super::tasks::status_color;
+use crate::{
+    app::{App, InputMode, TaskFormField},
+    metadata,
+    types::Task,
+    ui::components::{render_header, render_hints, render_status_bar},
+    utils::yes_no,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Min(10),   // Content
+            Constraint::Length(2), // Hints
+            Constraint::Length(2), // Status
+        ])
+        .split(frame.area());
+
+    let Some(task) = app.selected_task.as_ref().map(|t| &t.task) else {
+        render_header(frame, chunks[0], "Task");
+        render_status_bar(frame, chunks[3], app);
+        return;
+    };
+    render_header(frame, chunks[0], &format!("Task - {}", task.title));
+
+    let content_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+    render_text(frame, content_chunks[0], app, task);
+
+    let side_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(metadata_lines(task).len() as u16 + 7),     // Details
+            Constraint::Length(app.task_children.len().max(1) as u16 + 5), // Relationships
+            Constraint::Min(3),                                            // Attempts
+        ])
+        .split(content_chunks[1]);
+    render_details(frame, side_chunks[0], app, task);
+    render_relationships(frame, side_chunks[1], app);
+    render_attempts(frame, side_chunks[2], app);
+
+    let hints = if app.input_mode == InputMode::Editing {
+        vec![
+            ("Tab", "Next Field"),
+            ("Enter", "New Line"),
+            ("Ctrl+S", "Save"),
+            ("Esc", "Discard"),
+        ]
+    } else {
+        vec![
+            ("e", "Edit"),
+            ("Enter", "Workspaces"),
+            ("y", "Copy Prompt"),
+            ("t", "Tree"),
+            ("i", "Metadata"),
+            ("o", "Open Link"),
+            ("r", "Refresh"),
+            ("Esc", "Back"),
+        ]
+    };
+    render_hints(frame, chunks[2], &hints);
+
+    render_status_bar(frame, chunks[3], app);
+}
+
+/// Title and description, or the editor for them.
+fn render_text(frame: &mut Frame, area: Rect, app: &App, task: &Task) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(area);
+
+    let editing = app.input_mode == InputMode::Editing;
+    let (title, description) = if editing {
+        (
+            app.task_edit_title.as_str(),
+            app.task_edit_description.as_str(),
+        )
+    } else {
+        (
+            task.title.as_str(),
+            task.description.as_deref().unwrap_or(""),
+        )
+    };
+    let border_style = |field: TaskFormField| {
+        if editing && app.task_edit_field == field {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+
+    let title_paragraph = Paragraph::new(Span::styled(
+        title,
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .block(
+        Block::default()
+            .title(" Title ")
+            .borders(Borders::ALL)
+            .border_style(border_style(TaskFormField::Title)),
+    );
+    frame.render_widget(title_paragraph, chunks[0]);
+
+    let description_lines: Vec<Line> = if description.is_empty() && !editing {
+        vec![Line::from(Span::styled(
+            "No description",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        description
+            .split('\n')
+            .map(|line| Line::from(line.to_string()))
+            .collect()
+    };
+    let line_count = description_lines.len();
+    let description_paragraph = Paragraph::new(description_lines)
+        .block(
+            Block::default()
+                .title(" Description ")
+                .borders(Borders::ALL)
+                .border_style(border_style(TaskFormField::Description)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    if !editing {
+        frame.render_widget(description_paragraph.wrap(Wrap { trim: false }), chunks[1]);
+        return;
+    }
+
+    // While editing, lines are not wrapped so the cursor can follow the last one
+    let inner_height = chunks[1].height.saturating_sub(2) as usize;
+    let scroll = line_count.saturating_sub(inner_height) as u16;
+    frame.render_widget(description_paragraph.scroll((scroll, 0)), chunks[1]);
+
+    let (area, row, column) = match app.task_edit_field {
+        TaskFormField::Title => (chunks[0], 0, title.chars().count()),
+        TaskFormField::Description => {
+            let last = description.rsplit('\n').next().unwrap_or_default();
+            let row = line_count.saturating_sub(1) as u16 - scroll;
+            (chunks[1], row, last.chars().count())
+        }
+    };
+    frame.set_cursor_position((area.x + 1 + column as u16, area.y + 1 + row));
+}
+
+fn field_line<'a>(label: &'a str, value: Span<'a>) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(format!("{}: ", label), Style::default().fg(Color::Gray)),
+        value,
+    ])
+}
+
+fn render_details(frame: &mut Frame, area: Rect, app: &App, task: &Task) {
+    let complexity = match task.complexity {
+        Some(complexity) => Span::styled(
+            format!("{:?}", complexity),
+            Style::default().fg(Color::White),
+        ),
+        None => Span::styled("Not set", Style::default().fg(Color::DarkGray)),
+    };
+    let mut lines = vec![
+        field_line(
+            "Status",
+            Span::styled(
+                task.status.display_name(),
+                Style::default().fg(status_color(task.status)),
+            ),
+        ),
+        field_line("Complexity", complexity),
+        field_line(
+            "Epic",
+            Span::styled(yes_no(task.is_epic), Style::default().fg(Color::White)),
+        ),
+        field_line(
+            "Created",
+            Span::styled(
+                app.timestamp_str(&task.created_at),
+                Style::default().fg(Color::White),
+            ),
+        ),
+        field_line(
+            "Updated",
+            Span::styled(
+                app.timestamp_str(&task.updated_at),
+                Style::default().fg(Color::White),
+            ),
+        ),
+    ];
+    lines.extend(metadata_lines(task));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Details ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+/// Links shown in the details; the rest are counted
+const SHOWN_LINKS: usize = 3;
+
+/// The task's external IDs and links, and how many keys its metadata has.
+fn metadata_lines(task: &Task) -> Vec<Line<'static>> {
+    let metadata = match metadata::parse(task.metadata.as_deref()) {
+        Ok(metadata) if metadata.is_empty() => return Vec::new(),
+        Ok(metadata) => metadata,
+        Err(e) => {
+            return vec![field_line(
+                "Metadata",
+                Span::styled(e.to_string(), Style::default().fg(Color::Red)),
+            )];
+        }
+    };
+    let mut lines = Vec::new();
+    let ids = metadata::external_ids(&metadata);
+    if !ids.is_empty() {
+        let ids = ids
+            .iter()
+            .map(|(tracker, id)| format!("{} {}", tracker, id))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        lines.push(field_line(
+            "IDs",
+            Span::styled(ids, Style::default().fg(Color::Magenta)),
+        ));
+    }
+    let links = metadata::links(&metadata);
+    for link in links.iter().take(SHOWN_LINKS) {
+        lines.push(field_line(
+            "Link",
+            Span::styled(
+                link.title.clone().unwrap_or_else(|| link.url.clone()),
+                Style::default().fg(Color::Cyan),
+            ),
+        ));
+    }
+    if links.len() > SHOWN_LINKS {
+        lines.push(field_line(
+            "Link",
+            Span::styled(
+                format!("+{} more", links.len() - SHOWN_LINKS),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ));
+    }
+    lines.push(field_line(
+        "Metadata",
+        Span::styled(
+            format!("{} keys · i to inspect", metadata.len()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ));
+    lines
+}
+
+fn task_line(task: &Task) -> Line<'_> {
+    Line::from(vec![
+        Span::styled("• ", Style::default().fg(status_color(task.status))),
+        Span::styled(task.title.as_str(), Style::default().fg(Color::White)),
+        Span::styled(
+            format!(" ({})", task.status.display_name()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+fn render_relationships(frame: &mut Frame, area: Rect, app: &App) {
+    let mut lines = vec![Line::from(Span::styled(
+        "Parent",
+        Style::default().fg(Color::Gray),
+    ))];
+    lines.push(match &app.task_parent {
+        Some(parent) => task_line(parent),
+        None => Line::from(Span::styled("  None", Style::default().fg(Color::DarkGray))),
+    });
+    lines.push(Line::from(Span::styled(
+        "Children",
+        Style::default().fg(Color::Gray),
+    )));
+    if app.task_children.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  None",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines.extend(app.task_children.iter().map(task_line));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Related Tasks ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_attempts(frame: &mut Frame, area: Rect, app: &App) {
+    let lines: Vec<Line> = if app.workspaces.is_empty() {
+        vec![Line::from(Span::styled(
+            "No attempts yet",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.workspaces
+            .iter()
+            .flat_map(|workspace| {
+                let (icon, color) = if workspace.archived {
+                    ("⊘ ", Color::DarkGray)
+                } else if workspace.pinned {
+                    ("★ ", Color::Yellow)
+                } else {
+                    ("● ", Color::Green)
+                };
+                let mut lines = vec![Line::from(vec![
+                    Span::styled(icon, Style::default().fg(color)),
+                    Span::styled(
+                        workspace.name.as_deref().unwrap_or(&workspace.branch),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("  {}", app.timestamp_str(&workspace.created_at)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])];
+                // What the attempt did, in its agent's words
+                if let Some(result) = app.result_for(workspace.id) {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", result.headline()),
+                        Style::default().fg(Color::Gray),
+                    )));
+                }
+                lines
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(format!(" Attempts ({}) ", app.workspaces.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(paragraph, area);
+}
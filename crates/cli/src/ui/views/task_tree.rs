@@ -0,0 +1,208 @@
+//! A task's parent, its workspaces and the tasks created from each, as a tree.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+
+use super::tasks::status_color;
+use crate::{
+    app::{App, TaskTreeNode},
+    types::Task,
+    ui::components::{
+        focused_border_style, render_header, render_hints, render_scrolled_list,
+        render_status_bar, selected_style,
+    },
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Min(10),   // Content
+            Constraint::Length(2), // Hints
+            Constraint::Length(2), // Status
+        ])
+        .split(frame.area());
+
+    let title = app
+        .task_tree
+        .iter()
+        .find_map(|row| match &row.node {
+            TaskTreeNode::Task(task) => Some(format!("Related Tasks - {}", task.title)),
+            _ => None,
+        })
+        .unwrap_or_else(|| "Related Tasks".to_string());
+    render_header(frame, chunks[0], &title);
+
+    let content_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(6)])
+        .split(chunks[1]);
+    render_tree(frame, content_chunks[0], app);
+    render_node_details(frame, content_chunks[1], app);
+
+    render_hints(
+        frame,
+        chunks[2],
+        &[
+            ("↑/↓", "Navigate"),
+            ("Enter", "Open"),
+            ("r", "Refresh"),
+            ("Esc", "Back"),
+        ],
+    );
+
+    render_status_bar(frame, chunks[3], app);
+}
+
+fn render_tree(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Parent → Task → Attempts → Subtasks ")
+        .borders(Borders::ALL)
+        .border_style(focused_border_style());
+
+    if app.task_tree.is_empty() {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "Loading...",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .task_tree
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let selected = i == app.selected_tree_index;
+            let marker = if selected { "▸ " } else { "  " };
+            let mut spans = vec![
+                Span::styled(marker, selected_style()),
+                Span::styled(row.prefix.as_str(), Style::default().fg(Color::DarkGray)),
+            ];
+            spans.extend(node_spans(&row.node, app, selected));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    render_scrolled_list(
+        frame,
+        area,
+        List::new(items).block(block),
+        app.selected_tree_index,
+        &app.task_tree_offset,
+    );
+}
+
+fn node_spans<'a>(node: &'a TaskTreeNode, app: &App, selected: bool) -> Vec<Span<'a>> {
+    let title_style = if selected {
+        selected_style()
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let task_spans = |icon: &'static str, task: &'a Task, style: Style| {
+        vec![
+            Span::styled(icon, Style::default().fg(status_color(task.status))),
+            Span::styled(task.title.as_str(), style),
+            Span::styled(
+                format!(" ({})", task.status.display_name()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]
+    };
+    match node {
+        TaskTreeNode::Parent(task) => task_spans("▲ ", task, title_style),
+        TaskTreeNode::Task(task) => {
+            task_spans("● ", task, title_style.add_modifier(Modifier::BOLD))
+        }
+        TaskTreeNode::Child(task) => task_spans("• ", task, title_style),
+        TaskTreeNode::Workspace(workspace) => {
+            let mut spans = vec![
+                Span::styled("⎇ ", Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    workspace.name.as_deref().unwrap_or(&workspace.branch),
+                    if selected {
+                        selected_style()
+                    } else {
+                        Style::default().fg(Color::Cyan)
+                    },
+                ),
+                Span::styled(
+                    format!("  {}", app.timestamp_str(&workspace.created_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if workspace.archived {
+                spans.push(Span::styled(
+                    "  archived",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            spans
+        }
+    }
+}
+
+fn render_node_details(frame: &mut Frame, area: Rect, app: &App) {
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::Gray));
+    let content = match app.task_tree.get(app.selected_tree_index).map(|r| &r.node) {
+        Some(TaskTreeNode::Workspace(workspace)) => vec![
+            Line::from(vec![
+                label("Attempt: "),
+                Span::styled(&workspace.branch, Style::default().fg(Color::Cyan)),
+            ]),
+            Line::from(vec![
+                label("Created: "),
+                Span::styled(
+                    app.timestamp_str(&workspace.created_at),
+                    Style::default().fg(Color::White),
+                ),
+            ]),
+        ],
+        Some(
+            node @ (TaskTreeNode::Parent(task)
+            | TaskTreeNode::Task(task)
+            | TaskTreeNode::Child(task)),
+        ) => {
+            let kind = match node {
+                TaskTreeNode::Parent(_) => "Parent task: ",
+                TaskTreeNode::Child(_) => "Subtask: ",
+                _ => "Task: ",
+            };
+            vec![
+                Line::from(vec![
+                    label(kind),
+                    Span::styled(&task.title, Style::default().fg(Color::White)),
+                ]),
+                Line::from(vec![
+                    label("Description: "),
+                    match &task.description {
+                        Some(description) => {
+                            Span::styled(description, Style::default().fg(Color::White))
+                        }
+                        None => Span::styled("none", Style::default().fg(Color::DarkGray)),
+                    },
+                ]),
+            ]
+        }
+        None => vec![Line::from(Span::styled(
+            "Nothing selected",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Details ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(paragraph, area);
+}
@@ -4,80 +4,190 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 
 use crate::{
-    app::{App, TaskColumn},
+    app::{App, AttemptFilter, BoardZoom, InputMode, TaskColumn},
+    config::TaskSort,
+    metadata,
     types::TaskStatus,
     ui::components::{
-        focused_border_style, render_header, render_hints, render_status_bar, selected_style,
-        unfocused_border_style,
+        focused_border_style, render_header, render_hints, render_scrolled_list, render_status_bar,
+        selected_style, unfocused_border_style,
     },
 };
 
 pub fn render(frame: &mut Frame, app: &App) {
+    let searching = app.input_mode == InputMode::Editing;
+    let search_height =
+        u16::from(searching || !app.task_filter.is_empty() || app.has_quick_filters());
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(2),  // Header
+            Constraint::Length(1),  // Summary
             Constraint::Min(10),    // Kanban board
+            Constraint::Length(search_height), // Search
             Constraint::Length(2),  // Hints
             Constraint::Length(2),  // Status
         ])
         .split(frame.area());
 
-    // Header with project name
-    let title = if let Some(ref project) = app.selected_project {
-        format!("Tasks - {}", project.name)
-    } else {
+    // Header with the project and how far the board is zoomed in
+    let crumbs = app.board_breadcrumbs();
+    let title = if crumbs.is_empty() {
         "Tasks".to_string()
+    } else {
+        format!("Tasks - {}", crumbs.join(" › "))
     };
     render_header(frame, chunks[0], &title);
+    render_summary(frame, chunks[1], app);
 
-    // Kanban board (4 columns)
+    // Kanban board (4 columns, 5 with the cancelled tasks)
+    let columns = app.visible_columns();
+    let width = Constraint::Ratio(1, columns.len() as u32);
     let board_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-        ])
-        .split(chunks[1]);
+        .constraints(vec![width; columns.len()])
+        .split(chunks[2]);
+    let mut areas = [Rect::default(); 5];
+    for (&column, &area) in columns.iter().zip(board_chunks.iter()) {
+        areas[column.index()] = area;
+        render_column(frame, area, app, column);
+    }
+    app.board_columns.set(areas);
 
-    render_column(frame, board_chunks[0], app, TaskColumn::Todo);
-    render_column(frame, board_chunks[1], app, TaskColumn::InProgress);
-    render_column(frame, board_chunks[2], app, TaskColumn::InReview);
-    render_column(frame, board_chunks[3], app, TaskColumn::Done);
+    if search_height > 0 {
+        render_search(frame, chunks[3], app, searching);
+    }
 
     // Hints
-    render_hints(
-        frame,
-        chunks[2],
-        &[
+    let esc_hint = if !app.marked_tasks.is_empty() {
+        "Clear Marks"
+    } else if !app.task_filter.is_empty() {
+        "Clear Search"
+    } else if app.has_quick_filters() {
+        "Clear Filters"
+    } else if app.board_zoom != BoardZoom::Tasks {
+        "All Tasks"
+    } else {
+        "Back"
+    };
+    let hints = if searching {
+        vec![("↑/↓", "Task"), ("Enter", "Apply"), ("Esc", "Clear")]
+    } else {
+        let mut hints = vec![
             ("←/→", "Column"),
             ("↑/↓", "Task"),
             ("Enter", "View"),
+            ("/", "Search"),
             ("n", "New Task"),
-            ("m", "Move"),
-            ("Esc", "Back"),
-        ],
-    );
+        ];
+        if app.selected_column == TaskColumn::Cancelled {
+            hints.extend([("M", "Restore"), ("d", "Delete")]);
+        } else {
+            hints.extend([("m", "Move"), ("Space", "Mark"), ("a", "Start Attempts")]);
+        }
+        let cancelled_hint = if app.show_cancelled {
+            "Hide Cancelled"
+        } else {
+            "Cancelled"
+        };
+        match app.board_zoom {
+            BoardZoom::Epics => hints.push(("+", "Subtasks")),
+            _ => hints.extend([("+", "Attempts"), ("-", "Epics")]),
+        }
+        hints.extend([("c", cancelled_hint), ("o", "Open Link"), ("Esc", esc_hint)]);
+        hints
+    };
+    render_hints(frame, chunks[4], &hints);
 
     // Status bar
-    render_status_bar(frame, chunks[3], app);
+    render_status_bar(frame, chunks[5], app);
+}
+
+/// One line of counts for the cards on the board: a coloured badge per column,
+/// then how many tasks have an attempt running or a failed last attempt.
+fn render_summary(frame: &mut Frame, area: Rect, app: &App) {
+    let mut spans = vec![Span::raw(" ")];
+    let (mut running, mut failed) = (0, 0);
+    for &column in app.visible_columns() {
+        let tasks = app.tasks_for_column(column);
+        running += tasks.iter().filter(|t| t.has_in_progress_attempt).count();
+        failed += tasks.iter().filter(|t| t.last_attempt_failed).count();
+        spans.push(Span::styled(
+            format!(" {} ", tasks.len()),
+            Style::default()
+                .fg(Color::Black)
+                .bg(status_color(column.status())),
+        ));
+        spans.push(Span::styled(
+            format!(" {}  ", column.title()),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    // Zero counts are dimmed so the ones that need attention stand out
+    let dim = Style::default().fg(Color::DarkGray);
+    let count_style = |count: usize, color: Color| {
+        if count > 0 {
+            Style::default().fg(color)
+        } else {
+            dim
+        }
+    };
+    spans.push(Span::styled("│ ", dim));
+    spans.push(Span::styled(
+        format!("● {} running  ", running),
+        count_style(running, Color::Green),
+    ));
+    spans.push(Span::styled(
+        format!("✗ {} failed", failed),
+        count_style(failed, Color::Red),
+    ));
+    if !app.task_filter.is_empty() || app.has_quick_filters() {
+        spans.push(Span::styled("  (filtered)", dim));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn render_search(frame: &mut Frame, area: Rect, app: &App, searching: bool) {
+    let mut spans = vec![
+        Span::styled(" / ", Style::default().fg(Color::Cyan)),
+        Span::styled(app.task_filter.as_str(), Style::default().fg(Color::White)),
+    ];
+    if searching && app.task_filter.is_empty() {
+        spans.push(Span::styled(
+            "title or description, executor:claude, status:review",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    // The quick filters, after the query
+    let mut filters = Vec::new();
+    if app.attempt_filter != AttemptFilter::All {
+        filters.push(app.attempt_filter.label().to_string());
+    }
+    if let Some(executor) = &app.executor_filter {
+        filters.push(format!("executor {}", executor));
+    }
+    if !filters.is_empty() {
+        spans.push(Span::styled(
+            format!("  [{}]", filters.join(" · ")),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+
+    if searching {
+        let cursor_x = area.x + 3 + app.task_filter.chars().count() as u16;
+        frame.set_cursor_position((cursor_x, area.y));
+    }
 }
 
 fn render_column(frame: &mut Frame, area: Rect, app: &App, column: TaskColumn) {
     let is_focused = app.selected_column == column;
-    let column_index = match column {
-        TaskColumn::Todo => 0,
-        TaskColumn::InProgress => 1,
-        TaskColumn::InReview => 2,
-        TaskColumn::Done => 3,
-    };
+    let column_index = column.index();
     let selected_index = app.selected_task_indices[column_index];
 
     let tasks = app.tasks_for_column(column);
@@ -98,25 +208,62 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, column: TaskColumn) {
             // Status indicator
             let status_indicator = if task.has_in_progress_attempt {
                 Span::styled("● ", Style::default().fg(Color::Green))
+            } else if app.orphan_for_task(task).is_some() {
+                // The server failed it because its process disappeared
+                Span::styled("⚠ ", Style::default().fg(Color::Yellow))
             } else if task.last_attempt_failed {
                 Span::styled("✗ ", Style::default().fg(Color::Red))
             } else {
                 Span::raw("  ")
             };
 
+            // Epics show how many of their subtasks are done
+            let progress = if task.task.is_epic {
+                let (done, total) = app.epic_progress(task.task.id);
+                format!(" {}/{}", done, total)
+            } else {
+                String::new()
+            };
+
+            // IDs in other trackers and the number of links, from the metadata
+            let badges = match metadata::parse(task.task.metadata.as_deref()) {
+                Ok(metadata) => {
+                    let mut badges = String::new();
+                    for (_, id) in metadata::external_ids(&metadata) {
+                        badges.push_str(&format!(" {}", id));
+                    }
+                    match metadata::links(&metadata).len() {
+                        0 => {}
+                        links => badges.push_str(&format!(" ↗{}", links)),
+                    }
+                    badges
+                }
+                Err(_) => String::new(),
+            };
+
             // Truncate title if too long
-            let max_len = area.width.saturating_sub(8) as usize;
+            let max_len = area
+                .width
+                .saturating_sub(8 + progress.len() as u16 + badges.chars().count() as u16)
+                as usize;
             let title = if task.task.title.len() > max_len {
                 format!("{}...", &task.task.title[..max_len.saturating_sub(3)])
             } else {
                 task.task.title.clone()
             };
 
-            ListItem::new(Line::from(vec![
-                Span::styled(marker, style),
-                status_indicator,
-                Span::styled(title, style),
-            ]))
+            let mut spans = vec![Span::styled(marker, style), status_indicator];
+            if app.marked_tasks.contains(&task.task.id) {
+                spans.push(Span::styled("◆ ", Style::default().fg(Color::Magenta)));
+            }
+            spans.push(Span::styled(title, style));
+            if !progress.is_empty() {
+                spans.push(Span::styled(progress, Style::default().fg(Color::DarkGray)));
+            }
+            if !badges.is_empty() {
+                spans.push(Span::styled(badges, Style::default().fg(Color::DarkGray)));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -132,21 +279,37 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, column: TaskColumn) {
         Style::default().fg(Color::Gray)
     };
 
-    let title = format!(" {} ({}) ", column.title(), tasks.len());
+    // The count takes the colour of the column's status
+    let mut title = vec![
+        Span::styled(format!(" {} ", column.title()), title_style),
+        Span::styled(
+            format!("({})", tasks.len()),
+            Style::default().fg(status_color(column.status())),
+        ),
+    ];
+    match app.task_sort(column) {
+        TaskSort::Created => title.push(Span::raw(" ")),
+        sort => title.push(Span::styled(format!(" · {} ", sort.as_str()), title_style)),
+    }
 
     let list = List::new(items).block(
         Block::default()
-            .title(Span::styled(title, title_style))
+            .title(Line::from(title))
             .borders(Borders::ALL)
             .border_style(border_style),
     );
 
-    frame.render_widget(list, area);
+    render_scrolled_list(
+        frame,
+        area,
+        list,
+        selected_index,
+        &app.board_offsets[column_index],
+    );
 }
 
 /// Get color for task status.
-#[allow(dead_code)]
-fn status_color(status: TaskStatus) -> Color {
+pub fn status_color(status: TaskStatus) -> Color {
     match status {
         TaskStatus::Todo => Color::Gray,
         TaskStatus::Inprogress => Color::Yellow,
@@ -0,0 +1,159 @@
+//! Deleted tasks of the selected project, to restore or delete for good.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+
+use crate::{
+    app::App,
+    ui::{
+        components::{
+            focused_border_style, render_header, render_hints, render_scrolled_list,
+            render_status_bar, selected_style,
+        },
+        views::tasks::status_color,
+    },
+    utils::pad_truncate,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Min(10),   // Content
+            Constraint::Length(2), // Hints
+            Constraint::Length(2), // Status
+        ])
+        .split(frame.area());
+
+    let title = match &app.selected_project {
+        Some(project) => format!("Trash - {}", project.name),
+        None => "Trash".to_string(),
+    };
+    render_header(frame, chunks[0], &title);
+
+    let content_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(7)])
+        .split(chunks[1]);
+    render_trash_list(frame, content_chunks[0], app);
+    render_task_details(frame, content_chunks[1], app);
+
+    render_hints(
+        frame,
+        chunks[2],
+        &[
+            ("↑/↓", "Navigate"),
+            ("u", "Restore"),
+            ("d", "Delete for Good"),
+            ("r", "Refresh"),
+            ("Esc", "Back"),
+        ],
+    );
+
+    render_status_bar(frame, chunks[3], app);
+}
+
+fn render_trash_list(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(format!(" Deleted Tasks ({}) ", app.trash.len()))
+        .borders(Borders::ALL)
+        .border_style(focused_border_style());
+
+    if app.trash.is_empty() {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "The trash is empty",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    // Marker, status and deletion time around the title
+    let title_width = (area.width as usize).saturating_sub(2 + 2 + 13 + 18);
+    let items: Vec<ListItem> = app
+        .trash
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            let selected = i == app.selected_trash_index;
+            let style = if selected {
+                selected_style()
+            } else {
+                Style::default()
+            };
+            let marker = if selected { "▸ " } else { "  " };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(
+                    pad_truncate(task.status.display_name(), 12),
+                    Style::default().fg(status_color(task.status)),
+                ),
+                Span::raw(" "),
+                Span::styled(pad_truncate(&task.title, title_width), style),
+                Span::styled(
+                    format!(" {:>17}", app.timestamp(task.deleted_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    render_scrolled_list(
+        frame,
+        area,
+        List::new(items).block(block),
+        app.selected_trash_index,
+        &app.trash_list_offset,
+    );
+}
+
+fn render_task_details(frame: &mut Frame, area: Rect, app: &App) {
+    let content = match app.trash.get(app.selected_trash_index) {
+        Some(task) => vec![
+            Line::from(vec![
+                Span::styled("Task: ", Style::default().fg(Color::Gray)),
+                Span::styled(&task.title, Style::default().fg(Color::White)),
+            ]),
+            Line::from(vec![
+                Span::styled("Deleted: ", Style::default().fg(Color::Gray)),
+                Span::styled(
+                    app.timestamp(task.deleted_at),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!(" · created {}", app.timestamp(task.created_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Description: ", Style::default().fg(Color::Gray)),
+                match &task.description {
+                    Some(description) => {
+                        Span::styled(description, Style::default().fg(Color::White))
+                    }
+                    None => Span::styled("none", Style::default().fg(Color::DarkGray)),
+                },
+            ]),
+        ],
+        None => vec![Line::from(Span::styled(
+            "No task selected",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Details ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(paragraph, area);
+}
@@ -1,16 +1,22 @@
 //! Workspace detail view with git operations.
 
+use chrono::Utc;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Tabs},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs, Wrap},
     Frame,
 };
 
 use crate::{
     app::App,
-    ui::components::{render_header, render_hints, render_status_bar},
+    datetime,
+    types::{ExecutionProcess, ExecutionProcessStatus, ProtectionMode},
+    ui::components::{
+        render_header, render_hints, render_scrolled_list, render_status_bar, selected_style,
+    },
+    utils::{format_runtime, pad_truncate},
 };
 
 pub fn render(frame: &mut Frame, app: &App) {
@@ -25,9 +31,12 @@ pub fn render(frame: &mut Frame, app: &App) {
         ])
         .split(frame.area());
 
-    // Header with workspace branch
+    // Header with workspace name and branch
     let title = if let Some(ref workspace) = app.selected_workspace {
-        format!("Workspace - {}", workspace.branch)
+        match &workspace.name {
+            Some(name) => format!("Workspace - {} ({})", name, workspace.branch),
+            None => format!("Workspace - {}", workspace.branch),
+        }
     } else {
         "Workspace Detail".to_string()
     };
@@ -36,14 +45,30 @@ pub fn render(frame: &mut Frame, app: &App) {
     // Tab bar
     render_tabs(frame, chunks[1]);
 
-    // Content area
+    // Content area, with the processes and the commit log below
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(45),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+        ])
+        .split(chunks[2]);
     let content_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(chunks[2]);
+        .split(rows[0]);
+
+    let bottom_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(rows[2]);
 
     render_branch_status(frame, content_chunks[0], app);
     render_session_info(frame, content_chunks[1], app);
+    render_processes(frame, rows[1], app);
+    render_commits(frame, bottom_chunks[0], app);
+    render_notes(frame, bottom_chunks[1], app);
 
     // Hints
     render_hints(
@@ -51,10 +76,25 @@ pub fn render(frame: &mut Frame, app: &App) {
         chunks[3],
         &[
             ("m", "Merge"),
+            ("M", "Merge All"),
             ("p", "Push"),
-            ("r", "Rebase"),
+            ("b", "Rebase"),
+            ("B", "Rebase Onto"),
+            ("O", "Pull Request"),
+            ("e", "Editor"),
+            ("↑/↓", "Process"),
             ("s", "Stop"),
+            ("t", "Retry"),
+            ("v", "Resolve"),
+            ("D", "Dev Server"),
             ("f", "Follow-up"),
+            ("l", "Logs"),
+            ("d", "Diff"),
+            ("R", "Rename"),
+            ("n", "Notes"),
+            ("S", "Subtask"),
+            ("o", "Open Link"),
+            ("F", "Follow"),
             ("Esc", "Back"),
         ],
     );
@@ -82,10 +122,18 @@ fn render_branch_status(frame: &mut Frame, area: Rect, app: &App) {
     let mut content = vec![];
 
     if let Some(ref workspace) = app.selected_workspace {
-        content.push(Line::from(vec![
+        // Protected in any of the repos
+        let protection = app
+            .branch_statuses
+            .iter()
+            .filter_map(|status| status.status.branch_protection)
+            .max_by_key(|mode| *mode == ProtectionMode::Block);
+        let mut spans = vec![
             Span::styled("Branch: ", Style::default().fg(Color::Gray)),
             Span::styled(&workspace.branch, Style::default().fg(Color::Cyan)),
-        ]));
+        ];
+        spans.extend(protection_badge(protection));
+        content.push(Line::from(spans));
         content.push(Line::from(""));
     }
 
@@ -99,13 +147,15 @@ fn render_branch_status(frame: &mut Frame, area: Rect, app: &App) {
             ),
         ]));
 
-        content.push(Line::from(vec![
+        let mut spans = vec![
             Span::styled("  Target: ", Style::default().fg(Color::Gray)),
             Span::styled(
                 &status.status.target_branch_name,
                 Style::default().fg(Color::Yellow),
             ),
-        ]));
+        ];
+        spans.extend(protection_badge(status.status.target_branch_protection));
+        content.push(Line::from(spans));
 
         // Commit status
         if let (Some(ahead), Some(behind)) = (
@@ -152,6 +202,21 @@ fn render_branch_status(frame: &mut Frame, area: Rect, app: &App) {
                     status.status.conflicted_files.len().to_string(),
                     Style::default().fg(Color::Red),
                 ),
+                Span::styled(
+                    "  (v: resolve with the agent)",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        for pr in status.status.pull_requests() {
+            content.push(Line::from(vec![
+                Span::styled("  PR: ", Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!("#{} {}", pr.number, pr.status),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(format!(" {}", pr.url), Style::default().fg(Color::DarkGray)),
             ]));
         }
 
@@ -183,6 +248,179 @@ fn render_branch_status(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(paragraph, area);
 }
 
+/// The workspace's notes, edited with `n`
+fn render_notes(frame: &mut Frame, area: Rect, app: &App) {
+    let (content, title) = match &app.workspace_note {
+        Some(note) => (
+            Paragraph::new(note.body.as_str()).style(Style::default().fg(Color::White)),
+            format!(" Notes · {} ", app.timestamp(note.updated_at)),
+        ),
+        None => (
+            Paragraph::new("No notes yet; press n to add test results or review thoughts")
+                .style(Style::default().fg(Color::DarkGray)),
+            " Notes ".to_string(),
+        ),
+    };
+    let paragraph = content.wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// Commits of the workspace branch that each target branch doesn't have yet
+fn render_commits(frame: &mut Frame, area: Rect, app: &App) {
+    let mut content = vec![];
+    // Name the repo only when there is more than one
+    let several = app.workspace_commits.len() > 1;
+
+    for repo in &app.workspace_commits {
+        if several {
+            content.push(Line::from(vec![
+                Span::styled(
+                    &repo.repo_name,
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" → ", Style::default().fg(Color::DarkGray)),
+                Span::styled(&repo.target_branch_name, Style::default().fg(Color::Yellow)),
+            ]));
+        }
+        if repo.commits.is_empty() {
+            content.push(Line::from(Span::styled(
+                format!("  Nothing ahead of {}", repo.target_branch_name),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for commit in &repo.commits {
+            content.push(Line::from(vec![
+                Span::styled(
+                    format!("  {} ", commit.sha.get(..7).unwrap_or(&commit.sha)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    app.config
+                        .time_zone
+                        .format(commit.authored_at, "%m-%d %H:%M "),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{} ", pad_truncate(&commit.author_name, 16)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(&commit.subject, Style::default().fg(Color::White)),
+            ]));
+        }
+    }
+
+    if app.workspace_commits.is_empty() {
+        content.push(Line::from(Span::styled(
+            "No commits to show",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let total: usize = app.workspace_commits.iter().map(|r| r.commits.len()).sum();
+    let paragraph = Paragraph::new(content).block(
+        Block::default()
+            .title(format!(" Commits ({}) ", total))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Execution processes of all sessions, newest first; ↑/↓ picks one for `s`
+fn render_processes(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(format!(
+            " Processes ({}) · ↑/↓ pick · s stop · Esc clear ",
+            app.workspace_processes.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    if app.workspace_processes.is_empty() {
+        let paragraph = Paragraph::new("No processes yet")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .workspace_processes
+        .iter()
+        .enumerate()
+        .map(|(i, process)| {
+            let selected = app.selected_process_index == Some(i);
+            let style = if selected {
+                selected_style()
+            } else {
+                Style::default()
+            };
+            let marker = if selected { "▸ " } else { "  " };
+            let (status, status_color) = match process.status {
+                ExecutionProcessStatus::Running => ("running", Color::Yellow),
+                ExecutionProcessStatus::Completed => ("completed", Color::Green),
+                ExecutionProcessStatus::Failed => ("failed", Color::Red),
+                ExecutionProcessStatus::Killed => ("killed", Color::Magenta),
+            };
+            let exit = process
+                .exit_code
+                .map(|code| format!("exit {}", code))
+                .unwrap_or_default();
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(
+                    format!("{} ", pad_truncate(status, 10)),
+                    Style::default().fg(status_color),
+                ),
+                Span::styled(format!("{} ", pad_truncate(&process.run_reason, 16)), style),
+                Span::styled(
+                    format!("{} ", pad_truncate(&exit, 8)),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    process_duration(process),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(block);
+    match app.selected_process_index {
+        Some(selected) => {
+            render_scrolled_list(frame, area, list, selected, &app.process_list_offset)
+        }
+        None => frame.render_widget(list, area),
+    }
+}
+
+/// How long a process ran, or has been running so far
+fn process_duration(process: &ExecutionProcess) -> String {
+    let Some(started) = datetime::parse(&process.started_at) else {
+        return String::new();
+    };
+    let ended = process
+        .completed_at
+        .as_deref()
+        .and_then(datetime::parse)
+        .unwrap_or_else(Utc::now);
+    let seconds = (ended - started).num_seconds();
+    if seconds < 60 {
+        format!("{}s", seconds.max(0))
+    } else {
+        format_runtime(seconds)
+    }
+}
+
 fn render_session_info(frame: &mut Frame, area: Rect, app: &App) {
     let mut content = vec![];
 
@@ -207,7 +445,10 @@ fn render_session_info(frame: &mut Frame, area: Rect, app: &App) {
         ]));
         content.push(Line::from(vec![
             Span::styled("     Created: ", Style::default().fg(Color::Gray)),
-            Span::styled(&session.created_at, Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                app.timestamp_str(&session.created_at),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]));
     }
 
@@ -225,6 +466,77 @@ fn render_session_info(frame: &mut Frame, area: Rect, app: &App) {
         )));
     }
 
+    if let Some(at) = app
+        .selected_workspace_activity()
+        .and_then(|a| a.last_activity_at())
+    {
+        content.push(Line::from(vec![
+            Span::styled("Last activity: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                app.config.time_zone.format(at, "%H:%M:%S"),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+    }
+
+    // The address comes from the dev server's output, a moment after it starts
+    let dev_server = match (app.running_dev_server(), app.dev_server_address()) {
+        (Some(_), Some(url)) => Span::styled(
+            format!("running at {}", url),
+            Style::default().fg(Color::Green),
+        ),
+        (Some(_), None) => Span::styled(
+            "running, waiting for its address",
+            Style::default().fg(Color::Yellow),
+        ),
+        (None, _) => Span::styled("stopped", Style::default().fg(Color::DarkGray)),
+    };
+    content.push(Line::from(vec![
+        Span::styled("Dev server: ", Style::default().fg(Color::Gray)),
+        dev_server,
+    ]));
+
+    if let Some(limits) = &app.workspace_limits {
+        content.push(Line::from(vec![
+            Span::styled("Limits: ", Style::default().fg(Color::Gray)),
+            Span::styled(limits.limits.summary(), Style::default().fg(Color::White)),
+        ]));
+        // Stopped by a limit, not a failure of the agent itself
+        if let Some(kind) = limits.exceeded_limit {
+            let at = limits
+                .exceeded_at
+                .map(|at| format!(" at {}", app.config.time_zone.format(at, "%H:%M:%S")))
+                .unwrap_or_default();
+            content.push(Line::from(Span::styled(
+                format!("  ⏹ Stopped: {} limit exceeded{}", kind.label(), at),
+                Style::default().fg(Color::Magenta),
+            )));
+        }
+    }
+
+    if let Some(policy) = &app.workspace_auto_rebase {
+        content.push(Line::from(vec![
+            Span::styled("Auto-rebase: ", Style::default().fg(Color::Gray)),
+            Span::styled(policy.trigger.label(), Style::default().fg(Color::White)),
+        ]));
+        if let Some(reason) = &policy.paused_reason {
+            content.push(Line::from(Span::styled(
+                format!("  ⏸ Paused: {}", reason),
+                Style::default().fg(Color::Magenta),
+            )));
+        } else if let Some(error) = &policy.last_error {
+            content.push(Line::from(Span::styled(
+                format!("  ⚠ {}", error),
+                Style::default().fg(Color::Yellow),
+            )));
+        } else if let Some(at) = policy.last_rebased_at {
+            content.push(Line::from(Span::styled(
+                format!("  Last rebased {}", app.timestamp(at)),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+
     // Workspace repos
     content.push(Line::from(""));
     content.push(Line::from(vec![
@@ -253,3 +565,13 @@ fn render_session_info(frame: &mut Frame, area: Rect, app: &App) {
 
     frame.render_widget(paragraph, area);
 }
+
+/// Lock shown after a protected branch; merges, pushes and rebases writing to it
+/// need confirming or are refused
+fn protection_badge(mode: Option<ProtectionMode>) -> Option<Span<'static>> {
+    let (label, color) = match mode? {
+        ProtectionMode::Confirm => (" 🔒 protected", Color::Yellow),
+        ProtectionMode::Block => (" 🔒 blocked", Color::Red),
+    };
+    Some(Span::styled(label, Style::default().fg(color)))
+}
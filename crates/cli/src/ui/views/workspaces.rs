@@ -9,12 +9,21 @@ use ratatui::{
 };
 
 use crate::{
-    app::App,
+    app::{App, BoardZoom},
+    types::{
+        ContainerState, SessionProgress, WorkspaceContainerStatus, WorkspaceFailure,
+        WorkspaceResult,
+    },
     ui::components::{
-        focused_border_style, render_header, render_hints, render_status_bar, selected_style,
+        failure_style, focused_border_style, render_header, render_hints, render_scrolled_list,
+        render_status_bar, selected_style,
     },
+    utils::{format_eta, format_runtime, truncate_title},
 };
 
+/// Width of the progress bar on workspace rows, in cells
+const PROGRESS_BAR_WIDTH: usize = 10;
+
 pub fn render(frame: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -26,11 +35,22 @@ pub fn render(frame: &mut Frame, app: &App) {
         ])
         .split(frame.area());
 
-    // Header with task name
-    let title = if let Some(ref task) = app.selected_task {
-        format!("Workspaces - {}", task.task.title)
-    } else {
-        "Workspaces".to_string()
+    // Header with task name, under its epic when zoomed in from the epic's subtasks
+    let title = match &app.selected_task {
+        Some(task) => {
+            let in_epic = matches!(
+                app.board_zoom,
+                BoardZoom::Epic(epic_id) if app.epic_of(&task.task) == Some(epic_id)
+            );
+            let mut crumbs = if in_epic {
+                app.board_breadcrumbs()
+            } else {
+                Vec::new()
+            };
+            crumbs.push(task.task.title.clone());
+            format!("Workspaces - {}", crumbs.join(" › "))
+        }
+        None => "Workspaces".to_string(),
     };
     render_header(frame, chunks[0], &title);
 
@@ -52,6 +72,12 @@ pub fn render(frame: &mut Frame, app: &App) {
             ("Enter", "View Details"),
             ("n", "New Attempt"),
             ("s", "Stop"),
+            ("t", "Retry"),
+            ("c", "Restart"),
+            ("C", "Rebuild"),
+            ("R", "Rename"),
+            ("y", "Copy Branch"),
+            ("Q", "Queue"),
             ("Esc", "Back"),
         ],
     );
@@ -78,9 +104,15 @@ fn render_workspace_list(frame: &mut Frame, area: Rect, app: &App) {
                 "  "
             };
 
-            // Status indicator
+            // Status indicator; a failed latest attempt shows why it failed
+            let failure = app.failure_for(workspace.id);
             let status_icon = if workspace.archived {
                 Span::styled("⊘ ", Style::default().fg(Color::DarkGray))
+            } else if let Some(failure) = failure {
+                Span::styled(
+                    format!("{} ", failure.category.icon()),
+                    failure_style(failure.category),
+                )
             } else if workspace.pinned {
                 Span::styled("★ ", Style::default().fg(Color::Yellow))
             } else {
@@ -88,11 +120,7 @@ fn render_workspace_list(frame: &mut Frame, area: Rect, app: &App) {
             };
 
             // Workspace name or branch
-            let name = workspace
-                .name
-                .as_ref()
-                .map(|n| n.as_str())
-                .unwrap_or(&workspace.branch);
+            let name = workspace.name.as_deref().unwrap_or(&workspace.branch);
 
             // Truncate if too long
             let max_len = area.width.saturating_sub(10) as usize;
@@ -102,22 +130,61 @@ fn render_workspace_list(frame: &mut Frame, area: Rect, app: &App) {
                 name.to_string()
             };
 
-            ListItem::new(Line::from(vec![
-                Span::styled(marker, style),
-                status_icon,
-                Span::styled(display_name, style),
-            ]))
+            let mut spans = vec![Span::styled(marker, style), status_icon];
+            if let Some(entry) = app.queue_entry_for_workspace(workspace.id) {
+                spans.push(queued_badge(entry.position));
+            }
+            spans.push(Span::styled(display_name, style));
+            let status = app.container_status_for(workspace.id);
+            // A running agent that reports progress shows how far along it is instead
+            match (status, app.progress_for(workspace.id)) {
+                (Some(status), Some(progress))
+                    if status.state == ContainerState::Running && !status.is_drifted() =>
+                {
+                    spans.extend(progress_badge(progress));
+                }
+                (Some(status), _) => spans.push(container_badge(status)),
+                (None, _) => {}
+            }
+            if let Some(result) = app.result_for(workspace.id) {
+                spans.push(Span::styled(
+                    format!("  {}", truncate_title(result.headline())),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
+    // A task waiting for its first workspace has no row to badge, so show it on the list
+    let task_queue_entry = app
+        .selected_task
+        .as_ref()
+        .and_then(|t| app.queue_entry_for_task(t.task.id));
+    let title = match task_queue_entry {
+        Some(entry) => format!(
+            " Workspaces ({}) · queued (#{}) ",
+            app.workspaces.len(),
+            entry.position
+        ),
+        None => format!(" Workspaces ({}) ", app.workspaces.len()),
+    };
+
     let list = List::new(items).block(
         Block::default()
-            .title(format!(" Workspaces ({}) ", app.workspaces.len()))
+            .title(title)
             .borders(Borders::ALL)
             .border_style(focused_border_style()),
     );
 
-    frame.render_widget(list, area);
+    render_scrolled_list(
+        frame,
+        area,
+        list,
+        app.selected_workspace_index,
+        &app.workspace_list_offset,
+    );
 }
 
 fn render_workspace_details(frame: &mut Frame, area: Rect, app: &App) {
@@ -149,9 +216,34 @@ fn render_workspace_details(frame: &mut Frame, area: Rect, app: &App) {
             Line::from(""),
             Line::from(vec![
                 Span::styled("Created: ", Style::default().fg(Color::Gray)),
-                Span::styled(&workspace.created_at, Style::default().fg(Color::White)),
+                Span::styled(
+                    app.timestamp_str(&workspace.created_at),
+                    Style::default().fg(Color::White),
+                ),
             ]),
             Line::from(""),
+            match app.queue_entry_for_workspace(workspace.id) {
+                Some(entry) => Line::from(vec![
+                    Span::styled("Queue: ", Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!(
+                            "#{}, starts {}",
+                            entry.position,
+                            format_eta(entry.estimated_start_at, app.config.time_zone)
+                        ),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ]),
+                None => Line::from(vec![
+                    Span::styled("Queue: ", Style::default().fg(Color::Gray)),
+                    Span::styled("Not queued", Style::default().fg(Color::DarkGray)),
+                ]),
+            },
+            Line::from(""),
+            container_state_line(app.container_status_for(workspace.id)),
+            progress_line(app.progress_for(workspace.id)),
+            failure_line(app.failure_for(workspace.id)),
+            result_line(app.result_for(workspace.id)),
             if let Some(ref container) = workspace.container_ref {
                 Line::from(vec![
                     Span::styled("Container: ", Style::default().fg(Color::Gray)),
@@ -187,3 +279,161 @@ fn render_workspace_details(frame: &mut Frame, area: Rect, app: &App) {
 
     frame.render_widget(paragraph, area);
 }
+
+fn container_state_color(state: ContainerState) -> Color {
+    match state {
+        ContainerState::Running => Color::Green,
+        ContainerState::Stopped => Color::DarkGray,
+        ContainerState::Missing => Color::Red,
+    }
+}
+
+fn container_badge(status: &WorkspaceContainerStatus) -> Span<'static> {
+    if status.is_drifted() {
+        Span::styled(" drifted", Style::default().fg(Color::Yellow))
+    } else {
+        Span::styled(
+            format!(" {}", status.state.label()),
+            Style::default().fg(container_state_color(status.state)),
+        )
+    }
+}
+
+fn progress_badge(progress: &SessionProgress) -> Vec<Span<'static>> {
+    if progress.is_stale() {
+        let silent = (chrono::Utc::now() - progress.updated_at).num_seconds();
+        return vec![Span::styled(
+            format!(" no report for {}", format_runtime(silent)),
+            Style::default().fg(Color::Yellow),
+        )];
+    }
+    match progress.percent() {
+        Some(percent) => {
+            let filled = percent as usize * PROGRESS_BAR_WIDTH / 100;
+            vec![
+                Span::raw(" "),
+                Span::styled("█".repeat(filled), Style::default().fg(Color::Green)),
+                Span::styled(
+                    "░".repeat(PROGRESS_BAR_WIDTH - filled),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!(" {}%", percent), Style::default().fg(Color::Green)),
+            ]
+        }
+        None => vec![Span::styled(
+            format!(" working · {} files", progress.files_touched.len()),
+            Style::default().fg(Color::Green),
+        )],
+    }
+}
+
+fn progress_line(progress: Option<&SessionProgress>) -> Line<'static> {
+    let mut spans = vec![Span::styled("Progress: ", Style::default().fg(Color::Gray))];
+    let Some(progress) = progress else {
+        spans.push(Span::styled(
+            "Not reported",
+            Style::default().fg(Color::DarkGray),
+        ));
+        return Line::from(spans);
+    };
+
+    let mut parts = Vec::new();
+    if let (Some(completed), Some(total)) = (progress.steps_completed, progress.steps_total) {
+        parts.push(format!("{}/{} steps", completed, total));
+    }
+    parts.push(format!("{} files", progress.files_touched.len()));
+    if let Some(message) = &progress.message {
+        parts.push(message.clone());
+    }
+    spans.push(Span::styled(
+        parts.join(" · "),
+        Style::default().fg(Color::White),
+    ));
+
+    let silent = (chrono::Utc::now() - progress.updated_at).num_seconds();
+    let color = if progress.is_stale() {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+    let heartbeat = if silent < 60 {
+        " (just now)".to_string()
+    } else {
+        format!(" ({} ago)", format_runtime(silent))
+    };
+    spans.push(Span::styled(heartbeat, Style::default().fg(color)));
+    Line::from(spans)
+}
+
+fn failure_line(failure: Option<&WorkspaceFailure>) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        "Last attempt: ",
+        Style::default().fg(Color::Gray),
+    )];
+    match failure {
+        Some(failure) => {
+            spans.push(Span::styled(
+                format!("{} {}", failure.category.icon(), failure.category.label()),
+                failure_style(failure.category),
+            ));
+            if let Some(detail) = &failure.detail {
+                spans.push(Span::styled(
+                    format!(" ({})", detail),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            spans.push(Span::styled(
+                " · t to retry",
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        None => spans.push(Span::styled(
+            "No failure",
+            Style::default().fg(Color::DarkGray),
+        )),
+    }
+    Line::from(spans)
+}
+
+fn result_line(result: Option<&WorkspaceResult>) -> Line<'_> {
+    Line::from(vec![
+        Span::styled("Result: ", Style::default().fg(Color::Gray)),
+        match result {
+            Some(result) => Span::styled(result.headline(), Style::default().fg(Color::White)),
+            None => Span::styled("No summary yet", Style::default().fg(Color::DarkGray)),
+        },
+    ])
+}
+
+fn container_state_line(status: Option<&WorkspaceContainerStatus>) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        "Container state: ",
+        Style::default().fg(Color::Gray),
+    )];
+    match status {
+        Some(status) => {
+            spans.push(Span::styled(
+                status.state.label(),
+                Style::default().fg(container_state_color(status.state)),
+            ));
+            if status.is_drifted() {
+                spans.push(Span::styled(
+                    format!(" (missing {})", status.missing_repos.join(", ")),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+        None => spans.push(Span::styled(
+            "unknown",
+            Style::default().fg(Color::DarkGray),
+        )),
+    }
+    Line::from(spans)
+}
+
+fn queued_badge(position: i32) -> Span<'static> {
+    Span::styled(
+        format!("queued (#{}) ", position),
+        Style::default().fg(Color::Black).bg(Color::Yellow),
+    )
+}
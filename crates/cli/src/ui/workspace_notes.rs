@@ -0,0 +1,45 @@
+//! Workspace notes popup: free-form, multi-line text saved on the server.
+
+use ratatui::{
+    Frame,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{app::App, ui::components::centered_rect};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(editor) = &app.workspace_notes_editor else {
+        return;
+    };
+    let area = centered_rect(70, 60, frame.area());
+    let block = Block::default()
+        .title(format!(" Notes on {} ", editor.branch))
+        .title_bottom(" Enter New line │ Ctrl+S Save │ Esc Cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+
+    // Lines are not wrapped so the cursor can follow the last one
+    let lines: Vec<Line> = editor
+        .body
+        .split('\n')
+        .map(|line| Line::from(line.to_string()))
+        .collect();
+    let line_count = lines.len();
+    let scroll = line_count.saturating_sub(inner.height as usize) as u16;
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().fg(Color::White))
+            .scroll((scroll, 0))
+            .block(block),
+        area,
+    );
+
+    let last = editor.body.rsplit('\n').next().unwrap_or_default();
+    let row = line_count.saturating_sub(1) as u16 - scroll;
+    frame.set_cursor_position((inner.x + last.chars().count() as u16, inner.y + row));
+}
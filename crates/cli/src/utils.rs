@@ -1,5 +1,14 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use anyhow::{Result, anyhow, bail};
+use chrono::{DateTime, Utc};
 use regex::Regex;
 
+use crate::{datetime::TimeZone, types::BaseCodingAgent};
+
 pub fn pad_truncate(input: &str, width: usize) -> String {
     if input.len() <= width {
         format!("{input:width$}")
@@ -30,6 +39,192 @@ pub fn truncate_title(prompt: &str) -> String {
     }
 }
 
+/// Longest title suggested from a description
+const SUGGESTED_TITLE_LEN: usize = 60;
+
+/// Headings, quotes, list items and checkboxes
+static LINE_PREFIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:(?:#+|>|[-*+]|\d+[.)])\s*)*(?:\[[ xX]\]\s*)?").unwrap());
+static MARKDOWN_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap());
+static SENTENCE_END_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[.!?](\s|$)").unwrap());
+
+/// A short title for a task from its description: the first sentence of its
+/// first line of prose, without Markdown markup, cut at a word boundary.
+pub fn suggest_title(description: &str) -> Option<String> {
+    let mut in_code_block = false;
+    let line = description.lines().map(str::trim).find(|line| {
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            return false;
+        }
+        !in_code_block && line.chars().any(char::is_alphanumeric)
+    })?;
+
+    let line = LINE_PREFIX_RE.replace(line, "");
+    let line = MARKDOWN_LINK_RE.replace_all(&line, "$1");
+    let line = line.replace(['`', '*'], "").replace("__", "");
+
+    let sentence = match SENTENCE_END_RE.find(&line) {
+        // Questions keep their question mark
+        Some(end) if line[..end.start()].trim().len() > 1 => {
+            let question = line[end.start()..].starts_with('?');
+            line[..end.start() + usize::from(question)].to_string()
+        }
+        _ => line,
+    };
+    let sentence = sentence.trim().trim_end_matches([':', ';', ',']).trim_end();
+
+    let mut title = String::new();
+    for word in sentence.split_whitespace() {
+        if !title.is_empty() {
+            if title.chars().count() + 1 + word.chars().count() > SUGGESTED_TITLE_LEN {
+                break;
+            }
+            title.push(' ');
+        }
+        title.push_str(word);
+    }
+    let title: String = title.chars().take(SUGGESTED_TITLE_LEN).collect();
+    let title = title.trim_end_matches([',', ';', ':', '-']).trim_end();
+
+    let mut chars = title.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+/// Why `name` can't be a git branch name, by the rules of
+/// `git check-ref-format --branch`; `None` if it can.
+pub fn branch_name_problem(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        return Some("is empty");
+    }
+    if name.starts_with('-') {
+        return Some("can't start with '-'");
+    }
+    if name == "@" {
+        return Some("can't be '@'");
+    }
+    if name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+        return Some("can't start or end with '/' or have '//'");
+    }
+    if name.ends_with('.') {
+        return Some("can't end with '.'");
+    }
+    if name.contains("..") {
+        return Some("can't contain '..'");
+    }
+    if name.contains("@{") {
+        return Some("can't contain '@{'");
+    }
+    if name
+        .chars()
+        .any(|c| c.is_ascii_control() || c == ' ' || "~^:?*[\\".contains(c))
+    {
+        return Some("can't contain spaces or any of ~^:?*[\\");
+    }
+    if name
+        .split('/')
+        .any(|part| part.starts_with('.') || part.ends_with(".lock"))
+    {
+        return Some("parts between '/' can't start with '.' or end with '.lock'");
+    }
+    None
+}
+
+/// A Markdown code fence longer than any run of backticks in `lines`, so quoted
+/// code blocks can't close it.
+pub fn code_fence<'a>(lines: impl IntoIterator<Item = &'a str>) -> String {
+    let longest_run = lines
+        .into_iter()
+        .flat_map(|line| line.split(|c| c != '`'))
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest_run.max(2) + 1)
+}
+
 pub fn yes_no(value: bool) -> &'static str {
     if value { "yes" } else { "no" }
 }
+
+pub fn format_cents(cents: i64) -> String {
+    format!("${:.2}", cents as f64 / 100.0)
+}
+
+pub fn format_runtime(seconds: i64) -> String {
+    let minutes = seconds.max(0) / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Describe an estimated start time relative to now, e.g. "~14:20 (in 35m)".
+pub fn format_eta(at: DateTime<Utc>, zone: TimeZone) -> String {
+    let seconds = (at - Utc::now()).num_seconds();
+    if seconds < 60 {
+        "now".to_string()
+    } else {
+        format!(
+            "~{} (in {})",
+            zone.format(at, "%H:%M"),
+            format_runtime(seconds)
+        )
+    }
+}
+
+/// Parse an executor name as typed on the command line, e.g. `claude-code`.
+pub fn parse_executor(input: &str) -> Result<BaseCodingAgent> {
+    let normalized = input.trim().to_lowercase();
+    let executor = match normalized.as_str() {
+        "claude" | "claude-code" | "claude_code" => BaseCodingAgent::ClaudeCode,
+        "amp" => BaseCodingAgent::Amp,
+        "gemini" => BaseCodingAgent::Gemini,
+        "codex" => BaseCodingAgent::Codex,
+        "opencode" | "open-code" | "open_code" => BaseCodingAgent::Opencode,
+        "cursor" | "cursor-agent" | "cursor_agent" => BaseCodingAgent::CursorAgent,
+        "qwen" | "qwen-code" | "qwen_code" => BaseCodingAgent::QwenCode,
+        "copilot" => BaseCodingAgent::Copilot,
+        "droid" => BaseCodingAgent::Droid,
+        _ => {
+            return Err(anyhow!(
+                "Unknown tool '{}'. Try codex, claude-code, cursor, gemini, opencode, qwen-code, amp, copilot, droid.",
+                input
+            ));
+        }
+    };
+    Ok(executor)
+}
+
+/// Resolve a repository path typed by the user, expanding a leading `~`, to the
+/// absolute path of a git repository on this machine.
+pub fn resolve_repo_path(input: &str) -> Result<PathBuf> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("enter the path of a git repository");
+    }
+    let expanded = match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+            .ok_or_else(|| anyhow!("unable to determine the home directory"))?
+            .join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(input),
+    };
+    let path = std::fs::canonicalize(&expanded).map_err(|_| anyhow!("{} does not exist", input))?;
+    if !path.is_dir() {
+        bail!("{} is not a directory", input);
+    }
+    // A file rather than a directory in worktrees
+    if !path.join(".git").exists() {
+        bail!("{} is not a git repository", input);
+    }
+    Ok(path)
+}
+
+/// The last component of `path`, e.g. to name a project after its folder
+pub fn folder_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+}
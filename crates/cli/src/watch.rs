@@ -1,16 +1,22 @@
+use std::collections::HashMap;
+
 use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
 use futures_util::StreamExt;
 use json_patch::Patch;
+use serde::Serialize;
 use tokio::select;
 use tokio_tungstenite::connect_async;
 
 use crate::{
+    cli_args::OutputFormat,
+    output,
     render::{render_view, render_header, draw_screen, tasks_from_state},
     resolve::tasks_ws_url,
     utils::task_slug,
     VibeKanbanClient,
 };
-use vibe_kanban_cli::types::{Project, TaskWithAttemptStatus};
+use vibe_kanban_cli::types::{Project, TaskStatus, TaskWithAttemptStatus};
 
 #[derive(Clone, Debug)]
 pub enum WatchFilter {
@@ -19,11 +25,64 @@ pub enum WatchFilter {
     Slug(String),
 }
 
+/// How `watch` shows what it sees
+#[derive(Clone, Copy, Debug)]
+pub enum WatchOutput {
+    /// Redraw the board, or the watched task, on every change
+    Screen,
+    /// Print a line per status change or finished attempt, for scripts
+    Lines(OutputFormat),
+}
+
+/// Something that happened to a task, as printed by `watch --lines`. Every
+/// change has the same fields, so CSV columns stay put.
+#[derive(Debug, Serialize)]
+pub struct TaskChange {
+    pub at: DateTime<Utc>,
+    pub task_id: uuid::Uuid,
+    pub title: String,
+    pub event: TaskEvent,
+    /// Status before and after a `status` change
+    pub from: Option<TaskStatus>,
+    pub to: Option<TaskStatus>,
+    /// Whether the attempt of an `attempt_finished` change failed
+    pub failed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskEvent {
+    Status,
+    AttemptFinished,
+}
+
+impl TaskChange {
+    fn line(&self) -> String {
+        let what = match (self.event, self.from, self.to) {
+            (TaskEvent::Status, Some(from), Some(to)) => {
+                format!("{} -> {}", from.as_str(), to.as_str())
+            }
+            (TaskEvent::AttemptFinished, ..) if self.failed == Some(true) => {
+                "attempt failed".to_string()
+            }
+            _ => "attempt finished".to_string(),
+        };
+        format!(
+            "{}  {}  {}  {}",
+            self.at.format("%Y-%m-%dT%H:%M:%SZ"),
+            self.task_id,
+            what,
+            self.title
+        )
+    }
+}
+
 pub async fn watch_tasks(
     client: &VibeKanbanClient,
     server: &str,
     filter: WatchFilter,
     project: Option<Project>,
+    output: WatchOutput,
 ) -> Result<()> {
     let project = match (&filter, project) {
         (WatchFilter::TaskId(task_id), None) => {
@@ -42,8 +101,15 @@ pub async fn watch_tasks(
 
     let mut state = serde_json::json!({ "tasks": {} });
     let mut last_render = String::new();
+    // Tasks as of the last change printed; empty until the initial state is in
+    let mut seen: HashMap<uuid::Uuid, TaskWithAttemptStatus> = HashMap::new();
+    let mut ready = false;
+    // Whether the next line is the first, which in CSV comes after the header
+    let mut header = true;
 
-    draw_screen(&render_header(&project.name, "Connecting..."))?;
+    if let WatchOutput::Screen = output {
+        draw_screen(&render_header(&project.name, "Connecting..."))?;
+    }
 
     loop {
         select! {
@@ -64,6 +130,7 @@ pub async fn watch_tasks(
                 let mut updated = false;
 
                 if value.get("Ready").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    ready = true;
                     updated = true;
                 } else if value.get("finished").and_then(|v| v.as_bool()).unwrap_or(false) {
                     break;
@@ -75,12 +142,33 @@ pub async fn watch_tasks(
                     updated = true;
                 }
 
-                if updated {
-                    let tasks = tasks_from_state(&state);
-                    let output = render_view(&project.name, &tasks, &filter);
-                    if output != last_render {
-                        draw_screen(&output)?;
-                        last_render = output;
+                if !updated {
+                    continue;
+                }
+                let tasks = tasks_from_state(&state);
+                match output {
+                    WatchOutput::Screen => {
+                        let output = render_view(&project.name, &tasks, &filter);
+                        if output != last_render {
+                            draw_screen(&output)?;
+                            last_render = output;
+                        }
+                    }
+                    // Patches before `Ready` build up the initial state, which
+                    // is where changes are counted from rather than a change
+                    WatchOutput::Lines(_) if !ready => {}
+                    WatchOutput::Lines(format) => {
+                        let tasks: Vec<_> = tasks
+                            .into_iter()
+                            .filter(|task| matches_filter(task, &filter))
+                            .collect();
+                        for change in task_changes(&seen, &tasks) {
+                            output::print_line(format, &change, header, || {
+                                println!("{}", change.line());
+                            })?;
+                            header = false;
+                        }
+                        seen = tasks.into_iter().map(|task| (task.task.id, task)).collect();
                     }
                 }
             }
@@ -90,6 +178,52 @@ pub async fn watch_tasks(
     Ok(())
 }
 
+/// Status changes and finished attempts between `seen` and `tasks`. Tasks
+/// that are new or gone are left out; the board shows those.
+fn task_changes(
+    seen: &HashMap<uuid::Uuid, TaskWithAttemptStatus>,
+    tasks: &[TaskWithAttemptStatus],
+) -> Vec<TaskChange> {
+    let at = Utc::now();
+    let mut changes = Vec::new();
+    for task in tasks {
+        let Some(before) = seen.get(&task.task.id) else {
+            continue;
+        };
+        let change = |event| TaskChange {
+            at,
+            task_id: task.task.id,
+            title: task.task.title.clone(),
+            event,
+            from: None,
+            to: None,
+            failed: None,
+        };
+        if before.has_in_progress_attempt && !task.has_in_progress_attempt {
+            changes.push(TaskChange {
+                failed: Some(task.last_attempt_failed),
+                ..change(TaskEvent::AttemptFinished)
+            });
+        }
+        if before.task.status != task.task.status {
+            changes.push(TaskChange {
+                from: Some(before.task.status),
+                to: Some(task.task.status),
+                ..change(TaskEvent::Status)
+            });
+        }
+    }
+    changes
+}
+
+fn matches_filter(task: &TaskWithAttemptStatus, filter: &WatchFilter) -> bool {
+    match filter {
+        WatchFilter::TaskId(task_id) => task.task.id == *task_id,
+        WatchFilter::Slug(slug) => task_slug(&task.task.title) == slug.as_str(),
+        WatchFilter::None => true,
+    }
+}
+
 pub fn select_task_by_filter<'a>(
     tasks: &'a [TaskWithAttemptStatus],
     filter: &WatchFilter,
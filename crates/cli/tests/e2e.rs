@@ -0,0 +1,506 @@
+//! End-to-end tests against a real server.
+//!
+//! Each test starts the workspace's server crate on a free port, with a fresh
+//! SQLite database in a temporary asset directory and the QA mock in place of
+//! the coding agents, then drives it through the CLI's client and `App`. A
+//! field renamed or retyped on the server shows up here as a failed decode.
+//!
+//! The server is built on first use with `qa-mode`, in its own target directory
+//! so the usual build isn't invalidated; set `VK_E2E_SERVER` to a server binary
+//! built with `--features qa-mode` to skip that. Run with:
+//!
+//! ```text
+//! cargo test -p vibe-kanban-cli --features e2e --test e2e
+//! ```
+
+use std::{
+    fs,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use ratatui::{Terminal, backend::TestBackend};
+use uuid::Uuid;
+use vibe_kanban_cli::{
+    App, VibeKanbanClient,
+    app::{TaskColumn, View},
+    journal::Journal,
+    types::{
+        BaseCodingAgent, CreateAndStartTaskRequest, CreateProject, CreateProjectRepo, CreateTask,
+        CreateTaskAttemptBody, CreateTeamExecutionRequest, ExecutorProfileId, PlannerConfig,
+        Project, Repo, TaskStatus, TeamTaskStatus, UpdateTask, WorkspaceRepoInput,
+    },
+    ui,
+};
+
+/// Server binary to use instead of building one
+const SERVER_ENV: &str = "VK_E2E_SERVER";
+
+/// How long the server gets to start answering its health check
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A server running on its own database, stopped and cleaned up on drop
+struct TestServer {
+    child: Child,
+    dir: PathBuf,
+    client: VibeKanbanClient,
+}
+
+impl TestServer {
+    async fn start() -> Self {
+        // As in main, before any TLS is set up
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let dir = std::env::temp_dir().join(format!("vk-e2e-{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("assets")).unwrap();
+        let port = free_port();
+        let client = VibeKanbanClient::new(&format!("http://127.0.0.1:{port}"))
+            .unwrap()
+            .with_journal(Journal::at(dir.join("journal.jsonl")));
+        let log = fs::File::create(dir.join("server.log")).unwrap();
+        let child = Command::new(server_binary())
+            .env("VK_ASSET_DIR", dir.join("assets"))
+            .env("HOST", "127.0.0.1")
+            .env("BACKEND_PORT", port.to_string())
+            // Keeps the port file out of the way of a server already running
+            .env("TMPDIR", &dir)
+            // Worktrees are shared with other servers; none of theirs are orphans
+            .env("DISABLE_WORKTREE_ORPHAN_CLEANUP", "1")
+            .env("RUST_LOG", "warn")
+            .stdout(log.try_clone().unwrap())
+            .stderr(log)
+            .spawn()
+            .expect("failed to start the server");
+        let mut server = Self { child, dir, client };
+        server.wait_until_healthy().await;
+        server
+    }
+
+    async fn wait_until_healthy(&mut self) {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.child.try_wait().unwrap() {
+                panic!("server exited with {status}:\n{}", self.log());
+            }
+            if matches!(self.client.health_check().await, Ok(true)) {
+                return;
+            }
+            if started.elapsed() > STARTUP_TIMEOUT {
+                panic!("server didn't become healthy:\n{}", self.log());
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    fn log(&self) -> String {
+        fs::read_to_string(self.dir.join("server.log")).unwrap_or_default()
+    }
+
+    /// A git repository with one commit on `main`, for projects to point at
+    fn git_repo(&self, name: &str) -> PathBuf {
+        let path = self.dir.join(name);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("README.md"), format!("# {name}\n")).unwrap();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["add", "README.md"],
+            &[
+                "-c",
+                "user.name=e2e",
+                "-c",
+                "user.email=e2e@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "Initial commit",
+            ],
+        ] {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(&path)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        }
+        path
+    }
+
+    /// A project with one fresh repository, and that repository
+    async fn project(&self, name: &str) -> (Project, Repo) {
+        let path = self.git_repo(name);
+        let project = self
+            .client
+            .create_project(&CreateProject {
+                name: name.to_string(),
+                repositories: vec![CreateProjectRepo {
+                    display_name: name.to_string(),
+                    git_repo_path: path.to_string_lossy().into_owned(),
+                }],
+            })
+            .await
+            .unwrap();
+        let mut repos = self
+            .client
+            .get_project_repositories(project.id)
+            .await
+            .unwrap();
+        assert_eq!(repos.len(), 1);
+        (project, repos.remove(0))
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// The server binary, built once per test run unless `VK_E2E_SERVER` names one.
+fn server_binary() -> &'static Path {
+    static BINARY: OnceLock<PathBuf> = OnceLock::new();
+    BINARY.get_or_init(|| {
+        if let Some(binary) = std::env::var_os(SERVER_ENV) {
+            return PathBuf::from(binary);
+        }
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let target_dir = workspace.join("target/e2e");
+        let status = Command::new(env!("CARGO"))
+            .args([
+                "build",
+                "-p",
+                "server",
+                "--bin",
+                "server",
+                "--features",
+                "qa-mode",
+            ])
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .current_dir(&workspace)
+            .stdout(Stdio::null())
+            .status()
+            .expect("failed to run cargo");
+        assert!(status.success(), "building the server failed");
+        target_dir
+            .join("debug/server")
+            .with_extension(std::env::consts::EXE_EXTENSION)
+    })
+}
+
+fn new_task(project_id: Uuid, title: &str) -> CreateTask {
+    CreateTask {
+        project_id,
+        title: title.to_string(),
+        description: Some(format!("{title}, from the end-to-end tests")),
+        status: None,
+        parent_workspace_id: None,
+        image_ids: None,
+        is_epic: None,
+        complexity: None,
+        metadata: None,
+    }
+}
+
+fn executor() -> ExecutorProfileId {
+    // Any executor runs the QA mock in qa-mode
+    ExecutorProfileId {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+    }
+}
+
+/// The screen `app` shows, as text
+fn screen(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+    terminal.draw(|frame| ui::render(frame, app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn projects_and_tasks() {
+    let server = TestServer::start().await;
+    let client = &server.client;
+    let (project, repo) = server.project("e2e-board").await;
+    assert_eq!(repo.display_name, "e2e-board");
+
+    let projects = client.list_projects().await.unwrap();
+    assert!(projects.iter().any(|p| p.id == project.id));
+
+    let task = client
+        .create_task(
+            &new_task(project.id, "Write the smoke tests"),
+            &client.idempotency_key(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(task.status, TaskStatus::Todo);
+    let review = UpdateTask {
+        title: None,
+        description: None,
+        status: Some(TaskStatus::Inreview),
+        parent_workspace_id: None,
+        image_ids: None,
+        is_epic: None,
+        complexity: None,
+        metadata: None,
+    };
+    let updated = client.update_task(task.id, &review).await.unwrap();
+    assert_eq!(updated.status, TaskStatus::Inreview);
+    let doomed = client
+        .create_task(
+            &new_task(project.id, "Delete me"),
+            &client.idempotency_key(),
+        )
+        .await
+        .unwrap();
+    client.delete_task(doomed.id).await.unwrap();
+
+    let tasks = client.list_tasks(project.id).await.unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].task.id, task.id);
+
+    // Open the project from the project list, as the interactive UI would
+    let mut app = App::new(client.clone());
+    app.load_projects();
+    app.wait_for_requests().await.unwrap();
+    app.selected_project_index = app
+        .projects
+        .iter()
+        .position(|p| p.id == project.id)
+        .unwrap();
+    app.select_project();
+    app.wait_for_requests().await.unwrap();
+    assert_eq!(app.view, View::Tasks);
+    let in_review = app.tasks_for_column(TaskColumn::InReview);
+    assert_eq!(in_review.len(), 1);
+    assert_eq!(in_review[0].task.id, task.id);
+    assert!(app.tasks_for_column(TaskColumn::Todo).is_empty());
+    app.clear_messages();
+    assert!(screen(&app).contains("Write the smoke tests"));
+
+    // Every change went through the journal
+    let journal = Journal::at(server.dir.join("journal.jsonl"));
+    let changes: Vec<_> = journal
+        .entries()
+        .unwrap()
+        .into_iter()
+        .map(|e| (e.method, e.status))
+        .collect();
+    assert_eq!(changes.len(), 5, "{changes:?}");
+    assert!(changes.iter().all(|(_, status)| *status == Some(200)));
+}
+
+#[tokio::test]
+async fn attempts() {
+    let server = TestServer::start().await;
+    let client = &server.client;
+    let (project, repo) = server.project("e2e-attempts").await;
+    let task = client
+        .create_task(
+            &new_task(project.id, "Start an attempt"),
+            &client.idempotency_key(),
+        )
+        .await
+        .unwrap();
+
+    let workspace = client
+        .create_task_attempt(
+            &CreateTaskAttemptBody {
+                task_id: task.id,
+                executor_profile_id: executor(),
+                repos: vec![WorkspaceRepoInput {
+                    repo_id: repo.id,
+                    target_branch: "main".to_string(),
+                }],
+                resource_limits: None,
+                branch: Some("e2e/start-an-attempt".to_string()),
+            },
+            &client.idempotency_key(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(workspace.task_id, task.id);
+    assert_eq!(workspace.branch, "e2e/start-an-attempt");
+
+    let workspaces = client.list_workspaces(Some(task.id)).await.unwrap();
+    assert_eq!(workspaces.len(), 1);
+    let repos = client.get_workspace_repos(workspace.id).await.unwrap();
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0].target_branch, "main");
+    assert_eq!(repos[0].repo.id, repo.id);
+    let statuses = client.get_branch_status(workspace.id).await.unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].repo_id, repo.id);
+    let sessions = client.list_sessions(workspace.id).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    client
+        .list_execution_processes(sessions[0].id)
+        .await
+        .unwrap();
+
+    // The task's attempts, then the attempt itself, as the interactive UI shows them
+    let mut app = App::new(client.clone());
+    app.selected_project = Some(project);
+    app.selected_task = client
+        .list_tasks(task.project_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|t| t.task.id == task.id);
+    app.open_task_workspaces();
+    app.wait_for_requests().await.unwrap();
+    assert_eq!(app.view, View::Workspaces);
+    assert_eq!(app.workspaces.len(), 1);
+    app.select_workspace();
+    app.wait_for_requests().await.unwrap();
+    assert_eq!(app.view, View::WorkspaceDetail);
+    app.clear_messages();
+    assert!(screen(&app).contains("e2e/start-an-attempt"));
+
+    client.stop_workspace(workspace.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn create_and_start() {
+    let server = TestServer::start().await;
+    let client = &server.client;
+    let (project, repo) = server.project("e2e-create").await;
+
+    let started = client
+        .create_and_start_task(&CreateAndStartTaskRequest {
+            task: new_task(project.id, "Create and start"),
+            executor_profile_id: executor(),
+            repos: vec![WorkspaceRepoInput {
+                repo_id: repo.id,
+                target_branch: "main".to_string(),
+            }],
+            branch: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(started.task.title, "Create and start");
+    let workspaces = client.list_workspaces(Some(started.task.id)).await.unwrap();
+    assert_eq!(workspaces.len(), 1);
+    assert!(!workspaces[0].branch.is_empty());
+    let tasks = client.list_tasks(project.id).await.unwrap();
+    assert!(tasks.iter().any(|t| t.task.id == started.task.id));
+}
+
+#[tokio::test]
+async fn team_settings() {
+    let server = TestServer::start().await;
+    let client = &server.client;
+
+    let config = client.get_planner_config().await.unwrap();
+    let changed = PlannerConfig {
+        team_threshold: config.team_threshold + 1,
+        max_subtasks: config.max_subtasks + 1,
+        max_parallel_workers: config.max_parallel_workers + 1,
+    };
+    client.update_planner_config(&changed).await.unwrap();
+    let saved = client.get_planner_config().await.unwrap();
+    assert_eq!(saved.team_threshold, changed.team_threshold);
+    assert_eq!(saved.max_subtasks, changed.max_subtasks);
+    assert_eq!(saved.max_parallel_workers, changed.max_parallel_workers);
+
+    let seeded = client.seed_default_agent_profiles().await.unwrap();
+    assert!(!seeded.is_empty());
+    let profiles = client.list_agent_profiles().await.unwrap();
+    for profile in &seeded {
+        assert!(profiles.iter().any(|p| p.id == profile.id));
+    }
+    assert!(profiles.iter().any(|p| p.is_planner));
+
+    assert!(client.get_attempt_queue().await.unwrap().is_empty());
+    assert!(client.list_plan_templates().await.unwrap().is_empty());
+
+    // The queue view over the same routes
+    let mut app = App::new(client.clone());
+    app.navigate_to(View::Queue);
+    app.load_attempt_queue();
+    app.wait_for_requests().await.unwrap();
+    assert!(app.attempt_queue.is_empty());
+}
+
+#[tokio::test]
+async fn team_execution() {
+    let server = TestServer::start().await;
+    let client = &server.client;
+    let (project, _) = server.project("e2e-team").await;
+
+    let epic = client
+        .create_task(
+            &new_task(project.id, "Split me into subtasks"),
+            &client.idempotency_key(),
+        )
+        .await
+        .unwrap();
+    let execution = client
+        .create_team_execution(&CreateTeamExecutionRequest {
+            epic_task_id: epic.id,
+            workspace_id: None,
+            max_parallel_workers: Some(2),
+        })
+        .await
+        .unwrap();
+    assert_eq!(execution.epic_task_id, epic.id);
+    assert_eq!(execution.max_parallel_workers, 2);
+    assert!(client.get_task(epic.id).await.unwrap().is_epic);
+
+    let plan = client.plan_team(execution.id).await.unwrap();
+    assert!(!plan.subtasks.is_empty());
+
+    let executed = client.execute_team(execution.id).await.unwrap();
+    assert_eq!(executed.len(), plan.subtasks.len());
+    let team_tasks = client.get_team_tasks(execution.id).await.unwrap();
+    assert_eq!(team_tasks.len(), plan.subtasks.len());
+    for (planned, team_task) in plan.subtasks.iter().zip(&team_tasks) {
+        assert_eq!(team_task.team_execution_id, execution.id);
+        assert_eq!(team_task.status, TeamTaskStatus::Pending);
+        // Dependencies refer to the team tasks made from the planned ones
+        let dependencies: Vec<Uuid> = planned
+            .depends_on
+            .iter()
+            .map(|&index| team_tasks[index as usize].id)
+            .collect();
+        assert_eq!(team_task.dependencies(), dependencies);
+        let task = client.get_task(team_task.task_id).await.unwrap();
+        assert_eq!(task.title, planned.title);
+        assert_eq!(task.project_id, project.id);
+    }
+
+    // The subtasks join the epic on the board
+    let mut app = App::new(client.clone());
+    app.load_projects();
+    app.wait_for_requests().await.unwrap();
+    app.selected_project_index = app
+        .projects
+        .iter()
+        .position(|p| p.id == project.id)
+        .unwrap();
+    app.select_project();
+    app.wait_for_requests().await.unwrap();
+    let todo = app.tasks_for_column(TaskColumn::Todo);
+    assert_eq!(todo.len(), plan.subtasks.len() + 1);
+    for team_task in &team_tasks {
+        assert!(todo.iter().any(|t| t.task.id == team_task.task_id));
+    }
+}
@@ -0,0 +1,674 @@
+//! Drift between the CLI's API types and the server's.
+//!
+//! The server exports its API types to `shared/types.ts` with ts-rs (regenerate
+//! it with `pnpm run generate-types`). These tests hold the CLI's copies in
+//! `src/types.rs` to that file: every field the CLI reads or sends has to exist
+//! on the server type, fields the server may leave out or send as `null` have to
+//! be `Option`s in the CLI, and enums have to have the same variants. A mismatch
+//! fails here instead of as a decode error against a running server.
+//!
+//! Server fields the CLI leaves out are fine, serde skips them. Types with
+//! `#[serde(flatten)]` fields and internally tagged enums can't be traced this
+//! way and aren't checked.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    path::Path,
+    sync::OnceLock,
+};
+
+use regex::Regex;
+use serde::{
+    Serialize,
+    de::{self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, Visitor},
+    forward_to_deserialize_any,
+};
+use serde_json::Value;
+use uuid::Uuid;
+use vibe_kanban_cli::types::*;
+
+#[test]
+fn responses_match_the_server() {
+    let mut problems = Vec::new();
+    macro_rules! check {
+        ($($ty:ident),* $(,)?) => {
+            $(check_response::<$ty>(stringify!($ty), &mut problems);)*
+        };
+    }
+    check!(
+        AgentProfile,
+        AgentSkill,
+        BranchCommit,
+        BranchPolicyReport,
+        BranchStatus,
+        Diff,
+        DiffStats,
+        ExecutionProcess,
+        ExecutionProcessOrphan,
+        ExecutorHealth,
+        ExecutorProfileId,
+        ExecutorSpend,
+        GitBranch,
+        GitOperationPreview,
+        PlanTemplate,
+        PlannedSubtask,
+        PlannerConfig,
+        PreviewCommit,
+        ProfileSkillSuggestion,
+        Project,
+        ProjectBranchPolicy,
+        ProjectBudgetStatus,
+        ProjectSkillSuggestions,
+        ProtectedBranch,
+        ProtectedBranchViolation,
+        PullRequestInfo,
+        QueuedTeamTask,
+        Repo,
+        RepoCommits,
+        RepoScope,
+        RepoSkillProfile,
+        ResourceLimits,
+        RetentionCandidate,
+        RetentionPolicy,
+        Session,
+        SessionProgress,
+        Task,
+        TaskRelationships,
+        TeamExecution,
+        TeamPlanOutput,
+        TeamTask,
+        TrashedTask,
+        UserPreferences,
+        Workspace,
+        WorkspaceActivity,
+        WorkspaceAutoRebase,
+        WorkspaceContainerStatus,
+        WorkspaceFailure,
+        WorkspaceNote,
+        WorkspaceResult,
+        WorkspaceSummary,
+    );
+    assert!(problems.is_empty(), "{}", report(&problems));
+}
+
+#[test]
+fn enums_match_the_server() {
+    let mut problems = Vec::new();
+    macro_rules! check {
+        ($($ty:ident),* $(,)?) => {
+            $(check_enum::<$ty>(stringify!($ty), &mut problems);)*
+        };
+    }
+    check!(
+        AutoRebaseTrigger,
+        BaseCodingAgent,
+        BudgetState,
+        ContainerState,
+        DiffChangeKind,
+        ExecutionProcessStatus,
+        FailureCategory,
+        OrphanReason,
+        ProtectionMode,
+        ResourceLimitKind,
+        RetentionAction,
+        TaskComplexity,
+        TaskStatus,
+        TeamExecutionStatus,
+        TeamTaskStatus,
+    );
+    assert!(problems.is_empty(), "{}", report(&problems));
+}
+
+/// Requests are checked as the CLI serializes them, with every `Option` unset.
+#[test]
+fn requests_match_the_server() {
+    let id = Uuid::nil();
+    let executor_profile_id = ExecutorProfileId {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+    };
+    let create_task = || CreateTask {
+        project_id: id,
+        title: String::new(),
+        description: None,
+        status: None,
+        parent_workspace_id: None,
+        image_ids: None,
+        is_epic: None,
+        complexity: None,
+        metadata: None,
+    };
+
+    let mut problems = Vec::new();
+    check_request(
+        "CreateProject",
+        &CreateProject {
+            name: String::new(),
+            repositories: Vec::new(),
+        },
+        &mut problems,
+    );
+    check_request(
+        "CreateProjectRepo",
+        &CreateProjectRepo::default(),
+        &mut problems,
+    );
+    check_request("CreateTask", &create_task(), &mut problems);
+    check_request(
+        "UpdateTask",
+        &UpdateTask {
+            title: None,
+            description: None,
+            status: None,
+            parent_workspace_id: None,
+            image_ids: None,
+            is_epic: None,
+            complexity: None,
+            metadata: None,
+        },
+        &mut problems,
+    );
+    check_request(
+        "ReorderTasks",
+        &ReorderTasks {
+            project_id: id,
+            task_ids: vec![id],
+        },
+        &mut problems,
+    );
+    check_request(
+        "UpdateWorkspace",
+        &UpdateWorkspace::default(),
+        &mut problems,
+    );
+    check_request(
+        "CreateTaskAttemptBody",
+        &CreateTaskAttemptBody {
+            task_id: id,
+            executor_profile_id: executor_profile_id.clone(),
+            repos: Vec::new(),
+            resource_limits: None,
+            branch: None,
+        },
+        &mut problems,
+    );
+    check_request(
+        "CreateAndStartTaskRequest",
+        &CreateAndStartTaskRequest {
+            task: create_task(),
+            executor_profile_id: executor_profile_id.clone(),
+            repos: Vec::new(),
+            branch: None,
+        },
+        &mut problems,
+    );
+    check_request(
+        "WorkspaceRepoInput",
+        &WorkspaceRepoInput {
+            repo_id: id,
+            target_branch: String::new(),
+        },
+        &mut problems,
+    );
+    check_request(
+        "CreateFollowUpAttempt",
+        &CreateFollowUpAttempt {
+            prompt: String::new(),
+            executor_profile_id,
+            retry_process_id: None,
+            force_when_dirty: None,
+            perform_git_reset: None,
+        },
+        &mut problems,
+    );
+    check_request(
+        "MergeTaskAttemptRequest",
+        &MergeTaskAttemptRequest {
+            repo_id: id,
+            confirm_protected: false,
+        },
+        &mut problems,
+    );
+    check_request(
+        "PushTaskAttemptRequest",
+        &PushTaskAttemptRequest {
+            repo_id: id,
+            confirm_protected: false,
+        },
+        &mut problems,
+    );
+    check_request(
+        "RebaseTaskAttemptRequest",
+        &RebaseTaskAttemptRequest {
+            repo_id: id,
+            old_base_branch: None,
+            new_base_branch: None,
+            confirm_protected: false,
+        },
+        &mut problems,
+    );
+    check_request(
+        "UpdateAgentProfile",
+        &UpdateAgentProfile::default(),
+        &mut problems,
+    );
+    check_request(
+        "SetProjectBudget",
+        &SetProjectBudget {
+            monthly_cap_cents: None,
+        },
+        &mut problems,
+    );
+    check_request(
+        "SetProjectBranchPolicy",
+        &SetProjectBranchPolicy {
+            allowed_prefixes: Vec::new(),
+            max_length: None,
+            forbidden_chars: String::new(),
+        },
+        &mut problems,
+    );
+    check_request(
+        "CreateProtectedBranch",
+        &CreateProtectedBranch {
+            pattern: String::new(),
+            mode: ProtectionMode::Confirm,
+        },
+        &mut problems,
+    );
+    check_request(
+        "SetWorkspaceAutoRebase",
+        &SetWorkspaceAutoRebase {
+            trigger: AutoRebaseTrigger::Nightly,
+        },
+        &mut problems,
+    );
+    check_request(
+        "SetWorkspaceNote",
+        &SetWorkspaceNote {
+            body: String::new(),
+        },
+        &mut problems,
+    );
+    check_request(
+        "UserPreferences",
+        &UserPreferences::default(),
+        &mut problems,
+    );
+    assert!(problems.is_empty(), "{}", report(&problems));
+}
+
+fn report(problems: &[String]) -> String {
+    format!(
+        "src/types.rs has drifted from shared/types.ts:\n  {}",
+        problems.join("\n  ")
+    )
+}
+
+fn check_response<T: DeserializeOwned>(name: &str, problems: &mut Vec<String>) {
+    let Some(server) = server_object(name, problems) else {
+        return;
+    };
+    for (field, optional) in trace_fields::<T>(name) {
+        match server.iter().find(|f| f.name == field) {
+            None => problems.push(format!("{}.{}: not on the server", name, field)),
+            Some(server_field) if server_field.may_be_missing() && !optional => {
+                problems.push(format!(
+                    "{}.{}: the server may send null or leave it out, but it isn't an Option",
+                    name, field
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+fn check_enum<T: DeserializeOwned>(name: &str, problems: &mut Vec<String>) {
+    let Some(TsType::Enum(server)) = server_types().get(name) else {
+        problems.push(format!("{}: not an enum in shared/types.ts", name));
+        return;
+    };
+    let cli = trace_variants::<T>(name);
+    for variant in server.iter().filter(|v| !cli.contains(&v.as_str())) {
+        problems.push(format!(
+            "{}: no variant for the server's \"{}\"",
+            name, variant
+        ));
+    }
+    for variant in cli.iter().filter(|v| !server.iter().any(|s| s == *v)) {
+        problems.push(format!("{}: the server has no \"{}\"", name, variant));
+    }
+}
+
+fn check_request<T: Serialize>(name: &str, request: &T, problems: &mut Vec<String>) {
+    let Some(server) = server_object(name, problems) else {
+        return;
+    };
+    let Value::Object(sent) = serde_json::to_value(request).unwrap() else {
+        panic!("{} doesn't serialize to an object", name);
+    };
+    for (field, value) in &sent {
+        match server.iter().find(|f| &f.name == field) {
+            None => problems.push(format!("{}.{}: not on the server", name, field)),
+            Some(server_field) if value.is_null() && !server_field.may_be_missing() => problems
+                .push(format!(
+                    "{}.{}: sent as null, but the server requires a value",
+                    name, field
+                )),
+            Some(_) => {}
+        }
+    }
+    for field in server.iter().filter(|f| !f.may_be_missing()) {
+        if !sent.contains_key(&field.name) {
+            problems.push(format!(
+                "{}.{}: required by the server, but not sent",
+                name, field.name
+            ));
+        }
+    }
+}
+
+fn server_object<'a>(name: &str, problems: &mut Vec<String>) -> Option<&'a [TsField]> {
+    match server_types().get(name) {
+        Some(TsType::Object(fields)) => Some(fields),
+        _ => {
+            problems.push(format!("{}: not an object type in shared/types.ts", name));
+            None
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// The server's types, from shared/types.ts
+// ---------------------------------------------------------------------------
+
+enum TsType {
+    Object(Vec<TsField>),
+    /// String literal union or TypeScript enum, by value
+    Enum(Vec<String>),
+    Other,
+}
+
+struct TsField {
+    name: String,
+    /// Declared `name?: ...`
+    optional: bool,
+    /// Declared `name: ... | null`
+    nullable: bool,
+}
+
+impl TsField {
+    fn may_be_missing(&self) -> bool {
+        self.optional || self.nullable
+    }
+}
+
+fn server_types() -> &'static HashMap<String, TsType> {
+    static TYPES: OnceLock<HashMap<String, TsType>> = OnceLock::new();
+    TYPES.get_or_init(|| {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../shared/types.ts");
+        let source = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+        parse_types(&source)
+    })
+}
+
+fn parse_types(source: &str) -> HashMap<String, TsType> {
+    let comments = Regex::new(r"(?s)/\*.*?\*/|//[^\n]*").unwrap();
+    let source = comments.replace_all(source, "");
+    let declaration = Regex::new(r"export (type|enum) (\w+)").unwrap();
+
+    let mut types = HashMap::new();
+    for captures in declaration.captures_iter(&source) {
+        let name = captures[2].to_string();
+        let rest = &source[captures.get(0).unwrap().end()..];
+        let parsed = if &captures[1] == "enum" {
+            // export enum Name { A = "a", B = "b" }
+            let body = rest
+                .find('{')
+                .zip(rest.find('}'))
+                .map_or("", |(start, end)| &rest[start + 1..end]);
+            TsType::Enum(
+                split_top_level(body, ',')
+                    .filter_map(|member| member.split_once('='))
+                    .map(|(_, value)| value.trim().trim_matches('"').to_string())
+                    .collect(),
+            )
+        } else {
+            // export type Name<T> = ...;
+            let body = rest.split_once('=').map_or("", |(_, body)| body);
+            parse_type(split_top_level(body, ';').next().unwrap_or_default())
+        };
+        types.insert(name, parsed);
+    }
+    types
+}
+
+fn parse_type(body: &str) -> TsType {
+    let body = body.trim();
+    if let Some(inner) = body.strip_prefix('{').and_then(|b| b.strip_suffix('}'))
+        && split_top_level(body, '&').count() == 1
+    {
+        let fields = split_top_level(inner, ',')
+            .filter_map(|field| {
+                let (key, ty) = field.split_once(':')?;
+                let key = key.trim();
+                // Index signatures such as `[key in string]?: T`
+                if key.starts_with('[') {
+                    return None;
+                }
+                let (key, optional) = match key.strip_suffix('?') {
+                    Some(key) => (key, true),
+                    None => (key, false),
+                };
+                Some(TsField {
+                    name: key.trim_matches('"').to_string(),
+                    optional,
+                    nullable: split_top_level(ty, '|').any(|part| part == "null"),
+                })
+            })
+            .collect();
+        return TsType::Object(fields);
+    }
+    let variants: Vec<&str> = split_top_level(body, '|').collect();
+    if variants.iter().all(|v| v.starts_with('"')) {
+        return TsType::Enum(
+            variants
+                .iter()
+                .map(|v| v.trim_matches('"').to_string())
+                .collect(),
+        );
+    }
+    TsType::Other
+}
+
+/// Split `text` at `separator` where it isn't nested in brackets or a string,
+/// skipping empty parts.
+fn split_top_level(text: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '{' | '[' | '(' | '<' => depth += 1,
+            '}' | ']' | ')' | '>' => depth -= 1,
+            _ if c == separator && depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+}
+
+// ---------------------------------------------------------------------------
+// The CLI's types, traced through their Deserialize impls
+// ---------------------------------------------------------------------------
+
+/// The fields `T` deserializes, each with whether it is an `Option`
+fn trace_fields<T: DeserializeOwned>(name: &str) -> Vec<(&'static str, bool)> {
+    let traced = RefCell::new(Traced::default());
+    let _ = T::deserialize(Probe {
+        field: None,
+        traced: &traced,
+    });
+    let fields = traced
+        .into_inner()
+        .fields
+        .unwrap_or_else(|| panic!("{} doesn't deserialize as a plain struct", name));
+
+    fields
+        .iter()
+        .map(|&field| {
+            // Hand over just this field and see whether it is asked for as an option
+            let traced = RefCell::new(Traced::default());
+            let _ = T::deserialize(Probe {
+                field: Some(field),
+                traced: &traced,
+            });
+            (field, traced.into_inner().optional.get() == Some(true))
+        })
+        .collect()
+}
+
+/// The variants `T` deserializes from
+fn trace_variants<T: DeserializeOwned>(name: &str) -> &'static [&'static str] {
+    let traced = RefCell::new(Traced::default());
+    let _ = T::deserialize(Probe {
+        field: None,
+        traced: &traced,
+    });
+    traced
+        .into_inner()
+        .variants
+        .unwrap_or_else(|| panic!("{} doesn't deserialize as a plain enum", name))
+}
+
+#[derive(Default)]
+struct Traced {
+    fields: Option<&'static [&'static str]>,
+    variants: Option<&'static [&'static str]>,
+    optional: Cell<Option<bool>>,
+}
+
+#[derive(Debug)]
+struct Stop(String);
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Stop {}
+
+impl de::Error for Stop {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Stop(msg.to_string())
+    }
+}
+
+/// Records the fields or variants a type asks for. With `field` set, feeds a
+/// struct just that field, for [`ValueProbe`] to see how it is read.
+struct Probe<'a> {
+    field: Option<&'static str>,
+    traced: &'a RefCell<Traced>,
+}
+
+impl<'de> Deserializer<'de> for Probe<'_> {
+    type Error = Stop;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Stop> {
+        Err(de::Error::custom("not a plain struct or enum"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Stop> {
+        match self.field {
+            Some(field) => visitor.visit_map(OneField {
+                field: Some(field),
+                traced: self.traced,
+            }),
+            None => {
+                self.traced.borrow_mut().fields = Some(fields);
+                Err(de::Error::custom("traced"))
+            }
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        variants: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Stop> {
+        self.traced.borrow_mut().variants = Some(variants);
+        Err(de::Error::custom("traced"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+struct OneField<'a> {
+    field: Option<&'static str>,
+    traced: &'a RefCell<Traced>,
+}
+
+impl<'de> MapAccess<'de> for OneField<'_> {
+    type Error = Stop;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Stop> {
+        self.field
+            .take()
+            .map(|field| seed.deserialize(de::value::BorrowedStrDeserializer::new(field)))
+            .transpose()
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Stop> {
+        seed.deserialize(ValueProbe(self.traced))
+    }
+}
+
+/// Notes whether a field is read as an `Option`, which it can be given as `None`
+struct ValueProbe<'a>(&'a RefCell<Traced>);
+
+impl<'de> Deserializer<'de> for ValueProbe<'_> {
+    type Error = Stop;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Stop> {
+        self.0.borrow().optional.set(Some(false));
+        Err(de::Error::custom("traced"))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Stop> {
+        self.0.borrow().optional.set(Some(true));
+        visitor.visit_none()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct enum identifier ignored_any
+    }
+}
@@ -0,0 +1,10 @@
+-- Project budgets: optional monthly spend cap per project
+CREATE TABLE project_budgets (
+    project_id TEXT PRIMARY KEY NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    -- Monthly cap in cents (USD)
+    monthly_cap_cents INTEGER NOT NULL CHECK (monthly_cap_cents >= 0),
+    -- Month (YYYY-MM) for which an admin lifted the cap, if any
+    override_month TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,15 @@
+-- Resource limits requested when an attempt was created. The coding agent is
+-- stopped when it exceeds one of them.
+CREATE TABLE workspace_resource_limits (
+    workspace_id TEXT PRIMARY KEY NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    -- Wall-clock runtime of a single coding agent run, in seconds
+    max_runtime_secs INTEGER CHECK (max_runtime_secs > 0),
+    -- CPU time used by the agent's process group, in seconds
+    max_cpu_secs INTEGER CHECK (max_cpu_secs > 0),
+    -- Resident memory of the agent's process group, in MiB
+    max_memory_mb INTEGER CHECK (max_memory_mb > 0),
+    -- Limit that stopped the most recent run ('runtime', 'cpu' or 'memory')
+    exceeded_limit TEXT,
+    exceeded_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,12 @@
+-- Execution processes that were still marked running after their process or
+-- workspace had gone away, and were marked failed by the server.
+CREATE TABLE execution_process_orphans (
+    execution_process_id TEXT PRIMARY KEY NOT NULL REFERENCES execution_processes(id) ON DELETE CASCADE,
+    workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    task_id TEXT NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    -- 'server_restart', 'process_gone' or 'container_missing'
+    reason TEXT NOT NULL,
+    detected_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_execution_process_orphans_task_id ON execution_process_orphans(task_id);
@@ -0,0 +1,11 @@
+-- Latest progress reported by the coding agent of a session. Each report also
+-- serves as a heartbeat.
+CREATE TABLE session_progress (
+    session_id TEXT PRIMARY KEY NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    -- JSON array of paths, relative to the workspace
+    files_touched TEXT NOT NULL DEFAULT '[]',
+    steps_completed INTEGER,
+    steps_total INTEGER,
+    message TEXT,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,10 @@
+-- Plans of successful team executions, saved for reuse on similar epics.
+-- Subtask titles and descriptions refer to the epic as {epic_title}.
+CREATE TABLE plan_templates (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    source_team_execution_id TEXT REFERENCES team_executions(id) ON DELETE SET NULL,
+    -- JSON TeamPlanOutput
+    plan TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,11 @@
+-- Branch naming policies: optional rules for the branches of a project's workspaces
+CREATE TABLE project_branch_policies (
+    project_id TEXT PRIMARY KEY NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    -- JSON list of prefixes one of which every branch must start with; empty allows any
+    allowed_prefixes TEXT NOT NULL DEFAULT '[]',
+    max_length INTEGER CHECK (max_length IS NULL OR max_length > 0),
+    -- Characters that may not appear anywhere in a branch name
+    forbidden_chars TEXT NOT NULL DEFAULT '',
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
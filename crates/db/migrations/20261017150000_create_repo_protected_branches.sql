@@ -0,0 +1,13 @@
+-- Protected branches: merges, pushes and rebases that would write to a branch
+-- matching one of a repo's patterns need confirmation or are refused
+CREATE TABLE repo_protected_branches (
+    id TEXT PRIMARY KEY NOT NULL,
+    repo_id TEXT NOT NULL REFERENCES repos(id) ON DELETE CASCADE,
+    -- Branch name, or a glob such as 'release/*'
+    pattern TEXT NOT NULL,
+    -- 'confirm' or 'block'
+    mode TEXT NOT NULL DEFAULT 'confirm',
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    UNIQUE (repo_id, pattern)
+);
+CREATE INDEX idx_repo_protected_branches_repo_id ON repo_protected_branches(repo_id);
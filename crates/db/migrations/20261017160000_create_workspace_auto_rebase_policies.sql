@@ -0,0 +1,16 @@
+-- Opt-in policy that keeps a long-lived workspace branch rebased onto its target
+-- branches. A rebase that hits conflicts pauses the policy until it is resumed.
+CREATE TABLE workspace_auto_rebase_policies (
+    workspace_id TEXT PRIMARY KEY NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    -- 'nightly' or 'target_moved'
+    trigger TEXT NOT NULL,
+    -- Why the policy stopped rebasing, e.g. the conflicts it ran into
+    paused_reason TEXT,
+    paused_at TEXT,
+    -- Failure of the most recent check that did not pause the policy
+    last_error TEXT,
+    last_checked_at TEXT,
+    last_rebased_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
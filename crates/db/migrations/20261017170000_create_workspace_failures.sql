@@ -0,0 +1,15 @@
+-- Why attempts failed, classified by the server when it sees the failure. The
+-- latest failure of a workspace stays current until another setup script or
+-- coding agent run starts in it.
+CREATE TABLE workspace_failures (
+    id TEXT PRIMARY KEY NOT NULL,
+    workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    -- The failed run, if the failure came from one
+    execution_process_id TEXT REFERENCES execution_processes(id) ON DELETE SET NULL,
+    -- 'setup_script', 'agent_crash', 'rate_limit', 'timeout' or 'merge_conflict'
+    category TEXT NOT NULL,
+    detail TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_workspace_failures_workspace_id ON workspace_failures(workspace_id);
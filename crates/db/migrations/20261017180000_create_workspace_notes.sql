@@ -0,0 +1,8 @@
+-- Free-form notes kept alongside a workspace, such as manual test results or
+-- review thoughts. A workspace has at most one, replaced as a whole on save.
+CREATE TABLE workspace_notes (
+    workspace_id TEXT PRIMARY KEY NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
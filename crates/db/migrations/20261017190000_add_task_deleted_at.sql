@@ -0,0 +1,6 @@
+-- Deleting a task moves it to the trash: the row, its workspaces and their
+-- history stay until the task is restored or deleted for good. Tasks with
+-- `deleted_at` set are left off the board.
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at) WHERE deleted_at IS NOT NULL;
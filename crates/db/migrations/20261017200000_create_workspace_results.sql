@@ -0,0 +1,9 @@
+-- What each attempt did, in the words of its coding agent: the final message of
+-- the latest agent run that completed in the workspace, replaced by the next one.
+CREATE TABLE workspace_results (
+    workspace_id TEXT PRIMARY KEY NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    -- The run the summary came from
+    execution_process_id TEXT REFERENCES execution_processes(id) ON DELETE SET NULL,
+    summary TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,10 @@
+-- Place of a task in its board column when the column is ordered by hand.
+-- Reordering sets it for a whole column at once and leaves `updated_at` alone.
+ALTER TABLE tasks ADD COLUMN board_position INTEGER;
+
+-- Positions used to be kept under `board_position` in the task metadata
+UPDATE tasks
+SET board_position = json_extract(metadata, '$.board_position'),
+    metadata = json_remove(metadata, '$.board_position')
+WHERE json_valid(metadata)
+  AND json_type(metadata, '$.board_position') = 'integer';
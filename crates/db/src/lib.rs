@@ -86,6 +86,20 @@ impl DBService {
         Ok(DBService { pool })
     }
 
+    /// A fresh in-memory database with every migration applied, for tests
+    pub async fn new_in_memory() -> Result<DBService, Error> {
+        // Every connection to `:memory:` opens a database of its own, so the
+        // pool keeps a single one for its whole life
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(SqliteConnectOptions::from_str("sqlite::memory:")?)
+            .await?;
+        run_migrations(&pool).await?;
+        Ok(DBService { pool })
+    }
+
     pub async fn new_with_after_connect<F>(after_connect: F) -> Result<DBService, Error>
     where
         F: for<'a> Fn(
@@ -528,6 +528,23 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Mark a process failed if it is still marked running. Returns false when
+    /// something else, such as its exit monitor, has recorded how it ended since
+    /// it was read, in which case that status is kept.
+    pub async fn fail_if_running(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let completed_at = Utc::now();
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET status = 'failed', exit_code = NULL, completed_at = $1
+               WHERE id = $2 AND status = 'running'"#,
+            completed_at,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
     pub fn executor_action(&self) -> Result<&ExecutorAction, anyhow::Error> {
         match &self.executor_action.0 {
             ExecutorActionField::ExecutorAction(action) => Ok(action),
@@ -763,3 +780,131 @@ impl ExecutionProcess {
         Ok(rows.into_iter().collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use executors::actions::script::{ScriptContext, ScriptRequest, ScriptRequestLanguage};
+
+    use super::*;
+    use crate::{
+        DBService,
+        models::{
+            project::CreateProject, session::CreateSession, task::CreateTask,
+            workspace::CreateWorkspace,
+        },
+    };
+
+    async fn running_process(pool: &SqlitePool) -> ExecutionProcess {
+        let project = Project::create(
+            pool,
+            &CreateProject {
+                name: "Orphans".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(project.id, "Setup".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch: "vk/setup".to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        let session = Session::create(
+            pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await
+        .unwrap();
+        let action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "npm install".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::SetupScript,
+                working_dir: None,
+            }),
+            None,
+        );
+        ExecutionProcess::create(
+            pool,
+            &CreateExecutionProcess {
+                session_id: session.id,
+                executor_action: action,
+                run_reason: ExecutionProcessRunReason::SetupScript,
+            },
+            Uuid::new_v4(),
+            &[],
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fail_if_running_fails_a_process_still_running() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let process = running_process(pool).await;
+
+        assert!(
+            ExecutionProcess::fail_if_running(pool, process.id)
+                .await
+                .unwrap()
+        );
+        let process = ExecutionProcess::find_by_id(pool, process.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.status, ExecutionProcessStatus::Failed);
+        assert!(process.completed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn fail_if_running_keeps_an_exit_recorded_after_the_snapshot() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let process = running_process(pool).await;
+
+        // The watchdog reads the running processes, then the exit monitor
+        // records how this one ended before the watchdog gets to it
+        let running = ExecutionProcess::find_running(pool).await.unwrap();
+        assert_eq!(
+            running.iter().map(|p| p.id).collect::<Vec<_>>(),
+            [process.id]
+        );
+        ExecutionProcess::update_completion(
+            pool,
+            process.id,
+            ExecutionProcessStatus::Completed,
+            Some(0),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            !ExecutionProcess::fail_if_running(pool, process.id)
+                .await
+                .unwrap()
+        );
+        let process = ExecutionProcess::find_by_id(pool, process.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.status, ExecutionProcessStatus::Completed);
+        assert_eq!(process.exit_code, Some(0));
+    }
+}
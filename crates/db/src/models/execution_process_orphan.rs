@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Why a running execution process was found to be orphaned
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum OrphanReason {
+    /// The server restarted while the process was running
+    ServerRestart,
+    /// The server is no longer tracking a child process for it
+    ProcessGone,
+    /// The workspace directory it was running in no longer exists
+    ContainerMissing,
+}
+
+/// An execution process that was marked failed because it was orphaned
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionProcessOrphan {
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub reason: OrphanReason,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessOrphan {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        reason: OrphanReason,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessOrphan,
+            r#"INSERT INTO execution_process_orphans
+                   (execution_process_id, workspace_id, task_id, reason)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(execution_process_id) DO UPDATE SET reason = excluded.reason
+               RETURNING execution_process_id AS "execution_process_id!: Uuid",
+                         workspace_id AS "workspace_id!: Uuid",
+                         task_id AS "task_id!: Uuid",
+                         reason AS "reason!: OrphanReason",
+                         detected_at AS "detected_at!: DateTime<Utc>""#,
+            execution_process_id,
+            workspace_id,
+            task_id,
+            reason
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Most recently detected orphans, optionally limited to one project.
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessOrphan,
+            r#"SELECT o.execution_process_id AS "execution_process_id!: Uuid",
+                      o.workspace_id AS "workspace_id!: Uuid",
+                      o.task_id AS "task_id!: Uuid",
+                      o.reason AS "reason!: OrphanReason",
+                      o.detected_at AS "detected_at!: DateTime<Utc>"
+               FROM execution_process_orphans o
+               JOIN tasks t ON t.id = o.task_id
+               WHERE $1 IS NULL OR t.project_id = $1
+               ORDER BY o.detected_at DESC
+               LIMIT $2"#,
+            project_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -3,17 +3,30 @@ pub mod agent_skill;
 pub mod coding_agent_turn;
 pub mod execution_process;
 pub mod execution_process_logs;
+pub mod execution_process_orphan;
 pub mod execution_process_repo_state;
 pub mod image;
 pub mod merge;
+pub mod plan_template;
 pub mod project;
+pub mod project_branch_policy;
+pub mod project_budget;
 pub mod project_repo;
+pub mod protected_branch;
 pub mod repo;
 pub mod scratch;
 pub mod session;
+pub mod session_progress;
 pub mod tag;
 pub mod task;
+pub mod task_trash;
 pub mod team_execution;
 pub mod team_task;
 pub mod workspace;
+pub mod workspace_auto_rebase;
+pub mod workspace_failure;
+pub mod workspace_note;
 pub mod workspace_repo;
+pub mod workspace_resource_limits;
+pub mod workspace_result;
+pub mod workspace_retention;
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::team_execution::TeamPlanOutput;
+
+/// Plan of a successful team execution, saved so similar epics can skip planning.
+/// Subtask titles, descriptions and the reasoning refer to the epic through
+/// [`PlanTemplate::EPIC_TITLE`].
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PlanTemplate {
+    pub id: Uuid,
+    pub name: String,
+    /// Team execution the plan was saved from, if it still exists
+    pub source_team_execution_id: Option<Uuid>,
+    #[ts(type = "TeamPlanOutput")]
+    pub plan: Json<TeamPlanOutput>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreatePlanTemplate {
+    pub team_execution_id: Uuid,
+    /// Defaults to the epic title
+    pub name: Option<String>,
+}
+
+impl PlanTemplate {
+    /// Placeholder substituted with the epic title when the template is used
+    pub const EPIC_TITLE: &'static str = "{epic_title}";
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanTemplate,
+            r#"SELECT id AS "id!: Uuid",
+                      name,
+                      source_team_execution_id AS "source_team_execution_id: Uuid",
+                      plan AS "plan!: Json<TeamPlanOutput>",
+                      created_at AS "created_at!: DateTime<Utc>"
+               FROM plan_templates
+               ORDER BY name ASC, created_at DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanTemplate,
+            r#"SELECT id AS "id!: Uuid",
+                      name,
+                      source_team_execution_id AS "source_team_execution_id: Uuid",
+                      plan AS "plan!: Json<TeamPlanOutput>",
+                      created_at AS "created_at!: DateTime<Utc>"
+               FROM plan_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        name: &str,
+        source_team_execution_id: Option<Uuid>,
+        plan: &TeamPlanOutput,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let plan = Json(plan);
+        sqlx::query_as!(
+            PlanTemplate,
+            r#"INSERT INTO plan_templates (id, name, source_team_execution_id, plan)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id AS "id!: Uuid",
+                         name,
+                         source_team_execution_id AS "source_team_execution_id: Uuid",
+                         plan AS "plan!: Json<TeamPlanOutput>",
+                         created_at AS "created_at!: DateTime<Utc>""#,
+            id,
+            name,
+            source_team_execution_id,
+            plan
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM plan_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
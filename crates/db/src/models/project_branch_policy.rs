@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Naming rules for the branches created for a project's workspaces
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectBranchPolicy {
+    pub project_id: Uuid,
+    /// Branches must start with one of these; empty allows any prefix
+    #[ts(type = "Array<string>")]
+    pub allowed_prefixes: Json<Vec<String>>,
+    /// Maximum length of a branch name in characters
+    pub max_length: Option<i64>,
+    /// Characters that may not appear anywhere in a branch name
+    pub forbidden_chars: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct SetProjectBranchPolicy {
+    #[serde(default)]
+    pub allowed_prefixes: Vec<String>,
+    #[serde(default)]
+    pub max_length: Option<i64>,
+    #[serde(default)]
+    pub forbidden_chars: String,
+}
+
+impl ProjectBranchPolicy {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectBranchPolicy,
+            r#"SELECT project_id AS "project_id!: Uuid",
+                      allowed_prefixes AS "allowed_prefixes!: Json<Vec<String>>",
+                      max_length,
+                      forbidden_chars,
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM project_branch_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &SetProjectBranchPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        let allowed_prefixes = Json(&data.allowed_prefixes);
+        sqlx::query_as!(
+            ProjectBranchPolicy,
+            r#"INSERT INTO project_branch_policies (project_id, allowed_prefixes, max_length, forbidden_chars)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   allowed_prefixes = excluded.allowed_prefixes,
+                   max_length = excluded.max_length,
+                   forbidden_chars = excluded.forbidden_chars,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id AS "project_id!: Uuid",
+                         allowed_prefixes AS "allowed_prefixes!: Json<Vec<String>>",
+                         max_length,
+                         forbidden_chars,
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            project_id,
+            allowed_prefixes,
+            data.max_length,
+            data.forbidden_chars
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_branch_policies WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
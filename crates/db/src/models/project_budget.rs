@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectBudget {
+    pub project_id: Uuid,
+    pub monthly_cap_cents: i64,
+    /// Month (YYYY-MM) for which the cap has been lifted
+    pub override_month: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct SetProjectBudget {
+    /// New monthly cap in cents; `None` removes the budget entirely
+    pub monthly_cap_cents: Option<i64>,
+}
+
+/// Total coding agent runtime for one executor within a period
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutorRuntime {
+    pub executor: Option<String>,
+    pub runtime_seconds: i64,
+}
+
+impl ProjectBudget {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectBudget,
+            r#"SELECT project_id AS "project_id!: Uuid",
+                      monthly_cap_cents AS "monthly_cap_cents!: i64",
+                      override_month,
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM project_budgets
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        monthly_cap_cents: i64,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectBudget,
+            r#"INSERT INTO project_budgets (project_id, monthly_cap_cents)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   monthly_cap_cents = excluded.monthly_cap_cents,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id AS "project_id!: Uuid",
+                         monthly_cap_cents AS "monthly_cap_cents!: i64",
+                         override_month,
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            project_id,
+            monthly_cap_cents
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Lift (or restore) the cap for the given month. Passing `None` clears any override.
+    pub async fn set_override_month(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        month: Option<&str>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectBudget,
+            r#"UPDATE project_budgets
+               SET override_month = $2, updated_at = datetime('now', 'subsec')
+               WHERE project_id = $1
+               RETURNING project_id AS "project_id!: Uuid",
+                         monthly_cap_cents AS "monthly_cap_cents!: i64",
+                         override_month,
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            project_id,
+            month
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_budgets WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Sum coding agent runtime per executor for a project since `since`.
+    /// Processes that are still running are counted up to now.
+    pub async fn runtime_by_executor(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ExecutorRuntime>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutorRuntime,
+            r#"SELECT
+                s.executor,
+                CAST(COALESCE(SUM(
+                    (julianday(COALESCE(ep.completed_at, datetime('now', 'subsec')))
+                        - julianday(ep.started_at)) * 86400
+                ), 0) AS INTEGER) AS runtime_seconds
+            FROM execution_processes ep
+            JOIN sessions s ON s.id = ep.session_id
+            JOIN workspaces w ON w.id = s.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            WHERE t.project_id = $1
+              AND ep.run_reason = 'codingagent'
+              AND julianday(ep.started_at) >= julianday($2)
+            GROUP BY s.executor
+            ORDER BY runtime_seconds DESC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// What happens to an operation that would write to a protected branch
+#[derive(
+    Debug, Clone, Copy, Default, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ProtectionMode {
+    /// Allowed once the caller confirms it
+    #[default]
+    Confirm,
+    /// Refused until the protection is lifted
+    Block,
+}
+
+/// A branch name or glob of a repo that merges, pushes and rebases must not write
+/// to without care
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProtectedBranch {
+    pub id: Uuid,
+    pub repo_id: Uuid,
+    /// Branch name, or a glob where `*` matches within one path segment and `**`
+    /// across segments, e.g. `release/*`
+    pub pattern: String,
+    pub mode: ProtectionMode,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProtectedBranch {
+    pub pattern: String,
+    #[serde(default)]
+    pub mode: ProtectionMode,
+}
+
+impl ProtectedBranch {
+    pub async fn find_by_repo_id(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProtectedBranch,
+            r#"SELECT id AS "id!: Uuid",
+                      repo_id AS "repo_id!: Uuid",
+                      pattern,
+                      mode AS "mode!: ProtectionMode",
+                      created_at AS "created_at!: DateTime<Utc>"
+               FROM repo_protected_branches
+               WHERE repo_id = $1
+               ORDER BY pattern"#,
+            repo_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Protect a pattern, or change the mode of an already protected one.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+        data: &CreateProtectedBranch,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProtectedBranch,
+            r#"INSERT INTO repo_protected_branches (id, repo_id, pattern, mode)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(repo_id, pattern) DO UPDATE SET mode = excluded.mode
+               RETURNING id AS "id!: Uuid",
+                         repo_id AS "repo_id!: Uuid",
+                         pattern,
+                         mode AS "mode!: ProtectionMode",
+                         created_at AS "created_at!: DateTime<Utc>""#,
+            id,
+            repo_id,
+            data.pattern,
+            data.mode
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, repo_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM repo_protected_branches WHERE id = $1 AND repo_id = $2",
+            id,
+            repo_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,294 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Progress last reported by the coding agent of a session
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct SessionProgress {
+    pub session_id: Uuid,
+    pub workspace_id: Uuid,
+    /// Paths the agent has changed so far, relative to the workspace
+    #[ts(type = "Array<string>")]
+    pub files_touched: Json<Vec<String>>,
+    /// Plan steps the agent has finished
+    pub steps_completed: Option<i32>,
+    /// Plan steps the agent expects to take in total
+    pub steps_total: Option<i32>,
+    /// What the agent is doing right now
+    pub message: Option<String>,
+    /// Time of the latest report, used as the agent's heartbeat
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A progress report from a coding agent. Files are added to the ones already
+/// reported; the other fields replace earlier values when set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct ReportSessionProgress {
+    #[serde(default)]
+    pub files_touched: Vec<String>,
+    pub steps_completed: Option<i32>,
+    pub steps_total: Option<i32>,
+    pub message: Option<String>,
+}
+
+impl SessionProgress {
+    /// Share of plan steps completed, from 0 to 100, when the agent reported a plan.
+    pub fn percent(&self) -> Option<u8> {
+        let total = self.steps_total.filter(|t| *t > 0)?;
+        let completed = self.steps_completed.unwrap_or(0).clamp(0, total);
+        Some((completed * 100 / total) as u8)
+    }
+
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SessionProgress,
+            r#"SELECT p.session_id AS "session_id!: Uuid",
+                      s.workspace_id AS "workspace_id!: Uuid",
+                      p.files_touched AS "files_touched!: Json<Vec<String>>",
+                      p.steps_completed AS "steps_completed: i32",
+                      p.steps_total AS "steps_total: i32",
+                      p.message,
+                      p.updated_at AS "updated_at!: DateTime<Utc>"
+               FROM session_progress p
+               JOIN sessions s ON s.id = p.session_id
+               WHERE p.session_id = $1"#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The most recently reported progress of each workspace of a task, or of every
+    /// workspace when `task_id` is `None`.
+    pub async fn find_latest_by_workspace(
+        pool: &SqlitePool,
+        task_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SessionProgress,
+            r#"SELECT p.session_id AS "session_id!: Uuid",
+                      s.workspace_id AS "workspace_id!: Uuid",
+                      p.files_touched AS "files_touched!: Json<Vec<String>>",
+                      p.steps_completed AS "steps_completed: i32",
+                      p.steps_total AS "steps_total: i32",
+                      p.message,
+                      MAX(p.updated_at) AS "updated_at!: DateTime<Utc>"
+               FROM session_progress p
+               JOIN sessions s ON s.id = p.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               WHERE $1 IS NULL OR w.task_id = $1
+               GROUP BY s.workspace_id"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn report(
+        pool: &SqlitePool,
+        session_id: Uuid,
+        report: &ReportSessionProgress,
+    ) -> Result<Self, sqlx::Error> {
+        let mut files_touched = Self::find_by_session_id(pool, session_id)
+            .await?
+            .map(|p| p.files_touched.0)
+            .unwrap_or_default();
+        for file in &report.files_touched {
+            if !files_touched.contains(file) {
+                files_touched.push(file.clone());
+            }
+        }
+
+        let files_touched = Json(files_touched);
+        sqlx::query!(
+            r#"INSERT INTO session_progress
+                   (session_id, files_touched, steps_completed, steps_total, message)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT(session_id) DO UPDATE SET
+                   files_touched = excluded.files_touched,
+                   steps_completed = COALESCE(excluded.steps_completed, steps_completed),
+                   steps_total = COALESCE(excluded.steps_total, steps_total),
+                   message = COALESCE(excluded.message, message),
+                   updated_at = datetime('now', 'subsec')"#,
+            session_id,
+            files_touched,
+            report.steps_completed,
+            report.steps_total,
+            report.message
+        )
+        .execute(pool)
+        .await?;
+
+        Self::find_by_session_id(pool, session_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DBService,
+        models::{
+            project::{CreateProject, Project},
+            session::{CreateSession, Session},
+            task::{CreateTask, Task},
+            workspace::{CreateWorkspace, Workspace},
+        },
+    };
+
+    async fn workspace(pool: &SqlitePool) -> Workspace {
+        let project = Project::create(
+            pool,
+            &CreateProject {
+                name: "Progress".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(project.id, "Build it".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch: "vk/build-it".to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn session(pool: &SqlitePool, workspace: &Workspace) -> Session {
+        Session::create(
+            pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await
+        .unwrap()
+    }
+
+    fn steps(completed: i32, total: i32) -> ReportSessionProgress {
+        ReportSessionProgress {
+            steps_completed: Some(completed),
+            steps_total: Some(total),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn nothing_reported_has_no_progress() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let workspace = workspace(pool).await;
+        let session = session(pool, &workspace).await;
+
+        assert!(
+            SessionProgress::find_by_session_id(pool, session.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            SessionProgress::find_latest_by_workspace(pool, Some(workspace.task_id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let progress = SessionProgress::report(pool, session.id, &Default::default())
+            .await
+            .unwrap();
+        assert!(progress.files_touched.is_empty());
+        assert_eq!(progress.percent(), None);
+    }
+
+    #[tokio::test]
+    async fn partial_reports_add_up() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let workspace = workspace(pool).await;
+        let session = session(pool, &workspace).await;
+
+        SessionProgress::report(
+            pool,
+            session.id,
+            &ReportSessionProgress {
+                files_touched: vec!["src/lib.rs".to_string()],
+                message: Some("Writing the parser".to_string()),
+                ..steps(1, 4)
+            },
+        )
+        .await
+        .unwrap();
+        let progress = SessionProgress::report(
+            pool,
+            session.id,
+            &ReportSessionProgress {
+                files_touched: vec!["src/lib.rs".to_string(), "src/parser.rs".to_string()],
+                steps_completed: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(progress.files_touched.0, ["src/lib.rs", "src/parser.rs"]);
+        assert_eq!(progress.steps_total, Some(4));
+        assert_eq!(progress.message.as_deref(), Some("Writing the parser"));
+        assert_eq!(progress.percent(), Some(50));
+    }
+
+    #[tokio::test]
+    async fn completed_plan_is_the_latest_progress_of_its_workspace() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let other = workspace(pool).await;
+        let workspace = workspace(pool).await;
+        let first = session(pool, &workspace).await;
+        let latest = session(pool, &workspace).await;
+        let elsewhere = session(pool, &other).await;
+
+        SessionProgress::report(pool, first.id, &steps(1, 3))
+            .await
+            .unwrap();
+        SessionProgress::report(pool, elsewhere.id, &steps(0, 2))
+            .await
+            .unwrap();
+        // Reports are timestamped to the millisecond
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        SessionProgress::report(pool, latest.id, &steps(3, 3))
+            .await
+            .unwrap();
+
+        let progress = SessionProgress::find_latest_by_workspace(pool, Some(workspace.task_id))
+            .await
+            .unwrap();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].session_id, latest.id);
+        assert_eq!(progress[0].workspace_id, workspace.id);
+        assert_eq!(progress[0].percent(), Some(100));
+
+        let all = SessionProgress::find_latest_by_workspace(pool, None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+    }
+}
@@ -48,6 +48,8 @@ pub struct Task {
     pub is_epic: bool,                     // Whether this is an epic task for team execution
     pub complexity: Option<TaskComplexity>, // Estimated complexity
     pub metadata: Option<String>,          // JSON metadata for additional properties
+    /// Place in its board column when the column is ordered by hand
+    pub board_position: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -133,6 +135,13 @@ pub struct UpdateTask {
     pub metadata: Option<String>,
 }
 
+/// The tasks of a board column in the order they were arranged in by hand
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ReorderTasks {
+    pub project_id: Uuid,
+    pub task_ids: Vec<Uuid>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ProjectTaskStats {
     pub project_id: Uuid,
@@ -172,6 +181,7 @@ impl Task {
   t.is_epic                       AS "is_epic!: bool",
   t.complexity                    AS "complexity: TaskComplexity",
   t.metadata,
+  t.board_position,
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
 
@@ -208,6 +218,7 @@ impl Task {
 
 FROM tasks t
 WHERE t.project_id = $1
+  AND t.deleted_at IS NULL
 ORDER BY t.created_at DESC"#,
             project_id
         )
@@ -227,6 +238,7 @@ ORDER BY t.created_at DESC"#,
                     is_epic: rec.is_epic,
                     complexity: rec.complexity,
                     metadata: rec.metadata,
+                    board_position: rec.board_position,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
                 },
@@ -244,22 +256,23 @@ ORDER BY t.created_at DESC"#,
             Task,
             r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, 
                status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid",
-               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata,
+               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata, board_position,
                created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
-               WHERE id = $1"#,
+               WHERE id = $1 AND deleted_at IS NULL"#,
             id
         )
         .fetch_optional(pool)
         .await
     }
 
+    /// Tasks in the trash included, so the event stream can take them off the board
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
             r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, 
                status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid",
-               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata,
+               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata, board_position,
                created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE rowid = $1"#,
@@ -282,7 +295,7 @@ ORDER BY t.created_at DESC"#,
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
                RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, 
                status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid",
-               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata,
+               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata, board_position,
                created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             task_id,
             data.project_id,
@@ -314,7 +327,7 @@ ORDER BY t.created_at DESC"#,
                WHERE id = $1 AND project_id = $2
                RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, 
                status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid",
-               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata,
+               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata, board_position,
                created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             project_id,
@@ -336,10 +349,10 @@ ORDER BY t.created_at DESC"#,
             Task,
             r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, 
                status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid",
-               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata,
+               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata, board_position,
                created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
-               WHERE project_id = $1 AND is_epic = 1
+               WHERE project_id = $1 AND is_epic = 1 AND deleted_at IS NULL
                ORDER BY created_at DESC"#,
             project_id
         )
@@ -397,6 +410,35 @@ ORDER BY t.created_at DESC"#,
         Ok(())
     }
 
+    /// Number the given tasks of a project in order, in one transaction.
+    /// `updated_at` is left alone, as arranging cards doesn't change them.
+    /// Returns false, saving nothing, if one of them isn't a task of the project
+    /// on the board.
+    pub async fn set_board_positions(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for (position, task_id) in task_ids.iter().enumerate() {
+            let position = position as i64;
+            let result = sqlx::query!(
+                r#"UPDATE tasks SET board_position = $1
+                   WHERE id = $2 AND project_id = $3 AND deleted_at IS NULL"#,
+                position,
+                task_id,
+                project_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            if result.rows_affected() != 1 {
+                return Ok(false);
+            }
+        }
+        tx.commit().await?;
+        Ok(true)
+    }
+
     pub async fn update_status(
         pool: &SqlitePool,
         id: Uuid,
@@ -465,10 +507,10 @@ ORDER BY t.created_at DESC"#,
             Task,
             r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, 
                status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid",
-               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata,
+               is_epic as "is_epic!: bool", complexity as "complexity: TaskComplexity", metadata, board_position,
                created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
-               WHERE parent_workspace_id = $1
+               WHERE parent_workspace_id = $1 AND deleted_at IS NULL
                ORDER BY created_at DESC"#,
             workspace_id,
         )
@@ -524,7 +566,7 @@ ORDER BY t.created_at DESC"#,
                 COALESCE(SUM(CASE WHEN t.status = 'done' THEN 1 ELSE 0 END), 0) AS "done_count!: i64",
                 COALESCE(SUM(CASE WHEN t.status = 'cancelled' THEN 1 ELSE 0 END), 0) AS "cancelled_count!: i64"
             FROM projects p
-            LEFT JOIN tasks t ON t.project_id = p.id
+            LEFT JOIN tasks t ON t.project_id = p.id AND t.deleted_at IS NULL
             GROUP BY p.id"#
         )
         .fetch_all(pool)
@@ -541,6 +583,7 @@ ORDER BY t.created_at DESC"#,
             JOIN execution_processes ep ON ep.session_id = s.id
             WHERE ep.status = 'running'
               AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+              AND t.deleted_at IS NULL
             GROUP BY t.project_id"#
         )
         .fetch_all(pool)
@@ -566,3 +609,53 @@ ORDER BY t.created_at DESC"#,
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DBService, models::project::CreateProject};
+
+    #[tokio::test]
+    async fn set_board_positions_saves_a_column_at_once() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = Project::create(
+            pool,
+            &CreateProject {
+                name: "Board".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let mut tasks = Vec::new();
+        for title in ["First", "Second", "Third"] {
+            let data = CreateTask::from_title_description(project.id, title.to_string(), None);
+            tasks.push(Task::create(pool, &data, Uuid::new_v4()).await.unwrap());
+        }
+        let ids: Vec<Uuid> = tasks.iter().rev().map(|t| t.id).collect();
+
+        assert!(
+            Task::set_board_positions(pool, project.id, &ids)
+                .await
+                .unwrap()
+        );
+        for (position, task) in tasks.iter().rev().enumerate() {
+            let saved = Task::find_by_id(pool, task.id).await.unwrap().unwrap();
+            assert_eq!(saved.board_position, Some(position as i64));
+            assert_eq!(saved.updated_at, task.updated_at);
+        }
+
+        // An unknown task leaves the saved order as it was
+        let mut with_unknown = vec![tasks[0].id, Uuid::new_v4()];
+        with_unknown.extend(&ids[..2]);
+        assert!(
+            !Task::set_board_positions(pool, project.id, &with_unknown)
+                .await
+                .unwrap()
+        );
+        let first = Task::find_by_id(pool, tasks[0].id).await.unwrap().unwrap();
+        assert_eq!(first.board_position, Some(2));
+    }
+}
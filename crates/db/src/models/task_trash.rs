@@ -0,0 +1,271 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// A task in the trash: off the board, but kept with its workspaces until it is
+/// restored or deleted for good
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TrashedTask {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub deleted_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TrashedTask {
+    /// The project's trash, most recently deleted first
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TrashedTask,
+            r#"SELECT id AS "id!: Uuid",
+                      project_id AS "project_id!: Uuid",
+                      title,
+                      description,
+                      status AS "status!: TaskStatus",
+                      deleted_at AS "deleted_at!: DateTime<Utc>",
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1 AND deleted_at IS NOT NULL
+               ORDER BY deleted_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The task, if it is in the trash
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TrashedTask,
+            r#"SELECT id AS "id!: Uuid",
+                      project_id AS "project_id!: Uuid",
+                      title,
+                      description,
+                      status AS "status!: TaskStatus",
+                      deleted_at AS "deleted_at!: DateTime<Utc>",
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE id = $1 AND deleted_at IS NOT NULL"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Tasks that went into the trash at least `days` days ago
+    pub async fn find_deleted_before(
+        pool: &SqlitePool,
+        days: u32,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let cutoff = format!("-{days} days");
+        sqlx::query_as!(
+            TrashedTask,
+            r#"SELECT id AS "id!: Uuid",
+                      project_id AS "project_id!: Uuid",
+                      title,
+                      description,
+                      status AS "status!: TaskStatus",
+                      deleted_at AS "deleted_at!: DateTime<Utc>",
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE deleted_at IS NOT NULL
+                   AND datetime(deleted_at) <= datetime('now', $1)
+               ORDER BY deleted_at ASC"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Move a task to the trash; `None` if it is already there or doesn't exist.
+    pub async fn trash(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TrashedTask,
+            r#"UPDATE tasks
+               SET deleted_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NULL
+               RETURNING id AS "id!: Uuid",
+                         project_id AS "project_id!: Uuid",
+                         title,
+                         description,
+                         status AS "status!: TaskStatus",
+                         deleted_at AS "deleted_at!: DateTime<Utc>",
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Take a task out of the trash; false if it wasn't in it.
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET deleted_at = NULL, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NOT NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DBService,
+        models::{
+            project::{CreateProject, Project},
+            task::{CreateTask, Task},
+            workspace::{CreateWorkspace, Workspace},
+        },
+    };
+
+    async fn task_with_workspace(pool: &SqlitePool) -> (Task, Workspace) {
+        let project = Project::create(
+            pool,
+            &CreateProject {
+                name: "Trash".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(project.id, "Old task".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch: "vk/old-task".to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        (task, workspace)
+    }
+
+    #[tokio::test]
+    async fn trash_hides_task_and_its_workspaces() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let (task, workspace) = task_with_workspace(pool).await;
+
+        let trashed = TrashedTask::trash(pool, task.id).await.unwrap().unwrap();
+        assert_eq!(trashed.id, task.id);
+        assert!(TrashedTask::trash(pool, task.id).await.unwrap().is_none());
+
+        assert!(Task::find_by_id(pool, task.id).await.unwrap().is_none());
+        let trash = TrashedTask::find_by_project_id(pool, task.project_id)
+            .await
+            .unwrap();
+        assert_eq!(trash.iter().map(|t| t.id).collect::<Vec<_>>(), [task.id]);
+
+        assert!(Workspace::fetch_all(pool, None).await.unwrap().is_empty());
+        assert!(
+            Workspace::fetch_all(pool, Some(task.id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            Workspace::find_all_with_status(pool, None, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            Workspace::find_activity_by_project(pool, task.project_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        // Still there to be stopped and cleaned up
+        let kept = Workspace::find_by_task_id(pool, task.id).await.unwrap();
+        assert_eq!(
+            kept.iter().map(|w| w.id).collect::<Vec<_>>(),
+            [workspace.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn restore_brings_back_task_and_its_workspaces() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let (task, workspace) = task_with_workspace(pool).await;
+
+        assert!(!TrashedTask::restore(pool, task.id).await.unwrap());
+        TrashedTask::trash(pool, task.id).await.unwrap();
+        assert!(TrashedTask::restore(pool, task.id).await.unwrap());
+
+        assert!(Task::find_by_id(pool, task.id).await.unwrap().is_some());
+        assert!(
+            TrashedTask::find_by_id(pool, task.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let workspaces = Workspace::fetch_all(pool, None).await.unwrap();
+        assert_eq!(
+            workspaces.iter().map(|w| w.id).collect::<Vec<_>>(),
+            [workspace.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn purge_deletes_trashed_task_with_its_workspaces() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let (task, workspace) = task_with_workspace(pool).await;
+        TrashedTask::trash(pool, task.id).await.unwrap();
+
+        let due = TrashedTask::find_deleted_before(pool, 0).await.unwrap();
+        assert_eq!(due.iter().map(|t| t.id).collect::<Vec<_>>(), [task.id]);
+        assert!(
+            TrashedTask::find_deleted_before(pool, 1)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        assert_eq!(Task::delete(pool, task.id).await.unwrap(), 1);
+        assert!(
+            TrashedTask::find_by_id(pool, task.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            Workspace::find_by_id(pool, workspace.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
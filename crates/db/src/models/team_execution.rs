@@ -69,6 +69,18 @@ pub struct PlannedSubtask {
     pub depends_on: Vec<i32>, // Indices of dependent tasks
     pub complexity: i32,      // 1-5
     pub estimated_duration: Option<i32>, // minutes
+    /// Repositories the subtask works in; empty means all of the project's repositories
+    #[serde(default)]
+    pub repo_scopes: Vec<RepoScope>,
+}
+
+/// The part of a subtask's work that falls in one repository
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct RepoScope {
+    /// Repository name, as listed for the project
+    pub repo_name: String,
+    /// What to change in this repository
+    pub hint: String,
 }
 
 impl TeamExecution {
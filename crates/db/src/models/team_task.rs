@@ -184,6 +184,41 @@ impl TeamTask {
         Ok(result)
     }
 
+    /// Team task that a workspace was created for, if any
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TeamTask,
+            r#"SELECT
+                id AS "id!: Uuid",
+                team_execution_id AS "team_execution_id!: Uuid",
+                task_id AS "task_id!: Uuid",
+                workspace_id AS "workspace_id: Uuid",
+                sequence_order AS "sequence_order!: i32",
+                depends_on,
+                required_skills,
+                assigned_agent_profile_id AS "assigned_agent_profile_id: Uuid",
+                status AS "status!: TeamTaskStatus",
+                branch_name,
+                complexity AS "complexity!: i32",
+                duration_seconds AS "duration_seconds: i32",
+                error_message,
+                retry_count AS "retry_count!: i32",
+                max_retries AS "max_retries!: i32",
+                started_at AS "started_at: DateTime<Utc>",
+                completed_at AS "completed_at: DateTime<Utc>",
+                created_at AS "created_at!: DateTime<Utc>",
+                updated_at AS "updated_at!: DateTime<Utc>"
+            FROM team_tasks
+            WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn find_ready_tasks(
         pool: &SqlitePool,
         team_execution_id: Uuid,
@@ -66,6 +66,24 @@ impl std::ops::Deref for WorkspaceWithStatus {
     }
 }
 
+/// Latest activity in a workspace, used to follow whichever one is busiest
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceActivity {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    /// When a process in the workspace last wrote output
+    pub last_output_at: Option<DateTime<Utc>>,
+    /// When a process in the workspace last started or changed status
+    pub last_status_change_at: Option<DateTime<Utc>>,
+    pub is_running: bool,
+}
+
+impl WorkspaceActivity {
+    pub fn last_activity_at(&self) -> Option<DateTime<Utc>> {
+        self.last_output_at.max(self.last_status_change_at)
+    }
+}
+
 /// GitHub PR creation parameters
 pub struct CreatePrParams<'a> {
     pub workspace_id: Uuid,
@@ -109,6 +127,7 @@ impl Workspace {
     }
 
     /// Fetch all workspaces, optionally filtered by task_id. Newest first.
+    /// Workspaces of tasks in the trash are left out.
     pub async fn fetch_all(
         pool: &SqlitePool,
         task_id: Option<Uuid>,
@@ -116,20 +135,21 @@ impl Workspace {
         let workspaces = match task_id {
             Some(tid) => sqlx::query_as!(
                 Workspace,
-                r#"SELECT id AS "id!: Uuid",
-                              task_id AS "task_id!: Uuid",
-                              container_ref,
-                              branch,
-                              agent_working_dir,
-                              setup_completed_at AS "setup_completed_at: DateTime<Utc>",
-                              created_at AS "created_at!: DateTime<Utc>",
-                              updated_at AS "updated_at!: DateTime<Utc>",
-                              archived AS "archived!: bool",
-                              pinned AS "pinned!: bool",
-                              name
-                       FROM workspaces
-                       WHERE task_id = $1
-                       ORDER BY created_at DESC"#,
+                r#"SELECT w.id AS "id!: Uuid",
+                              w.task_id AS "task_id!: Uuid",
+                              w.container_ref,
+                              w.branch,
+                              w.agent_working_dir,
+                              w.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              w.created_at AS "created_at!: DateTime<Utc>",
+                              w.updated_at AS "updated_at!: DateTime<Utc>",
+                              w.archived AS "archived!: bool",
+                              w.pinned AS "pinned!: bool",
+                              w.name
+                       FROM workspaces w
+                       JOIN tasks t ON t.id = w.task_id
+                       WHERE w.task_id = $1 AND t.deleted_at IS NULL
+                       ORDER BY w.created_at DESC"#,
                 tid
             )
             .fetch_all(pool)
@@ -137,19 +157,21 @@ impl Workspace {
             .map_err(WorkspaceError::Database)?,
             None => sqlx::query_as!(
                 Workspace,
-                r#"SELECT id AS "id!: Uuid",
-                              task_id AS "task_id!: Uuid",
-                              container_ref,
-                              branch,
-                              agent_working_dir,
-                              setup_completed_at AS "setup_completed_at: DateTime<Utc>",
-                              created_at AS "created_at!: DateTime<Utc>",
-                              updated_at AS "updated_at!: DateTime<Utc>",
-                              archived AS "archived!: bool",
-                              pinned AS "pinned!: bool",
-                              name
-                       FROM workspaces
-                       ORDER BY created_at DESC"#
+                r#"SELECT w.id AS "id!: Uuid",
+                              w.task_id AS "task_id!: Uuid",
+                              w.container_ref,
+                              w.branch,
+                              w.agent_working_dir,
+                              w.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              w.created_at AS "created_at!: DateTime<Utc>",
+                              w.updated_at AS "updated_at!: DateTime<Utc>",
+                              w.archived AS "archived!: bool",
+                              w.pinned AS "pinned!: bool",
+                              w.name
+                       FROM workspaces w
+                       JOIN tasks t ON t.id = w.task_id
+                       WHERE t.deleted_at IS NULL
+                       ORDER BY w.created_at DESC"#
             )
             .fetch_all(pool)
             .await
@@ -159,6 +181,33 @@ impl Workspace {
         Ok(workspaces)
     }
 
+    /// Workspaces of a task, including those of a task in the trash. Newest first.
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT id AS "id!: Uuid",
+                      task_id AS "task_id!: Uuid",
+                      container_ref,
+                      branch,
+                      agent_working_dir,
+                      setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>",
+                      archived AS "archived!: bool",
+                      pinned AS "pinned!: bool",
+                      name
+               FROM workspaces
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Load workspace with full validation - ensures workspace belongs to task and task belongs to project
     pub async fn load_context(
         pool: &SqlitePool,
@@ -577,6 +626,8 @@ impl Workspace {
                 ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64"
 
             FROM workspaces w
+            JOIN tasks t ON t.id = w.task_id
+            WHERE t.deleted_at IS NULL
             ORDER BY w.updated_at DESC"#
         )
         .fetch_all(pool)
@@ -623,6 +674,49 @@ impl Workspace {
         Ok(workspaces)
     }
 
+    /// Latest output and status change for each unarchived workspace in a
+    /// project, leaving out those of tasks in the trash
+    pub async fn find_activity_by_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<WorkspaceActivity>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceActivity,
+            r#"SELECT
+                w.id AS "workspace_id!: Uuid",
+                w.task_id AS "task_id!: Uuid",
+                (
+                    SELECT MAX(l.inserted_at)
+                    FROM sessions s
+                    JOIN execution_processes ep ON ep.session_id = s.id
+                    JOIN execution_process_logs l ON l.execution_id = ep.id
+                    WHERE s.workspace_id = w.id
+                ) AS "last_output_at: DateTime<Utc>",
+                (
+                    SELECT MAX(ep.updated_at)
+                    FROM sessions s
+                    JOIN execution_processes ep ON ep.session_id = s.id
+                    WHERE s.workspace_id = w.id
+                ) AS "last_status_change_at: DateTime<Utc>",
+                EXISTS (
+                    SELECT 1
+                    FROM sessions s
+                    JOIN execution_processes ep ON ep.session_id = s.id
+                    WHERE s.workspace_id = w.id
+                      AND ep.status = 'running'
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                ) AS "is_running!: bool"
+            FROM workspaces w
+            JOIN tasks t ON t.id = w.task_id
+            WHERE t.project_id = $1
+              AND t.deleted_at IS NULL
+              AND w.archived = 0"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Delete a workspace by ID
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM workspaces WHERE id = $1", id)
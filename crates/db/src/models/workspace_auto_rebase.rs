@@ -0,0 +1,192 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// When a workspace branch is rebased onto its target branches
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AutoRebaseTrigger {
+    /// Once a night, if a target branch has new commits
+    Nightly,
+    /// Whenever a target branch has new commits
+    TargetMoved,
+}
+
+/// Opt-in policy that keeps a workspace branch rebased onto its target branches
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceAutoRebase {
+    pub workspace_id: Uuid,
+    pub trigger: AutoRebaseTrigger,
+    /// Why rebasing stopped, e.g. the conflicts it ran into; set until resumed
+    pub paused_reason: Option<String>,
+    pub paused_at: Option<DateTime<Utc>>,
+    /// Failure of the most recent check that did not pause the policy
+    pub last_error: Option<String>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub last_rebased_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct SetWorkspaceAutoRebase {
+    pub trigger: AutoRebaseTrigger,
+}
+
+impl WorkspaceAutoRebase {
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceAutoRebase,
+            r#"SELECT workspace_id AS "workspace_id!: Uuid",
+                      trigger AS "trigger!: AutoRebaseTrigger",
+                      paused_reason,
+                      paused_at AS "paused_at: DateTime<Utc>",
+                      last_error,
+                      last_checked_at AS "last_checked_at: DateTime<Utc>",
+                      last_rebased_at AS "last_rebased_at: DateTime<Utc>",
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM workspace_auto_rebase_policies
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Policies that are not paused, of workspaces that are not archived and
+    /// whose task is not in the trash
+    pub async fn find_active(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceAutoRebase,
+            r#"SELECT p.workspace_id AS "workspace_id!: Uuid",
+                      p.trigger AS "trigger!: AutoRebaseTrigger",
+                      p.paused_reason,
+                      p.paused_at AS "paused_at: DateTime<Utc>",
+                      p.last_error,
+                      p.last_checked_at AS "last_checked_at: DateTime<Utc>",
+                      p.last_rebased_at AS "last_rebased_at: DateTime<Utc>",
+                      p.created_at AS "created_at!: DateTime<Utc>",
+                      p.updated_at AS "updated_at!: DateTime<Utc>"
+               FROM workspace_auto_rebase_policies p
+               JOIN workspaces w ON w.id = p.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE p.paused_at IS NULL AND w.archived = 0 AND t.deleted_at IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Enable the policy or change its trigger. Also resumes a paused policy.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        data: &SetWorkspaceAutoRebase,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceAutoRebase,
+            r#"INSERT INTO workspace_auto_rebase_policies (workspace_id, trigger)
+               VALUES ($1, $2)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   trigger = excluded.trigger,
+                   paused_reason = NULL,
+                   paused_at = NULL,
+                   last_error = NULL,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING workspace_id AS "workspace_id!: Uuid",
+                         trigger AS "trigger!: AutoRebaseTrigger",
+                         paused_reason,
+                         paused_at AS "paused_at: DateTime<Utc>",
+                         last_error,
+                         last_checked_at AS "last_checked_at: DateTime<Utc>",
+                         last_rebased_at AS "last_rebased_at: DateTime<Utc>",
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            workspace_id,
+            data.trigger
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record a completed check, and whether it rebased the branch.
+    pub async fn record_checked(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        rebased: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE workspace_auto_rebase_policies
+               SET last_error = NULL,
+                   last_checked_at = datetime('now', 'subsec'),
+                   last_rebased_at = CASE WHEN $2 THEN datetime('now', 'subsec') ELSE last_rebased_at END
+               WHERE workspace_id = $1"#,
+            workspace_id,
+            rebased
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a check that failed without pausing the policy; it is retried later.
+    pub async fn record_error(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE workspace_auto_rebase_policies
+               SET last_error = $2,
+                   last_checked_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1"#,
+            workspace_id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stop rebasing until the policy is set again.
+    pub async fn pause(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        reason: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE workspace_auto_rebase_policies
+               SET paused_reason = $2,
+                   paused_at = datetime('now', 'subsec'),
+                   last_checked_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1"#,
+            workspace_id,
+            reason
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, workspace_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM workspace_auto_rebase_policies WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
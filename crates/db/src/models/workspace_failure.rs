@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Why an attempt failed
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FailureCategory {
+    /// The setup script exited with an error
+    SetupScript,
+    /// The coding agent exited with an error or disappeared
+    AgentCrash,
+    /// The agent's provider refused further requests for now
+    RateLimit,
+    /// The agent ran past its runtime or CPU limit and was stopped
+    Timeout,
+    /// Rebasing the workspace branch ran into conflicts
+    MergeConflict,
+}
+
+/// The current failure of a workspace, with the task it belongs to
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceFailure {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    pub branch: String,
+    pub execution_process_id: Option<Uuid>,
+    pub category: FailureCategory,
+    /// The last line of output, or what went wrong, if known
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// When runs of an executor last failed on a rate limit
+#[derive(Debug, Clone)]
+pub struct ExecutorRateLimit {
+    pub executor: Option<String>,
+    pub last_limited_at: DateTime<Utc>,
+}
+
+impl WorkspaceFailure {
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        execution_process_id: Option<Uuid>,
+        category: FailureCategory,
+        detail: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO workspace_failures
+                   (id, workspace_id, execution_process_id, category, detail)
+               VALUES ($1, $2, $3, $4, $5)"#,
+            id,
+            workspace_id,
+            execution_process_id,
+            category,
+            detail
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Failures of unarchived workspaces that no later failure or run has
+    /// superseded, newest first, optionally narrowed to a project, a task or a
+    /// category.
+    pub async fn find_current(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+        category: Option<FailureCategory>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceFailure,
+            r#"SELECT f.id AS "id!: Uuid",
+                      f.workspace_id AS "workspace_id!: Uuid",
+                      w.task_id AS "task_id!: Uuid",
+                      t.project_id AS "project_id!: Uuid",
+                      t.title AS task_title,
+                      w.branch,
+                      f.execution_process_id AS "execution_process_id: Uuid",
+                      f.category AS "category!: FailureCategory",
+                      f.detail,
+                      f.created_at AS "created_at!: DateTime<Utc>"
+               FROM workspace_failures f
+               JOIN workspaces w ON w.id = f.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE w.archived = 0
+                   AND ($1 IS NULL OR t.project_id = $1)
+                   AND ($2 IS NULL OR w.task_id = $2)
+                   AND ($3 IS NULL OR f.category = $3)
+                   AND NOT EXISTS (
+                       SELECT 1 FROM workspace_failures f2
+                       WHERE f2.workspace_id = f.workspace_id
+                           AND julianday(f2.created_at) > julianday(f.created_at)
+                   )
+                   AND NOT EXISTS (
+                       SELECT 1
+                       FROM sessions s
+                       JOIN execution_processes ep ON ep.session_id = s.id
+                       WHERE s.workspace_id = f.workspace_id
+                           AND ep.run_reason IN ('setupscript', 'codingagent')
+                           AND julianday(ep.started_at) > julianday(f.created_at)
+                   )
+               ORDER BY f.created_at DESC
+               LIMIT $4"#,
+            project_id,
+            task_id,
+            category,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Executors with runs that failed on a rate limit since `since`, with the
+    /// latest such failure of each
+    pub async fn find_rate_limits_since(
+        pool: &SqlitePool,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ExecutorRateLimit>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutorRateLimit,
+            r#"SELECT s.executor,
+                      MAX(f.created_at) AS "last_limited_at!: DateTime<Utc>"
+               FROM workspace_failures f
+               JOIN execution_processes ep ON ep.id = f.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE f.category = 'rate_limit'
+                   AND julianday(f.created_at) >= julianday($1)
+               GROUP BY s.executor"#,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
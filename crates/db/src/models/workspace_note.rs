@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Free-form notes on a workspace, e.g. manual test results or review thoughts
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceNote {
+    pub workspace_id: Uuid,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct SetWorkspaceNote {
+    /// Replaces the notes; blank notes are removed
+    pub body: String,
+}
+
+impl WorkspaceNote {
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceNote,
+            r#"SELECT workspace_id AS "workspace_id!: Uuid",
+                      body,
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM workspace_notes
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        body: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceNote,
+            r#"INSERT INTO workspace_notes (workspace_id, body)
+               VALUES ($1, $2)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   body = excluded.body,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING workspace_id AS "workspace_id!: Uuid",
+                         body,
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            workspace_id,
+            body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, workspace_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM workspace_notes WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Optional limits for the coding agent of an attempt
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct ResourceLimits {
+    /// Wall-clock runtime of a single coding agent run, in seconds
+    pub max_runtime_secs: Option<i64>,
+    /// CPU time used by the agent's process group, in seconds
+    pub max_cpu_secs: Option<i64>,
+    /// Resident memory of the agent's process group, in MiB
+    pub max_memory_mb: Option<i64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_runtime_secs.is_none()
+            && self.max_cpu_secs.is_none()
+            && self.max_memory_mb.is_none()
+    }
+
+    /// Name of the first limit that is set but not positive.
+    pub fn invalid_field(&self) -> Option<&'static str> {
+        [
+            ("max_runtime_secs", self.max_runtime_secs),
+            ("max_cpu_secs", self.max_cpu_secs),
+            ("max_memory_mb", self.max_memory_mb),
+        ]
+        .into_iter()
+        .find(|(_, value)| value.is_some_and(|v| v <= 0))
+        .map(|(name, _)| name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ResourceLimitKind {
+    Runtime,
+    Cpu,
+    Memory,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceResourceLimits {
+    pub workspace_id: Uuid,
+    pub max_runtime_secs: Option<i64>,
+    pub max_cpu_secs: Option<i64>,
+    pub max_memory_mb: Option<i64>,
+    /// Limit that stopped the most recent coding agent run, if any
+    pub exceeded_limit: Option<ResourceLimitKind>,
+    pub exceeded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkspaceResourceLimits {
+    pub fn limits(&self) -> ResourceLimits {
+        ResourceLimits {
+            max_runtime_secs: self.max_runtime_secs,
+            max_cpu_secs: self.max_cpu_secs,
+            max_memory_mb: self.max_memory_mb,
+        }
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceResourceLimits,
+            r#"SELECT workspace_id AS "workspace_id!: Uuid",
+                      max_runtime_secs,
+                      max_cpu_secs,
+                      max_memory_mb,
+                      exceeded_limit AS "exceeded_limit: ResourceLimitKind",
+                      exceeded_at AS "exceeded_at: DateTime<Utc>",
+                      created_at AS "created_at!: DateTime<Utc>"
+               FROM workspace_resource_limits
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        limits: &ResourceLimits,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceResourceLimits,
+            r#"INSERT INTO workspace_resource_limits
+                   (workspace_id, max_runtime_secs, max_cpu_secs, max_memory_mb)
+               VALUES ($1, $2, $3, $4)
+               RETURNING workspace_id AS "workspace_id!: Uuid",
+                         max_runtime_secs,
+                         max_cpu_secs,
+                         max_memory_mb,
+                         exceeded_limit AS "exceeded_limit: ResourceLimitKind",
+                         exceeded_at AS "exceeded_at: DateTime<Utc>",
+                         created_at AS "created_at!: DateTime<Utc>""#,
+            workspace_id,
+            limits.max_runtime_secs,
+            limits.max_cpu_secs,
+            limits.max_memory_mb
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record the limit that stopped a run. `None` clears it when a new run starts.
+    pub async fn set_exceeded(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        limit: Option<ResourceLimitKind>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE workspace_resource_limits
+               SET exceeded_limit = $2,
+                   exceeded_at = CASE WHEN $2 IS NULL THEN NULL ELSE datetime('now', 'subsec') END
+               WHERE workspace_id = $1"#,
+            workspace_id,
+            limit
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
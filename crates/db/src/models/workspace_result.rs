@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// The final message of the latest coding agent run that completed in a
+/// workspace, kept as what the attempt did
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceResult {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub execution_process_id: Option<Uuid>,
+    pub summary: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkspaceResult {
+    /// Keep `summary` as the result of the workspace, replacing an earlier one
+    pub async fn record(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        execution_process_id: Uuid,
+        summary: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO workspace_results (workspace_id, execution_process_id, summary)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   execution_process_id = excluded.execution_process_id,
+                   summary = excluded.summary,
+                   created_at = datetime('now', 'subsec')"#,
+            workspace_id,
+            execution_process_id,
+            summary
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Results of the workspaces of a task, or of all workspaces, newest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceResult,
+            r#"SELECT r.workspace_id AS "workspace_id!: Uuid",
+                      w.task_id AS "task_id!: Uuid",
+                      r.execution_process_id AS "execution_process_id: Uuid",
+                      r.summary,
+                      r.created_at AS "created_at!: DateTime<Utc>"
+               FROM workspace_results r
+               JOIN workspaces w ON w.id = r.workspace_id
+               WHERE $1 IS NULL OR w.task_id = $1
+               ORDER BY r.created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// What a retention policy does to a workspace
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// Archive a workspace whose repositories have all been merged
+    Archive,
+    /// Remove the worktrees of a workspace of a cancelled task
+    DeleteWorktree,
+}
+
+/// A workspace a retention policy applies to
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct RetentionCandidate {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub project_name: String,
+    pub task_title: String,
+    pub branch: String,
+    pub action: RetentionAction,
+    /// When the last repository was merged, or when the cancelled task was last
+    /// updated
+    pub since: DateTime<Utc>,
+}
+
+impl RetentionCandidate {
+    /// Unpinned, unarchived workspaces with every repository merged, directly or
+    /// through a merged PR, at least `days` days ago
+    pub async fn find_merged_before(
+        pool: &SqlitePool,
+        days: u32,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let cutoff = format!("-{days} days");
+        sqlx::query_as!(
+            RetentionCandidate,
+            r#"SELECT w.id AS "workspace_id!: Uuid",
+                      w.task_id AS "task_id!: Uuid",
+                      p.name AS "project_name!",
+                      t.title AS "task_title!",
+                      w.branch AS "branch!",
+                      'archive' AS "action!: RetentionAction",
+                      MAX(COALESCE(m.pr_merged_at, m.created_at)) AS "since!: DateTime<Utc>"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               JOIN projects p ON p.id = t.project_id
+               JOIN merges m ON m.workspace_id = w.id
+                   AND (m.merge_type = 'direct' OR m.pr_status = 'merged')
+               WHERE w.archived = 0 AND w.pinned = 0
+                   AND NOT EXISTS (
+                       SELECT 1 FROM workspace_repos wr
+                       WHERE wr.workspace_id = w.id
+                           AND NOT EXISTS (
+                               SELECT 1 FROM merges m2
+                               WHERE m2.workspace_id = w.id AND m2.repo_id = wr.repo_id
+                                   AND (m2.merge_type = 'direct' OR m2.pr_status = 'merged')
+                           )
+                   )
+                   AND w.id NOT IN (
+                       SELECT s.workspace_id
+                       FROM sessions s
+                       JOIN execution_processes ep ON ep.session_id = s.id
+                       WHERE ep.completed_at IS NULL
+                   )
+               GROUP BY w.id
+               HAVING datetime(MAX(COALESCE(m.pr_merged_at, m.created_at))) <= datetime('now', $1)
+               ORDER BY MAX(COALESCE(m.pr_merged_at, m.created_at)) ASC"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Unpinned workspaces of cancelled tasks that still have worktrees and
+    /// nothing running in them
+    pub async fn find_cancelled_with_worktree(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RetentionCandidate,
+            r#"SELECT w.id AS "workspace_id!: Uuid",
+                      w.task_id AS "task_id!: Uuid",
+                      p.name AS project_name,
+                      t.title AS task_title,
+                      w.branch,
+                      'delete_worktree' AS "action!: RetentionAction",
+                      t.updated_at AS "since!: DateTime<Utc>"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE t.status = 'cancelled' AND w.container_ref IS NOT NULL AND w.pinned = 0
+                   AND w.id NOT IN (
+                       SELECT s.workspace_id
+                       FROM sessions s
+                       JOIN execution_processes ep ON ep.session_id = s.id
+                       WHERE ep.completed_at IS NULL
+                   )
+               ORDER BY t.updated_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -8,6 +8,7 @@ use std::{
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::Utc;
 use command_group::AsyncGroupChild;
 use db::{
     DBService,
@@ -16,6 +17,7 @@ use db::{
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
+        execution_process_orphan::OrphanReason,
         execution_process_repo_state::ExecutionProcessRepoState,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
@@ -23,6 +25,8 @@ use db::{
         task::{Task, TaskStatus},
         workspace::Workspace,
         workspace_repo::WorkspaceRepo,
+        workspace_resource_limits::{ResourceLimits, WorkspaceResourceLimits},
+        workspace_result::WorkspaceResult,
     },
 };
 use deployment::DeploymentError;
@@ -42,6 +46,8 @@ use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
+    attempt_failure,
+    auto_rebase::AutoRebaseService,
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
@@ -49,6 +55,7 @@ use services::services::{
     image::ImageService,
     notification::NotificationService,
     queued_message::QueuedMessageService,
+    retention::RetentionService,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
 };
 use tokio::{sync::RwLock, task::JoinHandle};
@@ -60,7 +67,12 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{command, copy};
+use crate::{command, copy, resource_limits};
+
+/// How often running execution processes are checked for a live child process
+const ORPHAN_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Newly started processes may not have a child process in the store yet
+const ORPHAN_GRACE_PERIOD: Duration = Duration::from_secs(60);
 
 #[derive(Clone)]
 pub struct LocalContainerService {
@@ -108,6 +120,9 @@ impl LocalContainerService {
         };
 
         container.spawn_workspace_cleanup();
+        container.spawn_orphan_watchdog();
+        AutoRebaseService::spawn(container.clone());
+        RetentionService::spawn(container.clone(), container.config.clone());
 
         container
     }
@@ -207,6 +222,61 @@ impl LocalContainerService {
         });
     }
 
+    /// Periodically fail execution processes that are still marked running although
+    /// no child process backs them, e.g. because it was lost or its workspace deleted.
+    pub fn spawn_orphan_watchdog(&self) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            // Leftovers from a previous run are handled by the startup cleanup
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + ORPHAN_CHECK_INTERVAL,
+                ORPHAN_CHECK_INTERVAL,
+            );
+            loop {
+                interval.tick().await;
+                if let Err(e) = container.fail_orphaned_executions().await {
+                    tracing::error!("Failed to check for orphaned execution processes: {}", e);
+                }
+            }
+        });
+    }
+
+    async fn fail_orphaned_executions(&self) -> Result<(), ContainerError> {
+        for process in ExecutionProcess::find_running(&self.db.pool).await? {
+            let age = (Utc::now() - process.started_at)
+                .to_std()
+                .unwrap_or_default();
+            if age < ORPHAN_GRACE_PERIOD || self.get_child_from_store(&process.id).await.is_some() {
+                continue;
+            }
+
+            let container_missing = ExecutionProcess::load_context(&self.db.pool, process.id)
+                .await
+                .is_ok_and(|ctx| {
+                    ctx.workspace
+                        .container_ref
+                        .is_none_or(|dir| !Path::new(&dir).is_dir())
+                });
+            let reason = if container_missing {
+                OrphanReason::ContainerMissing
+            } else {
+                OrphanReason::ProcessGone
+            };
+            tracing::warn!(
+                "Execution process {} is marked running but has no process ({})",
+                process.id,
+                reason
+            );
+            // The exit monitor may have recorded how it ended since `find_running`
+            if self.fail_orphaned_execution(&process, reason).await
+                && let Some(msg) = self.msg_stores.read().await.get(&process.id)
+            {
+                msg.push_stderr("Marked failed: the process is no longer running".to_string());
+            }
+        }
+        Ok(())
+    }
+
     /// Record the current HEAD commit for each repository as the "after" state.
     /// Errors are silently ignored since this runs after the main execution completes
     /// and failure should not block process finalization.
@@ -435,8 +505,12 @@ impl LocalContainerService {
             }
 
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                if matches!(ctx.execution_process.status, ExecutionProcessStatus::Failed) {
+                    container.record_failure(&ctx).await;
+                }
+
                 // Update executor session summary if available
-                if let Err(e) = container.update_executor_session_summary(&exec_id).await {
+                if let Err(e) = container.update_executor_session_summary(&ctx).await {
                     tracing::warn!("Failed to update executor session summary: {}", e);
                 }
 
@@ -590,6 +664,107 @@ impl LocalContainerService {
         })
     }
 
+    /// Classify a failed setup script or coding agent run from its output and record
+    /// it against the workspace.
+    async fn record_failure(&self, ctx: &ExecutionContext) {
+        let history = match self.msg_stores.read().await.get(&ctx.execution_process.id) {
+            Some(msg) => msg.get_history(),
+            None => Vec::new(),
+        };
+        let Some(category) = attempt_failure::classify(
+            &ctx.execution_process.run_reason,
+            &attempt_failure::output_tail(&history, false),
+        ) else {
+            return;
+        };
+        let stderr = attempt_failure::output_tail(&history, true);
+        let detail = attempt_failure::detail_from_output(&stderr).or_else(|| {
+            ctx.execution_process
+                .exit_code
+                .map(|code| format!("Exited with code {}", code))
+        });
+        attempt_failure::record(
+            &self.db.pool,
+            ctx.workspace.id,
+            Some(ctx.execution_process.id),
+            category,
+            detail.as_deref(),
+        )
+        .await;
+    }
+
+    /// Spawn a background task that stops a coding agent once it exceeds the attempt's
+    /// resource limits, recording which limit it hit.
+    fn spawn_resource_limit_watchdog(
+        &self,
+        execution_process: ExecutionProcess,
+        workspace_id: Uuid,
+        limits: ResourceLimits,
+        pgid: Option<i32>,
+    ) -> JoinHandle<()> {
+        let container = self.clone();
+        let started = std::time::Instant::now();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(resource_limits::POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                if container
+                    .get_child_from_store(&execution_process.id)
+                    .await
+                    .is_none()
+                {
+                    return;
+                }
+
+                let usage = pgid.and_then(resource_limits::process_group_usage);
+                let Some(kind) = resource_limits::exceeded_limit(&limits, started.elapsed(), usage)
+                else {
+                    continue;
+                };
+
+                let reason = resource_limits::describe(kind, &limits);
+                tracing::info!(
+                    "Stopping execution process {}: {} exceeded",
+                    execution_process.id,
+                    reason
+                );
+                if let Err(e) = WorkspaceResourceLimits::set_exceeded(
+                    &container.db.pool,
+                    workspace_id,
+                    Some(kind),
+                )
+                .await
+                {
+                    tracing::error!("Failed to record exceeded resource limit: {}", e);
+                }
+                attempt_failure::record(
+                    &container.db.pool,
+                    workspace_id,
+                    Some(execution_process.id),
+                    attempt_failure::classify_exceeded_limit(kind),
+                    Some(&format!("Stopped: {} exceeded", reason)),
+                )
+                .await;
+                if let Some(msg) = container.msg_stores.read().await.get(&execution_process.id) {
+                    msg.push_stderr(format!("Stopped: {} exceeded", reason));
+                }
+                if let Err(e) = container
+                    .stop_execution(&execution_process, ExecutionProcessStatus::Killed)
+                    .await
+                {
+                    tracing::error!(
+                        "Failed to stop execution process {} after exceeding its {}: {}",
+                        execution_process.id,
+                        reason,
+                        e
+                    );
+                }
+                return;
+            }
+        })
+    }
+
     pub fn spawn_os_exit_watcher(
         &self,
         exec_id: Uuid,
@@ -698,8 +873,13 @@ impl LocalContainerService {
         None
     }
 
-    /// Update the coding agent turn summary with the final assistant message
-    async fn update_executor_session_summary(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
+    /// Update the coding agent turn summary with the final assistant message, and
+    /// keep it as the workspace's result when the run completed
+    async fn update_executor_session_summary(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<(), anyhow::Error> {
+        let exec_id = &ctx.execution_process.id;
         // Check if there's a coding agent turn for this execution process
         let turn = CodingAgentTurn::find_by_execution_process_id(&self.db.pool, *exec_id).await?;
 
@@ -708,6 +888,18 @@ impl LocalContainerService {
             if turn.summary.is_none() {
                 if let Some(summary) = self.extract_last_assistant_message(exec_id) {
                     CodingAgentTurn::update_summary(&self.db.pool, *exec_id, &summary).await?;
+                    if matches!(
+                        ctx.execution_process.status,
+                        ExecutionProcessStatus::Completed
+                    ) {
+                        WorkspaceResult::record(
+                            &self.db.pool,
+                            ctx.workspace.id,
+                            *exec_id,
+                            &summary,
+                        )
+                        .await?;
+                    }
                 } else {
                     tracing::debug!("No assistant message found for execution {}", exec_id);
                 }
@@ -1138,6 +1330,9 @@ impl ContainerService for LocalContainerService {
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
             .await;
 
+        // The agent leads its own process group, so its pid is the group id
+        let pgid = spawned.child.inner().id().map(|pid| pid as i32);
+
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
 
@@ -1150,6 +1345,22 @@ impl ContainerService for LocalContainerService {
         // Spawn unified exit monitor: watches OS exit and optional executor signal
         let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
 
+        if matches!(
+            execution_process.run_reason,
+            ExecutionProcessRunReason::CodingAgent
+        ) && let Some(limits) =
+            WorkspaceResourceLimits::find_by_workspace_id(&self.db.pool, workspace.id).await?
+        {
+            // A new run starts with a clean slate
+            WorkspaceResourceLimits::set_exceeded(&self.db.pool, workspace.id, None).await?;
+            self.spawn_resource_limit_watchdog(
+                execution_process.clone(),
+                workspace.id,
+                limits.limits(),
+                pgid,
+            );
+        }
+
         Ok(())
     }
 
@@ -35,6 +35,7 @@ mod command;
 pub mod container;
 mod copy;
 pub mod pty;
+mod resource_limits;
 
 #[derive(Clone)]
 pub struct LocalDeployment {
@@ -0,0 +1,180 @@
+//! Per-attempt resource limits for coding agent processes.
+//!
+//! Runtime is enforced on every platform. CPU time and resident memory are summed over
+//! the agent's process group from `/proc`, so those two limits are only enforced on Linux.
+
+use std::time::Duration;
+
+use db::models::workspace_resource_limits::{ResourceLimitKind, ResourceLimits};
+
+/// How often a running agent is checked against its limits
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Resources used by a process group at one point in time
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProcessGroupUsage {
+    pub cpu_secs: f64,
+    pub memory_mb: u64,
+}
+
+/// The first limit that `elapsed` and `usage` exceed, if any.
+pub fn exceeded_limit(
+    limits: &ResourceLimits,
+    elapsed: Duration,
+    usage: Option<ProcessGroupUsage>,
+) -> Option<ResourceLimitKind> {
+    let over = |limit: Option<i64>, value: f64| limit.is_some_and(|limit| value > limit as f64);
+
+    if over(limits.max_runtime_secs, elapsed.as_secs_f64()) {
+        return Some(ResourceLimitKind::Runtime);
+    }
+    let usage = usage?;
+    if over(limits.max_cpu_secs, usage.cpu_secs) {
+        Some(ResourceLimitKind::Cpu)
+    } else if over(limits.max_memory_mb, usage.memory_mb as f64) {
+        Some(ResourceLimitKind::Memory)
+    } else {
+        None
+    }
+}
+
+/// Human readable description of a limit, e.g. "runtime limit of 1800s".
+pub fn describe(kind: ResourceLimitKind, limits: &ResourceLimits) -> String {
+    match kind {
+        ResourceLimitKind::Runtime => {
+            format!("runtime limit of {}s", limits.max_runtime_secs.unwrap_or(0))
+        }
+        ResourceLimitKind::Cpu => format!("CPU limit of {}s", limits.max_cpu_secs.unwrap_or(0)),
+        ResourceLimitKind::Memory => {
+            format!("memory limit of {} MiB", limits.max_memory_mb.unwrap_or(0))
+        }
+    }
+}
+
+/// Total CPU time and resident memory of every process in the group.
+#[cfg(target_os = "linux")]
+pub fn process_group_usage(pgid: i32) -> Option<ProcessGroupUsage> {
+    use nix::unistd::{SysconfVar, sysconf};
+
+    let ticks_per_sec = sysconf(SysconfVar::CLK_TCK).ok().flatten()? as f64;
+    let page_size = sysconf(SysconfVar::PAGE_SIZE).ok().flatten()? as u64;
+
+    let mut cpu_ticks = 0;
+    let mut rss_pages = 0;
+    for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+        {
+            continue;
+        }
+        // Processes may exit between listing and reading
+        let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+            continue;
+        };
+        let Some(stat) = parse_stat(&stat) else {
+            continue;
+        };
+        if stat.pgrp == pgid {
+            cpu_ticks += stat.cpu_ticks;
+            rss_pages += stat.rss_pages;
+        }
+    }
+    Some(ProcessGroupUsage {
+        cpu_secs: cpu_ticks as f64 / ticks_per_sec,
+        memory_mb: rss_pages * page_size / (1024 * 1024),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn process_group_usage(_pgid: i32) -> Option<ProcessGroupUsage> {
+    None
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[derive(Debug, PartialEq)]
+struct ProcStat {
+    pgrp: i32,
+    /// utime + stime, in clock ticks
+    cpu_ticks: u64,
+    rss_pages: u64,
+}
+
+/// Parse the fields we need from `/proc/<pid>/stat`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_stat(stat: &str) -> Option<ProcStat> {
+    // The command name is parenthesised and may itself contain spaces or ')'
+    let fields: Vec<&str> = stat
+        .get(stat.rfind(')')? + 1..)?
+        .split_whitespace()
+        .collect();
+    // `fields[0]` is field 3 (state) in proc(5)
+    let field = |n: usize| fields.get(n - 3).copied();
+    Some(ProcStat {
+        pgrp: field(5)?.parse().ok()?,
+        cpu_ticks: field(14)?.parse::<u64>().ok()? + field(15)?.parse::<u64>().ok()?,
+        rss_pages: field(24)?.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_proc_stat_with_awkward_command_name() {
+        let stat = "4242 (node (a) b) S 1 4200 4200 0 -1 4194560 1000 0 0 0 150 50 0 0 20 0 \
+                    11 0 123456 987654321 2560 18446744073709551615";
+        assert_eq!(
+            parse_stat(stat),
+            Some(ProcStat {
+                pgrp: 4200,
+                cpu_ticks: 200,
+                rss_pages: 2560,
+            })
+        );
+    }
+
+    #[test]
+    fn runtime_is_checked_without_usage() {
+        let limits = ResourceLimits {
+            max_runtime_secs: Some(60),
+            max_memory_mb: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded_limit(&limits, Duration::from_secs(61), None),
+            Some(ResourceLimitKind::Runtime)
+        );
+        assert_eq!(exceeded_limit(&limits, Duration::from_secs(59), None), None);
+    }
+
+    #[test]
+    fn usage_limits() {
+        let limits = ResourceLimits {
+            max_cpu_secs: Some(10),
+            max_memory_mb: Some(512),
+            ..Default::default()
+        };
+        let usage = |cpu_secs, memory_mb| {
+            Some(ProcessGroupUsage {
+                cpu_secs,
+                memory_mb,
+            })
+        };
+        assert_eq!(
+            exceeded_limit(&limits, Duration::ZERO, usage(10.5, 100)),
+            Some(ResourceLimitKind::Cpu)
+        );
+        assert_eq!(
+            exceeded_limit(&limits, Duration::ZERO, usage(1.0, 513)),
+            Some(ResourceLimitKind::Memory)
+        );
+        assert_eq!(
+            exceeded_limit(&limits, Duration::ZERO, usage(10.0, 512)),
+            None
+        );
+    }
+}
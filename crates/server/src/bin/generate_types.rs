@@ -16,6 +16,13 @@ fn generate_types_content() -> String {
         db::models::project::UpdateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        db::models::project_budget::ProjectBudget::decl(),
+        db::models::project_budget::SetProjectBudget::decl(),
+        db::models::project_branch_policy::ProjectBranchPolicy::decl(),
+        db::models::project_branch_policy::SetProjectBranchPolicy::decl(),
+        db::models::protected_branch::ProtectionMode::decl(),
+        db::models::protected_branch::ProtectedBranch::decl(),
+        db::models::protected_branch::CreateProtectedBranch::decl(),
         db::models::repo::Repo::decl(),
         db::models::repo::UpdateRepo::decl(),
         db::models::project_repo::ProjectRepo::decl(),
@@ -33,7 +40,9 @@ fn generate_types_content() -> String {
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task::ReorderTasks::decl(),
         db::models::task::ProjectTaskStats::decl(),
+        db::models::task_trash::TrashedTask::decl(),
         // Agent Teams types
         db::models::agent_skill::AgentSkill::decl(),
         db::models::agent_skill::CreateAgentSkill::decl(),
@@ -50,11 +59,19 @@ fn generate_types_content() -> String {
         db::models::team_execution::UpdateTeamExecution::decl(),
         db::models::team_execution::TeamPlanOutput::decl(),
         db::models::team_execution::PlannedSubtask::decl(),
+        db::models::team_execution::RepoScope::decl(),
+        db::models::plan_template::PlanTemplate::decl(),
+        db::models::plan_template::CreatePlanTemplate::decl(),
         db::models::team_task::TeamTaskStatus::decl(),
         db::models::team_task::TeamTask::decl(),
         db::models::team_task::CreateTeamTask::decl(),
         db::models::team_task::TeamTaskWithDetails::decl(),
         db::models::team_task::TeamProgress::decl(),
+        services::services::team::PlannerConfig::decl(),
+        services::services::team::QueuedTeamTask::decl(),
+        services::services::team::skills::RepoSkillProfile::decl(),
+        services::services::team::skills::ProfileSkillSuggestion::decl(),
+        services::services::team::skills::ProjectSkillSuggestions::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::DraftWorkspaceData::decl(),
         db::models::scratch::DraftWorkspaceRepo::decl(),
@@ -69,11 +86,31 @@ fn generate_types_content() -> String {
         db::models::image::CreateImage::decl(),
         db::models::workspace::Workspace::decl(),
         db::models::workspace::WorkspaceWithStatus::decl(),
+        db::models::workspace::WorkspaceActivity::decl(),
+        db::models::workspace_resource_limits::ResourceLimits::decl(),
+        db::models::workspace_resource_limits::ResourceLimitKind::decl(),
+        db::models::workspace_resource_limits::WorkspaceResourceLimits::decl(),
+        db::models::workspace_auto_rebase::AutoRebaseTrigger::decl(),
+        db::models::workspace_auto_rebase::WorkspaceAutoRebase::decl(),
+        db::models::workspace_auto_rebase::SetWorkspaceAutoRebase::decl(),
+        db::models::workspace_note::WorkspaceNote::decl(),
+        db::models::workspace_note::SetWorkspaceNote::decl(),
+        db::models::workspace_result::WorkspaceResult::decl(),
+        db::models::workspace_failure::FailureCategory::decl(),
+        db::models::workspace_failure::WorkspaceFailure::decl(),
+        db::models::workspace_retention::RetentionAction::decl(),
+        db::models::workspace_retention::RetentionCandidate::decl(),
+        services::services::retention::RetentionPolicy::decl(),
+        services::services::user_preferences::UserPreferences::decl(),
         db::models::session::Session::decl(),
+        db::models::session_progress::SessionProgress::decl(),
+        db::models::session_progress::ReportSessionProgress::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_process_orphan::OrphanReason::decl(),
+        db::models::execution_process_orphan::ExecutionProcessOrphan::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -115,6 +152,7 @@ fn generate_types_content() -> String {
         utils::api::projects::RemoteProjectMembersResponse::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::projects::BudgetOverrideRequest::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
@@ -127,6 +165,7 @@ fn generate_types_content() -> String {
         server::routes::config::CheckEditorAvailabilityQuery::decl(),
         server::routes::config::CheckEditorAvailabilityResponse::decl(),
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
+        server::routes::config::ExecutorHealth::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
@@ -144,6 +183,9 @@ fn generate_types_content() -> String {
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
+        server::routes::task_attempts::CreateTaskAttemptError::decl(),
+        server::routes::task_attempts::container_status::ContainerState::decl(),
+        server::routes::task_attempts::container_status::WorkspaceContainerStatus::decl(),
         server::routes::task_attempts::WorkspaceRepoInput::decl(),
         server::routes::task_attempts::RunAgentSetupRequest::decl(),
         server::routes::task_attempts::RunAgentSetupResponse::decl(),
@@ -162,7 +204,14 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
         services::services::git_host::UnifiedPrComment::decl(),
         services::services::git_host::ProviderKind::decl(),
+        services::services::budget::BudgetState::decl(),
+        services::services::budget::ExecutorSpend::decl(),
+        services::services::budget::ProjectBudgetStatus::decl(),
+        services::services::branch_policy::BranchPolicyViolation::decl(),
+        services::services::branch_policy::BranchPolicyReport::decl(),
+        services::services::protected_branch::ProtectedBranchViolation::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
+        server::routes::task_attempts::RepoCommits::decl(),
         server::routes::task_attempts::UpdateWorkspace::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryRequest::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummary::decl(),
@@ -186,6 +235,9 @@ fn generate_types_content() -> String {
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         services::services::git::ConflictOp::decl(),
+        services::services::git::PreviewCommit::decl(),
+        services::services::git::GitOperationPreview::decl(),
+        services::services::git::BranchCommit::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
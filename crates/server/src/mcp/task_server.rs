@@ -3,6 +3,8 @@ use std::{future::Future, str::FromStr};
 use db::models::{
     project::Project,
     repo::Repo,
+    session::Session,
+    session_progress::{ReportSessionProgress, SessionProgress},
     tag::Tag,
     task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     workspace::{Workspace, WorkspaceContext},
@@ -307,6 +309,27 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ReportProgressRequest {
+    #[schemars(
+        description = "Files changed since the last report, relative to the workspace root. Earlier files are kept."
+    )]
+    #[serde(default)]
+    pub files_touched: Vec<String>,
+    #[schemars(description = "Number of plan steps finished so far")]
+    pub steps_completed: Option<i32>,
+    #[schemars(description = "Total number of steps in the current plan")]
+    pub steps_total: Option<i32>,
+    #[schemars(description = "Short description of what you are doing now")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ReportProgressResponse {
+    pub files_touched: usize,
+    pub percent: Option<u8>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
@@ -353,7 +376,10 @@ impl TaskServer {
 
         if context.is_none() {
             self.tool_router.map.remove("get_context");
-            tracing::debug!("VK context not available, get_context tool will not be registered");
+            self.tool_router.map.remove("report_progress");
+            tracing::debug!(
+                "VK context not available, get_context and report_progress tools will not be registered"
+            );
         } else {
             tracing::info!("VK context loaded, get_context tool available");
         }
@@ -578,6 +604,58 @@ impl TaskServer {
         TaskServer::success(context)
     }
 
+    #[tool(
+        description = "Report your progress on the current task: files you changed and how many steps of your plan are done. Call it after each plan step; it also tells Vibe Kanban you are still working."
+    )]
+    async fn report_progress(
+        &self,
+        Parameters(ReportProgressRequest {
+            files_touched,
+            steps_completed,
+            steps_total,
+            message,
+        }): Parameters<ReportProgressRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        // Only registered if context exists
+        let context = self.context.as_ref().expect("VK context should exist");
+
+        // Sessions are ordered by most recent use, so the first is the one running
+        let url = self.url("/api/sessions");
+        let sessions: Vec<Session> = match self
+            .send_json(
+                self.client
+                    .get(&url)
+                    .query(&[("workspace_id", context.workspace_id)]),
+            )
+            .await
+        {
+            Ok(sessions) => sessions,
+            Err(e) => return Ok(e),
+        };
+        let Some(session) = sessions.first() else {
+            return TaskServer::err("No session found for the current workspace", None);
+        };
+
+        let url = self.url(&format!("/api/sessions/{}/progress", session.id));
+        let progress: SessionProgress = match self
+            .send_json(self.client.post(&url).json(&ReportSessionProgress {
+                files_touched,
+                steps_completed,
+                steps_total,
+                message,
+            }))
+            .await
+        {
+            Ok(progress) => progress,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&ReportProgressResponse {
+            files_touched: progress.files_touched.len(),
+            percent: progress.percent(),
+        })
+    }
+
     #[tool(
         description = "Create a new task/ticket in a project. Always pass the `project_id` of the project you want to create the task in - it is required!"
     )]
@@ -897,6 +975,8 @@ impl TaskServer {
             task_id,
             executor_profile_id,
             repos: workspace_repos,
+            resource_limits: None,
+            branch: None,
         };
 
         let url = self.url("/api/task-attempts");
@@ -1009,7 +1089,7 @@ impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
         let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script'. Make sure to pass `project_id`, `task_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
-            let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
+            let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available. Use 'report_progress' to report the files you changed and the plan steps you completed as you work.";
             instruction = format!("{} {}", context_instruction, instruction);
         }
 
@@ -0,0 +1,315 @@
+//! Idempotency keys for mutating requests.
+//!
+//! A client may send an `Idempotency-Key` header with a POST. The first request with a key
+//! runs normally and, if it succeeds, its response is kept for a while and replayed for any
+//! repeat of the same key on the same path. A client retrying after a timeout therefore gets
+//! the original task or attempt back instead of creating a duplicate. Concurrent requests
+//! with the same key wait for the first one rather than running the handler twice. Failed
+//! responses are not kept, so a retry after an error runs again; that includes refusals sent
+//! as a 200 with an unsuccessful `ApiResponse`, such as a protected branch asking for
+//! confirmation. A key sent again with a different body is rejected rather than replayed,
+//! and a body too large to hold for that comparison is refused.
+
+use std::{sync::OnceLock, time::Duration};
+
+use axum::{
+    Json,
+    body::{Body, Bytes, to_bytes},
+    extract::Request,
+    http::{HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http_body_util::LengthLimitError;
+use moka::future::Cache;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use utils::response::ApiResponse;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on responses that were replayed from an earlier request with the same key
+pub const IDEMPOTENT_REPLAY_HEADER: &str = "idempotent-replay";
+
+/// How long a successful response is replayed for
+const REPLAY_TTL: Duration = Duration::from_secs(60 * 60);
+
+const MAX_STORED_RESPONSES: u64 = 10_000;
+
+/// Largest request body read to compare repeats of a key, that of an image upload
+const MAX_REQUEST_BODY_BYTES: usize = 20 * 1024 * 1024;
+
+/// Largest response body kept for replay
+const MAX_RESPONSE_BODY_BYTES: usize = 20 * 1024 * 1024;
+
+#[derive(Clone)]
+struct StoredResponse {
+    /// SHA-256 of the body of the request this responds to
+    request_digest: [u8; 32],
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl StoredResponse {
+    async fn collect(request_digest: [u8; 32], response: Response) -> Self {
+        let (parts, body) = response.into_parts();
+        match to_bytes(body, MAX_RESPONSE_BODY_BYTES).await {
+            Ok(body) => Self {
+                request_digest,
+                status: parts.status,
+                headers: parts.headers,
+                body,
+            },
+            Err(e) => {
+                tracing::error!("Failed to read response body: {}", e);
+                Self {
+                    request_digest,
+                    status: StatusCode::INTERNAL_SERVER_ERROR,
+                    headers: HeaderMap::new(),
+                    body: Bytes::new(),
+                }
+            }
+        }
+    }
+
+    /// Whether the request succeeded: a 2xx whose `ApiResponse` envelope, if it has
+    /// one, reports success.
+    fn succeeded(&self) -> bool {
+        if !self.status.is_success() {
+            return false;
+        }
+        match serde_json::from_slice::<Value>(&self.body) {
+            Ok(Value::Object(envelope)) => envelope
+                .get("success")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+            _ => true,
+        }
+    }
+
+    fn into_response(self) -> Response {
+        let mut response = Response::new(Body::from(self.body));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+fn stored_responses() -> &'static Cache<String, StoredResponse> {
+    static STORE: OnceLock<Cache<String, StoredResponse>> = OnceLock::new();
+    STORE.get_or_init(|| {
+        Cache::builder()
+            .max_capacity(MAX_STORED_RESPONSES)
+            .time_to_live(REPLAY_TTL)
+            .build()
+    })
+}
+
+pub async fn idempotency_middleware(request: Request, next: Next) -> Response {
+    let key = request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| !key.is_empty())
+        .map(|key| format!("{} {}", request.uri().path(), key));
+    let Some(key) = key.filter(|_| request.method() == Method::POST) else {
+        return next.run(request).await;
+    };
+
+    // The body is read up front so a repeat of the key can be checked against it
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_REQUEST_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            let e = e.into_inner();
+            if e.is::<LengthLimitError>() {
+                return (
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    Json(ApiResponse::<()>::error(&format!(
+                        "Request body is larger than {} MB",
+                        MAX_REQUEST_BODY_BYTES / (1024 * 1024)
+                    ))),
+                )
+                    .into_response();
+            }
+            tracing::error!("Failed to read request body: {}", e);
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<()>::error("Failed to read request body")),
+            )
+                .into_response();
+        }
+    };
+    let request_digest: [u8; 32] = Sha256::digest(&body).into();
+    let request = Request::from_parts(parts, Body::from(body));
+
+    let entry = stored_responses()
+        .entry(key)
+        .or_try_insert_with(async move {
+            let stored = StoredResponse::collect(request_digest, next.run(request).await).await;
+            if stored.succeeded() {
+                Ok(stored)
+            } else {
+                Err(stored)
+            }
+        })
+        .await;
+
+    match entry {
+        Ok(entry) if entry.is_fresh() => entry.into_value().into_response(),
+        Ok(entry) if entry.value().request_digest != request_digest => key_reused(),
+        Ok(entry) => {
+            let mut response = entry.into_value().into_response();
+            response
+                .headers_mut()
+                .insert(IDEMPOTENT_REPLAY_HEADER, HeaderValue::from_static("true"));
+            response
+        }
+        Err(failed) if failed.request_digest != request_digest => key_reused(),
+        // Not stored; requests that waited on this one see the same failure
+        Err(failed) => failed.as_ref().clone().into_response(),
+    }
+}
+
+/// The response to a key sent again with a different body, which would otherwise
+/// get the response to a request it didn't make.
+fn key_reused() -> Response {
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(ApiResponse::<()>::error(
+            "Idempotency-Key was already used for a request with a different body",
+        )),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use axum::{Router, middleware::from_fn, routing::post};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use super::*;
+
+    /// Counts its calls and answers with the call number. A `fail` body gets a 500 and
+    /// a `refuse` body a 200 with an unsuccessful envelope.
+    fn counting_router(calls: Arc<AtomicUsize>) -> Router {
+        let handler = move |body: Bytes| async move {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            match body.as_ref() {
+                b"fail" => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::<usize>::error("failed")),
+                )
+                    .into_response(),
+                b"refuse" => Json(ApiResponse::<usize>::error("refused")).into_response(),
+                _ => Json(ApiResponse::<usize>::success(call)).into_response(),
+            }
+        };
+        Router::new()
+            .route("/tasks", post(handler))
+            .layer(from_fn(idempotency_middleware))
+    }
+
+    async fn send(router: &Router, key: &str, body: impl Into<Body>) -> (Response, Bytes) {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/tasks")
+            .header(IDEMPOTENCY_KEY_HEADER, key)
+            .body(body.into())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body, usize::MAX).await.unwrap();
+        (Response::from_parts(parts, Body::empty()), body)
+    }
+
+    fn replayed(response: &Response) -> bool {
+        response.headers().contains_key(IDEMPOTENT_REPLAY_HEADER)
+    }
+
+    #[tokio::test]
+    async fn same_key_and_body_replays_the_first_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(calls.clone());
+        let key = Uuid::new_v4().to_string();
+
+        let (first, first_body) = send(&router, &key, "{}").await;
+        let (second, second_body) = send(&router, &key, "{}").await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(!replayed(&first));
+        assert_eq!(second.status(), StatusCode::OK);
+        assert!(replayed(&second));
+        assert_eq!(first_body, second_body);
+    }
+
+    #[tokio::test]
+    async fn same_key_with_a_different_body_is_rejected() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(calls.clone());
+        let key = Uuid::new_v4().to_string();
+
+        send(&router, &key, r#"{"title":"a"}"#).await;
+        let (response, _) = send(&router, &key, r#"{"title":"b"}"#).await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_responses_are_not_kept() {
+        for (body, status) in [
+            ("fail", StatusCode::INTERNAL_SERVER_ERROR),
+            ("refuse", StatusCode::OK),
+        ] {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let router = counting_router(calls.clone());
+            let key = Uuid::new_v4().to_string();
+
+            let (first, _) = send(&router, &key, body).await;
+            let (second, _) = send(&router, &key, body).await;
+
+            assert_eq!(first.status(), status, "{body}");
+            assert_eq!(second.status(), status, "{body}");
+            assert!(!replayed(&second), "{body}");
+            assert_eq!(calls.load(Ordering::SeqCst), 2, "{body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_with_a_key_run_the_handler_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(calls.clone());
+        let key = Uuid::new_v4().to_string();
+
+        let ((first, first_body), (second, second_body)) =
+            tokio::join!(send(&router, &key, "{}"), send(&router, &key, "{}"));
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(first_body, second_body);
+        assert!(replayed(&first) != replayed(&second));
+    }
+
+    #[tokio::test]
+    async fn body_over_the_limit_is_refused() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(calls.clone());
+        let key = Uuid::new_v4().to_string();
+
+        let (response, _) = send(&router, &key, vec![b'x'; MAX_REQUEST_BODY_BYTES + 1]).await;
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+}
@@ -1,5 +1,7 @@
+pub mod idempotency;
 pub mod model_loaders;
 pub mod origin;
 
+pub use idempotency::*;
 pub use model_loaders::*;
 pub use origin::*;
@@ -8,6 +8,8 @@ use axum::{
     response::{Json as ResponseJson, Response},
     routing::{get, put},
 };
+use chrono::{DateTime, Duration, Utc};
+use db::models::workspace_failure::WorkspaceFailure;
 use deployment::{Deployment, DeploymentError};
 use executors::{
     executors::{
@@ -18,10 +20,13 @@ use executors::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{
-    Config, ConfigError, SoundFile,
-    editor::{EditorConfig, EditorType},
-    save_config_to_file,
+use services::services::{
+    config::{
+        Config, ConfigError, SoundFile,
+        editor::{EditorConfig, EditorType},
+        save_config_to_file,
+    },
+    user_preferences::UserPreferences,
 };
 use tokio::fs;
 use ts_rs::TS;
@@ -33,6 +38,10 @@ pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/info", get(get_user_system_info))
         .route("/config", put(update_config))
+        .route(
+            "/user-preferences",
+            get(get_user_preferences).put(update_user_preferences),
+        )
         .route("/sounds/{sound}", get(get_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
@@ -41,6 +50,7 @@ pub fn router() -> Router<DeploymentImpl> {
             get(check_editor_availability),
         )
         .route("/agents/check-availability", get(check_agent_availability))
+        .route("/agents/health", get(get_agents_health))
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -123,6 +133,27 @@ async fn update_config(
         ));
     }
 
+    if let Err(e) = new_config.planner.validate() {
+        return ResponseJson(ApiResponse::error(&format!(
+            "Invalid planner config: {}",
+            e
+        )));
+    }
+
+    if let Err(e) = new_config.retention.validate() {
+        return ResponseJson(ApiResponse::error(&format!(
+            "Invalid retention policy: {}",
+            e
+        )));
+    }
+
+    if let Err(e) = new_config.user_preferences.validate() {
+        return ResponseJson(ApiResponse::error(&format!(
+            "Invalid user preferences: {}",
+            e
+        )));
+    }
+
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
 
@@ -141,6 +172,28 @@ async fn update_config(
     }
 }
 
+async fn get_user_preferences(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<UserPreferences>> {
+    let preferences = deployment.config().read().await.user_preferences.clone();
+    ResponseJson(ApiResponse::success(preferences))
+}
+
+/// Replace the CLI's user preferences and save them to the config file
+async fn update_user_preferences(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UserPreferences>,
+) -> Result<ResponseJson<ApiResponse<UserPreferences>>, ApiError> {
+    payload.validate().map_err(ApiError::BadRequest)?;
+
+    let mut config = deployment.config().read().await.clone();
+    config.user_preferences = payload.clone();
+    save_config_to_file(&config, &config_path()).await?;
+    deployment.config().write().await.user_preferences = payload.clone();
+
+    Ok(ResponseJson(ApiResponse::success(payload)))
+}
+
 /// Track config events when fields transition from false → true
 async fn track_config_events(deployment: &DeploymentImpl, old: &Config, new: &Config) {
     let events = [
@@ -485,3 +538,68 @@ async fn check_agent_availability(
 
     ResponseJson(ApiResponse::success(info))
 }
+
+/// How far back a run that failed on a rate limit still marks its executor as
+/// unavailable
+const RATE_LIMIT_WINDOW_MINUTES: i64 = 15;
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ExecutorHealth {
+    pub executor: BaseCodingAgent,
+    pub availability: AvailabilityInfo,
+    /// When a recent run for this executor last failed on a rate limit
+    pub rate_limited_at: Option<DateTime<Utc>>,
+    pub available: bool,
+    /// Explanation shown next to the executor when it is unavailable or degraded
+    pub message: Option<String>,
+}
+
+async fn get_agents_health(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutorHealth>>>, ApiError> {
+    let profiles = ExecutorConfigs::get_cached();
+    let since = Utc::now() - Duration::minutes(RATE_LIMIT_WINDOW_MINUTES);
+    let rate_limits =
+        WorkspaceFailure::find_rate_limits_since(&deployment.db().pool, since).await?;
+
+    let mut executors: Vec<BaseCodingAgent> = profiles.executors.keys().copied().collect();
+    executors.sort_by_key(|executor| executor.to_string());
+
+    let health = executors
+        .into_iter()
+        .map(|executor| {
+            let availability = match profiles.get_coding_agent(&ExecutorProfileId::new(executor)) {
+                Some(agent) => agent.get_availability_info(),
+                None => AvailabilityInfo::NotFound,
+            };
+            let rate_limited_at = rate_limits
+                .iter()
+                .find(|info| info.executor.as_deref() == Some(executor.to_string().as_str()))
+                .map(|info| info.last_limited_at);
+
+            let message = match (&availability, rate_limited_at) {
+                (AvailabilityInfo::NotFound, _) => {
+                    Some("CLI not installed or not configured".to_string())
+                }
+                (_, Some(at)) => Some(format!(
+                    "Rate limited at {}; try again later",
+                    at.format("%H:%M UTC")
+                )),
+                (AvailabilityInfo::InstallationFound, None) => {
+                    Some("Installed, but no login was detected".to_string())
+                }
+                (AvailabilityInfo::LoginDetected { .. }, None) => None,
+            };
+
+            ExecutorHealth {
+                executor,
+                available: availability.is_available() && rate_limited_at.is_none(),
+                availability,
+                rate_limited_at,
+                message,
+            }
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(health)))
+}
@@ -11,6 +11,7 @@ use axum::{
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process_orphan::ExecutionProcessOrphan,
     execution_process_repo_state::ExecutionProcessRepoState,
 };
 use deployment::Deployment;
@@ -30,6 +31,41 @@ pub struct SessionExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct OrphanedExecutionsQuery {
+    pub project_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+/// Execution processes the server marked failed because they were still marked
+/// running after their process or workspace had gone away, newest first.
+pub async fn get_orphaned_executions(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrphanedExecutionsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcessOrphan>>>, ApiError> {
+    let orphans = ExecutionProcessOrphan::find_recent(
+        &deployment.db().pool,
+        query.project_id,
+        query.limit.unwrap_or(50),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(orphans)))
+}
+
+/// Execution processes of a session, oldest first
+pub async fn get_execution_processes(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SessionExecutionProcessQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcess>>>, ApiError> {
+    let processes = ExecutionProcess::find_by_session_id(
+        &deployment.db().pool,
+        query.session_id,
+        query.show_soft_deleted.unwrap_or(false),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(processes)))
+}
+
 pub async fn get_execution_process_by_id(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(_deployment): State<DeploymentImpl>,
@@ -256,10 +292,12 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         ));
 
     let workspaces_router = Router::new()
+        .route("/", get(get_execution_processes))
         .route(
             "/stream/session/ws",
             get(stream_execution_processes_by_session_ws),
         )
+        .route("/orphans", get(get_orphaned_executions))
         .nest("/{id}", workspace_id_router);
 
     Router::new().nest("/execution-processes", workspaces_router)
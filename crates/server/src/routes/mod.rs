@@ -1,5 +1,6 @@
 use axum::{
     Router,
+    middleware::from_fn,
     routing::{IntoMakeService, get},
 };
 use tower_http::validate_request::ValidateRequestHeaderLayer;
@@ -50,6 +51,7 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(team::router(&deployment))
         .merge(terminal::router())
         .nest("/images", images::routes())
+        .layer(from_fn(middleware::idempotency_middleware))
         .layer(ValidateRequestHeaderLayer::custom(
             middleware::validate_origin,
         ))
@@ -14,6 +14,8 @@ use axum::{
 };
 use db::models::{
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
+    project_branch_policy::{ProjectBranchPolicy, SetProjectBranchPolicy},
+    project_budget::{ProjectBudget, SetProjectBudget},
     project_repo::{CreateProjectRepo, ProjectRepo},
     repo::Repo,
 };
@@ -21,7 +23,10 @@ use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::{
-    file_search::SearchQuery, project::ProjectServiceError,
+    branch_policy,
+    budget::{self, ProjectBudgetStatus},
+    file_search::SearchQuery,
+    project::ProjectServiceError,
     remote_client::CreateRemoteProjectPayload,
 };
 use ts_rs::TS;
@@ -44,6 +49,12 @@ pub struct CreateRemoteProjectRequest {
     pub name: String,
 }
 
+#[derive(Deserialize, TS)]
+pub struct BudgetOverrideRequest {
+    /// Lift the cap for the rest of the current month, or restore it
+    pub enabled: bool,
+}
+
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
@@ -568,6 +579,86 @@ pub async fn get_project_repository(
     }
 }
 
+pub async fn get_project_budget(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectBudgetStatus>>, ApiError> {
+    let status = budget::project_budget_status(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+pub async fn set_project_budget(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetProjectBudget>,
+) -> Result<ResponseJson<ApiResponse<ProjectBudgetStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+    match payload.monthly_cap_cents {
+        Some(cap) if cap < 0 => {
+            return Err(ApiError::BadRequest(
+                "Monthly cap cannot be negative".to_string(),
+            ));
+        }
+        Some(cap) => {
+            ProjectBudget::upsert(pool, project.id, cap).await?;
+        }
+        None => {
+            ProjectBudget::delete(pool, project.id).await?;
+        }
+    }
+
+    let status = budget::project_budget_status(pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+pub async fn set_project_budget_override(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<BudgetOverrideRequest>,
+) -> Result<ResponseJson<ApiResponse<ProjectBudgetStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let month = payload
+        .enabled
+        .then(|| budget::month_key(chrono::Utc::now()));
+    if ProjectBudget::set_override_month(pool, project.id, month.as_deref())
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(
+            "Project has no budget configured".to_string(),
+        ));
+    }
+
+    let status = budget::project_budget_status(pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+pub async fn get_project_branch_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectBranchPolicy>>>, ApiError> {
+    let policy = ProjectBranchPolicy::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+pub async fn set_project_branch_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetProjectBranchPolicy>,
+) -> Result<ResponseJson<ApiResponse<ProjectBranchPolicy>>, ApiError> {
+    branch_policy::validate(&payload).map_err(ApiError::BadRequest)?;
+    let policy = ProjectBranchPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+pub async fn delete_project_branch_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectBranchPolicy::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let project_id_router = Router::new()
         .route(
@@ -586,6 +677,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
         )
+        .route("/budget", get(get_project_budget).put(set_project_budget))
+        .route("/budget/override", post(set_project_budget_override))
+        .route(
+            "/branch-policy",
+            get(get_project_branch_policy)
+                .put(set_project_branch_policy)
+                .delete(delete_project_branch_policy),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
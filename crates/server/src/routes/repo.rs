@@ -3,15 +3,16 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use db::models::{
     project::SearchResult,
+    protected_branch::{CreateProtectedBranch, ProtectedBranch},
     repo::{Repo, UpdateRepo},
 };
 use deployment::Deployment;
 use serde::Deserialize;
-use services::services::{file_search::SearchQuery, git::GitBranch};
+use services::services::{file_search::SearchQuery, git::GitBranch, protected_branch};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -206,6 +207,41 @@ pub async fn search_repo(
     }
 }
 
+pub async fn get_protected_branches(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProtectedBranch>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    let protected = ProtectedBranch::find_by_repo_id(pool, repo.id).await?;
+    Ok(ResponseJson(ApiResponse::success(protected)))
+}
+
+pub async fn protect_branch(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    ResponseJson(payload): ResponseJson<CreateProtectedBranch>,
+) -> Result<ResponseJson<ApiResponse<ProtectedBranch>>, ApiError> {
+    protected_branch::validate_pattern(&payload.pattern).map_err(ApiError::BadRequest)?;
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    let protected = ProtectedBranch::upsert(pool, repo.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(protected)))
+}
+
+pub async fn unprotect_branch(
+    State(deployment): State<DeploymentImpl>,
+    Path((repo_id, protected_branch_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ProtectedBranch::delete(&deployment.db().pool, repo_id, protected_branch_id).await?;
+    if rows == 0 {
+        return Err(ApiError::BadRequest(
+            "Protected branch not found".to_string(),
+        ));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/repos", get(get_repos).post(register_repo))
@@ -215,4 +251,12 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/repos/{repo_id}/branches", get(get_repo_branches))
         .route("/repos/{repo_id}/search", get(search_repo))
         .route("/repos/{repo_id}/open-editor", post(open_repo_in_editor))
+        .route(
+            "/repos/{repo_id}/protected-branches",
+            get(get_protected_branches).post(protect_branch),
+        )
+        .route(
+            "/repos/{repo_id}/protected-branches/{protected_branch_id}",
+            delete(unprotect_branch),
+        )
 }
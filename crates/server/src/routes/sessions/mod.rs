@@ -1,3 +1,4 @@
+pub mod progress;
 pub mod queue;
 pub mod review;
 
@@ -236,6 +237,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
         .route("/review", post(review::start_review))
+        .route(
+            "/progress",
+            get(progress::get_progress).post(progress::report_progress),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
@@ -243,6 +248,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let sessions_router = Router::new()
         .route("/", get(get_sessions).post(create_session))
+        .route("/progress", get(progress::get_workspace_progress))
         .nest("/{session_id}", session_id_router)
         .nest("/{session_id}/queue", queue::router(deployment));
 
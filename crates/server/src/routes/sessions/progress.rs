@@ -0,0 +1,58 @@
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    session::Session,
+    session_progress::{ReportSessionProgress, SessionProgress},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceProgressQuery {
+    pub task_id: Option<Uuid>,
+}
+
+/// Latest progress reported in each workspace of a task
+pub async fn get_workspace_progress(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceProgressQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<SessionProgress>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let progress = SessionProgress::find_latest_by_workspace(pool, query.task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(progress)))
+}
+
+pub async fn get_progress(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<SessionProgress>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let progress = SessionProgress::find_by_session_id(pool, session.id).await?;
+    Ok(ResponseJson(ApiResponse::success(progress)))
+}
+
+/// Record a progress report from the session's coding agent
+pub async fn report_progress(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReportSessionProgress>,
+) -> Result<ResponseJson<ApiResponse<SessionProgress>>, ApiError> {
+    if let (Some(completed), Some(total)) = (payload.steps_completed, payload.steps_total)
+        && (completed < 0 || total < 0 || completed > total)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid plan progress: {completed} of {total} steps"
+        )));
+    }
+
+    let pool = &deployment.db().pool;
+    let progress = SessionProgress::report(pool, session.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(progress)))
+}
@@ -1,4 +1,5 @@
 pub mod codex_setup;
+pub mod container_status;
 pub mod cursor_setup;
 pub mod gh_cli_setup;
 pub mod images;
@@ -27,11 +28,18 @@ use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
     project::SearchResult,
+    protected_branch::{ProtectedBranch, ProtectionMode},
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskRelationships, TaskStatus},
-    workspace::{CreateWorkspace, Workspace, WorkspaceError},
+    workspace::{CreateWorkspace, Workspace, WorkspaceActivity, WorkspaceError},
+    workspace_auto_rebase::{SetWorkspaceAutoRebase, WorkspaceAutoRebase},
+    workspace_failure::{FailureCategory, WorkspaceFailure},
+    workspace_note::{SetWorkspaceNote, WorkspaceNote},
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
+    workspace_resource_limits::{ResourceLimits, WorkspaceResourceLimits},
+    workspace_result::WorkspaceResult,
+    workspace_retention::RetentionCandidate,
 };
 use deployment::Deployment;
 use executors::{
@@ -45,14 +53,20 @@ use executors::{
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    attempt_failure,
+    branch_policy::BranchPolicyReport,
+    config::save_config_to_file,
     container::ContainerService,
     file_search::SearchQuery,
-    git::{ConflictOp, GitCliError, GitServiceError},
+    git::{BranchCommit, ConflictOp, GitCliError, GitOperationPreview, GitServiceError},
+    protected_branch::{self, ProtectedBranchViolation},
+    retention::RetentionPolicy,
+    team::TeamManager,
     workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{assets::config_path, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{
@@ -65,6 +79,10 @@ pub struct RebaseTaskAttemptRequest {
     pub repo_id: Uuid,
     pub old_base_branch: Option<String>,
     pub new_base_branch: Option<String>,
+    /// Go ahead even though the workspace branch is protected in confirm mode
+    #[serde(default)]
+    #[ts(optional)]
+    pub confirm_protected: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -78,6 +96,7 @@ pub struct AbortConflictsRequest {
 pub enum GitOperationError {
     MergeConflicts { message: String, op: ConflictOp },
     RebaseInProgress,
+    ProtectedBranch(ProtectedBranchViolation),
 }
 
 #[derive(Debug, Deserialize)]
@@ -85,6 +104,19 @@ pub struct TaskAttemptQuery {
     pub task_id: Option<Uuid>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceActivityQuery {
+    pub project_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceFailureQuery {
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub category: Option<FailureCategory>,
+    pub limit: Option<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct DiffStreamQuery {
     #[serde(default)]
@@ -113,6 +145,40 @@ pub async fn get_task_attempts(
     Ok(ResponseJson(ApiResponse::success(workspaces)))
 }
 
+pub async fn get_workspace_activity(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceActivityQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceActivity>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let activity = Workspace::find_activity_by_project(pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(activity)))
+}
+
+/// Workspaces whose latest attempt failed, with why, newest first
+pub async fn get_workspace_failures(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceFailureQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceFailure>>>, ApiError> {
+    let failures = WorkspaceFailure::find_current(
+        &deployment.db().pool,
+        query.project_id,
+        query.task_id,
+        query.category,
+        query.limit.unwrap_or(100),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(failures)))
+}
+
+/// What the coding agent of each workspace said it did, newest first
+pub async fn get_workspace_results(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskAttemptQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceResult>>>, ApiError> {
+    let results = WorkspaceResult::find_by_task_id(&deployment.db().pool, query.task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
 pub async fn get_workspace_count(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<i64>>, ApiError> {
@@ -121,6 +187,39 @@ pub async fn get_workspace_count(
     Ok(ResponseJson(ApiResponse::success(count)))
 }
 
+pub async fn get_retention_policy(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<RetentionPolicy>>, ApiError> {
+    let policy = deployment.config().read().await.retention.clone();
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// Replace the retention policy and save it to the config file
+pub async fn update_retention_policy(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RetentionPolicy>,
+) -> Result<ResponseJson<ApiResponse<RetentionPolicy>>, ApiError> {
+    payload.validate().map_err(ApiError::BadRequest)?;
+
+    let mut config = deployment.config().read().await.clone();
+    config.retention = payload.clone();
+    save_config_to_file(&config, &config_path()).await?;
+    deployment.config().write().await.retention = payload.clone();
+
+    Ok(ResponseJson(ApiResponse::success(payload)))
+}
+
+/// Workspaces the given policy would archive or remove worktrees of right now,
+/// so a policy can be checked before it is saved
+pub async fn preview_retention_policy(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RetentionPolicy>,
+) -> Result<ResponseJson<ApiResponse<Vec<RetentionCandidate>>>, ApiError> {
+    payload.validate().map_err(ApiError::BadRequest)?;
+    let candidates = payload.candidates(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(candidates)))
+}
+
 pub async fn get_task_attempt(
     Extension(workspace): Extension<Workspace>,
 ) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
@@ -152,6 +251,22 @@ pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Limits enforced on the coding agent; omitted means unlimited
+    #[serde(default)]
+    #[ts(optional)]
+    pub resource_limits: Option<ResourceLimits>,
+    /// Branch to create for the attempt; generated from the task title when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum CreateTaskAttemptError {
+    /// The requested branch breaks the project's branch naming policy
+    BranchPolicyViolation(BranchPolicyReport),
 }
 
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
@@ -172,7 +287,7 @@ pub struct RunAgentSetupResponse {}
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
-) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<Workspace, CreateTaskAttemptError>>, ApiError> {
     let executor_profile_id = payload.executor_profile_id.clone();
 
     if payload.repos.is_empty() {
@@ -180,11 +295,19 @@ pub async fn create_task_attempt(
             "At least one repository is required".to_string(),
         ));
     }
+    let resource_limits = payload
+        .resource_limits
+        .as_ref()
+        .filter(|limits| !limits.is_empty());
+    if let Some(field) = resource_limits.and_then(|limits| limits.invalid_field()) {
+        return Err(ApiError::BadRequest(format!("{} must be positive", field)));
+    }
 
     let pool = &deployment.db().pool;
     let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
+    util::ensure_budget_allows_attempt(pool, task.project_id).await?;
 
     // Compute agent_working_dir based on repo count:
     // - Single repo: use repo name as working dir (agent runs in repo directory)
@@ -199,10 +322,30 @@ pub async fn create_task_attempt(
     };
 
     let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
+    let requested_branch = payload
+        .branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|branch| !branch.is_empty());
+    let git_branch_name = match requested_branch {
+        Some(branch) => {
+            if let Some(report) =
+                util::check_requested_branch(pool, task.project_id, task.id, branch).await?
+            {
+                return Ok(ResponseJson(ApiResponse::error_with_data(
+                    CreateTaskAttemptError::BranchPolicyViolation(report),
+                )));
+            }
+            branch.to_string()
+        }
+        None => {
+            let generated = deployment
+                .container()
+                .git_branch_from_workspace(&attempt_id, &task.title)
+                .await;
+            util::conform_generated_branch(pool, task.project_id, task.id, generated).await?
+        }
+    };
 
     let workspace = Workspace::create(
         pool,
@@ -225,6 +368,9 @@ pub async fn create_task_attempt(
         .collect();
 
     WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+    if let Some(limits) = resource_limits {
+        WorkspaceResourceLimits::create(pool, workspace.id, limits).await?;
+    }
     if let Err(err) = deployment
         .container()
         .start_workspace(&workspace, executor_profile_id.clone())
@@ -242,6 +388,8 @@ pub async fn create_task_attempt(
                 "executor": &executor_profile_id.executor,
                 "workspace_id": workspace.id.to_string(),
                 "repository_count": payload.repos.len(),
+                "has_resource_limits": resource_limits.is_some(),
+                "custom_branch": requested_branch.is_some(),
             }),
         )
         .await;
@@ -405,11 +553,19 @@ async fn handle_workspaces_ws(
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct MergeTaskAttemptRequest {
     pub repo_id: Uuid,
+    /// Go ahead even though the target branch is protected in confirm mode
+    #[serde(default)]
+    #[ts(optional)]
+    pub confirm_protected: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct PushTaskAttemptRequest {
     pub repo_id: Uuid,
+    /// Go ahead even though the workspace branch is protected in confirm mode
+    #[serde(default)]
+    #[ts(optional)]
+    pub confirm_protected: bool,
 }
 
 #[axum::debug_handler]
@@ -417,7 +573,7 @@ pub async fn merge_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<MergeTaskAttemptRequest>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -429,12 +585,25 @@ pub async fn merge_task_attempt(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    if let Some(violation) = util::check_protected_branch(
+        pool,
+        repo.id,
+        &workspace_repo.target_branch,
+        request.confirm_protected,
+    )
+    .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            GitOperationError::ProtectedBranch(violation),
+        )));
+    }
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
         .await?;
     let workspace_path = Path::new(&container_ref);
-    let worktree_path = workspace_path.join(repo.name);
+    let worktree_path = workspace_path.join(&repo.name);
 
     let task = workspace
         .parent_task(pool)
@@ -474,6 +643,31 @@ pub async fn merge_task_attempt(
         Workspace::set_archived(pool, workspace.id, true).await?;
     }
 
+    // Team tasks stacked on this branch now build on the branch it merged into
+    match TeamManager::new(pool.clone())
+        .restack_after_merge(&workspace, &repo, &workspace_repo.target_branch)
+        .await
+    {
+        Ok(restacked) => {
+            for entry in restacked {
+                match entry.rebase_error {
+                    Some(e) => tracing::warn!(
+                        "Moved {} onto {} without rebasing its worktree: {}",
+                        entry.branch,
+                        entry.base_branch,
+                        e
+                    ),
+                    None => {
+                        tracing::info!("Restacked {} onto {}", entry.branch, entry.base_branch)
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to restack tasks on {}: {}", workspace.branch, e);
+        }
+    }
+
     // Stop any running dev servers for this workspace
     let dev_servers =
         ExecutionProcess::find_running_dev_servers_by_workspace(pool, workspace.id).await?;
@@ -512,6 +706,31 @@ pub async fn merge_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Dry run of `merge_task_attempt`: what merging would bring in, without merging
+pub async fn preview_merge_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<MergeTaskAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<GitOperationPreview>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let preview = deployment.git().preview_merge(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?;
+    Ok(ResponseJson(ApiResponse::success(preview)))
+}
+
 pub async fn push_task_attempt_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -528,6 +747,15 @@ pub async fn push_task_attempt_branch(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    if let Some(violation) =
+        util::check_protected_branch(pool, repo.id, &workspace.branch, request.confirm_protected)
+            .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            PushError::ProtectedBranch(violation),
+        )));
+    }
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -563,6 +791,15 @@ pub async fn force_push_task_attempt_branch(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    if let Some(violation) =
+        util::check_protected_branch(pool, repo.id, &workspace.branch, request.confirm_protected)
+            .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            PushError::ProtectedBranch(violation),
+        )));
+    }
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -576,11 +813,42 @@ pub async fn force_push_task_attempt_branch(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Dry run of `push_task_attempt_branch`: the commits a push would send
+pub async fn preview_push_task_attempt_branch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<PushTaskAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<GitOperationPreview>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let preview = deployment
+        .git()
+        .preview_push(&worktree_path, &workspace.branch)?;
+    Ok(ResponseJson(ApiResponse::success(preview)))
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum PushError {
     ForcePushRequired,
+    /// The workspace branch is protected
+    ProtectedBranch(ProtectedBranchViolation),
 }
 
 #[derive(serde::Deserialize, TS)]
@@ -683,6 +951,10 @@ pub struct BranchStatus {
     pub conflict_op: Option<ConflictOp>,
     /// List of files currently in conflicted (unmerged) state
     pub conflicted_files: Vec<String>,
+    /// How the target branch is protected, if it is
+    pub target_branch_protection: Option<ProtectionMode>,
+    /// How the workspace branch is protected, if it is
+    pub branch_protection: Option<ProtectionMode>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -735,6 +1007,13 @@ pub async fn get_task_attempt_branch_status(
 
         let repo_merges = merges_by_repo.get(&repo.id).cloned().unwrap_or_default();
 
+        let protected = ProtectedBranch::find_by_repo_id(pool, repo.id).await?;
+        let protection = |branch: &str| {
+            protected_branch::strictest_match(&protected, branch).map(|rule| rule.mode)
+        };
+        let target_branch_protection = protection(&target_branch);
+        let branch_protection = protection(&workspace.branch);
+
         let worktree_path = workspace_dir.join(&repo.name);
 
         let head_oid = deployment
@@ -831,6 +1110,8 @@ pub async fn get_task_attempt_branch_status(
                 is_rebase_in_progress,
                 conflict_op,
                 conflicted_files,
+                target_branch_protection,
+                branch_protection,
             },
         });
     }
@@ -838,6 +1119,42 @@ pub async fn get_task_attempt_branch_status(
     Ok(ResponseJson(ApiResponse::success(results)))
 }
 
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RepoCommits {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub target_branch_name: String,
+    /// Commits of the workspace branch that the target branch doesn't have, newest first
+    pub commits: Vec<BranchCommit>,
+}
+
+/// Commit log of the workspace branch in each repo, up to its target branch
+pub async fn get_task_attempt_commits(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<RepoCommits>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let repositories =
+        WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?;
+    let mut results = Vec::with_capacity(repositories.len());
+    for repo in repositories {
+        let commits = deployment.git().get_commits_ahead(
+            &repo.repo.path,
+            &workspace.branch,
+            &repo.target_branch,
+        )?;
+        results.push(RepoCommits {
+            repo_id: repo.repo.id,
+            repo_name: repo.repo.name,
+            target_branch_name: repo.target_branch,
+            commits,
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
 #[derive(serde::Deserialize, Debug, TS)]
 pub struct ChangeTargetBranchRequest {
     pub repo_id: Uuid,
@@ -1085,6 +1402,16 @@ pub async fn rebase_task_attempt(
         .await?
         .ok_or(RepoError::NotFound)?;
 
+    // Rebasing rewrites the workspace branch, whatever it is rebased onto
+    if let Some(violation) =
+        util::check_protected_branch(pool, repo.id, &workspace.branch, payload.confirm_protected)
+            .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            GitOperationError::ProtectedBranch(violation),
+        )));
+    }
+
     let old_base_branch = payload
         .old_base_branch
         .unwrap_or_else(|| workspace_repo.target_branch.clone());
@@ -1133,15 +1460,27 @@ pub async fn rebase_task_attempt(
     if let Err(e) = result {
         use services::services::git::GitServiceError;
         return match e {
-            GitServiceError::MergeConflicts(msg) => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::MergeConflicts {
-                    message: msg,
-                    op: ConflictOp::Rebase,
-                },
-            ))),
+            GitServiceError::MergeConflicts(msg) => {
+                attempt_failure::record(
+                    pool,
+                    workspace.id,
+                    None,
+                    FailureCategory::MergeConflict,
+                    Some(&format!(
+                        "Rebasing onto {} in {} ran into conflicts",
+                        new_base_branch, repo.name
+                    )),
+                )
+                .await;
+                Ok(ResponseJson(
+                    ApiResponse::<(), GitOperationError>::error_with_data(
+                        GitOperationError::MergeConflicts {
+                            message: msg,
+                            op: ConflictOp::Rebase,
+                        },
+                    ),
+                ))
+            }
             GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
                 (),
                 GitOperationError,
@@ -1165,6 +1504,60 @@ pub async fn rebase_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Dry run of `rebase_task_attempt`: replays the branch in memory and reports the
+/// commits it would move and any conflicts, leaving the target branch unchanged
+pub async fn preview_rebase_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RebaseTaskAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<GitOperationPreview>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let old_base_branch = payload
+        .old_base_branch
+        .unwrap_or_else(|| workspace_repo.target_branch.clone());
+    let new_base_branch = payload
+        .new_base_branch
+        .unwrap_or_else(|| workspace_repo.target_branch.clone());
+
+    if !deployment
+        .git()
+        .check_branch_exists(&repo.path, &new_base_branch)?
+    {
+        return Ok(ResponseJson(ApiResponse::error(
+            format!(
+                "Branch '{}' does not exist in the repository",
+                new_base_branch
+            )
+            .as_str(),
+        )));
+    }
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let preview = deployment.git().preview_rebase(
+        &repo.path,
+        &worktree_path,
+        &new_base_branch,
+        &old_base_branch,
+        &workspace.branch,
+    )?;
+    Ok(ResponseJson(ApiResponse::success(preview)))
+}
+
 #[axum::debug_handler]
 pub async fn abort_conflicts_task_attempt(
     Extension(workspace): Extension<Workspace>,
@@ -1568,6 +1961,78 @@ pub async fn get_task_attempt_repos(
     Ok(ResponseJson(ApiResponse::success(repos)))
 }
 
+pub async fn get_task_attempt_resource_limits(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspaceResourceLimits>>>, ApiError> {
+    let limits =
+        WorkspaceResourceLimits::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(limits)))
+}
+
+pub async fn get_task_attempt_auto_rebase(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspaceAutoRebase>>>, ApiError> {
+    let policy =
+        WorkspaceAutoRebase::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// Enable scheduled rebasing of the workspace, or change when it happens. Setting
+/// the policy again resumes it after it paused on conflicts.
+pub async fn set_task_attempt_auto_rebase(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetWorkspaceAutoRebase>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceAutoRebase>>, ApiError> {
+    let policy = WorkspaceAutoRebase::upsert(&deployment.db().pool, workspace.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_auto_rebase_set",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "trigger": payload.trigger.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+pub async fn delete_task_attempt_auto_rebase(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    WorkspaceAutoRebase::delete(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_task_attempt_notes(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspaceNote>>>, ApiError> {
+    let note = WorkspaceNote::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(note)))
+}
+
+/// Replace the workspace's notes. Blank notes remove them, which returns `None`.
+pub async fn set_task_attempt_notes(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetWorkspaceNote>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspaceNote>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.body.trim().is_empty() {
+        WorkspaceNote::delete(pool, workspace.id).await?;
+        return Ok(ResponseJson(ApiResponse::success(None)));
+    }
+    let note = WorkspaceNote::upsert(pool, workspace.id, &payload.body).await?;
+    Ok(ResponseJson(ApiResponse::success(Some(note))))
+}
+
 pub async fn search_workspace_files(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1746,11 +2211,15 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/run-setup-script", post(run_setup_script))
         .route("/run-cleanup-script", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
+        .route("/commits", get(get_task_attempt_commits))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
         .route("/merge", post(merge_task_attempt))
+        .route("/merge/preview", post(preview_merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/push/force", post(force_push_task_attempt_branch))
+        .route("/push/preview", post(preview_push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
+        .route("/rebase/preview", post(preview_rebase_task_attempt))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
         .route("/pr", post(pr::create_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
@@ -1761,6 +2230,25 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
         .route("/repos", get(get_task_attempt_repos))
+        .route(
+            "/container/restart",
+            post(container_status::restart_container),
+        )
+        .route(
+            "/container/rebuild",
+            post(container_status::rebuild_container),
+        )
+        .route("/resource-limits", get(get_task_attempt_resource_limits))
+        .route(
+            "/auto-rebase",
+            get(get_task_attempt_auto_rebase)
+                .put(set_task_attempt_auto_rebase)
+                .delete(delete_task_attempt_auto_rebase),
+        )
+        .route(
+            "/notes",
+            get(get_task_attempt_notes).put(set_task_attempt_notes),
+        )
         .route("/search", get(search_workspace_files))
         .route("/first-message", get(get_first_user_message))
         .route("/mark-seen", put(mark_seen))
@@ -1772,6 +2260,18 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempts_router = Router::new()
         .route("/", get(get_task_attempts).post(create_task_attempt))
         .route("/count", get(get_workspace_count))
+        .route("/activity", get(get_workspace_activity))
+        .route("/failures", get(get_workspace_failures))
+        .route("/results", get(get_workspace_results))
+        .route(
+            "/retention-policy",
+            get(get_retention_policy).put(update_retention_policy),
+        )
+        .route("/retention-policy/preview", post(preview_retention_policy))
+        .route(
+            "/container-status",
+            get(container_status::get_container_statuses),
+        )
         .route("/stream/ws", get(stream_workspaces_ws))
         .route("/summary", post(workspace_summary::get_workspace_summaries))
         .nest("/{id}", task_attempt_id_router)
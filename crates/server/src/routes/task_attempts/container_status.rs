@@ -0,0 +1,334 @@
+use std::path::Path;
+
+use axum::{
+    Extension,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::ExecutionProcess, workspace::Workspace, workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerState {
+    /// The workspace directory exists and a process is running in it
+    Running,
+    /// The workspace directory exists but nothing is running
+    Stopped,
+    /// The workspace has no directory on disk
+    Missing,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct WorkspaceContainerStatus {
+    pub workspace_id: Uuid,
+    pub state: ContainerState,
+    pub container_ref: Option<String>,
+    /// Repositories whose worktree is missing from an existing workspace directory
+    pub missing_repos: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerStatusQuery {
+    pub task_id: Option<Uuid>,
+}
+
+async fn is_running(pool: &SqlitePool, workspace_id: Uuid) -> Result<bool, sqlx::Error> {
+    Ok(
+        ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace_id)
+            .await?
+            || !ExecutionProcess::find_running_dev_servers_by_workspace(pool, workspace_id)
+                .await?
+                .is_empty(),
+    )
+}
+
+async fn container_status(
+    pool: &SqlitePool,
+    workspace: &Workspace,
+) -> Result<WorkspaceContainerStatus, sqlx::Error> {
+    let dir = workspace.container_ref.as_deref().map(Path::new);
+    let (state, missing_repos) = match dir {
+        Some(dir) if dir.is_dir() => {
+            let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+            let missing_repos = repos
+                .into_iter()
+                .filter(|repo| !dir.join(&repo.name).exists())
+                .map(|repo| repo.name)
+                .collect();
+            let state = if is_running(pool, workspace.id).await? {
+                ContainerState::Running
+            } else {
+                ContainerState::Stopped
+            };
+            (state, missing_repos)
+        }
+        _ => (ContainerState::Missing, Vec::new()),
+    };
+
+    Ok(WorkspaceContainerStatus {
+        workspace_id: workspace.id,
+        state,
+        container_ref: workspace.container_ref.clone(),
+        missing_repos,
+    })
+}
+
+/// Statuses of the workspaces of a task, or of every workspace
+async fn container_statuses(
+    pool: &SqlitePool,
+    task_id: Option<Uuid>,
+) -> Result<Vec<WorkspaceContainerStatus>, ApiError> {
+    let workspaces = Workspace::fetch_all(pool, task_id).await?;
+
+    let mut statuses = Vec::with_capacity(workspaces.len());
+    for workspace in &workspaces {
+        statuses.push(container_status(pool, workspace).await?);
+    }
+    Ok(statuses)
+}
+
+pub async fn get_container_statuses(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ContainerStatusQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceContainerStatus>>>, ApiError> {
+    let statuses = container_statuses(&deployment.db().pool, query.task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(statuses)))
+}
+
+/// Stop anything running in the workspace and recreate whatever is missing from its
+/// directory, keeping existing worktrees and their changes.
+pub async fn restart_container(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceContainerStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    deployment.container().try_stop(&workspace, true).await;
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+
+    let workspace = Workspace::find_by_id(pool, workspace.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(
+        container_status(pool, &workspace).await?,
+    )))
+}
+
+/// Delete the workspace directory and create it again from the workspace branch.
+/// Refused while something is running or the worktrees have uncommitted changes,
+/// since those would be lost.
+pub async fn rebuild_container(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceContainerStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if is_running(pool, workspace.id).await? {
+        return Err(ApiError::Conflict(
+            "Stop the running processes before rebuilding the workspace".to_string(),
+        ));
+    }
+    if !deployment
+        .container()
+        .is_container_clean(&workspace)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Workspace has uncommitted changes; commit or discard them before rebuilding"
+                .to_string(),
+        ));
+    }
+
+    deployment.container().delete(&workspace).await?;
+    let workspace = Workspace::find_by_id(pool, workspace.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+
+    let workspace = Workspace::find_by_id(pool, workspace.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    tracing::info!("Rebuilt workspace {}", workspace.id);
+    Ok(ResponseJson(ApiResponse::success(
+        container_status(pool, &workspace).await?,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        DBService,
+        models::{
+            execution_process::{
+                CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason,
+            },
+            project::{CreateProject, Project},
+            repo::Repo,
+            session::{CreateSession, Session},
+            task::{CreateTask, Task},
+            workspace::CreateWorkspace,
+            workspace_repo::CreateWorkspaceRepo,
+        },
+    };
+    use executors::actions::{
+        ExecutorAction, ExecutorActionType,
+        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+    };
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// A task with a workspace in `container_ref` holding the `api` and `web` repositories
+    async fn workspace_in(pool: &SqlitePool, container_ref: Option<&Path>) -> Workspace {
+        let project = Project::create(
+            pool,
+            &CreateProject {
+                name: "Containers".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(project.id, "Status".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch: "vk/status".to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+
+        let mut repos = Vec::new();
+        for name in ["api", "web"] {
+            let path = std::env::temp_dir()
+                .join(Uuid::new_v4().to_string())
+                .join(name);
+            let repo = Repo::find_or_create(pool, &path, name).await.unwrap();
+            repos.push(CreateWorkspaceRepo {
+                repo_id: repo.id,
+                target_branch: "main".to_string(),
+            });
+        }
+        WorkspaceRepo::create_many(pool, workspace.id, &repos)
+            .await
+            .unwrap();
+        if let Some(container_ref) = container_ref {
+            Workspace::update_container_ref(pool, workspace.id, &container_ref.to_string_lossy())
+                .await
+                .unwrap();
+        }
+        Workspace::find_by_id(pool, workspace.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn start_setup_script(pool: &SqlitePool, workspace: &Workspace) {
+        let session = Session::create(
+            pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await
+        .unwrap();
+        let action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "npm install".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::SetupScript,
+                working_dir: None,
+            }),
+            None,
+        );
+        ExecutionProcess::create(
+            pool,
+            &CreateExecutionProcess {
+                session_id: session.id,
+                executor_action: action,
+                run_reason: ExecutionProcessRunReason::SetupScript,
+            },
+            Uuid::new_v4(),
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn running_container_lists_missing_worktrees() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("api")).unwrap();
+        let workspace = workspace_in(pool, Some(dir.path())).await;
+
+        let status = container_status(pool, &workspace).await.unwrap();
+        assert_eq!(status.state, ContainerState::Stopped);
+
+        start_setup_script(pool, &workspace).await;
+        let statuses = container_statuses(pool, Some(workspace.task_id))
+            .await
+            .unwrap();
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].workspace_id, workspace.id);
+        assert_eq!(statuses[0].state, ContainerState::Running);
+        assert_eq!(statuses[0].missing_repos, ["web"]);
+    }
+
+    #[tokio::test]
+    async fn container_without_a_directory_is_missing() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let dir = TempDir::new().unwrap();
+        let deleted = workspace_in(pool, Some(&dir.path().join("deleted"))).await;
+        let never_created = workspace_in(pool, None).await;
+
+        for workspace in [deleted, never_created] {
+            let status = container_status(pool, &workspace).await.unwrap();
+            assert_eq!(status.state, ContainerState::Missing);
+            assert_eq!(status.container_ref, workspace.container_ref);
+            assert!(status.missing_repos.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_attempt_has_no_container_statuses() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        workspace_in(pool, None).await;
+
+        let statuses = container_statuses(pool, Some(Uuid::new_v4()))
+            .await
+            .unwrap();
+        assert!(statuses.is_empty());
+    }
+}
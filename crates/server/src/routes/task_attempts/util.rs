@@ -2,11 +2,20 @@ use std::path::PathBuf;
 
 use db::models::{
     execution_process::ExecutionProcess, execution_process_repo_state::ExecutionProcessRepoState,
+    project_branch_policy::ProjectBranchPolicy, protected_branch::ProtectedBranch,
     workspace::Workspace, workspace_repo::WorkspaceRepo,
 };
 use deployment::Deployment;
-use services::services::{container::ContainerService, git::WorktreeResetOptions};
+use git2::Branch;
+use services::services::{
+    branch_policy::{self, BranchPolicyReport},
+    budget::{self, BudgetState},
+    container::ContainerService,
+    git::WorktreeResetOptions,
+    protected_branch::ProtectedBranchViolation,
+};
 use sqlx::SqlitePool;
+use utils::text::short_uuid;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -81,3 +90,88 @@ pub async fn restore_worktrees_to_process(
 
     Ok(())
 }
+
+/// Refuse to start new attempts once a project's monthly budget is used up.
+/// Logs a warning when spend has crossed the warning threshold.
+pub async fn ensure_budget_allows_attempt(
+    pool: &SqlitePool,
+    project_id: Uuid,
+) -> Result<(), ApiError> {
+    let status = budget::project_budget_status(pool, project_id).await?;
+    if status.blocks_new_attempts() {
+        return Err(ApiError::Forbidden(format!(
+            "Monthly budget for this project is exhausted (${:.2} of ${:.2} spent in {}). \
+             Raise the cap or lift it for the rest of the month.",
+            status.spent_cents as f64 / 100.0,
+            status.monthly_cap_cents.unwrap_or_default() as f64 / 100.0,
+            status.month,
+        )));
+    }
+    if status.state == BudgetState::Warning {
+        tracing::warn!(
+            "Project {} has used {:.0}% of its monthly budget",
+            project_id,
+            status.percent_used.unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Bring a server generated branch name for a task's attempt into line with the
+/// project's branch policy.
+pub async fn conform_generated_branch(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    task_id: Uuid,
+    branch: String,
+) -> Result<String, ApiError> {
+    let Some(policy) = ProjectBranchPolicy::find_by_project_id(pool, project_id).await? else {
+        return Ok(branch);
+    };
+    if branch_policy::check(&policy, &branch).is_empty() {
+        return Ok(branch);
+    }
+    let conformed = branch_policy::conform(&policy, &branch, &short_uuid(&task_id));
+    tracing::debug!(
+        "Renamed branch {} to {} to follow the branch policy of project {}",
+        branch,
+        conformed,
+        project_id
+    );
+    Ok(conformed)
+}
+
+/// Check a branch name requested for a new attempt of a task. Names git would reject
+/// are a bad request; names breaking the project's branch policy come back as a report.
+pub async fn check_requested_branch(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    task_id: Uuid,
+    branch: &str,
+) -> Result<Option<BranchPolicyReport>, ApiError> {
+    if !Branch::name_is_valid(branch).unwrap_or_default() {
+        return Err(ApiError::BadRequest(format!(
+            "'{}' is not a valid git branch name",
+            branch
+        )));
+    }
+    let policy = ProjectBranchPolicy::find_by_project_id(pool, project_id).await?;
+    Ok(policy
+        .and_then(|policy| BranchPolicyReport::for_branch(&policy, branch, &short_uuid(&task_id))))
+}
+
+/// Check an operation that writes to `branch` of a repo against the repo's protected
+/// branches. `confirmed` is the caller's go-ahead for branches in confirm mode.
+pub async fn check_protected_branch(
+    pool: &SqlitePool,
+    repo_id: Uuid,
+    branch: &str,
+    confirmed: bool,
+) -> Result<Option<ProtectedBranchViolation>, ApiError> {
+    let rules = ProtectedBranch::find_by_repo_id(pool, repo_id).await?;
+    let violation = ProtectedBranchViolation::for_branch(&rules, branch, confirmed);
+    if let Some(violation) = &violation {
+        tracing::info!("Refused to write to protected branch: {}", violation);
+    }
+    Ok(violation)
+}
@@ -1,10 +1,8 @@
-use std::path::PathBuf;
-
 use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Query, State,
+        Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
@@ -15,7 +13,8 @@ use axum::{
 use db::models::{
     image::TaskImage,
     repo::{Repo, RepoError},
-    task::{CreateTask, ProjectTaskStats, Task, TaskWithAttemptStatus, UpdateTask},
+    task::{CreateTask, ProjectTaskStats, ReorderTasks, Task, TaskWithAttemptStatus, UpdateTask},
+    task_trash::TrashedTask,
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -23,15 +22,20 @@ use deployment::Deployment;
 use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use services::services::{container::ContainerService, workspace_manager::WorkspaceManager};
+use services::services::{container::ContainerService, task_trash};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_task_middleware,
+    routes::task_attempts::{
+        CreateTaskAttemptError, WorkspaceRepoInput,
+        util::{check_requested_branch, conform_generated_branch, ensure_budget_allows_attempt},
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -149,12 +153,16 @@ pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Branch to create for the attempt; generated from the task title when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub branch: Option<String>,
 }
 
 pub async fn create_task_and_start(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateAndStartTaskRequest>,
-) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus, CreateTaskAttemptError>>, ApiError> {
     if payload.repos.is_empty() {
         return Err(ApiError::BadRequest(
             "At least one repository is required".to_string(),
@@ -162,8 +170,24 @@ pub async fn create_task_and_start(
     }
 
     let pool = &deployment.db().pool;
+    ensure_budget_allows_attempt(pool, payload.task.project_id).await?;
 
+    // Checked before the task is created so a rejected name leaves nothing behind
     let task_id = Uuid::new_v4();
+    let requested_branch = payload
+        .branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|branch| !branch.is_empty());
+    if let Some(branch) = requested_branch
+        && let Some(report) =
+            check_requested_branch(pool, payload.task.project_id, task_id, branch).await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            CreateTaskAttemptError::BranchPolicyViolation(report),
+        )));
+    }
+
     let task = Task::create(pool, &payload.task, task_id).await?;
 
     if let Some(image_ids) = &payload.task.image_ids {
@@ -183,10 +207,16 @@ pub async fn create_task_and_start(
         .await;
 
     let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
+    let git_branch_name = match requested_branch {
+        Some(branch) => branch.to_string(),
+        None => {
+            let generated = deployment
+                .container()
+                .git_branch_from_workspace(&attempt_id, &task.title)
+                .await;
+            conform_generated_branch(pool, task.project_id, task.id, generated).await?
+        }
+    };
 
     // Compute agent_working_dir based on repo count:
     // - Single repo: use repo name as working dir (agent runs in repo directory)
@@ -270,7 +300,7 @@ pub async fn update_task(
         .parent_workspace_id
         .or(existing_task.parent_workspace_id);
 
-    let task = Task::update(
+    let mut task = Task::update(
         &deployment.db().pool,
         existing_task.id,
         existing_task.project_id,
@@ -281,6 +311,12 @@ pub async fn update_task(
     )
     .await?;
 
+    // Field omitted = keep existing; clients store e.g. the board order here
+    if let Some(metadata) = payload.metadata {
+        Task::set_metadata(&deployment.db().pool, task.id, &metadata).await?;
+        task.metadata = Some(metadata);
+    }
+
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::delete_by_task_id(&deployment.db().pool, task.id).await?;
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
@@ -293,58 +329,71 @@ pub async fn delete_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<(StatusCode, ResponseJson<ApiResponse<()>>), ApiError> {
-    let pool = &deployment.db().pool;
-
-    // Gather task attempts data needed for background cleanup
-    let attempts = Workspace::fetch_all(pool, Some(task.id))
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch task attempts for task {}: {}", task.id, e);
-            ApiError::Workspace(e)
-        })?;
-
-    // Stop any running execution processes before deletion
-    for workspace in &attempts {
-        deployment.container().try_stop(workspace, true).await;
-    }
+    let attempt_count = task_trash::purge_task(deployment.container(), task.id).await?;
 
-    let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
+    deployment
+        .track_if_analytics_allowed(
+            "task_deleted",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "attempt_count": attempt_count,
+            }),
+        )
+        .await;
 
-    // Collect workspace directories that need cleanup
-    let workspace_dirs: Vec<PathBuf> = attempts
-        .iter()
-        .filter_map(|attempt| attempt.container_ref.as_ref().map(PathBuf::from))
-        .collect();
+    // Return 202 Accepted to indicate cleanup was scheduled
+    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+}
 
-    // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
-    let mut tx = pool.begin().await?;
+/// Move the task to the trash. Anything running in its workspaces is stopped,
+/// and it can be restored with them until it is deleted for good.
+pub async fn trash_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TrashedTask>>, ApiError> {
+    let trashed = task_trash::trash_task(deployment.container(), task.id)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Task is already in the trash".to_string()))?;
 
-    // Nullify parent_workspace_id for all child tasks before deletion
-    // This breaks parent-child relationships to avoid foreign key constraint violations
-    let mut total_children_affected = 0u64;
-    for attempt in &attempts {
-        let children_affected =
-            Task::nullify_children_by_workspace_id(&mut *tx, attempt.id).await?;
-        total_children_affected += children_affected;
-    }
+    deployment
+        .track_if_analytics_allowed(
+            "task_trashed",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
 
-    // Delete task from database (FK CASCADE will handle task_attempts)
-    let rows_affected = Task::delete(&mut *tx, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(trashed)))
+}
 
-    if rows_affected == 0 {
+/// Take a task out of the trash, back onto the board with its workspaces.
+pub async fn restore_task(
+    Path(task_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if !TrashedTask::restore(pool, task_id).await? {
         return Err(ApiError::Database(SqlxError::RowNotFound));
     }
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
 
-    // Commit the transaction - if this fails, all changes are rolled back
-    tx.commit().await?;
-
-    if total_children_affected > 0 {
-        tracing::info!(
-            "Nullified {} child task references before deleting task {}",
-            total_children_affected,
-            task.id
-        );
-    }
+/// Delete a task in the trash for good, with its workspaces. Their worktrees
+/// are removed in the background.
+pub async fn purge_task(
+    Path(task_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<(StatusCode, ResponseJson<ApiResponse<()>>), ApiError> {
+    let task = TrashedTask::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    let attempt_count = task_trash::purge_task(deployment.container(), task.id).await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -352,54 +401,44 @@ pub async fn delete_task(
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": task.project_id.to_string(),
-                "attempt_count": attempts.len(),
+                "attempt_count": attempt_count,
             }),
         )
         .await;
 
-    let task_id = task.id;
-    let pool = pool.clone();
-    tokio::spawn(async move {
-        tracing::info!(
-            "Starting background cleanup for task {} ({} workspaces, {} repos)",
-            task_id,
-            workspace_dirs.len(),
-            repositories.len()
-        );
-
-        for workspace_dir in &workspace_dirs {
-            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
-            {
-                tracing::error!(
-                    "Background workspace cleanup failed for task {} at {}: {}",
-                    task_id,
-                    workspace_dir.display(),
-                    e
-                );
-            }
-        }
-
-        match Repo::delete_orphaned(&pool).await {
-            Ok(count) if count > 0 => {
-                tracing::info!("Deleted {} orphaned repo records", count);
-            }
-            Err(e) => {
-                tracing::error!("Failed to delete orphaned repos: {}", e);
-            }
-            _ => {}
-        }
+    // Return 202 Accepted to indicate cleanup was scheduled
+    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+}
 
-        tracing::info!("Background cleanup completed for task {}", task_id);
-    });
+/// Save the order of a board column arranged by hand, in one go. Nothing is
+/// saved if one of the tasks isn't on the project's board.
+pub async fn reorder_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderTasks>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let saved =
+        Task::set_board_positions(&deployment.db().pool, payload.project_id, &payload.task_ids)
+            .await?;
+    if !saved {
+        return Err(ApiError::Database(SqlxError::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
 
-    // Return 202 Accepted to indicate deletion was scheduled
-    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+/// The project's tasks in the trash, most recently deleted first
+pub async fn get_trash(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TrashedTask>>>, ApiError> {
+    let tasks = TrashedTask::find_by_project_id(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
 }
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
-        .route("/", delete(delete_task));
+        .route("/", delete(delete_task))
+        .route("/trash", post(trash_task));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
@@ -409,6 +448,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
+        .route("/reorder", post(reorder_tasks))
+        .route("/trash", get(get_trash))
+        .route("/trash/{task_id}", delete(purge_task))
+        .route("/trash/{task_id}/restore", post(restore_task))
         .route("/create-and-start", post(create_task_and_start))
         .route("/stats/all-projects", get(get_all_projects_task_stats))
         .nest("/{task_id}", task_id_router);
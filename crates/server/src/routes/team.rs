@@ -3,6 +3,7 @@
 //! Provides endpoints for managing agent teams, including:
 //! - Creating and managing team executions
 //! - Task planning and decomposition
+//! - Reusable plan templates
 //! - Agent skill and profile management
 
 use axum::{
@@ -13,14 +14,23 @@ use axum::{
 use db::models::{
     agent_profile::{AgentProfile, CreateAgentProfile, UpdateAgentProfile},
     agent_skill::{AgentSkill, CreateAgentSkill, UpdateAgentSkill},
+    plan_template::{CreatePlanTemplate, PlanTemplate},
     task::Task,
     team_execution::{TeamExecution, TeamPlanOutput},
     team_task::{TeamProgress, TeamTask},
 };
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
+use services::services::{
+    config::save_config_to_file,
+    team::{
+        DefaultProfileService, PlannerConfig, PlannerService, QueuedTeamTask,
+        skills::ProjectSkillSuggestions,
+    },
+};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
+use utils::assets::config_path;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -47,14 +57,25 @@ pub struct TeamPlanResponse {
     pub plan: TeamPlanOutput,
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct PlanFromTemplateRequest {
+    pub template_id: Uuid,
+}
+
 // ============== Routes ==============
 
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     Router::new()
         // Team Execution routes
         .route("/teams", post(create_team_execution))
+        .route("/teams/queue", get(get_queue))
+        .route(
+            "/teams/planner-config",
+            get(get_planner_config).put(update_planner_config),
+        )
         .route("/teams/{id}", get(get_team_execution))
         .route("/teams/{id}/plan", post(generate_plan))
+        .route("/teams/{id}/plan-from-template", post(plan_from_template))
         .route("/teams/{id}/execute", post(execute_plan))
         .route("/teams/{id}/progress", get(get_progress))
         .route("/teams/{id}/pause", post(pause_execution))
@@ -64,6 +85,15 @@ pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/teams/{id}/tasks", get(get_team_tasks))
         .route("/teams/tasks/{task_id}/complete", post(complete_task))
         .route("/teams/tasks/{task_id}/fail", post(fail_task))
+        // Plan Template routes
+        .route(
+            "/plan-templates",
+            get(list_plan_templates).post(create_plan_template),
+        )
+        .route(
+            "/plan-templates/{id}",
+            get(get_plan_template).delete(delete_plan_template),
+        )
         // Agent Skills routes
         .route("/agent-skills", get(list_skills).post(create_skill))
         .route(
@@ -72,11 +102,16 @@ pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         // Agent Profiles routes
         .route("/agent-profiles", get(list_profiles).post(create_profile))
+        .route("/agent-profiles/defaults", post(seed_default_profiles))
         .route(
             "/agent-profiles/{id}",
             get(get_profile).put(update_profile).delete(delete_profile),
         )
         .route("/agent-profiles/{id}/skills", get(get_profile_skills))
+        .route(
+            "/projects/{project_id}/skill-suggestions",
+            get(get_skill_suggestions),
+        )
         .route(
             "/agent-profiles/{id}/skills/{skill_id}",
             post(add_profile_skill).delete(remove_profile_skill),
@@ -86,6 +121,12 @@ pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/tasks/{task_id}/set-epic", post(set_task_epic))
 }
 
+/// Planner using the limits from the server config
+async fn planner_service(deployment: &DeploymentImpl) -> PlannerService {
+    let config = deployment.config().read().await.planner.clone();
+    PlannerService::with_config(deployment.db().pool.clone(), config)
+}
+
 // ============== Team Execution Handlers ==============
 
 async fn create_team_execution(
@@ -104,7 +145,7 @@ async fn create_team_execution(
         Task::set_epic(pool, req.epic_task_id, true).await?;
     }
 
-    let planner = services::services::team::PlannerService::new(pool.clone());
+    let planner = planner_service(&deployment).await;
     let execution = planner
         .create_team_execution(req.epic_task_id, req.workspace_id, req.max_parallel_workers)
         .await
@@ -113,6 +154,21 @@ async fn create_team_execution(
     Ok(Json(execution))
 }
 
+/// Tasks across all executing teams that are waiting for a worker slot
+async fn get_queue(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<Vec<QueuedTeamTask>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let manager = services::services::team::TeamManager::new(pool.clone());
+
+    let queue = manager
+        .queued_tasks()
+        .await
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(queue))
+}
+
 async fn get_team_execution(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -138,7 +194,7 @@ async fn generate_plan(
     Path(id): Path<Uuid>,
 ) -> Result<Json<TeamPlanResponse>, ApiError> {
     let pool = &deployment.db().pool;
-    let planner = services::services::team::PlannerService::new(pool.clone());
+    let planner = planner_service(&deployment).await;
 
     let plan = planner
         .generate_plan(id)
@@ -152,6 +208,26 @@ async fn generate_plan(
     Ok(Json(TeamPlanResponse { execution, plan }))
 }
 
+async fn plan_from_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<PlanFromTemplateRequest>,
+) -> Result<Json<TeamPlanResponse>, ApiError> {
+    let pool = &deployment.db().pool;
+    let planner = planner_service(&deployment).await;
+
+    let plan = planner
+        .plan_from_template(id, req.template_id)
+        .await
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let execution = TeamExecution::find_by_id(pool, id)
+        .await?
+        .ok_or_else(|| ApiError::Database(SqlxError::RowNotFound))?;
+
+    Ok(Json(TeamPlanResponse { execution, plan }))
+}
+
 async fn execute_plan(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -168,7 +244,7 @@ async fn execute_plan(
         .ok_or_else(|| ApiError::BadRequest("No plan generated yet".into()))
         .and_then(|p| serde_json::from_str(p).map_err(|e| ApiError::BadRequest(e.to_string())))?;
 
-    let planner = services::services::team::PlannerService::new(pool.clone());
+    let planner = planner_service(&deployment).await;
     let tasks = planner
         .execute_plan(id, &plan)
         .await
@@ -243,6 +319,30 @@ async fn cancel_execution(
     Ok(Json(execution))
 }
 
+// ============== Planner Config Handlers ==============
+
+/// Limits the planner applies when decomposing epics
+async fn get_planner_config(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<PlannerConfig>, ApiError> {
+    Ok(Json(deployment.config().read().await.planner.clone()))
+}
+
+/// Replace the planner limits and save them to the config file
+async fn update_planner_config(
+    State(deployment): State<DeploymentImpl>,
+    Json(req): Json<PlannerConfig>,
+) -> Result<Json<PlannerConfig>, ApiError> {
+    req.validate().map_err(ApiError::BadRequest)?;
+
+    let mut config = deployment.config().read().await.clone();
+    config.planner = req.clone();
+    save_config_to_file(&config, &config_path()).await?;
+    deployment.config().write().await.planner = req.clone();
+
+    Ok(Json(req))
+}
+
 // ============== Team Tasks Handlers ==============
 
 async fn get_team_tasks(
@@ -298,6 +398,51 @@ async fn fail_task(
     Ok(Json(task))
 }
 
+// ============== Plan Template Handlers ==============
+
+async fn list_plan_templates(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<Vec<PlanTemplate>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let templates = PlanTemplate::find_all(pool).await?;
+    Ok(Json(templates))
+}
+
+async fn get_plan_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PlanTemplate>, ApiError> {
+    let pool = &deployment.db().pool;
+    let template = PlanTemplate::find_by_id(pool, id)
+        .await?
+        .ok_or_else(|| ApiError::Database(SqlxError::RowNotFound))?;
+    Ok(Json(template))
+}
+
+/// Save the plan of a completed team execution as a template
+async fn create_plan_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(req): Json<CreatePlanTemplate>,
+) -> Result<Json<PlanTemplate>, ApiError> {
+    let planner = planner_service(&deployment).await;
+
+    let template = planner
+        .save_plan_template(req.team_execution_id, req.name)
+        .await
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(template))
+}
+
+async fn delete_plan_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<bool>, ApiError> {
+    let pool = &deployment.db().pool;
+    let deleted = PlanTemplate::delete(pool, id).await?;
+    Ok(Json(deleted > 0))
+}
+
 // ============== Agent Skills Handlers ==============
 
 async fn list_skills(
@@ -357,6 +502,18 @@ async fn list_profiles(
     Ok(Json(profiles))
 }
 
+/// Create default profiles for the team roles no active profile fills.
+/// Returns the profiles created; team executions do the same on creation.
+async fn seed_default_profiles(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<Vec<AgentProfile>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let profiles = DefaultProfileService::new(pool.clone())
+        .seed_missing()
+        .await?;
+    Ok(Json(profiles))
+}
+
 async fn get_profile(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -430,6 +587,17 @@ async fn remove_profile_skill(
     Ok(Json(true))
 }
 
+/// Skills a project's repositories call for, and which worker profiles lack them
+async fn get_skill_suggestions(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectSkillSuggestions>, ApiError> {
+    let pool = &deployment.db().pool;
+    let service = services::services::team::SkillInferenceService::new(pool.clone());
+    let suggestions = service.suggest_for_project(project_id).await?;
+    Ok(Json(suggestions))
+}
+
 // ============== Epic Tasks Handlers ==============
 
 async fn list_epic_tasks(
@@ -0,0 +1,194 @@
+//! Classification of failed attempts.
+//!
+//! Failures are classified once, when the server sees them: from the run reason
+//! and the tail of the output for failed runs, from the limit that was hit for
+//! runs stopped by resource limits, and from the operation for rebases that ran
+//! into conflicts.
+
+use db::models::{
+    execution_process::ExecutionProcessRunReason,
+    workspace_failure::{FailureCategory, WorkspaceFailure},
+    workspace_resource_limits::ResourceLimitKind,
+};
+use sqlx::SqlitePool;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+/// How many of the last log messages are searched for known failures
+const OUTPUT_TAIL_MESSAGES: usize = 200;
+
+/// Longest detail kept with a failure, in characters
+const MAX_DETAIL_CHARS: usize = 200;
+
+/// What agents and their providers print when they are turned away for sending
+/// too many requests
+const RATE_LIMIT_MARKERS: &[&str] = &[
+    "rate limit",
+    "rate_limit",
+    "ratelimit",
+    "too many requests",
+    "status code 429",
+    "error 429",
+    "usage limit",
+    "quota exceeded",
+    "resource_exhausted",
+    "overloaded",
+];
+
+/// What git prints when a merge or rebase stops on conflicts
+const MERGE_CONFLICT_MARKERS: &[&str] = &[
+    "merge conflict",
+    "conflict (content)",
+    "conflict (add/add)",
+    "conflict (modify/delete)",
+    "unmerged files",
+    "fix conflicts and then commit",
+];
+
+/// Category of a run that exited with an error, or `None` for runs that are not
+/// part of an attempt (cleanup scripts, dev servers).
+pub fn classify(run_reason: &ExecutionProcessRunReason, output: &str) -> Option<FailureCategory> {
+    match run_reason {
+        ExecutionProcessRunReason::SetupScript => Some(FailureCategory::SetupScript),
+        ExecutionProcessRunReason::CodingAgent => {
+            let output = output.to_lowercase();
+            let mentions = |markers: &[&str]| markers.iter().any(|m| output.contains(m));
+            Some(if mentions(RATE_LIMIT_MARKERS) {
+                FailureCategory::RateLimit
+            } else if mentions(MERGE_CONFLICT_MARKERS) {
+                FailureCategory::MergeConflict
+            } else {
+                FailureCategory::AgentCrash
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Category of a run stopped for exceeding a resource limit
+pub fn classify_exceeded_limit(kind: ResourceLimitKind) -> FailureCategory {
+    match kind {
+        ResourceLimitKind::Runtime | ResourceLimitKind::Cpu => FailureCategory::Timeout,
+        ResourceLimitKind::Memory => FailureCategory::AgentCrash,
+    }
+}
+
+/// The text of the last log messages, oldest first. Coding agents print JSON on
+/// stdout, so `stderr_only` leaves it out for something to show a person.
+pub fn output_tail(history: &[LogMsg], stderr_only: bool) -> String {
+    let start = history.len().saturating_sub(OUTPUT_TAIL_MESSAGES);
+    history[start..]
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stdout(text) if !stderr_only => Some(text.as_str()),
+            LogMsg::Stderr(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The last non-empty line of `output`, shortened, to show with a failure
+pub fn detail_from_output(output: &str) -> Option<String> {
+    let line = output.lines().map(str::trim).rfind(|l| !l.is_empty())?;
+    if line.chars().count() > MAX_DETAIL_CHARS {
+        let kept: String = line.chars().take(MAX_DETAIL_CHARS - 1).collect();
+        Some(format!("{kept}…"))
+    } else {
+        Some(line.to_string())
+    }
+}
+
+/// Record a failure of a workspace. Errors are logged rather than returned, so
+/// that classifying a failure never gets in the way of handling it.
+pub async fn record(
+    pool: &SqlitePool,
+    workspace_id: Uuid,
+    execution_process_id: Option<Uuid>,
+    category: FailureCategory,
+    detail: Option<&str>,
+) {
+    tracing::info!(
+        "Workspace {} failed: {}{}",
+        workspace_id,
+        category,
+        detail.map(|d| format!(" ({d})")).unwrap_or_default()
+    );
+    if let Err(e) =
+        WorkspaceFailure::create(pool, workspace_id, execution_process_id, category, detail).await
+    {
+        tracing::error!(
+            "Failed to record failure of workspace {}: {}",
+            workspace_id,
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setup_script_failures_are_classified_by_run_reason() {
+        assert_eq!(
+            classify(&ExecutionProcessRunReason::SetupScript, "Too Many Requests"),
+            Some(FailureCategory::SetupScript)
+        );
+        assert_eq!(
+            classify(&ExecutionProcessRunReason::CleanupScript, ""),
+            None
+        );
+        assert_eq!(classify(&ExecutionProcessRunReason::DevServer, ""), None);
+    }
+
+    #[test]
+    fn coding_agent_failures_are_classified_by_output() {
+        let agent = ExecutionProcessRunReason::CodingAgent;
+        assert_eq!(
+            classify(
+                &agent,
+                r#"{"type":"error","error":{"type":"rate_limit_error"}}"#
+            ),
+            Some(FailureCategory::RateLimit)
+        );
+        assert_eq!(
+            classify(&agent, "Claude AI usage limit reached|1760000000"),
+            Some(FailureCategory::RateLimit)
+        );
+        assert_eq!(
+            classify(&agent, "CONFLICT (content): Merge conflict in src/lib.rs"),
+            Some(FailureCategory::MergeConflict)
+        );
+        assert_eq!(
+            classify(&agent, "thread 'main' panicked at src/main.rs:3:5"),
+            Some(FailureCategory::AgentCrash)
+        );
+        assert_eq!(classify(&agent, ""), Some(FailureCategory::AgentCrash));
+    }
+
+    #[test]
+    fn detail_is_the_last_non_empty_line() {
+        assert_eq!(
+            detail_from_output("npm ERR! code 1\n  npm ERR! missing script: build  \n\n"),
+            Some("npm ERR! missing script: build".to_string())
+        );
+        assert_eq!(detail_from_output(" \n"), None);
+
+        let detail = detail_from_output(&"x".repeat(500)).unwrap();
+        assert_eq!(detail.chars().count(), MAX_DETAIL_CHARS);
+        assert!(detail.ends_with('…'));
+    }
+
+    #[test]
+    fn output_tail_keeps_only_text_messages() {
+        let history = vec![
+            LogMsg::Stdout("building".to_string()),
+            LogMsg::SessionId("abc".to_string()),
+            LogMsg::Stderr("error: failed".to_string()),
+            LogMsg::Finished,
+        ];
+        assert_eq!(output_tail(&history, false), "building\nerror: failed");
+        assert_eq!(output_tail(&history, true), "error: failed");
+    }
+}
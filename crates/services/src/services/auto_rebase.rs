@@ -0,0 +1,299 @@
+//! Scheduled rebasing of long-lived workspaces.
+//!
+//! A workspace can opt in to being kept rebased onto its target branches, either
+//! nightly or whenever a target branch moves. Rebases only run while no coding
+//! agent is working in the workspace and its worktrees are clean. A rebase that
+//! hits conflicts is aborted, leaving the branch as it was, and the policy is
+//! paused with a notification until it is resumed.
+
+use std::{path::Path, time::Duration};
+
+use chrono::{DateTime, TimeZone};
+use db::models::{
+    execution_process::ExecutionProcess,
+    protected_branch::ProtectedBranch,
+    workspace::Workspace,
+    workspace_auto_rebase::{AutoRebaseTrigger, WorkspaceAutoRebase},
+    workspace_failure::FailureCategory,
+    workspace_repo::WorkspaceRepo,
+};
+use tokio::{task::JoinHandle, time::interval};
+use tracing::{debug, error, info, warn};
+
+use crate::services::{
+    attempt_failure,
+    container::{ContainerError, ContainerService},
+    git::GitServiceError,
+    protected_branch::ProtectedBranchViolation,
+};
+
+/// How often policies are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Local hour at which nightly rebases become due
+const NIGHTLY_HOUR: u32 = 2;
+
+/// Service to rebase workspaces that opted in onto their target branches
+pub struct AutoRebaseService<C> {
+    container: C,
+    poll_interval: Duration,
+}
+
+impl<C: ContainerService + Send + Sync + 'static> AutoRebaseService<C> {
+    pub fn spawn(container: C) -> JoinHandle<()> {
+        let service = Self {
+            container,
+            poll_interval: CHECK_INTERVAL,
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting auto-rebase service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_all_policies().await {
+                error!("Error checking auto-rebase policies: {}", e);
+            }
+        }
+    }
+
+    async fn check_all_policies(&self) -> Result<(), ContainerError> {
+        let pool = &self.container.db().pool;
+        let now = chrono::Local::now();
+        for policy in WorkspaceAutoRebase::find_active(pool).await? {
+            if !is_due(&policy, &now) {
+                continue;
+            }
+            if let Err(e) = self.check_workspace(&policy).await {
+                warn!(
+                    "Auto-rebase of workspace {} failed: {}",
+                    policy.workspace_id, e
+                );
+                WorkspaceAutoRebase::record_error(pool, policy.workspace_id, &e.to_string())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebase the workspace branch onto each target branch that has moved ahead of it
+    async fn check_workspace(&self, policy: &WorkspaceAutoRebase) -> Result<(), ContainerError> {
+        let pool = &self.container.db().pool;
+        let git = self.container.git();
+        let Some(workspace) = Workspace::find_by_id(pool, policy.workspace_id).await? else {
+            return Ok(());
+        };
+
+        // Don't move the branch under a running agent; try again on the next tick
+        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+            .await?
+        {
+            debug!(
+                "Workspace {} has running processes, postponing auto-rebase",
+                workspace.id
+            );
+            return Ok(());
+        }
+
+        let mut behind = Vec::new();
+        for repo in
+            WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?
+        {
+            let (_, commits_behind) =
+                git.get_branch_status(&repo.repo.path, &workspace.branch, &repo.target_branch)?;
+            if commits_behind > 0 {
+                behind.push(repo);
+            }
+        }
+        if behind.is_empty() {
+            WorkspaceAutoRebase::record_checked(pool, workspace.id, false).await?;
+            return Ok(());
+        }
+
+        // Nobody is around to confirm a rebase of a protected branch
+        for repo in &behind {
+            let rules = ProtectedBranch::find_by_repo_id(pool, repo.repo.id).await?;
+            if let Some(violation) =
+                ProtectedBranchViolation::for_branch(&rules, &workspace.branch, false)
+            {
+                let reason = format!(
+                    "Branch '{}' is protected (matches '{}')",
+                    violation.branch, violation.pattern
+                );
+                return self.pause(&workspace, &reason).await;
+            }
+        }
+
+        if !self.container.is_container_clean(&workspace).await? {
+            WorkspaceAutoRebase::record_error(
+                pool,
+                workspace.id,
+                "Workspace has uncommitted changes",
+            )
+            .await?;
+            return Ok(());
+        }
+
+        let container_ref = self.container.ensure_container_exists(&workspace).await?;
+        for repo in behind {
+            let worktree_path = Path::new(&container_ref).join(&repo.repo.name);
+            match git.rebase_branch(
+                &repo.repo.path,
+                &worktree_path,
+                &repo.target_branch,
+                &repo.target_branch,
+                &workspace.branch,
+            ) {
+                Ok(_) => info!(
+                    "Rebased workspace {} onto {} in {}",
+                    workspace.id, repo.target_branch, repo.repo.name
+                ),
+                Err(GitServiceError::MergeConflicts(_)) => {
+                    git.abort_conflicts(&worktree_path)?;
+                    let reason = format!(
+                        "Rebasing onto {} in {} ran into conflicts",
+                        repo.target_branch, repo.repo.name
+                    );
+                    attempt_failure::record(
+                        pool,
+                        workspace.id,
+                        None,
+                        FailureCategory::MergeConflict,
+                        Some(&reason),
+                    )
+                    .await;
+                    return self.pause(&workspace, &reason).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        WorkspaceAutoRebase::record_checked(pool, workspace.id, true).await?;
+        Ok(())
+    }
+
+    async fn pause(&self, workspace: &Workspace, reason: &str) -> Result<(), ContainerError> {
+        let pool = &self.container.db().pool;
+        warn!(
+            "Pausing auto-rebase of workspace {}: {}",
+            workspace.id, reason
+        );
+        WorkspaceAutoRebase::pause(pool, workspace.id, reason).await?;
+
+        let task_title = workspace
+            .parent_task(pool)
+            .await?
+            .map(|task| task.title)
+            .unwrap_or_else(|| workspace.branch.clone());
+        self.container
+            .notification_service()
+            .notify(
+                "Auto-rebase paused",
+                &format!("{task_title}: {reason}. Resume auto-rebase once resolved."),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+/// Whether a policy should be checked at `now`. Nightly policies are checked once
+/// per night, counting from when they were last checked or changed.
+pub fn is_due<Tz: TimeZone>(policy: &WorkspaceAutoRebase, now: &DateTime<Tz>) -> bool {
+    if policy.is_paused() {
+        return false;
+    }
+    match policy.trigger {
+        AutoRebaseTrigger::TargetMoved => true,
+        AutoRebaseTrigger::Nightly => {
+            let since = policy
+                .last_checked_at
+                .map_or(policy.updated_at, |checked| checked.max(policy.updated_at));
+            next_nightly_run(&since.with_timezone(&now.timezone())).is_some_and(|run| run <= *now)
+        }
+    }
+}
+
+/// The first nightly run after `after`, in its time zone.
+pub fn next_nightly_run<Tz: TimeZone>(after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let tz = after.timezone();
+    // The hour may not exist on a day the clocks change; move on to the next day
+    after
+        .date_naive()
+        .iter_days()
+        .take(3)
+        .filter_map(|day| {
+            tz.from_local_datetime(&day.and_hms_opt(NIGHTLY_HOUR, 0, 0)?)
+                .earliest()
+        })
+        .find(|run| run > after)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn policy(trigger: AutoRebaseTrigger, updated_at: &str) -> WorkspaceAutoRebase {
+        WorkspaceAutoRebase {
+            workspace_id: Uuid::new_v4(),
+            trigger,
+            paused_reason: None,
+            paused_at: None,
+            last_error: None,
+            last_checked_at: None,
+            last_rebased_at: None,
+            created_at: at(updated_at),
+            updated_at: at(updated_at),
+        }
+    }
+
+    #[test]
+    fn next_nightly_run_is_the_next_occurrence_of_the_hour() {
+        assert_eq!(
+            next_nightly_run(&at("2026-10-17T01:00:00Z")),
+            Some(at("2026-10-17T02:00:00Z"))
+        );
+        assert_eq!(
+            next_nightly_run(&at("2026-10-17T02:00:00Z")),
+            Some(at("2026-10-18T02:00:00Z"))
+        );
+        assert_eq!(
+            next_nightly_run(&at("2026-10-17T15:30:00Z")),
+            Some(at("2026-10-18T02:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn nightly_policy_is_due_once_per_night() {
+        let mut nightly = policy(AutoRebaseTrigger::Nightly, "2026-10-17T15:00:00Z");
+        assert!(!is_due(&nightly, &at("2026-10-17T23:00:00Z")));
+        assert!(is_due(&nightly, &at("2026-10-18T02:05:00Z")));
+
+        nightly.last_checked_at = Some(at("2026-10-18T02:05:00Z"));
+        assert!(!is_due(&nightly, &at("2026-10-18T12:00:00Z")));
+        assert!(is_due(&nightly, &at("2026-10-19T02:00:00Z")));
+    }
+
+    #[test]
+    fn paused_policy_is_never_due() {
+        let mut moved = policy(AutoRebaseTrigger::TargetMoved, "2026-10-17T15:00:00Z");
+        assert!(is_due(&moved, &at("2026-10-17T15:01:00Z")));
+
+        moved.paused_at = Some(at("2026-10-17T15:05:00Z"));
+        assert!(!is_due(&moved, &at("2026-10-18T15:00:00Z")));
+    }
+}
@@ -0,0 +1,321 @@
+//! Per-project branch naming policies.
+//!
+//! A policy restricts the names of the branches created for a project's workspaces:
+//! they may have to start with one of a set of prefixes, stay under a maximum length
+//! and avoid certain characters. Names the server generates itself are quietly brought
+//! into line with the policy; names a user asks for are rejected with the violations
+//! and a compliant suggestion.
+
+use std::fmt;
+
+use db::models::project_branch_policy::{ProjectBranchPolicy, SetProjectBranchPolicy};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Characters trimmed from the end of a shortened name
+const TRAILING_SEPARATORS: &[char] = &['-', '_', '/', '.'];
+
+/// One way in which a branch name breaks a project's policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum BranchPolicyViolation {
+    MissingPrefix { allowed_prefixes: Vec<String> },
+    TooLong { length: usize, max_length: usize },
+    ForbiddenCharacters { characters: String },
+}
+
+impl fmt::Display for BranchPolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BranchPolicyViolation::MissingPrefix { allowed_prefixes } => {
+                write!(f, "must start with one of: {}", allowed_prefixes.join(", "))
+            }
+            BranchPolicyViolation::TooLong { length, max_length } => write!(
+                f,
+                "is {} characters long, the limit is {}",
+                length, max_length
+            ),
+            BranchPolicyViolation::ForbiddenCharacters { characters } => {
+                write!(f, "contains forbidden characters: {}", characters)
+            }
+        }
+    }
+}
+
+/// A rejected branch name, with what is wrong with it and a name that would pass
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BranchPolicyReport {
+    pub branch: String,
+    pub violations: Vec<BranchPolicyViolation>,
+    pub suggested_branch: String,
+}
+
+impl BranchPolicyReport {
+    /// Check `branch` against `policy`, returning `None` if it complies. `fallback`
+    /// names the suggestion if nothing of `branch` survives, see [`conform`].
+    pub fn for_branch(policy: &ProjectBranchPolicy, branch: &str, fallback: &str) -> Option<Self> {
+        let violations = check(policy, branch);
+        if violations.is_empty() {
+            return None;
+        }
+        Some(Self {
+            branch: branch.to_string(),
+            violations,
+            suggested_branch: conform(policy, branch, fallback),
+        })
+    }
+}
+
+impl fmt::Display for BranchPolicyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let violations = self
+            .violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        write!(
+            f,
+            "Branch '{}' violates the project's naming policy ({}). Try '{}' instead.",
+            self.branch, violations, self.suggested_branch
+        )
+    }
+}
+
+/// Everything about `branch` that breaks `policy`.
+pub fn check(policy: &ProjectBranchPolicy, branch: &str) -> Vec<BranchPolicyViolation> {
+    let mut violations = Vec::new();
+
+    if !policy.allowed_prefixes.is_empty()
+        && !policy
+            .allowed_prefixes
+            .iter()
+            .any(|prefix| branch.starts_with(prefix.as_str()))
+    {
+        violations.push(BranchPolicyViolation::MissingPrefix {
+            allowed_prefixes: policy.allowed_prefixes.to_vec(),
+        });
+    }
+
+    let length = branch.chars().count();
+    if let Some(max_length) = max_length(policy)
+        && length > max_length
+    {
+        violations.push(BranchPolicyViolation::TooLong { length, max_length });
+    }
+
+    let mut characters = String::new();
+    for c in branch.chars() {
+        if policy.forbidden_chars.contains(c) && !characters.contains(c) {
+            characters.push(c);
+        }
+    }
+    if !characters.is_empty() {
+        violations.push(BranchPolicyViolation::ForbiddenCharacters { characters });
+    }
+
+    violations
+}
+
+/// The closest name to `branch` that satisfies `policy`: forbidden characters become
+/// hyphens (or are dropped if hyphens are forbidden too), the first allowed prefix is
+/// added if none is present, and the name is cut to the maximum length. If nothing
+/// is left after the prefix, `fallback` (usually the short task id) takes its place.
+pub fn conform(policy: &ProjectBranchPolicy, branch: &str, fallback: &str) -> String {
+    let replacement = (!policy.forbidden_chars.contains('-')).then_some('-');
+    let sanitize = |name: &str| -> String {
+        name.chars()
+            .filter_map(|c| {
+                if policy.forbidden_chars.contains(c) {
+                    replacement
+                } else {
+                    Some(c)
+                }
+            })
+            .collect()
+    };
+    let sanitized = sanitize(branch);
+
+    let (prefix, rest) = match policy
+        .allowed_prefixes
+        .iter()
+        .find(|prefix| sanitized.starts_with(prefix.as_str()))
+    {
+        Some(prefix) => (prefix.as_str(), &sanitized[prefix.len()..]),
+        None => (
+            policy.allowed_prefixes.first().map_or("", String::as_str),
+            sanitized.as_str(),
+        ),
+    };
+
+    let fit = |rest: &str| match max_length(policy) {
+        Some(max_length) => {
+            let room = max_length.saturating_sub(prefix.chars().count());
+            if rest.chars().count() > room {
+                let cut: String = rest.chars().take(room).collect();
+                cut.trim_end_matches(TRAILING_SEPARATORS).to_string()
+            } else {
+                rest.to_string()
+            }
+        }
+        None => rest.to_string(),
+    };
+    let mut rest = fit(rest);
+    if rest.trim_matches(TRAILING_SEPARATORS).is_empty() {
+        rest = fit(&sanitize(fallback));
+    }
+
+    format!("{}{}", prefix, rest)
+}
+
+/// Reject policies that no branch name could satisfy.
+pub fn validate(policy: &SetProjectBranchPolicy) -> Result<(), String> {
+    if let Some(max_length) = policy.max_length
+        && max_length < 1
+    {
+        return Err("max_length must be at least 1".to_string());
+    }
+    for prefix in &policy.allowed_prefixes {
+        if prefix.is_empty() {
+            return Err("Allowed prefixes must not be empty".to_string());
+        }
+        if let Some(c) = prefix.chars().find(|c| policy.forbidden_chars.contains(*c)) {
+            return Err(format!(
+                "Prefix '{}' contains the forbidden character '{}'",
+                prefix, c
+            ));
+        }
+        if let Some(max_length) = policy.max_length
+            && prefix.chars().count() as i64 >= max_length
+        {
+            return Err(format!(
+                "Prefix '{}' leaves no room for a branch name within {} characters",
+                prefix, max_length
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn max_length(policy: &ProjectBranchPolicy) -> Option<usize> {
+    policy.max_length.map(|max| max.max(0) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn policy(prefixes: &[&str], max_length: Option<i64>, forbidden: &str) -> ProjectBranchPolicy {
+        ProjectBranchPolicy {
+            project_id: Uuid::new_v4(),
+            allowed_prefixes: Json(prefixes.iter().map(|p| p.to_string()).collect()),
+            max_length,
+            forbidden_chars: forbidden.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn compliant_branch_has_no_violations() {
+        let policy = policy(&["feature/", "fix/"], Some(30), "_");
+        assert!(check(&policy, "fix/login-redirect").is_empty());
+        assert!(BranchPolicyReport::for_branch(&policy, "fix/login-redirect", "1a2b").is_none());
+        assert!(check(&self::policy(&[], None, ""), "anything goes").is_empty());
+    }
+
+    #[test]
+    fn every_violation_is_reported() {
+        let policy = policy(&["feature/"], Some(10), "_ ");
+        assert_eq!(
+            check(&policy, "vk/my_long branch"),
+            vec![
+                BranchPolicyViolation::MissingPrefix {
+                    allowed_prefixes: vec!["feature/".to_string()],
+                },
+                BranchPolicyViolation::TooLong {
+                    length: 17,
+                    max_length: 10,
+                },
+                BranchPolicyViolation::ForbiddenCharacters {
+                    characters: "_ ".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn suggestion_satisfies_the_policy() {
+        let policy = policy(&["feature/", "fix/"], Some(20), "_");
+        assert_eq!(
+            conform(&policy, "vk/1a2b-add_login", "1a2b"),
+            "feature/vk/1a2b-add"
+        );
+        assert_eq!(conform(&policy, "fix/some_bug", "1a2b"), "fix/some-bug");
+
+        for branch in ["vk/1a2b-add_login", "fix/a_very_long_bug_name_here", "x"] {
+            let suggested = conform(&policy, branch, "1a2b");
+            assert!(check(&policy, &suggested).is_empty(), "{}", suggested);
+        }
+    }
+
+    #[test]
+    fn forbidden_hyphen_is_dropped() {
+        let policy = policy(&[], None, "-_");
+        assert_eq!(
+            conform(&policy, "vk/1a2b-add_login", "1a2b"),
+            "vk/1a2baddlogin"
+        );
+    }
+
+    #[test]
+    fn empty_name_falls_back() {
+        let policy = policy(&["feature/"], Some(20), "_");
+        assert_eq!(conform(&policy, "feature/", "1a2b"), "feature/1a2b");
+        assert_eq!(conform(&policy, "___", "1a2b"), "feature/1a2b");
+        assert_eq!(
+            conform(&self::policy(&[], None, "-_"), "__", "1a2b"),
+            "1a2b"
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_policies_are_rejected() {
+        let valid = SetProjectBranchPolicy {
+            allowed_prefixes: vec!["feature/".to_string()],
+            max_length: Some(40),
+            forbidden_chars: "_".to_string(),
+        };
+        assert!(validate(&valid).is_ok());
+        assert!(validate(&SetProjectBranchPolicy::default()).is_ok());
+
+        let zero_length = SetProjectBranchPolicy {
+            max_length: Some(0),
+            ..valid.clone()
+        };
+        assert!(validate(&zero_length).is_err());
+
+        let empty_prefix = SetProjectBranchPolicy {
+            allowed_prefixes: vec![String::new()],
+            ..valid.clone()
+        };
+        assert!(validate(&empty_prefix).is_err());
+
+        let forbidden_prefix = SetProjectBranchPolicy {
+            allowed_prefixes: vec!["my_feature/".to_string()],
+            ..valid.clone()
+        };
+        assert!(validate(&forbidden_prefix).is_err());
+
+        let no_room = SetProjectBranchPolicy {
+            max_length: Some(8),
+            ..valid
+        };
+        assert!(validate(&no_room).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Per-project cost budgets.
+//!
+//! Spend is estimated from coding agent runtime: each executor has an approximate hourly
+//! rate, and the runtime of every coding agent process started this month is priced
+//! against it. The estimate is deliberately coarse, it only needs to be good enough to
+//! warn before a project runs away with its monthly allowance.
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use db::models::project_budget::ProjectBudget;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Percentage of the cap at which projects enter the warning state
+pub const BUDGET_WARNING_PERCENT: f64 = 80.0;
+
+/// Fallback hourly rate for executors without a dedicated estimate
+const DEFAULT_HOURLY_RATE_CENTS: i64 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetState {
+    /// No budget configured for the project
+    Unlimited,
+    Ok,
+    /// Spend has crossed the warning threshold
+    Warning,
+    /// Spend has reached the cap; new attempts are blocked
+    Exceeded,
+    /// Cap reached but lifted for the current month
+    Overridden,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExecutorSpend {
+    pub executor: String,
+    pub runtime_seconds: i64,
+    pub estimated_cost_cents: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectBudgetStatus {
+    pub project_id: Uuid,
+    /// Month the figures apply to (YYYY-MM)
+    pub month: String,
+    pub monthly_cap_cents: Option<i64>,
+    pub spent_cents: i64,
+    pub percent_used: Option<f64>,
+    pub state: BudgetState,
+    pub by_executor: Vec<ExecutorSpend>,
+}
+
+impl ProjectBudgetStatus {
+    pub fn blocks_new_attempts(&self) -> bool {
+        self.state == BudgetState::Exceeded
+    }
+}
+
+/// Approximate cost of one hour of agent runtime, in cents
+pub fn hourly_rate_cents(executor: &str) -> i64 {
+    match executor {
+        "CLAUDE_CODE" | "AMP" => 600,
+        "CODEX" | "CURSOR_AGENT" | "DROID" => 500,
+        "GEMINI" | "QWEN_CODE" | "COPILOT" | "OPENCODE" => 300,
+        _ => DEFAULT_HOURLY_RATE_CENTS,
+    }
+}
+
+pub fn estimate_cost_cents(executor: &str, runtime_seconds: i64) -> i64 {
+    (runtime_seconds.max(0) * hourly_rate_cents(executor) + 1800) / 3600
+}
+
+pub fn month_key(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m").to_string()
+}
+
+fn month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+fn budget_state(spent_cents: i64, cap_cents: Option<i64>, overridden: bool) -> BudgetState {
+    let Some(cap) = cap_cents else {
+        return BudgetState::Unlimited;
+    };
+    if spent_cents >= cap {
+        if overridden {
+            BudgetState::Overridden
+        } else {
+            BudgetState::Exceeded
+        }
+    } else if (spent_cents as f64) >= cap as f64 * BUDGET_WARNING_PERCENT / 100.0 {
+        BudgetState::Warning
+    } else {
+        BudgetState::Ok
+    }
+}
+
+/// Compute the current month's spend and budget state for a project
+pub async fn project_budget_status(
+    pool: &SqlitePool,
+    project_id: Uuid,
+) -> Result<ProjectBudgetStatus, sqlx::Error> {
+    let now = Utc::now();
+    let month = month_key(now);
+    let budget = ProjectBudget::find_by_project_id(pool, project_id).await?;
+    let runtimes = ProjectBudget::runtime_by_executor(pool, project_id, month_start(now)).await?;
+
+    let by_executor: Vec<ExecutorSpend> = runtimes
+        .into_iter()
+        .map(|r| {
+            let executor = r.executor.unwrap_or_else(|| "UNKNOWN".to_string());
+            ExecutorSpend {
+                estimated_cost_cents: estimate_cost_cents(&executor, r.runtime_seconds),
+                runtime_seconds: r.runtime_seconds,
+                executor,
+            }
+        })
+        .collect();
+    let spent_cents = by_executor.iter().map(|s| s.estimated_cost_cents).sum();
+
+    let monthly_cap_cents = budget.as_ref().map(|b| b.monthly_cap_cents);
+    let overridden = budget
+        .as_ref()
+        .and_then(|b| b.override_month.as_deref())
+        .is_some_and(|m| m == month);
+    let percent_used = monthly_cap_cents
+        .filter(|cap| *cap > 0)
+        .map(|cap| spent_cents as f64 / cap as f64 * 100.0);
+
+    Ok(ProjectBudgetStatus {
+        project_id,
+        month,
+        monthly_cap_cents,
+        spent_cents,
+        percent_used,
+        state: budget_state(spent_cents, monthly_cap_cents, overridden),
+        by_executor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_follows_thresholds() {
+        assert_eq!(budget_state(100, None, false), BudgetState::Unlimited);
+        assert_eq!(budget_state(79, Some(100), false), BudgetState::Ok);
+        assert_eq!(budget_state(80, Some(100), false), BudgetState::Warning);
+        assert_eq!(budget_state(100, Some(100), false), BudgetState::Exceeded);
+        assert_eq!(budget_state(150, Some(100), true), BudgetState::Overridden);
+        assert_eq!(budget_state(0, Some(0), false), BudgetState::Exceeded);
+    }
+
+    #[test]
+    fn cost_is_rounded_to_nearest_cent() {
+        assert_eq!(estimate_cost_cents("CLAUDE_CODE", 3600), 600);
+        assert_eq!(estimate_cost_cents("CLAUDE_CODE", 0), 0);
+        assert_eq!(estimate_cost_cents("SOMETHING_NEW", 1800), 200);
+    }
+}
@@ -7,7 +7,10 @@ pub use v7::{
     ThemeMode, UiLanguage,
 };
 
-use crate::services::config::versions::v7;
+use crate::services::{
+    config::versions::v7, retention::RetentionPolicy, team::PlannerConfig,
+    user_preferences::UserPreferences,
+};
 
 fn default_git_branch_prefix() -> String {
     "vk".to_string()
@@ -56,6 +59,15 @@ pub struct Config {
     pub commit_reminder: bool,
     #[serde(default)]
     pub send_message_shortcut: SendMessageShortcut,
+    /// Limits the team planner applies when decomposing epics
+    #[serde(default)]
+    pub planner: PlannerConfig,
+    /// When finished workspaces are archived or have their worktrees removed
+    #[serde(default)]
+    pub retention: RetentionPolicy,
+    /// Settings of the command-line client that follow the user across machines
+    #[serde(default)]
+    pub user_preferences: UserPreferences,
 }
 
 impl Config {
@@ -85,6 +97,9 @@ impl Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            planner: PlannerConfig::default(),
+            retention: RetentionPolicy::default(),
+            user_preferences: UserPreferences::default(),
         }
     }
 
@@ -139,6 +154,9 @@ impl Default for Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            planner: PlannerConfig::default(),
+            retention: RetentionPolicy::default(),
+            user_preferences: UserPreferences::default(),
         }
     }
 }
@@ -15,6 +15,7 @@ use db::{
             ExecutionProcessRunReason, ExecutionProcessStatus,
         },
         execution_process_logs::ExecutionProcessLogs,
+        execution_process_orphan::{ExecutionProcessOrphan, OrphanReason},
         execution_process_repo_state::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
@@ -51,6 +52,7 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    attempt_failure,
     git::{GitService, GitServiceError},
     notification::NotificationService,
     workspace_manager::WorkspaceError as WorkspaceManagerError,
@@ -205,70 +207,111 @@ pub trait ContainerService {
                 process.id,
                 process.session_id
             );
-            // Update the execution process status first
-            if let Err(e) = ExecutionProcess::update_completion(
-                &self.db().pool,
-                process.id,
-                ExecutionProcessStatus::Failed,
-                None, // No exit code for orphaned processes
-            )
-            .await
-            {
+            self.fail_orphaned_execution(&process, OrphanReason::ServerRestart)
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Mark a process that is no longer actually running as failed, record why, and
+    /// move its task to review so it isn't shown as in progress forever. Returns
+    /// false, leaving it alone, if it stopped being marked running since it was read.
+    async fn fail_orphaned_execution(
+        &self,
+        process: &ExecutionProcess,
+        reason: OrphanReason,
+    ) -> bool {
+        let pool = &self.db().pool;
+        // Only a process that is still marked running is failed, so an exit
+        // recorded after `process` was read is kept
+        match ExecutionProcess::fail_if_running(pool, process.id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::debug!(
+                    "Execution process {} finished before it could be marked orphaned",
+                    process.id
+                );
+                return false;
+            }
+            Err(e) => {
                 tracing::error!(
                     "Failed to update orphaned execution process {} status: {}",
                     process.id,
                     e
                 );
-                continue;
+                return false;
             }
-            // Capture after-head commit OID per repository
-            if let Ok(ctx) = ExecutionProcess::load_context(&self.db().pool, process.id).await
-                && let Some(ref container_ref) = ctx.workspace.container_ref
-            {
-                let workspace_root = PathBuf::from(container_ref);
-                for repo in &ctx.repos {
-                    let repo_path = workspace_root.join(&repo.name);
-                    if let Ok(head) = self.git().get_head_info(&repo_path)
-                        && let Err(err) = ExecutionProcessRepoState::update_after_head_commit(
-                            &self.db().pool,
-                            process.id,
-                            repo.id,
-                            &head.oid,
-                        )
-                        .await
-                    {
-                        tracing::warn!(
-                            "Failed to update after_head_commit for repo {} on process {}: {}",
-                            repo.id,
-                            process.id,
-                            err
-                        );
-                    }
+        }
+        // Capture after-head commit OID per repository
+        if let Ok(ctx) = ExecutionProcess::load_context(pool, process.id).await
+            && let Some(ref container_ref) = ctx.workspace.container_ref
+        {
+            let workspace_root = PathBuf::from(container_ref);
+            for repo in &ctx.repos {
+                let repo_path = workspace_root.join(&repo.name);
+                if let Ok(head) = self.git().get_head_info(&repo_path)
+                    && let Err(err) = ExecutionProcessRepoState::update_after_head_commit(
+                        pool, process.id, repo.id, &head.oid,
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to update after_head_commit for repo {} on process {}: {}",
+                        repo.id,
+                        process.id,
+                        err
+                    );
                 }
             }
-            // Process marked as failed
-            tracing::info!("Marked orphaned execution process {} as failed", process.id);
-            // Update task status to InReview for coding agent and setup script failures
-            if matches!(
-                process.run_reason,
-                ExecutionProcessRunReason::CodingAgent
-                    | ExecutionProcessRunReason::SetupScript
-                    | ExecutionProcessRunReason::CleanupScript
-            ) && let Ok(Some(session)) =
-                Session::find_by_id(&self.db().pool, process.session_id).await
-                && let Ok(Some(workspace)) =
-                    Workspace::find_by_id(&self.db().pool, session.workspace_id).await
-                && let Ok(Some(task)) = workspace.parent_task(&self.db().pool).await
-                && let Err(e) =
-                    Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await
-            {
-                tracing::error!(
-                    "Failed to update task status to InReview for orphaned session: {}",
-                    e
-                );
-            }
         }
-        Ok(())
+        // Process marked as failed
+        tracing::info!(
+            "Marked orphaned execution process {} as failed ({})",
+            process.id,
+            reason
+        );
+
+        let Ok(Some(session)) = Session::find_by_id(pool, process.session_id).await else {
+            return true;
+        };
+        let Ok(Some(workspace)) = Workspace::find_by_id(pool, session.workspace_id).await else {
+            return true;
+        };
+        let Ok(Some(task)) = workspace.parent_task(pool).await else {
+            return true;
+        };
+        if let Err(e) =
+            ExecutionProcessOrphan::create(pool, process.id, workspace.id, task.id, reason).await
+        {
+            tracing::error!(
+                "Failed to record orphaned execution process {}: {}",
+                process.id,
+                e
+            );
+        }
+        if let Some(category) = attempt_failure::classify(&process.run_reason, "") {
+            let detail = match reason {
+                OrphanReason::ServerRestart => "The server restarted while it was running",
+                OrphanReason::ProcessGone => "Its process is no longer running",
+                OrphanReason::ContainerMissing => "Its workspace directory no longer exists",
+            };
+            attempt_failure::record(pool, workspace.id, Some(process.id), category, Some(detail))
+                .await;
+        }
+        // Update task status to InReview for coding agent and setup script failures
+        if matches!(
+            process.run_reason,
+            ExecutionProcessRunReason::CodingAgent
+                | ExecutionProcessRunReason::SetupScript
+                | ExecutionProcessRunReason::CleanupScript
+        ) && let Err(e) = Task::update_status(pool, task.id, TaskStatus::InReview).await
+        {
+            tracing::error!(
+                "Failed to update task status to InReview for orphaned session: {}",
+                e
+            );
+        }
+        true
     }
 
     /// Backfill before_head_commit for legacy execution processes.
@@ -4,7 +4,7 @@ use db::{
     DBService,
     models::{
         execution_process::ExecutionProcess, project::Project, scratch::Scratch, session::Session,
-        task::Task, workspace::Workspace,
+        task::Task, task_trash::TrashedTask, workspace::Workspace,
     },
 };
 use serde_json::json;
@@ -282,17 +282,24 @@ impl EventService {
                                             task_list.into_iter().find(|t| t.id == task.id)
                                     {
                                         let patch = match hook.operation {
-                                            SqliteOperation::Insert => {
+                                            // An update may take the task out of the
+                                            // trash, which clients no longer have; `add`
+                                            // replaces the tasks they do have all the same
+                                            SqliteOperation::Insert | SqliteOperation::Update => {
                                                 task_patch::add(&task_with_status)
                                             }
-                                            SqliteOperation::Update => {
-                                                task_patch::replace(&task_with_status)
-                                            }
                                             _ => task_patch::replace(&task_with_status), // fallback
                                         };
                                         msg_store_for_hook.push_patch(patch);
                                         return;
                                     }
+                                    // Moved to the trash, so off the board
+                                    if let Ok(Some(trashed)) =
+                                        TrashedTask::find_by_id(&db.pool, task.id).await
+                                    {
+                                        msg_store_for_hook.push_patch(task_patch::remove(trashed.id));
+                                        return;
+                                    }
                                 }
                                 RecordTypes::DeletedTask {
                                     task_id: Some(task_id),
@@ -65,6 +65,42 @@ pub struct GitBranch {
     pub last_commit_date: DateTime<Utc>,
 }
 
+/// A commit that a merge, rebase or push would carry over
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PreviewCommit {
+    pub sha: String,
+    pub subject: String,
+}
+
+/// A commit of a workspace branch, for its commit log
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BranchCommit {
+    pub sha: String,
+    pub subject: String,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    #[ts(type = "Date")]
+    pub authored_at: DateTime<Utc>,
+}
+
+/// What a merge, rebase or push would do, worked out without moving any refs or
+/// touching a worktree
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GitOperationPreview {
+    /// Commits the operation would merge, replay or push, newest first
+    pub commits: Vec<PreviewCommit>,
+    /// Whether the destination only has to move forward: nothing to catch up on
+    /// before a merge, nothing to replay in a rebase, no remote commits lost in a push
+    pub fast_forward: bool,
+    /// Commits on the other side: new target commits for a merge, base commits picked
+    /// up by a rebase, remote commits a push would overwrite
+    pub behind: usize,
+    /// Files that would end up in conflict
+    pub conflicted_files: Vec<String>,
+    /// Why the operation itself would be refused, if it would be
+    pub blocker: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct HeadInfo {
     pub branch: String,
@@ -884,6 +920,132 @@ impl GitService {
             }
         }
     }
+
+    /// Work out what `merge_changes` would do without creating the squash commit.
+    pub fn preview_merge(
+        &self,
+        repo_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+    ) -> Result<GitOperationPreview, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let task_commit = Self::find_branch(&repo, task_branch_name)?
+            .get()
+            .peel_to_commit()?;
+        let base_commit = Self::find_branch(&repo, base_branch_name)?
+            .get()
+            .peel_to_commit()?;
+
+        let (_, behind) = repo.graph_ahead_behind(task_commit.id(), base_commit.id())?;
+        let commits = Self::commits_between(&repo, task_commit.id(), Some(base_commit.id()))?;
+        let conflicted_files = Self::conflicted_paths(&repo, &base_commit, &task_commit)?;
+        let blocker = (behind > 0).then(|| {
+            format!(
+                "'{base_branch_name}' is {behind} commits ahead of '{task_branch_name}'; rebase before merging"
+            )
+        });
+
+        Ok(GitOperationPreview {
+            commits,
+            fast_forward: behind == 0,
+            behind,
+            conflicted_files,
+            blocker,
+        })
+    }
+
+    /// Commits on `branch_name` that `base_branch_name` doesn't have, newest first.
+    pub fn get_commits_ahead(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch_name: &str,
+    ) -> Result<Vec<BranchCommit>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let tip = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let base = Self::find_branch(&repo, base_branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push(tip)?;
+        revwalk.hide(base)?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            let author = commit.author();
+            commits.push(BranchCommit {
+                sha: commit.id().to_string(),
+                subject: commit.summary().unwrap_or("(no subject)").to_string(),
+                author_name: author.name().unwrap_or("unknown").to_string(),
+                author_email: author.email().map(str::to_string),
+                authored_at: DateTime::from_timestamp(author.when().seconds(), 0)
+                    .unwrap_or_else(Utc::now),
+            });
+        }
+        Ok(commits)
+    }
+
+    /// Commits reachable from `tip` but not from `hide`, newest first.
+    fn commits_between(
+        repo: &Repository,
+        tip: git2::Oid,
+        hide: Option<git2::Oid>,
+    ) -> Result<Vec<PreviewCommit>, GitServiceError> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push(tip)?;
+        match hide {
+            Some(oid) => revwalk.hide(oid)?,
+            // Nothing to compare against: leave out whatever any remote already has
+            None => revwalk.hide_glob("refs/remotes/*")?,
+        }
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            commits.push(PreviewCommit {
+                sha: commit.id().to_string(),
+                subject: commit.summary().unwrap_or("(no subject)").to_string(),
+            });
+        }
+        Ok(commits)
+    }
+
+    /// Paths that an in-memory merge of `theirs` into `ours` leaves conflicted.
+    fn conflicted_paths(
+        repo: &Repository,
+        ours: &git2::Commit,
+        theirs: &git2::Commit,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let mut merge_opts = git2::MergeOptions::new();
+        merge_opts.find_renames(true);
+        let index = repo.merge_commits(ours, theirs, Some(&merge_opts))?;
+        Self::index_conflicts(&index)
+    }
+
+    fn index_conflicts(index: &git2::Index) -> Result<Vec<String>, GitServiceError> {
+        let mut files = Vec::new();
+        if !index.has_conflicts() {
+            return Ok(files);
+        }
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                files.push(String::from_utf8_lossy(&entry.path).into_owned());
+            }
+        }
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
     fn get_branch_status_inner(
         &self,
         repo: &Repository,
@@ -1423,6 +1585,81 @@ impl GitService {
         Ok(final_commit.id().to_string())
     }
 
+    /// Work out what `rebase_branch` would do by replaying the branch in memory.
+    /// Stops at the first commit that conflicts, as the real rebase would.
+    pub fn preview_rebase(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        new_base_branch: &str,
+        old_base_branch: &str,
+        task_branch: &str,
+    ) -> Result<GitOperationPreview, GitServiceError> {
+        let worktree_repo = Repository::open(worktree_path)?;
+        let main_repo = self.open_repo(repo_path)?;
+
+        let blocker = if GitCli::new()
+            .is_rebase_in_progress(worktree_path)
+            .unwrap_or(false)
+        {
+            Some(GitServiceError::RebaseInProgress.to_string())
+        } else {
+            self.check_worktree_clean(&worktree_repo)
+                .err()
+                .map(|e| e.to_string())
+        };
+
+        let nbr = Self::find_branch(&main_repo, new_base_branch)?.into_reference();
+        if nbr.is_remote() {
+            self.fetch_branch_from_remote(&main_repo, &nbr)?;
+        }
+        let new_base = nbr.peel_to_commit()?;
+        let old_base = Self::find_branch(&main_repo, old_base_branch)?
+            .get()
+            .peel_to_commit()?;
+        let task = Self::find_branch(&main_repo, task_branch)?
+            .get()
+            .peel_to_commit()?;
+
+        let upstream = main_repo.merge_base(old_base.id(), task.id())?;
+        let commits = Self::commits_between(&main_repo, task.id(), Some(upstream))?;
+        let (_, behind) = main_repo.graph_ahead_behind(task.id(), new_base.id())?;
+
+        let mut conflicted_files = Vec::new();
+        if !commits.is_empty() {
+            let branch = main_repo.find_annotated_commit(task.id())?;
+            let upstream = main_repo.find_annotated_commit(upstream)?;
+            let onto = main_repo.find_annotated_commit(new_base.id())?;
+            let mut opts = git2::RebaseOptions::new();
+            opts.inmemory(true);
+            let mut rebase =
+                main_repo.rebase(Some(&branch), Some(&upstream), Some(&onto), Some(&mut opts))?;
+            let signature = self.signature_with_fallback(&main_repo)?;
+            while let Some(operation) = rebase.next() {
+                operation?;
+                conflicted_files = Self::index_conflicts(&rebase.inmemory_index()?)?;
+                if !conflicted_files.is_empty() {
+                    break;
+                }
+                match rebase.commit(None, &signature, None) {
+                    Ok(_) => {}
+                    // Already in the new base; git skips these too
+                    Err(e) if e.code() == git2::ErrorCode::Applied => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            rebase.abort()?;
+        }
+
+        Ok(GitOperationPreview {
+            fast_forward: commits.is_empty(),
+            commits,
+            behind,
+            conflicted_files,
+            blocker,
+        })
+    }
+
     pub fn find_branch_type(
         &self,
         repo_path: &Path,
@@ -1711,6 +1948,59 @@ impl GitService {
         Ok(())
     }
 
+    /// Work out what `push_to_remote` would send, after fetching the remote branch.
+    pub fn preview_push(
+        &self,
+        worktree_path: &Path,
+        branch_name: &str,
+    ) -> Result<GitOperationPreview, GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let blocker = self
+            .check_worktree_clean(&repo)
+            .err()
+            .map(|e| e.to_string());
+
+        let remote_name = self.default_remote_name(&repo);
+        let remote = repo.find_remote(&remote_name)?;
+        let remote_url = remote
+            .url()
+            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
+        let remote_ref = format!("refs/remotes/{remote_name}/{branch_name}");
+        if GitCli::new().check_remote_branch_exists(worktree_path, remote_url, branch_name)? {
+            let refspec = format!("+refs/heads/{branch_name}:{remote_ref}");
+            self.fetch_from_remote(&repo, &remote, &refspec)?;
+        }
+
+        let local = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let remote_oid = repo
+            .find_reference(&remote_ref)
+            .ok()
+            .and_then(|r| r.target());
+        let commits = Self::commits_between(&repo, local, remote_oid)?;
+        let behind = match remote_oid {
+            Some(remote_oid) => repo.graph_ahead_behind(local, remote_oid)?.1,
+            None => 0,
+        };
+        let blocker = blocker.or_else(|| {
+            (behind > 0).then(|| {
+                format!(
+                    "The remote branch has {behind} commits not in '{branch_name}'; only a force push would go through, and it would discard them"
+                )
+            })
+        });
+
+        Ok(GitOperationPreview {
+            commits,
+            fast_forward: behind == 0,
+            behind,
+            conflicted_files: Vec::new(),
+            blocker,
+        })
+    }
+
     /// Fetch from remote repository using native git authentication
     fn fetch_from_remote(
         &self,
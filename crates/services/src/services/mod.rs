@@ -1,6 +1,10 @@
 pub mod analytics;
 pub mod approvals;
+pub mod attempt_failure;
 pub mod auth;
+pub mod auto_rebase;
+pub mod branch_policy;
+pub mod budget;
 pub mod config;
 pub mod container;
 pub mod diff_stream;
@@ -16,11 +20,15 @@ pub mod notification;
 pub mod oauth_credentials;
 pub mod pr_monitor;
 pub mod project;
+pub mod protected_branch;
 #[cfg(feature = "qa-mode")]
 pub mod qa_repos;
 pub mod queued_message;
 pub mod remote_client;
 pub mod repo;
+pub mod retention;
+pub mod task_trash;
 pub mod team;
+pub mod user_preferences;
 pub mod workspace_manager;
 pub mod worktree_manager;
@@ -0,0 +1,172 @@
+//! Protected branches of a repository.
+//!
+//! A repo can protect branches by name or glob (`main`, `release/*`). Merging into,
+//! pushing or rebasing a protected branch then needs an explicit confirmation from
+//! the caller, or is refused outright for patterns in block mode until the
+//! protection is lifted.
+
+use std::fmt;
+
+use db::models::protected_branch::{ProtectedBranch, ProtectionMode};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// An operation that would write to a protected branch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct ProtectedBranchViolation {
+    pub branch: String,
+    /// Pattern of the protection that applies
+    pub pattern: String,
+    pub mode: ProtectionMode,
+}
+
+impl ProtectedBranchViolation {
+    /// The protection an operation writing to `branch` runs into, if any; an
+    /// operation the caller confirmed only runs into blocking ones.
+    pub fn for_branch(rules: &[ProtectedBranch], branch: &str, confirmed: bool) -> Option<Self> {
+        let rule = strictest_match(rules, branch)?;
+        if confirmed && rule.mode == ProtectionMode::Confirm {
+            return None;
+        }
+        Some(Self {
+            branch: branch.to_string(),
+            pattern: rule.pattern.clone(),
+            mode: rule.mode,
+        })
+    }
+}
+
+impl fmt::Display for ProtectedBranchViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            ProtectionMode::Confirm => write!(
+                f,
+                "Branch '{}' is protected (matches '{}'); confirm to continue",
+                self.branch, self.pattern
+            ),
+            ProtectionMode::Block => write!(
+                f,
+                "Branch '{}' is protected (matches '{}') and cannot be changed from here. \
+                 Remove the protection to change it.",
+                self.branch, self.pattern
+            ),
+        }
+    }
+}
+
+/// The matching rule for `branch` that is hardest to get past, if any.
+pub fn strictest_match<'a>(
+    rules: &'a [ProtectedBranch],
+    branch: &str,
+) -> Option<&'a ProtectedBranch> {
+    rules
+        .iter()
+        .filter(|rule| matches(&rule.pattern, branch))
+        .max_by_key(|rule| rule.mode == ProtectionMode::Block)
+}
+
+/// Whether `branch` matches `pattern`, where `*` matches any run of characters
+/// other than `/` and `**` any run at all.
+pub fn matches(pattern: &str, branch: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let branch: Vec<char> = branch.chars().collect();
+    matches_from(&pattern, &branch)
+}
+
+fn matches_from(pattern: &[char], branch: &[char]) -> bool {
+    match pattern {
+        [] => branch.is_empty(),
+        ['*', '*', rest @ ..] => (0..=branch.len()).any(|i| matches_from(rest, &branch[i..])),
+        ['*', rest @ ..] => {
+            let segment_end = branch
+                .iter()
+                .position(|c| *c == '/')
+                .unwrap_or(branch.len());
+            (0..=segment_end).any(|i| matches_from(rest, &branch[i..]))
+        }
+        [c, rest @ ..] => branch.first() == Some(c) && matches_from(rest, &branch[1..]),
+    }
+}
+
+/// Reject patterns that are empty or could never match a branch.
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.trim().is_empty() {
+        return Err("Pattern must not be empty".to_string());
+    }
+    if pattern != pattern.trim() || pattern.contains(char::is_whitespace) {
+        return Err(format!(
+            "Pattern '{}' contains whitespace, which branch names cannot",
+            pattern
+        ));
+    }
+    if pattern.contains("***") {
+        return Err(format!(
+            "Pattern '{}' has more than two '*' in a row",
+            pattern
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn rule(pattern: &str, mode: ProtectionMode) -> ProtectedBranch {
+        ProtectedBranch {
+            id: Uuid::new_v4(),
+            repo_id: Uuid::new_v4(),
+            pattern: pattern.to_string(),
+            mode,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn globs_match_within_and_across_segments() {
+        assert!(matches("main", "main"));
+        assert!(!matches("main", "main2"));
+        assert!(matches("release/*", "release/1.2"));
+        assert!(!matches("release/*", "release/1.2/hotfix"));
+        assert!(!matches("release/*", "release"));
+        assert!(matches("release/**", "release/1.2/hotfix"));
+        assert!(matches("*-stable", "v2-stable"));
+        assert!(matches("**", "any/branch/at/all"));
+    }
+
+    #[test]
+    fn block_wins_over_confirm() {
+        let rules = [
+            rule("release/**", ProtectionMode::Confirm),
+            rule("release/*", ProtectionMode::Block),
+        ];
+        let strictest = strictest_match(&rules, "release/1.0").unwrap();
+        assert_eq!(strictest.mode, ProtectionMode::Block);
+        let strictest = strictest_match(&rules, "release/1.0/rc").unwrap();
+        assert_eq!(strictest.mode, ProtectionMode::Confirm);
+        assert!(strictest_match(&rules, "main").is_none());
+    }
+
+    #[test]
+    fn confirmation_only_gets_past_confirm_mode() {
+        let rules = [
+            rule("main", ProtectionMode::Confirm),
+            rule("prod", ProtectionMode::Block),
+        ];
+        assert!(ProtectedBranchViolation::for_branch(&rules, "main", false).is_some());
+        assert!(ProtectedBranchViolation::for_branch(&rules, "main", true).is_none());
+        assert!(ProtectedBranchViolation::for_branch(&rules, "prod", true).is_some());
+        assert!(ProtectedBranchViolation::for_branch(&rules, "vk/1a2b-fix", false).is_none());
+    }
+
+    #[test]
+    fn unusable_patterns_are_rejected() {
+        assert!(validate_pattern("release/*").is_ok());
+        assert!(validate_pattern("").is_err());
+        assert!(validate_pattern("release candidates").is_err());
+        assert!(validate_pattern("a/***").is_err());
+    }
+}
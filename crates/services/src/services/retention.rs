@@ -0,0 +1,210 @@
+//! Retention policies for finished workspaces and deleted tasks.
+//!
+//! The server can archive workspaces some days after all of their repositories
+//! were merged, and remove the worktrees of cancelled tasks as soon as nothing
+//! runs in them instead of after the usual idle period. Pinned workspaces are
+//! always left alone. It can also purge tasks some days after they went into
+//! the trash. All of these are off by default.
+
+use std::{sync::Arc, time::Duration};
+
+use db::models::{
+    task_trash::TrashedTask,
+    workspace::Workspace,
+    workspace_retention::{RetentionAction, RetentionCandidate},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tokio::{sync::RwLock, task::JoinHandle, time::interval};
+use tracing::{error, info, warn};
+use ts_rs::TS;
+
+use crate::services::{
+    config::Config,
+    container::{ContainerError, ContainerService},
+    task_trash,
+};
+
+/// How often the policy is applied
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Server-wide retention policy for workspaces, stored in the server config
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// Archive workspaces this many days after their last repository was merged
+    pub archive_merged_after_days: Option<u32>,
+    /// Remove the worktrees of cancelled tasks once nothing runs in them
+    pub delete_cancelled_worktrees: bool,
+    /// Delete tasks for good this many days after they went into the trash
+    pub purge_trash_after_days: Option<u32>,
+}
+
+impl RetentionPolicy {
+    /// Check that the archive and purge delays are at least a day.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.archive_merged_after_days == Some(0) {
+            return Err("archive_merged_after_days must be at least 1".to_string());
+        }
+        if self.purge_trash_after_days == Some(0) {
+            return Err("purge_trash_after_days must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.archive_merged_after_days.is_some()
+            || self.delete_cancelled_worktrees
+            || self.purge_trash_after_days.is_some()
+    }
+
+    /// The workspaces the policy applies to right now
+    pub async fn candidates(
+        &self,
+        pool: &SqlitePool,
+    ) -> Result<Vec<RetentionCandidate>, sqlx::Error> {
+        let mut candidates = Vec::new();
+        if let Some(days) = self.archive_merged_after_days {
+            candidates.extend(RetentionCandidate::find_merged_before(pool, days).await?);
+        }
+        if self.delete_cancelled_worktrees {
+            candidates.extend(RetentionCandidate::find_cancelled_with_worktree(pool).await?);
+        }
+        Ok(candidates)
+    }
+}
+
+/// Service to apply the retention policy from the server config
+pub struct RetentionService<C> {
+    container: C,
+    config: Arc<RwLock<Config>>,
+    poll_interval: Duration,
+}
+
+impl<C: ContainerService + Send + Sync + 'static> RetentionService<C> {
+    pub fn spawn(container: C, config: Arc<RwLock<Config>>) -> JoinHandle<()> {
+        let service = Self {
+            container,
+            config,
+            poll_interval: CHECK_INTERVAL,
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting retention service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.apply_policy().await {
+                error!("Error applying retention policy: {}", e);
+            }
+        }
+    }
+
+    async fn apply_policy(&self) -> Result<(), ContainerError> {
+        let policy = self.config.read().await.retention.clone();
+        if !policy.is_enabled() {
+            return Ok(());
+        }
+
+        let pool = &self.container.db().pool;
+        for candidate in policy.candidates(pool).await? {
+            match candidate.action {
+                RetentionAction::Archive => {
+                    info!(
+                        "Archiving workspace {} ({}), merged on {}",
+                        candidate.workspace_id, candidate.branch, candidate.since
+                    );
+                    Workspace::set_archived(pool, candidate.workspace_id, true).await?;
+                }
+                RetentionAction::DeleteWorktree => {
+                    let Some(workspace) =
+                        Workspace::find_by_id(pool, candidate.workspace_id).await?
+                    else {
+                        continue;
+                    };
+                    info!(
+                        "Removing worktrees of workspace {} ({}) of a cancelled task",
+                        workspace.id, workspace.branch
+                    );
+                    if let Err(e) = self.container.delete(&workspace).await {
+                        warn!(
+                            "Failed to remove worktrees of workspace {}: {}",
+                            workspace.id, e
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(days) = policy.purge_trash_after_days {
+            for task in TrashedTask::find_deleted_before(pool, days).await? {
+                info!(
+                    "Purging task {} ({}), in the trash since {}",
+                    task.id, task.title, task.deleted_at
+                );
+                if let Err(e) = task_trash::purge_task(&self.container, task.id).await {
+                    warn!("Failed to purge task {}: {}", task.id, e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_delay_must_be_at_least_a_day() {
+        let policy = RetentionPolicy {
+            archive_merged_after_days: Some(0),
+            delete_cancelled_worktrees: false,
+            purge_trash_after_days: None,
+        };
+        assert!(policy.validate().is_err());
+
+        let policy = RetentionPolicy {
+            archive_merged_after_days: Some(1),
+            ..policy
+        };
+        assert!(policy.validate().is_ok());
+        assert!(RetentionPolicy::default().validate().is_ok());
+    }
+
+    #[test]
+    fn purge_delay_must_be_at_least_a_day() {
+        let policy = RetentionPolicy {
+            purge_trash_after_days: Some(0),
+            ..Default::default()
+        };
+        assert!(policy.validate().is_err());
+
+        let policy = RetentionPolicy {
+            purge_trash_after_days: Some(30),
+            ..Default::default()
+        };
+        assert!(policy.validate().is_ok());
+        assert!(policy.is_enabled());
+    }
+
+    #[test]
+    fn default_policy_does_nothing() {
+        assert!(!RetentionPolicy::default().is_enabled());
+        assert!(
+            RetentionPolicy {
+                delete_cancelled_worktrees: true,
+                ..Default::default()
+            }
+            .is_enabled()
+        );
+    }
+}
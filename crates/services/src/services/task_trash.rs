@@ -0,0 +1,127 @@
+//! Deleting tasks.
+//!
+//! A task can be moved to the trash: whatever runs in its workspaces is stopped
+//! and it drops off the board, but it keeps its workspaces and their history
+//! until it is restored. Purging deletes it for good with its workspaces,
+//! straight away, by hand from the trash or once the retention policy's delay
+//! has passed, and removes their worktrees in the background.
+
+use std::path::PathBuf;
+
+use db::models::{
+    repo::Repo, task::Task, task_trash::TrashedTask, workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use sqlx::Error as SqlxError;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    workspace_manager::WorkspaceManager,
+};
+
+/// Move a task to the trash, stopping everything running in its workspaces.
+/// `None` if it is in the trash already.
+pub async fn trash_task<C: ContainerService + Sync>(
+    container: &C,
+    task_id: Uuid,
+) -> Result<Option<TrashedTask>, ContainerError> {
+    let pool = &container.db().pool;
+    for workspace in Workspace::find_by_task_id(pool, task_id).await? {
+        container.try_stop(&workspace, true).await;
+    }
+    Ok(TrashedTask::trash(pool, task_id).await?)
+}
+
+/// Delete a task for good, with its workspaces, whether it is in the trash or
+/// not. Their worktrees are removed in the background. Returns how many
+/// workspaces the task had.
+pub async fn purge_task<C: ContainerService + Sync>(
+    container: &C,
+    task_id: Uuid,
+) -> Result<usize, ContainerError> {
+    let pool = &container.db().pool;
+
+    // Gather task attempts data needed for background cleanup
+    let attempts = Workspace::find_by_task_id(pool, task_id).await?;
+
+    // Stop any running execution processes before deletion
+    for workspace in &attempts {
+        container.try_stop(workspace, true).await;
+    }
+
+    let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task_id).await?;
+
+    // Collect workspace directories that need cleanup
+    let workspace_dirs: Vec<PathBuf> = attempts
+        .iter()
+        .filter_map(|attempt| attempt.container_ref.as_ref().map(PathBuf::from))
+        .collect();
+
+    // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
+    let mut tx = pool.begin().await?;
+
+    // Nullify parent_workspace_id for all child tasks before deletion
+    // This breaks parent-child relationships to avoid foreign key constraint violations
+    let mut total_children_affected = 0u64;
+    for attempt in &attempts {
+        let children_affected =
+            Task::nullify_children_by_workspace_id(&mut *tx, attempt.id).await?;
+        total_children_affected += children_affected;
+    }
+
+    // Delete task from database (FK CASCADE will handle task_attempts)
+    let rows_affected = Task::delete(&mut *tx, task_id).await?;
+
+    if rows_affected == 0 {
+        return Err(ContainerError::Sqlx(SqlxError::RowNotFound));
+    }
+
+    // Commit the transaction - if this fails, all changes are rolled back
+    tx.commit().await?;
+
+    if total_children_affected > 0 {
+        info!(
+            "Nullified {} child task references before deleting task {}",
+            total_children_affected, task_id
+        );
+    }
+
+    let attempt_count = attempts.len();
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        info!(
+            "Starting background cleanup for task {} ({} workspaces, {} repos)",
+            task_id,
+            workspace_dirs.len(),
+            repositories.len()
+        );
+
+        for workspace_dir in &workspace_dirs {
+            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
+            {
+                error!(
+                    "Background workspace cleanup failed for task {} at {}: {}",
+                    task_id,
+                    workspace_dir.display(),
+                    e
+                );
+            }
+        }
+
+        match Repo::delete_orphaned(&pool).await {
+            Ok(count) if count > 0 => {
+                info!("Deleted {} orphaned repo records", count);
+            }
+            Err(e) => {
+                error!("Failed to delete orphaned repos: {}", e);
+            }
+            _ => {}
+        }
+
+        info!("Background cleanup completed for task {}", task_id);
+    });
+
+    Ok(attempt_count)
+}
@@ -3,19 +3,38 @@
 //! Orchestrates parallel execution of team tasks, managing agent assignment,
 //! workspace creation, and execution coordination.
 
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
 use db::models::{
     agent_profile::AgentProfile,
-    team_execution::{TeamExecution, TeamExecutionStatus},
+    execution_process::ExecutionProcess,
+    merge::Merge,
+    project_branch_policy::ProjectBranchPolicy,
+    project_repo::ProjectRepo,
+    repo::Repo,
+    team_execution::{TeamExecution, TeamExecutionStatus, TeamPlanOutput},
     team_task::{TeamProgress, TeamTask, TeamTaskStatus},
     task::{Task, TaskStatus},
     workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use thiserror::Error;
 use tokio::sync::mpsc;
+use ts_rs::TS;
+use utils::text::short_uuid;
 use uuid::Uuid;
 
+use crate::services::{
+    branch_policy::BranchPolicyReport,
+    git::{GitService, GitServiceError},
+};
+
+/// Assumed task duration when estimating start times before any task has completed
+const DEFAULT_TASK_DURATION_SECONDS: i64 = 20 * 60;
+
 #[derive(Debug, Error)]
 pub enum TeamError {
     #[error("Database error: {0}")]
@@ -58,6 +77,35 @@ pub enum TeamEvent {
     },
 }
 
+/// A team task waiting for a free worker slot
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QueuedTeamTask {
+    pub team_task_id: Uuid,
+    pub team_execution_id: Uuid,
+    pub task_id: Uuid,
+    /// Workspace from a previous run, set when the task was requeued for a retry
+    pub workspace_id: Option<Uuid>,
+    pub task_title: String,
+    /// 1-based position within the team execution's queue
+    pub position: i32,
+    pub running: i32,
+    pub max_parallel_workers: i32,
+    pub estimated_start_at: DateTime<Utc>,
+}
+
+/// A workspace moved off a merged upstream branch by `TeamManager::restack_after_merge`
+#[derive(Debug, Clone)]
+pub struct RestackedWorkspace {
+    pub team_task_id: Uuid,
+    pub workspace_id: Uuid,
+    pub branch: String,
+    /// Branch the workspace now builds on
+    pub base_branch: String,
+    /// Why the worktree was not rebased, e.g. an agent running in it or conflicts;
+    /// its target branch is updated regardless
+    pub rebase_error: Option<String>,
+}
+
 /// Configuration for the team manager
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamManagerConfig {
@@ -138,6 +186,73 @@ impl TeamManager {
         Ok(progress)
     }
 
+    /// List pending tasks of executing teams, in the order they will be picked up
+    pub async fn queued_tasks(&self) -> Result<Vec<QueuedTeamTask>, TeamError> {
+        let now = Utc::now();
+        let mut queue = Vec::new();
+
+        for execution in TeamExecution::find_active(&self.pool).await? {
+            if execution.status != TeamExecutionStatus::Executing {
+                continue;
+            }
+
+            let tasks = TeamTask::find_by_team_execution(&self.pool, execution.id).await?;
+            let durations: Vec<i64> = tasks
+                .iter()
+                .filter(|t| t.status == TeamTaskStatus::Completed)
+                .filter_map(|t| t.duration_seconds.map(i64::from))
+                .collect();
+            let average_duration = if durations.is_empty() {
+                DEFAULT_TASK_DURATION_SECONDS
+            } else {
+                durations.iter().sum::<i64>() / durations.len() as i64
+            };
+
+            let running_remaining: Vec<i64> = tasks
+                .iter()
+                .filter(|t| matches!(t.status, TeamTaskStatus::Running | TeamTaskStatus::Assigned))
+                .map(|t| {
+                    let elapsed = t
+                        .started_at
+                        .map(|started| (now - started).num_seconds())
+                        .unwrap_or(0);
+                    (average_duration - elapsed).max(0)
+                })
+                .collect();
+            let pending: Vec<&TeamTask> = tasks
+                .iter()
+                .filter(|t| t.status == TeamTaskStatus::Pending)
+                .collect();
+
+            let offsets = estimate_start_offsets(
+                execution.max_parallel_workers.max(1) as usize,
+                &running_remaining,
+                pending.len(),
+                average_duration,
+            );
+
+            for (index, (team_task, offset)) in pending.into_iter().zip(offsets).enumerate() {
+                let task_title = Task::find_by_id(&self.pool, team_task.task_id)
+                    .await?
+                    .map(|t| t.title)
+                    .unwrap_or_default();
+                queue.push(QueuedTeamTask {
+                    team_task_id: team_task.id,
+                    team_execution_id: execution.id,
+                    task_id: team_task.task_id,
+                    workspace_id: team_task.workspace_id,
+                    task_title,
+                    position: index as i32 + 1,
+                    running: running_remaining.len() as i32,
+                    max_parallel_workers: execution.max_parallel_workers,
+                    estimated_start_at: now + Duration::seconds(offset),
+                });
+            }
+        }
+
+        Ok(queue)
+    }
+
     /// Start executing ready tasks in a team
     pub async fn execute_ready_tasks(
         &self,
@@ -195,6 +310,27 @@ impl TeamManager {
         Ok(started_task_ids)
     }
 
+    /// Branch for a team task, following the project's branch policy if it has one
+    async fn branch_name(&self, team_task: &TeamTask, task: &Task) -> Result<String, TeamError> {
+        let branch_name = format!(
+            "{}/task-{}",
+            self.config.branch_prefix,
+            &team_task.id.to_string()[..8]
+        );
+        let Some(policy) =
+            ProjectBranchPolicy::find_by_project_id(&self.pool, task.project_id).await?
+        else {
+            return Ok(branch_name);
+        };
+        match BranchPolicyReport::for_branch(&policy, &branch_name, &short_uuid(&task.id)) {
+            Some(report) => {
+                tracing::warn!("{} Using the suggested name.", report);
+                Ok(report.suggested_branch)
+            }
+            None => Ok(branch_name),
+        }
+    }
+
     /// Start execution of a single task
     async fn start_task(&self, team_task: &TeamTask) -> Result<(), TeamError> {
         // Find an available agent with required skills
@@ -204,21 +340,17 @@ impl TeamManager {
         TeamTask::assign_agent(&self.pool, team_task.id, agent.id).await?;
 
         // Create workspace and branch for this task
-        let _execution = TeamExecution::find_by_id(&self.pool, team_task.team_execution_id)
+        let execution = TeamExecution::find_by_id(&self.pool, team_task.team_execution_id)
             .await?
             .ok_or(TeamError::ExecutionNotFound(team_task.team_execution_id))?;
 
-        let branch_name = format!(
-            "{}/task-{}",
-            self.config.branch_prefix,
-            &team_task.id.to_string()[..8]
-        );
-
         // Get the task for this team task
         let task = Task::find_by_id(&self.pool, team_task.task_id)
             .await?
             .ok_or(TeamError::TaskNotFound(team_task.task_id))?;
 
+        let branch_name = self.branch_name(team_task, &task).await?;
+
         // Create workspace
         let workspace = Workspace::create(
             &self.pool,
@@ -232,6 +364,9 @@ impl TeamManager {
         .await
         .map_err(|e| TeamError::ExecutionFailed(e.to_string()))?;
 
+        let repos = self.workspace_repos(&execution, team_task, &task).await?;
+        WorkspaceRepo::create_many(&self.pool, workspace.id, &repos).await?;
+
         // Update team task with workspace info
         TeamTask::set_workspace(&self.pool, team_task.id, workspace.id, &branch_name).await?;
         TeamTask::start(&self.pool, team_task.id).await?;
@@ -248,6 +383,243 @@ impl TeamManager {
         Ok(())
     }
 
+    /// Repositories for a team task's workspace: those its planned subtask is scoped
+    /// to, or else all of the epic workspace's (or the project's) repositories.
+    /// Repositories with unmerged work from the task it depends on target that
+    /// task's branch, stacking the two; the others target the epic workspace's
+    /// branch where it has the repository, so subtasks build on the epic's work.
+    async fn workspace_repos(
+        &self,
+        execution: &TeamExecution,
+        team_task: &TeamTask,
+        task: &Task,
+    ) -> Result<Vec<CreateWorkspaceRepo>, TeamError> {
+        let plan: Option<TeamPlanOutput> = execution
+            .planner_output
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok());
+        let scoped_names: Vec<String> = plan
+            .and_then(|plan| {
+                let index = team_task.sequence_order as usize;
+                plan.subtasks.into_iter().nth(index)
+            })
+            .map(|subtask| {
+                subtask
+                    .repo_scopes
+                    .into_iter()
+                    .map(|s| s.repo_name)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let epic_workspace = match execution.epic_workspace_id {
+            Some(id) => Workspace::find_by_id(&self.pool, id).await?,
+            None => None,
+        };
+        let epic_repos = match &epic_workspace {
+            Some(workspace) => {
+                WorkspaceRepo::find_repos_for_workspace(&self.pool, workspace.id).await?
+            }
+            None => Vec::new(),
+        };
+
+        let project_repos =
+            ProjectRepo::find_repos_for_project(&self.pool, task.project_id).await?;
+        let scoped: Vec<_> = project_repos
+            .iter()
+            .filter(|repo| scoped_names.contains(&repo.name))
+            .cloned()
+            .collect();
+        // Scopes naming repositories that have since been removed fall back to the defaults
+        let repos = if !scoped.is_empty() {
+            scoped
+        } else if !epic_repos.is_empty() {
+            epic_repos.clone()
+        } else {
+            project_repos
+        };
+
+        let upstream = self.stack_upstream(team_task).await?;
+        let git = GitService::new();
+        Ok(repos
+            .into_iter()
+            .map(|repo| {
+                // The upstream branch only exists once its workspace has been set up
+                let stacked = upstream.as_ref().filter(|(workspace, repo_ids)| {
+                    repo_ids.contains(&repo.id)
+                        && git
+                            .check_branch_exists(&repo.path, &workspace.branch)
+                            .unwrap_or(false)
+                });
+                let target_branch = match (stacked, &epic_workspace) {
+                    (Some((workspace, _)), _) => workspace.branch.clone(),
+                    (None, Some(workspace)) if epic_repos.iter().any(|r| r.id == repo.id) => {
+                        workspace.branch.clone()
+                    }
+                    _ => git
+                        .get_current_branch(&repo.path)
+                        .unwrap_or_else(|_| "main".to_string()),
+                };
+                CreateWorkspaceRepo {
+                    repo_id: repo.id,
+                    target_branch,
+                }
+            })
+            .collect())
+    }
+
+    /// Workspace of the task a team task stacks on, with the repositories it has not
+    /// merged yet. Only a task with a single dependency stacks: a branch cannot build
+    /// on several others, so tasks joining parallel work build on the epic instead.
+    async fn stack_upstream(
+        &self,
+        team_task: &TeamTask,
+    ) -> Result<Option<(Workspace, Vec<Uuid>)>, TeamError> {
+        let dependencies = team_task.get_dependencies();
+        let [dependency_id] = dependencies.as_slice() else {
+            return Ok(None);
+        };
+        let workspace_id = TeamTask::find_by_id(&self.pool, *dependency_id)
+            .await?
+            .and_then(|task| task.workspace_id);
+        let Some(workspace) = (match workspace_id {
+            Some(id) => Workspace::find_by_id(&self.pool, id).await?,
+            None => None,
+        }) else {
+            return Ok(None);
+        };
+
+        let mut repo_ids = Vec::new();
+        for repo in WorkspaceRepo::find_repos_for_workspace(&self.pool, workspace.id).await? {
+            let merges =
+                Merge::find_by_workspace_and_repo_id(&self.pool, workspace.id, repo.id).await?;
+            if !merges.iter().any(|m| matches!(m, Merge::Direct(_))) {
+                repo_ids.push(repo.id);
+            }
+        }
+        Ok(Some((workspace, repo_ids)))
+    }
+
+    /// Move the tasks stacked on a workspace's branch onto `new_base` after the
+    /// branch merged into it, rebasing their worktrees. Tasks stacked on those are
+    /// rebased in turn so the whole stack follows; worktrees that haven't been
+    /// created yet only have their target branch updated. Worktrees with an agent
+    /// running in them are left alone, and a rebase that runs into conflicts is
+    /// aborted, so either can be rebased by hand later.
+    pub async fn restack_after_merge(
+        &self,
+        workspace: &Workspace,
+        repo: &Repo,
+        new_base: &str,
+    ) -> Result<Vec<RestackedWorkspace>, TeamError> {
+        let Some(merged_task) = TeamTask::find_by_workspace_id(&self.pool, workspace.id).await?
+        else {
+            return Ok(Vec::new());
+        };
+        let tasks =
+            TeamTask::find_by_team_execution(&self.pool, merged_task.team_execution_id).await?;
+        let git = GitService::new();
+
+        let mut restacked = Vec::new();
+        // (upstream task, its branch, branch to rebase onto, revision the stack was based on)
+        let mut pending = vec![(
+            merged_task.id,
+            workspace.branch.clone(),
+            new_base.to_string(),
+            workspace.branch.clone(),
+        )];
+        while let Some((upstream_id, upstream_branch, onto, old_base)) = pending.pop() {
+            for task in tasks
+                .iter()
+                .filter(|t| t.get_dependencies().contains(&upstream_id))
+            {
+                let Some(workspace_id) = task.workspace_id else {
+                    continue;
+                };
+                let workspace_repo =
+                    WorkspaceRepo::find_by_workspace_and_repo_id(&self.pool, workspace_id, repo.id)
+                        .await?;
+                if workspace_repo.is_none_or(|r| r.target_branch != upstream_branch) {
+                    continue;
+                }
+                let Some(dependent) = Workspace::find_by_id(&self.pool, workspace_id).await? else {
+                    continue;
+                };
+
+                if onto != upstream_branch {
+                    WorkspaceRepo::update_target_branch(&self.pool, workspace_id, repo.id, &onto)
+                        .await?;
+                }
+                // Without a worktree there is nothing to rebase yet
+                let old_head = git.get_branch_oid(&repo.path, &dependent.branch).ok();
+                let rebase = match (&dependent.container_ref, old_head) {
+                    (Some(container_ref), Some(old_head)) => {
+                        let worktree_path = Path::new(container_ref).join(&repo.name);
+                        // Don't move the branch under a running agent
+                        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                            &self.pool,
+                            workspace_id,
+                        )
+                        .await?
+                        {
+                            Some(Err("A coding agent is running in the workspace".to_string()))
+                        } else {
+                            Some(
+                                match git.rebase_branch(
+                                    &repo.path,
+                                    &worktree_path,
+                                    &onto,
+                                    &old_base,
+                                    &dependent.branch,
+                                ) {
+                                    Ok(_) => Ok(old_head),
+                                    // Leave the worktree as it was rather than half rebased
+                                    Err(GitServiceError::MergeConflicts(e)) => {
+                                        if let Err(e) = git.abort_conflicts(&worktree_path) {
+                                            tracing::error!(
+                                                "Failed to abort the rebase of {}: {}",
+                                                dependent.branch,
+                                                e
+                                            );
+                                        }
+                                        Err(format!(
+                                            "Rebasing onto {} ran into conflicts: {}",
+                                            onto, e
+                                        ))
+                                    }
+                                    Err(e) => Err(e.to_string()),
+                                },
+                            )
+                        }
+                    }
+                    _ => None,
+                };
+                match &rebase {
+                    // Tasks on this branch follow it to where it was rebased
+                    Some(Ok(old_head)) => pending.push((
+                        task.id,
+                        dependent.branch.clone(),
+                        dependent.branch.clone(),
+                        old_head.clone(),
+                    )),
+                    Some(Err(e)) => {
+                        tracing::warn!("Failed to restack {}: {}", dependent.branch, e);
+                    }
+                    None => {}
+                }
+
+                restacked.push(RestackedWorkspace {
+                    team_task_id: task.id,
+                    workspace_id,
+                    branch: dependent.branch,
+                    base_branch: onto.clone(),
+                    rebase_error: rebase.and_then(Result::err),
+                });
+            }
+        }
+        Ok(restacked)
+    }
+
     /// Find the best available agent for a task based on required skills
     async fn find_best_agent(&self, team_task: &TeamTask) -> Result<AgentProfile, TeamError> {
         let required_skills = team_task.get_required_skills();
@@ -448,3 +820,42 @@ impl TeamManager {
         Ok(())
     }
 }
+
+/// Simulate worker slots to estimate, in seconds from now, when each queued task starts.
+/// Each slot is busy until its running task is expected to finish; queued tasks then
+/// take the earliest free slot and occupy it for `task_duration`.
+fn estimate_start_offsets(
+    slots: usize,
+    running_remaining: &[i64],
+    queued: usize,
+    task_duration: i64,
+) -> Vec<i64> {
+    let slots = slots.max(1);
+    let mut free_at = vec![0; slots.max(running_remaining.len())];
+    free_at[..running_remaining.len()].copy_from_slice(running_remaining);
+    free_at.sort_unstable();
+    // With more tasks running than slots, a slot only frees once the surplus has finished
+    let surplus = free_at.len() - slots;
+    free_at.drain(..surplus);
+
+    (0..queued)
+        .map(|_| {
+            let start = free_at[0];
+            free_at[0] = start + task_duration;
+            free_at.sort_unstable();
+            start
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_offsets_fill_earliest_free_slot() {
+        assert_eq!(estimate_start_offsets(2, &[], 3, 60), vec![0, 0, 60]);
+        assert_eq!(estimate_start_offsets(2, &[30], 3, 60), vec![0, 30, 60]);
+        assert_eq!(estimate_start_offsets(1, &[10, 40], 2, 60), vec![40, 100]);
+    }
+}
@@ -1,5 +1,9 @@
 pub mod manager;
 pub mod planner;
+pub mod profiles;
+pub mod skills;
 
-pub use manager::TeamManager;
-pub use planner::PlannerService;
+pub use manager::{QueuedTeamTask, RestackedWorkspace, TeamManager};
+pub use planner::{PlannerConfig, PlannerService};
+pub use profiles::DefaultProfileService;
+pub use skills::SkillInferenceService;
@@ -6,6 +6,7 @@
 
 use db::models::{
     agent_profile::AgentProfile,
+    plan_template::PlanTemplate,
     team_execution::{CreateTeamExecution, PlannedSubtask, TeamExecution, TeamExecutionStatus, TeamPlanOutput},
     team_task::{CreateTeamTask, TeamTask},
     task::{CreateTask, Task, TaskComplexity, TaskStatus},
@@ -13,8 +14,11 @@ use db::models::{
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use thiserror::Error;
+use ts_rs::TS;
 use uuid::Uuid;
 
+use super::{profiles::DefaultProfileService, skills::SkillInferenceService};
+
 #[derive(Debug, Error)]
 pub enum PlannerError {
     #[error("Database error: {0}")]
@@ -29,12 +33,19 @@ pub enum PlannerError {
     PlanningFailed(String),
     #[error("Invalid plan output: {0}")]
     InvalidPlanOutput(String),
+    #[error("Plan template not found: {0}")]
+    TemplateNotFound(Uuid),
+    #[error("Only completed team executions can be saved as templates")]
+    ExecutionNotCompleted,
+    #[error("Team execution has already started")]
+    ExecutionAlreadyStarted,
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 }
 
-/// Configuration for the planner service
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Configuration for the planner service, stored in the server config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default)]
 pub struct PlannerConfig {
     /// Minimum subtasks to trigger team execution
     pub team_threshold: i32,
@@ -54,6 +65,22 @@ impl Default for PlannerConfig {
     }
 }
 
+impl PlannerConfig {
+    /// Check that every limit is at least one.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("team_threshold", self.team_threshold),
+            ("max_subtasks", self.max_subtasks),
+            ("max_parallel_workers", self.max_parallel_workers),
+        ] {
+            if value < 1 {
+                return Err(format!("{name} must be at least 1"));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Service for planning and decomposing epic tasks
 pub struct PlannerService {
     pool: SqlitePool,
@@ -133,6 +160,9 @@ impl PlannerService {
             return Err(PlannerError::NotEpicTask);
         }
 
+        // Fill any role left without a profile, e.g. on a fresh install
+        DefaultProfileService::new(self.pool.clone()).seed_missing().await?;
+
         // Find a planner agent
         let planners = AgentProfile::find_planners(&self.pool).await?;
         let planner = planners.first().ok_or(PlannerError::NoPlannerAgent)?;
@@ -170,7 +200,12 @@ impl PlannerService {
 
         // Generate plan based on task analysis
         // This is a simplified version - in production, invoke the team manager agent
-        let plan = self.decompose_task(&task).await?;
+        let mut plan = self.decompose_task(&task).await?;
+
+        // Tailor the required skills to what the project's repositories use
+        SkillInferenceService::new(self.pool.clone())
+            .apply_to_subtasks(task.project_id, &mut plan.subtasks)
+            .await?;
 
         // Save plan output
         let plan_json = serde_json::to_string(&plan)?;
@@ -180,13 +215,89 @@ impl PlannerService {
         Ok(plan)
     }
 
+    /// Save the plan of a completed team execution as a reusable template,
+    /// with the epic title replaced by a placeholder
+    pub async fn save_plan_template(
+        &self,
+        team_execution_id: Uuid,
+        name: Option<String>,
+    ) -> Result<PlanTemplate, PlannerError> {
+        let execution = TeamExecution::find_by_id(&self.pool, team_execution_id)
+            .await?
+            .ok_or(PlannerError::PlanningFailed("Execution not found".into()))?;
+
+        if execution.status != TeamExecutionStatus::Completed {
+            return Err(PlannerError::ExecutionNotCompleted);
+        }
+
+        let plan_json = execution
+            .planner_output
+            .as_deref()
+            .ok_or(PlannerError::InvalidPlanOutput("Execution has no plan".into()))?;
+        let plan: TeamPlanOutput = serde_json::from_str(plan_json)?;
+
+        let task = Task::find_by_id(&self.pool, execution.epic_task_id)
+            .await?
+            .ok_or(PlannerError::TaskNotFound(execution.epic_task_id))?;
+
+        let template_plan = replace_epic_title(&plan, &task.title, PlanTemplate::EPIC_TITLE);
+        let name = name
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or(task.title);
+
+        Ok(PlanTemplate::create(&self.pool, &name, Some(execution.id), &template_plan).await?)
+    }
+
+    /// Plan a team execution from a saved template instead of decomposing the epic
+    pub async fn plan_from_template(
+        &self,
+        team_execution_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<TeamPlanOutput, PlannerError> {
+        let execution = TeamExecution::find_by_id(&self.pool, team_execution_id)
+            .await?
+            .ok_or(PlannerError::PlanningFailed("Execution not found".into()))?;
+
+        if !matches!(
+            execution.status,
+            TeamExecutionStatus::Planning | TeamExecutionStatus::Planned
+        ) {
+            return Err(PlannerError::ExecutionAlreadyStarted);
+        }
+
+        let task = Task::find_by_id(&self.pool, execution.epic_task_id)
+            .await?
+            .ok_or(PlannerError::TaskNotFound(execution.epic_task_id))?;
+
+        let template = PlanTemplate::find_by_id(&self.pool, template_id)
+            .await?
+            .ok_or(PlannerError::TemplateNotFound(template_id))?;
+
+        let mut plan = replace_epic_title(&template.plan, PlanTemplate::EPIC_TITLE, &task.title);
+        plan.reasoning = with_template_note(&plan.reasoning, &template.name);
+
+        let plan_json = serde_json::to_string(&plan)?;
+        TeamExecution::set_planner_output(&self.pool, team_execution_id, &plan_json).await?;
+        TeamExecution::update_status(&self.pool, team_execution_id, TeamExecutionStatus::Planned).await?;
+
+        Ok(plan)
+    }
+
     /// Decompose a task into subtasks
     async fn decompose_task(&self, task: &Task) -> Result<TeamPlanOutput, PlannerError> {
         let complexity = self.analyze_complexity(task).await;
 
         // Simple rule-based decomposition for demonstration
         // In production, this would be done by the team manager agent
-        let subtasks = self.generate_subtasks(task, &complexity);
+        let mut subtasks = self.generate_subtasks(task, &complexity);
+        let max_subtasks = self.config.max_subtasks.max(1);
+        if subtasks.len() > max_subtasks as usize {
+            subtasks.truncate(max_subtasks as usize);
+            for subtask in &mut subtasks {
+                subtask.depends_on.retain(|index| *index < max_subtasks);
+            }
+        }
         let subtasks_count = subtasks.len();
         let requires_team = subtasks_count >= self.config.team_threshold as usize;
 
@@ -217,6 +328,7 @@ impl PlannerService {
                     depends_on: vec![],
                     complexity: 1,
                     estimated_duration: Some(30),
+                    repo_scopes: vec![],
                 }]
             }
             TaskComplexity::Moderate => {
@@ -228,6 +340,7 @@ impl PlannerService {
                         depends_on: vec![],
                         complexity: 2,
                         estimated_duration: Some(30),
+                        repo_scopes: vec![],
                     },
                     PlannedSubtask {
                         title: format!("Implement: {}", task.title),
@@ -236,6 +349,7 @@ impl PlannerService {
                         depends_on: vec![0],
                         complexity: 3,
                         estimated_duration: Some(60),
+                        repo_scopes: vec![],
                     },
                     PlannedSubtask {
                         title: format!("Test: {}", task.title),
@@ -244,6 +358,7 @@ impl PlannerService {
                         depends_on: vec![1],
                         complexity: 2,
                         estimated_duration: Some(30),
+                        repo_scopes: vec![],
                     },
                 ]
             }
@@ -256,6 +371,7 @@ impl PlannerService {
                         depends_on: vec![],
                         complexity: 3,
                         estimated_duration: Some(45),
+                        repo_scopes: vec![],
                     },
                     PlannedSubtask {
                         title: "Backend implementation".to_string(),
@@ -264,6 +380,7 @@ impl PlannerService {
                         depends_on: vec![0],
                         complexity: 4,
                         estimated_duration: Some(90),
+                        repo_scopes: vec![],
                     },
                     PlannedSubtask {
                         title: "Frontend implementation".to_string(),
@@ -272,6 +389,7 @@ impl PlannerService {
                         depends_on: vec![0],
                         complexity: 4,
                         estimated_duration: Some(90),
+                        repo_scopes: vec![],
                     },
                     PlannedSubtask {
                         title: "Integration".to_string(),
@@ -280,6 +398,7 @@ impl PlannerService {
                         depends_on: vec![1, 2],
                         complexity: 3,
                         estimated_duration: Some(45),
+                        repo_scopes: vec![],
                     },
                     PlannedSubtask {
                         title: "Testing and QA".to_string(),
@@ -288,6 +407,7 @@ impl PlannerService {
                         depends_on: vec![3],
                         complexity: 3,
                         estimated_duration: Some(60),
+                        repo_scopes: vec![],
                     },
                     PlannedSubtask {
                         title: "Documentation".to_string(),
@@ -296,6 +416,7 @@ impl PlannerService {
                         depends_on: vec![3],
                         complexity: 2,
                         estimated_duration: Some(30),
+                        repo_scopes: vec![],
                     },
                 ]
             }
@@ -328,7 +449,7 @@ impl PlannerService {
                 &CreateTask {
                     project_id: epic_task.project_id,
                     title: planned.title.clone(),
-                    description: Some(planned.description.clone()),
+                    description: Some(describe_subtask(planned)),
                     status: Some(TaskStatus::Todo),
                     parent_workspace_id: execution.epic_workspace_id,
                     image_ids: None,
@@ -346,9 +467,7 @@ impl PlannerService {
             )
             .await?;
 
-            task_id_map.insert(idx, task.id);
-
-            // Map dependency indices to UUIDs
+            // Map dependency indices to the team tasks created for them
             let depends_on: Vec<Uuid> = planned
                 .depends_on
                 .iter()
@@ -374,6 +493,7 @@ impl PlannerService {
             )
             .await?;
 
+            task_id_map.insert(idx, team_task.id);
             team_tasks.push(team_task);
         }
 
@@ -385,8 +505,71 @@ impl PlannerService {
     }
 }
 
+/// Description for the task created from a planned subtask, listing the
+/// repositories it works in when it spans several
+fn describe_subtask(planned: &PlannedSubtask) -> String {
+    if planned.repo_scopes.len() < 2 {
+        return planned.description.clone();
+    }
+
+    let scopes: Vec<String> = planned
+        .repo_scopes
+        .iter()
+        .map(|scope| format!("- {}: {}", scope.repo_name, scope.hint))
+        .collect();
+    format!(
+        "{}\n\nThis task spans several repositories:\n{}",
+        planned.description,
+        scopes.join("\n")
+    )
+    .trim_start()
+    .to_string()
+}
+
+/// Start of the reasoning of a plan made from a template
+const TEMPLATE_NOTE: &str = "Reused plan template";
+
+/// `reasoning` noting the template the plan was made from. A plan saved from an
+/// execution that was itself planned from a template already has the note, and
+/// keeps the one it has.
+fn with_template_note(reasoning: &str, template_name: &str) -> String {
+    if reasoning.starts_with(TEMPLATE_NOTE) {
+        reasoning.to_string()
+    } else {
+        format!("{} '{}'. {}", TEMPLATE_NOTE, template_name, reasoning)
+    }
+}
+
+/// Copy of `plan` with every occurrence of `from` in the subtask text and
+/// reasoning replaced by `to`
+fn replace_epic_title(plan: &TeamPlanOutput, from: &str, to: &str) -> TeamPlanOutput {
+    let replace = |text: &str| {
+        if from.is_empty() {
+            text.to_string()
+        } else {
+            text.replace(from, to)
+        }
+    };
+
+    TeamPlanOutput {
+        subtasks: plan
+            .subtasks
+            .iter()
+            .map(|subtask| PlannedSubtask {
+                title: replace(&subtask.title),
+                description: replace(&subtask.description),
+                ..subtask.clone()
+            })
+            .collect(),
+        reasoning: replace(&plan.reasoning),
+        ..plan.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use db::models::team_execution::RepoScope;
+
     use super::*;
 
     #[test]
@@ -400,4 +583,104 @@ mod tests {
         assert!(service.estimate_title_complexity("Implement new feature") >= 2);
         assert!(service.estimate_title_complexity("Build complete authentication system") >= 4);
     }
+
+    #[test]
+    fn test_planner_config_validate() {
+        assert!(PlannerConfig::default().validate().is_ok());
+
+        let config = PlannerConfig {
+            max_subtasks: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err("max_subtasks must be at least 1".to_string())
+        );
+    }
+
+    fn sample_plan() -> TeamPlanOutput {
+        TeamPlanOutput {
+            complexity: "Moderate".into(),
+            requires_team: true,
+            subtasks: vec![
+                PlannedSubtask {
+                    title: "Design: Add invoices resource".into(),
+                    description: "Design the approach for: Add invoices resource".into(),
+                    required_skills: vec!["architecture".into()],
+                    depends_on: vec![],
+                    complexity: 2,
+                    estimated_duration: Some(30),
+                    repo_scopes: vec![],
+                },
+                PlannedSubtask {
+                    title: "Write tests".into(),
+                    description: "Cover the new endpoints".into(),
+                    required_skills: vec!["testing".into()],
+                    depends_on: vec![0],
+                    complexity: 2,
+                    estimated_duration: None,
+                    repo_scopes: vec![],
+                },
+            ],
+            estimated_total_duration: Some(60),
+            reasoning: "Task 'Add invoices resource' analyzed as Moderate complexity.".into(),
+        }
+    }
+
+    #[test]
+    fn test_plan_template_round_trip() {
+        let template = replace_epic_title(
+            &sample_plan(),
+            "Add invoices resource",
+            PlanTemplate::EPIC_TITLE,
+        );
+        assert_eq!(template.subtasks[0].title, "Design: {epic_title}");
+        assert_eq!(template.subtasks[0].description, "Design the approach for: {epic_title}");
+        assert_eq!(template.subtasks[1].title, "Write tests");
+        assert_eq!(template.subtasks[1].depends_on, vec![0]);
+        assert!(template.reasoning.contains("{epic_title}"));
+
+        let plan = replace_epic_title(&template, PlanTemplate::EPIC_TITLE, "Add customers resource");
+        assert_eq!(plan.subtasks[0].title, "Design: Add customers resource");
+        assert_eq!(plan.subtasks[0].description, "Design the approach for: Add customers resource");
+        assert_eq!(plan.estimated_total_duration, Some(60));
+        assert!(!plan.reasoning.contains("{epic_title}"));
+    }
+
+    #[test]
+    fn test_plan_template_ignores_empty_title() {
+        let plan = replace_epic_title(&sample_plan(), "", PlanTemplate::EPIC_TITLE);
+        assert_eq!(plan.subtasks[0].title, "Design: Add invoices resource");
+    }
+
+    #[test]
+    fn test_template_note_added_once() {
+        let reasoning = with_template_note("Analyzed as Moderate complexity.", "CRUD resource");
+        assert_eq!(
+            reasoning,
+            "Reused plan template 'CRUD resource'. Analyzed as Moderate complexity."
+        );
+        assert_eq!(with_template_note(&reasoning, "CRUD resource v2"), reasoning);
+    }
+
+    #[test]
+    fn test_describe_multi_repo_subtask() {
+        let mut subtask = sample_plan().subtasks.remove(1);
+        assert_eq!(describe_subtask(&subtask), "Cover the new endpoints");
+
+        subtask.repo_scopes = vec![
+            RepoScope {
+                repo_name: "api".into(),
+                hint: "backend changes".into(),
+            },
+            RepoScope {
+                repo_name: "web".into(),
+                hint: "frontend changes".into(),
+            },
+        ];
+        assert_eq!(
+            describe_subtask(&subtask),
+            "Cover the new endpoints\n\nThis task spans several repositories:\n- api: backend changes\n- web: frontend changes"
+        );
+    }
 }
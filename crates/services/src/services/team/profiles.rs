@@ -0,0 +1,213 @@
+//! Default Agent Profiles
+//!
+//! A team execution needs a planner, workers and reviewers. On a fresh install,
+//! or once every profile for a role has been deleted or deactivated, the missing
+//! roles are filled with default profiles that run on an executor the server has
+//! configured. They are ordinary profiles and can be reviewed, edited or replaced
+//! through the agent profile routes.
+
+use db::models::{
+    agent_profile::{AgentProfile, CreateAgentProfile, SkillAssignment},
+    agent_skill::AgentSkill,
+};
+use executors::{
+    executors::{BaseCodingAgent, StandardCodingAgentExecutor},
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use sqlx::SqlitePool;
+
+/// A part a profile can play in a team execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Planner,
+    Worker,
+    Reviewer,
+}
+
+impl Role {
+    const ALL: [Role; 3] = [Role::Planner, Role::Worker, Role::Reviewer];
+
+    fn is_filled_by(self, profile: &AgentProfile) -> bool {
+        profile.active
+            && match self {
+                Role::Planner => profile.is_planner,
+                Role::Worker => profile.is_worker,
+                Role::Reviewer => profile.is_reviewer,
+            }
+    }
+
+    fn default_profile(
+        self,
+        executor: BaseCodingAgent,
+        skills: &[AgentSkill],
+    ) -> CreateAgentProfile {
+        let (name, description, max_concurrent_tasks) = match self {
+            Role::Planner => ("Default Planner", "Decomposes epics into subtasks", 1),
+            Role::Worker => (
+                "Default Worker",
+                "General-purpose worker for any subtask",
+                2,
+            ),
+            Role::Reviewer => ("Default Reviewer", "Reviews the work of other agents", 3),
+        };
+        // Workers are matched to subtasks by skill, so the default one has them all
+        let skills = (self == Role::Worker).then(|| {
+            skills
+                .iter()
+                .map(|skill| SkillAssignment {
+                    skill_id: skill.id,
+                    proficiency: None,
+                })
+                .collect()
+        });
+
+        CreateAgentProfile {
+            name: name.to_string(),
+            description: Some(format!(
+                "{description}. Created automatically; edit as needed."
+            )),
+            executor: executor.to_string(),
+            variant: None,
+            executor_config: None,
+            is_planner: Some(self == Role::Planner),
+            is_reviewer: Some(self == Role::Reviewer),
+            is_worker: Some(self == Role::Worker),
+            max_concurrent_tasks: Some(max_concurrent_tasks),
+            priority: None,
+            skills,
+        }
+    }
+}
+
+/// Service for filling team roles that no profile covers
+pub struct DefaultProfileService {
+    pool: SqlitePool,
+}
+
+impl DefaultProfileService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a default profile for each role that no active profile fills.
+    /// Returns the profiles created, which is empty when every role is filled.
+    pub async fn seed_missing(&self) -> Result<Vec<AgentProfile>, sqlx::Error> {
+        let profiles = AgentProfile::find_all(&self.pool).await?;
+        let missing: Vec<Role> = Role::ALL
+            .into_iter()
+            .filter(|role| !profiles.iter().any(|profile| role.is_filled_by(profile)))
+            .collect();
+        if missing.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let executor = configured_executor();
+        let skills = AgentSkill::find_all(&self.pool).await?;
+        let mut created = Vec::with_capacity(missing.len());
+        for role in missing {
+            let profile =
+                AgentProfile::create(&self.pool, &role.default_profile(executor, &skills)).await?;
+            tracing::info!(
+                "Created default agent profile '{}' on {}",
+                profile.name,
+                executor
+            );
+            created.push(profile);
+        }
+        Ok(created)
+    }
+}
+
+/// The executor for default profiles, chosen among those configured on the server
+fn configured_executor() -> BaseCodingAgent {
+    let configs = ExecutorConfigs::get_cached();
+    let configured: Vec<(BaseCodingAgent, bool)> = configs
+        .executors
+        .keys()
+        .map(|executor| {
+            let available = configs
+                .get_coding_agent(&ExecutorProfileId::new(*executor))
+                .is_some_and(|agent| agent.get_availability_info().is_available());
+            (*executor, available)
+        })
+        .collect();
+    pick_executor(&configured)
+}
+
+/// Prefer an installed executor, then Claude Code as in the built-in profiles,
+/// then the name so the choice is stable. Falls back to Claude Code when
+/// nothing is configured.
+fn pick_executor(configured: &[(BaseCodingAgent, bool)]) -> BaseCodingAgent {
+    configured
+        .iter()
+        .min_by_key(|(executor, available)| {
+            (
+                !available,
+                *executor != BaseCodingAgent::ClaudeCode,
+                executor.to_string(),
+            )
+        })
+        .map_or(BaseCodingAgent::ClaudeCode, |(executor, _)| *executor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_executor_prefers_available() {
+        let configured = [
+            (BaseCodingAgent::ClaudeCode, false),
+            (BaseCodingAgent::Gemini, true),
+            (BaseCodingAgent::Codex, true),
+        ];
+        assert_eq!(pick_executor(&configured), BaseCodingAgent::Codex);
+    }
+
+    #[test]
+    fn test_pick_executor_prefers_claude_code() {
+        let configured = [
+            (BaseCodingAgent::Codex, true),
+            (BaseCodingAgent::ClaudeCode, true),
+        ];
+        assert_eq!(pick_executor(&configured), BaseCodingAgent::ClaudeCode);
+
+        let unavailable = [
+            (BaseCodingAgent::Codex, false),
+            (BaseCodingAgent::ClaudeCode, false),
+        ];
+        assert_eq!(pick_executor(&unavailable), BaseCodingAgent::ClaudeCode);
+    }
+
+    #[test]
+    fn test_pick_executor_without_configured_executors() {
+        assert_eq!(pick_executor(&[]), BaseCodingAgent::ClaudeCode);
+    }
+
+    #[test]
+    fn test_default_worker_has_every_skill() {
+        let skill = AgentSkill {
+            id: uuid::Uuid::new_v4(),
+            name: "rust".into(),
+            description: "Rust development".into(),
+            prompt_modifier: None,
+            category: "language".into(),
+            icon: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+
+        let worker = Role::Worker.default_profile(BaseCodingAgent::Codex, &[skill.clone()]);
+        assert_eq!(worker.executor, "CODEX");
+        assert_eq!(worker.is_worker, Some(true));
+        assert_eq!(
+            worker.skills.map(|skills| skills[0].skill_id),
+            Some(skill.id)
+        );
+
+        let planner = Role::Planner.default_profile(BaseCodingAgent::Codex, &[skill]);
+        assert_eq!(planner.is_planner, Some(true));
+        assert_eq!(planner.is_worker, Some(false));
+        assert!(planner.skills.is_none());
+    }
+}
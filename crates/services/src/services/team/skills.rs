@@ -0,0 +1,526 @@
+//! Skill Inference Service
+//!
+//! Detects the languages and frameworks a project uses from the manifests in its
+//! repositories and maps them to agent skills. The planner uses this to tailor
+//! the `required_skills` of planned subtasks to the project and to scope each
+//! subtask to the repositories it touches, and agent profiles get suggestions
+//! for the skills the project calls for.
+
+use std::{collections::BTreeSet, fs, path::Path};
+
+use db::models::{
+    agent_profile::AgentProfile,
+    agent_skill::AgentSkill,
+    project_repo::ProjectRepo,
+    team_execution::{PlannedSubtask, RepoScope},
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Manifest files and the language they indicate
+const MANIFESTS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("package.json", "javascript"),
+    ("tsconfig.json", "typescript"),
+    ("go.mod", "go"),
+    ("pyproject.toml", "python"),
+    ("requirements.txt", "python"),
+    ("setup.py", "python"),
+    ("Gemfile", "ruby"),
+    ("pom.xml", "java"),
+    ("build.gradle", "java"),
+    ("build.gradle.kts", "kotlin"),
+    ("composer.json", "php"),
+    ("mix.exs", "elixir"),
+    ("Package.swift", "swift"),
+];
+
+/// Dependencies that identify a framework: (manifest, text in the manifest,
+/// framework, skill it calls for)
+const FRAMEWORKS: &[(&str, &str, &str, &str)] = &[
+    ("package.json", "\"react\"", "react", "frontend"),
+    ("package.json", "\"vue\"", "vue", "frontend"),
+    ("package.json", "\"svelte\"", "svelte", "frontend"),
+    ("package.json", "\"next\"", "next.js", "frontend"),
+    ("package.json", "\"@angular/core\"", "angular", "frontend"),
+    ("package.json", "\"express\"", "express", "backend"),
+    ("package.json", "\"fastify\"", "fastify", "backend"),
+    ("package.json", "\"@nestjs/core\"", "nestjs", "backend"),
+    ("package.json", "\"prisma\"", "prisma", "database"),
+    ("package.json", "\"jest\"", "jest", "testing"),
+    ("package.json", "\"vitest\"", "vitest", "testing"),
+    (
+        "package.json",
+        "\"@playwright/test\"",
+        "playwright",
+        "testing",
+    ),
+    ("package.json", "\"cypress\"", "cypress", "testing"),
+    ("Cargo.toml", "axum", "axum", "backend"),
+    ("Cargo.toml", "actix-web", "actix-web", "backend"),
+    ("Cargo.toml", "sqlx", "sqlx", "database"),
+    ("Cargo.toml", "diesel", "diesel", "database"),
+    ("go.mod", "gin-gonic/gin", "gin", "backend"),
+    ("go.mod", "gorm.io/gorm", "gorm", "database"),
+    ("pyproject.toml", "django", "django", "backend"),
+    ("pyproject.toml", "flask", "flask", "backend"),
+    ("pyproject.toml", "fastapi", "fastapi", "backend"),
+    ("pyproject.toml", "sqlalchemy", "sqlalchemy", "database"),
+    ("pyproject.toml", "pytest", "pytest", "testing"),
+    ("requirements.txt", "django", "django", "backend"),
+    ("requirements.txt", "flask", "flask", "backend"),
+    ("requirements.txt", "fastapi", "fastapi", "backend"),
+    ("requirements.txt", "sqlalchemy", "sqlalchemy", "database"),
+    ("requirements.txt", "pytest", "pytest", "testing"),
+    ("Gemfile", "rails", "rails", "backend"),
+    ("pom.xml", "spring-boot", "spring boot", "backend"),
+    ("build.gradle", "spring-boot", "spring boot", "backend"),
+];
+
+/// Files and directories whose presence calls for a skill
+const PATHS: &[(&str, &str)] = &[
+    ("Dockerfile", "devops"),
+    ("docker-compose.yml", "devops"),
+    (".github/workflows", "devops"),
+    (".gitlab-ci.yml", "devops"),
+    ("migrations", "database"),
+    ("tests", "testing"),
+    ("docs", "documentation"),
+];
+
+/// Words in a subtask's title or description that call for a skill. A word
+/// matches when it starts with one of these.
+const SUBTASK_KEYWORDS: &[(&[&str], &str)] = &[
+    (&["test", "qa", "spec", "e2e"], "testing"),
+    (&["doc", "readme", "tutorial"], "documentation"),
+    (
+        &["database", "schema", "migration", "sql", "query"],
+        "database",
+    ),
+    (&["deploy", "docker", "pipeline", "ci", "infra"], "devops"),
+    (
+        &["ui", "frontend", "component", "page", "css", "style"],
+        "frontend",
+    ),
+    (
+        &["api", "endpoint", "backend", "server", "service"],
+        "backend",
+    ),
+    (&["auth", "security", "permission", "vulnerab"], "security"),
+    (&["refactor", "cleanup"], "refactoring"),
+];
+
+/// Skills that only make sense when the repositories have that part of the stack
+const STACK_SKILLS: &[&str] = &["frontend", "database"];
+
+/// Directories not worth looking into for manifests
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor"];
+
+/// Languages, frameworks and skills detected in a project's repositories
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+pub struct RepoSkillProfile {
+    pub languages: Vec<String>,
+    pub frameworks: Vec<String>,
+    /// Skill names the repositories call for
+    pub skills: Vec<String>,
+}
+
+impl RepoSkillProfile {
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty() && self.frameworks.is_empty() && self.skills.is_empty()
+    }
+
+    fn merge(&mut self, other: RepoSkillProfile) {
+        for (target, values) in [
+            (&mut self.languages, other.languages),
+            (&mut self.frameworks, other.frameworks),
+            (&mut self.skills, other.skills),
+        ] {
+            let merged: BTreeSet<String> = target.drain(..).chain(values).collect();
+            target.extend(merged);
+        }
+    }
+}
+
+/// Skills an agent profile is missing for a project
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProfileSkillSuggestion {
+    pub profile: AgentProfile,
+    pub missing_skills: Vec<AgentSkill>,
+}
+
+/// What a project's repositories call for, and how the worker profiles measure up
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectSkillSuggestions {
+    pub detected: RepoSkillProfile,
+    /// Existing skills matching what was detected
+    pub skills: Vec<AgentSkill>,
+    /// Worker profiles missing some of those skills
+    pub profiles: Vec<ProfileSkillSuggestion>,
+}
+
+/// Detect the languages, frameworks and skills of a repository checked out at `path`.
+/// Manifests are looked for at the root and one directory down, to cover
+/// monorepos with e.g. a `frontend/` and a `backend/` package.
+pub fn detect_repo_skills(path: &Path) -> RepoSkillProfile {
+    let mut languages = BTreeSet::new();
+    let mut frameworks = BTreeSet::new();
+    let mut skills = BTreeSet::new();
+
+    let mut dirs = vec![path.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(path) {
+        dirs.extend(entries.flatten().map(|e| e.path()).filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            p.is_dir() && !name.starts_with('.') && !SKIPPED_DIRS.contains(&name)
+        }));
+    }
+
+    for dir in &dirs {
+        for (manifest, language) in MANIFESTS {
+            let Ok(contents) = fs::read_to_string(dir.join(manifest)) else {
+                continue;
+            };
+            languages.insert(language.to_string());
+            for (framework_manifest, needle, framework, skill) in FRAMEWORKS {
+                if framework_manifest == manifest && contents.contains(needle) {
+                    frameworks.insert(framework.to_string());
+                    skills.insert(skill.to_string());
+                }
+            }
+        }
+        for (name, skill) in PATHS {
+            if dir.join(name).exists() {
+                skills.insert(skill.to_string());
+            }
+        }
+    }
+
+    RepoSkillProfile {
+        languages: languages.into_iter().collect(),
+        frameworks: frameworks.into_iter().collect(),
+        skills: skills.into_iter().collect(),
+    }
+}
+
+/// Required skills for a subtask, given what the project's repositories use.
+///
+/// Starts from the planner's skills plus those called for by words in the
+/// subtask, drops stack skills the repositories don't have, and adds the
+/// project's languages. Only names in `known_skills` are kept, so every
+/// suggestion can be matched against agent profiles.
+pub fn infer_subtask_skills(
+    subtask: &PlannedSubtask,
+    profile: &RepoSkillProfile,
+    known_skills: &[String],
+) -> Vec<String> {
+    let text = format!("{} {}", subtask.title, subtask.description).to_lowercase();
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut skills: Vec<String> = subtask.required_skills.clone();
+    for (keywords, skill) in SUBTASK_KEYWORDS {
+        if words
+            .iter()
+            .any(|word| keywords.iter().any(|k| word.starts_with(k)))
+        {
+            skills.push(skill.to_string());
+        }
+    }
+
+    // Without any detected repository contents there is nothing to go on
+    if !profile.is_empty() {
+        skills.retain(|s| !STACK_SKILLS.contains(&s.as_str()) || profile.skills.contains(s));
+        skills.extend(profile.languages.iter().cloned());
+    }
+
+    let mut seen = BTreeSet::new();
+    skills.retain(|s| known_skills.contains(s) && seen.insert(s.clone()));
+    skills
+}
+
+/// Repositories a subtask works in, going by the skills each repository calls for.
+///
+/// Empty, meaning all repositories, when the project has a single repository or
+/// none of them matches the subtask's skills.
+pub fn scope_subtask_repos(
+    subtask: &PlannedSubtask,
+    repos: &[(String, RepoSkillProfile)],
+) -> Vec<RepoScope> {
+    if repos.len() < 2 {
+        return Vec::new();
+    }
+
+    repos
+        .iter()
+        .filter_map(|(name, profile)| {
+            let matched: Vec<&str> = subtask
+                .required_skills
+                .iter()
+                .filter(|s| profile.skills.contains(s) || profile.languages.contains(s))
+                .map(String::as_str)
+                .collect();
+            (!matched.is_empty()).then(|| RepoScope {
+                repo_name: name.clone(),
+                hint: format!("{} changes", matched.join(" and ")),
+            })
+        })
+        .collect()
+}
+
+/// Service for inferring skills from repository contents
+pub struct SkillInferenceService {
+    pool: SqlitePool,
+}
+
+impl SkillInferenceService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Detected languages, frameworks and skills of each of a project's repositories,
+    /// by repository name
+    pub async fn repo_profiles(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<(String, RepoSkillProfile)>, sqlx::Error> {
+        let repos = ProjectRepo::find_repos_for_project(&self.pool, project_id).await?;
+        Ok(repos
+            .into_iter()
+            .map(|repo| {
+                let profile = detect_repo_skills(&repo.path);
+                (repo.name, profile)
+            })
+            .collect())
+    }
+
+    /// Detected languages, frameworks and skills across a project's repositories
+    pub async fn project_profile(&self, project_id: Uuid) -> Result<RepoSkillProfile, sqlx::Error> {
+        let mut profile = RepoSkillProfile::default();
+        for (_, repo_profile) in self.repo_profiles(project_id).await? {
+            profile.merge(repo_profile);
+        }
+        Ok(profile)
+    }
+
+    /// Tailor the required skills of planned subtasks to a project, and scope
+    /// subtasks the planner left unscoped to the repositories they touch
+    pub async fn apply_to_subtasks(
+        &self,
+        project_id: Uuid,
+        subtasks: &mut [PlannedSubtask],
+    ) -> Result<(), sqlx::Error> {
+        let repo_profiles = self.repo_profiles(project_id).await?;
+        let mut profile = RepoSkillProfile::default();
+        for (_, repo_profile) in &repo_profiles {
+            profile.merge(repo_profile.clone());
+        }
+        let known_skills: Vec<String> = AgentSkill::find_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+
+        for subtask in subtasks.iter_mut() {
+            subtask.required_skills = infer_subtask_skills(subtask, &profile, &known_skills);
+            if subtask.repo_scopes.is_empty() {
+                subtask.repo_scopes = scope_subtask_repos(subtask, &repo_profiles);
+            }
+        }
+        Ok(())
+    }
+
+    /// Skills a project calls for, and which of them each worker profile lacks
+    pub async fn suggest_for_project(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ProjectSkillSuggestions, sqlx::Error> {
+        let detected = self.project_profile(project_id).await?;
+        let skills: Vec<AgentSkill> = AgentSkill::find_all(&self.pool)
+            .await?
+            .into_iter()
+            .filter(|s| detected.skills.contains(&s.name) || detected.languages.contains(&s.name))
+            .collect();
+
+        let mut profiles = Vec::new();
+        for profile in AgentProfile::find_workers(&self.pool).await? {
+            let profile_skills = AgentProfile::get_skills(&self.pool, profile.id).await?;
+            let missing_skills: Vec<AgentSkill> = skills
+                .iter()
+                .filter(|s| !profile_skills.iter().any(|p| p.id == s.id))
+                .cloned()
+                .collect();
+            if !missing_skills.is_empty() {
+                profiles.push(ProfileSkillSuggestion {
+                    profile,
+                    missing_skills,
+                });
+            }
+        }
+
+        Ok(ProjectSkillSuggestions {
+            detected,
+            skills,
+            profiles,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn subtask(title: &str, description: &str, required_skills: &[&str]) -> PlannedSubtask {
+        PlannedSubtask {
+            title: title.to_string(),
+            description: description.to_string(),
+            required_skills: required_skills.iter().map(|s| s.to_string()).collect(),
+            depends_on: vec![],
+            complexity: 2,
+            estimated_duration: None,
+            repo_scopes: vec![],
+        }
+    }
+
+    fn known(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn detects_languages_frameworks_and_paths() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[dependencies]\naxum = \"0.8\"\nsqlx = \"0.8\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("frontend")).unwrap();
+        fs::write(
+            dir.path().join("frontend/package.json"),
+            r#"{"dependencies": {"react": "18"}, "devDependencies": {"vitest": "1"}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
+        fs::create_dir_all(dir.path().join("node_modules/x")).unwrap();
+        fs::write(dir.path().join("node_modules/x/go.mod"), "module x").unwrap();
+
+        let profile = detect_repo_skills(dir.path());
+        assert_eq!(profile.languages, known(&["javascript", "rust"]));
+        assert_eq!(
+            profile.frameworks,
+            known(&["axum", "react", "sqlx", "vitest"])
+        );
+        assert_eq!(
+            profile.skills,
+            known(&["backend", "database", "devops", "frontend", "testing"])
+        );
+    }
+
+    #[test]
+    fn missing_repository_detects_nothing() {
+        let profile = detect_repo_skills(Path::new("/nonexistent/vibe-kanban-repo"));
+        assert!(profile.is_empty());
+    }
+
+    #[test]
+    fn subtask_skills_follow_repository_stack() {
+        let known_skills = known(&["frontend", "backend", "testing", "database", "rust"]);
+        let profile = RepoSkillProfile {
+            languages: known(&["rust"]),
+            frameworks: known(&["axum"]),
+            skills: known(&["backend"]),
+        };
+
+        let skills = infer_subtask_skills(
+            &subtask(
+                "Implement: add invoices",
+                "Add the API endpoints",
+                &["backend", "frontend"],
+            ),
+            &profile,
+            &known_skills,
+        );
+        assert_eq!(skills, known(&["backend", "rust"]));
+
+        let skills = infer_subtask_skills(
+            &subtask("Write tests", "Cover the latest changes", &[]),
+            &profile,
+            &known_skills,
+        );
+        assert_eq!(skills, known(&["testing", "rust"]));
+    }
+
+    #[test]
+    fn subtask_skills_without_repository_info_keep_planner_skills() {
+        let skills = infer_subtask_skills(
+            &subtask(
+                "Frontend implementation",
+                "Build the settings page",
+                &["frontend"],
+            ),
+            &RepoSkillProfile::default(),
+            &known(&["frontend", "backend"]),
+        );
+        assert_eq!(skills, known(&["frontend"]));
+    }
+
+    #[test]
+    fn subtasks_are_scoped_to_matching_repos() {
+        let repos = vec![
+            (
+                "api".to_string(),
+                RepoSkillProfile {
+                    languages: known(&["rust"]),
+                    frameworks: known(&["axum"]),
+                    skills: known(&["backend", "database"]),
+                },
+            ),
+            (
+                "web".to_string(),
+                RepoSkillProfile {
+                    languages: known(&["typescript"]),
+                    frameworks: known(&["react"]),
+                    skills: known(&["frontend", "testing"]),
+                },
+            ),
+        ];
+
+        let scopes = scope_subtask_repos(
+            &subtask("Integration", "Wire things up", &["backend", "frontend"]),
+            &repos,
+        );
+        assert_eq!(
+            scopes,
+            vec![
+                RepoScope {
+                    repo_name: "api".into(),
+                    hint: "backend changes".into(),
+                },
+                RepoScope {
+                    repo_name: "web".into(),
+                    hint: "frontend changes".into(),
+                },
+            ]
+        );
+
+        let scopes = scope_subtask_repos(
+            &subtask("Frontend implementation", "", &["frontend"]),
+            &repos,
+        );
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].repo_name, "web");
+
+        // Nothing matches, so the subtask gets every repository
+        let scopes = scope_subtask_repos(&subtask("Documentation", "", &["documentation"]), &repos);
+        assert!(scopes.is_empty());
+
+        // A single repository needs no scoping
+        let scopes = scope_subtask_repos(&subtask("Integration", "", &["backend"]), &repos[..1]);
+        assert!(scopes.is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+//! Settings of the command-line client kept on the server.
+//!
+//! The CLI reads its settings from a local config file. The ones a user wants on
+//! every machine they work from (theme, default executor, saved board filters
+//! and key bindings) can also be kept here, in the server config; the CLI
+//! applies them wherever its local config file leaves them unset.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// CLI settings that follow the user across machines, stored in the server config
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct UserPreferences {
+    /// Name of the CLI theme, e.g. "solarized"
+    pub theme: Option<String>,
+    /// Executor preselected for new attempts, e.g. "claude-code"
+    pub default_executor: Option<String>,
+    /// Board search queries by name, used as `@name` in a search
+    pub saved_filters: BTreeMap<String, String>,
+    /// Keys bound to CLI actions, by action name, e.g. `{ "quit": ["ctrl+q"] }`
+    pub keybindings: BTreeMap<String, Vec<String>>,
+}
+
+impl UserPreferences {
+    /// Check that saved filters have a name of one word and a query, and that
+    /// every rebound action has a key.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, query) in &self.saved_filters {
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(format!(
+                    "saved filter name '{}' must be one word without spaces",
+                    name
+                ));
+            }
+            if query.trim().is_empty() {
+                return Err(format!("saved filter '{}' has an empty query", name));
+            }
+        }
+        if let Some((action, _)) = self.keybindings.iter().find(|(_, keys)| keys.is_empty()) {
+            return Err(format!("no keys given for action '{}'", action));
+        }
+        Ok(())
+    }
+}
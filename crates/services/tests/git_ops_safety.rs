@@ -1406,3 +1406,120 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+#[test]
+fn merge_preview_reports_commits_and_conflicts_without_moving_refs() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, _worktree_path) = setup_repo_with_worktree(&td);
+    let service = GitService::new();
+    let before = service.get_branch_oid(&repo_path, "new-base").unwrap();
+
+    let preview = service
+        .preview_merge(&repo_path, "feature", "old-base")
+        .unwrap();
+    assert!(preview.fast_forward);
+    assert!(preview.blocker.is_none());
+    assert!(preview.conflicted_files.is_empty());
+    let subjects: Vec<_> = preview.commits.iter().map(|c| c.subject.as_str()).collect();
+    assert_eq!(subjects, vec!["feature commit"]);
+
+    // new-base added base.txt differently, so merging would conflict there
+    let preview = service
+        .preview_merge(&repo_path, "feature", "new-base")
+        .unwrap();
+    assert!(!preview.fast_forward);
+    assert_eq!(preview.behind, 1);
+    assert!(preview.blocker.is_some());
+    assert_eq!(preview.conflicted_files, vec!["base.txt".to_string()]);
+
+    assert_eq!(
+        service.get_branch_oid(&repo_path, "new-base").unwrap(),
+        before
+    );
+}
+
+#[test]
+fn rebase_preview_lists_commits_to_replay() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let service = GitService::new();
+    let before = service.get_branch_oid(&repo_path, "feature").unwrap();
+
+    let preview = service
+        .preview_rebase(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+        )
+        .unwrap();
+    assert!(!preview.fast_forward);
+    assert_eq!(preview.behind, 1);
+    assert!(preview.conflicted_files.is_empty());
+    assert!(preview.blocker.is_none());
+    let subjects: Vec<_> = preview.commits.iter().map(|c| c.subject.as_str()).collect();
+    assert_eq!(subjects, vec!["feature commit"]);
+
+    assert_eq!(
+        service.get_branch_oid(&repo_path, "feature").unwrap(),
+        before
+    );
+}
+
+#[test]
+fn commits_ahead_lists_branch_commits_missing_from_base() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, _worktree_path) = setup_repo_with_worktree(&td);
+    let service = GitService::new();
+
+    let commits = service
+        .get_commits_ahead(&repo_path, "feature", "old-base")
+        .unwrap();
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0].subject, "feature commit");
+    assert_eq!(commits[0].author_name, "Test User");
+    assert_eq!(commits[0].author_email.as_deref(), Some("test@example.com"));
+
+    // new-base doesn't have old-base's commit either
+    let subjects: Vec<_> = service
+        .get_commits_ahead(&repo_path, "feature", "new-base")
+        .unwrap()
+        .into_iter()
+        .map(|c| c.subject)
+        .collect();
+    assert_eq!(subjects, vec!["feature commit", "old-base commit"]);
+
+    assert!(
+        service
+            .get_commits_ahead(&repo_path, "old-base", "feature")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn rebase_preview_reports_conflicts_without_starting_a_rebase() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let service = GitService::new();
+    let before = service.get_branch_oid(&repo_path, "feature").unwrap();
+
+    let preview = service
+        .preview_rebase(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+        )
+        .unwrap();
+    assert_eq!(preview.conflicted_files, vec!["conflict.txt".to_string()]);
+    assert!(!service.is_rebase_in_progress(&worktree_path).unwrap());
+    assert_eq!(
+        service.get_branch_oid(&repo_path, "feature").unwrap(),
+        before
+    );
+    let content = fs::read_to_string(worktree_path.join("conflict.txt")).unwrap();
+    assert_eq!(content, "feature version\n");
+}
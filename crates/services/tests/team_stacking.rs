@@ -0,0 +1,463 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use db::{
+    DBService,
+    models::{
+        agent_profile::{AgentProfile, CreateAgentProfile},
+        merge::Merge,
+        project::{CreateProject, Project},
+        project_repo::ProjectRepo,
+        repo::Repo,
+        task::{CreateTask, Task},
+        team_execution::{CreateTeamExecution, TeamExecution, TeamExecutionStatus},
+        team_task::{CreateTeamTask, TeamTask},
+        workspace::{CreateWorkspace, Workspace},
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+    },
+};
+use services::services::{
+    git::{GitCli, GitService},
+    team::TeamManager,
+};
+use sqlx::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+fn write_file<P: AsRef<Path>>(base: P, rel: &str, content: &str) {
+    let path = base.as_ref().join(rel);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(path, content).unwrap();
+}
+
+fn configure_user(repo_path: &Path, name: &str, email: &str) {
+    let repo = git2::Repository::open(repo_path).unwrap();
+    let mut cfg = repo.config().unwrap();
+    cfg.set_str("user.name", name).unwrap();
+    cfg.set_str("user.email", email).unwrap();
+}
+
+fn commit_all(path: &Path, message: &str) {
+    let git = GitCli::new();
+    git.git(path, ["add", "-A"]).unwrap();
+    git.git(path, ["commit", "-m", message]).unwrap();
+}
+
+fn init_repo_main(root: &TempDir) -> PathBuf {
+    let path = root.path().join("repo");
+    let s = GitService::new();
+    s.initialize_repo_with_main_branch(&path).unwrap();
+    configure_user(&path, "Test User", "test@example.com");
+    write_file(&path, "README.md", "# Stack\n");
+    commit_all(&path, "initial");
+    path
+}
+
+/// Create `branch` from `from` with one commit writing `file`, leaving `main` checked out.
+fn commit_on_new_branch(repo_path: &Path, from: &str, branch: &str, file: &str, content: &str) {
+    let git = GitCli::new();
+    git.git(repo_path, ["checkout", "-b", branch, from])
+        .unwrap();
+    write_file(repo_path, file, content);
+    commit_all(repo_path, &format!("Add {file}"));
+    git.git(repo_path, ["checkout", "main"]).unwrap();
+}
+
+fn head_of(repo_path: &Path, branch: &str) -> String {
+    GitService::new().get_branch_oid(repo_path, branch).unwrap()
+}
+
+fn parent_of(repo_path: &Path, commit: &str) -> String {
+    let repo = git2::Repository::open(repo_path).unwrap();
+    let commit = repo
+        .find_commit(git2::Oid::from_str(commit).unwrap())
+        .unwrap();
+    commit.parent_id(0).unwrap().to_string()
+}
+
+struct Team {
+    _db: DBService,
+    pool: SqlitePool,
+    project: Project,
+    repo: Repo,
+    execution: TeamExecution,
+}
+
+/// A project with the repository at `repo_path`, an epic task and a team execution
+/// for it that is executing.
+async fn team(repo_path: &Path, max_parallel_workers: i32) -> Team {
+    let db = DBService::new_in_memory().await.unwrap();
+    let pool = db.pool.clone();
+    let project = Project::create(
+        &pool,
+        &CreateProject {
+            name: "Stack".to_string(),
+            repositories: Vec::new(),
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let repo = Repo::find_or_create(&pool, repo_path, "repo")
+        .await
+        .unwrap();
+    ProjectRepo::create(&pool, project.id, repo.id)
+        .await
+        .unwrap();
+    let epic = Task::create(
+        &pool,
+        &CreateTask::from_title_description(project.id, "Epic".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let execution = TeamExecution::create(
+        &pool,
+        &CreateTeamExecution {
+            epic_task_id: epic.id,
+            epic_workspace_id: None,
+            planner_profile_id: None,
+            max_parallel_workers: Some(max_parallel_workers),
+        },
+    )
+    .await
+    .unwrap();
+    TeamExecution::update_status(&pool, execution.id, TeamExecutionStatus::Executing)
+        .await
+        .unwrap();
+    // The seeded profiles have text ids that `AgentProfile` can't decode, so the
+    // subtasks go to a worker of their own
+    sqlx::query("UPDATE agent_profiles SET active = 0")
+        .execute(&pool)
+        .await
+        .unwrap();
+    AgentProfile::create(
+        &pool,
+        &CreateAgentProfile {
+            name: "Worker".to_string(),
+            description: None,
+            executor: "CLAUDE_CODE".to_string(),
+            variant: None,
+            executor_config: None,
+            is_planner: Some(false),
+            is_reviewer: Some(false),
+            is_worker: Some(true),
+            max_concurrent_tasks: None,
+            priority: None,
+            skills: None,
+        },
+    )
+    .await
+    .unwrap();
+    Team {
+        _db: db,
+        pool,
+        project,
+        repo,
+        execution,
+    }
+}
+
+impl Team {
+    async fn subtask(&self, sequence_order: i32, depends_on: &[Uuid]) -> TeamTask {
+        let task = Task::create(
+            &self.pool,
+            &CreateTask::from_title_description(
+                self.project.id,
+                format!("Subtask {sequence_order}"),
+                None,
+            ),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        TeamTask::create(
+            &self.pool,
+            &CreateTeamTask {
+                team_execution_id: self.execution.id,
+                task_id: task.id,
+                sequence_order,
+                depends_on: Some(depends_on.to_vec()),
+                required_skills: None,
+                complexity: None,
+                max_retries: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Give a subtask a workspace on `branch` building on `target_branch`, with a
+    /// worktree under `container` if one is given.
+    async fn workspace(
+        &self,
+        team_task: &TeamTask,
+        branch: &str,
+        target_branch: &str,
+        container: Option<&Path>,
+    ) -> Workspace {
+        let workspace = Workspace::create(
+            &self.pool,
+            &CreateWorkspace {
+                branch: branch.to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            team_task.task_id,
+        )
+        .await
+        .unwrap();
+        WorkspaceRepo::create_many(
+            &self.pool,
+            workspace.id,
+            &[CreateWorkspaceRepo {
+                repo_id: self.repo.id,
+                target_branch: target_branch.to_string(),
+            }],
+        )
+        .await
+        .unwrap();
+        TeamTask::set_workspace(&self.pool, team_task.id, workspace.id, branch)
+            .await
+            .unwrap();
+        if let Some(container) = container {
+            GitCli::new()
+                .worktree_add(
+                    &self.repo.path,
+                    &container.join(&self.repo.name),
+                    branch,
+                    false,
+                )
+                .unwrap();
+            Workspace::update_container_ref(&self.pool, workspace.id, &container.to_string_lossy())
+                .await
+                .unwrap();
+        }
+        Workspace::find_by_id(&self.pool, workspace.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn started_workspace(&self, team_task: &TeamTask) -> Workspace {
+        let workspace_id = TeamTask::find_by_id(&self.pool, team_task.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .workspace_id
+            .expect("subtask was not started");
+        Workspace::find_by_id(&self.pool, workspace_id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn target_branch(&self, workspace: &Workspace) -> String {
+        WorkspaceRepo::find_by_workspace_and_repo_id(&self.pool, workspace.id, self.repo.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .target_branch
+    }
+}
+
+#[tokio::test]
+async fn dependent_subtask_stacks_on_its_upstream_branch() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let team = team(&repo_path, 1).await;
+    let manager = TeamManager::new(team.pool.clone());
+    let upstream = team.subtask(0, &[]).await;
+    let dependent = team.subtask(1, &[upstream.id]).await;
+
+    let started = manager
+        .execute_ready_tasks(team.execution.id)
+        .await
+        .unwrap();
+    assert_eq!(started, [upstream.id]);
+    let upstream_workspace = team.started_workspace(&upstream).await;
+    assert_eq!(team.target_branch(&upstream_workspace).await, "main");
+
+    // The upstream's worktree was set up and its branch created
+    GitCli::new()
+        .git(&repo_path, ["branch", &upstream_workspace.branch])
+        .unwrap();
+    manager.complete_task(upstream.id).await.unwrap();
+
+    let dependent_workspace = team.started_workspace(&dependent).await;
+    assert_eq!(
+        team.target_branch(&dependent_workspace).await,
+        upstream_workspace.branch
+    );
+}
+
+#[tokio::test]
+async fn dependent_subtask_builds_on_the_base_without_an_upstream_branch() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let team = team(&repo_path, 1).await;
+    let manager = TeamManager::new(team.pool.clone());
+    let upstream = team.subtask(0, &[]).await;
+    let dependent = team.subtask(1, &[upstream.id]).await;
+
+    manager
+        .execute_ready_tasks(team.execution.id)
+        .await
+        .unwrap();
+    // The upstream's worktree was never set up, so its branch doesn't exist
+    manager.complete_task(upstream.id).await.unwrap();
+
+    let dependent_workspace = team.started_workspace(&dependent).await;
+    assert_eq!(team.target_branch(&dependent_workspace).await, "main");
+}
+
+#[tokio::test]
+async fn dependent_subtask_builds_on_the_base_once_its_upstream_merged() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let team = team(&repo_path, 1).await;
+    let manager = TeamManager::new(team.pool.clone());
+    let upstream = team.subtask(0, &[]).await;
+    let dependent = team.subtask(1, &[upstream.id]).await;
+
+    manager
+        .execute_ready_tasks(team.execution.id)
+        .await
+        .unwrap();
+    let upstream_workspace = team.started_workspace(&upstream).await;
+    commit_on_new_branch(
+        &repo_path,
+        "main",
+        &upstream_workspace.branch,
+        "upstream.txt",
+        "upstream\n",
+    );
+    GitCli::new()
+        .git(&repo_path, ["merge", "--no-ff", &upstream_workspace.branch])
+        .unwrap();
+    Merge::create_direct(
+        &team.pool,
+        upstream_workspace.id,
+        team.repo.id,
+        "main",
+        &head_of(&repo_path, "main"),
+    )
+    .await
+    .unwrap();
+    manager.complete_task(upstream.id).await.unwrap();
+
+    let dependent_workspace = team.started_workspace(&dependent).await;
+    assert_eq!(team.target_branch(&dependent_workspace).await, "main");
+}
+
+#[tokio::test]
+async fn merged_upstream_restacks_the_whole_stack() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    commit_on_new_branch(&repo_path, "main", "team/a", "a.txt", "a\n");
+    commit_on_new_branch(&repo_path, "team/a", "team/b", "b.txt", "b\n");
+    commit_on_new_branch(&repo_path, "team/b", "team/c", "c.txt", "c\n");
+
+    let team = team(&repo_path, 3).await;
+    let a = team.subtask(0, &[]).await;
+    let b = team.subtask(1, &[a.id]).await;
+    let c = team.subtask(2, &[b.id]).await;
+    let a_workspace = team.workspace(&a, "team/a", "main", None).await;
+    let b_container = td.path().join("b");
+    let b_workspace = team
+        .workspace(&b, "team/b", "team/a", Some(&b_container))
+        .await;
+    let c_container = td.path().join("c");
+    let c_workspace = team
+        .workspace(&c, "team/c", "team/b", Some(&c_container))
+        .await;
+
+    // Squash merge team/a into main
+    write_file(&repo_path, "a.txt", "a\n");
+    commit_all(&repo_path, "Squash merge team/a");
+
+    let manager = TeamManager::new(team.pool.clone());
+    let restacked = manager
+        .restack_after_merge(&a_workspace, &team.repo, "main")
+        .await
+        .unwrap();
+
+    let summary: Vec<_> = restacked
+        .iter()
+        .map(|r| {
+            (
+                r.workspace_id,
+                r.base_branch.as_str(),
+                r.rebase_error.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (b_workspace.id, "main", None),
+            (c_workspace.id, "team/b", None),
+        ]
+    );
+    assert_eq!(team.target_branch(&b_workspace).await, "main");
+    assert_eq!(team.target_branch(&c_workspace).await, "team/b");
+
+    // Each branch keeps only its own commit, on top of the branch below it
+    let b_head = head_of(&repo_path, "team/b");
+    let c_head = head_of(&repo_path, "team/c");
+    assert_eq!(parent_of(&repo_path, &b_head), head_of(&repo_path, "main"));
+    assert_eq!(parent_of(&repo_path, &c_head), b_head);
+    let c_worktree = c_container.join(&team.repo.name);
+    for file in ["a.txt", "b.txt", "c.txt"] {
+        assert!(c_worktree.join(file).exists(), "{file} missing");
+    }
+}
+
+#[tokio::test]
+async fn conflicting_restack_is_aborted_and_reported() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    commit_on_new_branch(&repo_path, "main", "team/a", "a.txt", "a\n");
+    commit_on_new_branch(&repo_path, "team/a", "team/b", "shared.txt", "from b\n");
+
+    let team = team(&repo_path, 3).await;
+    let a = team.subtask(0, &[]).await;
+    let b = team.subtask(1, &[a.id]).await;
+    let a_workspace = team.workspace(&a, "team/a", "main", None).await;
+    let b_container = td.path().join("b");
+    let b_workspace = team
+        .workspace(&b, "team/b", "team/a", Some(&b_container))
+        .await;
+    let b_head = head_of(&repo_path, "team/b");
+
+    // team/a merges while main has changed the file team/b touches
+    write_file(&repo_path, "shared.txt", "from main\n");
+    commit_all(&repo_path, "Change shared.txt on main");
+    write_file(&repo_path, "a.txt", "a\n");
+    commit_all(&repo_path, "Squash merge team/a");
+
+    let manager = TeamManager::new(team.pool.clone());
+    let restacked = manager
+        .restack_after_merge(&a_workspace, &team.repo, "main")
+        .await
+        .unwrap();
+
+    assert_eq!(restacked.len(), 1);
+    assert_eq!(restacked[0].workspace_id, b_workspace.id);
+    assert_eq!(restacked[0].base_branch, "main");
+    let error = restacked[0].rebase_error.as_deref().unwrap();
+    assert!(error.contains("conflicts"), "{error}");
+    // The target moves on regardless, but the worktree is left as it was
+    assert_eq!(team.target_branch(&b_workspace).await, "main");
+    assert_eq!(head_of(&repo_path, "team/b"), b_head);
+    let b_worktree = b_container.join(&team.repo.name);
+    assert!(!GitCli::new().is_rebase_in_progress(&b_worktree).unwrap());
+    assert_eq!(
+        fs::read_to_string(b_worktree.join("shared.txt")).unwrap(),
+        "from b\n"
+    );
+}
@@ -3,8 +3,14 @@ use rust_embed::RustEmbed;
 
 const PROJECT_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 
+/// Overrides the asset directory, e.g. to run a throwaway server on an empty
+/// database
+pub const ASSET_DIR_ENV: &str = "VK_ASSET_DIR";
+
 pub fn asset_dir() -> std::path::PathBuf {
-    let path = if cfg!(debug_assertions) {
+    let path = if let Some(dir) = std::env::var_os(ASSET_DIR_ENV) {
+        std::path::PathBuf::from(dir)
+    } else if cfg!(debug_assertions) {
         std::path::PathBuf::from(PROJECT_ROOT).join("../../dev_assets")
     } else {
         ProjectDirs::from("ai", "bloop", "vibe-kanban")
@@ -18,6 +18,45 @@ score: bigint, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
+export type ProjectBudget = { project_id: string, monthly_cap_cents: bigint, 
+/**
+ * Month (YYYY-MM) for which the cap has been lifted
+ */
+override_month: string | null, created_at: string, updated_at: string, };
+
+export type SetProjectBudget = { 
+/**
+ * New monthly cap in cents; `None` removes the budget entirely
+ */
+monthly_cap_cents: bigint | null, };
+
+export type ProjectBranchPolicy = { project_id: string, 
+/**
+ * Branches must start with one of these; empty allows any prefix
+ */
+allowed_prefixes: Array<string>, 
+/**
+ * Maximum length of a branch name in characters
+ */
+max_length: bigint | null, 
+/**
+ * Characters that may not appear anywhere in a branch name
+ */
+forbidden_chars: string, created_at: string, updated_at: string, };
+
+export type SetProjectBranchPolicy = { allowed_prefixes: Array<string>, max_length: bigint | null, forbidden_chars: string, };
+
+export type ProtectionMode = "confirm" | "block";
+
+export type ProtectedBranch = { id: string, repo_id: string, 
+/**
+ * Branch name, or a glob where `*` matches within one path segment and `**`
+ * across segments, e.g. `release/*`
+ */
+pattern: string, mode: ProtectionMode, created_at: string, };
+
+export type CreateProtectedBranch = { pattern: string, mode: ProtectionMode, };
+
 export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, created_at: Date, updated_at: Date, };
 
 export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, };
@@ -42,9 +81,17 @@ export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelle
 
 export type TaskComplexity = "trivial" | "simple" | "moderate" | "complex" | "epic";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, is_epic: boolean, complexity: TaskComplexity | null, metadata: string | null, created_at: string, updated_at: string, };
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, is_epic: boolean, complexity: TaskComplexity | null, metadata: string | null, 
+/**
+ * Place in its board column when the column is ordered by hand
+ */
+board_position: bigint | null, created_at: string, updated_at: string, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, is_epic: boolean, complexity: TaskComplexity | null, metadata: string | null, created_at: string, updated_at: string, };
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, is_epic: boolean, complexity: TaskComplexity | null, metadata: string | null, 
+/**
+ * Place in its board column when the column is ordered by hand
+ */
+board_position: bigint | null, created_at: string, updated_at: string, };
 
 export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
 
@@ -52,8 +99,19 @@ export type CreateTask = { project_id: string, title: string, description: strin
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, is_epic: boolean | null, complexity: TaskComplexity | null, metadata: string | null, };
 
+/**
+ * The tasks of a board column in the order they were arranged in by hand
+ */
+export type ReorderTasks = { project_id: string, task_ids: Array<string>, };
+
 export type ProjectTaskStats = { project_id: string, todo_count: bigint, inprogress_count: bigint, inreview_count: bigint, done_count: bigint, cancelled_count: bigint, running_count: bigint, };
 
+/**
+ * A task in the trash: off the board, but kept with its workspaces until it is
+ * restored or deleted for good
+ */
+export type TrashedTask = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, deleted_at: string, created_at: string, updated_at: string, };
+
 export type AgentSkill = { id: string, name: string, description: string, prompt_modifier: string | null, category: string, icon: string | null, created_at: string, updated_at: string, };
 
 export type CreateAgentSkill = { name: string, description: string, prompt_modifier: string | null, category: string | null, icon: string | null, };
@@ -82,7 +140,33 @@ export type UpdateTeamExecution = { status: TeamExecutionStatus | null, planner_
 
 export type TeamPlanOutput = { complexity: string, requires_team: boolean, subtasks: Array<PlannedSubtask>, estimated_total_duration: number | null, reasoning: string, };
 
-export type PlannedSubtask = { title: string, description: string, required_skills: Array<string>, depends_on: Array<number>, complexity: number, estimated_duration: number | null, };
+export type PlannedSubtask = { title: string, description: string, required_skills: Array<string>, depends_on: Array<number>, complexity: number, estimated_duration: number | null, 
+/**
+ * Repositories the subtask works in; empty means all of the project's repositories
+ */
+repo_scopes: Array<RepoScope>, };
+
+export type RepoScope = { 
+/**
+ * Repository name, as listed for the project
+ */
+repo_name: string, 
+/**
+ * What to change in this repository
+ */
+hint: string, };
+
+export type PlanTemplate = { id: string, name: string, 
+/**
+ * Team execution the plan was saved from, if it still exists
+ */
+source_team_execution_id: string | null, plan: TeamPlanOutput, created_at: string, };
+
+export type CreatePlanTemplate = { team_execution_id: string, 
+/**
+ * Defaults to the epic title
+ */
+name: string | null, };
 
 export type TeamTaskStatus = "pending" | "blocked" | "assigned" | "running" | "completed" | "failed" | "skipped";
 
@@ -94,6 +178,51 @@ export type TeamTaskWithDetails = { task_title: string, task_description: string
 
 export type TeamProgress = { total: number, completed: number, running: number, failed: number, pending: number, skipped: number, };
 
+/**
+ * Configuration for the planner service, stored in the server config
+ */
+export type PlannerConfig = { 
+/**
+ * Minimum subtasks to trigger team execution
+ */
+team_threshold: number, 
+/**
+ * Maximum subtasks per epic
+ */
+max_subtasks: number, 
+/**
+ * Maximum parallel workers
+ */
+max_parallel_workers: number, };
+
+export type QueuedTeamTask = { team_task_id: string, team_execution_id: string, task_id: string, 
+/**
+ * Workspace from a previous run, set when the task was requeued for a retry
+ */
+workspace_id: string | null, task_title: string, 
+/**
+ * 1-based position within the team execution's queue
+ */
+position: number, running: number, max_parallel_workers: number, estimated_start_at: string, };
+
+export type RepoSkillProfile = { languages: Array<string>, frameworks: Array<string>, 
+/**
+ * Skill names the repositories call for
+ */
+skills: Array<string>, };
+
+export type ProfileSkillSuggestion = { profile: AgentProfile, missing_skills: Array<AgentSkill>, };
+
+export type ProjectSkillSuggestions = { detected: RepoSkillProfile, 
+/**
+ * Existing skills matching what was detected
+ */
+skills: Array<AgentSkill>, 
+/**
+ * Worker profiles missing some of those skills
+ */
+profiles: Array<ProfileSkillSuggestion>, };
+
 export type DraftFollowUpData = { message: string, executor_profile_id: ExecutorProfileId, };
 
 export type DraftWorkspaceData = { message: string, project_id: string | null, repos: Array<DraftWorkspaceRepo>, selected_profile: ExecutorProfileId | null, };
@@ -122,8 +251,159 @@ export type Workspace = { id: string, task_id: string, container_ref: string | n
 
 export type WorkspaceWithStatus = { is_running: boolean, is_errored: boolean, id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, archived: boolean, pinned: boolean, name: string | null, };
 
+export type WorkspaceActivity = { workspace_id: string, task_id: string, 
+/**
+ * When a process in the workspace last wrote output
+ */
+last_output_at: string | null, 
+/**
+ * When a process in the workspace last started or changed status
+ */
+last_status_change_at: string | null, is_running: boolean, };
+
+export type ResourceLimits = { 
+/**
+ * Wall-clock runtime of a single coding agent run, in seconds
+ */
+max_runtime_secs: bigint | null, 
+/**
+ * CPU time used by the agent's process group, in seconds
+ */
+max_cpu_secs: bigint | null, 
+/**
+ * Resident memory of the agent's process group, in MiB
+ */
+max_memory_mb: bigint | null, };
+
+export type ResourceLimitKind = "runtime" | "cpu" | "memory";
+
+export type WorkspaceResourceLimits = { workspace_id: string, max_runtime_secs: bigint | null, max_cpu_secs: bigint | null, max_memory_mb: bigint | null, 
+/**
+ * Limit that stopped the most recent coding agent run, if any
+ */
+exceeded_limit: ResourceLimitKind | null, exceeded_at: string | null, created_at: string, };
+
+export type AutoRebaseTrigger = "nightly" | "target_moved";
+
+export type WorkspaceAutoRebase = { workspace_id: string, trigger: AutoRebaseTrigger, 
+/**
+ * Why rebasing stopped, e.g. the conflicts it ran into; set until resumed
+ */
+paused_reason: string | null, paused_at: string | null, 
+/**
+ * Failure of the most recent check that did not pause the policy
+ */
+last_error: string | null, last_checked_at: string | null, last_rebased_at: string | null, created_at: string, updated_at: string, };
+
+export type SetWorkspaceAutoRebase = { trigger: AutoRebaseTrigger, };
+
+export type WorkspaceNote = { workspace_id: string, body: string, created_at: string, updated_at: string, };
+
+export type SetWorkspaceNote = { 
+/**
+ * Replaces the notes; blank notes are removed
+ */
+body: string, };
+
+/**
+ * The final message of the latest coding agent run that completed in a
+ * workspace, kept as what the attempt did
+ */
+export type WorkspaceResult = { workspace_id: string, task_id: string, execution_process_id: string | null, summary: string, created_at: string, };
+
+/**
+ * Why an attempt failed
+ */
+export type FailureCategory = "setup_script" | "agent_crash" | "rate_limit" | "timeout" | "merge_conflict";
+
+/**
+ * The current failure of a workspace, with the task it belongs to
+ */
+export type WorkspaceFailure = { id: string, workspace_id: string, task_id: string, project_id: string, task_title: string, branch: string, execution_process_id: string | null, category: FailureCategory, 
+/**
+ * The last line of output, or what went wrong, if known
+ */
+detail: string | null, created_at: string, };
+
+/**
+ * What a retention policy does to a workspace
+ */
+export type RetentionAction = "archive" | "delete_worktree";
+
+/**
+ * A workspace a retention policy applies to
+ */
+export type RetentionCandidate = { workspace_id: string, task_id: string, project_name: string, task_title: string, branch: string, action: RetentionAction, 
+/**
+ * When the last repository was merged, or when the cancelled task was last
+ * updated
+ */
+since: string, };
+
+/**
+ * Server-wide retention policy for workspaces, stored in the server config
+ */
+export type RetentionPolicy = { 
+/**
+ * Archive workspaces this many days after their last repository was merged
+ */
+archive_merged_after_days: number | null, 
+/**
+ * Remove the worktrees of cancelled tasks once nothing runs in them
+ */
+delete_cancelled_worktrees: boolean, 
+/**
+ * Delete tasks for good this many days after they went into the trash
+ */
+purge_trash_after_days: number | null, };
+
+/**
+ * CLI settings that follow the user across machines, stored in the server config
+ */
+export type UserPreferences = { 
+/**
+ * Name of the CLI theme, e.g. "solarized"
+ */
+theme: string | null, 
+/**
+ * Executor preselected for new attempts, e.g. "claude-code"
+ */
+default_executor: string | null, 
+/**
+ * Board search queries by name, used as `@name` in a search
+ */
+saved_filters: { [key in string]?: string }, 
+/**
+ * Keys bound to CLI actions, by action name, e.g. `{ "quit": ["ctrl+q"] }`
+ */
+keybindings: { [key in string]?: Array<string> }, };
+
 export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
 
+export type SessionProgress = { session_id: string, workspace_id: string, 
+/**
+ * Paths the agent has changed so far, relative to the workspace
+ */
+files_touched: Array<string>, 
+/**
+ * Plan steps the agent has finished
+ */
+steps_completed: number | null, 
+/**
+ * Plan steps the agent expects to take in total
+ */
+steps_total: number | null, 
+/**
+ * What the agent is doing right now
+ */
+message: string | null, 
+/**
+ * Time of the latest report, used as the agent's heartbeat
+ */
+updated_at: string, };
+
+export type ReportSessionProgress = { files_touched: Array<string>, steps_completed: number | null, steps_total: number | null, message: string | null, };
+
 export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
 /**
  * dropped: true if this process is excluded from the current
@@ -138,6 +418,10 @@ export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codin
 
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
+export type OrphanReason = "server_restart" | "process_gone" | "container_missing";
+
+export type ExecutionProcessOrphan = { execution_process_id: string, workspace_id: string, task_id: string, reason: OrphanReason, detected_at: string, };
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
 export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
@@ -228,6 +512,12 @@ export type CreateRemoteProjectRequest = { organization_id: string, name: string
 
 export type LinkToExistingRequest = { remote_project_id: string, };
 
+export type BudgetOverrideRequest = { 
+/**
+ * Lift the cap for the rest of the current month, or restore it
+ */
+enabled: boolean, };
+
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
@@ -256,6 +546,16 @@ export type CheckEditorAvailabilityResponse = { available: boolean, };
 
 export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
 
+export type ExecutorHealth = { executor: BaseCodingAgent, availability: AvailabilityInfo, 
+/**
+ * When a recent run for this executor last failed on a rate limit
+ */
+rate_limited_at: string | null, available: boolean, 
+/**
+ * Explanation shown next to the executor when it is unavailable or degraded
+ */
+message: string | null, };
+
 export type CurrentUserResponse = { user_id: string, };
 
 export type CreateFollowUpAttempt = { prompt: string, executor_profile_id: ExecutorProfileId, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
@@ -264,9 +564,17 @@ export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: st
 
 export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
 
-export type MergeTaskAttemptRequest = { repo_id: string, };
+export type MergeTaskAttemptRequest = { repo_id: string, 
+/**
+ * Go ahead even though the target branch is protected in confirm mode
+ */
+confirm_protected?: boolean, };
 
-export type PushTaskAttemptRequest = { repo_id: string, };
+export type PushTaskAttemptRequest = { repo_id: string, 
+/**
+ * Go ahead even though the workspace branch is protected in confirm mode
+ */
+confirm_protected?: boolean, };
 
 export type RenameBranchRequest = { new_branch_name: string, };
 
@@ -280,7 +588,11 @@ export type OpenEditorRequest = { editor_type: string | null, file_path: string
 
 export type OpenEditorResponse = { url: string | null, };
 
-export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Branch to create for the attempt; generated from the task title when omitted
+ */
+branch?: string, };
 
 export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
 
@@ -288,7 +600,25 @@ export type ImageResponse = { id: string, file_path: string, original_name: stri
 
 export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
 
-export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Limits enforced on the coding agent; omitted means unlimited
+ */
+resource_limits?: ResourceLimits, 
+/**
+ * Branch to create for the attempt; generated from the task title when omitted
+ */
+branch?: string, };
+
+export type CreateTaskAttemptError = { "type": "branch_policy_violation" } & BranchPolicyReport;
+
+export type ContainerState = "running" | "stopped" | "missing";
+
+export type WorkspaceContainerStatus = { workspace_id: string, state: ContainerState, container_ref: string | null, 
+/**
+ * Repositories whose worktree is missing from an existing workspace directory
+ */
+missing_repos: Array<string>, };
 
 export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
 
@@ -298,13 +628,17 @@ export type RunAgentSetupResponse = Record<string, never>;
 
 export type GhCliSetupError = "BREW_MISSING" | "SETUP_HELPER_NOT_SUPPORTED" | { "OTHER": { message: string, } };
 
-export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, };
+export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, 
+/**
+ * Go ahead even though the workspace branch is protected in confirm mode
+ */
+confirm_protected?: boolean, };
 
 export type AbortConflictsRequest = { repo_id: string, };
 
-export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
+export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" } | { "type": "protected_branch" } & ProtectedBranchViolation;
 
-export type PushError = { "type": "force_push_required" };
+export type PushError = { "type": "force_push_required" } | { "type": "protected_branch" } & ProtectedBranchViolation;
 
 export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "unsupported_provider" };
 
@@ -320,7 +654,15 @@ conflict_op: ConflictOp | null,
 /**
  * List of files currently in conflicted (unmerged) state
  */
-conflicted_files: Array<string>, };
+conflicted_files: Array<string>, 
+/**
+ * How the target branch is protected, if it is
+ */
+target_branch_protection: ProtectionMode | null, 
+/**
+ * How the workspace branch is protected, if it is
+ */
+branch_protection: ProtectionMode | null, };
 
 export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
 
@@ -338,6 +680,26 @@ export type UnifiedPrComment = { "comment_type": "general", id: string, author:
 
 export type ProviderKind = "git_hub" | "azure_dev_ops" | "unknown";
 
+export type BudgetState = "unlimited" | "ok" | "warning" | "exceeded" | "overridden";
+
+export type ExecutorSpend = { executor: string, runtime_seconds: bigint, estimated_cost_cents: bigint, };
+
+export type ProjectBudgetStatus = { project_id: string, 
+/**
+ * Month the figures apply to (YYYY-MM)
+ */
+month: string, monthly_cap_cents: bigint | null, spent_cents: bigint, percent_used: number | null, state: BudgetState, by_executor: Array<ExecutorSpend>, };
+
+export type BranchPolicyViolation = { "type": "missing_prefix", allowed_prefixes: Array<string>, } | { "type": "too_long", length: number, max_length: number, } | { "type": "forbidden_characters", characters: string, };
+
+export type BranchPolicyReport = { branch: string, violations: Array<BranchPolicyViolation>, suggested_branch: string, };
+
+export type ProtectedBranchViolation = { branch: string, 
+/**
+ * Pattern of the protection that applies
+ */
+pattern: string, mode: ProtectionMode, };
+
 export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
  * True if a `git rebase` is currently in progress in this worktree
@@ -350,7 +712,21 @@ conflict_op: ConflictOp | null,
 /**
  * List of files currently in conflicted (unmerged) state
  */
-conflicted_files: Array<string>, };
+conflicted_files: Array<string>, 
+/**
+ * How the target branch is protected, if it is
+ */
+target_branch_protection: ProtectionMode | null, 
+/**
+ * How the workspace branch is protected, if it is
+ */
+branch_protection: ProtectionMode | null, };
+
+export type RepoCommits = { repo_id: string, repo_name: string, target_branch_name: string, 
+/**
+ * Commits of the workspace branch that the target branch doesn't have, newest first
+ */
+commits: Array<BranchCommit>, };
 
 export type UpdateWorkspace = { archived: boolean | null, pinned: boolean | null, name: string | null, };
 
@@ -408,7 +784,19 @@ export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_pa
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, 
+/**
+ * Limits the team planner applies when decomposing epics
+ */
+planner: PlannerConfig, 
+/**
+ * When finished workspaces are archived or have their worktrees removed
+ */
+retention: RetentionPolicy, 
+/**
+ * Settings of the command-line client that follow the user across machines
+ */
+user_preferences: UserPreferences, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -450,6 +838,34 @@ export type QueueStatus = { "status": "empty" } | { "status": "queued", message:
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
+export type PreviewCommit = { sha: string, subject: string, };
+
+export type GitOperationPreview = { 
+/**
+ * Commits the operation would merge, replay or push, newest first
+ */
+commits: Array<PreviewCommit>, 
+/**
+ * Whether the destination only has to move forward: nothing to catch up on
+ * before a merge, nothing to replay in a rebase, no remote commits lost in a push
+ */
+fast_forward: boolean, 
+/**
+ * Commits on the other side: new target commits for a merge, base commits picked
+ * up by a rebase, remote commits a push would overwrite
+ */
+behind: number, 
+/**
+ * Files that would end up in conflict
+ */
+conflicted_files: Array<string>, 
+/**
+ * Why the operation itself would be refused, if it would be
+ */
+blocker: string | null, };
+
+export type BranchCommit = { sha: string, subject: string, author_name: string, author_email: string | null, authored_at: Date, };
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };